@@ -0,0 +1,34 @@
+//! Conversion between token amounts and their decimal display
+
+use anyhow::{bail, Result};
+
+/// Parse a decimal amount such as `12.5` into base units of a mint with `decimals`
+pub fn parse(amount: &str, decimals: u8) -> Result<u64> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        bail!("empty amount");
+    }
+    if fraction.len() > decimals as usize {
+        bail!("{} has more than {} decimals", amount, decimals);
+    }
+    let digits = format!("{}{:0<width$}", whole, fraction, width = decimals as usize);
+    match digits.parse::<u64>() {
+        Ok(units) => Ok(units),
+        Err(_) => bail!("invalid amount: {}", amount),
+    }
+}
+
+/// Format base units of a mint with `decimals` as a decimal amount
+pub fn format(units: u64, decimals: u8) -> String {
+    if decimals == 0 {
+        return units.to_string();
+    }
+    let scale = 10u64.pow(decimals as u32);
+    let fraction = format!("{:0width$}", units % scale, width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        (units / scale).to_string()
+    } else {
+        format!("{}.{}", units / scale, fraction)
+    }
+}
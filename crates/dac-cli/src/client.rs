@@ -0,0 +1,93 @@
+//! RPC access and transaction sending
+
+use anchor_lang::AccountDeserialize;
+use anyhow::{anyhow, Context, Result};
+use dac_sdk::offline::{self, Lifetime};
+use solana_client::rpc_client::RpcClient;
+use solana_rpc_client_nonce_utils as nonce_utils;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+/// A durable nonce account advanced by the transaction in place of a recent blockhash
+///
+/// Transactions on a nonce don't expire, so admin flows can be signed on a
+/// slow hardware wallet or collected from several signers before sending.
+pub struct DurableNonce {
+    pub account: Pubkey,
+    /// Signer authorized to advance the nonce, if not the payer
+    pub authority: Option<Box<dyn Signer>>,
+}
+
+pub struct Client {
+    pub rpc: RpcClient,
+    /// Fee payer and signer of every transaction
+    pub payer: Box<dyn Signer>,
+}
+
+impl Client {
+    pub fn new(url: String, payer: Box<dyn Signer>) -> Self {
+        Self {
+            rpc: RpcClient::new(url),
+            payer,
+        }
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Fetch and decode an account
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        let data = self
+            .rpc
+            .get_account_data(address)
+            .with_context(|| format!("failed to fetch account {}", address))?;
+        dac_sdk::accounts::deserialize(&data)
+            .map_err(|e| anyhow!("failed to decode account {}: {}", address, e))
+    }
+
+    /// Fetch and decode an account that may not exist yet
+    pub fn maybe_account<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<Option<T>> {
+        let account = self.rpc.get_account_with_commitment(address, self.rpc.commitment())?;
+        match account.value {
+            Some(account) => dac_sdk::accounts::deserialize(&account.data)
+                .map(Some)
+                .map_err(|e| anyhow!("failed to decode account {}: {}", address, e)),
+            None => Ok(None),
+        }
+    }
+
+    /// Sign `instructions` as one transaction, send it and wait for confirmation
+    pub fn send(&self, instructions: &[Instruction], nonce: Option<&DurableNonce>) -> Result<Signature> {
+        let payer = self.payer();
+        let mut signers: Vec<&dyn Signer> = vec![self.payer.as_ref()];
+        let lifetime = match nonce {
+            Some(nonce) => {
+                let account =
+                    nonce_utils::get_account_with_commitment(&self.rpc, &nonce.account, self.rpc.commitment())?;
+                let data = nonce_utils::data_from_account(&account)?;
+                let authority = match &nonce.authority {
+                    Some(authority) => {
+                        signers.push(authority.as_ref());
+                        authority.pubkey()
+                    }
+                    None => payer,
+                };
+                Lifetime::Nonce {
+                    account: nonce.account,
+                    authority,
+                    nonce: data.blockhash(),
+                }
+            }
+            None => Lifetime::Blockhash(self.rpc.get_latest_blockhash()?),
+        };
+
+        let mut transaction = offline::build(instructions, &payer, &lifetime);
+        transaction.try_sign(&signers, transaction.message.recent_blockhash)?;
+        self.rpc
+            .send_and_confirm_transaction_with_spinner(&transaction)
+            .context("transaction failed")
+    }
+}
@@ -0,0 +1,107 @@
+//! Human-readable printing of program state
+
+use dac_sdk::accounts::{
+    AttestationRequirement, DacConfig, Market, MarketConfig, Mint, Outcome, ResolutionProposal, VaultShard,
+};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::amount;
+
+pub fn dac_config(address: &Pubkey, config: &DacConfig, usdc: &Mint) {
+    println!("DAC token config {}", address);
+    println!("  Authority:       {}", config.authority);
+    println!("  DAC mint:        {}", config.dac_mint);
+    println!("  USDC mint:       {}", config.usdc_mint);
+    println!("  Vault shards:    {}", config.shard_count);
+    println!("  Shard float:     {} DAC", amount::format(config.shard_float, usdc.decimals));
+    println!("  Total wrapped:   {} USDC", amount::format(config.total_wrapped, usdc.decimals));
+    println!("  Wrap fee:        {} bps", config.fee_bps);
+    println!("  Fee vault:       {}", config.fee_vault);
+    if config.bundle_wraps {
+        println!("  Wrap bundling:   required with {}", config.market_program);
+    } else {
+        println!("  Wrap bundling:   off");
+    }
+    attestation(&config.wrap_attestation);
+    if config.compliance_threshold > 0 {
+        let threshold = amount::format(config.compliance_threshold, usdc.decimals);
+        println!("  Travel rule:     from {} USDC", threshold);
+    } else {
+        println!("  Travel rule:     off");
+    }
+}
+
+pub fn market_config(address: &Pubkey, config: &MarketConfig) {
+    println!("Dark market config {}", address);
+    println!("  Authority:       {}", config.authority);
+    println!("  Guardian:        {}", config.guardian);
+    println!("  DAC mint:        {}", config.dac_mint);
+    println!("  Markets created: {}", config.market_count);
+}
+
+pub fn market(address: &Pubkey, market: &Market, dac: &Mint, proposal: Option<&ResolutionProposal>) {
+    println!("Market #{} {}", market.id, address);
+    println!("  Question:        {}", market.question());
+    println!("  Creator:         {}", market.creator);
+    println!("  Resolver:        {}", market.resolver);
+    println!("  Ends:            {}", market.end_time);
+    println!("  Status:          {:?}", market.status());
+    if let Some(outcome) = market.outcome() {
+        println!("  Outcome:         {}", outcome_name(outcome));
+    }
+    println!("  Collateral:      {} DAC", amount::format(market.total_collateral, dac.decimals));
+    println!("  YES mint:        {}", market.yes_mint);
+    println!("  NO mint:         {}", market.no_mint);
+    println!("  Escrow:          {}", market.escrow);
+    attestation(&market.attestation());
+    if let Some(proposal) = proposal {
+        println!("  Proposed:        {} by {}", outcome_name(proposal.outcome), proposal.proposer);
+        println!("  Bond:            {} DAC", amount::format(proposal.bond, dac.decimals));
+        println!("  Challenge ends:  {}", proposal.challenge_ends());
+        println!("  Proposal status: {:?}", proposal.status);
+    }
+}
+
+/// The USDC backing of DAC, from each vault shard with its USDC vault and DAC float balances
+pub fn vault(config: &DacConfig, shards: &[(VaultShard, u64, u64)], usdc: &Mint, dac: &Mint) {
+    let mut backing = 0u64;
+    let mut unissued = 0u64;
+    println!("Vault shards ({})", shards.len());
+    for (shard, vault, float) in shards {
+        println!(
+            "  {:<17}{} USDC, {} DAC float",
+            format!("Shard {}:", shard.index),
+            amount::format(*vault, usdc.decimals),
+            amount::format(*float, dac.decimals)
+        );
+        backing = backing.saturating_add(*vault);
+        unissued = unissued.saturating_add(float.saturating_sub(shard.fees));
+    }
+    let issued = dac.supply.saturating_sub(unissued);
+    println!("  Balance:         {} USDC", amount::format(backing, usdc.decimals));
+    println!("  DAC supply:      {} DAC", amount::format(dac.supply, dac.decimals));
+    println!("  DAC issued:      {} DAC", amount::format(issued, dac.decimals));
+    println!("  Last synced:     {} USDC wrapped", amount::format(config.total_wrapped, usdc.decimals));
+    if issued > 0 {
+        let ratio = backing as f64 / 10f64.powi(usdc.decimals as i32)
+            / (issued as f64 / 10f64.powi(dac.decimals as i32));
+        println!("  Backing:         {:.4} USDC per DAC", ratio);
+    }
+}
+
+/// The attestation a wrap or a market requires, if any
+fn attestation(requirement: &AttestationRequirement) {
+    if requirement.is_required() {
+        println!("  Attestation:     schema {} from {}", requirement.schema, requirement.credential);
+    } else {
+        println!("  Attestation:     none");
+    }
+}
+
+fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Yes => "YES",
+        Outcome::No => "NO",
+        Outcome::Invalid => "INVALID",
+    }
+}
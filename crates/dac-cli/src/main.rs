@@ -0,0 +1,362 @@
+//! DAC command-line tool
+//!
+//! Wraps and unwraps DAC, creates markets, proposes resolutions and runs the
+//! admin flows of the DAC token and dark market programs. The RPC endpoint
+//! and signer default to the Solana CLI config.
+//!
+//! Signers are keypair files or hardware wallets (`usb://ledger`). Admin
+//! commands take `--nonce` to use a durable nonce instead of a recent
+//! blockhash, for signers that take longer than a blockhash lives.
+
+mod amount;
+mod client;
+mod display;
+
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+use clap::{ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use dac_sdk::accounts::{
+    DacConfig, MarketConfig, Mint, Outcome, ProtocolLookupTable, ResolutionProposal, TokenAccount, VaultShard,
+};
+use dac_sdk::{flows, instructions, lookup_tables, pda};
+use solana_clap_v3_utils::keypair::signer_from_path;
+use solana_remote_wallet::remote_wallet::RemoteWalletManager;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+use crate::client::{Client, DurableNonce};
+
+#[derive(Parser)]
+#[clap(name = "dac-cli", version, about = "DAC token and dark market command-line tool")]
+struct Cli {
+    /// RPC endpoint (defaults to the Solana CLI config)
+    #[clap(long, short = 'u', global = true)]
+    url: Option<String>,
+
+    /// Signer paying for and signing transactions: a keypair file or
+    /// `usb://ledger` (defaults to the Solana CLI config)
+    #[clap(long, short = 'k', global = true)]
+    keypair: Option<String>,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct NonceArgs {
+    /// Durable nonce account to use instead of a recent blockhash
+    #[clap(long)]
+    nonce: Option<Pubkey>,
+
+    /// Signer authorized to advance the nonce (defaults to the payer)
+    #[clap(long, requires = "nonce")]
+    nonce_authority: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Initialize the DAC token config and the dark market config
+    ///
+    /// The DAC mint's mint authority must already be the mint authority PDA.
+    Initialize {
+        #[clap(long)]
+        dac_mint: Pubkey,
+        #[clap(long)]
+        usdc_mint: Pubkey,
+        #[clap(flatten)]
+        nonce: NonceArgs,
+    },
+    /// Wrap USDC into DAC
+    Wrap {
+        /// USDC to wrap, e.g. 12.5
+        amount: String,
+    },
+    /// Unwrap DAC into USDC
+    Unwrap {
+        /// DAC to unwrap, e.g. 12.5
+        amount: String,
+    },
+    /// Update the protocol configuration (config authority only)
+    Config {
+        #[clap(subcommand)]
+        update: ConfigUpdate,
+        #[clap(flatten)]
+        nonce: NonceArgs,
+    },
+    /// Create a market
+    CreateMarket {
+        question: String,
+        /// Unix time trading ends
+        #[clap(long)]
+        end_time: i64,
+        /// Resolver of the market (defaults to the signer)
+        #[clap(long, conflicts_with = "optimistic")]
+        resolver: Option<Pubkey>,
+        /// Resolve the market optimistically through bonded proposals
+        #[clap(long)]
+        optimistic: bool,
+    },
+    /// Propose the outcome of an optimistically resolved market with a DAC bond
+    ProposeResolution {
+        market_id: u64,
+        #[clap(value_enum)]
+        outcome: OutcomeArg,
+        /// DAC to post as the bond, e.g. 100
+        bond: String,
+    },
+    /// Print protocol state
+    Show {
+        #[clap(subcommand)]
+        target: ShowTarget,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigUpdate {
+    /// Set the wrap and unwrap fee and the token account receiving it
+    WrapFee { fee_bps: u16, fee_vault: Pubkey },
+    /// Grow a DAC config created by the first deployment to the current layout, opening vault shard 0
+    MigrateConfig {
+        dac_mint: Pubkey,
+        usdc_mint: Pubkey,
+        /// DAC, in base units, to stock the shard's float with
+        float: u64,
+    },
+    /// Set the DAC, in base units, each vault shard's float is restocked to
+    ShardFloat { float: u64 },
+    /// Open the next vault shard; the first one takes over the unsharded USDC vault
+    AddVaultShard,
+    /// Require wraps to share a transaction with a market interaction
+    WrapBundling {
+        /// Lift the requirement instead
+        #[clap(long)]
+        disable: bool,
+    },
+    /// Require wrappers to hold a Solana Attestation Service attestation
+    WrapAttestation {
+        /// Credential (issuer) of the attestations; omit both to lift the requirement
+        credential: Option<Pubkey>,
+        /// Schema the attestations must follow
+        schema: Option<Pubkey>,
+    },
+    /// Hand the dark market config authority to a new key
+    Authority { new_authority: Pubkey },
+    /// Hand the circuit breaker guardian role to a new key
+    Guardian { guardian: Pubkey },
+    /// Add the programs, mints, configs and vault PDAs to a protocol lookup table
+    LookupTable {
+        /// Index of the lookup table
+        #[clap(default_value = "0")]
+        index: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum ShowTarget {
+    /// The DAC token and dark market configs
+    Config,
+    /// A market and its resolution proposal
+    Market { id: u64 },
+    /// The vault shards backing DAC
+    Vault,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutcomeArg {
+    Yes,
+    No,
+    Invalid,
+}
+
+impl From<OutcomeArg> for Outcome {
+    fn from(outcome: OutcomeArg) -> Self {
+        match outcome {
+            OutcomeArg::Yes => Outcome::Yes,
+            OutcomeArg::No => Outcome::No,
+            OutcomeArg::Invalid => Outcome::Invalid,
+        }
+    }
+}
+
+/// Signers are loaded from paths the way the Solana CLI does, sharing one
+/// hardware wallet manager
+struct Signers<'a> {
+    matches: &'a ArgMatches,
+    wallet_manager: Option<Rc<RemoteWalletManager>>,
+}
+
+impl Signers<'_> {
+    fn load(&mut self, path: &str, name: &str) -> Result<Box<dyn Signer>> {
+        signer_from_path(self.matches, path, name, &mut self.wallet_manager)
+            .map_err(|e| anyhow!("failed to load {} from {}: {}", name, path, e))
+    }
+
+    fn nonce(&mut self, args: NonceArgs) -> Result<Option<DurableNonce>> {
+        let Some(account) = args.nonce else {
+            return Ok(None);
+        };
+        let authority = match args.nonce_authority {
+            Some(path) => Some(self.load(&path, "nonce authority")?),
+            None => None,
+        };
+        Ok(Some(DurableNonce { account, authority }))
+    }
+}
+
+fn main() -> Result<()> {
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches)?;
+
+    let config = match solana_cli_config::CONFIG_FILE.as_ref() {
+        Some(path) => solana_cli_config::Config::load(path).unwrap_or_default(),
+        None => solana_cli_config::Config::default(),
+    };
+    let url = cli.url.unwrap_or(config.json_rpc_url);
+    let keypair = cli.keypair.unwrap_or(config.keypair_path);
+
+    let mut signers = Signers { matches: &matches, wallet_manager: None };
+    let client = Client::new(url, signers.load(&keypair, "keypair")?);
+
+    match cli.command {
+        Command::Show { target } => show(&client, target),
+        command => run(&client, &mut signers, command),
+    }
+}
+
+fn run(client: &Client, signers: &mut Signers, command: Command) -> Result<()> {
+    let payer = client.payer();
+    let signature = match command {
+        Command::Initialize { dac_mint, usdc_mint, nonce } => {
+            let nonce = signers.nonce(nonce)?;
+            let ixs = [
+                instructions::initialize_dac(&payer, &dac_mint, &usdc_mint),
+                instructions::initialize_market_config(&payer, &dac_mint),
+            ];
+            client.send(&ixs, nonce.as_ref())?
+        }
+        Command::Wrap { amount } => {
+            let config: DacConfig = client.account(&pda::dac_config().0)?;
+            let usdc: Mint = client.account(&config.usdc_mint)?;
+            let amount = amount::parse(&amount, usdc.decimals)?;
+            client.send(&flows::wrap(&payer, &config, amount), None)?
+        }
+        Command::Unwrap { amount } => {
+            let config: DacConfig = client.account(&pda::dac_config().0)?;
+            let dac: Mint = client.account(&config.dac_mint)?;
+            let amount = amount::parse(&amount, dac.decimals)?;
+            let shards = vault_shards(client, &config)?;
+            client.send(&flows::unwrap(&payer, &config, &shards, amount), None)?
+        }
+        Command::Config { update, nonce } => {
+            let nonce = signers.nonce(nonce)?;
+            let ix = match update {
+                ConfigUpdate::WrapFee { fee_bps, fee_vault } => {
+                    instructions::set_wrap_fee(&payer, fee_bps, fee_vault)
+                }
+                ConfigUpdate::MigrateConfig { dac_mint, usdc_mint, float } => {
+                    instructions::migrate_dac_config(&payer, &dac_mint, &usdc_mint, float)
+                }
+                ConfigUpdate::ShardFloat { float } => instructions::set_shard_float(&payer, float),
+                ConfigUpdate::AddVaultShard => {
+                    let config: DacConfig = client.account(&pda::dac_config().0)?;
+                    let index = config.shard_count;
+                    instructions::add_vault_shard(&payer, &config.dac_mint, &config.usdc_mint, index)
+                }
+                ConfigUpdate::WrapBundling { disable } => {
+                    instructions::set_wrap_bundling(&payer, dac_sdk::DARK_MARKET_PROGRAM_ID, !disable)
+                }
+                ConfigUpdate::WrapAttestation { credential, schema } => instructions::set_wrap_attestation(
+                    &payer,
+                    credential.unwrap_or_default(),
+                    schema.unwrap_or_default(),
+                ),
+                ConfigUpdate::Authority { new_authority } => {
+                    instructions::set_config_authority(&payer, new_authority)
+                }
+                ConfigUpdate::Guardian { guardian } => instructions::set_guardian(&payer, guardian),
+                ConfigUpdate::LookupTable { index } => {
+                    let config: DacConfig = client.account(&pda::dac_config().0)?;
+                    let table: ProtocolLookupTable = client.account(&pda::lookup_table(index).0)?;
+                    let addresses =
+                        lookup_tables::protocol_addresses(&config.dac_mint, &config.usdc_mint, config.shard_count);
+                    instructions::extend_lookup_table(&payer, index, &table.table, addresses)
+                }
+            };
+            client.send(&[ix], nonce.as_ref())?
+        }
+        Command::CreateMarket { question, end_time, resolver, optimistic } => {
+            let config: MarketConfig = client.account(&pda::market_config().0)?;
+            let resolver = if optimistic {
+                pda::market_config().0
+            } else {
+                resolver.unwrap_or(payer)
+            };
+            let (market, ixs) =
+                flows::create_market(&payer, &config.dac_mint, config.market_count, question, end_time, resolver);
+            println!("Market #{}: {}", config.market_count, market);
+            client.send(&ixs, None)?
+        }
+        Command::ProposeResolution { market_id, outcome, bond } => {
+            let config: MarketConfig = client.account(&pda::market_config().0)?;
+            let dac: Mint = client.account(&config.dac_mint)?;
+            let bond = amount::parse(&bond, dac.decimals)?;
+            let proposer_dac = pda::associated_token_address(&payer, &config.dac_mint);
+            let ix = instructions::propose_resolution(
+                &payer,
+                &config.dac_mint,
+                &proposer_dac,
+                &pda::market(market_id).0,
+                outcome.into(),
+                bond,
+            );
+            client.send(&[ix], None)?
+        }
+        Command::Show { .. } => unreachable!("handled by show"),
+    };
+
+    println!("Signature: {}", signature);
+    Ok(())
+}
+
+fn show(client: &Client, target: ShowTarget) -> Result<()> {
+    match target {
+        ShowTarget::Config => {
+            let (address, _) = pda::dac_config();
+            let config: DacConfig = client.account(&address)?;
+            let usdc: Mint = client.account(&config.usdc_mint)?;
+            display::dac_config(&address, &config, &usdc);
+            println!();
+            let (address, _) = pda::market_config();
+            display::market_config(&address, &client.account(&address)?);
+        }
+        ShowTarget::Market { id } => {
+            let config: MarketConfig = client.account(&pda::market_config().0)?;
+            let dac: Mint = client.account(&config.dac_mint)?;
+            let (address, _) = pda::market(id);
+            let market = dac_sdk::accounts::market(&client.rpc.get_account_data(&address)?)
+                .map_err(|e| anyhow!("failed to decode market {}: {}", address, e))?;
+            let proposal: Option<ResolutionProposal> =
+                client.maybe_account(&pda::resolution_proposal(&address).0)?;
+            display::market(&address, &market, &dac, proposal.as_ref());
+        }
+        ShowTarget::Vault => {
+            let config: DacConfig = client.account(&pda::dac_config().0)?;
+            let mut shards = Vec::new();
+            for shard in vault_shards(client, &config)? {
+                let vault: TokenAccount = client.account(&shard.usdc_vault)?;
+                let float: TokenAccount = client.account(&shard.dac_float)?;
+                shards.push((shard, vault.amount, float.amount));
+            }
+            let usdc: Mint = client.account(&config.usdc_mint)?;
+            let dac: Mint = client.account(&config.dac_mint)?;
+            display::vault(&config, &shards, &usdc, &dac);
+        }
+    }
+    Ok(())
+}
+
+/// Every vault shard of `config`, in index order
+fn vault_shards(client: &Client, config: &DacConfig) -> Result<Vec<VaultShard>> {
+    (0..config.shard_count).map(|index| client.account(&pda::vault_shard(index).0)).collect()
+}
@@ -0,0 +1,58 @@
+//! The random actions a fuzz case is made of
+
+use dac_sdk::accounts::Outcome;
+use dac_test_utils::VAULT_SHARDS;
+use proptest::prelude::*;
+
+/// Users trading in each case
+pub const USERS: usize = 3;
+/// Markets open in each case
+pub const MARKETS: usize = 2;
+/// Largest amount of a single action, in base units (10,000 DAC)
+const MAX_AMOUNT: u64 = 10_000_000_000;
+
+/// One user or keeper action; actions the programs reject are part of the fuzzing
+#[derive(Clone, Debug)]
+pub enum Action {
+    Wrap { user: usize, amount: u64 },
+    Unwrap { user: usize, amount: u64 },
+    Split { user: usize, market: usize, amount: u64 },
+    Merge { user: usize, market: usize, amount: u64 },
+    Buy { user: usize, market: usize, outcome: Outcome, amount: u64 },
+    Sell { user: usize, market: usize, outcome: Outcome, amount: u64 },
+    Resolve { market: usize, outcome: Outcome },
+    Redeem { user: usize, market: usize },
+    Restock { shard: u8 },
+    Sync,
+}
+
+fn outcome() -> impl Strategy<Value = Outcome> {
+    prop_oneof![Just(Outcome::Yes), Just(Outcome::No), Just(Outcome::Invalid)]
+}
+
+/// Trades only take `Yes` or `No`
+fn side() -> impl Strategy<Value = Outcome> {
+    prop_oneof![Just(Outcome::Yes), Just(Outcome::No)]
+}
+
+pub fn action() -> impl Strategy<Value = Action> {
+    let user = 0..USERS;
+    let market = 0..MARKETS;
+    let amount = 1..MAX_AMOUNT;
+    prop_oneof![
+        4 => (user.clone(), amount.clone()).prop_map(|(user, amount)| Action::Wrap { user, amount }),
+        2 => (user.clone(), amount.clone()).prop_map(|(user, amount)| Action::Unwrap { user, amount }),
+        3 => (user.clone(), market.clone(), amount.clone())
+            .prop_map(|(user, market, amount)| Action::Split { user, market, amount }),
+        2 => (user.clone(), market.clone(), amount.clone())
+            .prop_map(|(user, market, amount)| Action::Merge { user, market, amount }),
+        4 => (user.clone(), market.clone(), side(), amount.clone())
+            .prop_map(|(user, market, outcome, amount)| Action::Buy { user, market, outcome, amount }),
+        3 => (user.clone(), market.clone(), side(), amount)
+            .prop_map(|(user, market, outcome, amount)| Action::Sell { user, market, outcome, amount }),
+        1 => (market.clone(), outcome()).prop_map(|(market, outcome)| Action::Resolve { market, outcome }),
+        2 => (user, market).prop_map(|(user, market)| Action::Redeem { user, market }),
+        1 => (0..VAULT_SHARDS).prop_map(|shard| Action::Restock { shard }),
+        1 => Just(Action::Sync),
+    ]
+}
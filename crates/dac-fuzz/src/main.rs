@@ -0,0 +1,76 @@
+//! DAC invariant fuzzer
+//!
+//! Runs random sequences of wraps, unwraps, splits, merges, trades,
+//! resolutions and redemptions by a few users against two pooled markets
+//! in LiteSVM, and after every step checks that:
+//! - the USDC vault covers the DAC supply and the wrapped total
+//! - every market escrow covers what its outstanding outcome tokens can
+//!   claim, and its tracked collateral
+//! - the fee vault never shrinks
+//!
+//! A failing sequence is shrunk to a minimal one and printed. The programs
+//! are loaded like in `dac-test-utils` (run `anchor build` first).
+
+mod actions;
+mod world;
+
+use std::process::ExitCode;
+
+use clap::Parser;
+use proptest::collection::vec;
+use proptest::test_runner::{Config, TestCaseError, TestError, TestRunner};
+
+use crate::actions::action;
+use crate::world::World;
+
+#[derive(Parser)]
+#[clap(name = "dac-fuzz", version, about = "Fuzzes the DAC protocol invariants over random action sequences")]
+struct Args {
+    /// Number of random sequences to run
+    #[clap(long, default_value = "256")]
+    cases: u32,
+
+    /// Longest sequence of actions
+    #[clap(long, default_value = "64")]
+    max_steps: usize,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let mut runner = TestRunner::new(Config {
+        cases: args.cases,
+        failure_persistence: None,
+        ..Config::default()
+    });
+
+    let result = runner.run(&vec(action(), 1..=args.max_steps), |actions| {
+        let mut world = World::new();
+        world.check().map_err(|e| TestCaseError::fail(format!("after setup: {}", e)))?;
+        for (step, action) in actions.iter().enumerate() {
+            world.apply(action);
+            world
+                .check()
+                .map_err(|e| TestCaseError::fail(format!("step {} ({:?}): {}", step, action, e)))?;
+        }
+        Ok(())
+    });
+
+    match result {
+        Ok(()) => {
+            println!("{} sequences passed", args.cases);
+            ExitCode::SUCCESS
+        }
+        Err(TestError::Fail(reason, actions)) => {
+            println!("Invariant broken: {}", reason);
+            println!("Minimal sequence:");
+            for (step, action) in actions.iter().enumerate() {
+                println!("  {:>3}. {:?}", step, action);
+            }
+            ExitCode::FAILURE
+        }
+        Err(TestError::Abort(reason)) => {
+            println!("Fuzzing aborted: {}", reason);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! The simulated bank a fuzz case runs in, and the invariants checked after every action
+
+use anchor_spl::token::spl_token;
+use dac_sdk::accounts::{Market, MarketStatus};
+use dac_sdk::instructions::{self, PositionAccounts, SwapAccounts};
+use dac_sdk::{flows, pda};
+use dac_test_utils::DacFixture;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::actions::{Action, MARKETS, USERS};
+
+/// Wrap fee while fuzzing, so the fee paths run
+const WRAP_FEE_BPS: u16 = 30;
+/// Share of swap fees routed to stakers while fuzzing
+const FEE_SHARE_BPS: u16 = 2_000;
+/// Pool fee of the fuzzed markets
+const POOL_FEE_BPS: u16 = 100;
+/// Initial liquidity of each pool, in base units (1,000 DAC)
+const POOL_LIQUIDITY: u64 = 1_000_000_000;
+/// Seconds between the market end times
+const MARKET_SPACING: i64 = 3_600;
+
+pub struct World {
+    fixture: DacFixture,
+    users: Vec<Keypair>,
+    markets: Vec<Pubkey>,
+    /// Fee vault balance after the last action
+    fees: u64,
+}
+
+impl World {
+    pub fn new() -> Self {
+        let mut fixture = DacFixture::new();
+        let authority = fixture.authority.pubkey();
+        let fee_vault = fixture.fee_vault;
+        fixture.execute(
+            &[
+                instructions::set_wrap_fee(&authority, WRAP_FEE_BPS, fee_vault),
+                instructions::configure_fee_sharing(&authority, fee_vault, FEE_SHARE_BPS),
+            ],
+            &[],
+        );
+
+        let now = fixture.now();
+        let markets = (0..MARKETS)
+            .map(|i| {
+                let end_time = now + MARKET_SPACING * (i as i64 + 1);
+                let market = fixture.create_market(&format!("Fuzz market {}", i), end_time, authority);
+                fixture.create_pool(&market, POOL_FEE_BPS, POOL_LIQUIDITY);
+                market
+            })
+            .collect();
+        let users = (0..USERS).map(|_| fixture.user()).collect();
+
+        // The fee vault is the authority's DAC account; it only receives fees from here on
+        let fees = fixture.balance(&authority, &fixture.dac_mint);
+        Self { fixture, users, markets, fees }
+    }
+
+    /// Apply `action`; transactions the programs reject leave the state unchanged
+    pub fn apply(&mut self, action: &Action) {
+        let fixture = &mut self.fixture;
+        match *action {
+            Action::Wrap { user, amount } => {
+                let user = &self.users[user];
+                fixture.mint_usdc(&user.pubkey(), amount);
+                let config = fixture.dac_config();
+                let _ = fixture.send(&flows::wrap(&user.pubkey(), &config, amount), &[user]);
+            }
+            Action::Unwrap { user, amount } => {
+                let user = &self.users[user];
+                let config = fixture.dac_config();
+                let shards = fixture.vault_shards();
+                let _ = fixture.send(&flows::unwrap(&user.pubkey(), &config, &shards, amount), &[user]);
+            }
+            Action::Split { user, market, amount } => {
+                let user = &self.users[user];
+                let market_key = self.markets[market];
+                let state = fixture.market(&market_key);
+                let ixs = [
+                    instructions::create_associated_token_account(&user.pubkey(), &user.pubkey(), &state.yes_mint),
+                    instructions::create_associated_token_account(&user.pubkey(), &user.pubkey(), &state.no_mint),
+                    instructions::split(&market_key, &state, &position(fixture, user, &state), amount),
+                ];
+                let _ = fixture.send(&ixs, &[user]);
+            }
+            Action::Merge { user, market, amount } => {
+                let user = &self.users[user];
+                let market_key = self.markets[market];
+                let state = fixture.market(&market_key);
+                let ix = instructions::merge(&market_key, &state, &position(fixture, user, &state), amount);
+                let _ = fixture.send(&[ix], &[user]);
+            }
+            Action::Buy { user, market, outcome, amount } | Action::Sell { user, market, outcome, amount } => {
+                let user = &self.users[user];
+                let market_key = self.markets[market];
+                let state = fixture.market(&market_key);
+                let Some(outcome_mint) = state.outcome_mint(outcome) else {
+                    return;
+                };
+                let swap = SwapAccounts {
+                    user: user.pubkey(),
+                    user_dac: pda::associated_token_address(&user.pubkey(), &fixture.dac_mint),
+                    user_outcome: pda::associated_token_address(&user.pubkey(), &outcome_mint),
+                    fee_vault: fixture.fee_vault,
+                    track_portfolio: false,
+                    rebate_vault: None,
+                };
+                let trade = if matches!(action, Action::Buy { .. }) {
+                    instructions::buy(&market_key, &state, &swap, outcome, amount, 0, u64::MAX)
+                } else {
+                    instructions::sell(&market_key, &state, &swap, outcome, amount, 0, u64::MAX)
+                };
+                let ixs = [
+                    instructions::create_associated_token_account(&user.pubkey(), &user.pubkey(), &outcome_mint),
+                    trade,
+                ];
+                let _ = fixture.send(&ixs, &[user]);
+            }
+            Action::Resolve { market, outcome } => {
+                let market_key = self.markets[market];
+                let end_time = fixture.market(&market_key).end_time;
+                if fixture.now() < end_time {
+                    fixture.warp_to(end_time);
+                }
+                let authority = fixture.authority.pubkey();
+                let _ = fixture.send(&[instructions::resolve_market(&market_key, &authority, outcome)], &[]);
+            }
+            Action::Redeem { user, market } => {
+                let user = &self.users[user];
+                let market_key = self.markets[market];
+                let state = fixture.market(&market_key);
+                let _ = fixture.send(&flows::redeem(&user.pubkey(), &fixture.dac_mint, &market_key, &state), &[user]);
+            }
+            Action::Restock { shard } => {
+                let config = fixture.dac_config();
+                let fees = fixture.vault_shards()[shard as usize].fees;
+                let fee_vault = (fees > 0).then_some(config.fee_vault);
+                let restock = instructions::restock_vault_shard(&config.dac_mint, shard, fee_vault);
+                let _ = fixture.send(&[restock], &[]);
+            }
+            Action::Sync => {
+                let shard_count = fixture.dac_config().shard_count;
+                let _ = fixture.send(&[instructions::sync_vault_shards(shard_count)], &[]);
+            }
+        }
+    }
+
+    /// Check the global invariants, describing the first one broken
+    pub fn check(&mut self) -> Result<(), String> {
+        let fixture = &self.fixture;
+
+        // Every issued DAC is backed by at least one USDC, and each shard by its own vault
+        let mut vaults = 0;
+        let mut unissued = 0;
+        for shard in fixture.vault_shards() {
+            let vault = token_amount(fixture, &shard.usdc_vault);
+            let float = token_amount(fixture, &shard.dac_float);
+            if shard.wrapped > vault {
+                return Err(format!("shard {} wrapped {} above its vault {}", shard.index, shard.wrapped, vault));
+            }
+            if shard.fees > float {
+                return Err(format!("shard {} fees {} above its float {}", shard.index, shard.fees, float));
+            }
+            vaults += vault;
+            unissued += float - shard.fees;
+        }
+        let issued = mint_supply(fixture, &fixture.dac_mint) - unissued;
+        if vaults < issued {
+            return Err(format!("USDC vaults {} below issued DAC {}", vaults, issued));
+        }
+
+        // Every escrow covers what its outcome tokens can still claim
+        for market_key in &self.markets {
+            let market = fixture.market(market_key);
+            let escrow = token_amount(fixture, &market.escrow);
+            let owed = claimable(&market, mint_supply(fixture, &market.yes_mint), mint_supply(fixture, &market.no_mint));
+            if escrow < owed {
+                return Err(format!("market #{} escrow {} below claimable {}", market.id, escrow, owed));
+            }
+            if market.total_collateral > escrow {
+                return Err(format!(
+                    "market #{} collateral {} above escrow {}",
+                    market.id, market.total_collateral, escrow
+                ));
+            }
+        }
+
+        // Fees only ever accrue
+        let fees = fixture.balance(&fixture.authority.pubkey(), &fixture.dac_mint);
+        if fees < self.fees {
+            return Err(format!("fee vault fell from {} to {}", self.fees, fees));
+        }
+        self.fees = fees;
+        Ok(())
+    }
+}
+
+fn position(fixture: &DacFixture, user: &Keypair, market: &Market) -> PositionAccounts {
+    let owner = user.pubkey();
+    PositionAccounts {
+        user: owner,
+        user_dac: pda::associated_token_address(&owner, &fixture.dac_mint),
+        user_yes: pda::associated_token_address(&owner, &market.yes_mint),
+        user_no: pda::associated_token_address(&owner, &market.no_mint),
+        track_portfolio: false,
+    }
+}
+
+/// DAC the outstanding outcome tokens can claim: the winning side once resolved, the larger side before
+fn claimable(market: &Market, yes: u64, no: u64) -> u64 {
+    match market.status() {
+        MarketStatus::Resolved => market.redemption_value(yes, no).unwrap_or(u64::MAX),
+        MarketStatus::Swept => 0,
+        MarketStatus::Open | MarketStatus::Sealed => yes.max(no),
+    }
+}
+
+fn token_amount(fixture: &DacFixture, address: &Pubkey) -> u64 {
+    fixture
+        .svm
+        .get_account(address)
+        .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+        .map_or(0, |account| account.amount)
+}
+
+fn mint_supply(fixture: &DacFixture, mint: &Pubkey) -> u64 {
+    fixture
+        .svm
+        .get_account(mint)
+        .and_then(|account| spl_token::state::Mint::unpack(&account.data).ok())
+        .map_or(0, |mint| mint.supply)
+}
@@ -0,0 +1,138 @@
+//! Decoding of program accounts into JSON
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use dac_token::shard::VaultShard;
+use dac_token::DacConfig;
+use dark_market::state::{LeveragedPosition, Market, MarketConfig, Outcome, Portfolio};
+use serde_json::{json, Value};
+
+/// Decode an account of ours, returning its kind and fields
+///
+/// Accounts of other types are ignored.
+pub fn decode(owner: &Pubkey, data: &[u8]) -> Option<(&'static str, Value)> {
+    let discriminator = data.get(..8)?;
+    if *owner == dac_token::ID {
+        if discriminator == DacConfig::DISCRIMINATOR {
+            return Some(("dac_config", dac_config(&deserialize(data)?)));
+        }
+        if discriminator == VaultShard::DISCRIMINATOR {
+            return Some(("vault_shard", vault_shard(&deserialize(data)?)));
+        }
+    } else if *owner == dark_market::ID {
+        if discriminator == MarketConfig::DISCRIMINATOR {
+            return Some(("market_config", market_config(&deserialize(data)?)));
+        }
+        // Zero-copy markets are cast, so only at their exact size
+        if discriminator == Market::DISCRIMINATOR && data.len() == 8 + Market::LEN {
+            return Some(("market", market(&deserialize(data)?)));
+        }
+        if discriminator == Portfolio::DISCRIMINATOR {
+            return Some(("portfolio", portfolio(&deserialize(data)?)));
+        }
+        if discriminator == LeveragedPosition::DISCRIMINATOR {
+            return Some(("leveraged_position", leveraged_position(&deserialize(data)?)));
+        }
+    }
+    None
+}
+
+fn deserialize<T: AccountDeserialize>(mut data: &[u8]) -> Option<T> {
+    T::try_deserialize(&mut data).ok()
+}
+
+fn dac_config(config: &DacConfig) -> Value {
+    json!({
+        "authority": config.authority.to_string(),
+        "dac_mint": config.dac_mint.to_string(),
+        "usdc_mint": config.usdc_mint.to_string(),
+        "vault": config.vault.to_string(),
+        "total_wrapped": config.total_wrapped,
+        "market_program": config.market_program.to_string(),
+        "bundle_wraps": config.bundle_wraps,
+        "fee_bps": config.fee_bps,
+        "fee_vault": config.fee_vault.to_string(),
+        "shard_count": config.shard_count,
+        "shard_float": config.shard_float,
+    })
+}
+
+fn vault_shard(shard: &VaultShard) -> Value {
+    json!({
+        "index": shard.index,
+        "usdc_vault": shard.usdc_vault.to_string(),
+        "dac_float": shard.dac_float.to_string(),
+        "wrapped": shard.wrapped,
+        "fees": shard.fees,
+    })
+}
+
+fn market_config(config: &MarketConfig) -> Value {
+    json!({
+        "authority": config.authority.to_string(),
+        "guardian": config.guardian.to_string(),
+        "dac_mint": config.dac_mint.to_string(),
+        "market_count": config.market_count,
+    })
+}
+
+fn market(market: &Market) -> Value {
+    json!({
+        "id": market.id,
+        "creator": market.creator.to_string(),
+        "resolver": market.resolver.to_string(),
+        "question": market.question(),
+        "yes_mint": market.yes_mint.to_string(),
+        "no_mint": market.no_mint.to_string(),
+        "escrow": market.escrow.to_string(),
+        "end_time": market.end_time,
+        "status": format!("{:?}", market.status()),
+        "outcome": market.outcome().map(outcome_name),
+        "total_collateral": market.total_collateral,
+        "series": market.series.to_string(),
+        "resolved_at": market.resolved_at,
+        "claim_window": market.claim_window,
+    })
+}
+
+fn portfolio(portfolio: &Portfolio) -> Value {
+    let positions: Vec<Value> = portfolio
+        .positions
+        .iter()
+        .map(|p| {
+            json!({
+                "market": p.market.to_string(),
+                "yes": p.yes,
+                "no": p.no,
+                "yes_cost": p.yes_cost,
+                "no_cost": p.no_cost,
+            })
+        })
+        .collect();
+    json!({
+        "owner": portfolio.owner.to_string(),
+        "realized_pnl": portfolio.realized_pnl,
+        "volume": portfolio.volume,
+        "positions": positions,
+    })
+}
+
+fn leveraged_position(position: &LeveragedPosition) -> Value {
+    json!({
+        "margin_account": position.margin_account.to_string(),
+        "market": position.market.to_string(),
+        "outcome": outcome_name(position.outcome),
+        "size": position.size,
+        "borrowed": position.borrowed,
+        "funding_paid": position.funding_paid,
+        "last_funding": position.last_funding,
+    })
+}
+
+fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Yes => "yes",
+        Outcome::No => "no",
+        Outcome::Invalid => "invalid",
+    }
+}
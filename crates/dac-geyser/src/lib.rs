@@ -0,0 +1,138 @@
+//! DAC Geyser Plugin
+//!
+//! Streams changes of DAC token and dark market accounts from a validator to
+//! NATS, so downstream services get sub-second state without polling RPC.
+//!
+//! Key features:
+//! - Filters account updates by owner to our two programs
+//! - Decodes configs, vault shards, markets, portfolios and leveraged positions
+//! - Publishes field-level diffs per account, and closures
+//! - Never blocks the validator: updates go through a bounded queue to a
+//!   publisher thread, and are dropped when it is full
+//!
+//! Updates are reported at processed commitment; consumers that need
+//! finality should wait for the slot to be rooted.
+
+mod decode;
+mod publisher;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::thread::JoinHandle;
+
+use agave_geyser_plugin_interface::geyser_plugin_interface::{
+    GeyserPlugin, GeyserPluginError, ReplicaAccountInfoVersions, Result,
+};
+use anchor_lang::prelude::Pubkey;
+use serde::Deserialize;
+
+use crate::publisher::Update;
+
+/// Plugin configuration, read from the JSON file the validator is given
+#[derive(Deserialize)]
+struct Config {
+    nats_url: String,
+    /// Prefix of the subjects published to
+    #[serde(default = "default_subject_prefix")]
+    subject_prefix: String,
+    /// Updates buffered for the publisher before new ones are dropped
+    #[serde(default = "default_queue_capacity")]
+    queue_capacity: usize,
+}
+
+fn default_subject_prefix() -> String {
+    "dac".to_string()
+}
+
+fn default_queue_capacity() -> usize {
+    100_000
+}
+
+#[derive(Debug, Default)]
+pub struct DacGeyserPlugin {
+    sender: Option<SyncSender<Update>>,
+    publisher: Option<JoinHandle<()>>,
+    dropped: AtomicU64,
+}
+
+impl GeyserPlugin for DacGeyserPlugin {
+    fn name(&self) -> &'static str {
+        "dac-geyser"
+    }
+
+    fn on_load(&mut self, config_file: &str, _is_reload: bool) -> Result<()> {
+        let config = std::fs::read_to_string(config_file)
+            .map_err(|e| GeyserPluginError::ConfigFileReadError { msg: e.to_string() })?;
+        let config: Config = serde_json::from_str(&config)
+            .map_err(|e| GeyserPluginError::ConfigFileReadError { msg: e.to_string() })?;
+
+        let (sender, receiver) = mpsc::sync_channel(config.queue_capacity);
+        let publisher = std::thread::Builder::new()
+            .name("dacGeyserPub".to_string())
+            .spawn(move || publisher::run(config.nats_url, config.subject_prefix, receiver))
+            .map_err(|e| GeyserPluginError::Custom(Box::new(e)))?;
+        self.sender = Some(sender);
+        self.publisher = Some(publisher);
+        Ok(())
+    }
+
+    fn on_unload(&mut self) {
+        // Dropping the sender stops the publisher once it drains the queue
+        self.sender = None;
+        if let Some(publisher) = self.publisher.take() {
+            let _ = publisher.join();
+        }
+        log::info!("dac-geyser dropped {} updates", self.dropped.load(Ordering::Relaxed));
+    }
+
+    fn update_account(&self, account: ReplicaAccountInfoVersions, slot: u64, _is_startup: bool) -> Result<()> {
+        let Some(sender) = &self.sender else {
+            return Ok(());
+        };
+        let (pubkey, owner, lamports, data, write_version) = match account {
+            ReplicaAccountInfoVersions::V0_0_1(a) => (a.pubkey, a.owner, a.lamports, a.data, a.write_version),
+            ReplicaAccountInfoVersions::V0_0_2(a) => (a.pubkey, a.owner, a.lamports, a.data, a.write_version),
+            ReplicaAccountInfoVersions::V0_0_3(a) => (a.pubkey, a.owner, a.lamports, a.data, a.write_version),
+        };
+
+        // Closed accounts lose their owner, so every emptied account is passed on
+        // and the publisher reports the ones it knew
+        let (Ok(pubkey), Ok(owner)) = (Pubkey::try_from(pubkey), Pubkey::try_from(owner)) else {
+            return Ok(());
+        };
+        let state = if lamports == 0 {
+            None
+        } else if owner == dac_token::ID || owner == dark_market::ID {
+            match decode::decode(&owner, data) {
+                Some(state) => Some(state),
+                None => return Ok(()),
+            }
+        } else {
+            return Ok(());
+        };
+
+        let update = Update { pubkey, slot, write_version, state };
+        if let Err(TrySendError::Full(_)) = sender.try_send(update) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    fn account_data_notifications_enabled(&self) -> bool {
+        true
+    }
+
+    fn transaction_notifications_enabled(&self) -> bool {
+        false
+    }
+}
+
+#[no_mangle]
+#[allow(improper_ctypes_definitions)]
+/// # Safety
+///
+/// Called by the validator to load the plugin; the returned pointer is owned
+/// by the validator.
+pub unsafe extern "C" fn _create_plugin() -> *mut dyn GeyserPlugin {
+    Box::into_raw(Box::<DacGeyserPlugin>::default())
+}
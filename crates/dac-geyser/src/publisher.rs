@@ -0,0 +1,91 @@
+//! Diffing decoded accounts and publishing the changes to NATS
+
+use std::collections::HashMap;
+use std::sync::mpsc::Receiver;
+
+use anchor_lang::prelude::Pubkey;
+use serde_json::{json, Map, Value};
+
+/// A decoded account update, or the closure of an account
+pub struct Update {
+    pub pubkey: Pubkey,
+    pub slot: u64,
+    pub write_version: u64,
+    /// Account kind and fields, `None` once the account is closed
+    pub state: Option<(&'static str, Value)>,
+}
+
+/// Publish the changes carried by `updates` until the plugin drops the sender
+///
+/// Each message goes to `<prefix>.<kind>.<pubkey>` and holds only the
+/// fields that changed since the last update of the account, or every field
+/// the first time it is seen. Diffs are taken against the last update
+/// received, so an update dropped on a full queue folds into the next diff.
+pub fn run(nats_url: String, prefix: String, updates: Receiver<Update>) {
+    let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            log::error!("Failed to start the publisher runtime: {}", e);
+            return;
+        }
+    };
+    runtime.block_on(async move {
+        let client = match async_nats::connect(&nats_url).await {
+            Ok(client) => client,
+            Err(e) => {
+                log::error!("Failed to connect to NATS at {}: {}", nats_url, e);
+                return;
+            }
+        };
+
+        let mut last: HashMap<Pubkey, (&'static str, Map<String, Value>)> = HashMap::new();
+        while let Ok(update) = updates.recv() {
+            let Some((subject, message)) = diff(&mut last, &prefix, update) else {
+                continue;
+            };
+            if let Err(e) = client.publish(subject, message.to_string().into()).await {
+                log::warn!("Failed to publish to NATS: {}", e);
+            }
+        }
+        if let Err(e) = client.flush().await {
+            log::warn!("Failed to flush NATS: {}", e);
+        }
+    });
+}
+
+/// Subject and message of an update, if anything changed
+fn diff(
+    last: &mut HashMap<Pubkey, (&'static str, Map<String, Value>)>,
+    prefix: &str,
+    update: Update,
+) -> Option<(String, Value)> {
+    let (kind, changes) = match update.state {
+        Some((kind, Value::Object(fields))) => {
+            let previous = last.insert(update.pubkey, (kind, fields.clone()));
+            let changes: Map<String, Value> = match previous {
+                Some((_, previous)) => fields
+                    .into_iter()
+                    .filter(|(key, value)| previous.get(key) != Some(value))
+                    .collect(),
+                None => fields,
+            };
+            if changes.is_empty() {
+                return None;
+            }
+            (kind, json!({ "changes": changes }))
+        }
+        Some(_) => return None,
+        // Only accounts seen before are reported closed
+        None => {
+            let (kind, _) = last.remove(&update.pubkey)?;
+            (kind, json!({ "closed": true }))
+        }
+    };
+
+    let mut message = changes;
+    message["pubkey"] = json!(update.pubkey.to_string());
+    message["kind"] = json!(kind);
+    message["slot"] = json!(update.slot);
+    message["write_version"] = json!(update.write_version);
+    Some((format!("{}.{}.{}", prefix, kind, update.pubkey), message))
+}
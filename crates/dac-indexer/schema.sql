@@ -0,0 +1,90 @@
+-- Schema of the DAC event indexer, applied on startup
+--
+-- Every indexed transaction has one row in indexed_transactions, written in
+-- the same database transaction as its events; a transaction seen twice
+-- (backfill and websocket overlap) finds its row and is skipped.
+--
+-- Each indexed transaction then notifies the dac_indexed channel. The single
+-- writer commits transactions in seq order, so a reader can tail new events
+-- by seq.
+
+CREATE TABLE IF NOT EXISTS indexed_transactions (
+    signature   TEXT PRIMARY KEY,
+    slot        BIGINT NOT NULL,
+    -- Unix time, unknown for transactions seen over the websocket
+    block_time  BIGINT
+);
+ALTER TABLE indexed_transactions ADD COLUMN IF NOT EXISTS seq BIGSERIAL;
+CREATE UNIQUE INDEX IF NOT EXISTS indexed_transactions_seq ON indexed_transactions (seq);
+
+-- Newest finalized signature backfilled per program
+CREATE TABLE IF NOT EXISTS backfill_cursors (
+    program     TEXT PRIMARY KEY,
+    signature   TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS wraps (
+    signature   TEXT NOT NULL REFERENCES indexed_transactions,
+    event_index INT NOT NULL,
+    wallet      TEXT NOT NULL,
+    kind        TEXT NOT NULL CHECK (kind IN ('wrap', 'unwrap')),
+    amount      BIGINT NOT NULL,
+    fee         BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS wraps_wallet ON wraps (wallet);
+-- USDC backing DAC after the event, for events from before the vault shards
+ALTER TABLE wraps ADD COLUMN IF NOT EXISTS total_wrapped BIGINT;
+-- Vault shard the event went through and the USDC backing DAC in it after the event
+ALTER TABLE wraps ADD COLUMN IF NOT EXISTS shard SMALLINT;
+ALTER TABLE wraps ADD COLUMN IF NOT EXISTS shard_wrapped BIGINT;
+
+CREATE TABLE IF NOT EXISTS trades (
+    signature   TEXT NOT NULL REFERENCES indexed_transactions,
+    event_index INT NOT NULL,
+    market      TEXT NOT NULL,
+    wallet      TEXT NOT NULL,
+    outcome     TEXT NOT NULL,
+    side        TEXT NOT NULL CHECK (side IN ('buy', 'sell')),
+    amount_in   BIGINT NOT NULL,
+    amount_out  BIGINT NOT NULL,
+    fee         BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS trades_market ON trades (market);
+CREATE INDEX IF NOT EXISTS trades_wallet ON trades (wallet);
+
+CREATE TABLE IF NOT EXISTS pool_updates (
+    signature   TEXT NOT NULL REFERENCES indexed_transactions,
+    event_index INT NOT NULL,
+    market      TEXT NOT NULL,
+    yes_reserve BIGINT NOT NULL,
+    no_reserve  BIGINT NOT NULL,
+    -- Spot price of YES scaled by 1e6
+    yes_price   BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS pool_updates_market ON pool_updates (market);
+
+CREATE TABLE IF NOT EXISTS resolutions (
+    signature   TEXT NOT NULL REFERENCES indexed_transactions,
+    event_index INT NOT NULL,
+    market      TEXT NOT NULL,
+    outcome     TEXT NOT NULL,
+    resolved_at BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS resolutions_market ON resolutions (market);
+
+CREATE TABLE IF NOT EXISTS redemptions (
+    signature   TEXT NOT NULL REFERENCES indexed_transactions,
+    event_index INT NOT NULL,
+    market      TEXT NOT NULL,
+    wallet      TEXT NOT NULL,
+    yes_burned  BIGINT NOT NULL,
+    no_burned   BIGINT NOT NULL,
+    payout      BIGINT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS redemptions_market ON redemptions (market);
+CREATE INDEX IF NOT EXISTS redemptions_wallet ON redemptions (wallet);
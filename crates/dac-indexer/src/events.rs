@@ -0,0 +1,92 @@
+//! Anchor event decoding from transaction logs
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use dac_token::{Unwrapped, Wrapped};
+use dark_market::events::{MarketResolved, PoolUpdated, Redeemed, Trade};
+use solana_sdk::pubkey::Pubkey;
+
+/// An event the indexer stores
+pub enum Event {
+    Wrapped(Wrapped),
+    Unwrapped(Unwrapped),
+    Trade(Trade),
+    PoolUpdated(PoolUpdated),
+    MarketResolved(MarketResolved),
+    Redeemed(Redeemed),
+}
+
+impl Event {
+    /// Name of the event, for metrics
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Wrapped(_) => "wrapped",
+            Self::Unwrapped(_) => "unwrapped",
+            Self::Trade(_) => "trade",
+            Self::PoolUpdated(_) => "pool_updated",
+            Self::MarketResolved(_) => "market_resolved",
+            Self::Redeemed(_) => "redeemed",
+        }
+    }
+
+    /// Decode event data logged by `program`
+    fn decode(program: &Pubkey, data: &[u8]) -> Option<Self> {
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, mut body) = data.split_at(8);
+        if *program == dac_token::ID {
+            if discriminator == Wrapped::DISCRIMINATOR {
+                return Wrapped::deserialize(&mut body).ok().map(Self::Wrapped);
+            }
+            if discriminator == Unwrapped::DISCRIMINATOR {
+                return Unwrapped::deserialize(&mut body).ok().map(Self::Unwrapped);
+            }
+        } else if *program == dark_market::ID {
+            if discriminator == Trade::DISCRIMINATOR {
+                return Trade::deserialize(&mut body).ok().map(Self::Trade);
+            }
+            if discriminator == PoolUpdated::DISCRIMINATOR {
+                return PoolUpdated::deserialize(&mut body).ok().map(Self::PoolUpdated);
+            }
+            if discriminator == MarketResolved::DISCRIMINATOR {
+                return MarketResolved::deserialize(&mut body).ok().map(Self::MarketResolved);
+            }
+            if discriminator == Redeemed::DISCRIMINATOR {
+                return Redeemed::deserialize(&mut body).ok().map(Self::Redeemed);
+            }
+        }
+        None
+    }
+}
+
+/// Events of our programs in a successful transaction's logs, in order
+///
+/// Each `Program data:` line is attributed to the program on top of the
+/// invocation stack, so another program logging the same bytes is ignored.
+pub fn parse_logs(logs: &[String]) -> Vec<Event> {
+    let mut stack: Vec<Option<Pubkey>> = Vec::new();
+    let mut events = Vec::new();
+    for line in logs {
+        if let Some(data) = line.strip_prefix("Program data: ") {
+            let (Some(Some(program)), Ok(data)) = (stack.last(), STANDARD.decode(data)) else {
+                continue;
+            };
+            events.extend(Event::decode(program, &data));
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some(program), Some("invoke")) => stack.push(program.parse().ok()),
+                (Some(_), Some("success")) => {
+                    stack.pop();
+                }
+                (Some(_), Some(status)) if status.starts_with("failed") => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
@@ -0,0 +1,122 @@
+//! Transaction sources: the historical backfill and the live log subscription
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use futures::StreamExt;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::{RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::UiTransactionEncoding;
+use tokio::sync::mpsc::Sender;
+
+use crate::events::parse_logs;
+use crate::store::IndexedTransaction;
+
+/// Signatures per `getSignaturesForAddress` page
+const PAGE_SIZE: usize = 1_000;
+
+/// Index every finalized transaction of `program` after `cursor`, oldest first
+///
+/// Each transaction moves the program's cursor, so a restarted backfill
+/// resumes where the last one stopped.
+pub async fn backfill(
+    rpc: RpcClient,
+    program: Pubkey,
+    cursor: Option<String>,
+    sender: Sender<IndexedTransaction>,
+) -> Result<()> {
+    let until = cursor.as_deref().map(Signature::from_str).transpose()?;
+    let mut signatures = Vec::new();
+    let mut before = None;
+    loop {
+        let page = rpc
+            .get_signatures_for_address_with_config(
+                &program,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until,
+                    limit: Some(PAGE_SIZE),
+                    commitment: Some(CommitmentConfig::finalized()),
+                },
+            )
+            .await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = Some(Signature::from_str(&last.signature)?);
+        let full = page.len() == PAGE_SIZE;
+        signatures.extend(page);
+        if !full {
+            break;
+        }
+    }
+    log::info!("Backfilling {} transactions of {}", signatures.len(), program);
+
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Json),
+        commitment: Some(CommitmentConfig::finalized()),
+        max_supported_transaction_version: Some(0),
+    };
+    for status in signatures.into_iter().rev() {
+        // Failed transactions still move the cursor, with nothing to store
+        let mut events = Vec::new();
+        if status.err.is_none() {
+            let signature = Signature::from_str(&status.signature)?;
+            let tx = rpc.get_transaction_with_config(&signature, config).await?;
+            let logs = tx
+                .transaction
+                .meta
+                .and_then(|meta| Option::<Vec<String>>::from(meta.log_messages))
+                .unwrap_or_default();
+            events = parse_logs(&logs);
+        }
+        let indexed = IndexedTransaction {
+            signature: status.signature,
+            slot: status.slot,
+            block_time: status.block_time,
+            events,
+            cursor: Some(program),
+        };
+        sender.send(indexed).await.map_err(|_| anyhow!("store closed"))?;
+    }
+
+    log::info!("Backfill of {} done", program);
+    Ok(())
+}
+
+/// Index confirmed transactions mentioning `program` as they land
+///
+/// Returns an error when the subscription drops. Transactions missed while
+/// the indexer is down are picked up by the backfill on restart.
+pub async fn subscribe(url: String, program: Pubkey, sender: Sender<IndexedTransaction>) -> Result<()> {
+    let client = PubsubClient::new(&url).await?;
+    let (mut stream, _unsubscribe) = client
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![program.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .await?;
+    log::info!("Subscribed to logs of {}", program);
+
+    while let Some(response) = stream.next().await {
+        if response.value.err.is_some() {
+            continue;
+        }
+        let indexed = IndexedTransaction {
+            signature: response.value.signature,
+            slot: response.context.slot,
+            block_time: None,
+            events: parse_logs(&response.value.logs),
+            cursor: None,
+        };
+        sender.send(indexed).await.map_err(|_| anyhow!("store closed"))?;
+    }
+    Err(anyhow!("log subscription of {} closed", program))
+}
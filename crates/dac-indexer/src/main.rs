@@ -0,0 +1,99 @@
+//! DAC event indexer
+//!
+//! Decodes the Anchor events of the DAC token and dark market programs and
+//! writes wraps, trades, pool updates, resolutions and redemptions into
+//! Postgres for analytics, user history and the stream service.
+//!
+//! On startup each program is backfilled from its cursor with
+//! `getSignaturesForAddress` while a websocket log subscription indexes new
+//! transactions. The two overlap; a single writer stores each transaction
+//! exactly once (see `schema.sql`).
+//!
+//! Prometheus metrics (events stored, queue depth, slot lag, RPC errors)
+//! are served on `/metrics`, and `/healthz` fails while the indexer trails
+//! the programs' newest transaction by more than `--max-slot-lag`.
+
+mod events;
+mod ingest;
+mod metrics;
+mod store;
+
+use std::net::SocketAddr;
+
+use anyhow::Result;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+
+use crate::metrics::Metrics;
+use crate::store::Store;
+
+/// Programs whose transactions are indexed
+const PROGRAMS: [Pubkey; 2] = [dac_token::ID, dark_market::ID];
+
+#[derive(Parser)]
+#[clap(name = "dac-indexer", version, about = "Indexes DAC and dark market events into Postgres")]
+struct Args {
+    /// RPC endpoint for the backfill
+    #[clap(long, env = "RPC_URL", default_value = "https://api.devnet.solana.com")]
+    rpc_url: String,
+
+    /// Websocket endpoint for the live subscription
+    #[clap(long, env = "WS_URL", default_value = "wss://api.devnet.solana.com")]
+    ws_url: String,
+
+    /// Postgres connection string
+    #[clap(long, env = "DATABASE_URL")]
+    database_url: String,
+
+    /// Address of the metrics and health server
+    #[clap(long, env = "METRICS_ADDR", default_value = "0.0.0.0:9101")]
+    metrics_addr: SocketAddr,
+
+    /// Largest lag behind the newest program transaction of a healthy indexer, in slots
+    #[clap(long, default_value = "150")]
+    max_slot_lag: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let args = Args::parse();
+    let mut store = Store::connect(&args.database_url).await?;
+    let metrics = Metrics::new()?;
+
+    let (sender, mut receiver) = mpsc::channel(1_024);
+    let mut tasks = JoinSet::new();
+    for program in PROGRAMS {
+        tasks.spawn(ingest::subscribe(args.ws_url.clone(), program, sender.clone()));
+        let rpc = RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::finalized());
+        let cursor = store.cursor(&program).await?;
+        tasks.spawn(ingest::backfill(rpc, program, cursor, sender.clone()));
+    }
+    drop(sender);
+    let rpc = RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::confirmed());
+    tasks.spawn(metrics::serve(args.metrics_addr, metrics.clone(), args.max_slot_lag));
+    tokio::spawn(metrics::poll_lag(rpc, PROGRAMS.to_vec(), metrics.clone()));
+
+    loop {
+        tokio::select! {
+            Some(tx) = receiver.recv() => {
+                metrics.queue_depth.set(receiver.len() as i64);
+                if store.index(&tx).await? {
+                    metrics.indexed(tx.slot);
+                    for event in &tx.events {
+                        metrics.events.with_label_values(&[event.name()]).inc();
+                    }
+                    if !tx.events.is_empty() {
+                        log::info!("Indexed {} events of {}", tx.events.len(), tx.signature);
+                    }
+                }
+            }
+            Some(task) = tasks.join_next() => task??,
+            else => return Ok(()),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Prometheus metrics and the health endpoint
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::Router;
+use prometheus::{Encoder, IntCounter, IntCounterVec, IntGauge, Opts, Registry, TextEncoder};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+/// How often the cluster slot is polled for the lag
+const LAG_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    /// Transactions stored for the first time
+    pub transactions: IntCounter,
+    /// Events stored by event name
+    pub events: IntCounterVec,
+    /// Transactions received and waiting for the store
+    pub queue_depth: IntGauge,
+    /// Highest slot of a stored transaction
+    pub last_indexed_slot: IntGauge,
+    /// Slots between the newest program transaction and `last_indexed_slot`
+    pub slot_lag: IntGauge,
+    /// Failed RPC requests by operation
+    pub rpc_errors: IntCounterVec,
+    /// Unix time the lag was last measured
+    last_lag_poll: IntGauge,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self> {
+        let registry = Registry::new_custom(Some("dac_indexer".to_string()), None)?;
+        let metrics = Self {
+            transactions: IntCounter::new("transactions_total", "Transactions stored")?,
+            events: IntCounterVec::new(Opts::new("events_total", "Events stored by event"), &["event"])?,
+            queue_depth: IntGauge::new("queue_depth", "Transactions waiting for the store")?,
+            last_indexed_slot: IntGauge::new("last_indexed_slot", "Highest slot of a stored transaction")?,
+            slot_lag: IntGauge::new("slot_lag", "Slots between the newest program transaction and the last indexed one")?,
+            rpc_errors: IntCounterVec::new(
+                Opts::new("rpc_errors_total", "Failed RPC requests by operation"),
+                &["operation"],
+            )?,
+            last_lag_poll: IntGauge::new("last_lag_poll_timestamp", "Unix time the lag was last measured")?,
+            registry,
+        };
+        metrics.registry.register(Box::new(metrics.transactions.clone()))?;
+        metrics.registry.register(Box::new(metrics.events.clone()))?;
+        metrics.registry.register(Box::new(metrics.queue_depth.clone()))?;
+        metrics.registry.register(Box::new(metrics.last_indexed_slot.clone()))?;
+        metrics.registry.register(Box::new(metrics.slot_lag.clone()))?;
+        metrics.registry.register(Box::new(metrics.rpc_errors.clone()))?;
+        metrics.registry.register(Box::new(metrics.last_lag_poll.clone()))?;
+        Ok(metrics)
+    }
+
+    /// Record a stored transaction at `slot`
+    pub fn indexed(&self, slot: u64) {
+        self.transactions.inc();
+        if slot as i64 > self.last_indexed_slot.get() {
+            self.last_indexed_slot.set(slot as i64);
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("metrics encode as text");
+        String::from_utf8(buffer).expect("the text format is UTF-8")
+    }
+}
+
+/// Measure how far the indexer trails the newest transaction of `programs`, forever
+///
+/// Comparing against the programs' own activity rather than the cluster
+/// slot keeps a quiet program from looking like a stuck indexer.
+pub async fn poll_lag(rpc: RpcClient, programs: Vec<Pubkey>, metrics: Metrics) {
+    let mut interval = tokio::time::interval(LAG_POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        match newest_slot(&rpc, &programs).await {
+            Ok(newest) => {
+                metrics.slot_lag.set((newest as i64 - metrics.last_indexed_slot.get()).max(0));
+                metrics.last_lag_poll.set(unix_now());
+            }
+            Err(e) => {
+                metrics.rpc_errors.with_label_values(&["signatures"]).inc();
+                log::warn!("Failed to read the newest program transactions: {}", e);
+            }
+        }
+    }
+}
+
+/// Slot of the newest confirmed transaction of any of `programs`
+async fn newest_slot(rpc: &RpcClient, programs: &[Pubkey]) -> Result<u64> {
+    let mut newest = 0;
+    for program in programs {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            limit: Some(1),
+            commitment: Some(CommitmentConfig::confirmed()),
+            ..GetConfirmedSignaturesForAddress2Config::default()
+        };
+        let signatures = rpc.get_signatures_for_address_with_config(program, config).await?;
+        newest = signatures.iter().map(|status| status.slot).fold(newest, u64::max);
+    }
+    Ok(newest)
+}
+
+struct Server {
+    metrics: Metrics,
+    max_slot_lag: i64,
+}
+
+/// Serve `/metrics` and `/healthz`, healthy while the lag is measured and at most `max_slot_lag`
+pub async fn serve(addr: SocketAddr, metrics: Metrics, max_slot_lag: u64) -> Result<()> {
+    let router = Router::new()
+        .route("/metrics", get(|State(server): State<Arc<Server>>| async move { server.metrics.render() }))
+        .route("/healthz", get(healthz))
+        .with_state(Arc::new(Server { metrics, max_slot_lag: max_slot_lag as i64 }));
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router).await?;
+    Ok(())
+}
+
+async fn healthz(State(server): State<Arc<Server>>) -> (StatusCode, String) {
+    let metrics = &server.metrics;
+    let poll_age = unix_now() - metrics.last_lag_poll.get();
+    if poll_age > 3 * LAG_POLL_INTERVAL.as_secs() as i64 {
+        return (StatusCode::SERVICE_UNAVAILABLE, format!("lag not measured for {}s", poll_age));
+    }
+    let lag = metrics.slot_lag.get();
+    if lag > server.max_slot_lag {
+        return (StatusCode::SERVICE_UNAVAILABLE, format!("{} slots behind", lag));
+    }
+    (StatusCode::OK, "ok".to_string())
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
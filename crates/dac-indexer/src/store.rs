@@ -0,0 +1,191 @@
+//! Postgres storage of indexed transactions
+
+use anyhow::{Context, Result};
+use dark_market::state::Outcome;
+use solana_sdk::pubkey::Pubkey;
+use tokio_postgres::{Client, NoTls, Transaction};
+
+use crate::events::Event;
+
+const SCHEMA: &str = include_str!("../schema.sql");
+
+/// A transaction's events, ready to store
+pub struct IndexedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub events: Vec<Event>,
+    /// Program whose backfill cursor moves to this transaction
+    pub cursor: Option<Pubkey>,
+}
+
+pub struct Store {
+    client: Client,
+}
+
+impl Store {
+    /// Connect and apply the schema
+    pub async fn connect(url: &str) -> Result<Self> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls)
+            .await
+            .context("failed to connect to Postgres")?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                log::error!("Postgres connection closed: {}", e);
+            }
+        });
+        client.batch_execute(SCHEMA).await.context("failed to apply schema")?;
+        Ok(Self { client })
+    }
+
+    /// Newest signature backfilled for `program`
+    pub async fn cursor(&self, program: &Pubkey) -> Result<Option<String>> {
+        let row = self
+            .client
+            .query_opt("SELECT signature FROM backfill_cursors WHERE program = $1", &[&program.to_string()])
+            .await?;
+        Ok(row.map(|row| row.get(0)))
+    }
+
+    /// Store a transaction's events unless it was indexed before, and move its cursor
+    ///
+    /// Returns whether the events were new. Everything is written in one
+    /// database transaction, so a crash never leaves a transaction half
+    /// indexed or a cursor ahead of what was stored.
+    pub async fn index(&mut self, tx: &IndexedTransaction) -> Result<bool> {
+        let db = self.client.transaction().await?;
+        let inserted = db
+            .execute(
+                "INSERT INTO indexed_transactions (signature, slot, block_time) VALUES ($1, $2, $3)
+                 ON CONFLICT DO NOTHING",
+                &[&tx.signature, &bigint(tx.slot)?, &tx.block_time],
+            )
+            .await?
+            == 1;
+        if inserted {
+            for (index, event) in tx.events.iter().enumerate() {
+                insert_event(&db, &tx.signature, index as i32, event).await?;
+            }
+            // Delivered to listeners (the stream service) once this commits
+            db.execute("SELECT pg_notify('dac_indexed', '')", &[]).await?;
+        }
+        if let Some(program) = tx.cursor {
+            db.execute(
+                "INSERT INTO backfill_cursors (program, signature) VALUES ($1, $2)
+                 ON CONFLICT (program) DO UPDATE SET signature = EXCLUDED.signature",
+                &[&program.to_string(), &tx.signature],
+            )
+            .await?;
+        }
+        db.commit().await?;
+        Ok(inserted)
+    }
+}
+
+async fn insert_event(db: &Transaction<'_>, signature: &str, index: i32, event: &Event) -> Result<()> {
+    match event {
+        Event::Wrapped(e) => {
+            db.execute(
+                "INSERT INTO wraps (signature, event_index, wallet, kind, amount, fee, shard, shard_wrapped)
+                 VALUES ($1, $2, $3, 'wrap', $4, $5, $6, $7)",
+                &[
+                    &signature,
+                    &index,
+                    &e.user.to_string(),
+                    &bigint(e.amount)?,
+                    &bigint(e.fee)?,
+                    &(e.shard as i16),
+                    &bigint(e.shard_wrapped)?,
+                ],
+            )
+            .await?
+        }
+        Event::Unwrapped(e) => {
+            db.execute(
+                "INSERT INTO wraps (signature, event_index, wallet, kind, amount, fee, shard, shard_wrapped)
+                 VALUES ($1, $2, $3, 'unwrap', $4, $5, $6, $7)",
+                &[
+                    &signature,
+                    &index,
+                    &e.user.to_string(),
+                    &bigint(e.amount)?,
+                    &bigint(e.fee)?,
+                    &(e.shard as i16),
+                    &bigint(e.shard_wrapped)?,
+                ],
+            )
+            .await?
+        }
+        Event::Trade(e) => {
+            db.execute(
+                "INSERT INTO trades (signature, event_index, market, wallet, outcome, side, amount_in, amount_out, fee)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+                &[
+                    &signature,
+                    &index,
+                    &e.market.to_string(),
+                    &e.user.to_string(),
+                    &outcome_name(e.outcome),
+                    &if e.is_buy { "buy" } else { "sell" },
+                    &bigint(e.amount_in)?,
+                    &bigint(e.amount_out)?,
+                    &bigint(e.fee)?,
+                ],
+            )
+            .await?
+        }
+        Event::PoolUpdated(e) => {
+            db.execute(
+                "INSERT INTO pool_updates (signature, event_index, market, yes_reserve, no_reserve, yes_price)
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+                &[
+                    &signature,
+                    &index,
+                    &e.market.to_string(),
+                    &bigint(e.yes_reserve)?,
+                    &bigint(e.no_reserve)?,
+                    &bigint(e.yes_price)?,
+                ],
+            )
+            .await?
+        }
+        Event::MarketResolved(e) => {
+            db.execute(
+                "INSERT INTO resolutions (signature, event_index, market, outcome, resolved_at)
+                 VALUES ($1, $2, $3, $4, $5)",
+                &[&signature, &index, &e.market.to_string(), &outcome_name(e.outcome), &e.resolved_at],
+            )
+            .await?
+        }
+        Event::Redeemed(e) => {
+            db.execute(
+                "INSERT INTO redemptions (signature, event_index, market, wallet, yes_burned, no_burned, payout)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7)",
+                &[
+                    &signature,
+                    &index,
+                    &e.market.to_string(),
+                    &e.user.to_string(),
+                    &bigint(e.yes_burned)?,
+                    &bigint(e.no_burned)?,
+                    &bigint(e.payout)?,
+                ],
+            )
+            .await?
+        }
+    };
+    Ok(())
+}
+
+/// Postgres has no unsigned integers; token amounts fit a BIGINT in practice
+fn bigint(value: u64) -> Result<i64> {
+    i64::try_from(value).context("value exceeds BIGINT")
+}
+
+fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Yes => "yes",
+        Outcome::No => "no",
+        Outcome::Invalid => "invalid",
+    }
+}
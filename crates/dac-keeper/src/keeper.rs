@@ -0,0 +1,200 @@
+//! Chain access and transaction submission shared by the tasks
+
+use std::sync::RwLock;
+use std::time::Duration;
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{anyhow, Result};
+use dac_sdk::accounts::{DacConfig, Market, ProtocolLookupTable, VaultShard};
+use dac_sdk::{lookup_tables, pda};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::account::Account;
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::VersionedTransaction;
+
+use crate::metrics::Metrics;
+
+/// One transaction a task wants landed
+pub struct Job {
+    /// What the job does, for the logs
+    pub label: String,
+    pub instructions: Vec<Instruction>,
+}
+
+pub struct Keeper {
+    pub rpc: RpcClient,
+    pub payer: Keypair,
+    /// Priority fee in micro-lamports per compute unit
+    pub priority_fee: u64,
+    pub compute_unit_limit: u32,
+    /// Attempts after the first before a job is given up until the next round
+    pub max_retries: u32,
+    pub metrics: Metrics,
+    /// The protocol lookup tables transactions are compiled against
+    pub lookup_tables: RwLock<Vec<AddressLookupTableAccount>>,
+}
+
+impl Keeper {
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Cluster time of the latest slot
+    pub async fn now(&self) -> Result<i64> {
+        let slot = self.rpc.get_slot().await?;
+        Ok(self.rpc.get_block_time(slot).await?)
+    }
+
+    /// The DAC token config
+    pub async fn dac_config(&self) -> Result<DacConfig> {
+        Ok(dac_sdk::accounts::dac_config(&self.rpc.get_account_data(&pda::dac_config().0).await?)?)
+    }
+
+    /// Every vault shard of `config` with the balances of its USDC vault and DAC float
+    pub async fn vault_shards(&self, config: &DacConfig) -> Result<Vec<(VaultShard, u64, u64)>> {
+        let keys: Vec<Pubkey> = (0..config.shard_count).map(|index| pda::vault_shard(index).0).collect();
+        let mut shards = Vec::with_capacity(keys.len());
+        for (key, account) in keys.iter().zip(self.rpc.get_multiple_accounts(&keys).await?) {
+            let account = account.ok_or_else(|| anyhow!("vault shard {} missing", key))?;
+            shards.push(dac_sdk::accounts::vault_shard(&account.data)?);
+        }
+        let token_keys: Vec<Pubkey> =
+            shards.iter().flat_map(|shard| [shard.usdc_vault, shard.dac_float]).collect();
+        let mut balances = Vec::with_capacity(token_keys.len());
+        for (key, account) in token_keys.iter().zip(self.rpc.get_multiple_accounts(&token_keys).await?) {
+            let account = account.ok_or_else(|| anyhow!("token account {} missing", key))?;
+            balances.push(dac_sdk::accounts::token_account(&account.data)?.amount);
+        }
+        Ok(shards
+            .into_iter()
+            .zip(balances.chunks(2))
+            .map(|(shard, balances)| (shard, balances[0], balances[1]))
+            .collect())
+    }
+
+    /// Record the USDC in the vault shards against the DAC issued
+    ///
+    /// DAC in the shards' floats is minted but not issued, so it is left out
+    /// of the supply, except for the fees it holds for the fee vault.
+    pub async fn update_backing(&self) -> Result<()> {
+        let config = self.dac_config().await?;
+        let shards = self.vault_shards(&config).await?;
+        let vault: u64 = shards.iter().map(|(_, vault, _)| vault).sum();
+        let unissued: u64 = shards.iter().map(|(shard, _, float)| float.saturating_sub(shard.fees)).sum();
+        let supply: u64 = self.rpc.get_token_supply(&config.dac_mint).await?.amount.parse()?;
+        let issued = supply.saturating_sub(unissued);
+        self.metrics.vault_balance.set(vault as i64);
+        self.metrics.dac_supply.set(issued as i64);
+        // An empty supply is fully backed
+        self.metrics.backing_ratio.set(if issued == 0 { 1.0 } else { vault as f64 / issued as f64 });
+        Ok(())
+    }
+
+    /// Every account of type `T` owned by the dark market program
+    ///
+    /// Accounts that fail to decode (for example from an older layout) are skipped.
+    pub async fn program_accounts<T: AccountDeserialize + Discriminator>(&self) -> Result<Vec<(Pubkey, T)>> {
+        let accounts = self.raw_program_accounts(T::DISCRIMINATOR, None).await?;
+        Ok(accounts
+            .into_iter()
+            .filter_map(|(key, account)| Some((key, dac_sdk::accounts::deserialize(&account.data).ok()?)))
+            .collect())
+    }
+
+    /// Every market
+    ///
+    /// Zero-copy accounts only decode at their exact size, so the query filters by it.
+    pub async fn markets(&self) -> Result<Vec<(Pubkey, Market)>> {
+        let accounts = self.raw_program_accounts(Market::DISCRIMINATOR, Some(8 + Market::LEN)).await?;
+        Ok(accounts
+            .into_iter()
+            .filter_map(|(key, account)| Some((key, dac_sdk::accounts::market(&account.data).ok()?)))
+            .collect())
+    }
+
+    /// Accounts of the dark market program with `discriminator`, of `size` bytes if given
+    async fn raw_program_accounts(
+        &self,
+        discriminator: &[u8],
+        size: Option<usize>,
+    ) -> Result<Vec<(Pubkey, Account)>> {
+        let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, discriminator.to_vec()))];
+        filters.extend(size.map(|size| RpcFilterType::DataSize(size as u64)));
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        Ok(self
+            .rpc
+            .get_program_accounts_with_config(&dac_sdk::DARK_MARKET_PROGRAM_ID, config)
+            .await?)
+    }
+
+    /// Reload the protocol lookup tables
+    pub async fn refresh_lookup_tables(&self) -> Result<()> {
+        let records = self.program_accounts::<ProtocolLookupTable>().await?;
+        let keys: Vec<Pubkey> = records.iter().map(|(_, record)| record.table).collect();
+        let accounts = self.rpc.get_multiple_accounts(&keys).await?;
+        let mut tables = Vec::with_capacity(keys.len());
+        for (key, account) in keys.into_iter().zip(accounts) {
+            let Some(account) = account else { continue };
+            tables.push(lookup_tables::parse(key, &account.data)?);
+        }
+        *self.lookup_tables.write().expect("lookup tables lock poisoned") = tables;
+        Ok(())
+    }
+
+    /// Land `job` with the configured priority fee, retrying transient failures
+    ///
+    /// Each attempt is signed with a fresh blockhash and simulated before it
+    /// is sent, so a job whose conditions no longer hold (another keeper got
+    /// there first, the price moved) fails without paying fees and is not
+    /// retried. Transactions are v0, loading what they can from the
+    /// protocol lookup tables.
+    pub async fn submit(&self, job: &Job) -> Result<Signature> {
+        let mut instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(self.compute_unit_limit),
+            ComputeBudgetInstruction::set_compute_unit_price(self.priority_fee),
+        ];
+        instructions.extend(job.instructions.iter().cloned());
+        let tables = self.lookup_tables.read().expect("lookup tables lock poisoned").clone();
+        let mut message = lookup_tables::compile(&instructions, &self.payer(), &tables, Hash::default())?;
+
+        let mut attempt = 0;
+        loop {
+            let result = async {
+                message.set_recent_blockhash(self.rpc.get_latest_blockhash().await?);
+                let tx = VersionedTransaction::try_new(message.clone(), &[&self.payer])?;
+                self.rpc.send_and_confirm_transaction(&tx).await
+            }
+            .await;
+
+            match result {
+                Ok(signature) => return Ok(signature),
+                Err(e) if e.get_transaction_error().is_some() => return Err(e.into()),
+                Err(e) if attempt >= self.max_retries => {
+                    self.metrics.rpc_errors.with_label_values(&["send"]).inc();
+                    return Err(e.into());
+                }
+                Err(e) => {
+                    self.metrics.rpc_errors.with_label_values(&["send"]).inc();
+                    attempt += 1;
+                    log::warn!("{}: attempt {} failed, retrying: {}", job.label, attempt, e);
+                    tokio::time::sleep(Duration::from_millis(500 << attempt.min(6))).await;
+                }
+            }
+        }
+    }
+}
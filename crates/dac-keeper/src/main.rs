@@ -0,0 +1,212 @@
+//! DAC keeper
+//!
+//! Watches the dark market and DAC token programs and cranks their
+//! permissionless instructions:
+//! - `finalize`: finalizes resolution proposals once their challenge or
+//!   council voting period is over
+//! - `fill`: fills limit orders whose pool crossed the limit, earning the tip
+//! - `sweep`: sweeps unclaimed collateral of markets past their claim deadline
+//! - `tables`: creates and extends the protocol lookup tables with new markets
+//! - `shards`: restocks DAC vault shard floats that ran low or high, paying
+//!   out their fees, and syncs the DAC config's `total_wrapped`
+//!
+//! Transactions are v0 and load accounts from the protocol lookup tables,
+//! reloaded every round.
+//!
+//! Every round each enabled task scans the chain and runs its jobs with its
+//! own concurrency limit, so a backlog in one task never starves another.
+//!
+//! Prometheus metrics (vault backing, pending jobs, job results, RPC
+//! errors) are served on `/metrics`, and `/healthz` fails once rounds stop
+//! finishing.
+
+mod keeper;
+mod metrics;
+mod tasks;
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use clap::{Parser, ValueEnum};
+use futures::StreamExt;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::read_keypair_file;
+
+use crate::keeper::{Job, Keeper};
+use crate::metrics::Metrics;
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Task {
+    Finalize,
+    Fill,
+    Sweep,
+    Tables,
+    Shards,
+}
+
+#[derive(Parser)]
+#[clap(name = "dac-keeper", version, about = "Cranks permissionless dark market and DAC token instructions")]
+struct Args {
+    #[clap(long, env = "RPC_URL", default_value = "https://api.devnet.solana.com")]
+    rpc_url: String,
+
+    /// Keypair file of the keeper wallet, paying fees and collecting tips
+    #[clap(long, env = "KEEPER_KEYPAIR")]
+    keypair: String,
+
+    /// Tasks to run
+    #[clap(
+        long,
+        value_enum,
+        use_value_delimiter = true,
+        default_value = "finalize,fill,sweep,tables,shards"
+    )]
+    tasks: Vec<Task>,
+
+    /// Seconds between scans
+    #[clap(long, default_value = "10")]
+    interval: u64,
+
+    /// Priority fee in micro-lamports per compute unit
+    #[clap(long, default_value = "1000")]
+    priority_fee: u64,
+
+    #[clap(long, default_value = "400000")]
+    compute_unit_limit: u32,
+
+    /// Retries of a transiently failing transaction before it waits for the next round
+    #[clap(long, default_value = "3")]
+    max_retries: u32,
+
+    /// Smallest limit order tip worth filling, in DAC base units
+    #[clap(long, default_value = "0")]
+    min_tip: u64,
+
+    /// Concurrent transactions of the finalize task
+    #[clap(long, default_value = "4")]
+    finalize_concurrency: usize,
+
+    /// Concurrent transactions of the fill task
+    #[clap(long, default_value = "8")]
+    fill_concurrency: usize,
+
+    /// Concurrent transactions of the sweep task
+    #[clap(long, default_value = "2")]
+    sweep_concurrency: usize,
+
+    /// Address of the metrics and health server
+    #[clap(long, env = "METRICS_ADDR", default_value = "0.0.0.0:9100")]
+    metrics_addr: SocketAddr,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let args = Args::parse();
+    let keeper = Keeper {
+        rpc: RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::confirmed()),
+        payer: read_keypair_file(&args.keypair)
+            .map_err(|e| anyhow!("failed to read keypair {}: {}", args.keypair, e))?,
+        priority_fee: args.priority_fee,
+        compute_unit_limit: args.compute_unit_limit,
+        max_retries: args.max_retries,
+        metrics: Metrics::new()?,
+        lookup_tables: RwLock::new(Vec::new()),
+    };
+    log::info!("Keeper {} running {} tasks", keeper.payer(), args.tasks.len());
+
+    // Healthy while rounds keep finishing, allowing for slow ones
+    let max_round_age = (args.interval * 3).max(60) as i64;
+    let server = tokio::spawn(metrics::serve(args.metrics_addr, keeper.metrics.clone(), max_round_age));
+
+    let enabled = |task| args.tasks.contains(&task);
+    let mut interval = tokio::time::interval(Duration::from_secs(args.interval));
+    loop {
+        interval.tick().await;
+        if server.is_finished() {
+            return server.await?;
+        }
+        let now = match keeper.now().await {
+            Ok(now) => now,
+            Err(e) => {
+                keeper.metrics.rpc_errors.with_label_values(&["clock"]).inc();
+                log::warn!("Failed to read the cluster time: {}", e);
+                continue;
+            }
+        };
+        if let Err(e) = keeper.update_backing().await {
+            keeper.metrics.rpc_errors.with_label_values(&["backing"]).inc();
+            log::warn!("Failed to read the vault backing: {}", e);
+        }
+        if let Err(e) = keeper.refresh_lookup_tables().await {
+            keeper.metrics.rpc_errors.with_label_values(&["lookup_tables"]).inc();
+            log::warn!("Failed to load the lookup tables: {}", e);
+        }
+        tokio::join!(
+            run(
+                "finalize",
+                enabled(Task::Finalize),
+                args.finalize_concurrency,
+                &keeper,
+                tasks::finalize_resolutions(&keeper, now),
+            ),
+            run(
+                "fill",
+                enabled(Task::Fill),
+                args.fill_concurrency,
+                &keeper,
+                tasks::fill_limit_orders(&keeper, now, args.min_tip),
+            ),
+            run(
+                "sweep",
+                enabled(Task::Sweep),
+                args.sweep_concurrency,
+                &keeper,
+                tasks::sweep_unclaimed(&keeper, now),
+            ),
+            run("tables", enabled(Task::Tables), 1, &keeper, tasks::extend_lookup_tables(&keeper)),
+            run("shards", enabled(Task::Shards), 1, &keeper, tasks::restock_vault_shards(&keeper)),
+        );
+        keeper.metrics.last_round.set(metrics::unix_now());
+    }
+}
+
+/// Scan for a task's jobs and submit them, at most `concurrency` at a time
+async fn run(
+    name: &str,
+    enabled: bool,
+    concurrency: usize,
+    keeper: &Keeper,
+    scan: impl Future<Output = Result<Vec<Job>>>,
+) {
+    if !enabled {
+        return;
+    }
+    let jobs = match scan.await {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            keeper.metrics.rpc_errors.with_label_values(&["scan"]).inc();
+            log::warn!("{}: scan failed: {}", name, e);
+            return;
+        }
+    };
+    keeper.metrics.pending_jobs.with_label_values(&[name]).set(jobs.len() as i64);
+    futures::stream::iter(jobs)
+        .for_each_concurrent(concurrency.max(1), |job| async move {
+            match keeper.submit(&job).await {
+                Ok(signature) => {
+                    keeper.metrics.jobs.with_label_values(&[name, "ok"]).inc();
+                    log::info!("{}: {} in {}", name, job.label, signature);
+                }
+                Err(e) => {
+                    keeper.metrics.jobs.with_label_values(&[name, "failed"]).inc();
+                    log::warn!("{}: {} failed: {}", name, job.label, e);
+                }
+            }
+        })
+        .await;
+}
@@ -0,0 +1,98 @@
+//! Prometheus metrics and the health endpoint
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::Router;
+use prometheus::{Encoder, Gauge, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder};
+
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    /// USDC in the vault shards per DAC in circulation
+    pub backing_ratio: Gauge,
+    pub vault_balance: IntGauge,
+    pub dac_supply: IntGauge,
+    /// Jobs found by each task's latest scan
+    pub pending_jobs: IntGaugeVec,
+    /// Submitted jobs by task and result (`ok` or `failed`)
+    pub jobs: IntCounterVec,
+    /// Failed RPC requests by operation
+    pub rpc_errors: IntCounterVec,
+    /// Unix time the latest round finished
+    pub last_round: IntGauge,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self> {
+        let registry = Registry::new_custom(Some("dac_keeper".to_string()), None)?;
+        let metrics = Self {
+            backing_ratio: Gauge::new("backing_ratio", "USDC in the vault shards per DAC in circulation")?,
+            vault_balance: IntGauge::new("vault_balance", "USDC in the vault shards, in base units")?,
+            dac_supply: IntGauge::new("dac_supply", "DAC in circulation, in base units")?,
+            pending_jobs: IntGaugeVec::new(
+                Opts::new("pending_jobs", "Jobs found by the task's latest scan"),
+                &["task"],
+            )?,
+            jobs: IntCounterVec::new(Opts::new("jobs_total", "Submitted jobs by result"), &["task", "result"])?,
+            rpc_errors: IntCounterVec::new(
+                Opts::new("rpc_errors_total", "Failed RPC requests by operation"),
+                &["operation"],
+            )?,
+            last_round: IntGauge::new("last_round_timestamp", "Unix time the latest round finished")?,
+            registry,
+        };
+        metrics.registry.register(Box::new(metrics.backing_ratio.clone()))?;
+        metrics.registry.register(Box::new(metrics.vault_balance.clone()))?;
+        metrics.registry.register(Box::new(metrics.dac_supply.clone()))?;
+        metrics.registry.register(Box::new(metrics.pending_jobs.clone()))?;
+        metrics.registry.register(Box::new(metrics.jobs.clone()))?;
+        metrics.registry.register(Box::new(metrics.rpc_errors.clone()))?;
+        metrics.registry.register(Box::new(metrics.last_round.clone()))?;
+        Ok(metrics)
+    }
+
+    fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("metrics encode as text");
+        String::from_utf8(buffer).expect("the text format is UTF-8")
+    }
+}
+
+struct Server {
+    metrics: Metrics,
+    /// Longest time between rounds of a healthy keeper
+    max_round_age: i64,
+}
+
+/// Serve `/metrics` and `/healthz`, healthy while a round finished in the last `max_round_age` seconds
+pub async fn serve(addr: SocketAddr, metrics: Metrics, max_round_age: i64) -> Result<()> {
+    let router = Router::new()
+        .route("/metrics", get(|State(server): State<Arc<Server>>| async move { server.metrics.render() }))
+        .route("/healthz", get(healthz))
+        .with_state(Arc::new(Server { metrics, max_round_age }));
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router).await?;
+    Ok(())
+}
+
+async fn healthz(State(server): State<Arc<Server>>) -> (StatusCode, String) {
+    let age = unix_now() - server.metrics.last_round.get();
+    if age <= server.max_round_age {
+        (StatusCode::OK, "ok".to_string())
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, format!("no round finished in {}s", age))
+    }
+}
+
+/// Wall-clock Unix time
+pub fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
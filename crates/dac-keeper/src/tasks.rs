@@ -0,0 +1,244 @@
+//! Keeper tasks: each scans the chain for work and returns the jobs to run
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use dac_sdk::accounts::{
+    LimitOrder, LookupTableRegistry, Market, MarketConfig, MarketStatus, OrderSide, Pool, ProtocolLookupTable,
+    RebatePool, ResolutionProposal, ResolutionStatus,
+};
+use dac_sdk::lookup_tables::{LOOKUP_TABLE_CAPACITY, MARKET_LOOKUP_ADDRESSES};
+use dac_sdk::{instructions, pda};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::keeper::{Job, Keeper};
+
+/// Markets added to a lookup table per transaction
+const MARKETS_PER_EXTEND: u64 = 8;
+
+/// Create `owner`'s associated token account of `mint` if it is missing, paid by the keeper
+fn create_ata(keeper: &Keeper, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    instructions::create_associated_token_account(&keeper.payer(), owner, mint)
+}
+
+/// Finalize resolution proposals whose challenge or council voting period is over
+pub async fn finalize_resolutions(keeper: &Keeper, now: i64) -> Result<Vec<Job>> {
+    let config: MarketConfig = dac_sdk::accounts::deserialize(
+        &keeper.rpc.get_account_data(&pda::market_config().0).await?,
+    )?;
+    let proposals = keeper.program_accounts::<ResolutionProposal>().await?;
+
+    let mut jobs = Vec::new();
+    for (_, proposal) in proposals {
+        let winner = match proposal.status {
+            ResolutionStatus::Proposed if now >= proposal.challenge_ends() => proposal.proposer,
+            ResolutionStatus::Disputed if now >= proposal.voting_ends() => {
+                match proposal.council_decision() {
+                    Some(outcome) if outcome != proposal.outcome => proposal.challenger,
+                    _ => proposal.proposer,
+                }
+            }
+            _ => continue,
+        };
+        let winner_dac = pda::associated_token_address(&winner, &config.dac_mint);
+        jobs.push(Job {
+            label: format!("finalize resolution of {}", proposal.market),
+            instructions: vec![
+                create_ata(keeper, &winner, &config.dac_mint),
+                instructions::finalize_resolution(&proposal.market, &proposal, &winner_dac),
+            ],
+        });
+    }
+    Ok(jobs)
+}
+
+/// Fill limit orders whose pool has crossed the limit price and that tip at least `min_tip`
+///
+/// Orders are quoted without the owner's fee discount, so an order that
+/// only crosses with it waits for a better price.
+pub async fn fill_limit_orders(keeper: &Keeper, now: i64, min_tip: u64) -> Result<Vec<Job>> {
+    let config: MarketConfig = dac_sdk::accounts::deserialize(
+        &keeper.rpc.get_account_data(&pda::market_config().0).await?,
+    )?;
+    let slot = keeper.rpc.get_slot().await?;
+    let orders = keeper.program_accounts::<LimitOrder>().await?;
+    if orders.is_empty() {
+        return Ok(Vec::new());
+    }
+    let pools: HashMap<Pubkey, Pool> = keeper.program_accounts::<Pool>().await?.into_iter().collect();
+    let rebate_vaults: HashMap<Pubkey, Pubkey> = keeper
+        .program_accounts::<RebatePool>()
+        .await?
+        .into_iter()
+        .map(|(_, rebate_pool)| (rebate_pool.pool, rebate_pool.vault))
+        .collect();
+    let markets: HashMap<Pubkey, (Pubkey, Market)> = keeper
+        .markets()
+        .await?
+        .into_iter()
+        .map(|(key, market)| (pda::pool(&key).0, (key, market)))
+        .collect();
+    let cranker_dac = pda::associated_token_address(&keeper.payer(), &config.dac_mint);
+
+    let mut jobs = Vec::new();
+    for (order_key, order) in orders {
+        if order.tip < min_tip {
+            continue;
+        }
+        let Some(pool) = pools.get(&order.pool) else {
+            continue;
+        };
+        let Some((market_key, market)) = markets.get(&order.pool) else {
+            continue;
+        };
+        if !market.is_trading(now) || pool.breaker.is_halted(slot) {
+            continue;
+        }
+
+        let quote = match order.side {
+            OrderSide::Buy => pool.quote_buy(order.outcome, order.amount, 0),
+            OrderSide::Sell => pool.quote_sell(order.outcome, order.amount, 0),
+        };
+        let (Some(out), Some(min_out)) = (quote, order.min_amount_out()) else {
+            continue;
+        };
+        if out < min_out {
+            continue;
+        }
+
+        let proceeds_mint = match order.side {
+            OrderSide::Buy => match market.outcome_mint(order.outcome) {
+                Some(mint) => mint,
+                None => continue,
+            },
+            OrderSide::Sell => config.dac_mint,
+        };
+        let owner_proceeds = pda::associated_token_address(&order.owner, &proceeds_mint);
+        jobs.push(Job {
+            label: format!("fill limit order {}", order_key),
+            instructions: vec![
+                create_ata(keeper, &keeper.payer(), &config.dac_mint),
+                create_ata(keeper, &order.owner, &proceeds_mint),
+                instructions::fill_limit_order(
+                    &keeper.payer(),
+                    &cranker_dac,
+                    &order_key,
+                    &order,
+                    market_key,
+                    market,
+                    &owner_proceeds,
+                    rebate_vaults.get(&order.pool).copied(),
+                ),
+            ],
+        });
+    }
+    Ok(jobs)
+}
+
+/// Sweep the unclaimed collateral of resolved markets past their claim deadline
+pub async fn sweep_unclaimed(keeper: &Keeper, now: i64) -> Result<Vec<Job>> {
+    let config: MarketConfig = dac_sdk::accounts::deserialize(
+        &keeper.rpc.get_account_data(&pda::market_config().0).await?,
+    )?;
+    let markets = keeper.markets().await?;
+
+    let mut jobs = Vec::new();
+    for (market_key, market) in markets {
+        if market.status() != MarketStatus::Resolved || !market.claim_expired(now) {
+            continue;
+        }
+        let mut ixs = Vec::new();
+        let destination = if market.sweeps_to_creator() {
+            ixs.push(create_ata(keeper, &market.creator, &config.dac_mint));
+            pda::associated_token_address(&market.creator, &config.dac_mint)
+        } else {
+            pda::insurance_fund().0
+        };
+        ixs.push(instructions::sweep_unclaimed(&market_key, &market, &destination));
+        jobs.push(Job {
+            label: format!("sweep market #{}", market.id),
+            instructions: ixs,
+        });
+    }
+    Ok(jobs)
+}
+
+/// Create and extend the protocol lookup tables until they cover every market
+///
+/// Markets are added in id order, so each round yields at most one job,
+/// starting where the previous one ended.
+pub async fn extend_lookup_tables(keeper: &Keeper) -> Result<Vec<Job>> {
+    let config: MarketConfig = dac_sdk::accounts::deserialize(
+        &keeper.rpc.get_account_data(&pda::market_config().0).await?,
+    )?;
+    let registry = keeper
+        .rpc
+        .get_account_with_commitment(&pda::lookup_table_registry().0, keeper.rpc.commitment())
+        .await?
+        .value
+        .map(|account| dac_sdk::accounts::deserialize::<LookupTableRegistry>(&account.data))
+        .transpose()?;
+    let (table_count, next_market) = registry.map_or((0, 0), |registry| (registry.table_count, registry.next_market));
+    if next_market >= config.market_count {
+        return Ok(Vec::new());
+    }
+
+    let latest = match table_count.checked_sub(1) {
+        Some(index) => Some(dac_sdk::accounts::deserialize::<ProtocolLookupTable>(
+            &keeper.rpc.get_account_data(&pda::lookup_table(index).0).await?,
+        )?),
+        None => None,
+    };
+    let Some(table) = latest.filter(|table| !table.is_full()) else {
+        let slot = keeper.rpc.get_slot().await?;
+        return Ok(vec![Job {
+            label: format!("create lookup table #{}", table_count),
+            instructions: vec![instructions::create_lookup_table(&keeper.payer(), table_count, slot)],
+        }]);
+    };
+
+    let room = ((LOOKUP_TABLE_CAPACITY - table.len as usize) / MARKET_LOOKUP_ADDRESSES) as u64;
+    let end = config.market_count.min(next_market + room.min(MARKETS_PER_EXTEND));
+    let markets: Vec<Pubkey> = (next_market..end).map(|id| pda::market(id).0).collect();
+    Ok(vec![Job {
+        label: format!("add markets #{}-#{} to lookup table #{}", next_market, end - 1, table.index),
+        instructions: vec![instructions::extend_lookup_table_with_markets(
+            &keeper.payer(),
+            table.index,
+            &table.table,
+            &markets,
+        )],
+    }])
+}
+
+/// Restock vault shards whose float drifted from the configured size, and sync `total_wrapped` when stale
+///
+/// A float is restocked once wraps drew it below half the configured size,
+/// so they keep finding DAC, or unwraps filled it past twice of it; the
+/// fees it accrued are paid out along the way.
+pub async fn restock_vault_shards(keeper: &Keeper) -> Result<Vec<Job>> {
+    let config = keeper.dac_config().await?;
+    let shards = keeper.vault_shards(&config).await?;
+
+    let mut jobs = Vec::new();
+    for (shard, _, float) in &shards {
+        let unissued = float.saturating_sub(shard.fees);
+        if unissued >= config.shard_float / 2 && unissued <= config.shard_float.saturating_mul(2) {
+            continue;
+        }
+        let fee_vault = (shard.fees > 0).then_some(config.fee_vault);
+        jobs.push(Job {
+            label: format!("restock vault shard {}", shard.index),
+            instructions: vec![instructions::restock_vault_shard(&config.dac_mint, shard.index, fee_vault)],
+        });
+    }
+    let wrapped: u64 = shards.iter().map(|(shard, _, _)| shard.wrapped).sum();
+    if wrapped != config.total_wrapped {
+        jobs.push(Job {
+            label: format!("sync {} vault shards", config.shard_count),
+            instructions: vec![instructions::sync_vault_shards(config.shard_count)],
+        });
+    }
+    Ok(jobs)
+}
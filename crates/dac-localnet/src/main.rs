@@ -0,0 +1,127 @@
+//! DAC localnet
+//!
+//! One command for a local copy of the protocol: starts
+//! `solana-test-validator` with every program of `target/deploy` loaded,
+//! creates the USDC and DAC mints, initializes the DAC token and dark
+//! market programs, seeds sample markets with pool liquidity and prints
+//! every address as JSON. The validator keeps running until Ctrl-C.
+//!
+//! Run `anchor build` first. Wrap fees and fee sharing are off and wrap
+//! bundling is disabled, like in `dac-test-utils`. The authority's keypair
+//! is written next to the ledger so other tools (e.g. `dac-cli`) can use it.
+
+mod setup;
+mod validator;
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Result};
+use clap::Parser;
+use dac_sdk::pda;
+use serde_json::json;
+use solana_sdk::signature::{write_keypair_file, Keypair, Signer};
+
+use crate::setup::{Chain, USDC_DECIMALS, VAULT_SHARDS};
+use crate::validator::Validator;
+
+/// Questions of the sample markets, ending a week apart
+const SAMPLE_QUESTIONS: [&str; 3] = [
+    "Will SOL close above $300 this month?",
+    "Will the Fed cut rates at its next meeting?",
+    "Will a spot SOL ETF be approved this year?",
+];
+const WEEK: i64 = 7 * 24 * 60 * 60;
+
+#[derive(Parser)]
+#[clap(name = "dac-localnet", version, about = "Starts a local validator with the DAC protocol set up")]
+struct Args {
+    /// Directory with the built programs and their keypairs
+    #[clap(long, default_value = "target/deploy")]
+    programs: PathBuf,
+
+    /// Ledger directory, reset on every start
+    #[clap(long, default_value = "target/localnet-ledger")]
+    ledger: PathBuf,
+
+    /// RPC port (the websocket uses the next one)
+    #[clap(long, default_value = "8899")]
+    rpc_port: u16,
+
+    /// Number of sample markets
+    #[clap(long, default_value = "3")]
+    markets: usize,
+
+    /// Pool liquidity of each sample market, in DAC
+    #[clap(long, default_value = "10000")]
+    liquidity: u64,
+
+    /// Validator binary
+    #[clap(long, default_value = "solana-test-validator")]
+    validator: String,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    if args.markets > SAMPLE_QUESTIONS.len() {
+        bail!("at most {} sample markets", SAMPLE_QUESTIONS.len());
+    }
+    let programs = validator::workspace_programs(&args.programs)?;
+    let authority = Keypair::new();
+    let validator = Validator::start(
+        &args.validator,
+        &args.ledger,
+        args.rpc_port,
+        &programs,
+        &authority.pubkey(),
+    )?;
+    let keypair_path = args.ledger.join("authority.json");
+    fs::create_dir_all(&args.ledger)?;
+    write_keypair_file(&authority, &keypair_path)
+        .map_err(|e| anyhow!("failed to write {}: {}", keypair_path.display(), e))?;
+
+    let chain = Chain::new(&validator.rpc_url, authority)?;
+    let protocol = chain.initialize()?;
+    let liquidity = args.liquidity * 10u64.pow(USDC_DECIMALS as u32);
+    let now = chain.now()?;
+    let mut markets = Vec::new();
+    for (i, question) in SAMPLE_QUESTIONS.iter().take(args.markets).enumerate() {
+        let end_time = now + WEEK * (i as i64 + 1);
+        let (address, market) = chain.create_pooled_market(&protocol, question, end_time, liquidity)?;
+        markets.push(json!({
+            "id": market.id,
+            "question": question,
+            "address": address.to_string(),
+            "end_time": end_time,
+            "yes_mint": market.yes_mint.to_string(),
+            "no_mint": market.no_mint.to_string(),
+            "escrow": market.escrow.to_string(),
+            "pool": pda::pool(&address).0.to_string(),
+        }));
+    }
+
+    let output = json!({
+        "rpc_url": validator.rpc_url,
+        "ws_url": validator.ws_url,
+        "authority": {
+            "pubkey": chain.authority.pubkey().to_string(),
+            "keypair": keypair_path,
+        },
+        "programs": programs
+            .iter()
+            .map(|program| (program.name.clone(), json!(program.id.to_string())))
+            .collect::<serde_json::Map<_, _>>(),
+        "usdc_mint": protocol.usdc_mint.to_string(),
+        "dac_mint": protocol.dac_mint.to_string(),
+        "dac_config": pda::dac_config().0.to_string(),
+        "vault_shards": (0..VAULT_SHARDS)
+            .map(|index| pda::vault_shard(index).0.to_string())
+            .collect::<Vec<_>>(),
+        "market_config": pda::market_config().0.to_string(),
+        "fee_vault": protocol.fee_vault.to_string(),
+        "markets": markets,
+    });
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    eprintln!("Validator running at {}, Ctrl-C to stop", validator.rpc_url);
+    validator.wait()
+}
@@ -0,0 +1,172 @@
+//! Configuring the protocol on the fresh validator
+
+use anchor_spl::token::spl_token;
+use anyhow::{Context, Result};
+use dac_sdk::accounts::{self, DacConfig, Market};
+use dac_sdk::{flows, instructions, pda};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::account::from_account;
+use solana_sdk::clock::Clock;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::sysvar;
+use solana_sdk::transaction::Transaction;
+
+/// Decimals of the local USDC mint, and so of DAC
+pub const USDC_DECIMALS: u8 = 6;
+/// SOL airdropped to the authority
+const AIRDROP: u64 = 1_000 * LAMPORTS_PER_SOL;
+/// Swap fee of the sample pools
+const POOL_FEE_BPS: u16 = 100;
+/// Vault shards wraps and unwraps are spread over
+pub const VAULT_SHARDS: u8 = 4;
+/// DAC float of each vault shard, in base units (1,000,000 DAC)
+const SHARD_FLOAT: u64 = 1_000_000_000_000;
+
+/// The authority's connection to the validator
+pub struct Chain {
+    pub rpc: RpcClient,
+    /// Authority of both configs and of the USDC mint, resolver of the sample markets
+    pub authority: Keypair,
+}
+
+/// The protocol accounts created on the validator
+pub struct Protocol {
+    pub usdc_mint: Pubkey,
+    pub dac_mint: Pubkey,
+    /// The authority's DAC account, receiving the swap fee share
+    pub fee_vault: Pubkey,
+}
+
+impl Chain {
+    pub fn new(rpc_url: &str, authority: Keypair) -> Result<Self> {
+        let rpc = RpcClient::new_with_commitment(rpc_url.to_string(), CommitmentConfig::confirmed());
+        let signature = rpc.request_airdrop(&authority.pubkey(), AIRDROP)?;
+        rpc.poll_for_signature(&signature).context("airdrop failed")?;
+        Ok(Self { rpc, authority })
+    }
+
+    /// Send `instructions` paid and signed by the authority, plus `signers`
+    pub fn send(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<()> {
+        let mut all = vec![&self.authority];
+        all.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.authority.pubkey()),
+            &all,
+            self.rpc.get_latest_blockhash()?,
+        );
+        self.rpc.send_and_confirm_transaction(&tx)?;
+        Ok(())
+    }
+
+    /// Create both mints, initialize both programs with wrap bundling off and open the vault shards
+    pub fn initialize(&self) -> Result<Protocol> {
+        let authority = self.authority.pubkey();
+        let usdc_mint = self.create_mint(&authority).context("failed to create the USDC mint")?;
+        let dac_mint = self.create_mint(&pda::mint_authority().0).context("failed to create the DAC mint")?;
+        let fee_vault = pda::associated_token_address(&authority, &dac_mint);
+        self.send(
+            &[
+                instructions::initialize_dac(&authority, &dac_mint, &usdc_mint),
+                instructions::initialize_market_config(&authority, &dac_mint),
+                instructions::configure_wrap_bundling(&authority, dac_sdk::DAC_TOKEN_PROGRAM_ID, false),
+                instructions::create_associated_token_account(&authority, &authority, &dac_mint),
+                instructions::configure_fee_sharing(&authority, fee_vault, 0),
+            ],
+            &[],
+        )
+        .context("failed to initialize the programs")?;
+        let config = self.dac_config()?;
+        self.send(&flows::open_vault_shards(&authority, &config, VAULT_SHARDS, SHARD_FLOAT), &[])
+            .context("failed to open the vault shards")?;
+        Ok(Protocol { usdc_mint, dac_mint, fee_vault })
+    }
+
+    fn create_mint(&self, mint_authority: &Pubkey) -> Result<Pubkey> {
+        let mint = Keypair::new();
+        let rent = self.rpc.get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN)?;
+        self.send(
+            &[
+                system_instruction::create_account(
+                    &self.authority.pubkey(),
+                    &mint.pubkey(),
+                    rent,
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    mint_authority,
+                    None,
+                    USDC_DECIMALS,
+                )?,
+            ],
+            &[&mint],
+        )?;
+        Ok(mint.pubkey())
+    }
+
+    /// Mint `amount` USDC to the authority and wrap it
+    pub fn mint_and_wrap(&self, protocol: &Protocol, amount: u64) -> Result<()> {
+        let authority = self.authority.pubkey();
+        let usdc = pda::associated_token_address(&authority, &protocol.usdc_mint);
+        self.send(
+            &[
+                instructions::create_associated_token_account(&authority, &authority, &protocol.usdc_mint),
+                spl_token::instruction::mint_to(&spl_token::ID, &protocol.usdc_mint, &usdc, &authority, &[], amount)?,
+            ],
+            &[],
+        )?;
+        let config = self.dac_config()?;
+        self.send(&flows::wrap(&authority, &config, amount), &[])
+    }
+
+    /// Create a market resolved by the authority and open its pool with `liquidity` DAC
+    pub fn create_pooled_market(
+        &self,
+        protocol: &Protocol,
+        question: &str,
+        end_time: i64,
+        liquidity: u64,
+    ) -> Result<(Pubkey, Market)> {
+        let authority = self.authority.pubkey();
+        let config = accounts::market_config(&self.rpc.get_account_data(&pda::market_config().0)?)?;
+        let (market_key, ixs) = flows::create_market(
+            &authority,
+            &protocol.dac_mint,
+            config.market_count,
+            question.to_string(),
+            end_time,
+            authority,
+        );
+        self.send(&ixs, &[]).with_context(|| format!("failed to create market {:?}", question))?;
+
+        self.mint_and_wrap(protocol, liquidity)?;
+        let market = accounts::market(&self.rpc.get_account_data(&market_key)?)?;
+        let user_dac = pda::associated_token_address(&authority, &protocol.dac_mint);
+        self.send(
+            &[instructions::create_pool(&authority, &user_dac, &market_key, &market, POOL_FEE_BPS, liquidity)],
+            &[],
+        )
+        .with_context(|| format!("failed to open the pool of {:?}", question))?;
+        Ok((market_key, market))
+    }
+
+    fn dac_config(&self) -> Result<DacConfig> {
+        Ok(accounts::dac_config(&self.rpc.get_account_data(&pda::dac_config().0)?)?)
+    }
+
+    /// Cluster time, from the clock sysvar
+    pub fn now(&self) -> Result<i64> {
+        let account = self.rpc.get_account(&sysvar::clock::ID)?;
+        let clock: Clock = from_account(&account).context("invalid clock sysvar")?;
+        Ok(clock.unix_timestamp)
+    }
+}
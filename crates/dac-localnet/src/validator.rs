@@ -0,0 +1,122 @@
+//! Running `solana-test-validator` with the workspace programs
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+
+/// How long the validator may take to answer RPC requests
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A program to load at genesis
+pub struct Program {
+    /// Crate name, e.g. `dark_market`
+    pub name: String,
+    pub id: Pubkey,
+    pub path: PathBuf,
+}
+
+/// Every `<name>.so` in `dir` with its `<name>-keypair.json`, as `anchor build` leaves them
+///
+/// The keypairs are the ones `declare_id!` was synced to, so each program
+/// runs at the address it expects.
+pub fn workspace_programs(dir: &Path) -> Result<Vec<Program>> {
+    let mut programs = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {} (run `anchor build`)", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("so") {
+            continue;
+        }
+        let name = path.file_stem().and_then(|s| s.to_str()).context("invalid program file name")?.to_string();
+        let keypair_path = dir.join(format!("{}-keypair.json", name));
+        let keypair = read_keypair_file(&keypair_path)
+            .map_err(|e| anyhow::anyhow!("failed to read {}: {}", keypair_path.display(), e))?;
+        programs.push(Program { name, id: keypair.pubkey(), path });
+    }
+    if programs.is_empty() {
+        bail!("no programs in {} (run `anchor build`)", dir.display());
+    }
+    programs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(programs)
+}
+
+/// The validator process, killed when dropped
+pub struct Validator {
+    child: Child,
+    pub rpc_url: String,
+    pub ws_url: String,
+}
+
+impl Validator {
+    /// Start a fresh validator in `ledger` with `programs` upgradeable by `upgrade_authority`
+    pub fn start(
+        binary: &str,
+        ledger: &Path,
+        rpc_port: u16,
+        programs: &[Program],
+        upgrade_authority: &Pubkey,
+    ) -> Result<Self> {
+        let mut command = Command::new(binary);
+        command
+            .arg("--reset")
+            .arg("--quiet")
+            .arg("--ledger")
+            .arg(ledger)
+            .arg("--rpc-port")
+            .arg(rpc_port.to_string());
+        for program in programs {
+            command
+                .arg("--upgradeable-program")
+                .arg(program.id.to_string())
+                .arg(&program.path)
+                .arg(upgrade_authority.to_string());
+        }
+        let child = command
+            .stdout(Stdio::null())
+            .spawn()
+            .with_context(|| format!("failed to start {}", binary))?;
+
+        let mut validator = Self {
+            child,
+            rpc_url: format!("http://127.0.0.1:{}", rpc_port),
+            // The websocket listens on the port after the RPC port
+            ws_url: format!("ws://127.0.0.1:{}", rpc_port + 1),
+        };
+        validator.wait_until_healthy()?;
+        Ok(validator)
+    }
+
+    fn wait_until_healthy(&mut self) -> Result<()> {
+        let rpc = RpcClient::new(self.rpc_url.clone());
+        let started = Instant::now();
+        while rpc.get_health().is_err() {
+            if let Some(status) = self.child.try_wait()? {
+                bail!("validator exited during startup ({})", status);
+            }
+            if started.elapsed() > STARTUP_TIMEOUT {
+                bail!("validator not healthy after {:?}", STARTUP_TIMEOUT);
+            }
+            thread::sleep(Duration::from_millis(500));
+        }
+        Ok(())
+    }
+
+    /// Block until the validator exits (Ctrl-C stops both)
+    pub fn wait(mut self) -> Result<()> {
+        self.child.wait()?;
+        Ok(())
+    }
+}
+
+impl Drop for Validator {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,155 @@
+//! Reading the actual backing from the chain
+
+use std::str::FromStr;
+
+use anchor_lang::Discriminator;
+use anyhow::{Context, Result};
+use dac_sdk::accounts::CollateralType;
+use dac_sdk::pda;
+use serde::Serialize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::UiTransactionEncoding;
+
+/// The vaults, the DAC supply and the shards' totals as of `slot`
+#[derive(Clone, Copy, Serialize)]
+pub struct Backing {
+    pub slot: u64,
+    /// USDC in the vault shards and the config vault they took over
+    pub vault: u64,
+    pub supply: u64,
+    /// Sum of the shards' `wrapped`, or the config's total before there were shards
+    pub total_wrapped: u64,
+    /// DAC minted into the shards' floats and not issued yet
+    pub unissued: u64,
+    /// DAC minted against volatile collateral, outside the USDC backing
+    pub collateral_debt: u64,
+}
+
+impl Backing {
+    /// DAC issued against the USDC vaults
+    pub fn wrapped_supply(&self) -> u64 {
+        self.supply.saturating_sub(self.unissued).saturating_sub(self.collateral_debt)
+    }
+}
+
+/// The backing now
+pub async fn live(rpc: &RpcClient) -> Result<Backing> {
+    let slot = rpc.get_slot().await?;
+    let config = dac_sdk::accounts::dac_config(&rpc.get_account_data(&pda::dac_config().0).await?)?;
+    let mut vault = balance(rpc, &config.vault).await?;
+    let supply = rpc.get_token_supply(&config.dac_mint).await?.amount.parse()?;
+    let mut total_wrapped = if config.shard_count == 0 { config.total_wrapped } else { 0 };
+    let mut unissued = 0u64;
+    for index in 0..config.shard_count {
+        let shard = dac_sdk::accounts::vault_shard(&rpc.get_account_data(&pda::vault_shard(index).0).await?)?;
+        vault = vault.saturating_add(balance(rpc, &shard.usdc_vault).await?);
+        let float = balance(rpc, &shard.dac_float).await?.saturating_sub(shard.fees);
+        unissued = unissued.saturating_add(float);
+        total_wrapped = total_wrapped.saturating_add(shard.wrapped);
+    }
+    let collateral_debt = collateral_debt(rpc).await?;
+    Ok(Backing { slot, vault, supply, total_wrapped, unissued, collateral_debt })
+}
+
+/// Balance of the token account `address`
+async fn balance(rpc: &RpcClient, address: &Pubkey) -> Result<u64> {
+    Ok(rpc.get_token_account_balance(address).await?.amount.parse()?)
+}
+
+/// Debt of every collateral type
+async fn collateral_debt(rpc: &RpcClient) -> Result<u64> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            CollateralType::DISCRIMINATOR.to_vec(),
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = rpc
+        .get_program_accounts_with_config(&dac_sdk::DAC_TOKEN_PROGRAM_ID, config)
+        .await?;
+    accounts.iter().try_fold(0u64, |total, (_, account)| {
+        let collateral = dac_sdk::accounts::collateral_type(&account.data)?;
+        Ok(total.saturating_add(collateral.total_debt))
+    })
+}
+
+/// Balance of the USDC vault of `shard` right after the transaction `signature`
+///
+/// Read from the transaction's own token balances, so it works for any slot
+/// the RPC node still has the transaction for. Without a shard it is the
+/// config vault, which held the backing before there were shards.
+pub async fn vault_after(rpc: &RpcClient, signature: &str, shard: Option<u8>) -> Result<Option<u64>> {
+    let vault = match shard {
+        Some(index) => pda::shard_usdc_vault(index).0,
+        None => dac_sdk::accounts::dac_config(&rpc.get_account_data(&pda::dac_config().0).await?)?.vault,
+    };
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let tx = rpc
+        .get_transaction_with_config(&Signature::from_str(signature)?, config)
+        .await
+        .with_context(|| format!("failed to fetch {}", signature))?;
+    let (Some(meta), Some(transaction)) = (tx.transaction.meta, tx.transaction.transaction.decode()) else {
+        return Ok(None);
+    };
+
+    // Balances refer to accounts by index, counting those loaded from lookup tables last
+    let mut keys: Vec<_> = transaction.message.static_account_keys().iter().map(Pubkey::to_string).collect();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        keys.extend(loaded.writable.iter().chain(&loaded.readonly).cloned());
+    }
+    let vault = vault.to_string();
+    let balances: Option<Vec<_>> = meta.post_token_balances.into();
+    let balance = balances
+        .unwrap_or_default()
+        .into_iter()
+        .find(|balance| keys.get(balance.account_index as usize) == Some(&vault));
+    balance.map(|balance| balance.ui_token_amount.amount.parse().map_err(Into::into)).transpose()
+}
+
+/// Last slot with a block produced before the unix time `time`
+///
+/// Binary searches for the highest slot whose next block is still before
+/// `time`, which is then a block itself.
+pub async fn last_slot_before(rpc: &RpcClient, time: i64) -> Result<u64> {
+    let mut low = rpc.get_first_available_block().await?;
+    let mut high = rpc.get_slot().await?;
+    anyhow::ensure!(
+        block_before(rpc, low, high, time).await?,
+        "no block before {} is available",
+        time
+    );
+    while low < high {
+        let mid = low + (high - low + 1) / 2;
+        if block_before(rpc, mid, high, time).await? {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Ok(low)
+}
+
+/// Whether the first block from `slot` on, up to `limit`, was produced before `time`
+async fn block_before(rpc: &RpcClient, slot: u64, limit: u64, time: i64) -> Result<bool> {
+    let Some(&block) = rpc.get_blocks_with_limit(slot, 1).await?.first() else { return Ok(false) };
+    if block > limit {
+        return Ok(false);
+    }
+    Ok(rpc.get_block_time(block).await? < time)
+}
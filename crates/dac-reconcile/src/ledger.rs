@@ -0,0 +1,236 @@
+//! Replaying the indexed wraps and unwraps
+//!
+//! A wrap of `amount` USDC moves it into a vault shard and issues `amount`
+//! DAC from the shard's float, `fee` of it kept there for the fee vault. An
+//! unwrap of `amount` DAC returns it to the float, `fee` of it again kept,
+//! against `amount - fee` USDC from the shard's vault. So a shard's vault
+//! balance and its `wrapped` move together, and across shards so does the
+//! DAC issued: USDC wrapped less USDC redeemed. An unwrap drawing on further
+//! shards emits one event per shard, the fee all in the first.
+//!
+//! Events from before the shards went through the single config vault,
+//! which the first shard took over along with its backing; they replay as
+//! that shard's.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tokio_postgres::Client;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Wrap,
+    Unwrap,
+}
+
+/// One indexed wrap or unwrap
+#[derive(Clone, Serialize)]
+pub struct Event {
+    pub slot: u64,
+    /// Unix time, unknown for transactions the indexer saw over the websocket
+    pub block_time: Option<i64>,
+    pub signature: String,
+    pub event_index: u32,
+    pub wallet: String,
+    pub kind: Kind,
+    pub amount: u64,
+    pub fee: u64,
+    /// Vault shard the event went through, none for events from before the shards
+    pub shard: Option<u8>,
+    /// The shard's `wrapped` after the event, or the program's total for events
+    /// from before the shards, unknown for rows indexed before either was emitted
+    pub shard_wrapped: Option<u64>,
+}
+
+impl Event {
+    /// USDC leaving the vault (zero for wraps)
+    pub fn redeemed(&self) -> u64 {
+        match self.kind {
+            Kind::Wrap => 0,
+            Kind::Unwrap => self.amount - self.fee,
+        }
+    }
+
+    /// Shard whose backing the event moved, the first for events from before the shards
+    pub fn backing_shard(&self) -> u8 {
+        self.shard.unwrap_or(0)
+    }
+
+    /// USDC the event moved into its shard's vault, negative for unwraps
+    fn delta(&self) -> i128 {
+        match self.kind {
+            Kind::Wrap => self.amount as i128,
+            Kind::Unwrap => -(self.redeemed() as i128),
+        }
+    }
+}
+
+/// Every indexed wrap and unwrap up to and including `until_slot`, in chain order
+///
+/// Transactions of the same slot are ordered as they were indexed, which
+/// need not be the order they executed in.
+pub async fn load(client: &Client, until_slot: u64) -> Result<Vec<Event>> {
+    let rows = client
+        .query(
+            "SELECT t.slot, t.block_time, t.signature, e.event_index, e.wallet, e.kind, e.amount, e.fee,
+                    e.shard, COALESCE(e.shard_wrapped, e.total_wrapped) AS shard_wrapped
+             FROM wraps e JOIN indexed_transactions t USING (signature)
+             WHERE t.slot <= $1
+             ORDER BY t.slot, t.seq, e.event_index",
+            &[&(until_slot as i64)],
+        )
+        .await
+        .context("failed to read wraps")?;
+    Ok(rows
+        .iter()
+        .map(|row| Event {
+            slot: row.get::<_, i64>("slot") as u64,
+            block_time: row.get("block_time"),
+            signature: row.get("signature"),
+            event_index: row.get::<_, i32>("event_index") as u32,
+            wallet: row.get("wallet"),
+            kind: if row.get::<_, &str>("kind") == "wrap" { Kind::Wrap } else { Kind::Unwrap },
+            amount: row.get::<_, i64>("amount") as u64,
+            fee: row.get::<_, i64>("fee") as u64,
+            shard: row.get::<_, Option<i16>>("shard").map(|shard| shard as u8),
+            shard_wrapped: row.get::<_, Option<i64>>("shard_wrapped").map(|total| total as u64),
+        })
+        .collect())
+}
+
+/// Highest slot indexed so far
+pub async fn indexed_through(client: &Client) -> Result<u64> {
+    let row = client
+        .query_one("SELECT COALESCE(MAX(slot), 0) FROM indexed_transactions", &[])
+        .await?;
+    Ok(row.get::<_, i64>(0) as u64)
+}
+
+/// Flows summed over the replayed events
+#[derive(Default, Clone, Copy, Serialize)]
+pub struct Totals {
+    pub wraps: u64,
+    pub unwraps: u64,
+    /// USDC deposited by wraps
+    pub wrapped: u128,
+    /// USDC paid out by unwraps
+    pub redeemed: u128,
+    /// DAC kept for the fee vault by both
+    pub fees: u128,
+}
+
+impl Totals {
+    fn apply(&mut self, event: &Event) {
+        match event.kind {
+            Kind::Wrap => {
+                self.wraps += 1;
+                self.wrapped += event.amount as u128;
+            }
+            Kind::Unwrap => {
+                self.unwraps += 1;
+                self.redeemed += event.redeemed() as u128;
+            }
+        }
+        self.fees += event.fee as u128;
+    }
+
+    /// Expected balance of the vaults, DAC issued and `total_wrapped`
+    ///
+    /// Negative when unwraps outweigh the wraps indexed before them.
+    pub fn backing(&self) -> i128 {
+        self.wrapped as i128 - self.redeemed as i128
+    }
+}
+
+/// An event with the expected backing after it, in total and in its shard
+#[derive(Clone)]
+pub struct Entry {
+    pub event: Event,
+    pub backing: i128,
+    pub shard_backing: i128,
+}
+
+/// Where a shard's reported `wrapped` stopped matching the replay
+#[derive(Clone, Serialize)]
+pub struct Divergence {
+    pub slot: u64,
+    pub shard: u8,
+    /// Event reporting the shard's total
+    pub signature: String,
+    pub expected: i128,
+    pub actual: u64,
+}
+
+/// The transaction whose balances close the replay
+#[derive(Clone)]
+pub struct Closing {
+    pub signature: String,
+    /// Shard of its event, whose vault balance is checked
+    pub shard: Option<u8>,
+}
+
+pub struct Replay {
+    pub entries: Vec<Entry>,
+    pub totals: Totals,
+    /// Expected backing of each shard the events went through
+    pub shards: BTreeMap<u8, i128>,
+    pub divergences: Vec<Divergence>,
+    pub closing: Option<Closing>,
+}
+
+/// Replay `events` and check them against the totals the program reported
+///
+/// Events are checked a slot and a shard at a time, since the order within
+/// a slot is unknown: the shard's last event of the slot reports its total
+/// after all of them. A divergence is reported once, where it first
+/// appears, rather than at every later slot.
+pub fn replay(events: &[Event]) -> Replay {
+    let mut totals = Totals::default();
+    let mut shards = BTreeMap::new();
+    let mut entries = Vec::with_capacity(events.len());
+    let mut divergences = Vec::new();
+    let mut closing = None;
+    let mut offsets = BTreeMap::new();
+
+    for slot in events.chunk_by(|a, b| a.slot == b.slot) {
+        for event in slot {
+            totals.apply(event);
+            let shard_backing = shards.entry(event.backing_shard()).or_insert(0);
+            *shard_backing += event.delta();
+            let backing = totals.backing();
+            entries.push(Entry { event: event.clone(), backing, shard_backing: *shard_backing });
+        }
+        let last = slot.last().expect("chunks are not empty");
+        closing = Some(Closing { signature: last.signature.clone(), shard: last.shard });
+
+        let mut reported: Vec<_> = slot.iter().filter(|event| event.shard_wrapped.is_some()).collect();
+        reported.sort_by_key(|event| event.backing_shard());
+        for shard in reported.chunk_by(|a, b| a.backing_shard() == b.backing_shard()) {
+            let index = shard[0].backing_shard();
+            let expected = shards[&index];
+            let event = shard
+                .iter()
+                .find(|event| event.shard_wrapped.map(i128::from) == Some(expected))
+                .unwrap_or(shard.last().expect("chunks are not empty"));
+            let actual = event.shard_wrapped.expect("filtered on reported totals");
+            closing = Some(Closing { signature: event.signature.clone(), shard: event.shard });
+
+            let drift = actual as i128 - expected;
+            let offset = offsets.entry(index).or_insert(0);
+            if drift != *offset && drift != 0 {
+                divergences.push(Divergence {
+                    slot: event.slot,
+                    shard: index,
+                    signature: event.signature.clone(),
+                    expected,
+                    actual,
+                });
+            }
+            *offset = drift;
+        }
+    }
+
+    Replay { entries, totals, shards, divergences, closing }
+}
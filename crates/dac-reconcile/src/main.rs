@@ -0,0 +1,161 @@
+//! DAC vault reconciliation
+//!
+//! Replays every wrap and unwrap `dac-indexer` has stored, recomputes the
+//! USDC each vault shard should hold and the DAC that should be outstanding,
+//! and checks them against the chain:
+//! - the shard's `wrapped` the program reported with each event
+//! - the shard's vault balance after the last transaction the report covers
+//! - the live vaults, supply and shards, when the report runs up to now
+//!
+//! DAC minted against volatile collateral is backed by its own vaults, and
+//! DAC in the shards' floats is not issued yet, so the live supply is
+//! checked net of both.
+//!
+//! The result is exported as a JSON report and a CSV ledger of every event,
+//! and the exit code is non-zero when anything disagrees, so a monthly job
+//! can both archive the proof of backing and alert on it.
+
+mod chain;
+mod ledger;
+mod report;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use tokio_postgres::NoTls;
+
+use crate::report::Report;
+
+#[derive(Parser)]
+#[clap(name = "dac-reconcile", version, about = "Reconciles the DAC vault against its wrap history")]
+struct Args {
+    /// RPC endpoint
+    #[clap(long, env = "RPC_URL", default_value = "https://api.devnet.solana.com")]
+    rpc_url: String,
+
+    /// Postgres connection string of the indexer database
+    #[clap(long, env = "DATABASE_URL")]
+    database_url: String,
+
+    /// Reconcile up to and including this slot instead of now
+    #[clap(long, conflicts_with = "month")]
+    slot: Option<u64>,
+
+    /// Reconcile up to the end of this month (YYYY-MM, UTC) instead of now
+    #[clap(long)]
+    month: Option<String>,
+
+    /// Write the report as JSON to this file instead of stdout
+    #[clap(long)]
+    json: Option<PathBuf>,
+
+    /// Write the ledger of replayed events as CSV to this file
+    #[clap(long)]
+    csv: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    match run(Args::parse()).await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            log::error!("{:#}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Make and export the report, returning whether it reconciled
+async fn run(args: Args) -> Result<bool> {
+    let rpc = RpcClient::new(args.rpc_url);
+    let (client, connection) = tokio_postgres::connect(&args.database_url, NoTls)
+        .await
+        .context("failed to connect to Postgres")?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            log::error!("Postgres connection closed: {}", e);
+        }
+    });
+
+    let indexed_through = ledger::indexed_through(&client).await?;
+    let until = args.month.as_deref().map(month_end).transpose()?;
+    let slot = match (args.slot, until) {
+        (Some(slot), _) => slot,
+        (None, Some(until)) => chain::last_slot_before(&rpc, until).await?,
+        (None, None) => indexed_through,
+    };
+    if slot > indexed_through {
+        log::warn!("Indexer has only reached slot {}, events up to {} may be missing", indexed_through, slot);
+    }
+
+    let events = ledger::load(&client, slot).await?;
+    let replay = ledger::replay(&events);
+    log::info!(
+        "Replayed {} wraps and {} unwraps up to slot {}",
+        replay.totals.wraps,
+        replay.totals.unwraps,
+        slot
+    );
+    let vault = match &replay.closing {
+        Some(closing) => chain::vault_after(&rpc, &closing.signature, closing.shard).await?,
+        None => None,
+    };
+    let live = if args.slot.is_none() && until.is_none() { Some(chain::live(&rpc).await?) } else { None };
+
+    let mut report = Report {
+        slot,
+        until,
+        indexed_through,
+        totals: replay.totals,
+        expected: replay.totals.backing(),
+        shards: replay.shards,
+        vault_shard: replay.closing.and_then(|closing| closing.shard),
+        vault,
+        live,
+        divergences: replay.divergences,
+        reconciled: false,
+    };
+    let mismatches = report.mismatches();
+    report.reconciled = mismatches.is_empty();
+    for mismatch in &mismatches {
+        log::warn!("Mismatch: {}", mismatch);
+    }
+    if report.reconciled {
+        log::info!("Backing of {} reconciled", report.expected);
+    }
+
+    match &args.json {
+        Some(path) => report.write_json(path)?,
+        None => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+    if let Some(path) = &args.csv {
+        report::write_csv(&replay.entries, path)?;
+    }
+    Ok(report.reconciled)
+}
+
+/// Unix time the month `YYYY-MM` ends at (the start of the next one, UTC)
+fn month_end(month: &str) -> Result<i64> {
+    let Some((year, month)) = month.split_once('-') else { bail!("month must be YYYY-MM, got {}", month) };
+    let (year, month): (i64, i64) = (year.parse()?, month.parse()?);
+    if !(1..=12).contains(&month) {
+        bail!("month must be between 01 and 12, got {}", month);
+    }
+    let (year, month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    Ok(days_from_civil(year, month, 1) * 86_400)
+}
+
+/// Days from 1970-01-01 to the given date of the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
@@ -0,0 +1,118 @@
+//! The audit report and its exports
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::chain::Backing;
+use crate::ledger::{Divergence, Entry, Kind, Totals};
+
+/// Proof of backing as of one slot
+#[derive(Serialize)]
+pub struct Report {
+    /// Last slot whose events are included
+    pub slot: u64,
+    /// Unix time the report covers up to, for monthly reports
+    pub until: Option<i64>,
+    /// Highest slot the indexer had reached when the report was made
+    pub indexed_through: u64,
+    pub totals: Totals,
+    /// Balance of the vaults, DAC issued and `total_wrapped` implied by the events
+    pub expected: i128,
+    /// Backing implied for each vault shard, the first including the config vault's
+    pub shards: BTreeMap<u8, i128>,
+    /// Shard of the last included transaction, none for the config vault
+    pub vault_shard: Option<u8>,
+    /// Balance of that shard's vault after the transaction
+    pub vault: Option<u64>,
+    /// The backing read live, when the report runs up to the present
+    pub live: Option<Backing>,
+    pub divergences: Vec<Divergence>,
+    /// Every check that could be made agreed
+    pub reconciled: bool,
+}
+
+impl Report {
+    /// Mismatches between the replay and what the chain holds, for the log
+    pub fn mismatches(&self) -> Vec<String> {
+        let mut mismatches: Vec<_> = self
+            .divergences
+            .iter()
+            .map(|d| {
+                format!(
+                    "shard {} wrapped {} at slot {} ({}), expected {}",
+                    d.shard, d.actual, d.slot, d.signature, d.expected
+                )
+            })
+            .collect();
+        let shard = self.vault_shard.unwrap_or(0);
+        let expected = self.shards.get(&shard).copied().unwrap_or(0);
+        if let Some(vault) = self.vault.filter(|vault| *vault as i128 != expected) {
+            mismatches.push(format!(
+                "shard {} vault held {} at slot {}, expected {}",
+                shard, vault, self.slot, expected
+            ));
+        }
+        if let Some(live) = &self.live {
+            let backed = [
+                ("vault", live.vault),
+                ("supply less floats and collateral debt", live.wrapped_supply()),
+                ("total_wrapped", live.total_wrapped),
+            ];
+            for (name, actual) in backed {
+                if actual as i128 != self.expected {
+                    mismatches.push(format!("live {} is {} at slot {}, expected {}", name, actual, live.slot, self.expected));
+                }
+            }
+        }
+        mismatches
+    }
+
+    pub fn write_json(&self, path: &Path) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.write_all(b"\n")?;
+        Ok(writer.flush()?)
+    }
+}
+
+/// Write the replayed events as CSV, one row per event with the expected backings after it
+pub fn write_csv(entries: &[Entry], path: &Path) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(
+        writer,
+        "slot,block_time,signature,event_index,wallet,kind,amount,fee,usdc_in,usdc_out,shard,\
+         expected_backing,expected_shard_backing,reported_shard_wrapped"
+    )?;
+    for Entry { event, backing, shard_backing } in entries {
+        let usdc_in = if event.kind == Kind::Wrap { event.amount } else { 0 };
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            event.slot,
+            optional(event.block_time),
+            event.signature,
+            event.event_index,
+            event.wallet,
+            if event.kind == Kind::Wrap { "wrap" } else { "unwrap" },
+            event.amount,
+            event.fee,
+            usdc_in,
+            event.redeemed(),
+            optional(event.shard),
+            backing,
+            shard_backing,
+            optional(event.shard_wrapped),
+        )?;
+    }
+    Ok(writer.flush()?)
+}
+
+/// Empty for unknown values
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
@@ -0,0 +1,89 @@
+//! Account deserializers
+//!
+//! Account data is checked against the Anchor discriminator of the expected
+//! type before it is decoded.
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::{AccountDeserialize, Result};
+
+pub use anchor_spl::token::{Mint, TokenAccount};
+pub use dac_token::attestation::AttestationRequirement;
+pub use dac_token::collateral::{CollateralPosition, CollateralType};
+pub use dac_token::shard::VaultShard;
+pub use dac_token::DacConfig;
+pub use dark_market::state::{
+    LimitOrder, LookupTableRegistry, Market, MarketConfig, MarketStatus, OrderSide, Outcome, Pool, Portfolio,
+    PositionTree, ProtocolLookupTable, RebatePool, ReceiptTree, ResolutionProposal, ResolutionStatus,
+};
+
+/// Decode raw account data as `T`
+pub fn deserialize<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+pub fn dac_config(data: &[u8]) -> Result<DacConfig> {
+    deserialize(data)
+}
+
+pub fn vault_shard(data: &[u8]) -> Result<VaultShard> {
+    deserialize(data)
+}
+
+pub fn collateral_type(data: &[u8]) -> Result<CollateralType> {
+    deserialize(data)
+}
+
+pub fn collateral_position(data: &[u8]) -> Result<CollateralPosition> {
+    deserialize(data)
+}
+
+pub fn market_config(data: &[u8]) -> Result<MarketConfig> {
+    deserialize(data)
+}
+
+/// Decode a market
+///
+/// Zero-copy accounts are cast rather than deserialized, so data of any
+/// other size is rejected instead of being misread.
+pub fn market(data: &[u8]) -> Result<Market> {
+    if data.len() != 8 + Market::LEN {
+        return Err(ErrorCode::AccountDidNotDeserialize.into());
+    }
+    deserialize(data)
+}
+
+pub fn pool(data: &[u8]) -> Result<Pool> {
+    deserialize(data)
+}
+
+pub fn portfolio(data: &[u8]) -> Result<Portfolio> {
+    deserialize(data)
+}
+
+pub fn lookup_table_registry(data: &[u8]) -> Result<LookupTableRegistry> {
+    deserialize(data)
+}
+
+pub fn position_tree(data: &[u8]) -> Result<PositionTree> {
+    deserialize(data)
+}
+
+pub fn protocol_lookup_table(data: &[u8]) -> Result<ProtocolLookupTable> {
+    deserialize(data)
+}
+
+pub fn receipt_tree(data: &[u8]) -> Result<ReceiptTree> {
+    deserialize(data)
+}
+
+pub fn token_account(data: &[u8]) -> Result<TokenAccount> {
+    deserialize(data)
+}
+
+pub fn resolution_proposal(data: &[u8]) -> Result<ResolutionProposal> {
+    deserialize(data)
+}
+
+pub fn mint(data: &[u8]) -> Result<Mint> {
+    deserialize(data)
+}
@@ -0,0 +1,273 @@
+//! High-level flows
+//!
+//! Each flow returns the instructions of one transaction, in order, creating
+//! the user's associated token accounts on the way when they are missing.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::rent::Rent;
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::associated_token::get_associated_token_address;
+use dac_token::shard::VaultShard;
+use dac_token::DacConfig;
+use dark_market::{bubblegum, compression};
+use dark_market::state::{Market, Outcome};
+
+use crate::instructions::{self, PositionAccounts, SwapAccounts};
+use crate::pda;
+
+/// Create `owner`'s associated token account of `mint` unless it exists, paid by `owner`
+fn create_ata(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    instructions::create_associated_token_account(owner, owner, mint)
+}
+
+/// The vault shard `user` wraps through and first unwraps from
+///
+/// Spreading users over the shards by address keeps their wraps and
+/// unwraps from write-locking the same accounts.
+pub fn home_shard(user: &Pubkey, config: &DacConfig) -> u8 {
+    let bytes = user.to_bytes();
+    let key = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    (key % config.shard_count.max(1) as u32) as u8
+}
+
+/// Wrap `amount` of the user's USDC into DAC
+pub fn wrap(user: &Pubkey, config: &DacConfig, amount: u64) -> Vec<Instruction> {
+    vec![
+        create_ata(user, &config.dac_mint),
+        instructions::wrap(
+            user,
+            &get_associated_token_address(user, &config.usdc_mint),
+            &get_associated_token_address(user, &config.dac_mint),
+            home_shard(user, config),
+            instructions::required_attestation(&config.wrap_attestation, user),
+            amount,
+        ),
+    ]
+}
+
+/// A swap into USDC as returned by Jupiter's swap instructions API
+pub struct JupiterSwap {
+    /// Instructions to run first (creating token accounts, wrapping SOL)
+    pub setup: Vec<Instruction>,
+    /// The route, paying out to the user's USDC associated token account
+    pub route: Instruction,
+    /// Instruction to run last (unwrapping leftover SOL), if any
+    pub cleanup: Option<Instruction>,
+}
+
+/// Swap any token into USDC through Jupiter and wrap the output into DAC
+///
+/// Routes often need the address lookup tables Jupiter returns with them to
+/// fit in one transaction.
+pub fn swap_and_wrap(user: &Pubkey, config: &DacConfig, swap: &JupiterSwap, min_usdc: u64) -> Vec<Instruction> {
+    let mut ixs = swap.setup.clone();
+    ixs.push(create_ata(user, &config.usdc_mint));
+    ixs.push(create_ata(user, &config.dac_mint));
+    ixs.push(instructions::swap_and_wrap(
+        user,
+        &get_associated_token_address(user, &config.usdc_mint),
+        &get_associated_token_address(user, &config.dac_mint),
+        home_shard(user, config),
+        instructions::required_attestation(&config.wrap_attestation, user),
+        &swap.route,
+        min_usdc,
+    ));
+    ixs.extend(swap.cleanup.clone());
+    ixs
+}
+
+/// Unwrap `amount` of the user's DAC into USDC
+///
+/// `shards` are the config's vault shards in index order. The user's home
+/// shard pays out first; when it holds too little USDC, the rest comes from
+/// as many of the other shards as it takes, in index order, so only those
+/// are locked. Whatever they cannot cover together fails the transaction.
+pub fn unwrap(user: &Pubkey, config: &DacConfig, shards: &[VaultShard], amount: u64) -> Vec<Instruction> {
+    let home = home_shard(user, config);
+    let home_wrapped = shards.get(home as usize).map_or(0, |shard| shard.wrapped);
+    let mut remaining = (amount - config.fee(amount)).saturating_sub(home_wrapped);
+    let mut extra_shards = Vec::new();
+    for shard in shards.iter().filter(|shard| shard.index != home) {
+        if remaining == 0 {
+            break;
+        }
+        extra_shards.push(shard.index);
+        remaining = remaining.saturating_sub(shard.wrapped);
+    }
+    vec![
+        create_ata(user, &config.usdc_mint),
+        instructions::unwrap(
+            user,
+            &get_associated_token_address(user, &config.dac_mint),
+            &get_associated_token_address(user, &config.usdc_mint),
+            home,
+            &extra_shards,
+            amount,
+        ),
+    ]
+}
+
+/// Open `count` more vault shards with floats of `float` DAC, on top of the config's `shard_count`
+///
+/// Each shard is restocked as it is opened, so it takes wraps right away.
+/// A transaction fits a few shards; open more over several.
+pub fn open_vault_shards(authority: &Pubkey, config: &DacConfig, count: u8, float: u64) -> Vec<Instruction> {
+    let mut ixs = vec![instructions::set_shard_float(authority, float)];
+    for index in config.shard_count..config.shard_count + count {
+        ixs.push(instructions::add_vault_shard(authority, &config.dac_mint, &config.usdc_mint, index));
+        ixs.push(instructions::restock_vault_shard(&config.dac_mint, index, None));
+    }
+    ixs
+}
+
+/// Create the next market, returning its address with the instructions
+///
+/// `market_count` is the market config's current count; a market created by
+/// someone else first takes that id and this transaction fails.
+pub fn create_market(
+    creator: &Pubkey,
+    dac_mint: &Pubkey,
+    market_count: u64,
+    question: String,
+    end_time: i64,
+    resolver: Pubkey,
+) -> (Pubkey, Vec<Instruction>) {
+    let market = pda::market(market_count).0;
+    let ix = instructions::create_market(creator, dac_mint, market_count, question, end_time, resolver);
+    (market, vec![ix])
+}
+
+/// A bet on one outcome of a market
+pub struct Bet {
+    pub outcome: Outcome,
+    /// DAC spent
+    pub amount_in: u64,
+    /// Fewest outcome tokens accepted
+    pub min_amount_out: u64,
+    /// Last slot the trade may land in
+    pub expiry_slot: u64,
+}
+
+/// Bet DAC on an outcome by buying it from the market's pool
+///
+/// `fee_vault` is the stakers' fee vault from the fee sharing account, and
+/// `rebate_vault` the pool's rebate vault once maker rebates are configured.
+/// Returns `None` for a bet on `Outcome::Invalid`, which has no token.
+pub fn bet(
+    user: &Pubkey,
+    dac_mint: &Pubkey,
+    market_key: &Pubkey,
+    market: &Market,
+    fee_vault: Pubkey,
+    rebate_vault: Option<Pubkey>,
+    bet: Bet,
+) -> Option<Vec<Instruction>> {
+    let outcome_mint = market.outcome_mint(bet.outcome)?;
+    let swap = SwapAccounts {
+        user: *user,
+        user_dac: get_associated_token_address(user, dac_mint),
+        user_outcome: get_associated_token_address(user, &outcome_mint),
+        fee_vault,
+        track_portfolio: false,
+        rebate_vault,
+    };
+    Some(vec![
+        create_ata(user, &outcome_mint),
+        instructions::buy(
+            market_key,
+            market,
+            &swap,
+            bet.outcome,
+            bet.amount_in,
+            bet.min_amount_out,
+            bet.expiry_slot,
+        ),
+    ])
+}
+
+/// Redeem the user's outcome tokens of a resolved market for DAC
+pub fn redeem(user: &Pubkey, dac_mint: &Pubkey, market_key: &Pubkey, market: &Market) -> Vec<Instruction> {
+    let position = PositionAccounts {
+        user: *user,
+        user_dac: get_associated_token_address(user, dac_mint),
+        user_yes: get_associated_token_address(user, &market.yes_mint),
+        user_no: get_associated_token_address(user, &market.no_mint),
+        track_portfolio: false,
+    };
+    vec![
+        create_ata(user, dac_mint),
+        create_ata(user, &market.yes_mint),
+        create_ata(user, &market.no_mint),
+        instructions::redeem(market_key, market, &position),
+    ]
+}
+
+/// Size of a concurrent Merkle tree of compressed receipts or positions
+pub struct TreeSize {
+    /// Depth of the tree, holding up to 2^depth leaves
+    pub max_depth: u32,
+    /// Concurrent changes the tree accepts against one root, at least 64 for position trees
+    pub max_buffer_size: u32,
+    /// Levels of the tree kept on chain, shortening every proof by as many nodes
+    pub canopy_depth: u32,
+}
+
+impl TreeSize {
+    /// Bytes of the concurrent Merkle tree account: header, change log buffer, rightmost path and canopy
+    pub fn account_len(&self) -> usize {
+        compression::tree_account_len(self.max_depth, self.max_buffer_size, self.canopy_depth)
+    }
+}
+
+/// Allocate `merkle_tree`, a fresh keypair signing the transaction, and give the market its receipt tree
+pub fn enable_compressed_receipts(
+    creator: &Pubkey,
+    market_key: &Pubkey,
+    market: &Market,
+    merkle_tree: &Pubkey,
+    size: &TreeSize,
+) -> Vec<Instruction> {
+    let space = size.account_len();
+    vec![
+        system_instruction::create_account(
+            creator,
+            merkle_tree,
+            Rent::default().minimum_balance(space),
+            space as u64,
+            &bubblegum::ACCOUNT_COMPRESSION_PROGRAM_ID,
+        ),
+        instructions::enable_compressed_receipts(
+            creator,
+            market_key,
+            market,
+            merkle_tree,
+            size.max_depth,
+            size.max_buffer_size,
+        ),
+    ]
+}
+
+/// Allocate `merkle_tree`, a fresh keypair signing the transaction, and give the market its position tree
+///
+/// The canopy of `size` is fixed for the market's lifetime: every later
+/// update and redemption passes `max_depth - canopy_depth` proof nodes.
+pub fn enable_position_tree(
+    creator: &Pubkey,
+    market_key: &Pubkey,
+    market: &Market,
+    merkle_tree: &Pubkey,
+    size: &TreeSize,
+) -> Vec<Instruction> {
+    let space = size.account_len();
+    vec![
+        system_instruction::create_account(
+            creator,
+            merkle_tree,
+            Rent::default().minimum_balance(space),
+            space as u64,
+            &bubblegum::ACCOUNT_COMPRESSION_PROGRAM_ID,
+        ),
+        instructions::enable_position_tree(creator, market_key, market, merkle_tree, size),
+    ]
+}
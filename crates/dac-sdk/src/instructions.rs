@@ -0,0 +1,1441 @@
+//! Typed instruction builders
+//!
+//! Each builder derives the PDAs the instruction needs and takes the rest
+//! (wallets, token accounts, state read from the chain) as arguments.
+//! Optional accounts are left out unless passed.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::address_lookup_table;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token::ID as TOKEN_PROGRAM_ID;
+use dac_token::attestation::AttestationRequirement;
+use dark_market::bubblegum;
+use dark_market::state::{
+    FeeTier, LimitOrder, Market, Outcome, ResolutionProposal, SettledPosition, FEE_TIER_COUNT,
+};
+
+use crate::flows::TreeSize;
+use crate::pda;
+
+fn dac_token_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: dac_token::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn dark_market_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: dark_market::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Create `owner`'s associated token account of `mint` unless it exists
+pub fn create_associated_token_account(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    create_associated_token_account_idempotent(payer, owner, mint, &TOKEN_PROGRAM_ID)
+}
+
+// ============================================================================
+// DAC token
+// ============================================================================
+
+/// Set up the DAC config and USDC vault; the mint's authority must already be the mint authority PDA
+pub fn initialize_dac(authority: &Pubkey, dac_mint: &Pubkey, usdc_mint: &Pubkey) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::Initialize {
+            config: pda::dac_config().0,
+            dac_mint: *dac_mint,
+            usdc_mint: *usdc_mint,
+            usdc_vault: pda::usdc_vault().0,
+            mint_authority: pda::mint_authority().0,
+            vault_authority: pda::vault_authority().0,
+            authority: *authority,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        dac_token::instruction::Initialize {},
+    )
+}
+
+/// Grow a DAC config created by the first deployment to the current layout (DAC config authority only)
+///
+/// Also opens vault shard 0 over the unsharded USDC vault, with a float of `shard_float` DAC.
+pub fn migrate_dac_config(authority: &Pubkey, dac_mint: &Pubkey, usdc_mint: &Pubkey, shard_float: u64) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::MigrateConfig {
+            authority: *authority,
+            config: pda::dac_config().0,
+            shard: pda::vault_shard(0).0,
+            usdc_vault: pda::shard_usdc_vault(0).0,
+            dac_float: pda::shard_dac_float(0).0,
+            legacy_vault: pda::usdc_vault().0,
+            dac_mint: *dac_mint,
+            usdc_mint: *usdc_mint,
+            mint_authority: pda::mint_authority().0,
+            vault_authority: pda::vault_authority().0,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        dac_token::instruction::MigrateConfig { shard_float },
+    )
+}
+
+/// `user`'s attestation, when `requirement` asks for one
+pub fn required_attestation(requirement: &AttestationRequirement, user: &Pubkey) -> Option<Pubkey> {
+    requirement.is_required().then(|| pda::attestation(requirement, user).0)
+}
+
+fn wrap_accounts(
+    user: &Pubkey,
+    user_usdc: &Pubkey,
+    user_dac: &Pubkey,
+    shard: u8,
+    attestation: Option<Pubkey>,
+) -> dac_token::accounts::Wrap {
+    dac_token::accounts::Wrap {
+        config: pda::dac_config().0,
+        shard: pda::vault_shard(shard).0,
+        user_usdc: *user_usdc,
+        user_dac: *user_dac,
+        usdc_vault: pda::shard_usdc_vault(shard).0,
+        dac_float: pda::shard_dac_float(shard).0,
+        mint_authority: pda::mint_authority().0,
+        instructions: sysvar::instructions::ID,
+        attestation,
+        user: *user,
+        token_program: TOKEN_PROGRAM_ID,
+    }
+}
+
+/// Wrap `amount` USDC into DAC through vault shard `shard`
+///
+/// `attestation` is required while wraps require one. In compliance mode,
+/// large wraps must share their transaction with [`attach_travel_rule`].
+pub fn wrap(
+    user: &Pubkey,
+    user_usdc: &Pubkey,
+    user_dac: &Pubkey,
+    shard: u8,
+    attestation: Option<Pubkey>,
+    amount: u64,
+) -> Instruction {
+    dac_token_ix(
+        wrap_accounts(user, user_usdc, user_dac, shard, attestation),
+        dac_token::instruction::Wrap { amount },
+    )
+}
+
+/// Run the Jupiter `route` into `user_usdc` and wrap all of its output into DAC through vault shard `shard`
+///
+/// `route` is the swap instruction from Jupiter's swap API, with `user_usdc`
+/// as its destination; the wrap fails if it pays out less than `min_usdc`.
+pub fn swap_and_wrap(
+    user: &Pubkey,
+    user_usdc: &Pubkey,
+    user_dac: &Pubkey,
+    shard: u8,
+    attestation: Option<Pubkey>,
+    route: &Instruction,
+    min_usdc: u64,
+) -> Instruction {
+    let mut ix = dac_token_ix(
+        dac_token::accounts::SwapAndWrap {
+            wrap: wrap_accounts(user, user_usdc, user_dac, shard, attestation),
+            jupiter_program: route.program_id,
+        },
+        dac_token::instruction::SwapAndWrap { route_data: route.data.clone(), min_usdc },
+    );
+    ix.accounts.extend(route.accounts.iter().cloned());
+    ix
+}
+
+/// Unwrap `amount` DAC into USDC through vault shard `shard`
+///
+/// USDC the shard cannot cover comes from `extra_shards`, which must be in
+/// ascending index order and leave out `shard`.
+pub fn unwrap(
+    user: &Pubkey,
+    user_dac: &Pubkey,
+    user_usdc: &Pubkey,
+    shard: u8,
+    extra_shards: &[u8],
+    amount: u64,
+) -> Instruction {
+    let mut ix = dac_token_ix(
+        dac_token::accounts::Unwrap {
+            config: pda::dac_config().0,
+            shard: pda::vault_shard(shard).0,
+            user_dac: *user_dac,
+            user_usdc: *user_usdc,
+            usdc_vault: pda::shard_usdc_vault(shard).0,
+            dac_float: pda::shard_dac_float(shard).0,
+            vault_authority: pda::vault_authority().0,
+            instructions: sysvar::instructions::ID,
+            user: *user,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dac_token::instruction::Unwrap { amount },
+    );
+    for &index in extra_shards {
+        ix.accounts.push(AccountMeta::new(pda::vault_shard(index).0, false));
+        ix.accounts.push(AccountMeta::new(pda::shard_usdc_vault(index).0, false));
+        ix.accounts.push(AccountMeta::new(pda::shard_dac_float(index).0, false));
+    }
+    ix
+}
+
+/// Set the DAC each vault shard's float is restocked to (DAC config authority only)
+pub fn set_shard_float(authority: &Pubkey, float: u64) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::SetShardFloat {
+            config: pda::dac_config().0,
+            authority: *authority,
+        },
+        dac_token::instruction::SetShardFloat { float },
+    )
+}
+
+/// Open vault shard `index`, the config's next one (DAC config authority only)
+///
+/// The first shard takes over the USDC vault of the unsharded layout.
+pub fn add_vault_shard(authority: &Pubkey, dac_mint: &Pubkey, usdc_mint: &Pubkey, index: u8) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::AddVaultShard {
+            config: pda::dac_config().0,
+            shard: pda::vault_shard(index).0,
+            usdc_vault: pda::shard_usdc_vault(index).0,
+            dac_float: pda::shard_dac_float(index).0,
+            legacy_vault: (index == 0).then(|| pda::usdc_vault().0),
+            dac_mint: *dac_mint,
+            usdc_mint: *usdc_mint,
+            mint_authority: pda::mint_authority().0,
+            vault_authority: pda::vault_authority().0,
+            authority: *authority,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        dac_token::instruction::AddVaultShard {},
+    )
+}
+
+/// Pay vault shard `index`'s accrued fees and bring its float back to size
+///
+/// `fee_vault` is required while the shard has accrued fees.
+pub fn restock_vault_shard(dac_mint: &Pubkey, index: u8, fee_vault: Option<Pubkey>) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::RestockVaultShard {
+            config: pda::dac_config().0,
+            shard: pda::vault_shard(index).0,
+            dac_float: pda::shard_dac_float(index).0,
+            dac_mint: *dac_mint,
+            mint_authority: pda::mint_authority().0,
+            fee_vault,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dac_token::instruction::RestockVaultShard {},
+    )
+}
+
+/// Sum the config's `shard_count` vault shards into its `total_wrapped`
+pub fn sync_vault_shards(shard_count: u8) -> Instruction {
+    let mut ix = dac_token_ix(
+        dac_token::accounts::SyncVaultShards { config: pda::dac_config().0 },
+        dac_token::instruction::SyncVaultShards {},
+    );
+    ix.accounts
+        .extend((0..shard_count).map(|index| AccountMeta::new_readonly(pda::vault_shard(index).0, false)));
+    ix
+}
+
+/// Attach the travel-rule `payload`, encrypted to the compliance key, to a wrap or unwrap of up to `amount`
+///
+/// Required in the same transaction for wraps and unwraps from the
+/// config's compliance threshold up.
+pub fn attach_travel_rule(user: &Pubkey, amount: u64, payload: Vec<u8>) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::AttachTravelRule {
+            config: pda::dac_config().0,
+            user: *user,
+        },
+        dac_token::instruction::AttachTravelRule { amount, payload },
+    )
+}
+
+/// Change the wrap/unwrap fee (DAC config authority only)
+pub fn set_wrap_fee(authority: &Pubkey, fee_bps: u16, fee_vault: Pubkey) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::SetWrapFee {
+            config: pda::dac_config().0,
+            authority: *authority,
+        },
+        dac_token::instruction::SetWrapFee { fee_bps, fee_vault },
+    )
+}
+
+/// Require travel-rule payloads encrypted to `compliance_key` from `threshold` up (0 disables)
+pub fn set_compliance(authority: &Pubkey, threshold: u64, compliance_key: [u8; 32]) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::SetCompliance {
+            config: pda::dac_config().0,
+            authority: *authority,
+        },
+        dac_token::instruction::SetCompliance { threshold, compliance_key },
+    )
+}
+
+/// Require wraps to be bundled with a market interaction, or lift the requirement
+pub fn set_wrap_bundling(authority: &Pubkey, market_program: Pubkey, enabled: bool) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::SetWrapBundling {
+            config: pda::dac_config().0,
+            authority: *authority,
+        },
+        dac_token::instruction::SetWrapBundling { market_program, enabled },
+    )
+}
+
+/// Require wrappers to hold an attestation of `schema` from `credential`; a default schema lifts it
+pub fn set_wrap_attestation(authority: &Pubkey, credential: Pubkey, schema: Pubkey) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::SetWrapAttestation {
+            config: pda::dac_config().0,
+            authority: *authority,
+        },
+        dac_token::instruction::SetWrapAttestation { credential, schema },
+    )
+}
+
+/// Parameters of a collateral type
+pub struct CollateralParams {
+    /// Pyth feed pricing the collateral in USD
+    pub price_feed_id: [u8; 32],
+    /// Oldest price accepted, in seconds
+    pub max_price_age: i64,
+    pub haircut_bps: u16,
+    pub liquidation_bonus_bps: u16,
+    pub debt_ceiling: u64,
+}
+
+/// Accept `collateral_mint` as collateral or update its parameters (authority only)
+pub fn configure_collateral(authority: &Pubkey, collateral_mint: &Pubkey, params: CollateralParams) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::ConfigureCollateral {
+            config: pda::dac_config().0,
+            collateral_type: pda::collateral_type(collateral_mint).0,
+            collateral_vault: pda::collateral_vault(collateral_mint).0,
+            collateral_mint: *collateral_mint,
+            authority: *authority,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        dac_token::instruction::ConfigureCollateral {
+            price_feed_id: params.price_feed_id,
+            max_price_age: params.max_price_age,
+            haircut_bps: params.haircut_bps,
+            liquidation_bonus_bps: params.liquidation_bonus_bps,
+            debt_ceiling: params.debt_ceiling,
+        },
+    )
+}
+
+/// Deposit `amount` of `owner_collateral` into the owner's position
+pub fn deposit_collateral(owner: &Pubkey, collateral_mint: &Pubkey, owner_collateral: &Pubkey, amount: u64) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::DepositCollateral {
+            collateral_type: pda::collateral_type(collateral_mint).0,
+            position: pda::collateral_position(collateral_mint, owner).0,
+            collateral_vault: pda::collateral_vault(collateral_mint).0,
+            owner_collateral: *owner_collateral,
+            owner: *owner,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        dac_token::instruction::DepositCollateral { amount },
+    )
+}
+
+/// Mint `amount` DAC against the owner's collateral, priced by the Pyth `price_update` account
+pub fn mint_with_collateral(
+    owner: &Pubkey,
+    dac_mint: &Pubkey,
+    collateral_mint: &Pubkey,
+    price_update: &Pubkey,
+    owner_dac: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::MintWithCollateral {
+            config: pda::dac_config().0,
+            collateral_type: pda::collateral_type(collateral_mint).0,
+            position: pda::collateral_position(collateral_mint, owner).0,
+            price_update: *price_update,
+            dac_mint: *dac_mint,
+            owner_dac: *owner_dac,
+            mint_authority: pda::mint_authority().0,
+            owner: *owner,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dac_token::instruction::MintWithCollateral { amount },
+    )
+}
+
+/// Burn up to `amount` of the owner's DAC against their collateral debt
+pub fn repay_collateral_debt(
+    owner: &Pubkey,
+    dac_mint: &Pubkey,
+    collateral_mint: &Pubkey,
+    owner_dac: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::RepayCollateralDebt {
+            config: pda::dac_config().0,
+            collateral_type: pda::collateral_type(collateral_mint).0,
+            position: pda::collateral_position(collateral_mint, owner).0,
+            dac_mint: *dac_mint,
+            owner_dac: *owner_dac,
+            owner: *owner,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dac_token::instruction::RepayCollateralDebt { amount },
+    )
+}
+
+/// Withdraw `amount` collateral to `owner_collateral`
+pub fn withdraw_collateral(
+    owner: &Pubkey,
+    dac_mint: &Pubkey,
+    collateral_mint: &Pubkey,
+    price_update: &Pubkey,
+    owner_collateral: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::WithdrawCollateral {
+            config: pda::dac_config().0,
+            collateral_type: pda::collateral_type(collateral_mint).0,
+            position: pda::collateral_position(collateral_mint, owner).0,
+            collateral_vault: pda::collateral_vault(collateral_mint).0,
+            owner_collateral: *owner_collateral,
+            price_update: *price_update,
+            dac_mint: *dac_mint,
+            owner: *owner,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dac_token::instruction::WithdrawCollateral { amount },
+    )
+}
+
+/// Repay up to `amount` of `owner`'s unhealthy position for its collateral
+#[allow(clippy::too_many_arguments)]
+pub fn liquidate(
+    liquidator: &Pubkey,
+    owner: &Pubkey,
+    dac_mint: &Pubkey,
+    collateral_mint: &Pubkey,
+    price_update: &Pubkey,
+    liquidator_dac: &Pubkey,
+    liquidator_collateral: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    dac_token_ix(
+        dac_token::accounts::Liquidate {
+            config: pda::dac_config().0,
+            collateral_type: pda::collateral_type(collateral_mint).0,
+            position: pda::collateral_position(collateral_mint, owner).0,
+            collateral_vault: pda::collateral_vault(collateral_mint).0,
+            price_update: *price_update,
+            dac_mint: *dac_mint,
+            liquidator_dac: *liquidator_dac,
+            liquidator_collateral: *liquidator_collateral,
+            liquidator: *liquidator,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dac_token::instruction::Liquidate { amount },
+    )
+}
+
+// ============================================================================
+// Dark market
+// ============================================================================
+
+/// Set up the protocol-wide market config
+pub fn initialize_market_config(authority: &Pubkey, dac_mint: &Pubkey) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::InitializeConfig {
+            config: pda::market_config().0,
+            dac_mint: *dac_mint,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        dark_market::instruction::InitializeConfig {},
+    )
+}
+
+/// Hand the market config authority to a new key (authority only)
+pub fn set_config_authority(authority: &Pubkey, new_authority: Pubkey) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::SetConfigAuthority {
+            config: pda::market_config().0,
+            authority: *authority,
+        },
+        dark_market::instruction::SetConfigAuthority { new_authority },
+    )
+}
+
+/// Hand the guardian role to a new key (authority only)
+pub fn set_guardian(authority: &Pubkey, guardian: Pubkey) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::SetGuardian {
+            config: pda::market_config().0,
+            authority: *authority,
+        },
+        dark_market::instruction::SetGuardian { guardian },
+    )
+}
+
+/// Require splits and buys to be bundled with a DAC wrap, or lift the requirement
+pub fn configure_wrap_bundling(authority: &Pubkey, wrap_program: Pubkey, enabled: bool) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::ConfigureWrapBundling {
+            config: pda::market_config().0,
+            wrap_bundling: pda::wrap_bundling().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        dark_market::instruction::ConfigureWrapBundling { wrap_program, enabled },
+    )
+}
+
+/// Route `share_bps` of swap fees to the stakers' `fee_vault`
+pub fn configure_fee_sharing(authority: &Pubkey, fee_vault: Pubkey, share_bps: u16) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::ConfigureFeeSharing {
+            config: pda::market_config().0,
+            fee_sharing: pda::fee_sharing().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        dark_market::instruction::ConfigureFeeSharing { fee_vault, share_bps },
+    )
+}
+
+/// Set the circuit breaker of `market_key`'s pool (authority only)
+pub fn set_circuit_breaker(
+    authority: &Pubkey,
+    market_key: &Pubkey,
+    threshold_bps: u16,
+    window_slots: u64,
+    cooloff_slots: u64,
+) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::SetCircuitBreaker {
+            config: pda::market_config().0,
+            pool: pda::pool(market_key).0,
+            authority: *authority,
+        },
+        dark_market::instruction::SetCircuitBreaker { threshold_bps, window_slots, cooloff_slots },
+    )
+}
+
+/// Lift a tripped circuit breaker of `market_key`'s pool (guardian only)
+pub fn resume_trading(guardian: &Pubkey, market_key: &Pubkey) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::ResumeTrading {
+            config: pda::market_config().0,
+            pool: pda::pool(market_key).0,
+            guardian: *guardian,
+        },
+        dark_market::instruction::ResumeTrading {},
+    )
+}
+
+/// Set the volatility surcharge of `market_key`'s pool (authority only)
+pub fn set_dynamic_fee(
+    authority: &Pubkey,
+    market_key: &Pubkey,
+    window: i64,
+    multiplier_bps: u32,
+    max_fee_bps: u16,
+) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::SetDynamicFee {
+            config: pda::market_config().0,
+            pool: pda::pool(market_key).0,
+            authority: *authority,
+        },
+        dark_market::instruction::SetDynamicFee { window, multiplier_bps, max_fee_bps },
+    )
+}
+
+/// Set the leverage limits and funding of `market_key` (authority only)
+pub fn set_leverage_params(
+    authority: &Pubkey,
+    market_key: &Pubkey,
+    max_leverage_bps: u32,
+    funding_rate_bps: u16,
+    delever_window: i64,
+) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::SetLeverageParams {
+            config: pda::market_config().0,
+            market: *market_key,
+            leverage_params: pda::leverage_params(market_key).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        dark_market::instruction::SetLeverageParams { max_leverage_bps, funding_rate_bps, delever_window },
+    )
+}
+
+/// Set the staking fee discount tiers (authority only)
+pub fn set_fee_tiers(authority: &Pubkey, staking_program: Pubkey, tiers: [FeeTier; FEE_TIER_COUNT]) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::SetFeeTiers {
+            config: pda::market_config().0,
+            fee_tiers: pda::fee_tiers().0,
+            staking_program,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        dark_market::instruction::SetFeeTiers { tiers },
+    )
+}
+
+/// Set the taker share and maker rebate of `market_key`'s pool (authority only)
+pub fn configure_maker_rebates(
+    authority: &Pubkey,
+    dac_mint: &Pubkey,
+    market_key: &Pubkey,
+    taker_share_bps: u16,
+    maker_rebate_bps: u16,
+) -> Instruction {
+    let pool = pda::pool(market_key).0;
+    let rebate_pool = pda::rebate_pool(&pool).0;
+    dark_market_ix(
+        dark_market::accounts::ConfigureMakerRebates {
+            config: pda::market_config().0,
+            pool,
+            rebate_pool,
+            vault: pda::rebate_vault(&rebate_pool).0,
+            dac_mint: *dac_mint,
+            authority: *authority,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        dark_market::instruction::ConfigureMakerRebates { taker_share_bps, maker_rebate_bps },
+    )
+}
+
+/// Approve or revoke resolver registry entry `resolver_id` (authority only)
+pub fn set_resolver_approval(authority: &Pubkey, resolver_id: u64, approved: bool) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::SetResolverApproval {
+            config: pda::market_config().0,
+            entry: pda::resolver_entry(resolver_id).0,
+            authority: *authority,
+        },
+        dark_market::instruction::SetResolverApproval { approved },
+    )
+}
+
+/// Approve or revoke the relayer registered by `relayer_authority` (authority only)
+pub fn set_relayer_approval(authority: &Pubkey, relayer_authority: &Pubkey, approved: bool) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::SetRelayerApproval {
+            config: pda::market_config().0,
+            relayer: pda::relayer(relayer_authority).0,
+            authority: *authority,
+        },
+        dark_market::instruction::SetRelayerApproval { approved },
+    )
+}
+
+/// Set who publishes volume reports and the key they are encrypted to (authority only)
+pub fn configure_volume_reporting(
+    authority: &Pubkey,
+    reporting_authority: Pubkey,
+    reporting_key: [u8; 32],
+) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::ConfigureVolumeReporting {
+            config: pda::market_config().0,
+            volume_reporting: pda::volume_reporting().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        dark_market::instruction::ConfigureVolumeReporting { reporting_authority, reporting_key },
+    )
+}
+
+/// Set the gauge emission rate, creating the controller on first use (authority only)
+pub fn configure_gauges(authority: &Pubkey, dac_mint: &Pubkey, emission_rate: u64) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::ConfigureGauges {
+            config: pda::market_config().0,
+            controller: pda::gauge_controller().0,
+            reward_vault: pda::gauge_reward_vault().0,
+            dac_mint: *dac_mint,
+            authority: *authority,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        dark_market::instruction::ConfigureGauges { emission_rate },
+    )
+}
+
+/// Set the gauge weight of `market_key`'s pool, creating its gauge on first use (authority only)
+pub fn set_gauge_weight(authority: &Pubkey, market_key: &Pubkey, weight: u64) -> Instruction {
+    let pool = pda::pool(market_key).0;
+    let gauge = pda::gauge(&pool).0;
+    dark_market_ix(
+        dark_market::accounts::SetGaugeWeight {
+            config: pda::market_config().0,
+            controller: pda::gauge_controller().0,
+            pool,
+            gauge,
+            lp_vault: pda::gauge_lp_vault(&gauge).0,
+            lp_mint: pda::lp_mint(&pool).0,
+            authority: *authority,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        dark_market::instruction::SetGaugeWeight { weight },
+    )
+}
+
+/// Add protocol-wide addresses to lookup table number `index` (authority only)
+pub fn extend_lookup_table(authority: &Pubkey, index: u64, table: &Pubkey, addresses: Vec<Pubkey>) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::ExtendLookupTable {
+            config: pda::market_config().0,
+            lookup_table: pda::lookup_table(index).0,
+            table: *table,
+            lookup_table_authority: pda::lookup_table_authority().0,
+            authority: *authority,
+            address_lookup_table_program: address_lookup_table::program::ID,
+            system_program: system_program::ID,
+        },
+        dark_market::instruction::ExtendLookupTable { addresses },
+    )
+}
+
+/// Create market number `market_id`, the config's current `market_count`
+pub fn create_market(
+    creator: &Pubkey,
+    dac_mint: &Pubkey,
+    market_id: u64,
+    question: String,
+    end_time: i64,
+    resolver: Pubkey,
+) -> Instruction {
+    let market = pda::market(market_id).0;
+    dark_market_ix(
+        dark_market::accounts::CreateMarket {
+            config: pda::market_config().0,
+            market,
+            yes_mint: pda::yes_mint(&market).0,
+            no_mint: pda::no_mint(&market).0,
+            escrow: pda::escrow(&market).0,
+            dac_mint: *dac_mint,
+            creator: *creator,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        dark_market::instruction::CreateMarket { question, end_time, resolver },
+    )
+}
+
+/// The user's token accounts taking part in a split, merge or redemption
+pub struct PositionAccounts {
+    pub user: Pubkey,
+    pub user_dac: Pubkey,
+    pub user_yes: Pubkey,
+    pub user_no: Pubkey,
+    /// Record the trade in the user's portfolio
+    pub track_portfolio: bool,
+}
+
+impl PositionAccounts {
+    fn portfolio(&self) -> Option<Pubkey> {
+        self.track_portfolio.then(|| pda::portfolio(&self.user).0)
+    }
+}
+
+/// Split `amount` DAC into YES and NO tokens
+pub fn split(market_key: &Pubkey, market: &Market, position: &PositionAccounts, amount: u64) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::Split {
+            market: *market_key,
+            yes_mint: market.yes_mint,
+            no_mint: market.no_mint,
+            escrow: market.escrow,
+            user_dac: position.user_dac,
+            user_yes: position.user_yes,
+            user_no: position.user_no,
+            portfolio: position.portfolio(),
+            wrap_bundling: pda::wrap_bundling().0,
+            instructions: sysvar::instructions::ID,
+            attestation: required_attestation(&market.attestation(), &position.user),
+            user: position.user,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dark_market::instruction::Split { amount },
+    )
+}
+
+/// Merge `amount` YES and NO tokens back into DAC
+pub fn merge(market_key: &Pubkey, market: &Market, position: &PositionAccounts, amount: u64) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::Merge {
+            market: *market_key,
+            yes_mint: market.yes_mint,
+            no_mint: market.no_mint,
+            escrow: market.escrow,
+            user_dac: position.user_dac,
+            user_yes: position.user_yes,
+            user_no: position.user_no,
+            portfolio: position.portfolio(),
+            user: position.user,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dark_market::instruction::Merge { amount },
+    )
+}
+
+/// Resolve a market (its resolver only)
+pub fn resolve_market(market_key: &Pubkey, resolver: &Pubkey, outcome: Outcome) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::ResolveMarket {
+            market: *market_key,
+            sealed_resolution: pda::sealed_resolution(market_key).0,
+            resolver: *resolver,
+        },
+        dark_market::instruction::ResolveMarket { outcome },
+    )
+}
+
+/// Propose the outcome of an optimistically resolved market, posting `bond` DAC
+pub fn propose_resolution(
+    proposer: &Pubkey,
+    dac_mint: &Pubkey,
+    proposer_dac: &Pubkey,
+    market_key: &Pubkey,
+    outcome: Outcome,
+    bond: u64,
+) -> Instruction {
+    let proposal = pda::resolution_proposal(market_key).0;
+    dark_market_ix(
+        dark_market::accounts::ProposeResolution {
+            config: pda::market_config().0,
+            market: *market_key,
+            proposal,
+            bond_vault: pda::resolution_vault(&proposal).0,
+            dac_mint: *dac_mint,
+            proposer_dac: *proposer_dac,
+            proposer: *proposer,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        dark_market::instruction::ProposeResolution { outcome, bond },
+    )
+}
+
+/// Finalize a resolution proposal past its challenge or voting period
+///
+/// `winner_dac` is the DAC token account of the side the bonds go to.
+pub fn finalize_resolution(market_key: &Pubkey, proposal: &ResolutionProposal, winner_dac: &Pubkey) -> Instruction {
+    let proposal_key = pda::resolution_proposal(market_key).0;
+    dark_market_ix(
+        dark_market::accounts::FinalizeResolution {
+            market: *market_key,
+            proposal: proposal_key,
+            bond_vault: pda::resolution_vault(&proposal_key).0,
+            winner_dac: *winner_dac,
+            proposer: proposal.proposer,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dark_market::instruction::FinalizeResolution {},
+    )
+}
+
+/// Sweep the unclaimed collateral of a market past its claim deadline
+///
+/// `destination` is the insurance fund, or the creator's DAC token account
+/// for markets sweeping to their creator.
+pub fn sweep_unclaimed(market_key: &Pubkey, market: &Market, destination: &Pubkey) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::SweepUnclaimed {
+            market: *market_key,
+            escrow: market.escrow,
+            destination: *destination,
+            creator: market.creator,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dark_market::instruction::SweepUnclaimed {},
+    )
+}
+
+/// Require a market's participants to hold an attestation of `schema` from `credential` (creator only)
+pub fn set_market_attestation(
+    creator: &Pubkey,
+    market_key: &Pubkey,
+    credential: Pubkey,
+    schema: Pubkey,
+) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::SetMarketAttestation { market: *market_key, creator: *creator },
+        dark_market::instruction::SetMarketAttestation { credential, schema },
+    )
+}
+
+/// Redeem all of the user's outcome tokens of a resolved market
+pub fn redeem(market_key: &Pubkey, market: &Market, position: &PositionAccounts) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::Redeem {
+            market: *market_key,
+            yes_mint: market.yes_mint,
+            no_mint: market.no_mint,
+            escrow: market.escrow,
+            user_dac: position.user_dac,
+            user_yes: position.user_yes,
+            user_no: position.user_no,
+            portfolio: position.portfolio(),
+            user: position.user,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dark_market::instruction::Redeem {},
+    )
+}
+
+/// Give a market a Bubblegum tree of compressed receipts (market creator only)
+///
+/// `merkle_tree` must already be allocated and owned by the account
+/// compression program; [`crate::flows::enable_compressed_receipts`] does both.
+pub fn enable_compressed_receipts(
+    creator: &Pubkey,
+    market_key: &Pubkey,
+    market: &Market,
+    merkle_tree: &Pubkey,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Instruction {
+    let receipt_tree = pda::receipt_tree(market_key).0;
+    dark_market_ix(
+        dark_market::accounts::EnableCompressedReceipts {
+            market: *market_key,
+            receipt_tree,
+            yes_mint: market.yes_mint,
+            no_mint: market.no_mint,
+            yes_vault: pda::compressed_receipt_vault(&receipt_tree, &market.yes_mint).0,
+            no_vault: pda::compressed_receipt_vault(&receipt_tree, &market.no_mint).0,
+            merkle_tree: *merkle_tree,
+            tree_config: bubblegum::tree_config(merkle_tree),
+            creator: *creator,
+            bubblegum_program: bubblegum::BUBBLEGUM_PROGRAM_ID,
+            log_wrapper: bubblegum::NOOP_PROGRAM_ID,
+            compression_program: bubblegum::ACCOUNT_COMPRESSION_PROGRAM_ID,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        dark_market::instruction::EnableCompressedReceipts { max_depth, max_buffer_size },
+    )
+}
+
+/// Wrap `amount` `outcome` tokens from `owner_outcome` into a compressed receipt
+#[allow(clippy::too_many_arguments)]
+pub fn wrap_position_compressed(
+    owner: &Pubkey,
+    market_key: &Pubkey,
+    merkle_tree: &Pubkey,
+    owner_outcome: &Pubkey,
+    outcome_mint: &Pubkey,
+    outcome: Outcome,
+    amount: u64,
+    uri: String,
+) -> Instruction {
+    let receipt_tree = pda::receipt_tree(market_key).0;
+    dark_market_ix(
+        dark_market::accounts::WrapPositionCompressed {
+            market: *market_key,
+            receipt_tree,
+            owner_outcome: *owner_outcome,
+            vault: pda::compressed_receipt_vault(&receipt_tree, outcome_mint).0,
+            merkle_tree: *merkle_tree,
+            tree_config: bubblegum::tree_config(merkle_tree),
+            owner: *owner,
+            bubblegum_program: bubblegum::BUBBLEGUM_PROGRAM_ID,
+            log_wrapper: bubblegum::NOOP_PROGRAM_ID,
+            compression_program: bubblegum::ACCOUNT_COMPRESSION_PROGRAM_ID,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        dark_market::instruction::WrapPositionCompressed { outcome, amount, uri },
+    )
+}
+
+/// A compressed receipt as restated at unwrap, read from a DAS API
+pub struct CompressedReceipt {
+    pub outcome: Outcome,
+    pub amount: u64,
+    pub uri: String,
+    /// The leaf's nonce
+    pub nonce: u64,
+    /// The leaf's index in the tree
+    pub index: u32,
+    /// The tree root the proof was read against
+    pub root: [u8; 32],
+    /// The leaf's delegate: the holder unless they delegated it
+    pub leaf_delegate: Pubkey,
+}
+
+/// Burn `receipt`, proven by `proof`, and release its outcome tokens to `holder_outcome`
+///
+/// `proof` may leave out the nodes the tree's canopy stores.
+pub fn unwrap_compressed_position(
+    holder: &Pubkey,
+    market_key: &Pubkey,
+    merkle_tree: &Pubkey,
+    holder_outcome: &Pubkey,
+    outcome_mint: &Pubkey,
+    receipt: &CompressedReceipt,
+    proof: &[Pubkey],
+) -> Instruction {
+    let receipt_tree = pda::receipt_tree(market_key).0;
+    let mut ix = dark_market_ix(
+        dark_market::accounts::UnwrapCompressedPosition {
+            market: *market_key,
+            receipt_tree,
+            holder_outcome: *holder_outcome,
+            vault: pda::compressed_receipt_vault(&receipt_tree, outcome_mint).0,
+            merkle_tree: *merkle_tree,
+            tree_config: bubblegum::tree_config(merkle_tree),
+            leaf_delegate: receipt.leaf_delegate,
+            holder: *holder,
+            bubblegum_program: bubblegum::BUBBLEGUM_PROGRAM_ID,
+            log_wrapper: bubblegum::NOOP_PROGRAM_ID,
+            compression_program: bubblegum::ACCOUNT_COMPRESSION_PROGRAM_ID,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        dark_market::instruction::UnwrapCompressedPosition {
+            root: receipt.root,
+            outcome: receipt.outcome,
+            amount: receipt.amount,
+            uri: receipt.uri.clone(),
+            nonce: receipt.nonce,
+            index: receipt.index,
+        },
+    );
+    ix.accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(*node, false)));
+    ix
+}
+
+/// Give a market a concurrent Merkle tree of compressed positions (market creator only)
+///
+/// `merkle_tree` must already be allocated for exactly `size` and owned by
+/// the account compression program; [`crate::flows::enable_position_tree`] does both.
+pub fn enable_position_tree(
+    creator: &Pubkey,
+    market_key: &Pubkey,
+    market: &Market,
+    merkle_tree: &Pubkey,
+    size: &TreeSize,
+) -> Instruction {
+    let position_tree = pda::position_tree(market_key).0;
+    dark_market_ix(
+        dark_market::accounts::EnablePositionTree {
+            market: *market_key,
+            position_tree,
+            yes_mint: market.yes_mint,
+            no_mint: market.no_mint,
+            yes_vault: pda::position_tree_vault(&position_tree, &market.yes_mint).0,
+            no_vault: pda::position_tree_vault(&position_tree, &market.no_mint).0,
+            merkle_tree: *merkle_tree,
+            creator: *creator,
+            log_wrapper: bubblegum::NOOP_PROGRAM_ID,
+            compression_program: bubblegum::ACCOUNT_COMPRESSION_PROGRAM_ID,
+            token_program: TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        dark_market::instruction::EnablePositionTree {
+            max_depth: size.max_depth,
+            max_buffer_size: size.max_buffer_size,
+            canopy_depth: size.canopy_depth,
+        },
+    )
+}
+
+/// Deposit `amount` `outcome` tokens from `owner_outcome` into a new compressed position
+pub fn deposit_compressed_position(
+    owner: &Pubkey,
+    market_key: &Pubkey,
+    merkle_tree: &Pubkey,
+    owner_outcome: &Pubkey,
+    outcome_mint: &Pubkey,
+    outcome: Outcome,
+    amount: u64,
+) -> Instruction {
+    let position_tree = pda::position_tree(market_key).0;
+    dark_market_ix(
+        dark_market::accounts::DepositCompressedPosition {
+            market: *market_key,
+            position_tree,
+            owner_outcome: *owner_outcome,
+            vault: pda::position_tree_vault(&position_tree, outcome_mint).0,
+            merkle_tree: *merkle_tree,
+            owner: *owner,
+            log_wrapper: bubblegum::NOOP_PROGRAM_ID,
+            compression_program: bubblegum::ACCOUNT_COMPRESSION_PROGRAM_ID,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dark_market::instruction::DepositCompressedPosition { outcome, amount },
+    )
+}
+
+/// A compressed position as restated with its proof, read from `CompressedPositionChanged` events
+pub struct CompressedPosition {
+    pub outcome: Outcome,
+    pub amount: u64,
+    /// The leaf's index in the tree
+    pub index: u32,
+    /// The tree root the proof was read against
+    pub root: [u8; 32],
+}
+
+/// Resize `position`, proven by `proof`, to `new_amount`, settling the difference with `owner_outcome`
+///
+/// `proof` leaves out the nodes the tree's canopy stores.
+#[allow(clippy::too_many_arguments)]
+pub fn update_compressed_position(
+    owner: &Pubkey,
+    market_key: &Pubkey,
+    merkle_tree: &Pubkey,
+    owner_outcome: &Pubkey,
+    outcome_mint: &Pubkey,
+    position: &CompressedPosition,
+    proof: &[Pubkey],
+    new_amount: u64,
+) -> Instruction {
+    let position_tree = pda::position_tree(market_key).0;
+    let mut ix = dark_market_ix(
+        dark_market::accounts::UpdateCompressedPosition {
+            market: *market_key,
+            position_tree,
+            owner_outcome: *owner_outcome,
+            vault: pda::position_tree_vault(&position_tree, outcome_mint).0,
+            merkle_tree: *merkle_tree,
+            owner: *owner,
+            log_wrapper: bubblegum::NOOP_PROGRAM_ID,
+            compression_program: bubblegum::ACCOUNT_COMPRESSION_PROGRAM_ID,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dark_market::instruction::UpdateCompressedPosition {
+            root: position.root,
+            outcome: position.outcome,
+            amount: position.amount,
+            index: position.index,
+            new_amount,
+        },
+    );
+    ix.accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(*node, false)));
+    ix
+}
+
+/// Redeem `position`, proven by `proof`, paying `holder_dac` from the resolved market's escrow
+///
+/// `proof` leaves out the nodes the tree's canopy stores.
+pub fn redeem_compressed_position(
+    holder: &Pubkey,
+    holder_dac: &Pubkey,
+    market_key: &Pubkey,
+    market: &Market,
+    merkle_tree: &Pubkey,
+    position: &CompressedPosition,
+    proof: &[Pubkey],
+) -> Instruction {
+    let position_tree = pda::position_tree(market_key).0;
+    let outcome_mint = if position.outcome == Outcome::Yes { market.yes_mint } else { market.no_mint };
+    let mut ix = dark_market_ix(
+        dark_market::accounts::RedeemCompressedPosition {
+            market: *market_key,
+            position_tree,
+            outcome_mint,
+            vault: pda::position_tree_vault(&position_tree, &outcome_mint).0,
+            escrow: market.escrow,
+            holder_dac: *holder_dac,
+            merkle_tree: *merkle_tree,
+            holder: *holder,
+            log_wrapper: bubblegum::NOOP_PROGRAM_ID,
+            compression_program: bubblegum::ACCOUNT_COMPRESSION_PROGRAM_ID,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dark_market::instruction::RedeemCompressedPosition {
+            root: position.root,
+            outcome: position.outcome,
+            amount: position.amount,
+            index: position.index,
+        },
+    );
+    ix.accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(*node, false)));
+    ix
+}
+
+/// A compressed position a keeper settles, with where its payout goes and its proof
+pub struct BatchSettlement {
+    pub position: SettledPosition,
+    /// DAC token account of the leaf's owner, receiving the payout
+    pub owner_dac: Pubkey,
+    /// The leaf's proof, without the nodes the tree's canopy stores
+    pub proof: Vec<Pubkey>,
+}
+
+/// Redeem `settlements`, all proven against `root`, paying each owner from the resolved market's escrow
+///
+/// Permissionless. At most [`dark_market::state::MAX_SETTLE_BATCH`] positions, and no more than
+/// the tree's change log buffer accepts against one root.
+pub fn settle_batch(
+    market_key: &Pubkey,
+    market: &Market,
+    merkle_tree: &Pubkey,
+    root: [u8; 32],
+    settlements: &[BatchSettlement],
+) -> Instruction {
+    let position_tree = pda::position_tree(market_key).0;
+    let mut ix = dark_market_ix(
+        dark_market::accounts::SettleBatch {
+            market: *market_key,
+            position_tree,
+            yes_mint: market.yes_mint,
+            no_mint: market.no_mint,
+            yes_vault: pda::position_tree_vault(&position_tree, &market.yes_mint).0,
+            no_vault: pda::position_tree_vault(&position_tree, &market.no_mint).0,
+            escrow: market.escrow,
+            merkle_tree: *merkle_tree,
+            log_wrapper: bubblegum::NOOP_PROGRAM_ID,
+            compression_program: bubblegum::ACCOUNT_COMPRESSION_PROGRAM_ID,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dark_market::instruction::SettleBatch {
+            root,
+            positions: settlements.iter().map(|settlement| settlement.position).collect(),
+        },
+    );
+    for settlement in settlements {
+        ix.accounts.push(AccountMeta::new(settlement.owner_dac, false));
+        ix.accounts.extend(settlement.proof.iter().map(|node| AccountMeta::new_readonly(*node, false)));
+    }
+    ix
+}
+
+/// Open a market's pool with `amount` DAC of initial liquidity
+pub fn create_pool(
+    user: &Pubkey,
+    user_dac: &Pubkey,
+    market_key: &Pubkey,
+    market: &Market,
+    fee_bps: u16,
+    amount: u64,
+) -> Instruction {
+    let pool = pda::pool(market_key).0;
+    let lp_mint = pda::lp_mint(&pool).0;
+    dark_market_ix(
+        dark_market::accounts::CreatePool {
+            market: *market_key,
+            series: None,
+            launch: pda::launch(market_key).0,
+            pool,
+            observations: pda::observations(&pool).0,
+            yes_vault: pda::pool_yes_vault(&pool).0,
+            no_vault: pda::pool_no_vault(&pool).0,
+            lp_mint,
+            yes_mint: market.yes_mint,
+            no_mint: market.no_mint,
+            escrow: market.escrow,
+            user_dac: *user_dac,
+            user_lp: pda::associated_token_address(user, &lp_mint),
+            user: *user,
+            token_program: TOKEN_PROGRAM_ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        dark_market::instruction::CreatePool { fee_bps, amount },
+    )
+}
+
+/// Accounts of a pool trade besides the market's own
+pub struct SwapAccounts {
+    pub user: Pubkey,
+    pub user_dac: Pubkey,
+    /// The user's token account of the traded outcome
+    pub user_outcome: Pubkey,
+    /// The stakers' fee vault, read from the fee sharing account
+    pub fee_vault: Pubkey,
+    /// Record the trade in the user's portfolio
+    pub track_portfolio: bool,
+    /// The pool's rebate vault, required once maker rebates are configured on it
+    pub rebate_vault: Option<Pubkey>,
+}
+
+fn swap_accounts(market_key: &Pubkey, market: &Market, swap: &SwapAccounts) -> dark_market::accounts::Swap {
+    let pool = pda::pool(market_key).0;
+    dark_market::accounts::Swap {
+        market: *market_key,
+        pool,
+        observations: pda::observations(&pool).0,
+        yes_vault: pda::pool_yes_vault(&pool).0,
+        no_vault: pda::pool_no_vault(&pool).0,
+        yes_mint: market.yes_mint,
+        no_mint: market.no_mint,
+        escrow: market.escrow,
+        user_dac: swap.user_dac,
+        user_outcome: swap.user_outcome,
+        fee_tiers: None,
+        stake: None,
+        portfolio: swap.track_portfolio.then(|| pda::portfolio(&swap.user).0),
+        rebate_pool: pda::rebate_pool(&pool).0,
+        rebate_vault: swap.rebate_vault,
+        wrap_bundling: pda::wrap_bundling().0,
+        fee_sharing: pda::fee_sharing().0,
+        fee_vault: swap.fee_vault,
+        instructions: sysvar::instructions::ID,
+        attestation: required_attestation(&market.attestation(), &swap.user),
+        user: swap.user,
+        token_program: TOKEN_PROGRAM_ID,
+    }
+}
+
+/// Buy `outcome` tokens from the pool with `amount_in` DAC
+pub fn buy(
+    market_key: &Pubkey,
+    market: &Market,
+    swap: &SwapAccounts,
+    outcome: Outcome,
+    amount_in: u64,
+    min_amount_out: u64,
+    expiry_slot: u64,
+) -> Instruction {
+    dark_market_ix(
+        swap_accounts(market_key, market, swap),
+        dark_market::instruction::Buy { outcome, amount_in, min_amount_out, expiry_slot },
+    )
+}
+
+/// Sell `amount_in` `outcome` tokens to the pool for DAC
+pub fn sell(
+    market_key: &Pubkey,
+    market: &Market,
+    swap: &SwapAccounts,
+    outcome: Outcome,
+    amount_in: u64,
+    min_amount_out: u64,
+    expiry_slot: u64,
+) -> Instruction {
+    dark_market_ix(
+        swap_accounts(market_key, market, swap),
+        dark_market::instruction::Sell { outcome, amount_in, min_amount_out, expiry_slot },
+    )
+}
+
+/// Fill a limit order against its pool, paying the tip to `cranker_dac`
+///
+/// `owner_proceeds` is the owner's token account of what the order buys:
+/// the outcome for buys, DAC for sells. `rebate_vault` is the pool's rebate
+/// vault, required once maker rebates are configured on it.
+#[allow(clippy::too_many_arguments)]
+pub fn fill_limit_order(
+    cranker: &Pubkey,
+    cranker_dac: &Pubkey,
+    order_key: &Pubkey,
+    order: &LimitOrder,
+    market_key: &Pubkey,
+    market: &Market,
+    owner_proceeds: &Pubkey,
+    rebate_vault: Option<Pubkey>,
+) -> Instruction {
+    let pool = pda::pool(market_key).0;
+    dark_market_ix(
+        dark_market::accounts::FillLimitOrder {
+            market: *market_key,
+            pool,
+            observations: pda::observations(&pool).0,
+            yes_vault: pda::pool_yes_vault(&pool).0,
+            no_vault: pda::pool_no_vault(&pool).0,
+            yes_mint: market.yes_mint,
+            no_mint: market.no_mint,
+            escrow: market.escrow,
+            order: *order_key,
+            order_vault: pda::limit_order_vault(order_key).0,
+            owner_proceeds: *owner_proceeds,
+            owner: order.owner,
+            portfolio: None,
+            fee_tiers: None,
+            stake: None,
+            rebate_pool: pda::rebate_pool(&pool).0,
+            rebate_vault,
+            maker_rebate: pda::maker_rebate(&pool, &order.owner).0,
+            cranker_dac: *cranker_dac,
+            cranker: *cranker,
+            token_program: TOKEN_PROGRAM_ID,
+        },
+        dark_market::instruction::FillLimitOrder {},
+    )
+}
+
+/// Create lookup table number `index`, the registry's current `table_count`
+///
+/// `recent_slot` must still be in the slot hashes sysvar; every table after
+/// the first needs the previous one to be full.
+pub fn create_lookup_table(payer: &Pubkey, index: u64, recent_slot: u64) -> Instruction {
+    dark_market_ix(
+        dark_market::accounts::CreateLookupTable {
+            registry: pda::lookup_table_registry().0,
+            latest: index.checked_sub(1).map(|latest| pda::lookup_table(latest).0),
+            lookup_table: pda::lookup_table(index).0,
+            table: pda::lookup_table_address(recent_slot).0,
+            lookup_table_authority: pda::lookup_table_authority().0,
+            payer: *payer,
+            address_lookup_table_program: address_lookup_table::program::ID,
+            system_program: system_program::ID,
+        },
+        dark_market::instruction::CreateLookupTable { recent_slot },
+    )
+}
+
+/// Add `markets`, the registry's `next_market` onwards in id order, to lookup table number `index`
+pub fn extend_lookup_table_with_markets(
+    payer: &Pubkey,
+    index: u64,
+    table: &Pubkey,
+    markets: &[Pubkey],
+) -> Instruction {
+    let mut ix = dark_market_ix(
+        dark_market::accounts::ExtendLookupTableWithMarkets {
+            registry: pda::lookup_table_registry().0,
+            lookup_table: pda::lookup_table(index).0,
+            table: *table,
+            lookup_table_authority: pda::lookup_table_authority().0,
+            payer: *payer,
+            address_lookup_table_program: address_lookup_table::program::ID,
+            system_program: system_program::ID,
+        },
+        dark_market::instruction::ExtendLookupTableWithMarkets {},
+    );
+    ix.accounts.extend(markets.iter().map(|market| AccountMeta::new_readonly(*market, false)));
+    ix
+}
@@ -0,0 +1,25 @@
+//! DAC Client SDK
+//!
+//! Builds instructions for the DAC token and dark market programs without
+//! hand-rolling account lists from the IDL. Everything here is offline:
+//! callers fetch account data with their own RPC client and pass it in.
+//!
+//! - [`pda`]: every PDA the programs derive
+//! - [`accounts`]: account deserializers and the program account types
+//! - [`instructions`]: one typed builder per instruction
+//! - [`flows`]: multi-instruction flows (wrap, swap and wrap, unwrap, vault shards, create market, bet,
+//!   redeem, compressed receipt and position trees) that also create the accounts they need
+//! - [`offline`]: building, signing and serializing transactions on
+//!   air-gapped machines, with durable nonces
+//! - [`lookup_tables`]: the protocol's address lookup tables and v0
+//!   transactions compiled against them
+
+pub mod accounts;
+pub mod flows;
+pub mod instructions;
+pub mod lookup_tables;
+pub mod offline;
+pub mod pda;
+
+pub use dac_token::ID as DAC_TOKEN_PROGRAM_ID;
+pub use dark_market::ID as DARK_MARKET_PROGRAM_ID;
@@ -0,0 +1,89 @@
+//! Protocol address lookup tables and the v0 transactions that use them
+//!
+//! The dark market program owns a series of lookup tables: the config
+//! authority adds the protocol-wide addresses, and keepers append every
+//! market's accounts in id order. Transactions compiled against all of them
+//! fit settlement and trading batches that would exceed the account limit
+//! of a legacy transaction.
+//!
+//! ```ignore
+//! let table = lookup_tables::parse(record.table, &rpc.get_account_data(&record.table)?)?;
+//! let message = lookup_tables::compile(&instructions, &payer, &[table], blockhash)?;
+//! let tx = VersionedTransaction::try_new(message, &[&keypair])?;
+//! ```
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{Instruction, InstructionError};
+use anchor_spl::associated_token::ID as ASSOCIATED_TOKEN_PROGRAM_ID;
+use anchor_spl::token::ID as TOKEN_PROGRAM_ID;
+use dark_market::state::Market;
+use solana_sdk::address_lookup_table::state::AddressLookupTable;
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{v0, CompileError, VersionedMessage};
+use solana_sdk::system_program;
+
+use crate::pda;
+
+pub use dark_market::state::{LOOKUP_TABLE_CAPACITY, MARKET_LOOKUP_ADDRESSES};
+
+/// Decode an address lookup table account
+pub fn parse(key: Pubkey, data: &[u8]) -> Result<AddressLookupTableAccount, InstructionError> {
+    let table = AddressLookupTable::deserialize(data)?;
+    Ok(AddressLookupTableAccount { key, addresses: table.addresses.to_vec() })
+}
+
+/// Protocol-wide addresses: the programs, configs, mints and vault PDAs every batch shares
+///
+/// `shard_count` is the DAC config's; each vault shard adds its account, USDC vault and DAC float.
+pub fn protocol_addresses(dac_mint: &Pubkey, usdc_mint: &Pubkey, shard_count: u8) -> Vec<Pubkey> {
+    let mut addresses = vec![
+        dac_token::ID,
+        dark_market::ID,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        system_program::ID,
+        *dac_mint,
+        *usdc_mint,
+        pda::dac_config().0,
+        pda::mint_authority().0,
+        pda::vault_authority().0,
+        pda::market_config().0,
+        pda::insurance_fund().0,
+        pda::fee_tiers().0,
+        pda::fee_sharing().0,
+        pda::wrap_bundling().0,
+    ];
+    for index in 0..shard_count {
+        addresses.extend([
+            pda::vault_shard(index).0,
+            pda::shard_usdc_vault(index).0,
+            pda::shard_dac_float(index).0,
+        ]);
+    }
+    addresses
+}
+
+/// The addresses the program adds for a market, in its order
+pub fn market_addresses(market_key: &Pubkey, market: &Market) -> [Pubkey; MARKET_LOOKUP_ADDRESSES] {
+    let pool = pda::pool(market_key).0;
+    [
+        *market_key,
+        market.yes_mint,
+        market.no_mint,
+        market.escrow,
+        pool,
+        pda::pool_yes_vault(&pool).0,
+        pda::pool_no_vault(&pool).0,
+    ]
+}
+
+/// An unsigned v0 message of `instructions` paid by `payer`, loading any account found in `tables`
+pub fn compile(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<VersionedMessage, CompileError> {
+    Ok(VersionedMessage::V0(v0::Message::try_compile(payer, instructions, tables, blockhash)?))
+}
@@ -0,0 +1,138 @@
+//! Transactions built, signed and serialized without RPC access
+//!
+//! For cold-storage signers: an online machine looks up the durable nonce
+//! (or a blockhash) once, then the transaction is built from the admin
+//! instruction builders, carried between air-gapped signers as a base64
+//! string, signed by each in turn and finally submitted online.
+//!
+//! ```ignore
+//! let lifetime = Lifetime::Nonce { account: nonce_account, authority: signer_a, nonce };
+//! let mut tx = offline::build(&[instructions::set_wrap_fee(&authority, 10, fee_vault)], &signer_a, &lifetime);
+//! offline::sign(&mut tx, &keypair_a)?;
+//! let carried = offline::encode(&tx);
+//! // on the next signer
+//! let mut tx = offline::decode(&carried)?;
+//! offline::sign(&mut tx, &keypair_b)?;
+//! assert!(offline::missing_signers(&tx).is_empty());
+//! ```
+
+use std::fmt;
+
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::Message;
+use solana_sdk::signature::{Signature, Signer, SignerError};
+use solana_sdk::transaction::Transaction;
+
+/// How long a transaction stays valid
+#[derive(Clone, Debug)]
+pub enum Lifetime {
+    /// A recent blockhash: the transaction expires after about a minute
+    Blockhash(Hash),
+    /// A durable nonce: the transaction stays valid until the nonce is advanced
+    Nonce {
+        account: Pubkey,
+        /// Signer allowed to advance the nonce
+        authority: Pubkey,
+        /// The nonce account's current value
+        nonce: Hash,
+    },
+}
+
+#[derive(Debug)]
+pub enum OfflineError {
+    /// Not valid base64 of a serialized transaction
+    Decode(String),
+    /// The key is not a signer of the transaction
+    NotASigner(Pubkey),
+    /// The signature does not verify against the transaction's message
+    InvalidSignature(Pubkey),
+    Signer(SignerError),
+}
+
+impl fmt::Display for OfflineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode(e) => write!(f, "invalid transaction encoding: {}", e),
+            Self::NotASigner(key) => write!(f, "{} is not a signer of the transaction", key),
+            Self::InvalidSignature(key) => write!(f, "signature of {} does not match the transaction", key),
+            Self::Signer(e) => write!(f, "signing failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for OfflineError {}
+
+impl From<SignerError> for OfflineError {
+    fn from(e: SignerError) -> Self {
+        Self::Signer(e)
+    }
+}
+
+/// An unsigned transaction of `instructions` paid by `payer`
+///
+/// A durable nonce prepends the instruction advancing it, as the runtime
+/// requires.
+pub fn build(instructions: &[Instruction], payer: &Pubkey, lifetime: &Lifetime) -> Transaction {
+    let message = match lifetime {
+        Lifetime::Blockhash(blockhash) => Message::new_with_blockhash(instructions, Some(payer), blockhash),
+        Lifetime::Nonce { account, authority, nonce } => {
+            let mut message = Message::new_with_nonce(instructions.to_vec(), Some(payer), account, authority);
+            message.recent_blockhash = *nonce;
+            message
+        }
+    };
+    Transaction::new_unsigned(message)
+}
+
+/// Add `signer`'s signature, keeping the others
+pub fn sign(tx: &mut Transaction, signer: &dyn Signer) -> Result<(), OfflineError> {
+    let blockhash = tx.message.recent_blockhash;
+    tx.try_partial_sign(&[signer], blockhash)?;
+    Ok(())
+}
+
+/// Add a signature made elsewhere, such as on a hardware wallet over [`message_data`]
+pub fn add_signature(tx: &mut Transaction, signer: &Pubkey, signature: Signature) -> Result<(), OfflineError> {
+    let index = tx
+        .message
+        .account_keys
+        .iter()
+        .take(tx.message.header.num_required_signatures as usize)
+        .position(|key| key == signer)
+        .ok_or(OfflineError::NotASigner(*signer))?;
+    if !signature.verify(signer.as_ref(), &tx.message_data()) {
+        return Err(OfflineError::InvalidSignature(*signer));
+    }
+    tx.signatures[index] = signature;
+    Ok(())
+}
+
+/// The bytes each signer signs
+pub fn message_data(tx: &Transaction) -> Vec<u8> {
+    tx.message_data()
+}
+
+/// Signers whose signature is still missing, in order
+pub fn missing_signers(tx: &Transaction) -> Vec<Pubkey> {
+    tx.message
+        .account_keys
+        .iter()
+        .zip(&tx.signatures)
+        .filter(|(_, signature)| **signature == Signature::default())
+        .map(|(key, _)| *key)
+        .collect()
+}
+
+/// The transaction in wire format as base64, as `sendTransaction` accepts it
+pub fn encode(tx: &Transaction) -> String {
+    STANDARD.encode(bincode::serialize(tx).expect("transactions serialize"))
+}
+
+pub fn decode(encoded: &str) -> Result<Transaction, OfflineError> {
+    let bytes = STANDARD.decode(encoded.trim()).map_err(|e| OfflineError::Decode(e.to_string()))?;
+    bincode::deserialize(&bytes).map_err(|e| OfflineError::Decode(e.to_string()))
+}
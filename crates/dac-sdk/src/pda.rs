@@ -0,0 +1,294 @@
+//! PDA derivations of the DAC token and dark market programs
+//!
+//! Each function returns the address and its bump.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::address_lookup_table::instruction::derive_lookup_table_address;
+use anchor_spl::associated_token::get_associated_token_address;
+use dac_token::attestation::{AttestationRequirement, ATTESTATION_SEED, SAS_PROGRAM_ID};
+
+fn dac_token_pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &dac_token::ID)
+}
+
+fn dark_market_pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &dark_market::ID)
+}
+
+// ============================================================================
+// DAC token
+// ============================================================================
+
+/// The DAC token config
+pub fn dac_config() -> (Pubkey, u8) {
+    dac_token_pda(&[dac_token::CONFIG_SEED])
+}
+
+/// The DAC mint authority
+pub fn mint_authority() -> (Pubkey, u8) {
+    dac_token_pda(&[dac_token::MINT_AUTHORITY_SEED, dac_config().0.as_ref()])
+}
+
+/// Authority of the USDC backing vault
+pub fn vault_authority() -> (Pubkey, u8) {
+    dac_token_pda(&[dac_token::VAULT_AUTHORITY_SEED, dac_config().0.as_ref()])
+}
+
+/// The USDC vault of the unsharded layout, emptied into the first vault shard
+pub fn usdc_vault() -> (Pubkey, u8) {
+    dac_token_pda(&[b"usdc_vault", dac_config().0.as_ref()])
+}
+
+/// Vault shard `index`
+pub fn vault_shard(index: u8) -> (Pubkey, u8) {
+    dac_token_pda(&[dac_token::shard::VAULT_SHARD_SEED, &[index]])
+}
+
+/// USDC vault of vault shard `index`
+pub fn shard_usdc_vault(index: u8) -> (Pubkey, u8) {
+    dac_token_pda(&[dac_token::shard::SHARD_VAULT_SEED, dac_config().0.as_ref(), &[index]])
+}
+
+/// DAC float of vault shard `index`
+pub fn shard_dac_float(index: u8) -> (Pubkey, u8) {
+    dac_token_pda(&[dac_token::shard::DAC_FLOAT_SEED, &[index]])
+}
+
+/// Collateral type of `mint`
+pub fn collateral_type(mint: &Pubkey) -> (Pubkey, u8) {
+    dac_token_pda(&[dac_token::collateral::COLLATERAL_TYPE_SEED, mint.as_ref()])
+}
+
+/// Vault of the collateral type of `mint`
+pub fn collateral_vault(mint: &Pubkey) -> (Pubkey, u8) {
+    dac_token_pda(&[dac_token::collateral::COLLATERAL_VAULT_SEED, mint.as_ref()])
+}
+
+/// `owner`'s position in the collateral type of `mint`
+pub fn collateral_position(mint: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    dac_token_pda(&[
+        dac_token::collateral::COLLATERAL_POSITION_SEED,
+        collateral_type(mint).0.as_ref(),
+        owner.as_ref(),
+    ])
+}
+
+/// The Solana Attestation Service attestation issued to `user` under `requirement`
+pub fn attestation(requirement: &AttestationRequirement, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ATTESTATION_SEED, requirement.credential.as_ref(), requirement.schema.as_ref(), user.as_ref()],
+        &SAS_PROGRAM_ID,
+    )
+}
+
+// ============================================================================
+// Dark market
+// ============================================================================
+
+/// The protocol-wide market config
+pub fn market_config() -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::MARKET_CONFIG_SEED])
+}
+
+/// The market with sequential id `id`
+pub fn market(id: u64) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::MARKET_SEED, &id.to_le_bytes()])
+}
+
+/// A market's YES outcome mint
+pub fn yes_mint(market: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::YES_MINT_SEED, market.as_ref()])
+}
+
+/// A market's NO outcome mint
+pub fn no_mint(market: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::NO_MINT_SEED, market.as_ref()])
+}
+
+/// A market's DAC escrow
+pub fn escrow(market: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::ESCROW_SEED, market.as_ref()])
+}
+
+/// A market's compressed receipt tree
+pub fn receipt_tree(market: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::RECEIPT_TREE_SEED, market.as_ref()])
+}
+
+/// The vault of a receipt tree's `outcome_mint` tokens
+pub fn compressed_receipt_vault(receipt_tree: &Pubkey, outcome_mint: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::COMPRESSED_RECEIPT_VAULT_SEED, receipt_tree.as_ref(), outcome_mint.as_ref()])
+}
+
+/// A market's compressed position tree
+pub fn position_tree(market: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::POSITION_TREE_SEED, market.as_ref()])
+}
+
+/// The vault of a position tree's `outcome_mint` tokens
+pub fn position_tree_vault(position_tree: &Pubkey, outcome_mint: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::POSITION_TREE_VAULT_SEED, position_tree.as_ref(), outcome_mint.as_ref()])
+}
+
+/// A market's AMM pool
+pub fn pool(market: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::POOL_SEED, market.as_ref()])
+}
+
+/// A pool's YES reserve vault
+pub fn pool_yes_vault(pool: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::POOL_YES_SEED, pool.as_ref()])
+}
+
+/// A pool's NO reserve vault
+pub fn pool_no_vault(pool: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::POOL_NO_SEED, pool.as_ref()])
+}
+
+/// A pool's LP share mint
+pub fn lp_mint(pool: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::LP_MINT_SEED, pool.as_ref()])
+}
+
+/// A market's bonding-curve launch
+pub fn launch(market: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::LAUNCH_SEED, market.as_ref()])
+}
+
+/// A pool's price observation buffer
+pub fn observations(pool: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::OBSERVATIONS_SEED, pool.as_ref()])
+}
+
+/// The vault holding a limit order's locked asset
+pub fn limit_order_vault(order: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::LIMIT_ORDER_VAULT_SEED, order.as_ref()])
+}
+
+/// The insurance fund's DAC vault
+pub fn insurance_fund() -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::INSURANCE_FUND_SEED])
+}
+
+/// A user's trading portfolio
+pub fn portfolio(owner: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::PORTFOLIO_SEED, owner.as_ref()])
+}
+
+/// The swap fee discount tiers
+pub fn fee_tiers() -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::FEE_TIERS_SEED])
+}
+
+/// A pool's maker rebate settings
+pub fn rebate_pool(pool: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::REBATE_POOL_SEED, pool.as_ref()])
+}
+
+/// The wrap bundling setting
+pub fn wrap_bundling() -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::WRAP_BUNDLING_SEED])
+}
+
+/// The stakers' swap fee share
+pub fn fee_sharing() -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::FEE_SHARING_SEED])
+}
+
+/// A market's leverage settings
+pub fn leverage_params(market: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::LEVERAGE_PARAMS_SEED, market.as_ref()])
+}
+
+/// The DAC vault funding a rebate pool
+pub fn rebate_vault(rebate_pool: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::REBATE_VAULT_SEED, rebate_pool.as_ref()])
+}
+
+/// A market's private resolution settings
+pub fn sealed_resolution(market: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::SEALED_RESOLUTION_SEED, market.as_ref()])
+}
+
+/// A maker's accrued rebates on a pool
+pub fn maker_rebate(pool: &Pubkey, maker: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::MAKER_REBATE_SEED, pool.as_ref(), maker.as_ref()])
+}
+
+/// Entry `id` of the resolver registry
+pub fn resolver_entry(id: u64) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::RESOLVER_SEED, &id.to_le_bytes()])
+}
+
+/// The relayer registered by `authority`
+pub fn relayer(authority: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::RELAYER_SEED, authority.as_ref()])
+}
+
+/// The volume reporting settings
+pub fn volume_reporting() -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::VOLUME_REPORTING_SEED])
+}
+
+/// The liquidity gauge controller
+pub fn gauge_controller() -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::GAUGE_CONTROLLER_SEED])
+}
+
+/// The DAC vault paying gauge rewards
+pub fn gauge_reward_vault() -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::GAUGE_REWARD_VAULT_SEED])
+}
+
+/// A pool's liquidity gauge
+pub fn gauge(pool: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::GAUGE_SEED, pool.as_ref()])
+}
+
+/// The vault holding LP tokens deposited in a gauge
+pub fn gauge_lp_vault(gauge: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::GAUGE_LP_VAULT_SEED, gauge.as_ref()])
+}
+
+/// A market's optimistic resolution proposal
+pub fn resolution_proposal(market: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::RESOLUTION_SEED, market.as_ref()])
+}
+
+/// The vault holding a resolution proposal's bonds
+pub fn resolution_vault(proposal: &Pubkey) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::RESOLUTION_VAULT_SEED, proposal.as_ref()])
+}
+
+/// The registry of protocol lookup tables
+pub fn lookup_table_registry() -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::LOOKUP_TABLE_REGISTRY_SEED])
+}
+
+/// The record of protocol lookup table number `index`
+pub fn lookup_table(index: u64) -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::LOOKUP_TABLE_SEED, &index.to_le_bytes()])
+}
+
+/// Authority of every protocol lookup table
+pub fn lookup_table_authority() -> (Pubkey, u8) {
+    dark_market_pda(&[dark_market::LOOKUP_TABLE_AUTHORITY_SEED])
+}
+
+/// The address lookup table created at `recent_slot` by the lookup table authority
+pub fn lookup_table_address(recent_slot: u64) -> (Pubkey, u8) {
+    derive_lookup_table_address(&lookup_table_authority().0, recent_slot)
+}
+
+/// A user's outcome token accounts in a market: the YES and NO associated token accounts
+pub fn position(owner: &Pubkey, market: &Pubkey) -> (Pubkey, Pubkey) {
+    (
+        get_associated_token_address(owner, &yes_mint(market).0),
+        get_associated_token_address(owner, &no_mint(market).0),
+    )
+}
+
+/// `owner`'s associated token account of `mint`
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(owner, mint)
+}
@@ -0,0 +1,9 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // The WebSocket API sends the same messages as JSON
+    tonic_build::configure()
+        .build_client(false)
+        .type_attribute(".", "#[derive(serde::Serialize)]")
+        .type_attribute(".", "#[serde(rename_all = \"snake_case\")]")
+        .compile_protos(&["proto/dac_stream.proto"], &["proto"])?;
+    Ok(())
+}
@@ -0,0 +1,146 @@
+//! Reading the indexer's tables back as stream updates
+
+use anyhow::{Context, Result};
+use tokio_postgres::{Client, Row};
+
+use crate::pb::{self, market_update, MarketUpdate, Outcome, ReservesUpdate, Source};
+
+/// Transactions read per query, bounding a replay's memory
+const PAGE: i64 = 1_000;
+
+/// An update of either stream
+#[derive(Clone)]
+pub enum Update {
+    Market(MarketUpdate),
+    Reserves(ReservesUpdate),
+}
+
+impl Update {
+    pub fn source(&self) -> &Source {
+        let source = match self {
+            Self::Market(update) => &update.source,
+            Self::Reserves(update) => &update.source,
+        };
+        source.as_ref().expect("updates are built with a source")
+    }
+}
+
+/// Highest seq indexed so far
+pub async fn latest_seq(client: &Client) -> Result<u64> {
+    let row = client
+        .query_one("SELECT COALESCE(MAX(seq), 0) FROM indexed_transactions", &[])
+        .await?;
+    Ok(row.get::<_, i64>(0) as u64)
+}
+
+/// Updates of up to `PAGE` transactions after `after`, in order, and the last seq read
+pub async fn updates_after(client: &Client, after: u64) -> Result<(Vec<Update>, u64)> {
+    let after = after as i64;
+    let row = client
+        .query_one(
+            "SELECT MAX(seq) FROM (SELECT seq FROM indexed_transactions WHERE seq > $1 ORDER BY seq LIMIT $2) page",
+            &[&after, &PAGE],
+        )
+        .await?;
+    let Some(until) = row.get::<_, Option<i64>>(0) else {
+        return Ok((Vec::new(), after as u64));
+    };
+
+    let mut updates = Vec::new();
+    for row in client.query(TRADES, &[&after, &until]).await.context("failed to read trades")? {
+        updates.push(Update::Market(MarketUpdate {
+            source: Some(source(&row)),
+            market: row.get("market"),
+            event: Some(market_update::Event::Trade(pb::Trade {
+                user: row.get("wallet"),
+                outcome: outcome(row.get("outcome")) as i32,
+                is_buy: row.get::<_, &str>("side") == "buy",
+                amount_in: amount(&row, "amount_in"),
+                amount_out: amount(&row, "amount_out"),
+                fee: amount(&row, "fee"),
+            })),
+        }));
+    }
+    for row in client.query(POOL_UPDATES, &[&after, &until]).await.context("failed to read pool updates")? {
+        updates.push(Update::Market(MarketUpdate {
+            source: Some(source(&row)),
+            market: row.get("market"),
+            event: Some(market_update::Event::PoolUpdate(pb::PoolUpdate {
+                yes_reserve: amount(&row, "yes_reserve"),
+                no_reserve: amount(&row, "no_reserve"),
+                yes_price: amount(&row, "yes_price"),
+            })),
+        }));
+    }
+    for row in client.query(RESOLUTIONS, &[&after, &until]).await.context("failed to read resolutions")? {
+        updates.push(Update::Market(MarketUpdate {
+            source: Some(source(&row)),
+            market: row.get("market"),
+            event: Some(market_update::Event::Resolved(pb::MarketResolved {
+                outcome: outcome(row.get("outcome")) as i32,
+                resolved_at: row.get("resolved_at"),
+            })),
+        }));
+    }
+    // Wraps indexed before the event carried a total cannot be streamed
+    for row in client.query(WRAPS, &[&after, &until]).await.context("failed to read wraps")? {
+        updates.push(Update::Reserves(ReservesUpdate {
+            source: Some(source(&row)),
+            user: row.get("wallet"),
+            is_wrap: row.get::<_, &str>("kind") == "wrap",
+            amount: amount(&row, "amount"),
+            fee: amount(&row, "fee"),
+            total_wrapped: row.get::<_, Option<i64>>("total_wrapped").map(|total| total as u64),
+            shard: row.get::<_, Option<i16>>("shard").map(|shard| shard as u32),
+            shard_wrapped: row.get::<_, Option<i64>>("shard_wrapped").map(|total| total as u64),
+        }));
+    }
+
+    updates.sort_by_key(|update| (update.source().seq, update.source().event_index));
+    Ok((updates, until as u64))
+}
+
+const TRADES: &str = "
+    SELECT t.seq, t.signature, t.slot, e.event_index, e.market, e.wallet, e.outcome, e.side,
+           e.amount_in, e.amount_out, e.fee
+    FROM trades e JOIN indexed_transactions t USING (signature)
+    WHERE t.seq > $1 AND t.seq <= $2";
+
+const POOL_UPDATES: &str = "
+    SELECT t.seq, t.signature, t.slot, e.event_index, e.market, e.yes_reserve, e.no_reserve, e.yes_price
+    FROM pool_updates e JOIN indexed_transactions t USING (signature)
+    WHERE t.seq > $1 AND t.seq <= $2";
+
+const RESOLUTIONS: &str = "
+    SELECT t.seq, t.signature, t.slot, e.event_index, e.market, e.outcome, e.resolved_at
+    FROM resolutions e JOIN indexed_transactions t USING (signature)
+    WHERE t.seq > $1 AND t.seq <= $2";
+
+const WRAPS: &str = "
+    SELECT t.seq, t.signature, t.slot, e.event_index, e.wallet, e.kind, e.amount, e.fee,
+           e.total_wrapped, e.shard, e.shard_wrapped
+    FROM wraps e JOIN indexed_transactions t USING (signature)
+    WHERE t.seq > $1 AND t.seq <= $2 AND (e.total_wrapped IS NOT NULL OR e.shard_wrapped IS NOT NULL)";
+
+fn source(row: &Row) -> Source {
+    Source {
+        seq: row.get::<_, i64>("seq") as u64,
+        signature: row.get("signature"),
+        slot: row.get::<_, i64>("slot") as u64,
+        event_index: row.get::<_, i32>("event_index") as u32,
+    }
+}
+
+/// Amounts are stored as BIGINT and never negative
+fn amount(row: &Row, column: &str) -> u64 {
+    row.get::<_, i64>(column) as u64
+}
+
+fn outcome(name: &str) -> Outcome {
+    match name {
+        "yes" => Outcome::Yes,
+        "no" => Outcome::No,
+        "invalid" => Outcome::Invalid,
+        _ => Outcome::Unspecified,
+    }
+}
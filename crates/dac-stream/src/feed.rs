@@ -0,0 +1,149 @@
+//! Tailing the indexer and fanning updates out to subscribers
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use futures::{stream, StreamExt};
+use tokio::sync::{broadcast, mpsc, Notify};
+use tokio_postgres::{AsyncMessage, Client, NoTls};
+
+use crate::db::{self, Update};
+
+/// Channel the indexer notifies after each indexed transaction
+const CHANNEL: &str = "dac_indexed";
+/// Tail the tables even without a notification, in case one was missed
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Live updates buffered per subscriber before it counts as lagging
+const BUFFER: usize = 4_096;
+
+/// Live indexer updates, with replay of older ones for resuming subscribers
+#[derive(Clone)]
+pub struct Feed {
+    client: Arc<Client>,
+    sender: broadcast::Sender<Update>,
+}
+
+impl Feed {
+    /// Connect to the indexer database and start tailing it
+    pub async fn start(database_url: &str) -> Result<Self> {
+        let client = Arc::new(connect(database_url, None).await?);
+        let notify = Arc::new(Notify::new());
+        let listener = connect(database_url, Some(notify.clone())).await?;
+        listener
+            .batch_execute(&format!("LISTEN {}", CHANNEL))
+            .await
+            .context("failed to listen for indexed transactions")?;
+
+        let (sender, _) = broadcast::channel(BUFFER);
+        let feed = Self { client, sender };
+        let head = db::latest_seq(&feed.client).await?;
+        tokio::spawn(feed.clone().tail(head, notify, listener));
+        Ok(feed)
+    }
+
+    /// Broadcast each newly indexed transaction's updates, in seq order
+    ///
+    /// `listener` is held so the LISTEN stays registered.
+    async fn tail(self, mut head: u64, notify: Arc<Notify>, _listener: Client) {
+        loop {
+            tokio::select! {
+                _ = notify.notified() => {}
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+            }
+            loop {
+                match db::updates_after(&self.client, head).await {
+                    Ok((updates, until)) if until > head => {
+                        for update in updates {
+                            // No subscribers is not an error
+                            let _ = self.sender.send(update);
+                        }
+                        head = until;
+                    }
+                    Ok(_) => break,
+                    Err(e) => {
+                        log::error!("Failed to read new updates: {:#}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Updates matching `filter`: those after `from_seq` first, then live ones
+    ///
+    /// A subscriber too slow for the live stream gets an error and should
+    /// resubscribe from the last seq it received.
+    pub fn subscribe<F>(&self, from_seq: Option<u64>, filter: F) -> mpsc::Receiver<Result<Update>>
+    where
+        F: Fn(&Update) -> bool + Send + 'static,
+    {
+        // Subscribe before replaying, so nothing falls between the two
+        let mut live = self.sender.subscribe();
+        let client = self.client.clone();
+        let (sender, receiver) = mpsc::channel(BUFFER);
+        tokio::spawn(async move {
+            let mut replayed = 0;
+            if let Some(mut after) = from_seq {
+                loop {
+                    let (updates, until) = match db::updates_after(&client, after).await {
+                        Ok(page) => page,
+                        Err(e) => {
+                            let _ = sender.send(Err(e)).await;
+                            return;
+                        }
+                    };
+                    if until == after {
+                        break;
+                    }
+                    for update in updates.into_iter().filter(|update| filter(update)) {
+                        if sender.send(Ok(update)).await.is_err() {
+                            return;
+                        }
+                    }
+                    after = until;
+                }
+                replayed = after;
+            }
+            loop {
+                let update = match live.recv().await {
+                    Ok(update) => update,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        let _ = sender.send(Err(anyhow!("subscriber lagged by {} updates", skipped))).await;
+                        return;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                };
+                if update.source().seq > replayed && filter(&update) && sender.send(Ok(update)).await.is_err() {
+                    return;
+                }
+            }
+        });
+        receiver
+    }
+}
+
+/// Connect to Postgres, waking `notify` on every notification when given
+async fn connect(url: &str, notify: Option<Arc<Notify>>) -> Result<Client> {
+    let (client, mut connection) = tokio_postgres::connect(url, NoTls)
+        .await
+        .context("failed to connect to Postgres")?;
+    tokio::spawn(async move {
+        let mut messages = stream::poll_fn(move |cx| connection.poll_message(cx));
+        while let Some(message) = messages.next().await {
+            match message {
+                Ok(AsyncMessage::Notification(_)) => {
+                    if let Some(notify) = &notify {
+                        notify.notify_one();
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    log::error!("Postgres connection closed: {}", e);
+                    return;
+                }
+            }
+        }
+    });
+    Ok(client)
+}
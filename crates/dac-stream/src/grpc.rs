@@ -0,0 +1,63 @@
+//! The gRPC API
+
+use std::collections::HashSet;
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+use crate::db::Update;
+use crate::feed::Feed;
+use crate::pb::dac_stream_server::DacStream;
+use crate::pb::{MarketUpdate, ReservesUpdate, SubscribeMarketsRequest, SubscribeReservesRequest};
+
+type UpdateStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+pub struct Service {
+    pub feed: Feed,
+}
+
+#[tonic::async_trait]
+impl DacStream for Service {
+    type SubscribeMarketsStream = UpdateStream<MarketUpdate>;
+    type SubscribeReservesStream = UpdateStream<ReservesUpdate>;
+
+    async fn subscribe_markets(
+        &self,
+        request: Request<SubscribeMarketsRequest>,
+    ) -> Result<Response<Self::SubscribeMarketsStream>, Status> {
+        let request = request.into_inner();
+        let filter = market_filter(request.markets);
+        let updates = self.feed.subscribe(request.from_seq, move |update| match update {
+            Update::Market(update) => filter(update),
+            Update::Reserves(_) => false,
+        });
+        let stream = ReceiverStream::new(updates).map(|update| match update {
+            Ok(Update::Market(update)) => Ok(update),
+            Ok(Update::Reserves(_)) => unreachable!("filtered out"),
+            Err(e) => Err(Status::unavailable(e.to_string())),
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn subscribe_reserves(
+        &self,
+        request: Request<SubscribeReservesRequest>,
+    ) -> Result<Response<Self::SubscribeReservesStream>, Status> {
+        let request = request.into_inner();
+        let updates = self.feed.subscribe(request.from_seq, |update| matches!(update, Update::Reserves(_)));
+        let stream = ReceiverStream::new(updates).map(|update| match update {
+            Ok(Update::Reserves(update)) => Ok(update),
+            Ok(Update::Market(_)) => unreachable!("filtered out"),
+            Err(e) => Err(Status::unavailable(e.to_string())),
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// Whether a market update is for one of `markets` (all when empty)
+pub fn market_filter(markets: Vec<String>) -> impl Fn(&MarketUpdate) -> bool + Send + 'static {
+    let markets: HashSet<String> = markets.into_iter().collect();
+    move |update| markets.is_empty() || markets.contains(&update.market)
+}
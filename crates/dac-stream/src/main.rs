@@ -0,0 +1,66 @@
+//! DAC stream service
+//!
+//! Tails the tables of `dac-indexer` and streams what it indexes to market
+//! makers and integrators, so they can follow the protocol without running
+//! their own decoder:
+//! - per-market trades, pool updates (the AMM's book) and resolutions
+//! - wraps and unwraps with the USDC backing DAC in their vault shard after each
+//!
+//! Both streams are served over gRPC (see `proto/dac_stream.proto`) and as
+//! JSON over WebSocket. Clients resume from the last sequence number they
+//! saw; older updates are replayed from the database before going live.
+
+mod db;
+mod feed;
+mod grpc;
+mod ws;
+
+mod pb {
+    tonic::include_proto!("dac.stream.v1");
+}
+
+use std::future::IntoFuture;
+use std::net::SocketAddr;
+
+use anyhow::Result;
+use clap::Parser;
+use tonic::transport::Server;
+
+use crate::feed::Feed;
+use crate::pb::dac_stream_server::DacStreamServer;
+
+#[derive(Parser)]
+#[clap(name = "dac-stream", version, about = "Streams indexed DAC events over gRPC and WebSocket")]
+struct Args {
+    /// Postgres connection string of the indexer database
+    #[clap(long, env = "DATABASE_URL")]
+    database_url: String,
+
+    /// Address of the gRPC server
+    #[clap(long, env = "GRPC_ADDR", default_value = "0.0.0.0:50051")]
+    grpc_addr: SocketAddr,
+
+    /// Address of the WebSocket server
+    #[clap(long, env = "WS_ADDR", default_value = "0.0.0.0:8080")]
+    ws_addr: SocketAddr,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let args = Args::parse();
+    let feed = Feed::start(&args.database_url).await?;
+
+    let grpc = Server::builder()
+        .add_service(DacStreamServer::new(grpc::Service { feed: feed.clone() }))
+        .serve(args.grpc_addr);
+    let listener = tokio::net::TcpListener::bind(args.ws_addr).await?;
+    let websocket = axum::serve(listener, ws::router(feed));
+    log::info!("Serving gRPC on {} and WebSocket on {}", args.grpc_addr, args.ws_addr);
+
+    tokio::select! {
+        result = grpc => result?,
+        result = websocket.into_future() => result?,
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! The WebSocket API: the gRPC streams as JSON messages
+//!
+//! - `/ws/markets?market=<address>&market=...&from_seq=<seq>`
+//! - `/ws/reserves?from_seq=<seq>`
+//!
+//! A stream error is sent as `{"error": "..."}` before the socket closes.
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{RawQuery, State};
+use axum::response::Response;
+use axum::routing::get;
+use axum::Router;
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::db::Update;
+use crate::feed::Feed;
+use crate::grpc::market_filter;
+
+pub fn router(feed: Feed) -> Router {
+    Router::new()
+        .route("/ws/markets", get(markets))
+        .route("/ws/reserves", get(reserves))
+        .with_state(feed)
+}
+
+async fn markets(upgrade: WebSocketUpgrade, State(feed): State<Feed>, RawQuery(query): RawQuery) -> Response {
+    let params = parse_query(query.as_deref());
+    let markets = params.iter().filter(|(key, _)| key == "market").map(|(_, value)| value.clone()).collect();
+    let from_seq = from_seq(&params);
+    let filter = market_filter(markets);
+    upgrade.on_upgrade(move |socket| {
+        let updates = feed.subscribe(from_seq, move |update| match update {
+            Update::Market(update) => filter(update),
+            Update::Reserves(_) => false,
+        });
+        forward(socket, updates)
+    })
+}
+
+async fn reserves(upgrade: WebSocketUpgrade, State(feed): State<Feed>, RawQuery(query): RawQuery) -> Response {
+    let from_seq = from_seq(&parse_query(query.as_deref()));
+    upgrade.on_upgrade(move |socket| {
+        let updates = feed.subscribe(from_seq, |update| matches!(update, Update::Reserves(_)));
+        forward(socket, updates)
+    })
+}
+
+/// Send updates until the client leaves or the subscription fails
+async fn forward(mut socket: WebSocket, mut updates: mpsc::Receiver<anyhow::Result<Update>>) {
+    loop {
+        tokio::select! {
+            update = updates.recv() => {
+                let json = match update {
+                    Some(Ok(Update::Market(update))) => to_json(&update),
+                    Some(Ok(Update::Reserves(update))) => to_json(&update),
+                    Some(Err(e)) => to_json(&serde_json::json!({ "error": e.to_string() })),
+                    None => break,
+                };
+                if socket.send(Message::Text(json)).await.is_err() {
+                    return;
+                }
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => return,
+                // Clients have nothing to say after subscribing
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    let _ = socket.close().await;
+}
+
+fn to_json(value: &impl Serialize) -> String {
+    serde_json::to_string(value).expect("updates serialize to JSON")
+}
+
+/// Query parameters in order; repeated keys are kept
+fn parse_query(query: Option<&str>) -> Vec<(String, String)> {
+    query
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+fn from_seq(params: &[(String, String)]) -> Option<u64> {
+    params.iter().find(|(key, _)| key == "from_seq").and_then(|(_, value)| value.parse().ok())
+}
@@ -0,0 +1,323 @@
+//! DAC Test Utilities
+//!
+//! A LiteSVM fixture with the DAC token and dark market programs deployed
+//! and configured, for protocols integrating DAC to write integration tests
+//! without copying our setup.
+//!
+//! ```ignore
+//! let mut fixture = DacFixture::new();
+//! let alice = fixture.user();
+//! fixture.fund_and_wrap(&alice, 100_000_000);
+//! let market = fixture.create_resolved_market(Outcome::Yes, &[(&alice, 10_000_000)]);
+//! ```
+//!
+//! The programs are loaded from `target/deploy` of this workspace (run
+//! `anchor build` first), or from the directory in `DAC_PROGRAMS_DIR`.
+//! Helpers panic with the transaction logs when something fails; use
+//! [`DacFixture::send`] to assert on failures.
+
+use std::path::PathBuf;
+
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::spl_token;
+use dac_sdk::accounts::{DacConfig, Market, MarketConfig, Outcome, VaultShard};
+use dac_sdk::instructions::{self, PositionAccounts};
+use dac_sdk::{flows, pda};
+use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+use litesvm::LiteSVM;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+
+/// Decimals of the test USDC mint, and so of DAC
+pub const USDC_DECIMALS: u8 = 6;
+
+/// SOL airdropped to each new wallet
+const AIRDROP: u64 = 10 * LAMPORTS_PER_SOL;
+/// Vault shards opened by the fixture
+pub const VAULT_SHARDS: u8 = 2;
+/// DAC float of each vault shard, in base units (1,000,000 DAC)
+pub const SHARD_FLOAT: u64 = 1_000_000_000_000;
+
+/// Directory holding `dac_token.so` and `dark_market.so`
+fn programs_dir() -> PathBuf {
+    match std::env::var_os("DAC_PROGRAMS_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"),
+    }
+}
+
+/// A bank with both programs deployed, the mints created and the configs initialized
+///
+/// Wrap fees and swap fee sharing start off and wrap bundling is disabled,
+/// so every flow works standalone. Wraps and unwraps go through
+/// [`VAULT_SHARDS`] vault shards, each stocked with [`SHARD_FLOAT`].
+pub struct DacFixture {
+    pub svm: LiteSVM,
+    /// Authority of both configs, of the test USDC mint, and default resolver
+    pub authority: Keypair,
+    pub usdc_mint: Pubkey,
+    pub dac_mint: Pubkey,
+    /// DAC token account the swap fee share is routed to
+    pub fee_vault: Pubkey,
+}
+
+impl Default for DacFixture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DacFixture {
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        let dir = programs_dir();
+        for (program_id, file) in [
+            (dac_sdk::DAC_TOKEN_PROGRAM_ID, "dac_token.so"),
+            (dac_sdk::DARK_MARKET_PROGRAM_ID, "dark_market.so"),
+        ] {
+            let path = dir.join(file);
+            svm.add_program_from_file(program_id, &path)
+                .unwrap_or_else(|e| panic!("failed to load {}: {}", path.display(), e));
+        }
+
+        let authority = Keypair::new();
+        svm.airdrop(&authority.pubkey(), AIRDROP).expect("airdrop failed");
+        let mut fixture = Self {
+            svm,
+            authority,
+            usdc_mint: Pubkey::default(),
+            dac_mint: Pubkey::default(),
+            fee_vault: Pubkey::default(),
+        };
+
+        fixture.usdc_mint = fixture.create_mint(&fixture.authority.pubkey());
+        fixture.dac_mint = fixture.create_mint(&pda::mint_authority().0);
+        let authority = fixture.authority.pubkey();
+        fixture.fee_vault = pda::associated_token_address(&authority, &fixture.dac_mint);
+        fixture.execute(
+            &[
+                instructions::initialize_dac(&authority, &fixture.dac_mint, &fixture.usdc_mint),
+                instructions::initialize_market_config(&authority, &fixture.dac_mint),
+                instructions::configure_wrap_bundling(&authority, dac_sdk::DAC_TOKEN_PROGRAM_ID, false),
+                instructions::create_associated_token_account(&authority, &authority, &fixture.dac_mint),
+                instructions::configure_fee_sharing(&authority, fixture.fee_vault, 0),
+            ],
+            &[],
+        );
+        let config = fixture.dac_config();
+        fixture.execute(&flows::open_vault_shards(&authority, &config, VAULT_SHARDS, SHARD_FLOAT), &[]);
+        fixture
+    }
+
+    // ========================================================================
+    // Transactions
+    // ========================================================================
+
+    /// Send `instructions` paid by the authority, signed by it and `signers`
+    pub fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let mut all: Vec<&Keypair> = vec![&self.authority];
+        all.extend(signers.iter().copied().filter(|s| s.pubkey() != self.authority.pubkey()));
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.authority.pubkey()),
+            &all,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        // Identical transactions later in the test must not be rejected as duplicates
+        self.svm.expire_blockhash();
+        result
+    }
+
+    /// Send `instructions`, panicking with the logs if they fail
+    pub fn execute(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> TransactionMetadata {
+        match self.send(instructions, signers) {
+            Ok(meta) => meta,
+            Err(failed) => panic!("transaction failed: {}\n{}", failed.err, failed.meta.logs.join("\n")),
+        }
+    }
+
+    // ========================================================================
+    // Accounts and clock
+    // ========================================================================
+
+    /// A new wallet with SOL for fees and rent
+    pub fn user(&mut self) -> Keypair {
+        let user = Keypair::new();
+        self.svm.airdrop(&user.pubkey(), AIRDROP).expect("airdrop failed");
+        user
+    }
+
+    /// Decode an account, `None` if it does not exist or has another type
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> Option<T> {
+        let account = self.svm.get_account(address)?;
+        dac_sdk::accounts::deserialize(&account.data).ok()
+    }
+
+    /// Token balance of `owner`'s associated token account of `mint` (zero if missing)
+    pub fn balance(&self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        let address = pda::associated_token_address(owner, mint);
+        self.svm
+            .get_account(&address)
+            .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+            .map_or(0, |account| account.amount)
+    }
+
+    pub fn dac_config(&self) -> DacConfig {
+        self.account(&pda::dac_config().0).expect("DAC config missing")
+    }
+
+    /// Every vault shard, in index order
+    pub fn vault_shards(&self) -> Vec<VaultShard> {
+        let count = self.dac_config().shard_count;
+        (0..count).map(|index| self.account(&pda::vault_shard(index).0).expect("vault shard missing")).collect()
+    }
+
+    pub fn market(&self, address: &Pubkey) -> Market {
+        self.account(address).expect("market missing")
+    }
+
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    /// Move the clock to `unix_timestamp`
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    // ========================================================================
+    // Flows
+    // ========================================================================
+
+    /// Create a mint with `authority` and `USDC_DECIMALS`
+    pub fn create_mint(&mut self, authority: &Pubkey) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.authority.pubkey();
+        let rent = self.svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN);
+        self.execute(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &mint.pubkey(),
+                    rent,
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), authority, None, USDC_DECIMALS)
+                    .expect("invalid mint instruction"),
+            ],
+            &[&mint],
+        );
+        mint.pubkey()
+    }
+
+    /// Mint `amount` test USDC to `owner`'s associated token account
+    pub fn mint_usdc(&mut self, owner: &Pubkey, amount: u64) {
+        let authority = self.authority.pubkey();
+        let destination = pda::associated_token_address(owner, &self.usdc_mint);
+        self.execute(
+            &[
+                instructions::create_associated_token_account(&authority, owner, &self.usdc_mint),
+                spl_token::instruction::mint_to(&spl_token::ID, &self.usdc_mint, &destination, &authority, &[], amount)
+                    .expect("invalid mint instruction"),
+            ],
+            &[],
+        );
+    }
+
+    /// Mint `amount` USDC to `user` and wrap it all into DAC
+    pub fn fund_and_wrap(&mut self, user: &Keypair, amount: u64) {
+        self.mint_usdc(&user.pubkey(), amount);
+        let config = self.dac_config();
+        self.execute(&flows::wrap(&user.pubkey(), &config, amount), &[user]);
+    }
+
+    /// Create the next market, ending at `end_time` and resolved by `resolver`
+    pub fn create_market(&mut self, question: &str, end_time: i64, resolver: Pubkey) -> Pubkey {
+        let config: MarketConfig = self.account(&pda::market_config().0).expect("market config missing");
+        let authority = self.authority.pubkey();
+        let (market, ixs) = flows::create_market(
+            &authority,
+            &self.dac_mint,
+            config.market_count,
+            question.to_string(),
+            end_time,
+            resolver,
+        );
+        self.execute(&ixs, &[]);
+        market
+    }
+
+    /// Split `amount` of `user`'s DAC into YES and NO tokens of `market`
+    pub fn split(&mut self, user: &Keypair, market: &Pubkey, amount: u64) {
+        let state = self.market(market);
+        let owner = user.pubkey();
+        let position = PositionAccounts {
+            user: owner,
+            user_dac: pda::associated_token_address(&owner, &self.dac_mint),
+            user_yes: pda::associated_token_address(&owner, &state.yes_mint),
+            user_no: pda::associated_token_address(&owner, &state.no_mint),
+            track_portfolio: false,
+        };
+        self.execute(
+            &[
+                instructions::create_associated_token_account(&owner, &owner, &state.yes_mint),
+                instructions::create_associated_token_account(&owner, &owner, &state.no_mint),
+                instructions::split(market, &state, &position, amount),
+            ],
+            &[user],
+        );
+    }
+
+    /// Open `market`'s pool with `liquidity` DAC wrapped by the authority
+    pub fn create_pool(&mut self, market: &Pubkey, fee_bps: u16, liquidity: u64) {
+        let authority = self.authority.insecure_clone();
+        self.fund_and_wrap(&authority, liquidity);
+        let config = self.dac_config();
+        let liquidity = liquidity - config.fee(liquidity);
+        let state = self.market(market);
+        let user_dac = pda::associated_token_address(&authority.pubkey(), &self.dac_mint);
+        self.execute(
+            &[instructions::create_pool(&authority.pubkey(), &user_dac, market, &state, fee_bps, liquidity)],
+            &[],
+        );
+    }
+
+    /// Resolve `market` (resolved by the authority) once its end time has passed
+    pub fn resolve(&mut self, market: &Pubkey, outcome: Outcome) {
+        let end_time = self.market(market).end_time;
+        if self.now() < end_time {
+            self.warp_to(end_time);
+        }
+        let authority = self.authority.pubkey();
+        self.execute(&[instructions::resolve_market(market, &authority, outcome)], &[]);
+    }
+
+    /// A market resolved to `outcome` in which each holder wrapped and split the given DAC amount
+    ///
+    /// Holders end up with equal YES and NO balances, ready to redeem.
+    pub fn create_resolved_market(&mut self, outcome: Outcome, holders: &[(&Keypair, u64)]) -> Pubkey {
+        let end_time = self.now() + 60;
+        let resolver = self.authority.pubkey();
+        let market = self.create_market("Resolved test market", end_time, resolver);
+        for &(holder, amount) in holders {
+            self.fund_and_wrap(holder, amount);
+            self.split(holder, &market, amount);
+        }
+        self.resolve(&market, outcome);
+        market
+    }
+}
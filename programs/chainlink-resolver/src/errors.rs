@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ResolverError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Market is not open")]
+    MarketNotOpen,
+    #[msg("Market has not ended")]
+    MarketNotEnded,
+    #[msg("Settle delay is too short")]
+    InvalidSettleDelay,
+    #[msg("Feed id has an unsupported report schema")]
+    UnsupportedFeed,
+    #[msg("Account is not the Data Streams verifier program")]
+    InvalidVerifier,
+    #[msg("Verifier returned no report, or one that could not be decoded")]
+    InvalidReport,
+    #[msg("Report is for another feed")]
+    FeedMismatch,
+    #[msg("Report was observed before the market ended")]
+    ReportTooEarly,
+    #[msg("A report observed no later is already posted")]
+    ReportNotEarlier,
+    #[msg("Settlement report is final")]
+    ConditionSettled,
+    #[msg("Condition is for another market")]
+    MarketMismatch,
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// A verified report became a market's settlement price
+#[event]
+pub struct ReportPosted {
+    pub market: Pubkey,
+    pub feed_id: [u8; 32],
+    /// Unix timestamp of the report's observations
+    pub observed_at: i64,
+    /// Benchmark price, in the stream's fixed point (18 decimals)
+    pub price: i128,
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{get_return_data, invoke};
+use dark_market::state::Market;
+
+use crate::errors::ResolverError;
+use crate::events::ReportPosted;
+use crate::state::*;
+use crate::verifier::{self, Report, SUPPORTED_REPORT_VERSIONS, VERIFIER_PROGRAM_ID};
+use crate::PRICE_CONDITION_SEED;
+
+/// Settle `market` on the price of `feed_id` compared to `strike` (market creator only)
+///
+/// The market must name the registry entry of this adapter as its resolver
+/// for the condition to take effect.
+pub fn create_price_condition(
+    ctx: Context<CreatePriceCondition>,
+    feed_id: [u8; 32],
+    strike: i128,
+    comparison: Comparison,
+    settle_delay: i64,
+) -> Result<()> {
+    let version = u16::from_be_bytes([feed_id[0], feed_id[1]]);
+    require!(SUPPORTED_REPORT_VERSIONS.contains(&version), ResolverError::UnsupportedFeed);
+    require!(settle_delay >= MIN_SETTLE_DELAY, ResolverError::InvalidSettleDelay);
+
+    let condition = &mut ctx.accounts.condition;
+    condition.market = ctx.accounts.market.key();
+    condition.feed_id = feed_id;
+    condition.strike = strike;
+    condition.comparison = comparison;
+    condition.settle_delay = settle_delay;
+    condition.observed_at = 0;
+    condition.price = 0;
+    condition.bump = ctx.bumps.condition;
+
+    msg!("Market {} settles on price {:?} {}", ctx.accounts.market.load()?.id, comparison, strike);
+    Ok(())
+}
+
+/// Verify a signed Data Streams report and post it as the settlement price (permissionless)
+///
+/// `signed_report` is the snappy-compressed full report as served by Data
+/// Streams. The verifier checks the DON's signatures; the report must be
+/// of the condition's feed and observed at or after the market's end, and
+/// replaces a posted one only if observed earlier, until the settle delay
+/// has passed.
+pub fn post_report(ctx: Context<PostReport>, signed_report: Vec<u8>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let end_time = ctx.accounts.market.load()?.end_time;
+    require!(now >= end_time, ResolverError::MarketNotEnded);
+    require!(!ctx.accounts.condition.is_settled(end_time, now), ResolverError::ConditionSettled);
+
+    let ix = verifier::verify(
+        &ctx.accounts.access_controller.key(),
+        &ctx.accounts.poster.key(),
+        &ctx.accounts.config_account.key(),
+        signed_report,
+    );
+    invoke(
+        &ix,
+        &[
+            ctx.accounts.verifier_account.to_account_info(),
+            ctx.accounts.access_controller.to_account_info(),
+            ctx.accounts.poster.to_account_info(),
+            ctx.accounts.config_account.to_account_info(),
+            ctx.accounts.verifier_program.to_account_info(),
+        ],
+    )?;
+
+    let (returned_by, data) = get_return_data().ok_or(ResolverError::InvalidReport)?;
+    require_keys_eq!(returned_by, VERIFIER_PROGRAM_ID, ResolverError::InvalidReport);
+    let report = Report::decode(&data).ok_or(ResolverError::InvalidReport)?;
+
+    let condition = &mut ctx.accounts.condition;
+    require!(report.feed_id == condition.feed_id, ResolverError::FeedMismatch);
+    require!(report.observations_timestamp >= end_time, ResolverError::ReportTooEarly);
+    require!(
+        !condition.has_report() || report.observations_timestamp < condition.observed_at,
+        ResolverError::ReportNotEarlier
+    );
+    condition.observed_at = report.observations_timestamp;
+    condition.price = report.price;
+
+    emit!(ReportPosted {
+        market: condition.market,
+        feed_id: report.feed_id,
+        observed_at: report.observations_timestamp,
+        price: report.price,
+    });
+
+    msg!("Report observed at {} posted: price {}", report.observations_timestamp, report.price);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreatePriceCondition<'info> {
+    /// The price condition (one per market)
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PriceCondition::LEN,
+        seeds = [PRICE_CONDITION_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub condition: Account<'info, PriceCondition>,
+
+    /// The market to settle
+    #[account(
+        has_one = creator @ ResolverError::Unauthorized,
+        constraint = market.load()?.is_open() @ ResolverError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PostReport<'info> {
+    /// The market's price condition
+    #[account(
+        mut,
+        seeds = [PRICE_CONDITION_SEED, market.key().as_ref()],
+        bump = condition.bump,
+    )]
+    pub condition: Account<'info, PriceCondition>,
+
+    /// The market the report settles
+    pub market: AccountLoader<'info, Market>,
+
+    /// The verifier's state account
+    /// CHECK: Address checked; validated by the verifier
+    #[account(address = verifier::verifier_account() @ ResolverError::InvalidVerifier)]
+    pub verifier_account: UncheckedAccount<'info>,
+
+    /// The verifier's access controller
+    /// CHECK: Validated by the verifier against its state
+    pub access_controller: UncheckedAccount<'info>,
+
+    /// The verifier's config for the report's config digest
+    /// CHECK: Validated by the verifier against the report
+    pub config_account: UncheckedAccount<'info>,
+
+    /// Anyone posting a report
+    pub poster: Signer<'info>,
+
+    /// CHECK: Address checked
+    #[account(address = VERIFIER_PROGRAM_ID @ ResolverError::InvalidVerifier)]
+    pub verifier_program: UncheckedAccount<'info>,
+}
@@ -0,0 +1,5 @@
+pub mod condition;
+pub mod resolve;
+
+pub use condition::*;
+pub use resolve::*;
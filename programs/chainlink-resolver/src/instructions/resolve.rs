@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use dark_market::state::{Market, Outcome};
+
+use crate::errors::ResolverError;
+use crate::state::*;
+use crate::PRICE_CONDITION_SEED;
+
+/// The dark market's `ResolverInterface`: the outcome of `market`, once its report is final
+///
+/// Invoked by `resolve_with_adapter` with the market followed by its price
+/// condition. `None` while no report is posted or the settle delay is
+/// running.
+pub fn resolve(ctx: Context<Resolve>, market: Pubkey, end_time: i64) -> Result<Option<Outcome>> {
+    require_keys_eq!(ctx.accounts.market.key(), market, ResolverError::MarketMismatch);
+
+    let condition = &ctx.accounts.condition;
+    if !condition.is_settled(end_time, Clock::get()?.unix_timestamp) {
+        msg!("Market {} has no final report yet", ctx.accounts.market.load()?.id);
+        return Ok(None);
+    }
+
+    let outcome = condition.outcome();
+    msg!("Market {} settles {:?} at price {}", ctx.accounts.market.load()?.id, outcome, condition.price);
+    Ok(Some(outcome))
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct Resolve<'info> {
+    /// The market being resolved (passed read-only by the dark market)
+    pub market: AccountLoader<'info, Market>,
+
+    /// The market's price condition
+    #[account(
+        seeds = [PRICE_CONDITION_SEED, market.key().as_ref()],
+        bump = condition.bump,
+    )]
+    pub condition: Account<'info, PriceCondition>,
+}
@@ -0,0 +1,60 @@
+//! Chainlink Resolver Program
+//!
+//! Resolver adapter settling price markets on Chainlink Data Streams, for
+//! assets Chainlink covers better than Pyth or Switchboard. A market's
+//! creator attaches a price condition (feed, strike and comparison); once
+//! the market ends, anyone posts a signed report of the feed, which the
+//! Data Streams verifier checks against the DON's signatures before its
+//! benchmark price is stored. The dark market then resolves the market
+//! through its registry entry for this adapter.
+//!
+//! Key features:
+//! - Report signatures verified on-chain by the Data Streams verifier
+//! - Earliest report observed at or after the market's end settles it
+//! - Settle delay during which an earlier report can still be posted
+
+// Anchor 0.31's `#[program]` expansion still calls the deprecated `AccountInfo::realloc`
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+use dark_market::state::Outcome;
+
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod state;
+pub mod verifier;
+
+use instructions::*;
+use state::Comparison;
+
+declare_id!("9ugPtMkc5GCubSFLcry2BBMW5cft2hDjsyXSzJe2T2D4");
+
+/// Seeds for a market's price condition (followed by the market's key)
+pub const PRICE_CONDITION_SEED: &[u8] = b"price_condition";
+
+#[program]
+pub mod chainlink_resolver {
+    use super::*;
+
+    /// Settle a market on a Data Streams price (market creator only)
+    pub fn create_price_condition(
+        ctx: Context<CreatePriceCondition>,
+        feed_id: [u8; 32],
+        strike: i128,
+        comparison: Comparison,
+        settle_delay: i64,
+    ) -> Result<()> {
+        instructions::create_price_condition(ctx, feed_id, strike, comparison, settle_delay)
+    }
+
+    /// Verify a signed report and post it as the market's settlement price
+    pub fn post_report(ctx: Context<PostReport>, signed_report: Vec<u8>) -> Result<()> {
+        instructions::post_report(ctx, signed_report)
+    }
+
+    /// Answer the dark market's resolver interface
+    pub fn resolve(ctx: Context<Resolve>, market: Pubkey, end_time: i64) -> Result<Option<Outcome>> {
+        instructions::resolve(ctx, market, end_time)
+    }
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use dark_market::state::Outcome;
+
+/// Shortest wait between a market's end and settlement on its report
+pub const MIN_SETTLE_DELAY: i64 = 5 * 60;
+
+/// How the settlement price is compared to the strike
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Comparison {
+    /// YES if the price is at or above the strike
+    AtOrAbove,
+    /// YES if the price is below the strike
+    Below,
+}
+
+/// The price question a market settles on
+///
+/// Reports are posted by anyone once the market ends. The earliest one
+/// observed at or after the end time is the settlement price; a report
+/// observed earlier still replaces it until `settle_delay` has passed, so a
+/// late poster cannot pick a convenient price.
+#[account]
+pub struct PriceCondition {
+    /// The market this condition resolves
+    pub market: Pubkey,
+    /// The Data Streams feed id (its first two bytes are the report schema)
+    pub feed_id: [u8; 32],
+    /// Strike price, in the stream's fixed point (18 decimals)
+    pub strike: i128,
+    /// How the settlement price is compared to the strike
+    pub comparison: Comparison,
+    /// Seconds after the market's end before it can settle
+    pub settle_delay: i64,
+    /// Observation timestamp of the settlement report (0 while none is posted)
+    pub observed_at: i64,
+    /// Benchmark price of the settlement report
+    pub price: i128,
+    /// Bump for the condition PDA
+    pub bump: u8,
+}
+
+impl PriceCondition {
+    pub const LEN: usize = 32 + 32 + 16 + 1 + 8 + 8 + 16 + 1; // 114 bytes
+
+    pub fn has_report(&self) -> bool {
+        self.observed_at != 0
+    }
+
+    /// Whether the posted report is final for a market ending at `end_time`
+    pub fn is_settled(&self, end_time: i64, now: i64) -> bool {
+        self.has_report() && now >= end_time.saturating_add(self.settle_delay)
+    }
+
+    /// Outcome of the posted report's price
+    pub fn outcome(&self) -> Outcome {
+        let yes = match self.comparison {
+            Comparison::AtOrAbove => self.price >= self.strike,
+            Comparison::Below => self.price < self.strike,
+        };
+        if yes {
+            Outcome::Yes
+        } else {
+            Outcome::No
+        }
+    }
+}
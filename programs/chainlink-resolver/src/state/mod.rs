@@ -0,0 +1,3 @@
+mod condition;
+
+pub use condition::*;
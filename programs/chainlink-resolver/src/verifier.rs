@@ -0,0 +1,98 @@
+//! The Chainlink Data Streams verifier, invoked by hand
+//!
+//! The Data Streams SDK pins an older Solana, so the one instruction the
+//! adapter invokes is encoded here: `verify` checks the DON's signatures
+//! over a signed report against the verifier's config for the report's
+//! config digest, and returns the report through return data. The report
+//! is then decoded from its ABI encoding.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::pubkey;
+
+pub const VERIFIER_PROGRAM_ID: Pubkey = pubkey!("Gt9S41PtjR58CbG9JhJ3J6vxesqrNAswbWYbLNTMZA3c");
+pub const VERIFIER_SEED: &[u8] = b"verifier";
+
+/// Report schemas carrying the benchmark price in the same position (v2, v3 crypto and v4 RWA)
+pub const SUPPORTED_REPORT_VERSIONS: [u16; 3] = [2, 3, 4];
+
+/// ABI word size
+const WORD: usize = 32;
+/// Word of the observation timestamp
+const OBSERVATIONS_TIMESTAMP_WORD: usize = 2;
+/// Word of the benchmark price
+const PRICE_WORD: usize = 6;
+
+/// The verifier's state account
+pub fn verifier_account() -> Pubkey {
+    Pubkey::find_program_address(&[VERIFIER_SEED], &VERIFIER_PROGRAM_ID).0
+}
+
+/// `verify` of a snappy-compressed signed report, signed by `user`
+///
+/// `config_account` is the verifier's config of the report's config digest
+/// (the PDA of its first 32 bytes); the verifier rejects any other.
+pub fn verify(
+    access_controller: &Pubkey,
+    user: &Pubkey,
+    config_account: &Pubkey,
+    signed_report: Vec<u8>,
+) -> Instruction {
+    let mut data = hash(b"global:verify").to_bytes()[..8].to_vec();
+    signed_report.serialize(&mut data).expect("signed report serializes");
+    Instruction {
+        program_id: VERIFIER_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(verifier_account(), false),
+            AccountMeta::new_readonly(*access_controller, false),
+            AccountMeta::new_readonly(*user, true),
+            AccountMeta::new_readonly(*config_account, false),
+        ],
+        data,
+    }
+}
+
+/// The fields of a verified report a price market settles on
+#[derive(Clone, Copy, Debug)]
+pub struct Report {
+    /// Stream id; its first two bytes are the report schema version
+    pub feed_id: [u8; 32],
+    /// Unix timestamp of the observations the price comes from
+    pub observations_timestamp: i64,
+    /// Benchmark price, in the stream's fixed point (18 decimals)
+    pub price: i128,
+}
+
+impl Report {
+    /// Decode a verified report of a supported schema
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let feed_id: [u8; 32] = word(data, 0)?;
+        let version = u16::from_be_bytes([feed_id[0], feed_id[1]]);
+        if !SUPPORTED_REPORT_VERSIONS.contains(&version) {
+            return None;
+        }
+        let timestamp = word(data, OBSERVATIONS_TIMESTAMP_WORD)?;
+        let observations_timestamp = u32::from_be_bytes(timestamp[28..].try_into().ok()?);
+        if timestamp[..28].iter().any(|&b| b != 0) {
+            return None;
+        }
+        Some(Self {
+            feed_id,
+            observations_timestamp: observations_timestamp as i64,
+            price: int(&word(data, PRICE_WORD)?)?,
+        })
+    }
+}
+
+/// The `index`th ABI word of `data`
+fn word(data: &[u8], index: usize) -> Option<[u8; 32]> {
+    data.get(index * WORD..(index + 1) * WORD)?.try_into().ok()
+}
+
+/// A sign-extended ABI integer, if it fits in an `i128`
+fn int(word: &[u8; 32]) -> Option<i128> {
+    let value = i128::from_be_bytes(word[16..].try_into().ok()?);
+    let extension = if value < 0 { 0xff } else { 0 };
+    word[..16].iter().all(|&b| b == extension).then_some(value)
+}
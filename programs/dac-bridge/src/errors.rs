@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum BridgeError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Token account is not for the DAC mint")]
+    MintMismatch,
+    #[msg("Account is not the NTT manager program")]
+    InvalidNttManager,
+    #[msg("Peer address cannot be zero")]
+    InvalidPeer,
+    #[msg("Recipient address cannot be zero")]
+    InvalidRecipient,
+    #[msg("Outbound rate limit exceeded")]
+    RateLimitExceeded,
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// DAC locked for a transfer to a peer chain
+#[event]
+pub struct BridgedOut {
+    pub sender: Pubkey,
+    pub amount: u64,
+    /// Wormhole chain id of the destination
+    pub chain_id: u16,
+    /// Recipient on the destination chain, left-padded to 32 bytes
+    pub recipient: [u8; 32],
+    /// The manager's record of the transfer, released to the transceivers
+    pub outbox_item: Pubkey,
+}
@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::Mint;
+use dark_market::state::MarketConfig;
+use dark_market::MARKET_CONFIG_SEED;
+
+use crate::errors::BridgeError;
+use crate::ntt::{self, ChainId, SetPeerArgs};
+use crate::state::*;
+use crate::{BRIDGE_SEED, PEER_SEED};
+
+/// Create the bridge (dark market config authority only)
+///
+/// The NTT manager's ownership must then be transferred to the bridge PDA,
+/// so its peers can only be set through `register_peer`.
+pub fn initialize_bridge(
+    ctx: Context<InitializeBridge>,
+    admin: Pubkey,
+    ntt_manager: Pubkey,
+    outbound_limit: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let bridge = &mut ctx.accounts.bridge;
+    bridge.admin = admin;
+    bridge.ntt_manager = ntt_manager;
+    bridge.dac_mint = ctx.accounts.dac_mint.key();
+    bridge.outbound = RateLimit { limit: outbound_limit, capacity: outbound_limit, last_update: now };
+    bridge.bump = ctx.bumps.bridge;
+
+    msg!("Bridge initialized over NTT manager {} with a {} DAC outbound limit", ntt_manager, outbound_limit);
+    Ok(())
+}
+
+/// Register or update the peer manager on `chain_id` (admin only)
+///
+/// Mirrored into the NTT manager, which enforces `inbound_limit` on
+/// transfers from the peer.
+pub fn register_peer(
+    ctx: Context<RegisterPeer>,
+    chain_id: u16,
+    address: [u8; 32],
+    decimals: u8,
+    inbound_limit: u64,
+) -> Result<()> {
+    require!(address != [0; 32], BridgeError::InvalidPeer);
+
+    let peer = &mut ctx.accounts.peer;
+    peer.chain_id = chain_id;
+    peer.address = address;
+    peer.decimals = decimals;
+    peer.inbound_limit = inbound_limit;
+    peer.bump = ctx.bumps.peer;
+
+    let bridge = &ctx.accounts.bridge;
+    let ix = ntt::set_peer(
+        &bridge.ntt_manager,
+        &ctx.accounts.admin.key(),
+        &bridge.key(),
+        SetPeerArgs { chain_id: ChainId { id: chain_id }, address, limit: inbound_limit, token_decimals: decimals },
+    );
+    let seeds = &[BRIDGE_SEED, &[bridge.bump]];
+    let signer_seeds = &[&seeds[..]];
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.admin.to_account_info(),
+            bridge.to_account_info(),
+            ctx.accounts.ntt_config.to_account_info(),
+            ctx.accounts.ntt_peer.to_account_info(),
+            ctx.accounts.ntt_inbox_rate_limit.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.ntt_manager.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    msg!("Peer on chain {} registered with a {} DAC inbound limit", chain_id, inbound_limit);
+    Ok(())
+}
+
+/// Set the cap on DAC bridged out per window (admin only)
+///
+/// A limit of zero stops outbound transfers.
+pub fn set_outbound_limit(ctx: Context<SetOutboundLimit>, limit: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.bridge.outbound.set_limit(limit, now);
+
+    msg!("Outbound limit set to {} DAC", limit);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeBridge<'info> {
+    /// The dark market config, whose authority sets up the bridge
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        seeds::program = dark_market::ID,
+        has_one = authority @ BridgeError::Unauthorized,
+        has_one = dac_mint @ BridgeError::MintMismatch,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The bridge to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Bridge::LEN,
+        seeds = [BRIDGE_SEED],
+        bump
+    )]
+    pub bridge: Box<Account<'info, Bridge>>,
+
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct RegisterPeer<'info> {
+    #[account(
+        seeds = [BRIDGE_SEED],
+        bump = bridge.bump,
+        has_one = admin @ BridgeError::Unauthorized,
+    )]
+    pub bridge: Box<Account<'info, Bridge>>,
+
+    /// The peer (created on first registration)
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + Peer::LEN,
+        seeds = [PEER_SEED, &chain_id.to_be_bytes()],
+        bump
+    )]
+    pub peer: Box<Account<'info, Peer>>,
+
+    /// CHECK: must be the bridge's NTT manager
+    #[account(address = bridge.ntt_manager @ BridgeError::InvalidNttManager)]
+    pub ntt_manager: UncheckedAccount<'info>,
+
+    /// CHECK: the manager's config, checked by the manager
+    pub ntt_config: UncheckedAccount<'info>,
+
+    /// CHECK: the manager's peer account, created or checked by the manager
+    #[account(mut)]
+    pub ntt_peer: UncheckedAccount<'info>,
+
+    /// CHECK: the manager's inbound rate limit of the peer, created or checked by the manager
+    #[account(mut)]
+    pub ntt_inbox_rate_limit: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetOutboundLimit<'info> {
+    #[account(
+        mut,
+        seeds = [BRIDGE_SEED],
+        bump = bridge.bump,
+        has_one = admin @ BridgeError::Unauthorized,
+    )]
+    pub bridge: Box<Account<'info, Bridge>>,
+
+    pub admin: Signer<'info>,
+}
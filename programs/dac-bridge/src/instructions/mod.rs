@@ -0,0 +1,5 @@
+pub mod admin;
+pub mod transfer;
+
+pub use admin::*;
+pub use transfer::*;
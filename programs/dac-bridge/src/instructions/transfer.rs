@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token::{self, Approve, Mint, Token, TokenAccount};
+
+use crate::errors::BridgeError;
+use crate::events::BridgedOut;
+use crate::ntt::{self, ChainId, TransferArgs, TransferLockAccounts};
+use crate::state::*;
+use crate::{BRIDGE_SEED, PEER_SEED};
+
+/// Bridge `amount` DAC to `recipient` on the peer chain `chain_id`
+///
+/// The DAC is locked in the NTT manager's custody and released again when
+/// it is bridged back, so it stays backed by the USDC vault while away. The
+/// manager's transceivers then carry the transfer to the peer. Fails rather
+/// than queueing when either rate limit is exhausted.
+pub fn bridge_out(ctx: Context<BridgeOut>, amount: u64, chain_id: u16, recipient: [u8; 32]) -> Result<()> {
+    require!(amount > 0, BridgeError::ZeroAmount);
+    require!(recipient != [0; 32], BridgeError::InvalidRecipient);
+
+    let now = Clock::get()?.unix_timestamp;
+    let bridge = &mut ctx.accounts.bridge;
+    require!(bridge.outbound.consume(amount, now), BridgeError::RateLimitExceeded);
+
+    let args = TransferArgs {
+        amount,
+        recipient_chain: ChainId { id: chain_id },
+        recipient_address: recipient,
+        should_queue: false,
+    };
+
+    // The manager pulls the DAC through a session authority bound to the arguments
+    let approve_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Approve {
+            to: ctx.accounts.sender_dac.to_account_info(),
+            delegate: ctx.accounts.ntt_session_authority.to_account_info(),
+            authority: ctx.accounts.sender.to_account_info(),
+        },
+    );
+    token::approve(approve_ctx, amount)?;
+
+    let accounts = TransferLockAccounts {
+        payer: ctx.accounts.sender.key(),
+        mint: ctx.accounts.dac_mint.key(),
+        from: ctx.accounts.sender_dac.key(),
+        sender: ctx.accounts.sender.key(),
+        token_program: ctx.accounts.token_program.key(),
+        outbox_item: ctx.accounts.ntt_outbox_item.key(),
+        custody: ctx.accounts.ntt_custody.key(),
+    };
+    let ix = ntt::transfer_lock(&bridge.ntt_manager, &accounts, args);
+    invoke(
+        &ix,
+        &[
+            ctx.accounts.sender.to_account_info(),
+            ctx.accounts.ntt_config.to_account_info(),
+            ctx.accounts.dac_mint.to_account_info(),
+            ctx.accounts.sender_dac.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.ntt_outbox_item.to_account_info(),
+            ctx.accounts.ntt_outbox_rate_limit.to_account_info(),
+            ctx.accounts.ntt_custody.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.ntt_inbox_rate_limit.to_account_info(),
+            ctx.accounts.ntt_peer.to_account_info(),
+            ctx.accounts.ntt_session_authority.to_account_info(),
+            ctx.accounts.ntt_token_authority.to_account_info(),
+            ctx.accounts.ntt_manager.to_account_info(),
+        ],
+    )?;
+
+    emit!(BridgedOut {
+        sender: ctx.accounts.sender.key(),
+        amount,
+        chain_id,
+        recipient,
+        outbox_item: ctx.accounts.ntt_outbox_item.key(),
+    });
+
+    msg!("Bridged {} DAC out to chain {}", amount, chain_id);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(amount: u64, chain_id: u16)]
+pub struct BridgeOut<'info> {
+    #[account(
+        mut,
+        seeds = [BRIDGE_SEED],
+        bump = bridge.bump,
+        has_one = dac_mint @ BridgeError::MintMismatch,
+    )]
+    pub bridge: Box<Account<'info, Bridge>>,
+
+    /// The destination chain's peer, which must be registered
+    #[account(
+        seeds = [PEER_SEED, &chain_id.to_be_bytes()],
+        bump = peer.bump,
+    )]
+    pub peer: Box<Account<'info, Peer>>,
+
+    #[account(mut)]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    /// Sender's DAC token account
+    #[account(
+        mut,
+        constraint = sender_dac.mint == dac_mint.key() @ BridgeError::MintMismatch,
+        constraint = sender_dac.owner == sender.key() @ BridgeError::Unauthorized,
+    )]
+    pub sender_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// CHECK: must be the bridge's NTT manager
+    #[account(address = bridge.ntt_manager @ BridgeError::InvalidNttManager)]
+    pub ntt_manager: UncheckedAccount<'info>,
+
+    /// CHECK: the manager's config, checked by the manager
+    pub ntt_config: UncheckedAccount<'info>,
+
+    /// Fresh keypair the manager records the outbound transfer at
+    #[account(mut)]
+    pub ntt_outbox_item: Signer<'info>,
+
+    /// CHECK: the manager's outbound rate limit, checked by the manager
+    #[account(mut)]
+    pub ntt_outbox_rate_limit: UncheckedAccount<'info>,
+
+    /// CHECK: the manager's DAC custody, checked by the manager
+    #[account(mut)]
+    pub ntt_custody: UncheckedAccount<'info>,
+
+    /// CHECK: the manager's inbound rate limit of the peer, checked by the manager
+    #[account(mut)]
+    pub ntt_inbox_rate_limit: UncheckedAccount<'info>,
+
+    /// CHECK: the manager's peer account, checked by the manager
+    pub ntt_peer: UncheckedAccount<'info>,
+
+    /// CHECK: the session authority of this transfer, checked by the manager
+    pub ntt_session_authority: UncheckedAccount<'info>,
+
+    /// CHECK: owner of the manager's custody, checked by the manager
+    pub ntt_token_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,74 @@
+//! DAC Bridge Program
+//!
+//! Solana hub of DAC's Wormhole Native Token Transfers (NTT) deployment. The
+//! NTT manager runs in locking mode here: DAC bridged out is locked in its
+//! custody and released when it comes back, while the managers on the EVM
+//! chains burn and mint. DAC on every chain is therefore backed by the one
+//! USDC vault, and the DAC supply on Solana still equals the USDC wrapped.
+//!
+//! The bridge PDA owns the NTT manager, so peers are registered through the
+//! bridge, and outbound transfers go through it to be capped by its rate
+//! limit on top of the manager's own. Transfers sent to the manager directly
+//! skip the bridge's limit but not the manager's, which should be set no
+//! higher.
+//!
+//! Key features:
+//! - Peer registry per Wormhole chain, mirrored into the NTT manager
+//! - Outbound rate limit refilling linearly over 24 hours
+//! - Inbound limits per peer, enforced by the NTT manager
+
+// Anchor 0.31's `#[program]` expansion still calls the deprecated `AccountInfo::realloc`
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod ntt;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("CkgUCHxXJWiCTve4qbE81E4g7bsNrBx86iZKB8u77aZU");
+
+/// Seeds for the bridge PDA, owner of the NTT manager
+pub const BRIDGE_SEED: &[u8] = b"bridge";
+/// Seeds for a peer (followed by its big-endian Wormhole chain id)
+pub const PEER_SEED: &[u8] = b"peer";
+
+#[program]
+pub mod dac_bridge {
+    use super::*;
+
+    /// Create the bridge over an NTT manager (dark market config authority only)
+    pub fn initialize_bridge(
+        ctx: Context<InitializeBridge>,
+        admin: Pubkey,
+        ntt_manager: Pubkey,
+        outbound_limit: u64,
+    ) -> Result<()> {
+        instructions::initialize_bridge(ctx, admin, ntt_manager, outbound_limit)
+    }
+
+    /// Register or update the peer on a chain (admin only)
+    pub fn register_peer(
+        ctx: Context<RegisterPeer>,
+        chain_id: u16,
+        address: [u8; 32],
+        decimals: u8,
+        inbound_limit: u64,
+    ) -> Result<()> {
+        instructions::register_peer(ctx, chain_id, address, decimals, inbound_limit)
+    }
+
+    /// Set the outbound rate limit (admin only)
+    pub fn set_outbound_limit(ctx: Context<SetOutboundLimit>, limit: u64) -> Result<()> {
+        instructions::set_outbound_limit(ctx, limit)
+    }
+
+    /// Lock DAC in the NTT manager's custody for a recipient on a peer chain
+    pub fn bridge_out(ctx: Context<BridgeOut>, amount: u64, chain_id: u16, recipient: [u8; 32]) -> Result<()> {
+        instructions::bridge_out(ctx, amount, chain_id, recipient)
+    }
+}
@@ -0,0 +1,139 @@
+//! Instructions of the Wormhole NTT manager, built by hand
+//!
+//! The NTT crates pin an older Anchor, so the two instructions the bridge
+//! invokes are encoded here from the manager's IDL: the Anchor discriminator
+//! followed by the Borsh arguments, with accounts in the order of the
+//! manager's `SetPeer` and `TransferLock` contexts.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::keccak;
+
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const PEER_SEED: &[u8] = b"peer";
+pub const INBOX_RATE_LIMIT_SEED: &[u8] = b"inbox_rate_limit";
+pub const OUTBOX_RATE_LIMIT_SEED: &[u8] = b"outbox_rate_limit";
+pub const SESSION_AUTHORITY_SEED: &[u8] = b"session_authority";
+pub const TOKEN_AUTHORITY_SEED: &[u8] = b"token_authority";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ChainId {
+    pub id: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct SetPeerArgs {
+    pub chain_id: ChainId,
+    pub address: [u8; 32],
+    pub limit: u64,
+    pub token_decimals: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TransferArgs {
+    pub amount: u64,
+    pub recipient_chain: ChainId,
+    pub recipient_address: [u8; 32],
+    pub should_queue: bool,
+}
+
+impl TransferArgs {
+    /// Hash binding the session authority to these exact arguments
+    pub fn keccak256(&self) -> [u8; 32] {
+        let mut data = Vec::new();
+        self.serialize(&mut data).expect("transfer args serialize");
+        keccak::hash(&data).to_bytes()
+    }
+}
+
+pub fn config(manager: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CONFIG_SEED], manager).0
+}
+
+pub fn peer(manager: &Pubkey, chain_id: u16) -> Pubkey {
+    Pubkey::find_program_address(&[PEER_SEED, &chain_id.to_be_bytes()], manager).0
+}
+
+pub fn inbox_rate_limit(manager: &Pubkey, chain_id: u16) -> Pubkey {
+    Pubkey::find_program_address(&[INBOX_RATE_LIMIT_SEED, &chain_id.to_be_bytes()], manager).0
+}
+
+pub fn outbox_rate_limit(manager: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[OUTBOX_RATE_LIMIT_SEED], manager).0
+}
+
+/// Delegate the sender approves for one transfer with `args`
+pub fn session_authority(manager: &Pubkey, sender: &Pubkey, args: &TransferArgs) -> Pubkey {
+    Pubkey::find_program_address(
+        &[SESSION_AUTHORITY_SEED, sender.as_ref(), &args.keccak256()],
+        manager,
+    )
+    .0
+}
+
+/// Owner of the manager's custody account
+pub fn token_authority(manager: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[TOKEN_AUTHORITY_SEED], manager).0
+}
+
+/// `set_peer`, signed by the manager's owner
+pub fn set_peer(manager: &Pubkey, payer: &Pubkey, owner: &Pubkey, args: SetPeerArgs) -> Instruction {
+    let chain_id = args.chain_id.id;
+    Instruction {
+        program_id: *manager,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(config(manager), false),
+            AccountMeta::new(peer(manager, chain_id), false),
+            AccountMeta::new(inbox_rate_limit(manager, chain_id), false),
+            AccountMeta::new_readonly(System::id(), false),
+        ],
+        data: data("set_peer", &args),
+    }
+}
+
+/// Accounts of `transfer_lock` besides the derived ones
+pub struct TransferLockAccounts {
+    pub payer: Pubkey,
+    pub mint: Pubkey,
+    /// Sender's token account, with the session authority approved for the amount
+    pub from: Pubkey,
+    pub sender: Pubkey,
+    pub token_program: Pubkey,
+    /// Fresh keypair the manager creates the outbox item at
+    pub outbox_item: Pubkey,
+    pub custody: Pubkey,
+}
+
+/// `transfer_lock`, locking the sender's DAC in the manager's custody
+pub fn transfer_lock(manager: &Pubkey, accounts: &TransferLockAccounts, args: TransferArgs) -> Instruction {
+    let chain_id = args.recipient_chain.id;
+    Instruction {
+        program_id: *manager,
+        accounts: vec![
+            AccountMeta::new(accounts.payer, true),
+            AccountMeta::new_readonly(config(manager), false),
+            AccountMeta::new(accounts.mint, false),
+            AccountMeta::new(accounts.from, false),
+            AccountMeta::new_readonly(accounts.token_program, false),
+            AccountMeta::new(accounts.outbox_item, true),
+            AccountMeta::new(outbox_rate_limit(manager), false),
+            AccountMeta::new(accounts.custody, false),
+            AccountMeta::new_readonly(System::id(), false),
+            AccountMeta::new(inbox_rate_limit(manager, chain_id), false),
+            AccountMeta::new_readonly(peer(manager, chain_id), false),
+            AccountMeta::new_readonly(session_authority(manager, &accounts.sender, &args), false),
+            AccountMeta::new_readonly(token_authority(manager), false),
+        ],
+        data: data("transfer_lock", &args),
+    }
+}
+
+/// Anchor instruction data: the method's discriminator, then its arguments
+fn data<T: AnchorSerialize>(method: &str, args: &T) -> Vec<u8> {
+    let mut data = hash(format!("global:{}", method).as_bytes()).to_bytes()[..8].to_vec();
+    args.serialize(&mut data).expect("instruction args serialize");
+    data
+}
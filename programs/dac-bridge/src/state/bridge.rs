@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+/// Window over which a rate limit refills completely (24 hours, as in NTT)
+pub const RATE_LIMIT_DURATION: i64 = 24 * 60 * 60;
+
+/// The bridge, owner of the NTT manager
+#[account]
+pub struct Bridge {
+    /// Key that registers peers and sets the outbound limit
+    pub admin: Pubkey,
+    /// The NTT manager program, running in locking mode for the DAC mint
+    pub ntt_manager: Pubkey,
+    /// The bridged DAC mint
+    pub dac_mint: Pubkey,
+    /// Cap on DAC bridged out per window
+    pub outbound: RateLimit,
+    /// Bump for the bridge PDA
+    pub bump: u8,
+}
+
+impl Bridge {
+    pub const LEN: usize = 32 + 32 + 32 + RateLimit::LEN + 1; // 121 bytes
+}
+
+/// NTT manager on another chain that DAC can be bridged to
+#[account]
+pub struct Peer {
+    /// Wormhole chain id
+    pub chain_id: u16,
+    /// The peer manager's address, left-padded to 32 bytes
+    pub address: [u8; 32],
+    /// Decimals of DAC on the peer chain
+    pub decimals: u8,
+    /// Cap on DAC bridged in from the peer per window, enforced by the NTT manager
+    pub inbound_limit: u64,
+    /// Bump for the peer PDA
+    pub bump: u8,
+}
+
+impl Peer {
+    pub const LEN: usize = 2 + 32 + 1 + 8 + 1; // 44 bytes
+}
+
+/// Capacity that refills linearly to `limit` over `RATE_LIMIT_DURATION`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RateLimit {
+    /// Most that can be spent in one window
+    pub limit: u64,
+    /// Capacity left at `last_update`
+    pub capacity: u64,
+    /// Last time the capacity was brought up to date
+    pub last_update: i64,
+}
+
+impl RateLimit {
+    pub const LEN: usize = 8 + 8 + 8;
+
+    /// Capacity at `now`
+    pub fn capacity_at(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.last_update).max(0) as u128;
+        let refill = elapsed * self.limit as u128 / RATE_LIMIT_DURATION as u128;
+        (self.capacity as u128 + refill).min(self.limit as u128) as u64
+    }
+
+    /// Spend `amount` at `now`, if that much capacity is left
+    pub fn consume(&mut self, amount: u64, now: i64) -> bool {
+        let capacity = self.capacity_at(now);
+        if amount > capacity {
+            return false;
+        }
+        self.capacity = capacity - amount;
+        self.last_update = now;
+        true
+    }
+
+    /// Change the limit at `now`, keeping what was spent in the window spent
+    pub fn set_limit(&mut self, limit: u64, now: i64) {
+        let spent = self.limit - self.capacity_at(now);
+        self.capacity = limit.saturating_sub(spent);
+        self.limit = limit;
+        self.last_update = now;
+    }
+}
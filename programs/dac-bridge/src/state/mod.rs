@@ -0,0 +1,3 @@
+mod bridge;
+
+pub use bridge::*;
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum FaucetError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Mint mismatch")]
+    MintMismatch,
+    #[msg("Cannot process zero amount")]
+    ZeroAmount,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Drip exceeds the wallet's daily cap")]
+    DailyCapExceeded,
+}
@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use dac_token::program::DacToken;
+
+use crate::errors::FaucetError;
+use crate::state::*;
+use crate::{DRIP_SEED, FAUCET_SEED};
+
+/// Mint `amount` test USDC to the caller, within the daily cap
+pub fn drip(ctx: Context<Drip>, amount: u64) -> Result<()> {
+    mint_drip(
+        &ctx.accounts.faucet,
+        &mut ctx.accounts.drip_record,
+        ctx.bumps.drip_record,
+        &ctx.accounts.usdc_mint,
+        &ctx.accounts.user_usdc,
+        &ctx.accounts.user,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    msg!("Dripped {} test USDC", amount);
+    Ok(())
+}
+
+/// Mint `amount` test USDC to the caller and wrap all of it into DAC
+pub fn drip_dac(ctx: Context<DripDac>, amount: u64) -> Result<()> {
+    mint_drip(
+        &ctx.accounts.faucet,
+        &mut ctx.accounts.drip_record,
+        ctx.bumps.drip_record,
+        &ctx.accounts.usdc_mint,
+        &ctx.accounts.user_usdc,
+        &ctx.accounts.user,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    // The caller's signature carries through to the wrap
+    let wrap_ctx = CpiContext::new(
+        ctx.accounts.dac_token_program.to_account_info(),
+        dac_token::cpi::accounts::Wrap {
+            config: ctx.accounts.dac_config.to_account_info(),
+            shard: ctx.accounts.vault_shard.to_account_info(),
+            user_usdc: ctx.accounts.user_usdc.to_account_info(),
+            user_dac: ctx.accounts.user_dac.to_account_info(),
+            usdc_vault: ctx.accounts.usdc_vault.to_account_info(),
+            dac_float: ctx.accounts.dac_float.to_account_info(),
+            mint_authority: ctx.accounts.mint_authority.to_account_info(),
+            instructions: ctx.accounts.instructions.to_account_info(),
+            attestation: ctx.accounts.attestation.as_ref().map(|a| a.to_account_info()),
+            user: ctx.accounts.user.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    );
+    dac_token::cpi::wrap(wrap_ctx, amount)?;
+
+    msg!("Dripped {} test USDC wrapped into DAC", amount);
+    Ok(())
+}
+
+/// Check the daily cap and mint the drip
+#[allow(clippy::too_many_arguments)]
+fn mint_drip<'info>(
+    faucet: &Account<'info, Faucet>,
+    drip_record: &mut Account<'info, DripRecord>,
+    drip_record_bump: u8,
+    usdc_mint: &Account<'info, Mint>,
+    user_usdc: &Account<'info, TokenAccount>,
+    user: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, FaucetError::ZeroAmount);
+    if drip_record.wallet == Pubkey::default() {
+        drip_record.wallet = user.key();
+        drip_record.day = 0;
+        drip_record.drawn = 0;
+        drip_record.bump = drip_record_bump;
+    }
+    drip_record
+        .record(Clock::get()?.unix_timestamp, amount, faucet.daily_cap)
+        .ok_or(FaucetError::DailyCapExceeded)?;
+
+    let seeds = &[FAUCET_SEED, &[faucet.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let mint_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        MintTo {
+            mint: usdc_mint.to_account_info(),
+            to: user_usdc.to_account_info(),
+            authority: faucet.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::mint_to(mint_ctx, amount)
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct Drip<'info> {
+    #[account(
+        seeds = [FAUCET_SEED],
+        bump = faucet.bump,
+        has_one = usdc_mint @ FaucetError::MintMismatch,
+    )]
+    pub faucet: Box<Account<'info, Faucet>>,
+
+    /// The caller's drips of the day (created on first use)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + DripRecord::LEN,
+        seeds = [DRIP_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub drip_record: Box<Account<'info, DripRecord>>,
+
+    #[account(mut)]
+    pub usdc_mint: Box<Account<'info, Mint>>,
+
+    /// Caller's test USDC token account (destination)
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+    )]
+    pub user_usdc: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DripDac<'info> {
+    #[account(
+        seeds = [FAUCET_SEED],
+        bump = faucet.bump,
+        has_one = usdc_mint @ FaucetError::MintMismatch,
+    )]
+    pub faucet: Box<Account<'info, Faucet>>,
+
+    /// The caller's drips of the day (created on first use)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + DripRecord::LEN,
+        seeds = [DRIP_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub drip_record: Box<Account<'info, DripRecord>>,
+
+    #[account(mut)]
+    pub usdc_mint: Box<Account<'info, Mint>>,
+
+    /// Caller's test USDC token account, passed through to the wrap
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+    )]
+    pub user_usdc: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The DAC token config, checked by the DAC token program
+    pub dac_config: UncheckedAccount<'info>,
+
+    /// CHECK: The DAC token vault shard the wrap goes through, checked by the DAC token program
+    #[account(mut)]
+    pub vault_shard: UncheckedAccount<'info>,
+
+    /// CHECK: Caller's DAC token account, checked by the DAC token program
+    #[account(mut)]
+    pub user_dac: UncheckedAccount<'info>,
+
+    /// CHECK: The vault shard's USDC vault, checked by the DAC token program
+    #[account(mut)]
+    pub usdc_vault: UncheckedAccount<'info>,
+
+    /// CHECK: The vault shard's DAC float, checked by the DAC token program
+    #[account(mut)]
+    pub dac_float: UncheckedAccount<'info>,
+
+    /// CHECK: The DAC mint authority PDA, checked by the DAC token program
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: The instructions sysvar
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: The caller's SAS attestation, checked by the DAC token program
+    pub attestation: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub dac_token_program: Program<'info, DacToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
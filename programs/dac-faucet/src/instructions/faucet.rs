@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+use dark_market::state::MarketConfig;
+use dark_market::MARKET_CONFIG_SEED;
+
+use crate::errors::FaucetError;
+use crate::state::*;
+use crate::{FAUCET_SEED, TEST_USDC_MINT_SEED};
+
+pub fn initialize_faucet(ctx: Context<InitializeFaucet>, daily_cap: u64) -> Result<()> {
+    let faucet = &mut ctx.accounts.faucet;
+    faucet.authority = ctx.accounts.authority.key();
+    faucet.usdc_mint = ctx.accounts.usdc_mint.key();
+    faucet.daily_cap = daily_cap;
+    faucet.bump = ctx.bumps.faucet;
+
+    msg!("Faucet initialized: test USDC {}, {} per day", faucet.usdc_mint, daily_cap);
+    Ok(())
+}
+
+pub fn set_daily_cap(ctx: Context<SetDailyCap>, daily_cap: u64) -> Result<()> {
+    ctx.accounts.faucet.daily_cap = daily_cap;
+
+    msg!("Faucet daily cap set to {}", daily_cap);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeFaucet<'info> {
+    /// The dark market config, whose authority sets up the faucet
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        seeds::program = dark_market::ID,
+        has_one = authority @ FaucetError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The faucet to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Faucet::LEN,
+        seeds = [FAUCET_SEED],
+        bump
+    )]
+    pub faucet: Box<Account<'info, Faucet>>,
+
+    /// The test USDC mint to create
+    #[account(
+        init,
+        payer = authority,
+        seeds = [TEST_USDC_MINT_SEED],
+        bump,
+        mint::decimals = TEST_USDC_DECIMALS,
+        mint::authority = faucet,
+    )]
+    pub usdc_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetDailyCap<'info> {
+    #[account(
+        mut,
+        seeds = [FAUCET_SEED],
+        bump = faucet.bump,
+        has_one = authority @ FaucetError::Unauthorized,
+    )]
+    pub faucet: Box<Account<'info, Faucet>>,
+
+    pub authority: Signer<'info>,
+}
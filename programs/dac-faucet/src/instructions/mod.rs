@@ -0,0 +1,5 @@
+pub mod drip;
+pub mod faucet;
+
+pub use drip::*;
+pub use faucet::*;
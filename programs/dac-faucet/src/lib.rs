@@ -0,0 +1,62 @@
+//! DAC Faucet Program (devnet only)
+//!
+//! Hands out a test USDC mint, optionally wrapped straight into DAC, so the
+//! full wrap, bet and resolve flow can be exercised without distributing
+//! tokens by hand. Each wallet can draw up to a daily cap.
+//!
+//! The faucet mints collateral backed by nothing, so building it for the
+//! chain is a compile error unless the `devnet` feature is enabled, which
+//! only `make faucet-devnet` does. `anchor build` leaves it out.
+//!
+//! Key features:
+//! - Test USDC mint owned by the faucet PDA
+//! - Per-wallet daily cap on drips
+//! - Optional auto-wrap of the drip into DAC through the DAC token program
+
+// Anchor 0.31's `#[program]` expansion still calls the deprecated `AccountInfo::realloc`
+#![allow(deprecated)]
+
+#[cfg(all(target_os = "solana", not(feature = "devnet")))]
+compile_error!("the faucet mints unbacked test collateral and only builds for devnet, with the `devnet` feature");
+
+use anchor_lang::prelude::*;
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("4vPCX4Py1fkZxYCpQJywgCXSazWFGjFPa8aMAzzrnNwP");
+
+/// Seeds for the faucet, the test USDC mint authority
+pub const FAUCET_SEED: &[u8] = b"faucet";
+/// Seeds for the test USDC mint
+pub const TEST_USDC_MINT_SEED: &[u8] = b"test_usdc";
+/// Seeds for a wallet's drip record (followed by the wallet)
+pub const DRIP_SEED: &[u8] = b"drip";
+
+#[program]
+pub mod dac_faucet {
+    use super::*;
+
+    /// Create the faucet and its test USDC mint (dark market config authority only)
+    pub fn initialize_faucet(ctx: Context<InitializeFaucet>, daily_cap: u64) -> Result<()> {
+        instructions::initialize_faucet(ctx, daily_cap)
+    }
+
+    /// Change the per-wallet daily cap (faucet authority only)
+    pub fn set_daily_cap(ctx: Context<SetDailyCap>, daily_cap: u64) -> Result<()> {
+        instructions::set_daily_cap(ctx, daily_cap)
+    }
+
+    /// Mint test USDC to the caller
+    pub fn drip(ctx: Context<Drip>, amount: u64) -> Result<()> {
+        instructions::drip(ctx, amount)
+    }
+
+    /// Mint test USDC to the caller and wrap it into DAC
+    pub fn drip_dac(ctx: Context<DripDac>, amount: u64) -> Result<()> {
+        instructions::drip_dac(ctx, amount)
+    }
+}
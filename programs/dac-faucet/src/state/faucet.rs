@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+/// Length of a drip day
+pub const DRIP_DAY: i64 = 24 * 60 * 60;
+/// Decimals of the test USDC mint, matching USDC
+pub const TEST_USDC_DECIMALS: u8 = 6;
+
+/// The faucet
+#[account]
+pub struct Faucet {
+    /// Key that sets the cap
+    pub authority: Pubkey,
+    /// The test USDC mint, minted by the faucet PDA
+    pub usdc_mint: Pubkey,
+    /// Test USDC a wallet can draw per day
+    pub daily_cap: u64,
+    /// Bump for the faucet PDA
+    pub bump: u8,
+}
+
+impl Faucet {
+    pub const LEN: usize = 32 + 32 + 8 + 1; // 73 bytes
+}
+
+/// One wallet's drips of the current day
+#[account]
+pub struct DripRecord {
+    pub wallet: Pubkey,
+    /// Day number (`unix time / DRIP_DAY`) of `drawn`
+    pub day: i64,
+    /// Test USDC drawn that day
+    pub drawn: u64,
+    /// Bump for the drip record PDA
+    pub bump: u8,
+}
+
+impl DripRecord {
+    pub const LEN: usize = 32 + 8 + 8 + 1; // 49 bytes
+
+    /// Record a drip of `amount` at `now`, resetting on a new day
+    pub fn record(&mut self, now: i64, amount: u64, daily_cap: u64) -> Option<()> {
+        let day = now / DRIP_DAY;
+        if day != self.day {
+            self.day = day;
+            self.drawn = 0;
+        }
+        let drawn = self.drawn.checked_add(amount)?;
+        if drawn > daily_cap {
+            return None;
+        }
+        self.drawn = drawn;
+        Some(())
+    }
+}
@@ -0,0 +1,3 @@
+mod faucet;
+
+pub use faucet::*;
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum GovernanceError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Invalid governance parameters")]
+    InvalidParams,
+    #[msg("Proposal instruction exceeds the maximum size")]
+    ProposalTooLarge,
+    #[msg("Voting power below the proposal threshold")]
+    InsufficientVotingPower,
+    #[msg("Stake unlocks before voting ends")]
+    StakeUnlocksEarly,
+    #[msg("Proposal is not open for voting")]
+    VotingClosed,
+    #[msg("Voting has not ended")]
+    VotingOpen,
+    #[msg("Proposal is not queued")]
+    ProposalNotQueued,
+    #[msg("Proposal timelock has not elapsed")]
+    TimelockActive,
+}
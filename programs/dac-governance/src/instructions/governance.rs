@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use dac_staking::state::StakingPool;
+use dark_market::state::MarketConfig;
+use dark_market::MARKET_CONFIG_SEED;
+
+use crate::errors::GovernanceError;
+use crate::state::*;
+use crate::GOVERNANCE_SEED;
+
+/// Create the governance (dark market config authority only)
+///
+/// Governance takes effect once the config authority is handed to the
+/// governance PDA with the dark market's `set_config_authority`.
+pub fn initialize_governance(
+    ctx: Context<InitializeGovernance>,
+    params: GovernanceParams,
+) -> Result<()> {
+    require!(params.is_valid(), GovernanceError::InvalidParams);
+
+    let governance = &mut ctx.accounts.governance;
+    governance.staking_pool = ctx.accounts.staking_pool.key();
+    governance.params = params;
+    governance.proposal_count = 0;
+    governance.bump = ctx.bumps.governance;
+
+    msg!("Governance initialized: {}", governance.key());
+    Ok(())
+}
+
+/// Change the voting rules (governance PDA only)
+///
+/// Only reachable through `execute_proposal`, which signs for the PDA.
+pub fn set_governance_params(
+    ctx: Context<SetGovernanceParams>,
+    params: GovernanceParams,
+) -> Result<()> {
+    require!(params.is_valid(), GovernanceError::InvalidParams);
+
+    ctx.accounts.governance.params = params;
+
+    msg!(
+        "Governance params set: {}s voting, {}s delay, quorum {}, threshold {}",
+        params.voting_period,
+        params.execution_delay,
+        params.quorum,
+        params.proposal_threshold
+    );
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    /// The dark market config, whose authority sets up governance
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        seeds::program = dark_market::ID,
+        has_one = authority @ GovernanceError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The governance to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Governance::LEN,
+        seeds = [GOVERNANCE_SEED],
+        bump
+    )]
+    pub governance: Box<Account<'info, Governance>>,
+
+    /// The staking pool of the protocol's DAC mint
+    #[account(
+        constraint = staking_pool.dac_mint == config.dac_mint @ GovernanceError::InvalidParams,
+    )]
+    pub staking_pool: Box<Account<'info, StakingPool>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGovernanceParams<'info> {
+    /// The governance, which must sign for itself
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_SEED],
+        bump = governance.bump,
+        constraint = governance.to_account_info().is_signer @ GovernanceError::Unauthorized,
+    )]
+    pub governance: Box<Account<'info, Governance>>,
+}
@@ -0,0 +1,5 @@
+pub mod governance;
+pub mod proposal;
+
+pub use governance::*;
+pub use proposal::*;
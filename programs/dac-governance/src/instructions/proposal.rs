@@ -0,0 +1,232 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use dac_staking::state::StakeAccount;
+use dac_staking::STAKE_SEED;
+
+use crate::errors::GovernanceError;
+use crate::state::*;
+use crate::{GOVERNANCE_SEED, PROPOSAL_SEED, VOTE_SEED};
+
+/// Propose an instruction for the governance PDA to sign
+///
+/// The proposer's stake weight must reach the proposal threshold.
+pub fn create_proposal(
+    ctx: Context<CreateProposal>,
+    program_id: Pubkey,
+    accounts: Vec<ProposalAccount>,
+    data: Vec<u8>,
+) -> Result<()> {
+    require!(
+        accounts.len() <= MAX_PROPOSAL_ACCOUNTS && data.len() <= MAX_PROPOSAL_DATA_LEN,
+        GovernanceError::ProposalTooLarge
+    );
+    let governance = &mut ctx.accounts.governance;
+    require!(
+        ctx.accounts.stake.weight >= governance.params.proposal_threshold,
+        GovernanceError::InsufficientVotingPower
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.id = governance.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.program_id = program_id;
+    proposal.accounts = accounts;
+    proposal.data = data;
+    proposal.voting_ends = now
+        .checked_add(governance.params.voting_period)
+        .ok_or(GovernanceError::Overflow)?;
+    proposal.yes_votes = 0;
+    proposal.no_votes = 0;
+    proposal.executable_at = 0;
+    proposal.state = ProposalState::Voting;
+    proposal.bump = ctx.bumps.proposal;
+
+    governance.proposal_count = governance.proposal_count
+        .checked_add(1)
+        .ok_or(GovernanceError::Overflow)?;
+
+    msg!("Proposal {} created, voting until {}", proposal.id, proposal.voting_ends);
+    Ok(())
+}
+
+/// Vote with the full weight of the voter's stake
+///
+/// The stake must stay locked until voting ends, so the same DAC cannot be
+/// unstaked and voted again from another wallet.
+pub fn cast_vote(ctx: Context<CastVote>, approve: bool) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        proposal.state == ProposalState::Voting && now < proposal.voting_ends,
+        GovernanceError::VotingClosed
+    );
+    let stake = &ctx.accounts.stake;
+    require!(stake.lock_end >= proposal.voting_ends, GovernanceError::StakeUnlocksEarly);
+    require!(stake.weight > 0, GovernanceError::InsufficientVotingPower);
+
+    let tally = if approve { &mut proposal.yes_votes } else { &mut proposal.no_votes };
+    *tally = tally.checked_add(stake.weight).ok_or(GovernanceError::Overflow)?;
+
+    let vote = &mut ctx.accounts.vote;
+    vote.proposal = proposal.key();
+    vote.voter = ctx.accounts.voter.key();
+    vote.approve = approve;
+    vote.weight = stake.weight;
+    vote.bump = ctx.bumps.vote;
+
+    msg!("Voted {} on proposal {} with {}", approve, proposal.id, stake.weight);
+    Ok(())
+}
+
+/// Queue a passed proposal behind the execution delay, or mark it defeated (anyone)
+pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let params = ctx.accounts.governance.params;
+    let proposal = &mut ctx.accounts.proposal;
+    require!(proposal.state == ProposalState::Voting, GovernanceError::VotingClosed);
+    require!(now >= proposal.voting_ends, GovernanceError::VotingOpen);
+
+    if proposal.passed(params.quorum) {
+        proposal.state = ProposalState::Queued;
+        proposal.executable_at = now
+            .checked_add(params.execution_delay)
+            .ok_or(GovernanceError::Overflow)?;
+        msg!("Proposal {} queued until {}", proposal.id, proposal.executable_at);
+    } else {
+        proposal.state = ProposalState::Defeated;
+        msg!("Proposal {} defeated", proposal.id);
+    }
+    Ok(())
+}
+
+/// Execute a queued proposal with the governance PDA as signer (anyone)
+///
+/// The instruction's accounts, and the program it calls, are passed as
+/// remaining accounts.
+pub fn execute_proposal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    require!(proposal.state == ProposalState::Queued, GovernanceError::ProposalNotQueued);
+    require!(
+        Clock::get()?.unix_timestamp >= proposal.executable_at,
+        GovernanceError::TimelockActive
+    );
+    proposal.state = ProposalState::Executed;
+
+    let seeds = &[GOVERNANCE_SEED, &[ctx.accounts.governance.bump]];
+    let signer_seeds = &[&seeds[..]];
+    invoke_signed(&proposal.instruction(), ctx.remaining_accounts, signer_seeds)?;
+
+    msg!("Proposal {} executed", proposal.id);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_SEED],
+        bump = governance.bump,
+    )]
+    pub governance: Box<Account<'info, Governance>>,
+
+    /// The proposal to create
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::LEN,
+        seeds = [PROPOSAL_SEED, governance.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// The proposer's stake, carrying their voting power
+    #[account(
+        seeds = [STAKE_SEED, governance.staking_pool.as_ref(), proposer.key().as_ref()],
+        bump = stake.bump,
+        seeds::program = dac_staking::ID,
+    )]
+    pub stake: Box<Account<'info, StakeAccount>>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(
+        seeds = [GOVERNANCE_SEED],
+        bump = governance.bump,
+    )]
+    pub governance: Box<Account<'info, Governance>>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// The voter's record, whose creation stops a second vote
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::LEN,
+        seeds = [VOTE_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote: Box<Account<'info, VoteRecord>>,
+
+    /// The voter's stake, carrying their voting power
+    #[account(
+        seeds = [STAKE_SEED, governance.staking_pool.as_ref(), voter.key().as_ref()],
+        bump = stake.bump,
+        seeds::program = dac_staking::ID,
+    )]
+    pub stake: Box<Account<'info, StakeAccount>>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(
+        seeds = [GOVERNANCE_SEED],
+        bump = governance.bump,
+    )]
+    pub governance: Box<Account<'info, Governance>>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+        seeds = [GOVERNANCE_SEED],
+        bump = governance.bump,
+    )]
+    pub governance: Box<Account<'info, Governance>>,
+
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+}
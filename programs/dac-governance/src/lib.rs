@@ -0,0 +1,83 @@
+//! DAC Governance Program
+//!
+//! Staked DAC holders propose and vote on protocol changes. A proposal
+//! carries one instruction, which the governance PDA signs once the vote
+//! passes and a timelock elapses. Handing the dark market config authority
+//! to the governance PDA puts every parameter it controls (fees, caps,
+//! resolver registry, staking emissions) under the vote instead of a
+//! single admin key.
+//!
+//! Key features:
+//! - Proposals by stakers holding at least the proposal threshold of voting power
+//! - Voting power from dac-staking stake weight, locked through the voting window
+//! - Quorum and majority checks, then a timelocked execution queue
+//! - Execution by CPI with the governance PDA as signer, including changes to governance itself
+
+// Anchor 0.31's `#[program]` expansion still calls the deprecated `AccountInfo::realloc`
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+use state::{GovernanceParams, ProposalAccount};
+
+declare_id!("7zAC4Bk1yTwEw1AAfQixypRgBdAvVFjWvv6q1e5J4jgF");
+
+/// Seeds for the governance PDA, the authority governed programs answer to
+pub const GOVERNANCE_SEED: &[u8] = b"governance";
+/// Seeds for a proposal (followed by the proposal id)
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+/// Seeds for a vote record (followed by the proposal and the voter)
+pub const VOTE_SEED: &[u8] = b"vote";
+
+#[program]
+pub mod dac_governance {
+    use super::*;
+
+    /// Create the governance of the DAC staking pool (dark market config authority only)
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        params: GovernanceParams,
+    ) -> Result<()> {
+        instructions::initialize_governance(ctx, params)
+    }
+
+    /// Change the voting rules (governance PDA only, through a proposal)
+    pub fn set_governance_params(
+        ctx: Context<SetGovernanceParams>,
+        params: GovernanceParams,
+    ) -> Result<()> {
+        instructions::set_governance_params(ctx, params)
+    }
+
+    /// Propose an instruction for the governance PDA to sign
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        program_id: Pubkey,
+        accounts: Vec<ProposalAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::create_proposal(ctx, program_id, accounts, data)
+    }
+
+    /// Vote on an open proposal with a stake's weight
+    pub fn cast_vote(ctx: Context<CastVote>, approve: bool) -> Result<()> {
+        instructions::cast_vote(ctx, approve)
+    }
+
+    /// Queue or defeat a proposal once voting ends
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        instructions::finalize_proposal(ctx)
+    }
+
+    /// Execute a queued proposal after its timelock
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
+        instructions::execute_proposal(ctx)
+    }
+}
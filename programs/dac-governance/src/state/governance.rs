@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+/// Most accounts a proposal's instruction can take
+pub const MAX_PROPOSAL_ACCOUNTS: usize = 16;
+/// Largest instruction data a proposal can carry
+pub const MAX_PROPOSAL_DATA_LEN: usize = 512;
+
+/// Voting rules, changeable only by a passed proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GovernanceParams {
+    /// Seconds a proposal stays open for votes
+    pub voting_period: i64,
+    /// Seconds between a proposal passing and its execution
+    pub execution_delay: i64,
+    /// Approving voting power a proposal needs to pass
+    pub quorum: u64,
+    /// Voting power needed to create a proposal
+    pub proposal_threshold: u64,
+}
+
+impl GovernanceParams {
+    pub const LEN: usize = 8 + 8 + 8 + 8; // 32 bytes
+
+    pub fn is_valid(&self) -> bool {
+        self.voting_period > 0 && self.execution_delay >= 0 && self.quorum > 0
+    }
+}
+
+/// The governance of the protocol
+///
+/// Its PDA is the authority of the governed programs: it signs exactly the
+/// instructions of passed proposals.
+#[account]
+pub struct Governance {
+    /// The dac-staking pool whose stakes carry voting power
+    pub staking_pool: Pubkey,
+    /// Voting rules
+    pub params: GovernanceParams,
+    /// Number of proposals created (next proposal id)
+    pub proposal_count: u64,
+    /// Bump for the governance PDA
+    pub bump: u8,
+}
+
+impl Governance {
+    pub const LEN: usize = 32 + GovernanceParams::LEN + 8 + 1; // 73 bytes
+}
+
+/// An account of a proposal's instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl ProposalAccount {
+    pub const LEN: usize = 32 + 1 + 1; // 34 bytes
+}
+
+/// Lifecycle of a proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProposalState {
+    /// Accepting votes until `voting_ends`
+    Voting,
+    /// Passed, executable from `executable_at`
+    Queued,
+    /// Instruction executed
+    Executed,
+    /// Failed quorum or majority
+    Defeated,
+}
+
+/// A proposed instruction and the votes on it
+#[account]
+pub struct Proposal {
+    /// Proposal id (index in the governance's proposals)
+    pub id: u64,
+    /// Staker who created the proposal
+    pub proposer: Pubkey,
+    /// Program the instruction calls
+    pub program_id: Pubkey,
+    /// Accounts of the instruction
+    pub accounts: Vec<ProposalAccount>,
+    /// Data of the instruction
+    pub data: Vec<u8>,
+    /// Time voting closes
+    pub voting_ends: i64,
+    /// Voting power approving
+    pub yes_votes: u64,
+    /// Voting power rejecting
+    pub no_votes: u64,
+    /// Earliest execution time, set when the proposal is queued
+    pub executable_at: i64,
+    pub state: ProposalState,
+    /// Bump for the proposal PDA
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const LEN: usize = 8
+        + 32
+        + 32
+        + (4 + MAX_PROPOSAL_ACCOUNTS * ProposalAccount::LEN)
+        + (4 + MAX_PROPOSAL_DATA_LEN)
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1
+        + 1; // 1170 bytes
+
+    /// Whether the votes meet quorum with an approving majority
+    pub fn passed(&self, quorum: u64) -> bool {
+        self.yes_votes >= quorum && self.yes_votes > self.no_votes
+    }
+
+    /// The instruction to execute
+    pub fn instruction(&self) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: self.accounts
+                .iter()
+                .map(|a| AccountMeta {
+                    pubkey: a.pubkey,
+                    is_signer: a.is_signer,
+                    is_writable: a.is_writable,
+                })
+                .collect(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+/// One staker's vote on a proposal, which also stops them voting twice
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub approve: bool,
+    /// Voting power cast
+    pub weight: u64,
+    /// Bump for the vote record PDA
+    pub bump: u8,
+}
+
+impl VoteRecord {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 1; // 74 bytes
+}
@@ -0,0 +1,3 @@
+mod governance;
+
+pub use governance::*;
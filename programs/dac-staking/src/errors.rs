@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum StakingError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Cannot process zero amount")]
+    ZeroAmount,
+    #[msg("Mint mismatch")]
+    MintMismatch,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Lock duration exceeds the maximum")]
+    LockTooLong,
+    #[msg("Stake is still locked")]
+    StakeLocked,
+    #[msg("Unstake amount exceeds the stake")]
+    InsufficientStake,
+    #[msg("Emission end must be in the future")]
+    InvalidEmissionEnd,
+    #[msg("Fee epoch has not ended")]
+    FeeEpochActive,
+}
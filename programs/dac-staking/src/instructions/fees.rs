@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::StakingError;
+use crate::instructions::{pay_from_pool, sync_stake};
+use crate::state::*;
+use crate::{STAKE_SEED, STAKING_POOL_SEED};
+
+/// Start a fee epoch streaming the fees collected since the last one (anyone)
+///
+/// Only callable once the previous epoch has ended, so each distribution
+/// covers one epoch's worth of collected fees.
+pub fn distribute_fees(ctx: Context<DistributeFees>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &mut ctx.accounts.pool;
+    require!(now >= pool.fee_epoch_end, StakingError::FeeEpochActive);
+    pool.update(now).ok_or(StakingError::Overflow)?;
+
+    // The remainder of the division is picked up by the next distribution
+    let collected = ctx.accounts.fee_vault.amount.saturating_sub(pool.fees_outstanding);
+    pool.fee_rate = collected / FEE_EPOCH_DURATION as u64;
+    pool.fee_epoch_end = now + FEE_EPOCH_DURATION;
+    pool.fees_outstanding = pool.fees_outstanding
+        .checked_add(pool.fee_rate * FEE_EPOCH_DURATION as u64)
+        .ok_or(StakingError::Overflow)?;
+
+    msg!("Distributing {} DAC/s of fees until {}", pool.fee_rate, pool.fee_epoch_end);
+    Ok(())
+}
+
+/// Pay out the stake's share of the distributed fees
+pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &mut ctx.accounts.pool;
+    let stake = &mut ctx.accounts.stake;
+    sync_stake(pool, stake, now)?;
+
+    let payout = stake.pending_fees.min(ctx.accounts.fee_vault.amount);
+    require!(payout > 0, StakingError::ZeroAmount);
+    stake.pending_fees -= payout;
+    pool.fees_outstanding = pool.fees_outstanding.saturating_sub(payout);
+
+    pay_from_pool(
+        &ctx.accounts.pool,
+        &ctx.accounts.fee_vault,
+        &ctx.accounts.staker_dac,
+        &ctx.accounts.token_program,
+        payout,
+    )?;
+
+    msg!("Claimed {} DAC of fees", payout);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct DistributeFees<'info> {
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED, pool.dac_mint.as_ref()],
+        bump = pool.bump,
+        has_one = fee_vault @ StakingError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, StakingPool>>,
+
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED, pool.dac_mint.as_ref()],
+        bump = pool.bump,
+        has_one = fee_vault @ StakingError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, StakingPool>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, pool.key().as_ref(), staker.key().as_ref()],
+        bump = stake.bump,
+        has_one = staker @ StakingError::Unauthorized,
+    )]
+    pub stake: Box<Account<'info, StakeAccount>>,
+
+    #[account(mut)]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Staker's DAC token account (destination)
+    #[account(
+        mut,
+        constraint = staker_dac.mint == pool.dac_mint @ StakingError::MintMismatch,
+    )]
+    pub staker_dac: Box<Account<'info, TokenAccount>>,
+
+    pub staker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
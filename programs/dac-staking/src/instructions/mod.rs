@@ -0,0 +1,7 @@
+pub mod fees;
+pub mod pool;
+pub mod stake;
+
+pub use fees::*;
+pub use pool::*;
+pub use stake::*;
@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use dark_market::state::MarketConfig;
+use dark_market::MARKET_CONFIG_SEED;
+
+use crate::errors::StakingError;
+use crate::state::*;
+use crate::{FEE_VAULT_SEED, REWARD_VAULT_SEED, STAKE_VAULT_SEED, STAKING_POOL_SEED};
+
+pub fn initialize_staking(
+    ctx: Context<InitializeStaking>,
+    emission_rate: u64,
+    emission_end: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(emission_end > now, StakingError::InvalidEmissionEnd);
+
+    let pool = &mut ctx.accounts.pool;
+    pool.dac_mint = ctx.accounts.dac_mint.key();
+    pool.stake_vault = ctx.accounts.stake_vault.key();
+    pool.reward_vault = ctx.accounts.reward_vault.key();
+    pool.emission_rate = emission_rate;
+    pool.emission_end = emission_end;
+    pool.reward_per_weight = 0;
+    pool.last_update = now;
+    pool.total_staked = 0;
+    pool.total_weight = 0;
+    pool.bump = ctx.bumps.pool;
+    pool.fee_vault = ctx.accounts.fee_vault.key();
+    pool.fee_rate = 0;
+    pool.fee_epoch_end = now;
+    pool.fee_per_stake = 0;
+    pool.fees_outstanding = 0;
+
+    msg!("Staking pool initialized: {} DAC/s until {}", emission_rate, emission_end);
+    Ok(())
+}
+
+/// Change the emission schedule from now on (dark market config authority only)
+///
+/// Rewards accrued under the old schedule are kept.
+pub fn set_emissions(ctx: Context<SetEmissions>, emission_rate: u64, emission_end: i64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(emission_end > now, StakingError::InvalidEmissionEnd);
+
+    let pool = &mut ctx.accounts.pool;
+    pool.update(now).ok_or(StakingError::Overflow)?;
+    pool.emission_rate = emission_rate;
+    pool.emission_end = emission_end;
+
+    msg!("Emissions set to {} DAC/s until {}", emission_rate, emission_end);
+    Ok(())
+}
+
+/// Top up the reward vault (the protocol treasury, or anyone)
+pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+    require!(amount > 0, StakingError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.funder_dac.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    msg!("Reward vault funded with {} DAC", amount);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeStaking<'info> {
+    /// The dark market config, whose authority sets up the pool
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        seeds::program = dark_market::ID,
+        has_one = authority @ StakingError::Unauthorized,
+        has_one = dac_mint @ StakingError::MintMismatch,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The pool to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + StakingPool::LEN,
+        seeds = [STAKING_POOL_SEED, dac_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Box<Account<'info, StakingPool>>,
+
+    /// DAC vault holding the stakes
+    #[account(
+        init,
+        payer = authority,
+        seeds = [STAKE_VAULT_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = pool,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    /// DAC vault paying the rewards
+    #[account(
+        init,
+        payer = authority,
+        seeds = [REWARD_VAULT_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = pool,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    /// DAC vault collecting protocol fees for stakers
+    #[account(
+        init,
+        payer = authority,
+        seeds = [FEE_VAULT_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = pool,
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetEmissions<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        seeds::program = dark_market::ID,
+        has_one = authority @ StakingError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED, pool.dac_mint.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, StakingPool>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    #[account(
+        seeds = [STAKING_POOL_SEED, pool.dac_mint.as_ref()],
+        bump = pool.bump,
+        has_one = reward_vault @ StakingError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, StakingPool>>,
+
+    #[account(mut)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Funder's DAC token account
+    #[account(
+        mut,
+        constraint = funder_dac.mint == pool.dac_mint @ StakingError::MintMismatch,
+    )]
+    pub funder_dac: Box<Account<'info, TokenAccount>>,
+
+    pub funder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
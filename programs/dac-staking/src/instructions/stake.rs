@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::errors::StakingError;
+use crate::state::*;
+use crate::{STAKE_SEED, STAKING_POOL_SEED};
+
+/// Stake `amount` DAC, locked for at least `lock_duration` seconds
+///
+/// Adding to a stake can only extend its lock, and keeps the higher of the
+/// old and new multipliers.
+pub fn stake(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<()> {
+    require!(amount > 0, StakingError::ZeroAmount);
+    require!(
+        (0..=MAX_LOCK_DURATION).contains(&lock_duration),
+        StakingError::LockTooLong
+    );
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.staker_dac.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.staker.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &mut ctx.accounts.pool;
+    let stake = &mut ctx.accounts.stake;
+    if stake.staker == Pubkey::default() {
+        stake.staker = ctx.accounts.staker.key();
+        stake.amount = 0;
+        stake.pool = pool.key();
+        stake.lock_end = now;
+        stake.multiplier_bps = BASE_MULTIPLIER_BPS;
+        stake.weight = 0;
+        stake.reward_debt = pool.reward_per_weight;
+        stake.pending_rewards = 0;
+        stake.bump = ctx.bumps.stake;
+        stake.fee_debt = pool.fee_per_stake;
+        stake.pending_fees = 0;
+    }
+    sync_stake(pool, stake, now)?;
+
+    stake.amount = stake.amount.checked_add(amount).ok_or(StakingError::Overflow)?;
+    stake.lock_end = stake.lock_end.max(now + lock_duration);
+    stake.multiplier_bps = stake.multiplier_bps.max(lock_multiplier_bps(lock_duration));
+    reweigh(pool, stake)?;
+    pool.total_staked = pool.total_staked.checked_add(amount).ok_or(StakingError::Overflow)?;
+
+    msg!("Staked {} DAC until {} at {}bps", amount, stake.lock_end, stake.multiplier_bps);
+    Ok(())
+}
+
+pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+    require!(amount > 0, StakingError::ZeroAmount);
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= ctx.accounts.stake.lock_end, StakingError::StakeLocked);
+    require!(amount <= ctx.accounts.stake.amount, StakingError::InsufficientStake);
+
+    let pool = &mut ctx.accounts.pool;
+    let stake = &mut ctx.accounts.stake;
+    sync_stake(pool, stake, now)?;
+    stake.amount -= amount;
+    reweigh(pool, stake)?;
+    pool.total_staked -= amount;
+
+    pay_from_pool(
+        &ctx.accounts.pool,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.staker_dac,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    msg!("Unstaked {} DAC", amount);
+    Ok(())
+}
+
+/// Pay out the stake's rewards, as far as the reward vault covers them
+pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &mut ctx.accounts.pool;
+    let stake = &mut ctx.accounts.stake;
+    sync_stake(pool, stake, now)?;
+
+    let payout = stake.pending_rewards.min(ctx.accounts.reward_vault.amount);
+    require!(payout > 0, StakingError::ZeroAmount);
+    stake.pending_rewards -= payout;
+
+    pay_from_pool(
+        &ctx.accounts.pool,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.staker_dac,
+        &ctx.accounts.token_program,
+        payout,
+    )?;
+
+    msg!("Claimed {} DAC of rewards", payout);
+    Ok(())
+}
+
+/// Accrue the pool, settle the stake's rewards and fees and drop an expired lock's boost
+pub(crate) fn sync_stake(pool: &mut StakingPool, stake: &mut StakeAccount, now: i64) -> Result<()> {
+    pool.update(now).ok_or(StakingError::Overflow)?;
+    stake.settle(pool.reward_per_weight, pool.fee_per_stake).ok_or(StakingError::Overflow)?;
+    if now >= stake.lock_end && stake.multiplier_bps > BASE_MULTIPLIER_BPS {
+        stake.multiplier_bps = BASE_MULTIPLIER_BPS;
+        reweigh(pool, stake)?;
+    }
+    Ok(())
+}
+
+/// Recompute the stake's weight and carry the change into the pool total
+fn reweigh(pool: &mut StakingPool, stake: &mut StakeAccount) -> Result<()> {
+    let old_weight = stake.weight;
+    let new_weight = stake.reweigh().ok_or(StakingError::Overflow)?;
+    pool.total_weight = (pool.total_weight - old_weight)
+        .checked_add(new_weight)
+        .ok_or(StakingError::Overflow)?;
+    Ok(())
+}
+
+/// Transfer `amount` out of one of the pool's vaults
+pub(crate) fn pay_from_pool<'info>(
+    pool: &Account<'info, StakingPool>,
+    vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[STAKING_POOL_SEED, pool.dac_mint.as_ref(), &[pool.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: vault.to_account_info(),
+            to: to.to_account_info(),
+            authority: pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED, pool.dac_mint.as_ref()],
+        bump = pool.bump,
+        has_one = stake_vault @ StakingError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, StakingPool>>,
+
+    /// The staker's stake (created on first use)
+    #[account(
+        init_if_needed,
+        payer = staker,
+        space = 8 + StakeAccount::LEN,
+        seeds = [STAKE_SEED, pool.key().as_ref(), staker.key().as_ref()],
+        bump
+    )]
+    pub stake: Box<Account<'info, StakeAccount>>,
+
+    #[account(mut)]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Staker's DAC token account (source of the stake)
+    #[account(
+        mut,
+        constraint = staker_dac.mint == pool.dac_mint @ StakingError::MintMismatch,
+    )]
+    pub staker_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED, pool.dac_mint.as_ref()],
+        bump = pool.bump,
+        has_one = stake_vault @ StakingError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, StakingPool>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, pool.key().as_ref(), staker.key().as_ref()],
+        bump = stake.bump,
+        has_one = staker @ StakingError::Unauthorized,
+    )]
+    pub stake: Box<Account<'info, StakeAccount>>,
+
+    #[account(mut)]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Staker's DAC token account (destination)
+    #[account(
+        mut,
+        constraint = staker_dac.mint == pool.dac_mint @ StakingError::MintMismatch,
+    )]
+    pub staker_dac: Box<Account<'info, TokenAccount>>,
+
+    pub staker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        seeds = [STAKING_POOL_SEED, pool.dac_mint.as_ref()],
+        bump = pool.bump,
+        has_one = reward_vault @ StakingError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, StakingPool>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, pool.key().as_ref(), staker.key().as_ref()],
+        bump = stake.bump,
+        has_one = staker @ StakingError::Unauthorized,
+    )]
+    pub stake: Box<Account<'info, StakeAccount>>,
+
+    #[account(mut)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Staker's DAC token account (destination)
+    #[account(
+        mut,
+        constraint = staker_dac.mint == pool.dac_mint @ StakingError::MintMismatch,
+    )]
+    pub staker_dac: Box<Account<'info, TokenAccount>>,
+
+    pub staker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
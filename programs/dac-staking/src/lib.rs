@@ -0,0 +1,89 @@
+//! DAC Staking Program
+//!
+//! Users lock DAC for a duration of their choice and earn DAC rewards from
+//! an emission schedule. Longer locks weigh more in the reward split, and
+//! stakes also qualify traders for the dark market's swap fee discounts.
+//!
+//! Key features:
+//! - Stake, unstake and claim instructions with per-staker stake accounts
+//! - Lockup multipliers from 1x (unlocked) to 4x (four-year lock)
+//! - Emissions at a per-second rate until an end time, set by the protocol authority
+//! - Reward vault funded by the protocol treasury
+//! - Weekly distribution of protocol fee revenue by time-weighted stake
+
+// Anchor 0.31's `#[program]` expansion still calls the deprecated `AccountInfo::realloc`
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("4vgM9WiGd3nRn7pkVQ5noxHpCCgzexJKCdRTDqu2p2Tt");
+
+/// Seeds for the staking pool (followed by the DAC mint)
+pub const STAKING_POOL_SEED: &[u8] = b"staking_pool";
+/// Seeds for the pool's stake vault (followed by the pool)
+pub const STAKE_VAULT_SEED: &[u8] = b"stake_vault";
+/// Seeds for the pool's reward vault (followed by the pool)
+pub const REWARD_VAULT_SEED: &[u8] = b"reward_vault";
+/// Seeds for a stake account (followed by the pool and the staker)
+pub const STAKE_SEED: &[u8] = b"stake";
+/// Seeds for the pool's fee revenue vault (followed by the pool)
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+
+#[program]
+pub mod dac_staking {
+    use super::*;
+
+    /// Create the staking pool of the DAC mint (dark market config authority only)
+    pub fn initialize_staking(
+        ctx: Context<InitializeStaking>,
+        emission_rate: u64,
+        emission_end: i64,
+    ) -> Result<()> {
+        instructions::initialize_staking(ctx, emission_rate, emission_end)
+    }
+
+    /// Change the emission rate and end time (dark market config authority only)
+    pub fn set_emissions(
+        ctx: Context<SetEmissions>,
+        emission_rate: u64,
+        emission_end: i64,
+    ) -> Result<()> {
+        instructions::set_emissions(ctx, emission_rate, emission_end)
+    }
+
+    /// Add DAC to the reward vault
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        instructions::fund_rewards(ctx, amount)
+    }
+
+    /// Stake DAC with a lockup
+    pub fn stake(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<()> {
+        instructions::stake(ctx, amount, lock_duration)
+    }
+
+    /// Withdraw unlocked DAC from a stake
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        instructions::unstake(ctx, amount)
+    }
+
+    /// Claim a stake's accrued rewards
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::claim_rewards(ctx)
+    }
+
+    /// Stream the fees collected since the last distribution over the next epoch
+    pub fn distribute_fees(ctx: Context<DistributeFees>) -> Result<()> {
+        instructions::distribute_fees(ctx)
+    }
+
+    /// Claim a stake's share of the distributed fees
+    pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+        instructions::claim_fees(ctx)
+    }
+}
@@ -0,0 +1,3 @@
+mod staking;
+
+pub use staking::*;
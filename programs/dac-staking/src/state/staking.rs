@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+
+/// Longest lockup a stake can choose (4 years)
+pub const MAX_LOCK_DURATION: i64 = 4 * 365 * 24 * 60 * 60;
+/// Weight multiplier of an unlocked stake, in basis points
+pub const BASE_MULTIPLIER_BPS: u64 = 10_000;
+/// Weight multiplier of a stake locked for `MAX_LOCK_DURATION`, in basis points
+pub const MAX_MULTIPLIER_BPS: u64 = 40_000;
+/// Fixed-point scale of `reward_per_weight` and `fee_per_stake`
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+/// Length of a fee distribution epoch (7 days)
+pub const FEE_EPOCH_DURATION: i64 = 7 * 24 * 60 * 60;
+
+/// The staking pool of the DAC mint
+///
+/// Rewards are emitted at `emission_rate` DAC per second until
+/// `emission_end` and shared among stakes by weight: the staked amount
+/// scaled by a lockup multiplier growing linearly from 1x unlocked to 4x
+/// at the longest lock. `reward_per_weight` accumulates the rewards of one
+/// unit of weight since the pool started, so a stake's rewards are its
+/// weight times the growth since it last settled.
+///
+/// Protocol fees collect in the fee vault and are paid out by epoch: each
+/// distribution streams the fees collected since the last one over the next
+/// `FEE_EPOCH_DURATION` by staked amount, so every staker earns in
+/// proportion to their time-weighted stake. Fees streamed while nothing is
+/// staked go back into the next distribution.
+#[account]
+pub struct StakingPool {
+    /// The staked DAC mint
+    pub dac_mint: Pubkey,
+    /// DAC vault holding the stakes
+    pub stake_vault: Pubkey,
+    /// DAC vault paying the rewards, funded by the treasury
+    pub reward_vault: Pubkey,
+    /// DAC emitted per second across all stakes
+    pub emission_rate: u64,
+    /// Time emissions stop
+    pub emission_end: i64,
+    /// Rewards per unit of weight since the start, scaled by `REWARD_PRECISION`
+    pub reward_per_weight: u128,
+    /// Last time `reward_per_weight` was brought up to date
+    pub last_update: i64,
+    /// DAC staked across all stakes
+    pub total_staked: u64,
+    /// Weight of all stakes
+    pub total_weight: u64,
+    /// Bump for the pool PDA
+    pub bump: u8,
+    /// DAC vault collecting protocol fees for stakers
+    pub fee_vault: Pubkey,
+    /// Fee DAC streamed per second during the current epoch
+    pub fee_rate: u64,
+    /// Time the current fee epoch ends
+    pub fee_epoch_end: i64,
+    /// Fees per staked DAC since the start, scaled by `REWARD_PRECISION`
+    pub fee_per_stake: u128,
+    /// Fee DAC distributed and not yet claimed, including the rest of the current stream
+    pub fees_outstanding: u64,
+}
+
+impl StakingPool {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 16 + 8 + 8 + 8 + 1 + 32 + 8 + 8 + 16 + 8; // 225 bytes
+
+    /// Accrue emissions and streamed fees up to `now`
+    pub fn update(&mut self, now: i64) -> Option<()> {
+        let end = now.min(self.emission_end);
+        if end > self.last_update && self.total_weight > 0 {
+            let emitted = (end - self.last_update) as u128 * self.emission_rate as u128;
+            self.reward_per_weight = self.reward_per_weight
+                .checked_add(emitted.checked_mul(REWARD_PRECISION)? / self.total_weight as u128)?;
+        }
+        let fee_end = now.min(self.fee_epoch_end);
+        if fee_end > self.last_update {
+            let streamed = (fee_end - self.last_update) as u128 * self.fee_rate as u128;
+            if self.total_staked > 0 {
+                self.fee_per_stake = self.fee_per_stake
+                    .checked_add(streamed.checked_mul(REWARD_PRECISION)? / self.total_staked as u128)?;
+            } else {
+                self.fees_outstanding = self.fees_outstanding
+                    .saturating_sub(u64::try_from(streamed).ok()?);
+            }
+        }
+        self.last_update = self.last_update.max(now);
+        Some(())
+    }
+}
+
+/// Weight multiplier of a lock of `duration` seconds, in basis points
+pub fn lock_multiplier_bps(duration: i64) -> u64 {
+    let duration = duration.clamp(0, MAX_LOCK_DURATION) as u64;
+    BASE_MULTIPLIER_BPS
+        + (MAX_MULTIPLIER_BPS - BASE_MULTIPLIER_BPS) * duration / MAX_LOCK_DURATION as u64
+}
+
+/// One staker's DAC in the pool
+///
+/// The dark market program reads `staker`, `amount`, `pool` and `lock_end`
+/// straight from the account data for its fee tiers, so they stay the first
+/// four fields.
+#[account]
+pub struct StakeAccount {
+    /// Wallet owning the stake
+    pub staker: Pubkey,
+    /// DAC staked
+    pub amount: u64,
+    /// The staking pool
+    pub pool: Pubkey,
+    /// Time the stake unlocks
+    pub lock_end: i64,
+    /// Weight multiplier of the lock, in basis points
+    pub multiplier_bps: u64,
+    /// Share of the pool's emissions (`amount` scaled by the multiplier)
+    pub weight: u64,
+    /// Pool `reward_per_weight` the stake last settled at
+    pub reward_debt: u128,
+    /// Settled rewards not yet claimed
+    pub pending_rewards: u64,
+    /// Bump for the stake PDA
+    pub bump: u8,
+    /// Pool `fee_per_stake` the stake last settled at
+    pub fee_debt: u128,
+    /// Settled fee revenue not yet claimed
+    pub pending_fees: u64,
+}
+
+impl StakeAccount {
+    pub const LEN: usize = 32 + 8 + 32 + 8 + 8 + 8 + 16 + 8 + 1 + 16 + 8; // 145 bytes
+
+    /// Move rewards and fees earned since the last settlement into the pending amounts
+    pub fn settle(&mut self, reward_per_weight: u128, fee_per_stake: u128) -> Option<()> {
+        let earned = (reward_per_weight - self.reward_debt) * self.weight as u128 / REWARD_PRECISION;
+        self.pending_rewards = self.pending_rewards.checked_add(u64::try_from(earned).ok()?)?;
+        self.reward_debt = reward_per_weight;
+        let fees = (fee_per_stake - self.fee_debt) * self.amount as u128 / REWARD_PRECISION;
+        self.pending_fees = self.pending_fees.checked_add(u64::try_from(fees).ok()?)?;
+        self.fee_debt = fee_per_stake;
+        Some(())
+    }
+
+    /// Recompute the weight after `amount` or the multiplier changed
+    pub fn reweigh(&mut self) -> Option<u64> {
+        let weight = self.amount as u128 * self.multiplier_bps as u128 / BASE_MULTIPLIER_BPS as u128;
+        self.weight = u64::try_from(weight).ok()?;
+        Some(self.weight)
+    }
+}
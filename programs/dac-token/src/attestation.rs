@@ -0,0 +1,85 @@
+//! Attestations of the Solana Attestation Service, decoded by hand
+//!
+//! KYC gating checks that a user holds an attestation of a configured
+//! schema, issued under a configured credential (the issuer) to their
+//! wallet. The SAS client crate pins an older Solana, so its attestation
+//! layout is decoded here: a one-byte account type followed by the Borsh
+//! fields. Revoking an attestation closes its account, so one that loads
+//! and has not expired is valid.
+//!
+//! The dark market program reuses the same check for its per-market
+//! requirements.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+
+/// The Solana Attestation Service program, owner of every attestation
+pub const SAS_PROGRAM_ID: Pubkey = pubkey!("22zoJMtdu4tQc2PzL74ZUT7FrwgB1Udec8DdW4yw4BdG");
+/// Seeds of an attestation (followed by its credential, schema and nonce)
+pub const ATTESTATION_SEED: &[u8] = b"attestation";
+/// Account type byte of SAS attestations
+pub const ATTESTATION_DISCRIMINATOR: u8 = 2;
+
+/// A SAS attestation, as stored after its account type byte
+#[derive(AnchorDeserialize, Clone)]
+pub struct Attestation {
+    /// The wallet the attestation was issued to
+    pub nonce: Pubkey,
+    /// The credential (issuer) it was issued under
+    pub credential: Pubkey,
+    /// The schema its data follows
+    pub schema: Pubkey,
+    /// The attested data, laid out by the schema
+    pub data: Vec<u8>,
+    /// The issuer's signer who attested
+    pub signer: Pubkey,
+    /// Unix timestamp the attestation expires at (0 never expires)
+    pub expiry: i64,
+    /// Token account of a tokenized attestation
+    pub token_account: Pubkey,
+}
+
+impl Attestation {
+    /// Decode `info` if it is an attestation account of the service
+    pub fn load(info: &AccountInfo) -> Option<Self> {
+        if *info.owner != SAS_PROGRAM_ID {
+            return None;
+        }
+        let data = info.try_borrow_data().ok()?;
+        let (&account_type, mut body) = data.split_first()?;
+        if account_type != ATTESTATION_DISCRIMINATOR {
+            return None;
+        }
+        Self::deserialize(&mut body).ok()
+    }
+
+    /// Whether the attestation is still valid at `now`
+    pub fn is_live(&self, now: i64) -> bool {
+        self.expiry == 0 || now < self.expiry
+    }
+}
+
+/// The attestation a user must hold: a schema issued under a credential
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct AttestationRequirement {
+    /// The credential attestations must be issued under
+    pub credential: Pubkey,
+    /// The schema attestations must follow (default when nothing is required)
+    pub schema: Pubkey,
+}
+
+impl AttestationRequirement {
+    pub const LEN: usize = 32 + 32; // 64 bytes
+
+    pub fn is_required(&self) -> bool {
+        self.schema != Pubkey::default()
+    }
+
+    /// Whether `attestation` satisfies the requirement for `user` at `now`
+    pub fn accepts(&self, attestation: &Attestation, user: &Pubkey, now: i64) -> bool {
+        attestation.credential == self.credential
+            && attestation.schema == self.schema
+            && attestation.nonce == *user
+            && attestation.is_live(now)
+    }
+}
@@ -0,0 +1,742 @@
+//! Over-collateralized DAC minted against volatile tokens (SOL, LSTs)
+//!
+//! Each accepted token has a collateral type with its own vault, owned by
+//! the collateral type PDA so nothing here can touch the USDC vault or the
+//! other way round. A position's collateral is valued with Pyth at the low
+//! end of the confidence interval, less the type's haircut; DAC can be
+//! minted while that covers the position's debt, and anyone can liquidate a
+//! position once it no longer does, repaying debt for collateral at a bonus.
+//!
+//! DAC minted here is backed by collateral rather than USDC, so the DAC
+//! issued is `total_wrapped` plus the debt of every collateral type; the
+//! supply also counts the vault shards' floats, minted but not yet issued.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::pubkey;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+
+use crate::{DacConfig, DacError, CONFIG_SEED, MINT_AUTHORITY_SEED};
+
+/// Seeds for a collateral type (followed by its mint)
+pub const COLLATERAL_TYPE_SEED: &[u8] = b"collateral_type";
+/// Seeds for a collateral type's vault (followed by its mint)
+pub const COLLATERAL_VAULT_SEED: &[u8] = b"collateral_vault";
+/// Seeds for a position (followed by the collateral type and the owner)
+pub const COLLATERAL_POSITION_SEED: &[u8] = b"collateral_position";
+/// Pyth receiver program, owner of the price update accounts
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+/// Highest liquidation bonus, in basis points (20%)
+pub const MAX_LIQUIDATION_BONUS_BPS: u16 = 2_000;
+
+const BPS: u128 = 10_000;
+
+// ============================================================================
+// Instructions
+// ============================================================================
+
+/// Accept `collateral_mint` as collateral, or update its parameters (config authority only)
+pub fn configure_collateral(
+    ctx: Context<ConfigureCollateral>,
+    price_feed_id: [u8; 32],
+    max_price_age: i64,
+    haircut_bps: u16,
+    liquidation_bonus_bps: u16,
+    debt_ceiling: u64,
+) -> Result<()> {
+    require!((haircut_bps as u128) < BPS, DacError::InvalidCollateralParams);
+    require!(liquidation_bonus_bps <= MAX_LIQUIDATION_BONUS_BPS, DacError::InvalidCollateralParams);
+    require!(max_price_age > 0, DacError::InvalidCollateralParams);
+
+    let collateral = &mut ctx.accounts.collateral_type;
+    if collateral.mint == Pubkey::default() {
+        collateral.mint = ctx.accounts.collateral_mint.key();
+        collateral.vault = ctx.accounts.collateral_vault.key();
+        collateral.decimals = ctx.accounts.collateral_mint.decimals;
+        collateral.total_deposited = 0;
+        collateral.total_debt = 0;
+        collateral.bump = ctx.bumps.collateral_type;
+    }
+    collateral.price_feed_id = price_feed_id;
+    collateral.max_price_age = max_price_age;
+    collateral.haircut_bps = haircut_bps;
+    collateral.liquidation_bonus_bps = liquidation_bonus_bps;
+    collateral.debt_ceiling = debt_ceiling;
+
+    msg!(
+        "Collateral {} configured: {} bps haircut, {} bps bonus, {} DAC ceiling",
+        collateral.mint,
+        haircut_bps,
+        liquidation_bonus_bps,
+        debt_ceiling
+    );
+    Ok(())
+}
+
+/// Move `amount` collateral into the owner's position
+pub fn deposit_collateral(ctx: Context<DepositCollateral>, amount: u64) -> Result<()> {
+    require!(amount > 0, DacError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_collateral.to_account_info(),
+            to: ctx.accounts.collateral_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let collateral = &mut ctx.accounts.collateral_type;
+    let position = &mut ctx.accounts.position;
+    if position.owner == Pubkey::default() {
+        position.owner = ctx.accounts.owner.key();
+        position.collateral_type = collateral.key();
+        position.deposited = 0;
+        position.debt = 0;
+        position.bump = ctx.bumps.position;
+    }
+    position.deposited = position.deposited.checked_add(amount).ok_or(DacError::Overflow)?;
+    collateral.total_deposited = collateral.total_deposited.checked_add(amount).ok_or(DacError::Overflow)?;
+
+    emit!(CollateralDeposited {
+        owner: position.owner,
+        collateral_mint: collateral.mint,
+        amount,
+        deposited: position.deposited,
+    });
+    Ok(())
+}
+
+/// Mint `amount` DAC against the position, which must stay healthy
+pub fn mint_with_collateral(ctx: Context<MintWithCollateral>, amount: u64) -> Result<()> {
+    require!(amount > 0, DacError::ZeroAmount);
+
+    let collateral = &mut ctx.accounts.collateral_type;
+    let position = &mut ctx.accounts.position;
+    position.debt = position.debt.checked_add(amount).ok_or(DacError::Overflow)?;
+    collateral.total_debt = collateral.total_debt.checked_add(amount).ok_or(DacError::Overflow)?;
+    require!(collateral.total_debt <= collateral.debt_ceiling, DacError::DebtCeilingReached);
+
+    let price = collateral.price(&ctx.accounts.price_update, Clock::get()?.unix_timestamp)?;
+    let power = collateral
+        .borrowing_power(&price, position.deposited, ctx.accounts.dac_mint.decimals)
+        .ok_or(DacError::Overflow)?;
+    require!(position.debt <= power, DacError::PositionUnhealthy);
+
+    let config_key = ctx.accounts.config.key();
+    let seeds = &[
+        MINT_AUTHORITY_SEED,
+        config_key.as_ref(),
+        &[ctx.accounts.config.mint_authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let mint_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.dac_mint.to_account_info(),
+            to: ctx.accounts.owner_dac.to_account_info(),
+            authority: ctx.accounts.mint_authority.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::mint_to(mint_ctx, amount)?;
+
+    emit!(CollateralMinted {
+        owner: position.owner,
+        collateral_mint: collateral.mint,
+        amount,
+        debt: position.debt,
+        total_debt: collateral.total_debt,
+    });
+    Ok(())
+}
+
+/// Burn up to `amount` of the owner's DAC against the position's debt
+pub fn repay_collateral_debt(ctx: Context<RepayCollateralDebt>, amount: u64) -> Result<()> {
+    let amount = amount.min(ctx.accounts.position.debt);
+    require!(amount > 0, DacError::ZeroAmount);
+
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.dac_mint.to_account_info(),
+            from: ctx.accounts.owner_dac.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::burn(burn_ctx, amount)?;
+
+    let collateral = &mut ctx.accounts.collateral_type;
+    let position = &mut ctx.accounts.position;
+    position.debt -= amount;
+    collateral.total_debt -= amount;
+
+    emit!(CollateralRepaid {
+        owner: position.owner,
+        collateral_mint: collateral.mint,
+        amount,
+        debt: position.debt,
+        total_debt: collateral.total_debt,
+    });
+    Ok(())
+}
+
+/// Take `amount` collateral back out; a position with debt must stay healthy
+pub fn withdraw_collateral(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
+    require!(amount > 0, DacError::ZeroAmount);
+
+    let collateral = &mut ctx.accounts.collateral_type;
+    let position = &mut ctx.accounts.position;
+    position.deposited = position.deposited.checked_sub(amount).ok_or(DacError::InsufficientCollateral)?;
+    collateral.total_deposited -= amount;
+    if position.debt > 0 {
+        let price = collateral.price(&ctx.accounts.price_update, Clock::get()?.unix_timestamp)?;
+        let power = collateral
+            .borrowing_power(&price, position.deposited, ctx.accounts.dac_mint.decimals)
+            .ok_or(DacError::Overflow)?;
+        require!(position.debt <= power, DacError::PositionUnhealthy);
+    }
+
+    pay_collateral(
+        collateral,
+        &ctx.accounts.collateral_vault,
+        &ctx.accounts.owner_collateral,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(CollateralWithdrawn {
+        owner: position.owner,
+        collateral_mint: collateral.mint,
+        amount,
+        deposited: position.deposited,
+    });
+    Ok(())
+}
+
+/// Repay up to `amount` of an unhealthy position's debt for its collateral plus the bonus (anyone)
+pub fn liquidate(ctx: Context<Liquidate>, amount: u64) -> Result<()> {
+    let collateral = &mut ctx.accounts.collateral_type;
+    let position = &mut ctx.accounts.position;
+    let amount = amount.min(position.debt);
+    require!(amount > 0, DacError::ZeroAmount);
+
+    let dac_decimals = ctx.accounts.dac_mint.decimals;
+    let price = collateral.price(&ctx.accounts.price_update, Clock::get()?.unix_timestamp)?;
+    let power = collateral
+        .borrowing_power(&price, position.deposited, dac_decimals)
+        .ok_or(DacError::Overflow)?;
+    require!(position.debt > power, DacError::PositionHealthy);
+
+    let owed = (amount as u128 * (BPS + collateral.liquidation_bonus_bps as u128) / BPS) as u64;
+    let seized = price
+        .amount_for(owed, collateral.decimals, dac_decimals)
+        .ok_or(DacError::Overflow)?
+        .min(position.deposited);
+
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.dac_mint.to_account_info(),
+            from: ctx.accounts.liquidator_dac.to_account_info(),
+            authority: ctx.accounts.liquidator.to_account_info(),
+        },
+    );
+    token::burn(burn_ctx, amount)?;
+
+    position.debt -= amount;
+    position.deposited -= seized;
+    collateral.total_debt -= amount;
+    collateral.total_deposited -= seized;
+
+    pay_collateral(
+        collateral,
+        &ctx.accounts.collateral_vault,
+        &ctx.accounts.liquidator_collateral,
+        &ctx.accounts.token_program,
+        seized,
+    )?;
+
+    emit!(PositionLiquidated {
+        owner: position.owner,
+        collateral_mint: collateral.mint,
+        liquidator: ctx.accounts.liquidator.key(),
+        repaid: amount,
+        seized,
+        debt: position.debt,
+        total_debt: collateral.total_debt,
+    });
+    Ok(())
+}
+
+/// Transfer `amount` out of a collateral vault
+fn pay_collateral<'info>(
+    collateral: &Account<'info, CollateralType>,
+    vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[COLLATERAL_TYPE_SEED, collateral.mint.as_ref(), &[collateral.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: vault.to_account_info(),
+            to: to.to_account_info(),
+            authority: collateral.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)
+}
+
+// ============================================================================
+// State
+// ============================================================================
+
+/// A volatile token accepted as collateral for DAC
+#[account]
+pub struct CollateralType {
+    /// The collateral mint (the native mint for SOL)
+    pub mint: Pubkey,
+    /// Vault holding every position's collateral, owned by this account
+    pub vault: Pubkey,
+    /// Decimals of the collateral mint
+    pub decimals: u8,
+    /// Pyth feed pricing the collateral in USD
+    pub price_feed_id: [u8; 32],
+    /// Oldest price accepted, in seconds
+    pub max_price_age: i64,
+    /// Share of the collateral's value that cannot be borrowed against, in basis points
+    pub haircut_bps: u16,
+    /// Extra collateral paid to liquidators on top of the debt they repay, in basis points
+    pub liquidation_bonus_bps: u16,
+    /// Most DAC that can be minted against this collateral
+    pub debt_ceiling: u64,
+    /// DAC minted against this collateral and not yet repaid
+    pub total_debt: u64,
+    /// Collateral held in the vault
+    pub total_deposited: u64,
+    /// Bump for the collateral type PDA
+    pub bump: u8,
+}
+
+impl CollateralType {
+    pub const LEN: usize = 32 + 32 + 1 + 32 + 8 + 2 + 2 + 8 + 8 + 8 + 1; // 134 bytes
+
+    /// The collateral's price from a Pyth price update, if fresh
+    pub fn price(&self, price_update: &AccountInfo, now: i64) -> Result<Price> {
+        require_keys_eq!(*price_update.owner, PYTH_RECEIVER_PROGRAM_ID, DacError::InvalidPriceUpdate);
+        let price = read_price_update(&price_update.try_borrow_data()?, &self.price_feed_id)
+            .ok_or(DacError::InvalidPriceUpdate)?;
+        require!(now - price.publish_time <= self.max_price_age, DacError::StalePrice);
+        Ok(price)
+    }
+
+    /// DAC that `deposited` collateral can back at `price`, after the haircut
+    pub fn borrowing_power(&self, price: &Price, deposited: u64, dac_decimals: u8) -> Option<u64> {
+        let value = price.value(deposited, self.decimals, dac_decimals)?;
+        Some((value as u128 * (BPS - self.haircut_bps as u128) / BPS) as u64)
+    }
+}
+
+/// One owner's collateral and debt of a collateral type
+#[account]
+pub struct CollateralPosition {
+    pub owner: Pubkey,
+    pub collateral_type: Pubkey,
+    /// Collateral deposited
+    pub deposited: u64,
+    /// DAC minted against it and not yet repaid
+    pub debt: u64,
+    /// Bump for the position PDA
+    pub bump: u8,
+}
+
+impl CollateralPosition {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 1; // 81 bytes
+}
+
+/// A Pyth price, `price * 10^exponent` USD per whole token
+pub struct Price {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl Price {
+    /// The low end of the confidence interval, zero if it is not positive
+    fn low(&self) -> u128 {
+        (self.price as i128 - self.conf as i128).max(0) as u128
+    }
+
+    /// Power of ten converting collateral units times the price into DAC units
+    fn scale(&self, decimals: u8, dac_decimals: u8) -> i32 {
+        self.exponent + dac_decimals as i32 - decimals as i32
+    }
+
+    /// DAC value of `amount` collateral units, at the low price
+    pub fn value(&self, amount: u64, decimals: u8, dac_decimals: u8) -> Option<u64> {
+        let raw = amount as u128 * self.low();
+        let scale = self.scale(decimals, dac_decimals);
+        let value = if scale >= 0 {
+            raw.checked_mul(10u128.checked_pow(scale as u32)?)?
+        } else {
+            raw / 10u128.checked_pow(scale.unsigned_abs())?
+        };
+        u64::try_from(value).ok()
+    }
+
+    /// Collateral units worth `value` DAC, at the low price (`u64::MAX` at a price of zero)
+    pub fn amount_for(&self, value: u64, decimals: u8, dac_decimals: u8) -> Option<u64> {
+        let low = self.low();
+        if low == 0 {
+            return Some(u64::MAX);
+        }
+        let scale = self.scale(decimals, dac_decimals);
+        let amount = if scale >= 0 {
+            value as u128 / low.checked_mul(10u128.checked_pow(scale as u32)?)?
+        } else {
+            (value as u128).checked_mul(10u128.checked_pow(scale.unsigned_abs())?)? / low
+        };
+        Some(u64::try_from(amount).unwrap_or(u64::MAX))
+    }
+}
+
+/// Price of `feed_id` from a Pyth receiver `PriceUpdateV2` account
+///
+/// Parsed by hand: the discriminator, the write authority, the verification
+/// level (only fully verified updates, the single tag byte 1, are accepted),
+/// then the price message starting with its feed id.
+pub fn read_price_update(data: &[u8], feed_id: &[u8; 32]) -> Option<Price> {
+    let discriminator = hash(b"account:PriceUpdateV2").to_bytes();
+    if data.get(..8)? != &discriminator[..8] || *data.get(40)? != 1 {
+        return None;
+    }
+    let message = data.get(41..41 + 32 + 8 + 8 + 4 + 8)?;
+    if message[..32] != feed_id[..] {
+        return None;
+    }
+    Some(Price {
+        price: i64::from_le_bytes(message[32..40].try_into().ok()?),
+        conf: u64::from_le_bytes(message[40..48].try_into().ok()?),
+        exponent: i32::from_le_bytes(message[48..52].try_into().ok()?),
+        publish_time: i64::from_le_bytes(message[52..60].try_into().ok()?),
+    })
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[event]
+pub struct CollateralDeposited {
+    pub owner: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub amount: u64,
+    /// Position's collateral after the deposit
+    pub deposited: u64,
+}
+
+#[event]
+pub struct CollateralWithdrawn {
+    pub owner: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub amount: u64,
+    /// Position's collateral after the withdrawal
+    pub deposited: u64,
+}
+
+/// DAC minted against collateral
+#[event]
+pub struct CollateralMinted {
+    pub owner: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub amount: u64,
+    /// Position's debt after the mint
+    pub debt: u64,
+    /// Collateral type's debt after the mint
+    pub total_debt: u64,
+}
+
+/// DAC burned against collateral debt
+#[event]
+pub struct CollateralRepaid {
+    pub owner: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub amount: u64,
+    pub debt: u64,
+    pub total_debt: u64,
+}
+
+#[event]
+pub struct PositionLiquidated {
+    pub owner: Pubkey,
+    pub collateral_mint: Pubkey,
+    pub liquidator: Pubkey,
+    /// DAC burned by the liquidator
+    pub repaid: u64,
+    /// Collateral paid to the liquidator
+    pub seized: u64,
+    pub debt: u64,
+    pub total_debt: u64,
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ConfigureCollateral<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump,
+        has_one = authority @ DacError::Unauthorized,
+    )]
+    pub config: Account<'info, DacConfig>,
+
+    /// The collateral type (created on first configuration)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + CollateralType::LEN,
+        seeds = [COLLATERAL_TYPE_SEED, collateral_mint.key().as_ref()],
+        bump
+    )]
+    pub collateral_type: Box<Account<'info, CollateralType>>,
+
+    /// The collateral type's vault (created on first configuration)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [COLLATERAL_VAULT_SEED, collateral_mint.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = collateral_type,
+    )]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(constraint = collateral_mint.key() != config.dac_mint @ DacError::MintMismatch)]
+    pub collateral_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositCollateral<'info> {
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_SEED, collateral_type.mint.as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Box<Account<'info, CollateralType>>,
+
+    /// The owner's position (created on first deposit)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + CollateralPosition::LEN,
+        seeds = [COLLATERAL_POSITION_SEED, collateral_type.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, CollateralPosition>>,
+
+    #[account(mut, address = collateral_type.vault @ DacError::InvalidCollateralVault)]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's collateral token account (source)
+    #[account(
+        mut,
+        constraint = owner_collateral.mint == collateral_type.mint @ DacError::MintMismatch,
+    )]
+    pub owner_collateral: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintWithCollateral<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump,
+        constraint = config.dac_mint == dac_mint.key() @ DacError::MintMismatch,
+    )]
+    pub config: Box<Account<'info, DacConfig>>,
+
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_SEED, collateral_type.mint.as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Box<Account<'info, CollateralType>>,
+
+    #[account(
+        mut,
+        seeds = [COLLATERAL_POSITION_SEED, collateral_type.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Box<Account<'info, CollateralPosition>>,
+
+    /// CHECK: Pyth price update of the collateral, checked when read
+    pub price_update: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    /// Owner's DAC token account (destination)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == config.dac_mint @ DacError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Mint authority PDA
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, config.key().as_ref()],
+        bump = config.mint_authority_bump,
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RepayCollateralDebt<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump,
+        constraint = config.dac_mint == dac_mint.key() @ DacError::MintMismatch,
+    )]
+    pub config: Box<Account<'info, DacConfig>>,
+
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_SEED, collateral_type.mint.as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Box<Account<'info, CollateralType>>,
+
+    #[account(
+        mut,
+        seeds = [COLLATERAL_POSITION_SEED, collateral_type.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Box<Account<'info, CollateralPosition>>,
+
+    #[account(mut)]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    /// Owner's DAC token account (burned from)
+    #[account(mut)]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCollateral<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump,
+        constraint = config.dac_mint == dac_mint.key() @ DacError::MintMismatch,
+    )]
+    pub config: Box<Account<'info, DacConfig>>,
+
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_SEED, collateral_type.mint.as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Box<Account<'info, CollateralType>>,
+
+    #[account(
+        mut,
+        seeds = [COLLATERAL_POSITION_SEED, collateral_type.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Box<Account<'info, CollateralPosition>>,
+
+    #[account(mut, address = collateral_type.vault @ DacError::InvalidCollateralVault)]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's collateral token account (destination)
+    #[account(
+        mut,
+        constraint = owner_collateral.mint == collateral_type.mint @ DacError::MintMismatch,
+    )]
+    pub owner_collateral: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Pyth price update of the collateral, checked when read (unused without debt)
+    pub price_update: AccountInfo<'info>,
+
+    /// The DAC mint, for its decimals
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Liquidate<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump,
+        constraint = config.dac_mint == dac_mint.key() @ DacError::MintMismatch,
+    )]
+    pub config: Box<Account<'info, DacConfig>>,
+
+    #[account(
+        mut,
+        seeds = [COLLATERAL_TYPE_SEED, collateral_type.mint.as_ref()],
+        bump = collateral_type.bump,
+    )]
+    pub collateral_type: Box<Account<'info, CollateralType>>,
+
+    /// The unhealthy position
+    #[account(
+        mut,
+        seeds = [COLLATERAL_POSITION_SEED, collateral_type.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump,
+    )]
+    pub position: Box<Account<'info, CollateralPosition>>,
+
+    #[account(mut, address = collateral_type.vault @ DacError::InvalidCollateralVault)]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Pyth price update of the collateral, checked when read
+    pub price_update: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    /// Liquidator's DAC token account (burned from)
+    #[account(mut)]
+    pub liquidator_dac: Box<Account<'info, TokenAccount>>,
+
+    /// Liquidator's collateral token account (destination)
+    #[account(
+        mut,
+        constraint = liquidator_collateral.mint == collateral_type.mint @ DacError::MintMismatch,
+    )]
+    pub liquidator_collateral: Box<Account<'info, TokenAccount>>,
+
+    pub liquidator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
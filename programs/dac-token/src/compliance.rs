@@ -0,0 +1,134 @@
+//! Compliance mode: travel-rule payloads on large wraps and unwraps
+//!
+//! Institutions moving amounts at or above the configured threshold must
+//! attach the originator and beneficiary data required by the travel rule,
+//! encrypted to the compliance key. The payload is carried by a separate
+//! `attach_travel_rule` instruction in the same transaction, which emits it
+//! as an event; wraps and unwraps find it by instruction introspection, so
+//! their arguments are unchanged.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
+
+use crate::{DacConfig, DacError, CONFIG_SEED};
+
+/// Maximum size of an encrypted travel-rule payload in bytes
+pub const MAX_TRAVEL_RULE_PAYLOAD_LEN: usize = 512;
+
+/// Index of the user among the accounts of `attach_travel_rule`
+const ATTACH_USER_INDEX: usize = 1;
+
+// ============================================================================
+// Instructions
+// ============================================================================
+
+/// Require travel-rule payloads from `threshold` up, encrypted to `compliance_key` (config authority only)
+pub fn set_compliance(ctx: Context<SetCompliance>, threshold: u64, compliance_key: [u8; 32]) -> Result<()> {
+    require!(
+        threshold == 0 || compliance_key != [0; 32],
+        DacError::InvalidComplianceParams
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.compliance_threshold = threshold;
+    config.compliance_key = compliance_key;
+
+    if threshold > 0 {
+        msg!("Travel-rule payloads required from {}", threshold);
+    } else {
+        msg!("Compliance mode disabled");
+    }
+    Ok(())
+}
+
+/// Publish the travel-rule payload covering a wrap or unwrap of up to `amount` in this transaction
+pub fn attach_travel_rule(ctx: Context<AttachTravelRule>, amount: u64, payload: Vec<u8>) -> Result<()> {
+    require!(
+        !payload.is_empty() && payload.len() <= MAX_TRAVEL_RULE_PAYLOAD_LEN,
+        DacError::InvalidTravelRulePayload
+    );
+
+    emit!(TravelRuleMemo {
+        user: ctx.accounts.user.key(),
+        amount,
+        compliance_key: ctx.accounts.config.compliance_key,
+        payload,
+    });
+    msg!("Travel-rule payload attached for {}", amount);
+    Ok(())
+}
+
+/// Fail if `amount` needs a travel-rule payload and the transaction has none from `user` covering it
+pub(crate) fn check_travel_rule(
+    config: &DacConfig,
+    instructions: &AccountInfo,
+    user: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    if !config.requires_travel_rule(amount) {
+        return Ok(());
+    }
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+        if ix.program_id == crate::ID && ix.data.starts_with(crate::instruction::AttachTravelRule::DISCRIMINATOR) {
+            let attached = crate::instruction::AttachTravelRule::deserialize(&mut &ix.data[8..])?;
+            let attacher = ix.accounts.get(ATTACH_USER_INDEX).map(|meta| meta.pubkey);
+            if attacher == Some(*user) && attached.amount >= amount {
+                return Ok(());
+            }
+        }
+        index += 1;
+    }
+    err!(DacError::TravelRuleMissing)
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+/// Travel-rule data of a large wrap or unwrap
+///
+/// The payload is opaque on-chain; the compliance officer decrypts it with
+/// the secret of `compliance_key` and matches it to the transaction's
+/// `Wrapped` or `Unwrapped` event.
+#[event]
+pub struct TravelRuleMemo {
+    pub user: Pubkey,
+    /// Largest wrap or unwrap the payload covers
+    pub amount: u64,
+    /// X25519 public key the payload is encrypted to
+    pub compliance_key: [u8; 32],
+    /// Encrypted originator and beneficiary data
+    pub payload: Vec<u8>,
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetCompliance<'info> {
+    /// The config account
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+        has_one = authority @ DacError::Unauthorized,
+    )]
+    pub config: Account<'info, DacConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AttachTravelRule<'info> {
+    /// The config account (read for the compliance key)
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, DacConfig>,
+
+    /// The wrapping or unwrapping user (kept second: wraps look it up by position)
+    pub user: Signer<'info>,
+}
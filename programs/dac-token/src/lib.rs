@@ -1,26 +1,68 @@
+//! Dark Alpha Confidential (DAC) Token Program
+//!
+//! This program provides a wrapped token (DAC) that can be used as collateral
+//! in PNP prediction markets. Users deposit USDC to mint DAC tokens at 1:1 ratio.
+//!
+//! Key features:
+//! - Standard SPL Token compatible (works with PNP markets)
+//! - 1:1 backing with USDC in program vault
+//! - Mint authority controlled by program PDA
+//! - Simple wrap/unwrap mechanism
+//! - Optional bundling mode: wraps must share a transaction with a market interaction
+//! - Optional wrap/unwrap fee, paid in DAC to the stakers' fee distributor
+//! - Swap-and-wrap: any token routed through Jupiter into USDC and wrapped in one instruction
+//! - Over-collateralized DAC minted against SOL and LSTs priced by Pyth, in isolated vaults
+//! - Optional KYC gating: wraps require a Solana Attestation Service attestation of a configured schema
+//! - Optional compliance mode: large wraps and unwraps carry a travel-rule payload for the compliance key
+//! - Vault shards: wraps and unwraps go through one of several vaults with a pre-minted DAC float, in parallel
+//!
+//! `wrap` and `unwrap` are kept within a fixed compute budget so they compose
+//! cheaply with bets and Jupiter routes in one transaction: accounts the
+//! token program already validates are not deserialized, no PDA is derived
+//! on the hot path, and the result is reported by event only. They only
+//! write the user's accounts and their vault shard's, so they do not queue
+//! behind each other across shards.
+
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo, Burn};
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::pubkey;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as ix_sysvar, load_instruction_at_checked,
+};
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
+
+pub mod attestation;
+pub mod collateral;
+pub mod compliance;
+pub mod migration;
+pub mod shard;
+
+use attestation::{Attestation, AttestationRequirement};
+use collateral::*;
+use compliance::*;
+use migration::*;
+use shard::*;
 
 declare_id!("ByaYNFzb2fPCkWLJCMEY4tdrfNqEAKAPJB3kDX86W5Rq");
 
-/// Dark Alpha Confidential (DAC) Token Program
-///
-/// This program provides a wrapped token (DAC) that can be used as collateral
-/// in PNP prediction markets. Users deposit USDC to mint DAC tokens at 1:1 ratio.
-///
-/// Key features:
-/// - Standard SPL Token compatible (works with PNP markets)
-/// - 1:1 backing with USDC in program vault
-/// - Mint authority controlled by program PDA
-/// - Simple wrap/unwrap mechanism
-
 /// Seeds for the mint authority PDA
 pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
 /// Seeds for the vault authority PDA  
 pub const VAULT_AUTHORITY_SEED: &[u8] = b"vault_authority";
 /// Seeds for the config account
 pub const CONFIG_SEED: &[u8] = b"config";
-
+/// Highest wrap/unwrap fee, in basis points (1%)
+pub const MAX_WRAP_FEE_BPS: u16 = 100;
+/// Jupiter aggregator v6, the only program `swap_and_wrap` routes through
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+/// Compute budget of `wrap`, with the fee, bundling, attestation and travel-rule checks enabled
+pub const WRAP_COMPUTE_UNITS: u32 = 30_000;
+/// Compute budget of `unwrap`, with the fee and travel-rule check enabled
+pub const UNWRAP_COMPUTE_UNITS: u32 = 30_000;
+
+// Anchor 0.31's `#[program]` expansion still calls the deprecated `AccountInfo::realloc`
+#[allow(deprecated)]
 #[program]
 pub mod dac_token {
     use super::*;
@@ -37,6 +79,15 @@ pub mod dac_token {
         config.mint_authority_bump = ctx.bumps.mint_authority;
         config.vault_authority_bump = ctx.bumps.vault_authority;
         config.is_initialized = true;
+        config.market_program = Pubkey::default();
+        config.bundle_wraps = false;
+        config.fee_bps = 0;
+        config.fee_vault = Pubkey::default();
+        config.wrap_attestation = AttestationRequirement::default();
+        config.compliance_threshold = 0;
+        config.compliance_key = [0; 32];
+        config.shard_count = 0;
+        config.shard_float = 0;
 
         msg!("DAC Token Config initialized");
         msg!("DAC Mint: {}", config.dac_mint);
@@ -45,97 +96,407 @@ pub mod dac_token {
         Ok(())
     }
 
+    /// Grow a config created by the first deployment to the current layout, opening its first vault shard
+    /// Only the config authority can migrate it; the fields added since start disabled
+    pub fn migrate_config(ctx: Context<MigrateConfig>, shard_float: u64) -> Result<()> {
+        migration::migrate_config(ctx, shard_float)
+    }
+
+    /// Require wraps to be bundled with a market interaction, or lift the requirement
+    /// Only the config authority can change the mode
+    pub fn set_wrap_bundling(
+        ctx: Context<SetWrapBundling>,
+        market_program: Pubkey,
+        enabled: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.market_program = market_program;
+        config.bundle_wraps = enabled;
+
+        msg!(
+            "Wrap bundling {} for {}",
+            if enabled { "enabled" } else { "disabled" },
+            market_program
+        );
+        Ok(())
+    }
+
+    /// Set the wrap/unwrap fee and the DAC account receiving it
+    /// Only the config authority can change the fee
+    pub fn set_wrap_fee(ctx: Context<SetWrapFee>, fee_bps: u16, fee_vault: Pubkey) -> Result<()> {
+        require!(fee_bps <= MAX_WRAP_FEE_BPS, DacError::FeeTooHigh);
+
+        let config = &mut ctx.accounts.config;
+        config.fee_bps = fee_bps;
+        config.fee_vault = fee_vault;
+
+        msg!("Wrap fee set to {} bps, paid to {}", fee_bps, fee_vault);
+        Ok(())
+    }
+
+    /// Require wrappers to hold an attestation of `schema` issued under `credential`
+    /// Only the config authority can change the requirement; a default schema lifts it
+    pub fn set_wrap_attestation(
+        ctx: Context<SetWrapAttestation>,
+        credential: Pubkey,
+        schema: Pubkey,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.wrap_attestation = AttestationRequirement { credential, schema };
+
+        if config.wrap_attestation.is_required() {
+            msg!("Wraps require schema {} attested by credential {}", schema, credential);
+        } else {
+            msg!("Wraps no longer require an attestation");
+        }
+        Ok(())
+    }
+
+    /// Require travel-rule payloads on wraps and unwraps from `threshold` up (0 disables)
+    /// Only the config authority can change compliance mode
+    pub fn set_compliance(ctx: Context<SetCompliance>, threshold: u64, compliance_key: [u8; 32]) -> Result<()> {
+        compliance::set_compliance(ctx, threshold, compliance_key)
+    }
+
+    /// Attach an encrypted travel-rule payload to a wrap or unwrap in the same transaction
+    pub fn attach_travel_rule(ctx: Context<AttachTravelRule>, amount: u64, payload: Vec<u8>) -> Result<()> {
+        compliance::attach_travel_rule(ctx, amount, payload)
+    }
+
+    /// Set the DAC each vault shard's float is restocked to
+    /// Only the config authority can size the floats
+    pub fn set_shard_float(ctx: Context<SetShardFloat>, float: u64) -> Result<()> {
+        shard::set_shard_float(ctx, float)
+    }
+
+    /// Open the next vault shard; the first one takes over the unsharded USDC vault
+    /// Only the config authority can add shards
+    pub fn add_vault_shard(ctx: Context<AddVaultShard>) -> Result<()> {
+        shard::add_vault_shard(ctx)
+    }
+
+    /// Pay a vault shard's accrued fees to the fee vault and bring its float back to size (anyone)
+    pub fn restock_vault_shard(ctx: Context<RestockVaultShard>) -> Result<()> {
+        shard::restock_vault_shard(ctx)
+    }
+
+    /// Sum the vault shards into `total_wrapped` (anyone)
+    pub fn sync_vault_shards<'info>(ctx: Context<'_, '_, 'info, 'info, SyncVaultShards<'info>>) -> Result<()> {
+        shard::sync_vault_shards(ctx)
+    }
+
     /// Wrap USDC to DAC tokens
-    /// User deposits USDC into vault, receives equivalent DAC tokens
+    /// User deposits USDC into a vault shard, receives equivalent DAC tokens from its float less the wrap fee
     pub fn wrap(ctx: Context<Wrap>, amount: u64) -> Result<()> {
         require!(amount > 0, DacError::ZeroAmount);
+        ctx.accounts.check_bundled()?;
+        ctx.accounts.check_attested()?;
+        check_travel_rule(&ctx.accounts.config, &ctx.accounts.instructions, &ctx.accounts.user.key(), amount)?;
+        ctx.accounts.deposit(amount)
+    }
 
-        // Transfer USDC from user to vault
+    /// Swap any token into USDC through Jupiter and wrap all of it into DAC
+    /// `route_data` is a Jupiter route instruction paying out to `user_usdc`, with its accounts
+    /// passed as remaining accounts; fails if it yields less than `min_usdc`
+    pub fn swap_and_wrap<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwapAndWrap<'info>>,
+        route_data: Vec<u8>,
+        min_usdc: u64,
+    ) -> Result<()> {
+        require!(min_usdc > 0, DacError::ZeroAmount);
+        ctx.accounts.wrap.check_bundled()?;
+        ctx.accounts.wrap.check_attested()?;
+
+        // Only the swap's output is wrapped, never USDC the user already held
+        let before = token::accessor::amount(&ctx.accounts.wrap.user_usdc)?;
+        let route = Instruction {
+            program_id: JUPITER_PROGRAM_ID,
+            accounts: ctx
+                .remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: route_data,
+        };
+        let mut route_accounts = ctx.remaining_accounts.to_vec();
+        route_accounts.push(ctx.accounts.jupiter_program.to_account_info());
+        invoke(&route, &route_accounts)?;
+
+        let received = token::accessor::amount(&ctx.accounts.wrap.user_usdc)?.saturating_sub(before);
+        require!(received >= min_usdc, DacError::SlippageExceeded);
+        let wrap = &mut ctx.accounts.wrap;
+        check_travel_rule(&wrap.config, &wrap.instructions, &wrap.user.key(), received)?;
+        wrap.deposit(received)
+    }
+
+    /// Accept a token as collateral or update its parameters
+    /// Only the config authority can configure collateral
+    pub fn configure_collateral(
+        ctx: Context<ConfigureCollateral>,
+        price_feed_id: [u8; 32],
+        max_price_age: i64,
+        haircut_bps: u16,
+        liquidation_bonus_bps: u16,
+        debt_ceiling: u64,
+    ) -> Result<()> {
+        collateral::configure_collateral(
+            ctx,
+            price_feed_id,
+            max_price_age,
+            haircut_bps,
+            liquidation_bonus_bps,
+            debt_ceiling,
+        )
+    }
+
+    /// Deposit collateral into the caller's position
+    pub fn deposit_collateral(ctx: Context<DepositCollateral>, amount: u64) -> Result<()> {
+        collateral::deposit_collateral(ctx, amount)
+    }
+
+    /// Mint DAC against the caller's collateral, keeping the position healthy
+    pub fn mint_with_collateral(ctx: Context<MintWithCollateral>, amount: u64) -> Result<()> {
+        collateral::mint_with_collateral(ctx, amount)
+    }
+
+    /// Burn DAC to repay the caller's collateral debt
+    pub fn repay_collateral_debt(ctx: Context<RepayCollateralDebt>, amount: u64) -> Result<()> {
+        collateral::repay_collateral_debt(ctx, amount)
+    }
+
+    /// Withdraw collateral, keeping the position healthy
+    pub fn withdraw_collateral(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
+        collateral::withdraw_collateral(ctx, amount)
+    }
+
+    /// Repay an unhealthy position's debt for its collateral plus a bonus (anyone)
+    pub fn liquidate(ctx: Context<Liquidate>, amount: u64) -> Result<()> {
+        collateral::liquidate(ctx, amount)
+    }
+
+    /// Unwrap DAC tokens back to USDC
+    /// User returns DAC tokens to a vault shard's float, receives equivalent USDC less the unwrap fee
+    /// USDC the shard cannot cover comes from further shards passed as remaining accounts, each as
+    /// its shard, USDC vault and DAC float in ascending index order; every shard drawn on emits its own event
+    pub fn unwrap<'info>(ctx: Context<'_, '_, 'info, 'info, Unwrap<'info>>, amount: u64) -> Result<()> {
+        require!(amount > 0, DacError::ZeroAmount);
+        require!(ctx.remaining_accounts.len() % 3 == 0, DacError::IncompleteExtraShard);
+        let accounts = ctx.accounts;
+        check_travel_rule(&accounts.config, &accounts.instructions, &accounts.user.key(), amount)?;
+
+        // The fee stays in the picked shard's float, owed to the fee vault
+        let fee = accounts.config.fee(amount);
+        let redeemed = amount - fee;
+        let covered = redeemed.min(accounts.shard.wrapped);
+        let shard = &mut accounts.shard;
+        shard.wrapped -= covered;
+        shard.fees = shard.fees.checked_add(fee).ok_or(DacError::Overflow)?;
+        let (index, shard_wrapped) = (shard.index, shard.wrapped);
+        accounts.redeem(&accounts.usdc_vault, &accounts.dac_float, covered + fee, covered)?;
+        emit!(Unwrapped {
+            user: accounts.user.key(),
+            amount: covered + fee,
+            fee,
+            shard: index,
+            shard_wrapped,
+        });
+
+        let mut remaining = redeemed - covered;
+        let mut previous = None;
+        for extra in ctx.remaining_accounts.chunks(3) {
+            if remaining == 0 {
+                break;
+            }
+            let mut shard = Account::<VaultShard>::try_from(&extra[0])?;
+            require!(
+                shard.index != index && previous.map_or(true, |previous| shard.index > previous),
+                DacError::InvalidVaultShard
+            );
+            require_keys_eq!(extra[1].key(), shard.usdc_vault, DacError::InvalidVaultShard);
+            require_keys_eq!(extra[2].key(), shard.dac_float, DacError::InvalidVaultShard);
+
+            let covered = remaining.min(shard.wrapped);
+            shard.wrapped -= covered;
+            accounts.redeem(&extra[1], &extra[2], covered, covered)?;
+            shard.exit(&crate::ID)?;
+            emit!(Unwrapped {
+                user: accounts.user.key(),
+                amount: covered,
+                fee: 0,
+                shard: shard.index,
+                shard_wrapped: shard.wrapped,
+            });
+            remaining -= covered;
+            previous = Some(shard.index);
+        }
+        require!(remaining == 0, DacError::ShardDepleted);
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Whether any top-level instruction of the current transaction calls `program`
+fn transaction_calls(instructions: &AccountInfo, program: &Pubkey) -> Result<bool> {
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+        if ix.program_id == *program {
+            return Ok(true);
+        }
+        index += 1;
+    }
+    Ok(false)
+}
+
+impl<'info> Unwrap<'info> {
+    /// Return `dac` of the user's DAC to a shard's float and pay out `usdc` from its vault
+    fn redeem(
+        &self,
+        usdc_vault: &AccountInfo<'info>,
+        dac_float: &AccountInfo<'info>,
+        dac: u64,
+        usdc: u64,
+    ) -> Result<()> {
+        let token_program = self.token_program.to_account_info();
         let transfer_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
+            token_program.clone(),
             Transfer {
-                from: ctx.accounts.user_usdc.to_account_info(),
-                to: ctx.accounts.usdc_vault.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
+                from: self.user_dac.to_account_info(),
+                to: dac_float.clone(),
+                authority: self.user.to_account_info(),
             },
         );
-        token::transfer(transfer_ctx, amount)?;
+        token::transfer(transfer_ctx, dac)?;
 
-        // Mint DAC tokens to user
-        let config_key = ctx.accounts.config.key();
-        let seeds = &[
-            MINT_AUTHORITY_SEED,
-            config_key.as_ref(),
-            &[ctx.accounts.config.mint_authority_bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
-
-        let mint_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            MintTo {
-                mint: ctx.accounts.dac_mint.to_account_info(),
-                to: ctx.accounts.user_dac.to_account_info(),
-                authority: ctx.accounts.mint_authority.to_account_info(),
+        let config_key = self.config.key();
+        let seeds = &[VAULT_AUTHORITY_SEED, config_key.as_ref(), &[self.config.vault_authority_bump]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            token_program,
+            Transfer {
+                from: usdc_vault.clone(),
+                to: self.user_usdc.to_account_info(),
+                authority: self.vault_authority.to_account_info(),
             },
-            signer_seeds,
+            &[&seeds[..]],
         );
-        token::mint_to(mint_ctx, amount)?;
+        token::transfer(transfer_ctx, usdc)
+    }
+}
 
-        // Update total wrapped
-        let config = &mut ctx.accounts.config;
-        config.total_wrapped = config.total_wrapped.checked_add(amount)
-            .ok_or(DacError::Overflow)?;
+impl<'info> Wrap<'info> {
+    /// A standalone wrap would reveal the intent to trade ahead of the trade itself
+    fn check_bundled(&self) -> Result<()> {
+        if self.config.bundle_wraps {
+            require!(
+                transaction_calls(&self.instructions, &self.config.market_program)?,
+                DacError::WrapNotBundled
+            );
+        }
+        Ok(())
+    }
 
-        msg!("Wrapped {} USDC to DAC", amount);
+    /// KYC gating: the user must hold a live attestation of the configured schema
+    fn check_attested(&self) -> Result<()> {
+        let requirement = &self.config.wrap_attestation;
+        if !requirement.is_required() {
+            return Ok(());
+        }
+        let info = self.attestation.as_ref().ok_or(DacError::AttestationRequired)?;
+        let attestation = Attestation::load(info).ok_or(DacError::InvalidAttestation)?;
+        require!(
+            requirement.accepts(&attestation, &self.user.key(), Clock::get()?.unix_timestamp),
+            DacError::InvalidAttestation
+        );
         Ok(())
     }
 
-    /// Unwrap DAC tokens back to USDC
-    /// User burns DAC tokens, receives equivalent USDC from vault
-    pub fn unwrap(ctx: Context<Unwrap>, amount: u64) -> Result<()> {
-        require!(amount > 0, DacError::ZeroAmount);
+    /// Move `amount` of the user's USDC into the shard's vault and issue DAC against it from the float
+    fn deposit(&mut self, amount: u64) -> Result<()> {
+        // The fee stays in the float, owed to the fee vault
+        let fee = self.config.fee(amount);
+        let issued = amount - fee;
+        let shard = &mut self.shard;
+        let available = token::accessor::amount(&self.dac_float)?.saturating_sub(shard.fees);
+        require!(available >= issued, DacError::ShardDepleted);
+        shard.wrapped = shard.wrapped.checked_add(amount).ok_or(DacError::Overflow)?;
+        shard.fees = shard.fees.checked_add(fee).ok_or(DacError::Overflow)?;
 
-        // Burn DAC tokens from user
-        let burn_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Burn {
-                mint: ctx.accounts.dac_mint.to_account_info(),
-                from: ctx.accounts.user_dac.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
+        // Transfer USDC from user to vault
+        let token_program = self.token_program.to_account_info();
+        let transfer_ctx = CpiContext::new(
+            token_program.clone(),
+            Transfer {
+                from: self.user_usdc.to_account_info(),
+                to: self.usdc_vault.to_account_info(),
+                authority: self.user.to_account_info(),
             },
         );
-        token::burn(burn_ctx, amount)?;
-
-        // Transfer USDC from vault to user
-        let config_key = ctx.accounts.config.key();
-        let seeds = &[
-            VAULT_AUTHORITY_SEED,
-            config_key.as_ref(),
-            &[ctx.accounts.config.vault_authority_bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        token::transfer(transfer_ctx, amount)?;
 
+        // Issue DAC tokens from the float to user
+        let config_key = self.config.key();
+        let seeds = &[MINT_AUTHORITY_SEED, config_key.as_ref(), &[self.config.mint_authority_bump]];
         let transfer_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
+            token_program,
             Transfer {
-                from: ctx.accounts.usdc_vault.to_account_info(),
-                to: ctx.accounts.user_usdc.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
+                from: self.dac_float.to_account_info(),
+                to: self.user_dac.to_account_info(),
+                authority: self.mint_authority.to_account_info(),
             },
-            signer_seeds,
+            &[&seeds[..]],
         );
-        token::transfer(transfer_ctx, amount)?;
+        token::transfer(transfer_ctx, issued)?;
 
-        // Update total wrapped
-        let config = &mut ctx.accounts.config;
-        config.total_wrapped = config.total_wrapped.checked_sub(amount)
-            .ok_or(DacError::Underflow)?;
+        emit!(Wrapped {
+            user: self.user.key(),
+            amount,
+            fee,
+            shard: self.shard.index,
+            shard_wrapped: self.shard.wrapped,
+        });
 
-        msg!("Unwrapped {} DAC to USDC", amount);
         Ok(())
     }
 }
 
+// ============================================================================
+// Events
+// ============================================================================
+
+/// USDC wrapped into DAC
+#[event]
+pub struct Wrapped {
+    pub user: Pubkey,
+    /// USDC deposited
+    pub amount: u64,
+    /// DAC kept for the fee vault instead of issued to the user
+    pub fee: u64,
+    /// Vault shard the wrap went through
+    pub shard: u8,
+    /// USDC backing DAC in the shard after the wrap
+    pub shard_wrapped: u64,
+}
+
+/// DAC unwrapped into USDC
+#[event]
+pub struct Unwrapped {
+    pub user: Pubkey,
+    /// DAC given up, including the fee
+    pub amount: u64,
+    /// DAC kept for the fee vault instead of being redeemed
+    pub fee: u64,
+    /// Vault shard the unwrap went through
+    pub shard: u8,
+    /// USDC backing DAC in the shard after the unwrap
+    pub shard_wrapped: u64,
+}
+
 // ============================================================================
 // Account Structures
 // ============================================================================
@@ -149,9 +510,9 @@ pub struct DacConfig {
     pub dac_mint: Pubkey,
     /// The underlying USDC mint
     pub usdc_mint: Pubkey,
-    /// The USDC vault holding backing funds
+    /// The USDC vault of the unsharded layout, emptied into the first vault shard
     pub vault: Pubkey,
-    /// Total amount of USDC wrapped (for tracking)
+    /// Total amount of USDC wrapped, summed from the vault shards by `sync_vault_shards`
     pub total_wrapped: u64,
     /// Bump for mint authority PDA
     pub mint_authority_bump: u8,
@@ -159,10 +520,39 @@ pub struct DacConfig {
     pub vault_authority_bump: u8,
     /// Is initialized flag
     pub is_initialized: bool,
+    /// Market program wraps must be bundled with when bundling is enabled
+    pub market_program: Pubkey,
+    /// Whether wraps must share a transaction with a market interaction
+    pub bundle_wraps: bool,
+    /// Fee on wraps and unwraps, in basis points of the amount
+    pub fee_bps: u16,
+    /// DAC token account receiving the fees (the stakers' fee distributor)
+    pub fee_vault: Pubkey,
+    /// Attestation wrappers must hold (none while its schema is default)
+    pub wrap_attestation: AttestationRequirement,
+    /// Wraps and unwraps from this amount up need a travel-rule payload (0 disables)
+    pub compliance_threshold: u64,
+    /// X25519 public key travel-rule payloads are encrypted to
+    pub compliance_key: [u8; 32],
+    /// Vault shards opened so far
+    pub shard_count: u8,
+    /// DAC each shard's float is restocked to
+    pub shard_float: u64,
 }
 
 impl DacConfig {
-    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 1 + 1 + 1; // 139 bytes
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 1 + 1 + 1 + 32 + 1 + 2 + 32
+        + AttestationRequirement::LEN + 8 + 32 + 1 + 8; // 319 bytes
+
+    /// Fee on wrapping or unwrapping `amount`
+    pub fn fee(&self, amount: u64) -> u64 {
+        (amount as u128 * self.fee_bps as u128 / 10_000) as u64
+    }
+
+    /// Whether wrapping or unwrapping `amount` needs a travel-rule payload
+    pub fn requires_travel_rule(&self, amount: u64) -> bool {
+        self.compliance_threshold > 0 && amount >= self.compliance_threshold
+    }
 }
 
 // ============================================================================
@@ -224,101 +614,137 @@ pub struct Initialize<'info> {
 }
 
 #[derive(Accounts)]
-pub struct Wrap<'info> {
+pub struct SetWrapBundling<'info> {
     /// The config account
     #[account(
         mut,
         seeds = [CONFIG_SEED],
         bump,
-        constraint = config.is_initialized @ DacError::NotInitialized,
-        constraint = config.dac_mint == dac_mint.key() @ DacError::MintMismatch,
+        has_one = authority @ DacError::Unauthorized,
     )]
     pub config: Account<'info, DacConfig>,
 
-    /// The DAC SPL token mint
-    #[account(mut)]
-    pub dac_mint: Account<'info, Mint>,
+    pub authority: Signer<'info>,
+}
 
-    /// User's USDC token account (source)
+#[derive(Accounts)]
+pub struct SetWrapFee<'info> {
+    /// The config account
     #[account(
         mut,
-        constraint = user_usdc.mint == config.usdc_mint @ DacError::MintMismatch,
+        seeds = [CONFIG_SEED],
+        bump,
+        has_one = authority @ DacError::Unauthorized,
     )]
-    pub user_usdc: Account<'info, TokenAccount>,
+    pub config: Account<'info, DacConfig>,
 
-    /// User's DAC token account (destination)
-    #[account(
-        mut,
-        constraint = user_dac.mint == config.dac_mint @ DacError::MintMismatch,
-    )]
-    pub user_dac: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+}
 
-    /// The USDC vault
+#[derive(Accounts)]
+pub struct SetWrapAttestation<'info> {
+    /// The config account
     #[account(
         mut,
-        seeds = [b"usdc_vault", config.key().as_ref()],
+        seeds = [CONFIG_SEED],
         bump,
+        has_one = authority @ DacError::Unauthorized,
     )]
-    pub usdc_vault: Account<'info, TokenAccount>,
+    pub config: Account<'info, DacConfig>,
 
-    /// CHECK: Mint authority PDA
-    #[account(
-        seeds = [MINT_AUTHORITY_SEED, config.key().as_ref()],
-        bump = config.mint_authority_bump,
-    )]
+    pub authority: Signer<'info>,
+}
+
+// The wrap and unwrap contexts are on the hot path: the config and vault shards are
+// only created at their PDAs, so their seeds are not re-derived, and token accounts
+// the token program validates in the CPIs are checked by address, not decoded. The
+// config is only read, so wraps and unwraps through different shards run in parallel.
+
+#[derive(Accounts)]
+pub struct Wrap<'info> {
+    /// The config account
+    #[account(constraint = config.is_initialized @ DacError::NotInitialized)]
+    pub config: Account<'info, DacConfig>,
+
+    /// The vault shard the client picked
+    #[account(mut)]
+    pub shard: Account<'info, VaultShard>,
+
+    /// CHECK: User's USDC token account (source); the transfer to the vault checks its mint
+    #[account(mut)]
+    pub user_usdc: UncheckedAccount<'info>,
+
+    /// CHECK: User's DAC token account (destination); the transfer from the float checks its mint
+    #[account(mut)]
+    pub user_dac: UncheckedAccount<'info>,
+
+    /// CHECK: The shard's USDC vault
+    #[account(mut, address = shard.usdc_vault @ DacError::InvalidVaultShard)]
+    pub usdc_vault: UncheckedAccount<'info>,
+
+    /// CHECK: The shard's DAC float
+    #[account(mut, address = shard.dac_float @ DacError::InvalidVaultShard)]
+    pub dac_float: UncheckedAccount<'info>,
+
+    /// CHECK: Mint authority PDA; the float only accepts it signed for with the config's bump
     pub mint_authority: AccountInfo<'info>,
 
+    /// CHECK: The instructions sysvar (read when wraps must be bundled)
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    /// CHECK: The user's SAS attestation (required while wraps require one), decoded and checked
+    pub attestation: Option<UncheckedAccount<'info>>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct SwapAndWrap<'info> {
+    /// The wrap of the swap's output
+    pub wrap: Wrap<'info>,
+
+    /// CHECK: Jupiter, invoked with the route's accounts
+    #[account(address = JUPITER_PROGRAM_ID)]
+    pub jupiter_program: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct Unwrap<'info> {
     /// The config account
-    #[account(
-        mut,
-        seeds = [CONFIG_SEED],
-        bump,
-        constraint = config.is_initialized @ DacError::NotInitialized,
-        constraint = config.dac_mint == dac_mint.key() @ DacError::MintMismatch,
-    )]
+    #[account(constraint = config.is_initialized @ DacError::NotInitialized)]
     pub config: Account<'info, DacConfig>,
 
-    /// The DAC SPL token mint
+    /// The vault shard the client picked, drawn on first and keeping the fee
     #[account(mut)]
-    pub dac_mint: Account<'info, Mint>,
+    pub shard: Account<'info, VaultShard>,
 
-    /// User's DAC token account (source - will be burned)
-    #[account(
-        mut,
-        constraint = user_dac.mint == config.dac_mint @ DacError::MintMismatch,
-    )]
-    pub user_dac: Account<'info, TokenAccount>,
+    /// CHECK: User's DAC token account (source); the transfer to the float checks its mint
+    #[account(mut)]
+    pub user_dac: UncheckedAccount<'info>,
 
-    /// User's USDC token account (destination)
-    #[account(
-        mut,
-        constraint = user_usdc.mint == config.usdc_mint @ DacError::MintMismatch,
-    )]
-    pub user_usdc: Account<'info, TokenAccount>,
+    /// CHECK: User's USDC token account (destination); the transfer from the vault checks its mint
+    #[account(mut)]
+    pub user_usdc: UncheckedAccount<'info>,
 
-    /// The USDC vault
-    #[account(
-        mut,
-        seeds = [b"usdc_vault", config.key().as_ref()],
-        bump,
-    )]
-    pub usdc_vault: Account<'info, TokenAccount>,
+    /// CHECK: The shard's USDC vault
+    #[account(mut, address = shard.usdc_vault @ DacError::InvalidVaultShard)]
+    pub usdc_vault: UncheckedAccount<'info>,
 
-    /// CHECK: Vault authority PDA
-    #[account(
-        seeds = [VAULT_AUTHORITY_SEED, config.key().as_ref()],
-        bump = config.vault_authority_bump,
-    )]
+    /// CHECK: The shard's DAC float
+    #[account(mut, address = shard.dac_float @ DacError::InvalidVaultShard)]
+    pub dac_float: UncheckedAccount<'info>,
+
+    /// CHECK: Vault authority PDA; the vault only accepts it signed for with the config's bump
     pub vault_authority: AccountInfo<'info>,
 
+    /// CHECK: The instructions sysvar (read in compliance mode)
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: AccountInfo<'info>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -343,4 +769,48 @@ pub enum DacError {
     Overflow,
     #[msg("Arithmetic underflow")]
     Underflow,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Wrap must be bundled with a market interaction")]
+    WrapNotBundled,
+    #[msg("Wrap fee exceeds the maximum")]
+    FeeTooHigh,
+    #[msg("Fee vault missing or not the configured one")]
+    InvalidFeeVault,
+    #[msg("Swap returned less USDC than the minimum")]
+    SlippageExceeded,
+    #[msg("Invalid collateral parameters")]
+    InvalidCollateralParams,
+    #[msg("Collateral vault is not the collateral type's")]
+    InvalidCollateralVault,
+    #[msg("Price update is not a verified Pyth update of the collateral's feed")]
+    InvalidPriceUpdate,
+    #[msg("Price update is too old")]
+    StalePrice,
+    #[msg("Collateral debt ceiling reached")]
+    DebtCeilingReached,
+    #[msg("Position would not cover its debt")]
+    PositionUnhealthy,
+    #[msg("Position covers its debt and cannot be liquidated")]
+    PositionHealthy,
+    #[msg("Not enough collateral in the position")]
+    InsufficientCollateral,
+    #[msg("An attestation is required")]
+    AttestationRequired,
+    #[msg("Attestation is not a live one of the required schema and credential for the user")]
+    InvalidAttestation,
+    #[msg("Invalid compliance parameters")]
+    InvalidComplianceParams,
+    #[msg("Travel-rule payload is empty or too long")]
+    InvalidTravelRulePayload,
+    #[msg("Amount needs a travel-rule payload from the user in the same transaction")]
+    TravelRuleMissing,
+    #[msg("Vault shard missing, out of order, not the one passed or over the limit")]
+    InvalidVaultShard,
+    #[msg("Vault shard cannot cover the amount; pass further shards or restock it")]
+    ShardDepleted,
+    #[msg("Config is not in the first deployment's layout")]
+    NotLegacyConfig,
+    #[msg("Further vault shards must each come as shard, USDC vault and DAC float")]
+    IncompleteExtraShard,
 }
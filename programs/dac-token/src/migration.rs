@@ -0,0 +1,182 @@
+//! Migration of the config created by the first deployment
+//!
+//! The first deployment's config ends at `is_initialized`; every field after
+//! it was appended later. An account in that layout is too short to load as
+//! `DacConfig`, so every instruction taking the config fails until
+//! `migrate_config` grows it. Wraps and unwraps also need a vault shard, so
+//! the migration opens the first one over the unsharded USDC vault with a
+//! stocked float, and they resume as soon as it lands.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+
+use crate::attestation::AttestationRequirement;
+use crate::shard::*;
+use crate::{DacConfig, DacError, CONFIG_SEED, MINT_AUTHORITY_SEED, VAULT_AUTHORITY_SEED};
+
+/// Size of the config in the first deployment's layout
+pub const LEGACY_CONFIG_LEN: usize = 32 + 32 + 32 + 32 + 8 + 1 + 1 + 1; // 139 bytes
+
+/// Grow the config to the current layout and open its first vault shard (config authority only)
+///
+/// The account is reallocated in place with the authority paying the extra
+/// rent, and every field added since the first deployment starts disabled.
+/// The first shard takes over the unsharded vault and its `total_wrapped`,
+/// and its float is minted up to `shard_float`.
+pub fn migrate_config(ctx: Context<MigrateConfig>, shard_float: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    let info = accounts.config.to_account_info();
+    let mut config = DacConfig::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require_keys_eq!(accounts.dac_mint.key(), config.dac_mint, DacError::MintMismatch);
+    require_keys_eq!(accounts.usdc_mint.key(), config.usdc_mint, DacError::MintMismatch);
+    require_keys_eq!(accounts.legacy_vault.key(), config.vault, DacError::InvalidVaultShard);
+
+    config.market_program = Pubkey::default();
+    config.bundle_wraps = false;
+    config.fee_bps = 0;
+    config.fee_vault = Pubkey::default();
+    config.wrap_attestation = AttestationRequirement::default();
+    config.compliance_threshold = 0;
+    config.compliance_key = [0; 32];
+    config.shard_count = 1;
+    config.shard_float = shard_float;
+
+    let config_key = info.key();
+    take_over_legacy_vault(
+        &config_key,
+        config.vault_authority_bump,
+        &accounts.legacy_vault,
+        &accounts.usdc_vault,
+        &accounts.vault_authority,
+        &accounts.token_program,
+    )?;
+    open_vault_shard(
+        &mut accounts.shard,
+        0,
+        accounts.usdc_vault.key(),
+        accounts.dac_float.key(),
+        config.total_wrapped,
+        ctx.bumps.shard,
+    );
+
+    if shard_float > 0 {
+        let seeds = &[MINT_AUTHORITY_SEED, config_key.as_ref(), &[config.mint_authority_bump]];
+        let mint_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            MintTo {
+                mint: accounts.dac_mint.to_account_info(),
+                to: accounts.dac_float.to_account_info(),
+                authority: accounts.mint_authority.to_account_info(),
+            },
+            &[&seeds[..]],
+        );
+        token::mint_to(mint_ctx, shard_float)?;
+    }
+    emit!(VaultShardRestocked {
+        shard: 0,
+        fees: 0,
+        minted: shard_float,
+        burned: 0,
+    });
+
+    let mut data = info.try_borrow_mut_data()?;
+    config.try_serialize(&mut &mut data[..])?;
+
+    msg!("DAC config migrated to {} bytes", 8 + DacConfig::LEN);
+    Ok(())
+}
+
+/// Authority of a config still in the first deployment's layout
+fn legacy_authority(config: &AccountInfo) -> Result<Pubkey> {
+    let data = config.try_borrow_data()?;
+    require!(
+        data.len() == 8 + LEGACY_CONFIG_LEN && data[..8] == *DacConfig::DISCRIMINATOR,
+        DacError::NotLegacyConfig
+    );
+    Ok(Pubkey::try_from(&data[8..40]).unwrap())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// The config authority, paying the rent of the larger config and the first shard
+    ///
+    /// Checked ahead of the config, whose realloc would otherwise hide the legacy length.
+    #[account(
+        mut,
+        constraint = legacy_authority(&config)? == authority.key() @ DacError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+
+    /// CHECK: The config in the first deployment's layout, grown to the current one
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+        realloc = 8 + DacConfig::LEN,
+        realloc::payer = authority,
+        realloc::zero = true,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// The first vault shard
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VaultShard::LEN,
+        seeds = [VAULT_SHARD_SEED, 0u8.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub shard: Box<Account<'info, VaultShard>>,
+
+    /// The first shard's USDC vault
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SHARD_VAULT_SEED, config.key().as_ref(), 0u8.to_le_bytes().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = vault_authority,
+    )]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+
+    /// The first shard's DAC float
+    #[account(
+        init,
+        payer = authority,
+        seeds = [DAC_FLOAT_SEED, 0u8.to_le_bytes().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = mint_authority,
+    )]
+    pub dac_float: Box<Account<'info, TokenAccount>>,
+
+    /// The USDC vault of the unsharded layout, emptied into the first shard's
+    #[account(mut)]
+    pub legacy_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    pub usdc_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Mint authority PDA, owner of the float
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// CHECK: Vault authority PDA, owner of the vaults
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, config.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,416 @@
+//! Vault shards: wraps and unwraps spread over independent accounts
+//!
+//! With a single config counting `total_wrapped`, a single USDC vault and
+//! DAC minted and burned on every wrap, each wrap and unwrap write-locked
+//! the same three accounts and the whole protocol ran one user at a time.
+//! Each shard instead has its own USDC vault and a float of DAC minted
+//! ahead of time, and counts its own backing and fees. A wrap moves USDC
+//! into the shard's vault and DAC out of its float, an unwrap the other way
+//! round, and neither writes the config, the mint or the fee vault, so users
+//! on different shards execute in parallel. Wraps and unwraps land unevenly,
+//! so an unwrap its shard cannot cover draws the rest from further shards
+//! it passes, locking those too.
+//!
+//! Off the hot path, `restock_vault_shard` brings a float back to the
+//! configured size, minting what wraps drew or burning what unwraps
+//! returned, and pays the fees accrued in it to the fee vault;
+//! `sync_vault_shards` sums the shards into the config's `total_wrapped`.
+//! Float DAC is minted but not issued, so the DAC backed by USDC is the
+//! supply less every float's unissued balance (and less collateral debt).
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+
+use crate::{DacConfig, DacError, CONFIG_SEED, MINT_AUTHORITY_SEED, VAULT_AUTHORITY_SEED};
+
+/// Seeds for a vault shard (followed by its index)
+pub const VAULT_SHARD_SEED: &[u8] = b"vault_shard";
+/// Seeds for a shard's USDC vault (followed by the config and the index)
+pub const SHARD_VAULT_SEED: &[u8] = b"usdc_vault";
+/// Seeds for a shard's DAC float (followed by the index)
+pub const DAC_FLOAT_SEED: &[u8] = b"dac_float";
+/// Most vault shards the config can have
+pub const MAX_VAULT_SHARDS: u8 = 32;
+
+// ============================================================================
+// Instructions
+// ============================================================================
+
+/// Set the DAC each shard's float is restocked to (config authority only)
+pub fn set_shard_float(ctx: Context<SetShardFloat>, float: u64) -> Result<()> {
+    ctx.accounts.config.shard_float = float;
+
+    msg!("Vault shard float set to {}", float);
+    Ok(())
+}
+
+/// Open the next vault shard (config authority only)
+///
+/// The first shard takes over the USDC vault of the unsharded layout: its
+/// balance moves into the shard's vault and `total_wrapped` becomes the
+/// shard's backing.
+pub fn add_vault_shard(ctx: Context<AddVaultShard>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let index = accounts.config.shard_count;
+    require!(index < MAX_VAULT_SHARDS, DacError::InvalidVaultShard);
+
+    let mut wrapped = 0;
+    if index == 0 {
+        let legacy_vault = accounts.legacy_vault.as_ref().ok_or(DacError::InvalidVaultShard)?;
+        take_over_legacy_vault(
+            &accounts.config.key(),
+            accounts.config.vault_authority_bump,
+            legacy_vault,
+            &accounts.usdc_vault,
+            &accounts.vault_authority,
+            &accounts.token_program,
+        )?;
+        wrapped = accounts.config.total_wrapped;
+    }
+
+    open_vault_shard(
+        &mut accounts.shard,
+        index,
+        accounts.usdc_vault.key(),
+        accounts.dac_float.key(),
+        wrapped,
+        ctx.bumps.shard,
+    );
+    accounts.config.shard_count = index + 1;
+    Ok(())
+}
+
+/// Pay a shard's accrued fees to the fee vault and bring its float back to size (anyone)
+pub fn restock_vault_shard(ctx: Context<RestockVaultShard>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let config_key = accounts.config.key();
+    let seeds = &[MINT_AUTHORITY_SEED, config_key.as_ref(), &[accounts.config.mint_authority_bump]];
+    let signer_seeds = &[&seeds[..]];
+    let token_program = accounts.token_program.to_account_info();
+    let dac_float = accounts.dac_float.to_account_info();
+    let mint_authority = accounts.mint_authority.to_account_info();
+
+    let fees = accounts.shard.fees;
+    if fees > 0 {
+        let fee_vault = accounts.fee_vault.as_ref().ok_or(DacError::InvalidFeeVault)?;
+        let transfer_ctx = CpiContext::new_with_signer(
+            token_program.clone(),
+            Transfer {
+                from: dac_float.clone(),
+                to: fee_vault.to_account_info(),
+                authority: mint_authority.clone(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, fees)?;
+        accounts.shard.fees = 0;
+    }
+
+    let float = accounts.dac_float.amount.checked_sub(fees).ok_or(DacError::Underflow)?;
+    let target = accounts.config.shard_float;
+    let (minted, burned) = (target.saturating_sub(float), float.saturating_sub(target));
+    if minted > 0 {
+        let mint_ctx = CpiContext::new_with_signer(
+            token_program,
+            MintTo {
+                mint: accounts.dac_mint.to_account_info(),
+                to: dac_float,
+                authority: mint_authority,
+            },
+            signer_seeds,
+        );
+        token::mint_to(mint_ctx, minted)?;
+    } else if burned > 0 {
+        let burn_ctx = CpiContext::new_with_signer(
+            token_program,
+            Burn {
+                mint: accounts.dac_mint.to_account_info(),
+                from: dac_float,
+                authority: mint_authority,
+            },
+            signer_seeds,
+        );
+        token::burn(burn_ctx, burned)?;
+    }
+
+    emit!(VaultShardRestocked {
+        shard: accounts.shard.index,
+        fees,
+        minted,
+        burned,
+    });
+    msg!("Vault shard {} restocked", accounts.shard.index);
+    Ok(())
+}
+
+/// Sum every shard's backing into the config's `total_wrapped` (anyone)
+///
+/// The shards are passed as remaining accounts, all of them, in index order.
+pub fn sync_vault_shards<'info>(ctx: Context<'_, '_, 'info, 'info, SyncVaultShards<'info>>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(
+        ctx.remaining_accounts.len() == config.shard_count as usize,
+        DacError::InvalidVaultShard
+    );
+
+    let mut total_wrapped: u64 = 0;
+    for (index, info) in ctx.remaining_accounts.iter().enumerate() {
+        let shard = Account::<VaultShard>::try_from(info)?;
+        require!(shard.index as usize == index, DacError::InvalidVaultShard);
+        total_wrapped = total_wrapped.checked_add(shard.wrapped).ok_or(DacError::Overflow)?;
+    }
+    config.total_wrapped = total_wrapped;
+
+    emit!(VaultShardsSynced {
+        shards: config.shard_count,
+        total_wrapped,
+    });
+    msg!("Synced {} wrapped over {} vault shards", total_wrapped, config.shard_count);
+    Ok(())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Move the whole balance of the unsharded USDC vault into the first shard's vault
+pub(crate) fn take_over_legacy_vault<'info>(
+    config_key: &Pubkey,
+    vault_authority_bump: u8,
+    legacy_vault: &Account<'info, TokenAccount>,
+    usdc_vault: &Account<'info, TokenAccount>,
+    vault_authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let seeds = &[VAULT_AUTHORITY_SEED, config_key.as_ref(), &[vault_authority_bump]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: legacy_vault.to_account_info(),
+            to: usdc_vault.to_account_info(),
+            authority: vault_authority.clone(),
+        },
+        &[&seeds[..]],
+    );
+    token::transfer(transfer_ctx, legacy_vault.amount)
+}
+
+/// Fill in a newly created shard, backing `wrapped` USDC
+pub(crate) fn open_vault_shard(
+    shard: &mut VaultShard,
+    index: u8,
+    usdc_vault: Pubkey,
+    dac_float: Pubkey,
+    wrapped: u64,
+    bump: u8,
+) {
+    shard.index = index;
+    shard.usdc_vault = usdc_vault;
+    shard.dac_float = dac_float;
+    shard.wrapped = wrapped;
+    shard.fees = 0;
+    shard.bump = bump;
+
+    emit!(VaultShardAdded {
+        shard: index,
+        usdc_vault,
+        dac_float,
+        wrapped,
+    });
+    msg!("Vault shard {} opened with {} wrapped", index, wrapped);
+}
+
+// ============================================================================
+// Account Structures
+// ============================================================================
+
+/// One of the independent vaults wraps and unwraps go through
+#[account]
+pub struct VaultShard {
+    /// Position among the config's shards
+    pub index: u8,
+    /// USDC vault of the shard, owned by the vault authority
+    pub usdc_vault: Pubkey,
+    /// DAC minted ahead of wraps, owned by the mint authority
+    pub dac_float: Pubkey,
+    /// USDC backing DAC held in this shard's vault
+    pub wrapped: u64,
+    /// DAC in the float owed to the fee vault
+    pub fees: u64,
+    /// Bump for the vault shard PDA
+    pub bump: u8,
+}
+
+impl VaultShard {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 1; // 82 bytes
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[event]
+pub struct VaultShardAdded {
+    pub shard: u8,
+    pub usdc_vault: Pubkey,
+    pub dac_float: Pubkey,
+    /// Backing taken over from the unsharded vault (first shard only)
+    pub wrapped: u64,
+}
+
+#[event]
+pub struct VaultShardRestocked {
+    pub shard: u8,
+    /// Accrued fees paid to the fee vault
+    pub fees: u64,
+    /// DAC minted into the float
+    pub minted: u64,
+    /// DAC burned from the float
+    pub burned: u64,
+}
+
+#[event]
+pub struct VaultShardsSynced {
+    pub shards: u8,
+    pub total_wrapped: u64,
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetShardFloat<'info> {
+    /// The config account
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+        has_one = authority @ DacError::Unauthorized,
+    )]
+    pub config: Account<'info, DacConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddVaultShard<'info> {
+    /// The config account
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+        has_one = authority @ DacError::Unauthorized,
+        has_one = dac_mint @ DacError::MintMismatch,
+        has_one = usdc_mint @ DacError::MintMismatch,
+    )]
+    pub config: Box<Account<'info, DacConfig>>,
+
+    /// The shard to open, at the config's next index
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VaultShard::LEN,
+        seeds = [VAULT_SHARD_SEED, config.shard_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub shard: Box<Account<'info, VaultShard>>,
+
+    /// The shard's USDC vault
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SHARD_VAULT_SEED, config.key().as_ref(), config.shard_count.to_le_bytes().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = vault_authority,
+    )]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+
+    /// The shard's DAC float
+    #[account(
+        init,
+        payer = authority,
+        seeds = [DAC_FLOAT_SEED, config.shard_count.to_le_bytes().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = mint_authority,
+    )]
+    pub dac_float: Box<Account<'info, TokenAccount>>,
+
+    /// The USDC vault of the unsharded layout (required for the first shard)
+    #[account(mut, address = config.vault)]
+    pub legacy_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    pub usdc_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Mint authority PDA, owner of the float
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, config.key().as_ref()],
+        bump = config.mint_authority_bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// CHECK: Vault authority PDA, owner of the vaults
+    #[account(
+        seeds = [VAULT_AUTHORITY_SEED, config.key().as_ref()],
+        bump = config.vault_authority_bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RestockVaultShard<'info> {
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump,
+        has_one = dac_mint @ DacError::MintMismatch,
+    )]
+    pub config: Box<Account<'info, DacConfig>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SHARD_SEED, shard.index.to_le_bytes().as_ref()],
+        bump = shard.bump,
+    )]
+    pub shard: Box<Account<'info, VaultShard>>,
+
+    #[account(mut, address = shard.dac_float @ DacError::InvalidVaultShard)]
+    pub dac_float: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Mint authority PDA, owner of the float
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, config.key().as_ref()],
+        bump = config.mint_authority_bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    /// CHECK: DAC fee vault (required while the shard has accrued fees)
+    #[account(mut, address = config.fee_vault @ DacError::InvalidFeeVault)]
+    pub fee_vault: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SyncVaultShards<'info> {
+    /// The config account
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, DacConfig>,
+}
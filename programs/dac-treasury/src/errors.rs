@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum TreasuryError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Cannot process zero amount")]
+    ZeroAmount,
+    #[msg("Mint mismatch")]
+    MintMismatch,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Amount exceeds the treasury's unreserved balance")]
+    InsufficientFunds,
+    #[msg("Stream must end after it starts")]
+    InvalidStream,
+    #[msg("Nothing accrued to withdraw")]
+    NothingToWithdraw,
+    #[msg("Stream was already cancelled")]
+    StreamCancelled,
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+/// A one-off payment from the treasury
+#[event]
+pub struct TreasurySpend {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    /// Hash of the off-chain memo describing the payment
+    pub memo_hash: [u8; 32],
+}
+
+/// A payment stream opened by the treasury
+#[event]
+pub struct StreamCreated {
+    pub stream: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub start: i64,
+    pub end: i64,
+    /// Hash of the off-chain memo describing the grant
+    pub memo_hash: [u8; 32],
+}
+
+/// A stream stopped before its end
+#[event]
+pub struct StreamCancelled {
+    pub stream: Pubkey,
+    /// DAC the recipient keeps, withdrawn or not
+    pub accrued: u64,
+    /// DAC released back to the treasury
+    pub released: u64,
+}
@@ -0,0 +1,5 @@
+pub mod stream;
+pub mod treasury;
+
+pub use stream::*;
+pub use treasury::*;
@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::TreasuryError;
+use crate::events::{StreamCancelled, StreamCreated};
+use crate::instructions::pay_from_treasury;
+use crate::state::*;
+use crate::{STREAM_SEED, TREASURY_SEED};
+
+/// Commit `amount` DAC to a grant accruing linearly from `start` to `end` (authority only)
+pub fn create_stream(
+    ctx: Context<CreateStream>,
+    recipient: Pubkey,
+    amount: u64,
+    start: i64,
+    end: i64,
+    memo_hash: [u8; 32],
+) -> Result<()> {
+    require!(amount > 0, TreasuryError::ZeroAmount);
+    require!(start < end, TreasuryError::InvalidStream);
+    let treasury = &mut ctx.accounts.treasury;
+    require!(
+        amount <= treasury.available(ctx.accounts.vault.amount),
+        TreasuryError::InsufficientFunds
+    );
+
+    let stream = &mut ctx.accounts.stream;
+    stream.id = treasury.stream_count;
+    stream.recipient = recipient;
+    stream.total = amount;
+    stream.withdrawn = 0;
+    stream.start = start;
+    stream.end = end;
+    stream.memo_hash = memo_hash;
+    stream.cancelled = false;
+    stream.bump = ctx.bumps.stream;
+
+    treasury.reserved = treasury.reserved.checked_add(amount).ok_or(TreasuryError::Overflow)?;
+    treasury.stream_count = treasury.stream_count.checked_add(1).ok_or(TreasuryError::Overflow)?;
+
+    emit!(StreamCreated {
+        stream: stream.key(),
+        recipient,
+        amount,
+        start,
+        end,
+        memo_hash,
+    });
+
+    msg!("Stream {} of {} DAC to {} opened", stream.id, amount, recipient);
+    Ok(())
+}
+
+/// Withdraw everything accrued and not yet withdrawn (recipient only)
+pub fn withdraw_stream(ctx: Context<WithdrawStream>) -> Result<()> {
+    let stream = &ctx.accounts.stream;
+    let amount = stream.accrued(Clock::get()?.unix_timestamp) - stream.withdrawn;
+    require!(amount > 0, TreasuryError::NothingToWithdraw);
+
+    pay_from_treasury(
+        &ctx.accounts.treasury,
+        &ctx.accounts.vault,
+        &ctx.accounts.recipient_dac,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    let stream = &mut ctx.accounts.stream;
+    stream.withdrawn += amount;
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.reserved = treasury.reserved.saturating_sub(amount);
+
+    msg!("Withdrew {} DAC from stream {}", amount, stream.id);
+    Ok(())
+}
+
+/// Stop a stream now; the recipient keeps what has accrued (authority only)
+pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+    let stream = &mut ctx.accounts.stream;
+    require!(!stream.cancelled, TreasuryError::StreamCancelled);
+
+    let accrued = stream.accrued(Clock::get()?.unix_timestamp);
+    let released = stream.total - accrued;
+    stream.total = accrued;
+    stream.cancelled = true;
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.reserved = treasury.reserved.saturating_sub(released);
+
+    emit!(StreamCancelled {
+        stream: stream.key(),
+        accrued,
+        released,
+    });
+
+    msg!("Stream {} cancelled: {} released to the treasury", stream.id, released);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreateStream<'info> {
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        has_one = authority @ TreasuryError::Unauthorized,
+        has_one = vault @ TreasuryError::MintMismatch,
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    /// The stream to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PaymentStream::LEN,
+        seeds = [STREAM_SEED, treasury.stream_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub stream: Box<Account<'info, PaymentStream>>,
+
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStream<'info> {
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        has_one = vault @ TreasuryError::MintMismatch,
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    #[account(
+        mut,
+        seeds = [STREAM_SEED, stream.id.to_le_bytes().as_ref()],
+        bump = stream.bump,
+        has_one = recipient @ TreasuryError::Unauthorized,
+    )]
+    pub stream: Box<Account<'info, PaymentStream>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Recipient's DAC token account (destination)
+    #[account(
+        mut,
+        constraint = recipient_dac.mint == treasury.dac_mint @ TreasuryError::MintMismatch,
+    )]
+    pub recipient_dac: Box<Account<'info, TokenAccount>>,
+
+    pub recipient: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        has_one = authority @ TreasuryError::Unauthorized,
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    #[account(
+        mut,
+        seeds = [STREAM_SEED, stream.id.to_le_bytes().as_ref()],
+        bump = stream.bump,
+    )]
+    pub stream: Box<Account<'info, PaymentStream>>,
+
+    pub authority: Signer<'info>,
+}
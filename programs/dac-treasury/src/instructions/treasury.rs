@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use dark_market::state::MarketConfig;
+use dark_market::MARKET_CONFIG_SEED;
+
+use crate::errors::TreasuryError;
+use crate::events::TreasurySpend;
+use crate::state::*;
+use crate::{TREASURY_SEED, TREASURY_VAULT_SEED};
+
+/// Create the treasury and its vault (dark market config authority only)
+pub fn initialize_treasury(ctx: Context<InitializeTreasury>, authority: Pubkey) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.authority = authority;
+    treasury.dac_mint = ctx.accounts.dac_mint.key();
+    treasury.vault = ctx.accounts.vault.key();
+    treasury.reserved = 0;
+    treasury.stream_count = 0;
+    treasury.bump = ctx.bumps.treasury;
+
+    msg!("Treasury initialized under {}", authority);
+    Ok(())
+}
+
+/// Pay `amount` DAC to the recipient (authority only)
+pub fn spend(ctx: Context<Spend>, amount: u64, memo_hash: [u8; 32]) -> Result<()> {
+    require!(amount > 0, TreasuryError::ZeroAmount);
+    require!(
+        amount <= ctx.accounts.treasury.available(ctx.accounts.vault.amount),
+        TreasuryError::InsufficientFunds
+    );
+
+    pay_from_treasury(
+        &ctx.accounts.treasury,
+        &ctx.accounts.vault,
+        &ctx.accounts.recipient_dac,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(TreasurySpend {
+        recipient: ctx.accounts.recipient_dac.owner,
+        amount,
+        memo_hash,
+    });
+
+    msg!("Treasury paid {} DAC to {}", amount, ctx.accounts.recipient_dac.owner);
+    Ok(())
+}
+
+/// Transfer `amount` out of the treasury vault
+pub(crate) fn pay_from_treasury<'info>(
+    treasury: &Account<'info, Treasury>,
+    vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[TREASURY_SEED, &[treasury.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: vault.to_account_info(),
+            to: to.to_account_info(),
+            authority: treasury.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    /// The dark market config, whose authority sets up the treasury
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        seeds::program = dark_market::ID,
+        has_one = authority @ TreasuryError::Unauthorized,
+        has_one = dac_mint @ TreasuryError::MintMismatch,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The treasury to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Treasury::LEN,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    /// DAC vault holding the treasury's funds
+    #[account(
+        init,
+        payer = authority,
+        seeds = [TREASURY_VAULT_SEED, treasury.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = treasury,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Spend<'info> {
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump,
+        has_one = authority @ TreasuryError::Unauthorized,
+        has_one = vault @ TreasuryError::MintMismatch,
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Recipient's DAC token account
+    #[account(
+        mut,
+        constraint = recipient_dac.mint == treasury.dac_mint @ TreasuryError::MintMismatch,
+    )]
+    pub recipient_dac: Box<Account<'info, TokenAccount>>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,70 @@
+//! DAC Treasury Program
+//!
+//! Holds protocol revenue in a DAC vault that only its authority, the
+//! governance PDA, can spend from. Every payment is an instruction of a
+//! passed governance proposal, recorded on-chain with the hash of its memo,
+//! in place of manual transfers from an admin wallet.
+//!
+//! Key features:
+//! - One-off payments to a recipient with a memo hash
+//! - Streaming payments for recurring grants, withdrawn by the recipient as they accrue
+//! - Cancellation of a stream's unaccrued remainder
+//! - Funds committed to streams reserved against other spending
+
+// Anchor 0.31's `#[program]` expansion still calls the deprecated `AccountInfo::realloc`
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("3GVmoiMRfeAtqMVZPcawMEyQPBsHwn2oTefJTFxXbYdv");
+
+/// Seeds for the treasury
+pub const TREASURY_SEED: &[u8] = b"treasury";
+/// Seeds for the treasury's DAC vault (followed by the treasury)
+pub const TREASURY_VAULT_SEED: &[u8] = b"treasury_vault";
+/// Seeds for a payment stream (followed by the stream id)
+pub const STREAM_SEED: &[u8] = b"stream";
+
+#[program]
+pub mod dac_treasury {
+    use super::*;
+
+    /// Create the treasury under an authority (dark market config authority only)
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>, authority: Pubkey) -> Result<()> {
+        instructions::initialize_treasury(ctx, authority)
+    }
+
+    /// Pay DAC from the treasury (authority only)
+    pub fn spend(ctx: Context<Spend>, amount: u64, memo_hash: [u8; 32]) -> Result<()> {
+        instructions::spend(ctx, amount, memo_hash)
+    }
+
+    /// Stream DAC from the treasury to a recipient over time (authority only)
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        recipient: Pubkey,
+        amount: u64,
+        start: i64,
+        end: i64,
+        memo_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::create_stream(ctx, recipient, amount, start, end, memo_hash)
+    }
+
+    /// Withdraw the accrued part of a stream (recipient only)
+    pub fn withdraw_stream(ctx: Context<WithdrawStream>) -> Result<()> {
+        instructions::withdraw_stream(ctx)
+    }
+
+    /// Stop a stream, releasing its unaccrued remainder (authority only)
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        instructions::cancel_stream(ctx)
+    }
+}
@@ -0,0 +1,3 @@
+mod treasury;
+
+pub use treasury::*;
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+/// The protocol treasury
+#[account]
+pub struct Treasury {
+    /// Key allowed to spend (the governance PDA)
+    pub authority: Pubkey,
+    /// The DAC mint
+    pub dac_mint: Pubkey,
+    /// DAC vault holding the treasury's funds
+    pub vault: Pubkey,
+    /// DAC committed to streams and not yet withdrawn
+    pub reserved: u64,
+    /// Number of streams created (next stream id)
+    pub stream_count: u64,
+    /// Bump for the treasury PDA
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 1; // 113 bytes
+
+    /// Vault balance not committed to streams
+    pub fn available(&self, vault_balance: u64) -> u64 {
+        vault_balance.saturating_sub(self.reserved)
+    }
+}
+
+/// A grant paid out linearly between `start` and `end`
+#[account]
+pub struct PaymentStream {
+    /// Stream id (index in the treasury's streams)
+    pub id: u64,
+    /// Wallet receiving the grant
+    pub recipient: Pubkey,
+    /// DAC granted (cut to the accrued amount on cancellation)
+    pub total: u64,
+    /// DAC withdrawn so far
+    pub withdrawn: u64,
+    /// Time the stream starts accruing
+    pub start: i64,
+    /// Time the grant is fully accrued
+    pub end: i64,
+    /// Hash of the off-chain memo describing the grant
+    pub memo_hash: [u8; 32],
+    /// Whether the stream was cancelled
+    pub cancelled: bool,
+    /// Bump for the stream PDA
+    pub bump: u8,
+}
+
+impl PaymentStream {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 32 + 1 + 1; // 106 bytes
+
+    /// DAC accrued at `now` (all of `total` once cancelled)
+    pub fn accrued(&self, now: i64) -> u64 {
+        if self.cancelled || now >= self.end {
+            self.total
+        } else if now <= self.start {
+            0
+        } else {
+            (self.total as u128 * (now - self.start) as u128 / (self.end - self.start) as u128) as u64
+        }
+    }
+}
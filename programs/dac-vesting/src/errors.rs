@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum VestingError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Cannot process zero amount")]
+    ZeroAmount,
+    #[msg("Mint mismatch")]
+    MintMismatch,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Schedule must satisfy start <= cliff <= end with start < end")]
+    InvalidSchedule,
+    #[msg("Nothing vested to claim")]
+    NothingToClaim,
+    #[msg("Vesting is not revocable")]
+    NotRevocable,
+    #[msg("Vesting was already revoked")]
+    AlreadyRevoked,
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// A new allocation locked for a beneficiary
+#[event]
+pub struct VestingCreated {
+    pub vesting: Pubkey,
+    pub grantor: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub total: u64,
+    pub start: i64,
+    pub cliff: i64,
+    pub end: i64,
+    pub revocable: bool,
+}
+
+/// Vested tokens released to the beneficiary
+#[event]
+pub struct VestedClaimed {
+    pub vesting: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    /// Tokens claimed over the vesting's lifetime, including this claim
+    pub claimed: u64,
+}
+
+/// Unvested tokens returned to the grantor
+#[event]
+pub struct VestingRevoked {
+    pub vesting: Pubkey,
+    pub grantor: Pubkey,
+    /// Tokens returned
+    pub unvested: u64,
+    /// Tokens the beneficiary keeps, claimed or not
+    pub vested: u64,
+}
@@ -0,0 +1,3 @@
+pub mod vesting;
+
+pub use vesting::*;
@@ -0,0 +1,247 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::VestingError;
+use crate::events::{VestedClaimed, VestingCreated, VestingRevoked};
+use crate::state::*;
+use crate::{VESTING_SEED, VESTING_VAULT_SEED};
+
+/// Lock `amount` of the grantor's tokens for the beneficiary
+pub fn create_vesting(
+    ctx: Context<CreateVesting>,
+    amount: u64,
+    start: i64,
+    cliff: i64,
+    end: i64,
+    revocable: bool,
+) -> Result<()> {
+    require!(amount > 0, VestingError::ZeroAmount);
+    require!(Vesting::is_valid_schedule(start, cliff, end), VestingError::InvalidSchedule);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.grantor_tokens.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.grantor.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let vesting = &mut ctx.accounts.vesting;
+    vesting.grantor = ctx.accounts.grantor.key();
+    vesting.beneficiary = ctx.accounts.beneficiary.key();
+    vesting.mint = ctx.accounts.mint.key();
+    vesting.vault = ctx.accounts.vault.key();
+    vesting.total = amount;
+    vesting.claimed = 0;
+    vesting.start = start;
+    vesting.cliff = cliff;
+    vesting.end = end;
+    vesting.revocable = revocable;
+    vesting.revoked = false;
+    vesting.bump = ctx.bumps.vesting;
+
+    emit!(VestingCreated {
+        vesting: vesting.key(),
+        grantor: vesting.grantor,
+        beneficiary: vesting.beneficiary,
+        mint: vesting.mint,
+        total: amount,
+        start,
+        cliff,
+        end,
+        revocable,
+    });
+
+    msg!("Vesting {} tokens to {} from {} to {}", amount, vesting.beneficiary, start, end);
+    Ok(())
+}
+
+/// Release everything vested and not yet claimed
+pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+    let amount = ctx.accounts.vesting.claimable(Clock::get()?.unix_timestamp);
+    require!(amount > 0, VestingError::NothingToClaim);
+
+    pay_from_vault(
+        &ctx.accounts.vesting,
+        &ctx.accounts.vault,
+        &ctx.accounts.beneficiary_tokens,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    let vesting = &mut ctx.accounts.vesting;
+    vesting.claimed = vesting.claimed.checked_add(amount).ok_or(VestingError::Overflow)?;
+
+    emit!(VestedClaimed {
+        vesting: vesting.key(),
+        beneficiary: vesting.beneficiary,
+        amount,
+        claimed: vesting.claimed,
+    });
+
+    msg!("Claimed {} vested tokens", amount);
+    Ok(())
+}
+
+/// Return the unvested tokens to the grantor (grantor only)
+///
+/// What has vested stays claimable by the beneficiary.
+pub fn revoke_vesting(ctx: Context<RevokeVesting>) -> Result<()> {
+    let vesting = &ctx.accounts.vesting;
+    require!(vesting.revocable, VestingError::NotRevocable);
+    require!(!vesting.revoked, VestingError::AlreadyRevoked);
+
+    let vested = vesting.vested(Clock::get()?.unix_timestamp);
+    let unvested = vesting.total - vested;
+    if unvested > 0 {
+        pay_from_vault(
+            vesting,
+            &ctx.accounts.vault,
+            &ctx.accounts.grantor_tokens,
+            &ctx.accounts.token_program,
+            unvested,
+        )?;
+    }
+
+    let vesting = &mut ctx.accounts.vesting;
+    vesting.total = vested;
+    vesting.revoked = true;
+
+    emit!(VestingRevoked {
+        vesting: vesting.key(),
+        grantor: vesting.grantor,
+        unvested,
+        vested,
+    });
+
+    msg!("Vesting revoked: {} returned, {} kept by the beneficiary", unvested, vested);
+    Ok(())
+}
+
+/// Transfer `amount` out of a vesting's vault
+fn pay_from_vault<'info>(
+    vesting: &Account<'info, Vesting>,
+    vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[
+        VESTING_SEED,
+        vesting.mint.as_ref(),
+        vesting.beneficiary.as_ref(),
+        &[vesting.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: vault.to_account_info(),
+            to: to.to_account_info(),
+            authority: vesting.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreateVesting<'info> {
+    /// The vesting to create
+    #[account(
+        init,
+        payer = grantor,
+        space = 8 + Vesting::LEN,
+        seeds = [VESTING_SEED, mint.key().as_ref(), beneficiary.key().as_ref()],
+        bump
+    )]
+    pub vesting: Box<Account<'info, Vesting>>,
+
+    /// Token vault holding the allocation
+    #[account(
+        init,
+        payer = grantor,
+        seeds = [VESTING_VAULT_SEED, vesting.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vesting,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Any wallet can be a beneficiary
+    pub beneficiary: UncheckedAccount<'info>,
+
+    /// Grantor's token account funding the vesting
+    #[account(
+        mut,
+        constraint = grantor_tokens.mint == mint.key() @ VestingError::MintMismatch,
+    )]
+    pub grantor_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub grantor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(
+        mut,
+        seeds = [VESTING_SEED, vesting.mint.as_ref(), beneficiary.key().as_ref()],
+        bump = vesting.bump,
+        has_one = beneficiary @ VestingError::Unauthorized,
+        has_one = vault @ VestingError::MintMismatch,
+    )]
+    pub vesting: Box<Account<'info, Vesting>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Beneficiary's token account (destination)
+    #[account(
+        mut,
+        constraint = beneficiary_tokens.mint == vesting.mint @ VestingError::MintMismatch,
+    )]
+    pub beneficiary_tokens: Box<Account<'info, TokenAccount>>,
+
+    pub beneficiary: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeVesting<'info> {
+    #[account(
+        mut,
+        seeds = [VESTING_SEED, vesting.mint.as_ref(), vesting.beneficiary.as_ref()],
+        bump = vesting.bump,
+        has_one = grantor @ VestingError::Unauthorized,
+        has_one = vault @ VestingError::MintMismatch,
+    )]
+    pub vesting: Box<Account<'info, Vesting>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Grantor's token account (destination)
+    #[account(
+        mut,
+        constraint = grantor_tokens.mint == vesting.mint @ VestingError::MintMismatch,
+    )]
+    pub grantor_tokens: Box<Account<'info, TokenAccount>>,
+
+    pub grantor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,59 @@
+//! DAC Vesting Program
+//!
+//! Locks team and partner allocations of DAC, or any SPL token, in
+//! per-beneficiary vaults that release them on a schedule. Every vesting
+//! and claim is on-chain, so allocations can be checked against what was
+//! announced.
+//!
+//! Key features:
+//! - Linear schedules with an optional cliff; a cliff at the end is a pure cliff
+//! - One vesting PDA and vault per mint and beneficiary
+//! - Claims of everything vested so far by the beneficiary
+//! - Revocation of the unvested remainder by the grantor, when the vesting allows it
+//! - Events for creation, claims and revocation
+
+// Anchor 0.31's `#[program]` expansion still calls the deprecated `AccountInfo::realloc`
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("8kXLqHU6kDws2d4e2fnKWmkvfHUNvk1CoEaYmnGVMVxi");
+
+/// Seeds for a vesting (followed by the mint and the beneficiary)
+pub const VESTING_SEED: &[u8] = b"vesting";
+/// Seeds for a vesting's token vault (followed by the vesting)
+pub const VESTING_VAULT_SEED: &[u8] = b"vesting_vault";
+
+#[program]
+pub mod dac_vesting {
+    use super::*;
+
+    /// Lock `amount` tokens for a beneficiary on a schedule
+    pub fn create_vesting(
+        ctx: Context<CreateVesting>,
+        amount: u64,
+        start: i64,
+        cliff: i64,
+        end: i64,
+        revocable: bool,
+    ) -> Result<()> {
+        instructions::create_vesting(ctx, amount, start, cliff, end, revocable)
+    }
+
+    /// Release the vested tokens to the beneficiary
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        instructions::claim_vested(ctx)
+    }
+
+    /// Return the unvested tokens to the grantor (grantor only)
+    pub fn revoke_vesting(ctx: Context<RevokeVesting>) -> Result<()> {
+        instructions::revoke_vesting(ctx)
+    }
+}
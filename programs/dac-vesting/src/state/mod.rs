@@ -0,0 +1,3 @@
+mod vesting;
+
+pub use vesting::*;
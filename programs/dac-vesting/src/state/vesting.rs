@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+/// A beneficiary's allocation of one mint, released on a schedule
+///
+/// Nothing vests before `cliff`; from then on the vested amount is the
+/// share of `start..end` elapsed, so the cliff releases the share accrued
+/// since `start` at once. `cliff == start` is a plain linear schedule and
+/// `cliff == end` a pure cliff.
+#[account]
+pub struct Vesting {
+    /// Wallet that funded the vesting and may revoke it
+    pub grantor: Pubkey,
+    /// Wallet the tokens vest to
+    pub beneficiary: Pubkey,
+    /// The vested mint
+    pub mint: Pubkey,
+    /// Token vault holding the unclaimed tokens
+    pub vault: Pubkey,
+    /// Tokens allocated (cut to the vested amount on revocation)
+    pub total: u64,
+    /// Tokens claimed so far
+    pub claimed: u64,
+    /// Time vesting starts accruing
+    pub start: i64,
+    /// Time before which nothing can be claimed
+    pub cliff: i64,
+    /// Time the allocation is fully vested
+    pub end: i64,
+    /// Whether the grantor can revoke the unvested tokens
+    pub revocable: bool,
+    /// Whether the vesting was revoked
+    pub revoked: bool,
+    /// Bump for the vesting PDA
+    pub bump: u8,
+}
+
+impl Vesting {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 1; // 171 bytes
+
+    pub fn is_valid_schedule(start: i64, cliff: i64, end: i64) -> bool {
+        start < end && start <= cliff && cliff <= end
+    }
+
+    /// Tokens vested at `now` (all of `total` once revoked)
+    pub fn vested(&self, now: i64) -> u64 {
+        if self.revoked || now >= self.end {
+            self.total
+        } else if now < self.cliff {
+            0
+        } else {
+            (self.total as u128 * (now - self.start) as u128 / (self.end - self.start) as u128) as u64
+        }
+    }
+
+    /// Tokens vested at `now` and not yet claimed
+    pub fn claimable(&self, now: i64) -> u64 {
+        self.vested(now).saturating_sub(self.claimed)
+    }
+}
@@ -0,0 +1,197 @@
+//! Instructions of Metaplex Bubblegum, built by hand
+//!
+//! The Bubblegum crate pins an older Solana, so the three instructions the
+//! compressed receipts invoke are encoded here from its IDL: the Anchor
+//! discriminator followed by the Borsh arguments, with accounts in the order
+//! of Bubblegum's `CreateTree`, `MintV1` and `Burn` contexts. The leaf
+//! hashes are computed the way Bubblegum does, so a burn can be checked
+//! against metadata the caller claims.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::keccak;
+
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+pub const ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+pub const NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum TokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub token_program_version: TokenProgramVersion,
+    pub creators: Vec<Creator>,
+}
+
+impl MetadataArgs {
+    /// The leaf's data hash: the metadata's hash, then the seller fee
+    pub fn data_hash(&self) -> [u8; 32] {
+        let mut data = Vec::new();
+        self.serialize(&mut data).expect("metadata serializes");
+        let metadata_hash = keccak::hashv(&[&data]);
+        keccak::hashv(&[&metadata_hash.to_bytes(), &self.seller_fee_basis_points.to_le_bytes()]).to_bytes()
+    }
+
+    /// The leaf's creator hash over every creator's address, verification and share
+    pub fn creator_hash(&self) -> [u8; 32] {
+        let creators: Vec<Vec<u8>> = self
+            .creators
+            .iter()
+            .map(|creator| [creator.address.as_ref(), &[creator.verified as u8], &[creator.share]].concat())
+            .collect();
+        let slices: Vec<&[u8]> = creators.iter().map(Vec::as_slice).collect();
+        keccak::hashv(&slices).to_bytes()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CreateTreeArgs {
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub public: Option<bool>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BurnArgs {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+}
+
+/// Bubblegum's config of `merkle_tree`, its authority in the compression program
+pub fn tree_config(merkle_tree: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[merkle_tree.as_ref()], &BUBBLEGUM_PROGRAM_ID).0
+}
+
+/// Id of the asset minted at `nonce` in `merkle_tree`
+pub fn asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    let nonce = nonce.to_le_bytes();
+    Pubkey::find_program_address(&[b"asset", merkle_tree.as_ref(), &nonce], &BUBBLEGUM_PROGRAM_ID).0
+}
+
+/// `create_tree`, initializing the pre-allocated `merkle_tree` for `tree_creator`
+pub fn create_tree(
+    merkle_tree: &Pubkey,
+    payer: &Pubkey,
+    tree_creator: &Pubkey,
+    args: CreateTreeArgs,
+) -> Instruction {
+    Instruction {
+        program_id: BUBBLEGUM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tree_config(merkle_tree), false),
+            AccountMeta::new(*merkle_tree, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*tree_creator, true),
+            AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ACCOUNT_COMPRESSION_PROGRAM_ID, false),
+            AccountMeta::new_readonly(System::id(), false),
+        ],
+        data: data("create_tree", &args),
+    }
+}
+
+/// `mint_v1` of a leaf owned and delegated to `leaf_owner`, signed by the tree delegate
+pub fn mint_v1(
+    merkle_tree: &Pubkey,
+    leaf_owner: &Pubkey,
+    payer: &Pubkey,
+    tree_delegate: &Pubkey,
+    metadata: &MetadataArgs,
+) -> Instruction {
+    Instruction {
+        program_id: BUBBLEGUM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(tree_config(merkle_tree), false),
+            AccountMeta::new_readonly(*leaf_owner, false),
+            AccountMeta::new_readonly(*leaf_owner, false),
+            AccountMeta::new(*merkle_tree, false),
+            AccountMeta::new_readonly(*payer, true),
+            AccountMeta::new_readonly(*tree_delegate, true),
+            AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ACCOUNT_COMPRESSION_PROGRAM_ID, false),
+            AccountMeta::new_readonly(System::id(), false),
+        ],
+        data: data("mint_v1", metadata),
+    }
+}
+
+/// `burn` of a leaf, signed by its owner, with the proof nodes appended
+pub fn burn(
+    merkle_tree: &Pubkey,
+    leaf_owner: &Pubkey,
+    leaf_delegate: &Pubkey,
+    proof: &[Pubkey],
+    args: BurnArgs,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(tree_config(merkle_tree), false),
+        AccountMeta::new_readonly(*leaf_owner, true),
+        AccountMeta::new_readonly(*leaf_delegate, false),
+        AccountMeta::new(*merkle_tree, false),
+        AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+        AccountMeta::new_readonly(ACCOUNT_COMPRESSION_PROGRAM_ID, false),
+        AccountMeta::new_readonly(System::id(), false),
+    ];
+    accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(*node, false)));
+    Instruction { program_id: BUBBLEGUM_PROGRAM_ID, accounts, data: data("burn", &args) }
+}
+
+/// Anchor instruction data: the method's discriminator, then its arguments
+pub(crate) fn data<T: AnchorSerialize>(method: &str, args: &T) -> Vec<u8> {
+    let mut data = hash(format!("global:{}", method).as_bytes()).to_bytes()[..8].to_vec();
+    args.serialize(&mut data).expect("instruction args serialize");
+    data
+}
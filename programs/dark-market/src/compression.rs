@@ -0,0 +1,82 @@
+//! Instructions of SPL account compression, built by hand
+//!
+//! Compressed positions keep their leaves in a concurrent Merkle tree the
+//! market's position tree PDA owns directly, without Bubblegum. Like
+//! [`crate::bubblegum`], the three instructions invoked are encoded from the
+//! program's IDL; its `Initialize` and `Modify` contexts both take the tree,
+//! its authority and the noop program, with any proof nodes appended.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+use crate::bubblegum::{data, ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID};
+
+/// Leaf of an empty slot, which a closed position is replaced with
+pub const EMPTY_LEAF: [u8; 32] = [0; 32];
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct InitEmptyMerkleTreeArgs {
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ReplaceLeafArgs {
+    pub root: [u8; 32],
+    pub previous_leaf: [u8; 32],
+    pub new_leaf: [u8; 32],
+    pub index: u32,
+}
+
+/// Bytes of a concurrent Merkle tree account: header, change log buffer, rightmost path and canopy
+pub fn tree_account_len(max_depth: u32, max_buffer_size: u32, canopy_depth: u32) -> usize {
+    let (depth, buffer) = (max_depth as usize, max_buffer_size as usize);
+    let header = 2 + 54;
+    let change_log = 32 + 32 * depth + 4 + 4;
+    let rightmost_path = 32 * depth + 32 + 4 + 4;
+    let canopy = ((1usize << (canopy_depth + 1)) - 2) * 32;
+    header + 8 + 8 + 8 + buffer * change_log + rightmost_path + canopy
+}
+
+/// `init_empty_merkle_tree` of the pre-allocated `merkle_tree`, owned from then on by `authority`
+pub fn init_empty_merkle_tree(
+    merkle_tree: &Pubkey,
+    authority: &Pubkey,
+    args: InitEmptyMerkleTreeArgs,
+) -> Instruction {
+    Instruction {
+        program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        accounts: modify_accounts(merkle_tree, authority),
+        data: data("init_empty_merkle_tree", &args),
+    }
+}
+
+/// `append` of `leaf` at the tree's next index, signed by its authority
+pub fn append(merkle_tree: &Pubkey, authority: &Pubkey, leaf: [u8; 32]) -> Instruction {
+    Instruction {
+        program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        accounts: modify_accounts(merkle_tree, authority),
+        data: data("append", &leaf),
+    }
+}
+
+/// `replace_leaf`, signed by the tree's authority, with the proof nodes appended
+pub fn replace_leaf(
+    merkle_tree: &Pubkey,
+    authority: &Pubkey,
+    proof: &[Pubkey],
+    args: ReplaceLeafArgs,
+) -> Instruction {
+    let mut accounts = modify_accounts(merkle_tree, authority);
+    accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(*node, false)));
+    Instruction { program_id: ACCOUNT_COMPRESSION_PROGRAM_ID, accounts, data: data("replace_leaf", &args) }
+}
+
+/// The tree, its signing authority and the noop program the change logs go through
+fn modify_accounts(merkle_tree: &Pubkey, authority: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*merkle_tree, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+    ]
+}
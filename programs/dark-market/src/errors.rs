@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum MarketError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Mint mismatch")]
+    MintMismatch,
+    #[msg("Cannot process zero amount")]
+    ZeroAmount,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Arithmetic underflow")]
+    Underflow,
+    #[msg("Question is too long")]
+    QuestionTooLong,
+    #[msg("End time must be in the future")]
+    InvalidEndTime,
+    #[msg("Market is not open")]
+    MarketNotOpen,
+    #[msg("Market is not resolved")]
+    MarketNotResolved,
+    #[msg("Market has not reached its end time")]
+    MarketNotEnded,
+    #[msg("Market trading has closed")]
+    MarketClosed,
+    #[msg("Invalid number of parlay legs")]
+    InvalidLegCount,
+    #[msg("Parlay legs must reference distinct markets")]
+    DuplicateLeg,
+    #[msg("Parlay legs must back YES or NO")]
+    InvalidLegOutcome,
+    #[msg("Leg market accounts do not match the parlay legs")]
+    LegMarketMismatch,
+    #[msg("Parlay payout must exceed the stake")]
+    InvalidPayout,
+    #[msg("Parlay is not open")]
+    ParlayNotOpen,
+    #[msg("Parlay is not backed")]
+    ParlayNotBacked,
+    #[msg("Receipts must wrap YES or NO tokens")]
+    InvalidReceiptOutcome,
+    #[msg("Metadata URI is too long")]
+    UriTooLong,
+    #[msg("Signer does not hold the receipt NFT")]
+    NotReceiptHolder,
+    #[msg("Fee exceeds the maximum")]
+    FeeTooHigh,
+    #[msg("Limit price must be between 0 and 1")]
+    InvalidLimitPrice,
+    #[msg("Order asset does not match its side and outcome")]
+    InvalidOrderOutcome,
+    #[msg("Pool price has not reached the limit")]
+    LimitNotReached,
+    #[msg("TWAP window must be positive and for YES or NO")]
+    InvalidTwapWindow,
+    #[msg("Not enough observation history for the TWAP window")]
+    InsufficientObservations,
+    #[msg("Trading is halted by the circuit breaker")]
+    TradingHalted,
+    #[msg("Circuit breaker threshold must be at most 100% with non-zero window and cool-off")]
+    InvalidBreakerParams,
+    #[msg("Market is not resolved optimistically")]
+    NotOptimisticMarket,
+    #[msg("Resolution bond is below the minimum")]
+    BondTooLow,
+    #[msg("Challenge period has ended")]
+    ChallengePeriodOver,
+    #[msg("Challenge period has not ended")]
+    ChallengePeriodActive,
+    #[msg("Proposal is not in the required status")]
+    InvalidResolutionStatus,
+    #[msg("Council voting has ended")]
+    VotingClosed,
+    #[msg("Council voting has not ended")]
+    VotingActive,
+    #[msg("Stake was added after the dispute was raised")]
+    StakedAfterDispute,
+    #[msg("Council member has unsettled votes")]
+    VotesPending,
+    #[msg("Insufficient stake")]
+    InsufficientStake,
+    #[msg("Series interval must be positive")]
+    InvalidSeriesInterval,
+    #[msg("Market does not belong to this series")]
+    SeriesMismatch,
+    #[msg("Margin account would be under-collateralized")]
+    MarginUnhealthy,
+    #[msg("Margin account holds positions in too many markets")]
+    TooManyMarginPositions,
+    #[msg("Margin account has too many market links")]
+    TooManyMarginLinks,
+    #[msg("Margin positions must be YES or NO tokens")]
+    InvalidMarginOutcome,
+    #[msg("Margin position is smaller than the amount")]
+    InsufficientPosition,
+    #[msg("Market link must join two distinct markets")]
+    InvalidMarketLink,
+    #[msg("Market link is already attached")]
+    LinkAlreadyAttached,
+    #[msg("Market link is not attached")]
+    LinkNotAttached,
+    #[msg("Margin account is not under-collateralized")]
+    MarginHealthy,
+    #[msg("Liquidation would not repay any debt")]
+    NothingToLiquidate,
+    #[msg("Not enough margin collateral")]
+    InsufficientCollateral,
+    #[msg("Leverage must be between 1x and the market's maximum")]
+    InvalidLeverage,
+    #[msg("Leverage must be 0 or between 1x and 10x, with a capped funding rate and non-negative window")]
+    InvalidLeverageParams,
+    #[msg("Market is too close to resolution for leverage")]
+    DeleverWindow,
+    #[msg("Forced deleveraging has not started")]
+    DeleverNotStarted,
+    #[msg("Portfolio tracks too many markets")]
+    PortfolioFull,
+    #[msg("Pool of a portfolio position was not passed")]
+    PortfolioPoolMissing,
+    #[msg("Fee tiers must ascend with discounts of at most 100%")]
+    InvalidFeeTiers,
+    #[msg("Stake account is not a stake of the trader in the staking program")]
+    InvalidStakeAccount,
+    #[msg("Invalid maker rebate parameters")]
+    InvalidRebateParams,
+    #[msg("Rebate vault does not belong to the rebate pool")]
+    InvalidRebateVault,
+    #[msg("Claim window must be zero or at least the minimum")]
+    InvalidClaimWindow,
+    #[msg("The market's claim window has not expired")]
+    ClaimWindowOpen,
+    #[msg("Sweep destination does not match the market's settings")]
+    InvalidSweepDestination,
+    #[msg("Launch curve needs a start price below one DAC and a positive slope")]
+    InvalidLaunchCurve,
+    #[msg("Launch seed does not cover the curve's discount")]
+    LaunchSeedTooLow,
+    #[msg("The launch has ended")]
+    LaunchEnded,
+    #[msg("The market is still in its launch")]
+    LaunchActive,
+    #[msg("The market's pool is already open")]
+    PoolAlreadyOpen,
+    #[msg("Trade price is worse than the caller's limit")]
+    SlippageExceeded,
+    #[msg("Trade submitted past its expiry slot")]
+    TradeExpired,
+    #[msg("Resolver name too long")]
+    ResolverNameTooLong,
+    #[msg("Resolver adapter is not approved")]
+    ResolverNotApproved,
+    #[msg("Resolver adapter returned an invalid response")]
+    InvalidResolverResponse,
+    #[msg("Resolver adapter has no outcome yet")]
+    ResolutionPending,
+    #[msg("Invalid dynamic fee parameters")]
+    InvalidDynamicFee,
+    #[msg("Bet batch is not in the window for this step")]
+    BetWindowClosed,
+    #[msg("Bet batch reveal window has not closed")]
+    BetWindowOpen,
+    #[msg("Bet does not match its commitment")]
+    InvalidBetReveal,
+    #[msg("Bet batch already settled")]
+    BetBatchSettled,
+    #[msg("Bet batch not settled yet")]
+    BetBatchNotSettled,
+    #[msg("Invalid verifying key")]
+    InvalidVerifyingKey,
+    #[msg("Invalid zero-knowledge proof")]
+    InvalidProof,
+    #[msg("Dark fill exceeds the matched orders' budgets")]
+    InvalidDarkFill,
+    #[msg("Invalid auction parameters")]
+    InvalidAuctionParams,
+    #[msg("Auction is not in the window for this step")]
+    AuctionWindowClosed,
+    #[msg("Auction reveal window has not closed")]
+    AuctionWindowOpen,
+    #[msg("Bid does not match its commitment")]
+    InvalidBidReveal,
+    #[msg("Auction already settled")]
+    AuctionSettled,
+    #[msg("Stealth payment hint too long")]
+    StealthHintTooLong,
+    #[msg("Invalid decryption committee")]
+    InvalidCommittee,
+    #[msg("Position already decrypted")]
+    AlreadyDecrypted,
+    #[msg("Confidential positions are still being decrypted")]
+    DecryptionPending,
+    #[msg("Relayer fee above the maximum")]
+    InvalidRelayerFee,
+    #[msg("Relayer is not approved")]
+    RelayerNotApproved,
+    #[msg("Invalid relayed batch")]
+    InvalidRelayBatch,
+    #[msg("Relayed order is not signed by its owner or was already executed")]
+    InvalidOrderSignature,
+    #[msg("Insufficient relay account balance")]
+    InsufficientRelayBalance,
+    #[msg("Ciphertext is not a valid Ristretto point pair")]
+    InvalidCiphertext,
+    #[msg("Volume already reported")]
+    VolumeAlreadyReported,
+    #[msg("Audit memo is missing, too long, or given without a viewing key")]
+    InvalidAuditMemo,
+    #[msg("Bet limits must be positive with min at most max")]
+    InvalidBetLimits,
+    #[msg("Reveal delay must be positive and at most 7 days")]
+    InvalidRevealDelay,
+    #[msg("Sealed outcome is empty, too long, or does not match its commitment")]
+    InvalidSealedOutcome,
+    #[msg("Sealed outcome cannot be revealed yet")]
+    RevealTooEarly,
+    #[msg("Market outcome is not sealed")]
+    MarketNotSealed,
+    #[msg("Creation bond is below the minimum")]
+    CreationBondTooLow,
+    #[msg("Bet must be bundled with a DAC wrap")]
+    BetNotBundled,
+    #[msg("Withdrawal exceeds the LP shares deposited in the gauge")]
+    InsufficientGaugeStake,
+    #[msg("Fee share exceeds 100%")]
+    InvalidFeeShare,
+    #[msg("Fee vault is not the configured one")]
+    InvalidFeeVault,
+    #[msg("Lookup table or its markets do not match the registry")]
+    InvalidLookupTable,
+    #[msg("Lookup table has no room for these addresses")]
+    LookupTableFull,
+    #[msg("Latest lookup table still has room")]
+    LookupTableNotFull,
+    #[msg("Receipt tree is too shallow or does not match the market")]
+    InvalidReceiptTree,
+    #[msg("Market requires an attestation")]
+    AttestationRequired,
+    #[msg("Attestation is not a live one of the market's schema and credential for the user")]
+    InvalidAttestation,
+    #[msg("Position tree depth, buffer, canopy or size is invalid, or it does not match the market")]
+    InvalidPositionTree,
+    #[msg("Proof does not have one node per tree level below the canopy")]
+    InvalidProofLength,
+    #[msg("Settlement batch is empty, too large or does not match its accounts")]
+    InvalidSettleBatch,
+    #[msg("Market resolves privately: seal and reveal its outcome instead")]
+    ResolvesPrivately,
+    #[msg("Sealed outcome can still be revealed")]
+    RevealDeadlineNotPassed,
+    #[msg("Staking program is not a deployed program")]
+    InvalidStakingProgram,
+}
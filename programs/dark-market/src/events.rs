@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Outcome;
+
+/// Maximum size of an encrypted stealth payment hint in bytes
+pub const MAX_STEALTH_HINT_LEN: usize = 128;
+
+/// Winnings paid to a one-time stealth account
+///
+/// Recipients scan these events: the view tag is a one-byte filter on the
+/// shared secret of the ephemeral key and their viewing key, and only a
+/// matching event needs its hint decrypted. The hint, encrypted to the
+/// recipient, carries what they need to derive the payout account's key.
+#[event]
+pub struct StealthPayment {
+    pub market: Pubkey,
+    /// The fresh token account holding the payout
+    pub payout_account: Pubkey,
+    /// Sender's one-time public key
+    pub ephemeral_pubkey: [u8; 32],
+    pub view_tag: u8,
+    pub encrypted_hint: Vec<u8>,
+    pub amount: u64,
+}
+
+/// Maximum size of an encrypted audit memo in bytes
+pub const MAX_AUDIT_MEMO_LEN: usize = 256;
+
+/// Kind of private action an audit memo describes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PrivateAction {
+    ConfidentialPosition,
+    DarkOrder,
+    BetCommitment,
+}
+
+/// Details of a private action, encrypted to the owner's auditor
+///
+/// Emitted only for users with a registered viewing key. The memo is opaque
+/// on-chain; the auditor decrypts it with the secret of the viewing key's
+/// encryption key to rebuild the user's history.
+#[event]
+pub struct AuditMemo {
+    pub owner: Pubkey,
+    pub auditor: Pubkey,
+    pub action: PrivateAction,
+    /// The position, order or commitment created
+    pub subject: Pubkey,
+    pub memo: Vec<u8>,
+}
+
+/// A pool trade
+#[event]
+pub struct Trade {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome: Outcome,
+    /// Whether the user bought the outcome (else sold it)
+    pub is_buy: bool,
+    /// DAC paid when buying, outcome tokens sold when selling
+    pub amount_in: u64,
+    /// Outcome tokens bought when buying, DAC received when selling
+    pub amount_out: u64,
+    /// Swap fee charged, in DAC
+    pub fee: u64,
+}
+
+/// A pool's reserves after a trade or a liquidity change
+#[event]
+pub struct PoolUpdated {
+    pub market: Pubkey,
+    pub yes_reserve: u64,
+    pub no_reserve: u64,
+    /// Spot price of YES scaled by `PRICE_SCALE`
+    pub yes_price: u64,
+}
+
+/// A market's outcome became final
+#[event]
+pub struct MarketResolved {
+    pub market: Pubkey,
+    pub outcome: Outcome,
+    pub resolved_at: i64,
+}
+
+/// Outcome tokens of a resolved market burned for DAC
+#[event]
+pub struct Redeemed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub yes_burned: u64,
+    pub no_burned: u64,
+    pub payout: u64,
+}
+
+/// A compressed position receipt was minted
+///
+/// Lets holders and indexers find the leaf to prove at unwrap without
+/// replaying the tree's noop logs.
+#[event]
+pub struct CompressedReceiptMinted {
+    pub market: Pubkey,
+    pub merkle_tree: Pubkey,
+    /// Bubblegum asset id of the receipt
+    pub asset_id: Pubkey,
+    /// The leaf's nonce, equal to its index
+    pub nonce: u64,
+    pub owner: Pubkey,
+    pub outcome: Outcome,
+    pub amount: u64,
+}
+
+/// A compressed position's leaf was appended or replaced
+///
+/// Indexers replay these to know every leaf and build the proofs callers
+/// supply; a closed or redeemed position reports an amount of zero.
+#[event]
+pub struct CompressedPositionChanged {
+    pub market: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub index: u32,
+    pub owner: Pubkey,
+    pub outcome: Outcome,
+    pub amount: u64,
+    /// The new leaf
+    pub leaf: [u8; 32],
+}
+
+/// A keeper redeemed a batch of compressed positions for their owners
+#[event]
+pub struct BatchSettled {
+    pub market: Pubkey,
+    pub positions: u32,
+    pub yes_burned: u64,
+    pub no_burned: u64,
+    pub payout: u64,
+}
@@ -0,0 +1,638 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::events::MarketResolved;
+use crate::state::*;
+use crate::{
+    COUNCIL_MEMBER_SEED, COUNCIL_SEED, COUNCIL_VAULT_SEED, COUNCIL_VOTE_SEED, MARKET_CONFIG_SEED,
+    MARKET_SEED, RESOLUTION_SEED, RESOLUTION_VAULT_SEED,
+};
+
+pub fn initialize_council(ctx: Context<InitializeCouncil>) -> Result<()> {
+    let council = &mut ctx.accounts.council;
+    council.vault = ctx.accounts.vault.key();
+    council.total_stake = 0;
+    council.slashed = 0;
+    council.bump = ctx.bumps.council;
+    council.vault_bump = ctx.bumps.vault;
+
+    msg!("Arbitration council initialized");
+    Ok(())
+}
+
+pub fn join_council(ctx: Context<JoinCouncil>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.member_dac.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.member.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let member = &mut ctx.accounts.member_account;
+    member.member = ctx.accounts.member.key();
+    member.stake = member.stake.checked_add(amount).ok_or(MarketError::Overflow)?;
+    member.staked_at = Clock::get()?.unix_timestamp;
+    member.bump = ctx.bumps.member_account;
+
+    let council = &mut ctx.accounts.council;
+    council.total_stake = council.total_stake.checked_add(amount)
+        .ok_or(MarketError::Overflow)?;
+
+    msg!("Council member {} staked {} (total {})", member.member, amount, member.stake);
+    Ok(())
+}
+
+pub fn leave_council(ctx: Context<LeaveCouncil>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+    let member = &mut ctx.accounts.member_account;
+    require!(member.pending_votes == 0, MarketError::VotesPending);
+    member.stake = member.stake.checked_sub(amount).ok_or(MarketError::InsufficientStake)?;
+
+    let council = &mut ctx.accounts.council;
+    council.total_stake = council.total_stake.checked_sub(amount)
+        .ok_or(MarketError::Underflow)?;
+
+    pay_from_council_vault(council, &ctx.accounts.vault, &ctx.accounts.member_dac, &ctx.accounts.token_program, amount)?;
+
+    msg!("Council member {} unstaked {}", member.member, amount);
+    Ok(())
+}
+
+pub fn propose_resolution(ctx: Context<ProposeResolution>, outcome: Outcome, bond: u64) -> Result<()> {
+    require!(bond >= MIN_RESOLUTION_BOND, MarketError::BondTooLow);
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= ctx.accounts.market.load()?.end_time, MarketError::MarketNotEnded);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.proposer_dac.to_account_info(),
+            to: ctx.accounts.bond_vault.to_account_info(),
+            authority: ctx.accounts.proposer.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, bond)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.market = ctx.accounts.market.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.challenger = Pubkey::default();
+    proposal.outcome = outcome;
+    proposal.bond = bond;
+    proposal.proposed_at = now;
+    proposal.disputed_at = 0;
+    proposal.council_stake = 0;
+    proposal.votes = [0; 3];
+    proposal.status = ResolutionStatus::Proposed;
+    proposal.council_decided = false;
+    proposal.final_outcome = None;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.vault_bump = ctx.bumps.bond_vault;
+
+    msg!("Market {} resolution proposed: {:?} with bond {}", ctx.accounts.market.load()?.id, outcome, bond);
+    Ok(())
+}
+
+pub fn dispute_resolution(ctx: Context<DisputeResolution>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    require!(now < proposal.challenge_ends(), MarketError::ChallengePeriodOver);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.challenger_dac.to_account_info(),
+            to: ctx.accounts.bond_vault.to_account_info(),
+            authority: ctx.accounts.challenger.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, proposal.bond)?;
+
+    proposal.challenger = ctx.accounts.challenger.key();
+    proposal.disputed_at = now;
+    proposal.council_stake = ctx.accounts.council.total_stake;
+    proposal.status = ResolutionStatus::Disputed;
+
+    msg!("Resolution disputed, council voting until {}", proposal.voting_ends());
+    Ok(())
+}
+
+pub fn cast_vote(ctx: Context<CastVote>, outcome: Outcome) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        Clock::get()?.unix_timestamp < proposal.voting_ends(),
+        MarketError::VotingClosed
+    );
+    let member = &mut ctx.accounts.member_account;
+    require!(member.stake > 0, MarketError::InsufficientStake);
+    require!(member.staked_at < proposal.disputed_at, MarketError::StakedAfterDispute);
+
+    proposal.add_votes(outcome, member.stake).ok_or(MarketError::Overflow)?;
+    member.pending_votes = member.pending_votes.checked_add(1)
+        .ok_or(MarketError::Overflow)?;
+
+    let vote = &mut ctx.accounts.vote;
+    vote.proposal = proposal.key();
+    vote.member = member.member;
+    vote.outcome = outcome;
+    vote.weight = member.stake;
+    vote.bump = ctx.bumps.vote;
+
+    msg!("Council vote: {:?} with weight {}", outcome, vote.weight);
+    Ok(())
+}
+
+pub fn finalize_resolution(ctx: Context<FinalizeResolution>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+
+    // Without a council decision the proposal stands
+    let (final_outcome, winner) = match proposal.status {
+        ResolutionStatus::Proposed => {
+            require!(now >= proposal.challenge_ends(), MarketError::ChallengePeriodActive);
+            (proposal.outcome, proposal.proposer)
+        }
+        ResolutionStatus::Disputed => {
+            require!(now >= proposal.voting_ends(), MarketError::VotingActive);
+            let decision = proposal.council_decision();
+            proposal.council_decided = decision.is_some();
+            let final_outcome = decision.unwrap_or(proposal.outcome);
+            let winner = if final_outcome == proposal.outcome {
+                proposal.proposer
+            } else {
+                proposal.challenger
+            };
+            (final_outcome, winner)
+        }
+        ResolutionStatus::Finalized => return err!(MarketError::InvalidResolutionStatus),
+    };
+    require!(ctx.accounts.winner_dac.owner == winner, MarketError::Unauthorized);
+
+    proposal.status = ResolutionStatus::Finalized;
+    proposal.final_outcome = Some(final_outcome);
+
+    let market_key = proposal.market;
+    let seeds = &[RESOLUTION_SEED, market_key.as_ref(), &[proposal.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.bond_vault.to_account_info(),
+            to: ctx.accounts.winner_dac.to_account_info(),
+            authority: proposal.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, ctx.accounts.bond_vault.amount)?;
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.bond_vault.to_account_info(),
+            destination: ctx.accounts.proposer.to_account_info(),
+            authority: proposal.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::close_account(close_ctx)?;
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.resolve(final_outcome, now);
+
+    emit!(MarketResolved {
+        market: ctx.accounts.market.key(),
+        outcome: final_outcome,
+        resolved_at: now,
+    });
+
+    msg!("Market {} resolved: {:?}, bonds to {}", market.id, final_outcome, winner);
+    Ok(())
+}
+
+pub fn settle_vote(ctx: Context<SettleVote>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let vote = &ctx.accounts.vote;
+    let member = &mut ctx.accounts.member_account;
+    member.pending_votes = member.pending_votes.checked_sub(1)
+        .ok_or(MarketError::Underflow)?;
+
+    if proposal.council_decided && proposal.final_outcome != Some(vote.outcome) {
+        let slashed = slash_amount(member.stake);
+        member.stake -= slashed;
+        let council = &mut ctx.accounts.council;
+        council.total_stake = council.total_stake.checked_sub(slashed)
+            .ok_or(MarketError::Underflow)?;
+        council.slashed = council.slashed.checked_add(slashed)
+            .ok_or(MarketError::Overflow)?;
+        msg!("Council member {} slashed {}", member.member, slashed);
+    }
+
+    msg!("Council vote settled");
+    Ok(())
+}
+
+pub fn sweep_slashed(ctx: Context<SweepSlashed>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+    let council = &mut ctx.accounts.council;
+    council.slashed = council.slashed.checked_sub(amount)
+        .ok_or(MarketError::InsufficientStake)?;
+
+    pay_from_council_vault(council, &ctx.accounts.vault, &ctx.accounts.destination, &ctx.accounts.token_program, amount)?;
+
+    msg!("Swept {} slashed DAC", amount);
+    Ok(())
+}
+
+fn pay_from_council_vault<'info>(
+    council: &Account<'info, Council>,
+    vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[COUNCIL_SEED, &[council.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: vault.to_account_info(),
+            to: to.to_account_info(),
+            authority: council.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeCouncil<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The council account to initialize
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Council::LEN,
+        seeds = [COUNCIL_SEED],
+        bump
+    )]
+    pub council: Account<'info, Council>,
+
+    /// DAC vault holding member stakes
+    #[account(
+        init,
+        payer = authority,
+        seeds = [COUNCIL_VAULT_SEED],
+        bump,
+        token::mint = dac_mint,
+        token::authority = council,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == config.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct JoinCouncil<'info> {
+    #[account(
+        mut,
+        seeds = [COUNCIL_SEED],
+        bump = council.bump,
+        has_one = vault @ MarketError::MintMismatch,
+    )]
+    pub council: Account<'info, Council>,
+
+    /// The council stake vault
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// The member's stake account (created on first join)
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = 8 + CouncilMember::LEN,
+        seeds = [COUNCIL_MEMBER_SEED, member.key().as_ref()],
+        bump
+    )]
+    pub member_account: Account<'info, CouncilMember>,
+
+    /// Member's DAC token account (source of the stake)
+    #[account(
+        mut,
+        constraint = member_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub member_dac: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveCouncil<'info> {
+    #[account(
+        mut,
+        seeds = [COUNCIL_SEED],
+        bump = council.bump,
+        has_one = vault @ MarketError::MintMismatch,
+    )]
+    pub council: Account<'info, Council>,
+
+    /// The council stake vault
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// The member's stake account
+    #[account(
+        mut,
+        seeds = [COUNCIL_MEMBER_SEED, member.key().as_ref()],
+        bump = member_account.bump,
+    )]
+    pub member_account: Account<'info, CouncilMember>,
+
+    /// Member's DAC token account (unstake destination)
+    #[account(
+        mut,
+        constraint = member_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub member_dac: Account<'info, TokenAccount>,
+
+    pub member: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeResolution<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The market to resolve; only markets resolved by the config PDA take proposals
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        constraint = market.load()?.resolver == config.key() @ MarketError::NotOptimisticMarket,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The proposal to create
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + ResolutionProposal::LEN,
+        seeds = [RESOLUTION_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// DAC vault holding both bonds
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [RESOLUTION_VAULT_SEED, proposal.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = proposal,
+    )]
+    pub bond_vault: Box<Account<'info, TokenAccount>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == config.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    /// Proposer's DAC token account (source of the bond)
+    #[account(
+        mut,
+        constraint = proposer_dac.mint == config.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub proposer_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeResolution<'info> {
+    #[account(
+        seeds = [COUNCIL_SEED],
+        bump = council.bump,
+    )]
+    pub council: Account<'info, Council>,
+
+    /// The proposal to dispute
+    #[account(
+        mut,
+        seeds = [RESOLUTION_SEED, proposal.market.as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.status == ResolutionStatus::Proposed @ MarketError::InvalidResolutionStatus,
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// The proposal's bond vault
+    #[account(
+        mut,
+        seeds = [RESOLUTION_VAULT_SEED, proposal.key().as_ref()],
+        bump = proposal.vault_bump,
+    )]
+    pub bond_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Challenger's DAC token account (source of the matching bond)
+    #[account(
+        mut,
+        constraint = challenger_dac.mint == bond_vault.mint @ MarketError::MintMismatch,
+    )]
+    pub challenger_dac: Box<Account<'info, TokenAccount>>,
+
+    pub challenger: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    /// The disputed proposal
+    #[account(
+        mut,
+        seeds = [RESOLUTION_SEED, proposal.market.as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.status == ResolutionStatus::Disputed @ MarketError::InvalidResolutionStatus,
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// The voting member's stake account
+    #[account(
+        mut,
+        seeds = [COUNCIL_MEMBER_SEED, member.key().as_ref()],
+        bump = member_account.bump,
+    )]
+    pub member_account: Account<'info, CouncilMember>,
+
+    /// The vote to record (one per member per proposal)
+    #[account(
+        init,
+        payer = member,
+        space = 8 + CouncilVote::LEN,
+        seeds = [COUNCIL_VOTE_SEED, proposal.key().as_ref(), member.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, CouncilVote>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeResolution<'info> {
+    /// The market to resolve
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The proposal to finalize
+    #[account(
+        mut,
+        seeds = [RESOLUTION_SEED, market.key().as_ref()],
+        bump = proposal.bump,
+        has_one = proposer @ MarketError::Unauthorized,
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// The proposal's bond vault
+    #[account(
+        mut,
+        seeds = [RESOLUTION_VAULT_SEED, proposal.key().as_ref()],
+        bump = proposal.vault_bump,
+    )]
+    pub bond_vault: Box<Account<'info, TokenAccount>>,
+
+    /// DAC token account of the winning side (checked in the handler)
+    #[account(
+        mut,
+        constraint = winner_dac.mint == bond_vault.mint @ MarketError::MintMismatch,
+    )]
+    pub winner_dac: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Proposer, receives the bond vault's rent
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SettleVote<'info> {
+    #[account(
+        mut,
+        seeds = [COUNCIL_SEED],
+        bump = council.bump,
+    )]
+    pub council: Account<'info, Council>,
+
+    /// The finalized proposal
+    #[account(
+        seeds = [RESOLUTION_SEED, proposal.market.as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.status == ResolutionStatus::Finalized @ MarketError::InvalidResolutionStatus,
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// The vote to settle
+    #[account(
+        mut,
+        close = member,
+        seeds = [COUNCIL_VOTE_SEED, proposal.key().as_ref(), member.key().as_ref()],
+        bump = vote.bump,
+    )]
+    pub vote: Account<'info, CouncilVote>,
+
+    /// The voter's stake account
+    #[account(
+        mut,
+        seeds = [COUNCIL_MEMBER_SEED, member.key().as_ref()],
+        bump = member_account.bump,
+    )]
+    pub member_account: Account<'info, CouncilMember>,
+
+    /// CHECK: The voter, receives the vote's rent; bound by the vote and member seeds
+    #[account(mut)]
+    pub member: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SweepSlashed<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [COUNCIL_SEED],
+        bump = council.bump,
+        has_one = vault @ MarketError::MintMismatch,
+    )]
+    pub council: Account<'info, Council>,
+
+    /// The council stake vault
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// DAC token account receiving the slashed stake
+    #[account(
+        mut,
+        constraint = destination.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
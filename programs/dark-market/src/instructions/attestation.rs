@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use dac_token::attestation::Attestation;
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::MARKET_SEED;
+
+/// Require participants to hold an attestation of `schema` issued under `credential` (creator only)
+///
+/// Splits, buys and limit orders then need the participant's live
+/// attestation; a default schema lifts the requirement.
+pub fn set_market_attestation(
+    ctx: Context<SetMarketAttestation>,
+    credential: Pubkey,
+    schema: Pubkey,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.set_attestation(AttestationRequirement { credential, schema });
+
+    if market.attestation().is_required() {
+        msg!("Market {} requires schema {} attested by credential {}", market.id, schema, credential);
+    } else {
+        msg!("Market {} no longer requires an attestation", market.id);
+    }
+    Ok(())
+}
+
+/// Fail if the market requires an attestation and `attestation` is not a live one issued to `user`
+pub(crate) fn check_attested(market: &Market, attestation: Option<&AccountInfo>, user: &Pubkey) -> Result<()> {
+    let requirement = market.attestation();
+    if !requirement.is_required() {
+        return Ok(());
+    }
+    let info = attestation.ok_or(MarketError::AttestationRequired)?;
+    let attestation = Attestation::load(info).ok_or(MarketError::InvalidAttestation)?;
+    require!(
+        requirement.accepts(&attestation, user, Clock::get()?.unix_timestamp),
+        MarketError::InvalidAttestation
+    );
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetMarketAttestation<'info> {
+    /// The market to gate
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = creator @ MarketError::Unauthorized,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
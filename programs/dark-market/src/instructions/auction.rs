@@ -0,0 +1,422 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{AUCTION_DAC_SEED, AUCTION_SEED, AUCTION_TOKENS_SEED, MARKET_SEED, SEALED_BID_SEED};
+
+pub fn create_auction(
+    ctx: Context<CreateAuction>,
+    id: u64,
+    outcome: Outcome,
+    amount: u64,
+    reserve_price: u64,
+    commit_duration: i64,
+    reveal_duration: i64,
+) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+    require!(
+        commit_duration > 0 && reveal_duration > 0,
+        MarketError::InvalidAuctionParams
+    );
+    require!(
+        ctx.accounts.market.load()?.outcome_mint(outcome) == Some(ctx.accounts.outcome_mint.key()),
+        MarketError::InvalidOrderOutcome
+    );
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.seller_tokens.to_account_info(),
+            to: ctx.accounts.token_vault.to_account_info(),
+            authority: ctx.accounts.seller.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let auction = &mut ctx.accounts.auction;
+    auction.seller = ctx.accounts.seller.key();
+    auction.id = id;
+    auction.market = ctx.accounts.market.key();
+    auction.outcome = outcome;
+    auction.amount = amount;
+    auction.reserve_price = reserve_price;
+    auction.token_vault = ctx.accounts.token_vault.key();
+    auction.dac_vault = ctx.accounts.dac_vault.key();
+    auction.commit_end = now.checked_add(commit_duration).ok_or(MarketError::Overflow)?;
+    auction.reveal_end = auction.commit_end.checked_add(reveal_duration)
+        .ok_or(MarketError::Overflow)?;
+    auction.highest_bidder = Pubkey::default();
+    auction.highest_bid = 0;
+    auction.settled = false;
+    auction.bump = ctx.bumps.auction;
+
+    msg!("Auction {} opened: {} {:?}, reserve {} DAC", id, amount, outcome, reserve_price);
+    Ok(())
+}
+
+/// Escrow DAC behind a hash of a hidden bid
+pub fn commit_bid(ctx: Context<CommitBid>, hash: [u8; 32], deposit: u64) -> Result<()> {
+    require!(deposit > 0, MarketError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.bidder_dac.to_account_info(),
+            to: ctx.accounts.dac_vault.to_account_info(),
+            authority: ctx.accounts.bidder.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, deposit)?;
+
+    let bid = &mut ctx.accounts.bid;
+    bid.auction = ctx.accounts.auction.key();
+    bid.bidder = ctx.accounts.bidder.key();
+    bid.hash = hash;
+    bid.deposit = deposit;
+    bid.bid = 0;
+    bid.revealed = false;
+    bid.bump = ctx.bumps.bid;
+
+    msg!("Sealed bid committed with {} DAC escrowed", deposit);
+    Ok(())
+}
+
+/// Open a sealed bid; the earliest reveal wins ties
+pub fn reveal_bid(ctx: Context<RevealBid>, bid: u64, salt: [u8; 32]) -> Result<()> {
+    let sealed = &ctx.accounts.bid;
+    require!(
+        SealedBid::bid_hash(&sealed.auction, &sealed.bidder, bid, &salt) == sealed.hash,
+        MarketError::InvalidBidReveal
+    );
+    require!(bid <= sealed.deposit, MarketError::InvalidBidReveal);
+
+    let auction = &mut ctx.accounts.auction;
+    if bid >= auction.reserve_price && bid > auction.highest_bid {
+        auction.highest_bid = bid;
+        auction.highest_bidder = sealed.bidder;
+    }
+    let sealed = &mut ctx.accounts.bid;
+    sealed.bid = bid;
+    sealed.revealed = true;
+
+    msg!("Bid revealed: {} DAC", bid);
+    Ok(())
+}
+
+/// Pay the seller and deliver the tokens to the highest bidder, or return them unsold (permissionless)
+pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    let id_bytes = auction.id.to_le_bytes();
+    let seeds = &[AUCTION_SEED, auction.seller.as_ref(), id_bytes.as_ref(), &[auction.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let token_destination = if auction.has_winner() {
+        let winner_tokens = ctx.accounts.winner_tokens.as_ref()
+            .ok_or(MarketError::InvalidAuctionParams)?;
+        require_keys_eq!(winner_tokens.owner, auction.highest_bidder, MarketError::Unauthorized);
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.dac_vault.to_account_info(),
+                to: ctx.accounts.seller_dac.to_account_info(),
+                authority: auction.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, auction.highest_bid)?;
+        winner_tokens.to_account_info()
+    } else {
+        ctx.accounts.seller_tokens.to_account_info()
+    };
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.token_vault.to_account_info(),
+            to: token_destination,
+            authority: auction.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, auction.amount)?;
+
+    let auction = &mut ctx.accounts.auction;
+    auction.settled = true;
+
+    msg!(
+        "Auction {} settled: {} DAC from {}",
+        auction.id,
+        auction.highest_bid,
+        auction.highest_bidder
+    );
+    Ok(())
+}
+
+/// Release a bid's deposit once the auction settles (permissionless)
+///
+/// Revealed bids get their deposit back, less the price for the winner.
+/// Deposits of unrevealed bids go to the seller.
+pub fn close_bid(ctx: Context<CloseBid>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    let bid = &ctx.accounts.bid;
+    let (to, amount) = if bid.revealed {
+        let price = if auction.highest_bidder == bid.bidder { auction.highest_bid } else { 0 };
+        (&ctx.accounts.bidder_dac, bid.deposit - price)
+    } else {
+        (&ctx.accounts.seller_dac, bid.deposit)
+    };
+
+    if amount > 0 {
+        let id_bytes = auction.id.to_le_bytes();
+        let seeds = &[AUCTION_SEED, auction.seller.as_ref(), id_bytes.as_ref(), &[auction.bump]];
+        let signer_seeds = &[&seeds[..]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.dac_vault.to_account_info(),
+                to: to.to_account_info(),
+                authority: auction.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, amount)?;
+    }
+
+    msg!(
+        "Bid closed: {} DAC {}",
+        amount,
+        if bid.revealed { "refunded" } else { "forfeited" }
+    );
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateAuction<'info> {
+    /// The market of the tokens sold
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = escrow @ MarketError::MintMismatch,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The auction to create
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + Auction::LEN,
+        seeds = [AUCTION_SEED, seller.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    /// Mint of the outcome sold
+    pub outcome_mint: Box<Account<'info, Mint>>,
+
+    /// Vault holding the tokens for sale
+    #[account(
+        init,
+        payer = seller,
+        seeds = [AUCTION_TOKENS_SEED, auction.key().as_ref()],
+        bump,
+        token::mint = outcome_mint,
+        token::authority = auction,
+    )]
+    pub token_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Vault holding the bid deposits
+    #[account(
+        init,
+        payer = seller,
+        seeds = [AUCTION_DAC_SEED, auction.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = auction,
+    )]
+    pub dac_vault: Box<Account<'info, TokenAccount>>,
+
+    /// The market's DAC escrow (identifies the DAC mint)
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    /// Seller's outcome token account (source)
+    #[account(
+        mut,
+        constraint = seller_tokens.mint == outcome_mint.key() @ MarketError::MintMismatch,
+    )]
+    pub seller_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CommitBid<'info> {
+    /// The auction to bid on
+    #[account(
+        seeds = [AUCTION_SEED, auction.seller.as_ref(), auction.id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        has_one = dac_vault @ MarketError::MintMismatch,
+        constraint = auction.is_committing(Clock::get()?.unix_timestamp) @ MarketError::AuctionWindowClosed,
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    /// The sealed bid to create
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + SealedBid::LEN,
+        seeds = [SEALED_BID_SEED, auction.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Box<Account<'info, SealedBid>>,
+
+    #[account(mut)]
+    pub dac_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Bidder's DAC token account (source of the deposit)
+    #[account(
+        mut,
+        constraint = bidder_dac.mint == dac_vault.mint @ MarketError::MintMismatch,
+    )]
+    pub bidder_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
+    /// The auction in its reveal window
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction.seller.as_ref(), auction.id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        constraint = auction.is_revealing(Clock::get()?.unix_timestamp) @ MarketError::AuctionWindowClosed,
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    /// The bid to open
+    #[account(
+        mut,
+        seeds = [SEALED_BID_SEED, auction.key().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump,
+        constraint = !bid.revealed @ MarketError::InvalidBidReveal,
+    )]
+    pub bid: Box<Account<'info, SealedBid>>,
+
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    /// The auction whose reveal window has closed
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction.seller.as_ref(), auction.id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        has_one = token_vault @ MarketError::MintMismatch,
+        has_one = dac_vault @ MarketError::MintMismatch,
+        constraint = !auction.settled @ MarketError::AuctionSettled,
+        constraint = Clock::get()?.unix_timestamp >= auction.reveal_end @ MarketError::AuctionWindowOpen,
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    #[account(mut)]
+    pub token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub dac_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Seller's DAC token account (receives the winning bid)
+    #[account(
+        mut,
+        constraint = seller_dac.owner == auction.seller @ MarketError::Unauthorized,
+        constraint = seller_dac.mint == dac_vault.mint @ MarketError::MintMismatch,
+    )]
+    pub seller_dac: Box<Account<'info, TokenAccount>>,
+
+    /// Seller's outcome token account (receives unsold tokens)
+    #[account(
+        mut,
+        constraint = seller_tokens.owner == auction.seller @ MarketError::Unauthorized,
+        constraint = seller_tokens.mint == token_vault.mint @ MarketError::MintMismatch,
+    )]
+    pub seller_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// Winner's outcome token account, required when there is a winner
+    #[account(
+        mut,
+        constraint = winner_tokens.mint == token_vault.mint @ MarketError::MintMismatch,
+    )]
+    pub winner_tokens: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBid<'info> {
+    /// The settled auction
+    #[account(
+        seeds = [AUCTION_SEED, auction.seller.as_ref(), auction.id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        has_one = dac_vault @ MarketError::MintMismatch,
+        constraint = auction.settled @ MarketError::AuctionWindowOpen,
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    /// The bid to close (rent returns to the bidder)
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [SEALED_BID_SEED, auction.key().as_ref(), bid.bidder.as_ref()],
+        bump = bid.bump,
+        has_one = bidder @ MarketError::Unauthorized,
+    )]
+    pub bid: Box<Account<'info, SealedBid>>,
+
+    #[account(mut)]
+    pub dac_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Bidder's DAC token account (receives a refund)
+    #[account(
+        mut,
+        constraint = bidder_dac.owner == bid.bidder @ MarketError::Unauthorized,
+        constraint = bidder_dac.mint == dac_vault.mint @ MarketError::MintMismatch,
+    )]
+    pub bidder_dac: Box<Account<'info, TokenAccount>>,
+
+    /// Seller's DAC token account (receives a forfeited deposit)
+    #[account(
+        mut,
+        constraint = seller_dac.owner == auction.seller @ MarketError::Unauthorized,
+        constraint = seller_dac.mint == dac_vault.mint @ MarketError::MintMismatch,
+    )]
+    pub seller_dac: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The bidder's wallet, receiving the bid's rent
+    #[account(mut)]
+    pub bidder: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{MARKET_CONFIG_SEED, POOL_SEED};
+
+pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+    ctx.accounts.config.guardian = guardian;
+
+    msg!("Guardian set to {}", guardian);
+    Ok(())
+}
+
+pub fn set_circuit_breaker(
+    ctx: Context<SetCircuitBreaker>,
+    threshold_bps: u16,
+    window_slots: u64,
+    cooloff_slots: u64,
+) -> Result<()> {
+    require!(
+        threshold_bps as u64 <= BPS_DENOMINATOR && window_slots > 0 && cooloff_slots > 0,
+        MarketError::InvalidBreakerParams
+    );
+
+    // Keep an active halt in place; only the parameters change
+    let breaker = &mut ctx.accounts.pool.breaker;
+    breaker.threshold_bps = threshold_bps;
+    breaker.window_slots = window_slots;
+    breaker.cooloff_slots = cooloff_slots;
+
+    msg!(
+        "Circuit breaker set: {} bps over {} slots, {} slot cool-off",
+        threshold_bps,
+        window_slots,
+        cooloff_slots
+    );
+    Ok(())
+}
+
+pub fn resume_trading(ctx: Context<ResumeTrading>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let price = pool.price(Outcome::Yes);
+    pool.breaker.resume(Clock::get()?.slot, price);
+
+    msg!("Trading resumed at YES price {}", price);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCircuitBreaker<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.market.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResumeTrading<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = guardian @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.market.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub guardian: Signer<'info>,
+}
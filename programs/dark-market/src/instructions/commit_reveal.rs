@@ -0,0 +1,615 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::events::PrivateAction;
+use crate::instructions::{
+    emit_audit_memo, impl_pool_vaults, record_trade, track_price_move, PoolVaults,
+};
+use crate::state::*;
+use crate::{
+    BET_BATCH_NO_SEED, BET_BATCH_SEED, BET_BATCH_VAULT_SEED, BET_BATCH_YES_SEED,
+    BET_COMMITMENT_SEED, MARKET_SEED, OBSERVATIONS_SEED, POOL_SEED, PORTFOLIO_SEED,
+    VIEWING_KEY_SEED,
+};
+
+pub fn open_bet_batch(ctx: Context<OpenBetBatch>, id: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let reveal_end = now + BET_COMMIT_WINDOW + BET_REVEAL_WINDOW;
+    require!(reveal_end < ctx.accounts.market.load()?.end_time, MarketError::MarketClosed);
+
+    let batch = &mut ctx.accounts.batch;
+    batch.pool = ctx.accounts.pool.key();
+    batch.id = id;
+    batch.commit_end = now + BET_COMMIT_WINDOW;
+    batch.reveal_end = reveal_end;
+    batch.vault = ctx.accounts.vault.key();
+    batch.yes_holding = ctx.accounts.yes_holding.key();
+    batch.no_holding = ctx.accounts.no_holding.key();
+    batch.yes_in = 0;
+    batch.no_in = 0;
+    batch.yes_out = 0;
+    batch.no_out = 0;
+    batch.settled = false;
+    batch.bump = ctx.bumps.batch;
+
+    msg!("Bet batch {} opened, commits until {}", id, batch.commit_end);
+    Ok(())
+}
+
+/// Escrow DAC behind a hash of the bet, keeping its direction and size hidden
+pub fn commit_bet(
+    ctx: Context<CommitBet>,
+    hash: [u8; 32],
+    deposit: u64,
+    audit_memo: Vec<u8>,
+) -> Result<()> {
+    require!(deposit > 0, MarketError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_dac.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, deposit)?;
+
+    let commitment = &mut ctx.accounts.commitment;
+    commitment.batch = ctx.accounts.batch.key();
+    commitment.owner = ctx.accounts.owner.key();
+    commitment.hash = hash;
+    commitment.deposit = deposit;
+    commitment.amount = 0;
+    commitment.outcome = None;
+    commitment.bump = ctx.bumps.commitment;
+    emit_audit_memo(
+        &ctx.accounts.viewing_key,
+        commitment.owner,
+        PrivateAction::BetCommitment,
+        commitment.key(),
+        audit_memo,
+    )?;
+
+    msg!("Bet committed with {} DAC escrowed", deposit);
+    Ok(())
+}
+
+/// Open a committed bet, adding it to its side of the batch and refunding the excess deposit
+pub fn reveal_bet(ctx: Context<RevealBet>, amount: u64, outcome: Outcome, salt: [u8; 32]) -> Result<()> {
+    let commitment = &ctx.accounts.commitment;
+    require!(outcome != Outcome::Invalid, MarketError::InvalidOrderOutcome);
+    require!(
+        BetCommitment::bet_hash(&commitment.batch, &commitment.owner, amount, outcome, &salt)
+            == commitment.hash,
+        MarketError::InvalidBetReveal
+    );
+    require!(amount > 0 && amount <= commitment.deposit, MarketError::InvalidBetReveal);
+
+    let excess = commitment.deposit - amount;
+    if excess > 0 {
+        pay_from_batch_vault(
+            &ctx.accounts.batch,
+            &ctx.accounts.vault,
+            &ctx.accounts.owner_dac,
+            &ctx.accounts.token_program,
+            excess,
+        )?;
+    }
+
+    let batch = &mut ctx.accounts.batch;
+    match outcome {
+        Outcome::No => batch.no_in = batch.no_in.checked_add(amount).ok_or(MarketError::Overflow)?,
+        _ => batch.yes_in = batch.yes_in.checked_add(amount).ok_or(MarketError::Overflow)?,
+    }
+    let commitment = &mut ctx.accounts.commitment;
+    commitment.amount = amount;
+    commitment.outcome = Some(outcome);
+
+    msg!("Bet revealed: {} DAC on {:?}", amount, outcome);
+    Ok(())
+}
+
+/// Trade a batch's revealed bets against the pool once the reveal window closes (permissionless)
+pub fn settle_bet_batch(ctx: Context<SettleBetBatch>) -> Result<()> {
+    ctx.accounts.pool.refresh_fee(&ctx.accounts.observations.load()?, Clock::get()?.unix_timestamp);
+
+    let batch = &ctx.accounts.batch;
+    let (yes_in, no_in) = (batch.yes_in, batch.no_in);
+    let pool_key = batch.pool;
+    let id_bytes = batch.id.to_le_bytes();
+    let seeds = &[BET_BATCH_SEED, pool_key.as_ref(), id_bytes.as_ref(), &[batch.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let batch_info = batch.to_account_info();
+
+    // The smaller side trades first
+    let legs = if yes_in <= no_in {
+        [(Outcome::Yes, yes_in), (Outcome::No, no_in)]
+    } else {
+        [(Outcome::No, no_in), (Outcome::Yes, yes_in)]
+    };
+    let (mut yes_out, mut no_out) = (0, 0);
+    for (outcome, amount) in legs {
+        if amount == 0 {
+            continue;
+        }
+        let accounts = &ctx.accounts;
+        let out = accounts.pool.quote_buy(outcome, amount, 0)
+            .ok_or(MarketError::Overflow)?;
+        let holding = match outcome {
+            Outcome::No => &accounts.no_holding,
+            _ => &accounts.yes_holding,
+        };
+        let vaults = accounts.vaults();
+        vaults.deposit_dac(&accounts.vault.to_account_info(), &batch_info, signer_seeds, amount)?;
+        vaults.withdraw_outcome(outcome, holding, out)?;
+
+        let price_before = ctx.accounts.pool.price(Outcome::Yes);
+        ctx.accounts.pool.apply_buy(outcome, amount, out)
+            .ok_or(MarketError::Overflow)?;
+        track_price_move(&mut ctx.accounts.pool, &mut ctx.accounts.observations.load_mut()?, price_before)?;
+        let market = &mut ctx.accounts.market.load_mut()?;
+        market.total_collateral = market.total_collateral.checked_add(amount)
+            .ok_or(MarketError::Overflow)?;
+        match outcome {
+            Outcome::No => no_out = out,
+            _ => yes_out = out,
+        }
+    }
+
+    let batch = &mut ctx.accounts.batch;
+    batch.yes_out = yes_out;
+    batch.no_out = no_out;
+    batch.settled = true;
+
+    msg!(
+        "Bet batch {} settled: {} DAC bought {} YES, {} DAC bought {} NO",
+        batch.id,
+        yes_in,
+        yes_out,
+        no_in,
+        no_out
+    );
+    Ok(())
+}
+
+/// Collect a revealed bet's share of its side's purchase from a settled batch
+pub fn claim_bet(ctx: Context<ClaimBet>) -> Result<()> {
+    let commitment = &ctx.accounts.commitment;
+    let batch = &ctx.accounts.batch;
+    let outcome = commitment.outcome.ok_or(MarketError::InvalidBetReveal)?;
+    let payout = batch.payout(outcome, commitment.amount)
+        .ok_or(MarketError::Overflow)?;
+
+    let holding = match outcome {
+        Outcome::No => &ctx.accounts.no_holding,
+        _ => &ctx.accounts.yes_holding,
+    };
+    require_keys_eq!(holding.mint, ctx.accounts.owner_outcome.mint, MarketError::MintMismatch);
+
+    let id_bytes = batch.id.to_le_bytes();
+    let seeds = &[BET_BATCH_SEED, batch.pool.as_ref(), id_bytes.as_ref(), &[batch.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    if payout > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: holding.to_account_info(),
+                to: ctx.accounts.owner_outcome.to_account_info(),
+                authority: batch.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, payout)?;
+    }
+
+    let market_key = ctx.accounts.market.key();
+    let amount = commitment.amount;
+    record_trade(ctx.accounts.portfolio.as_mut(), |p| {
+        p.record_buy(market_key, outcome, payout, amount)
+    })?;
+
+    msg!("Bet claimed: {} {:?} for {} DAC", payout, outcome, amount);
+    Ok(())
+}
+
+/// Return the deposit of a bet that was never revealed, or of a batch that
+/// can no longer settle because its market stopped trading
+pub fn refund_bet(ctx: Context<RefundBet>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.commitment.outcome.is_none()
+            || (!ctx.accounts.batch.settled && !ctx.accounts.market.load()?.is_trading(now)),
+        MarketError::InvalidBetReveal
+    );
+
+    let deposit = ctx.accounts.commitment.deposit;
+    pay_from_batch_vault(
+        &ctx.accounts.batch,
+        &ctx.accounts.vault,
+        &ctx.accounts.owner_dac,
+        &ctx.accounts.token_program,
+        deposit,
+    )?;
+
+    msg!("Unrevealed bet refunded: {} DAC", deposit);
+    Ok(())
+}
+
+/// Transfer DAC out of a batch's vault, signed by the batch PDA
+fn pay_from_batch_vault<'info>(
+    batch: &Account<'info, BetBatch>,
+    vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let id_bytes = batch.id.to_le_bytes();
+    let seeds = &[BET_BATCH_SEED, batch.pool.as_ref(), id_bytes.as_ref(), &[batch.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: vault.to_account_info(),
+            to: to.to_account_info(),
+            authority: batch.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)
+}
+
+impl_pool_vaults!(SettleBetBatch);
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct OpenBetBatch<'info> {
+    /// The pool's market
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_trading(Clock::get()?.unix_timestamp) @ MarketError::MarketClosed,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The pool the batch will trade against
+    #[account(
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The batch to create
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + BetBatch::LEN,
+        seeds = [BET_BATCH_SEED, pool.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub batch: Box<Account<'info, BetBatch>>,
+
+    /// DAC vault holding the escrowed deposits
+    #[account(
+        init,
+        payer = payer,
+        seeds = [BET_BATCH_VAULT_SEED, batch.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = batch,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// YES bought for the batch
+    #[account(
+        init,
+        payer = payer,
+        seeds = [BET_BATCH_YES_SEED, batch.key().as_ref()],
+        bump,
+        token::mint = yes_mint,
+        token::authority = batch,
+    )]
+    pub yes_holding: Box<Account<'info, TokenAccount>>,
+
+    /// NO bought for the batch
+    #[account(
+        init,
+        payer = payer,
+        seeds = [BET_BATCH_NO_SEED, batch.key().as_ref()],
+        bump,
+        token::mint = no_mint,
+        token::authority = batch,
+    )]
+    pub no_holding: Box<Account<'info, TokenAccount>>,
+
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow (identifies the DAC mint)
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CommitBet<'info> {
+    /// The batch to commit to
+    #[account(
+        seeds = [BET_BATCH_SEED, batch.pool.as_ref(), batch.id.to_le_bytes().as_ref()],
+        bump = batch.bump,
+        has_one = vault @ MarketError::MintMismatch,
+        constraint = batch.is_committing(Clock::get()?.unix_timestamp) @ MarketError::BetWindowClosed,
+    )]
+    pub batch: Box<Account<'info, BetBatch>>,
+
+    /// The commitment to create
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + BetCommitment::LEN,
+        seeds = [BET_COMMITMENT_SEED, batch.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub commitment: Box<Account<'info, BetCommitment>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account (source of the deposit)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: the owner's viewing key PDA, empty if none is registered (read by `emit_audit_memo`)
+    #[account(seeds = [VIEWING_KEY_SEED, owner.key().as_ref()], bump)]
+    pub viewing_key: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealBet<'info> {
+    /// The batch being revealed
+    #[account(
+        mut,
+        seeds = [BET_BATCH_SEED, batch.pool.as_ref(), batch.id.to_le_bytes().as_ref()],
+        bump = batch.bump,
+        has_one = vault @ MarketError::MintMismatch,
+        constraint = batch.is_revealing(Clock::get()?.unix_timestamp) @ MarketError::BetWindowClosed,
+    )]
+    pub batch: Box<Account<'info, BetBatch>>,
+
+    /// The commitment to open
+    #[account(
+        mut,
+        seeds = [BET_COMMITMENT_SEED, batch.key().as_ref(), owner.key().as_ref()],
+        bump = commitment.bump,
+        constraint = commitment.outcome.is_none() @ MarketError::InvalidBetReveal,
+    )]
+    pub commitment: Box<Account<'info, BetCommitment>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account (receives the excess deposit)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SettleBetBatch<'info> {
+    /// The pool's market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_trading(Clock::get()?.unix_timestamp) @ MarketError::MarketClosed,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The pool to trade against
+    #[account(
+        mut,
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump = pool.bump,
+        has_one = yes_vault @ MarketError::MintMismatch,
+        has_one = no_vault @ MarketError::MintMismatch,
+        constraint = !pool.breaker.is_halted(Clock::get()?.slot) @ MarketError::TradingHalted,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The pool's price observation buffer
+    #[account(
+        mut,
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump = observations.load()?.bump,
+    )]
+    pub observations: AccountLoader<'info, PoolObservations>,
+
+    #[account(mut)]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The batch to settle
+    #[account(
+        mut,
+        seeds = [BET_BATCH_SEED, pool.key().as_ref(), batch.id.to_le_bytes().as_ref()],
+        bump = batch.bump,
+        has_one = vault @ MarketError::MintMismatch,
+        has_one = yes_holding @ MarketError::MintMismatch,
+        has_one = no_holding @ MarketError::MintMismatch,
+        constraint = !batch.settled @ MarketError::BetBatchSettled,
+        constraint = Clock::get()?.unix_timestamp >= batch.reveal_end @ MarketError::BetWindowOpen,
+    )]
+    pub batch: Box<Account<'info, BetBatch>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub yes_holding: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub no_holding: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBet<'info> {
+    /// The batch's market
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The settled batch
+    #[account(
+        seeds = [BET_BATCH_SEED, batch.pool.as_ref(), batch.id.to_le_bytes().as_ref()],
+        bump = batch.bump,
+        has_one = yes_holding @ MarketError::MintMismatch,
+        has_one = no_holding @ MarketError::MintMismatch,
+        constraint = batch.settled @ MarketError::BetBatchNotSettled,
+    )]
+    pub batch: Box<Account<'info, BetBatch>>,
+
+    /// The batch's pool
+    #[account(
+        address = batch.pool @ MarketError::MintMismatch,
+        constraint = pool.market == market.key() @ MarketError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The revealed commitment (closed)
+    #[account(
+        mut,
+        close = owner,
+        seeds = [BET_COMMITMENT_SEED, batch.key().as_ref(), owner.key().as_ref()],
+        bump = commitment.bump,
+    )]
+    pub commitment: Box<Account<'info, BetCommitment>>,
+
+    #[account(mut)]
+    pub yes_holding: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub no_holding: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's token account for the side bet on
+    #[account(mut)]
+    pub owner_outcome: Box<Account<'info, TokenAccount>>,
+
+    /// The owner's portfolio, updated when passed
+    #[account(
+        mut,
+        seeds = [PORTFOLIO_SEED, owner.key().as_ref()],
+        bump = portfolio.bump,
+    )]
+    pub portfolio: Option<Box<Account<'info, Portfolio>>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RefundBet<'info> {
+    /// The batch's market
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The batch's pool
+    #[account(
+        address = batch.pool @ MarketError::MintMismatch,
+        constraint = pool.market == market.key() @ MarketError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The batch whose reveal window has closed
+    #[account(
+        seeds = [BET_BATCH_SEED, batch.pool.as_ref(), batch.id.to_le_bytes().as_ref()],
+        bump = batch.bump,
+        has_one = vault @ MarketError::MintMismatch,
+        constraint = Clock::get()?.unix_timestamp >= batch.reveal_end @ MarketError::BetWindowOpen,
+    )]
+    pub batch: Box<Account<'info, BetBatch>>,
+
+    /// The commitment to refund (closed)
+    #[account(
+        mut,
+        close = owner,
+        seeds = [BET_COMMITMENT_SEED, batch.key().as_ref(), owner.key().as_ref()],
+        bump = commitment.bump,
+    )]
+    pub commitment: Box<Account<'info, BetCommitment>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account (receives the deposit)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,387 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::bubblegum::{
+    self, BurnArgs, CreateTreeArgs, ACCOUNT_COMPRESSION_PROGRAM_ID, BUBBLEGUM_PROGRAM_ID, NOOP_PROGRAM_ID,
+};
+use crate::errors::MarketError;
+use crate::events::CompressedReceiptMinted;
+use crate::state::*;
+use crate::{COMPRESSED_RECEIPT_VAULT_SEED, MARKET_SEED, RECEIPT_TREE_SEED};
+
+/// Give a market a Bubblegum tree of compressed position receipts (market creator only)
+///
+/// `merkle_tree` must already be allocated, owned by the account
+/// compression program, with room for `max_depth` and `max_buffer_size`.
+pub fn enable_compressed_receipts(
+    ctx: Context<EnableCompressedReceipts>,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<()> {
+    require!(max_depth >= MIN_RECEIPT_TREE_DEPTH, MarketError::InvalidReceiptTree);
+
+    let market_key = ctx.accounts.market.key();
+    let receipt_tree = &mut ctx.accounts.receipt_tree;
+    receipt_tree.market = market_key;
+    receipt_tree.merkle_tree = ctx.accounts.merkle_tree.key();
+    receipt_tree.minted = 0;
+    receipt_tree.yes_amount = 0;
+    receipt_tree.no_amount = 0;
+    receipt_tree.bump = ctx.bumps.receipt_tree;
+
+    // The receipt tree PDA creates the tree, so it is the only one that can mint into it
+    let ix = bubblegum::create_tree(
+        &receipt_tree.merkle_tree,
+        &ctx.accounts.creator.key(),
+        &receipt_tree.key(),
+        CreateTreeArgs { max_depth, max_buffer_size, public: Some(false) },
+    );
+    let seeds = &[RECEIPT_TREE_SEED, market_key.as_ref(), &[receipt_tree.bump]];
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.tree_config.to_account_info(),
+            ctx.accounts.merkle_tree.to_account_info(),
+            ctx.accounts.creator.to_account_info(),
+            receipt_tree.to_account_info(),
+            ctx.accounts.log_wrapper.to_account_info(),
+            ctx.accounts.compression_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.bubblegum_program.to_account_info(),
+        ],
+        &[&seeds[..]],
+    )?;
+
+    msg!("Market #{} issues compressed receipts in tree {}", ctx.accounts.market.load()?.id, receipt_tree.merkle_tree);
+    Ok(())
+}
+
+/// Wrap outcome tokens into a compressed position receipt minted to the owner
+pub fn wrap_position_compressed(
+    ctx: Context<WrapPositionCompressed>,
+    outcome: Outcome,
+    amount: u64,
+    uri: String,
+) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+    require!(uri.len() <= MAX_RECEIPT_URI_LEN, MarketError::UriTooLong);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_outcome.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let market_key = ctx.accounts.market.key();
+    let owner_key = ctx.accounts.owner.key();
+    let receipt_tree = &mut ctx.accounts.receipt_tree;
+    let ix = bubblegum::mint_v1(
+        &receipt_tree.merkle_tree,
+        &owner_key,
+        &owner_key,
+        &receipt_tree.key(),
+        &ReceiptTree::metadata(outcome, amount, uri),
+    );
+    let seeds = &[RECEIPT_TREE_SEED, market_key.as_ref(), &[receipt_tree.bump]];
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.tree_config.to_account_info(),
+            ctx.accounts.owner.to_account_info(),
+            ctx.accounts.merkle_tree.to_account_info(),
+            receipt_tree.to_account_info(),
+            ctx.accounts.log_wrapper.to_account_info(),
+            ctx.accounts.compression_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.bubblegum_program.to_account_info(),
+        ],
+        &[&seeds[..]],
+    )?;
+
+    let nonce = receipt_tree.minted;
+    receipt_tree.minted += 1;
+    let wrapped = receipt_tree.wrapped(outcome);
+    *wrapped = wrapped.checked_add(amount).ok_or(MarketError::Overflow)?;
+
+    emit!(CompressedReceiptMinted {
+        market: market_key,
+        merkle_tree: receipt_tree.merkle_tree,
+        asset_id: bubblegum::asset_id(&receipt_tree.merkle_tree, nonce),
+        nonce,
+        owner: owner_key,
+        outcome,
+        amount,
+    });
+    msg!("Wrapped {} outcome tokens into compressed receipt #{}", amount, nonce);
+    Ok(())
+}
+
+/// Burn a compressed position receipt and release its outcome tokens to the holder
+///
+/// The caller restates the receipt's side, amount and URI and passes the
+/// leaf's proof nodes as remaining accounts; the burn only verifies against
+/// `root` if they match the minted leaf.
+#[allow(clippy::too_many_arguments)]
+pub fn unwrap_compressed_position<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UnwrapCompressedPosition<'info>>,
+    root: [u8; 32],
+    outcome: Outcome,
+    amount: u64,
+    uri: String,
+    nonce: u64,
+    index: u32,
+) -> Result<()> {
+    let metadata = ReceiptTree::metadata(outcome, amount, uri);
+    let args = BurnArgs {
+        root,
+        data_hash: metadata.data_hash(),
+        creator_hash: metadata.creator_hash(),
+        nonce,
+        index,
+    };
+    let proof: Vec<Pubkey> = ctx.remaining_accounts.iter().map(|node| node.key()).collect();
+    let ix = bubblegum::burn(
+        &ctx.accounts.receipt_tree.merkle_tree,
+        &ctx.accounts.holder.key(),
+        &ctx.accounts.leaf_delegate.key(),
+        &proof,
+        args,
+    );
+    let mut infos = vec![
+        ctx.accounts.tree_config.to_account_info(),
+        ctx.accounts.holder.to_account_info(),
+        ctx.accounts.leaf_delegate.to_account_info(),
+        ctx.accounts.merkle_tree.to_account_info(),
+        ctx.accounts.log_wrapper.to_account_info(),
+        ctx.accounts.compression_program.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.bubblegum_program.to_account_info(),
+    ];
+    infos.extend(ctx.remaining_accounts.iter().cloned());
+    invoke(&ix, &infos)?;
+
+    let receipt_tree = &mut ctx.accounts.receipt_tree;
+    let wrapped = receipt_tree.wrapped(outcome);
+    *wrapped = wrapped.checked_sub(amount).ok_or(MarketError::Overflow)?;
+
+    let market_key = ctx.accounts.market.key();
+    let seeds = &[RECEIPT_TREE_SEED, market_key.as_ref(), &[receipt_tree.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.holder_outcome.to_account_info(),
+            authority: receipt_tree.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    msg!("Unwrapped compressed receipt #{} into {} outcome tokens", nonce, amount);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct EnableCompressedReceipts<'info> {
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = creator @ MarketError::Unauthorized,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + ReceiptTree::LEN,
+        seeds = [RECEIPT_TREE_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub receipt_tree: Box<Account<'info, ReceiptTree>>,
+
+    #[account(address = market.load()?.yes_mint @ MarketError::MintMismatch)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(address = market.load()?.no_mint @ MarketError::MintMismatch)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// Vault holding the YES tokens of every compressed receipt
+    #[account(
+        init,
+        payer = creator,
+        seeds = [COMPRESSED_RECEIPT_VAULT_SEED, receipt_tree.key().as_ref(), yes_mint.key().as_ref()],
+        bump,
+        token::mint = yes_mint,
+        token::authority = receipt_tree,
+    )]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Vault holding the NO tokens of every compressed receipt
+    #[account(
+        init,
+        payer = creator,
+        seeds = [COMPRESSED_RECEIPT_VAULT_SEED, receipt_tree.key().as_ref(), no_mint.key().as_ref()],
+        bump,
+        token::mint = no_mint,
+        token::authority = receipt_tree,
+    )]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: pre-allocated tree, initialized by Bubblegum through the compression program
+    #[account(mut, owner = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum's config of the tree, created by Bubblegum
+    #[account(mut, seeds = [merkle_tree.key().as_ref()], seeds::program = BUBBLEGUM_PROGRAM_ID, bump)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: the Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: the noop program Bubblegum logs leaves through
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: the account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome: Outcome)]
+pub struct WrapPositionCompressed<'info> {
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [RECEIPT_TREE_SEED, market.key().as_ref()],
+        bump = receipt_tree.bump,
+        has_one = merkle_tree @ MarketError::InvalidReceiptTree,
+    )]
+    pub receipt_tree: Box<Account<'info, ReceiptTree>>,
+
+    /// Owner's outcome token account (source)
+    #[account(
+        mut,
+        constraint = market.load()?.outcome_mint(outcome) == Some(owner_outcome.mint) @ MarketError::InvalidReceiptOutcome,
+    )]
+    pub owner_outcome: Box<Account<'info, TokenAccount>>,
+
+    /// Vault of the wrapped side
+    #[account(
+        mut,
+        seeds = [COMPRESSED_RECEIPT_VAULT_SEED, receipt_tree.key().as_ref(), owner_outcome.mint.as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: the receipt tree's Merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum's config of the tree
+    #[account(mut, seeds = [merkle_tree.key().as_ref()], seeds::program = BUBBLEGUM_PROGRAM_ID, bump)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: the Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: the noop program Bubblegum logs leaves through
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: the account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32], outcome: Outcome)]
+pub struct UnwrapCompressedPosition<'info> {
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [RECEIPT_TREE_SEED, market.key().as_ref()],
+        bump = receipt_tree.bump,
+        has_one = merkle_tree @ MarketError::InvalidReceiptTree,
+    )]
+    pub receipt_tree: Box<Account<'info, ReceiptTree>>,
+
+    /// Holder's outcome token account (destination)
+    #[account(
+        mut,
+        constraint = market.load()?.outcome_mint(outcome) == Some(holder_outcome.mint) @ MarketError::InvalidReceiptOutcome,
+    )]
+    pub holder_outcome: Box<Account<'info, TokenAccount>>,
+
+    /// Vault of the unwrapped side
+    #[account(
+        mut,
+        seeds = [COMPRESSED_RECEIPT_VAULT_SEED, receipt_tree.key().as_ref(), holder_outcome.mint.as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: the receipt tree's Merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum's config of the tree
+    #[account(seeds = [merkle_tree.key().as_ref()], seeds::program = BUBBLEGUM_PROGRAM_ID, bump)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: the leaf's delegate, part of the leaf Bubblegum verifies
+    pub leaf_delegate: UncheckedAccount<'info>,
+
+    /// Owner of the receipt leaf
+    pub holder: Signer<'info>,
+
+    /// CHECK: the Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: the noop program Bubblegum logs leaves through
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: the account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,422 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::events::PrivateAction;
+use crate::instructions::emit_audit_memo;
+use crate::state::*;
+use crate::{
+    CONFIDENTIAL_MARKET_SEED, CONFIDENTIAL_POSITION_SEED, CONFIDENTIAL_VAULT_SEED, MARKET_SEED,
+    VERIFYING_KEY_SEED, VIEWING_KEY_SEED, VOLUME_COUNTER_SEED, VOLUME_REPORTING_SEED,
+};
+
+pub fn create_confidential_market(
+    ctx: Context<CreateConfidentialMarket>,
+    committee: Vec<Pubkey>,
+    threshold: u8,
+    encryption_key: [u8; 32],
+    min_bet: u64,
+    max_bet: u64,
+) -> Result<()> {
+    require!(min_bet > 0 && min_bet <= max_bet, MarketError::InvalidBetLimits);
+    require!(
+        committee.len() <= MAX_COMMITTEE_SIZE
+            && threshold > 0
+            && threshold as usize <= committee.len()
+            && committee.iter().enumerate().all(|(i, m)| !committee[..i].contains(m)),
+        MarketError::InvalidCommittee
+    );
+
+    let confidential = &mut ctx.accounts.confidential_market;
+    confidential.market = ctx.accounts.market.key();
+    confidential.committee = committee;
+    confidential.threshold = threshold;
+    confidential.encryption_key = encryption_key;
+    confidential.vault = ctx.accounts.vault.key();
+    confidential.position_count = 0;
+    confidential.decrypted_count = 0;
+    confidential.yes_total = 0;
+    confidential.no_total = 0;
+    confidential.min_bet = min_bet;
+    confidential.max_bet = max_bet;
+    confidential.range_circuit = ctx.accounts.range_key.circuit_id;
+    confidential.bump = ctx.bumps.confidential_market;
+
+    let counter = &mut ctx.accounts.volume_counter;
+    counter.market = ctx.accounts.market.key();
+    counter.reporting_key = ctx.accounts.volume_reporting.reporting_key;
+    counter.volume = ElGamalCiphertext::default();
+    counter.trades = 0;
+    counter.reported = None;
+    counter.bump = ctx.bumps.volume_counter;
+
+    msg!(
+        "Confidential market opened: {}-of-{} committee",
+        threshold,
+        confidential.committee.len()
+    );
+    Ok(())
+}
+
+/// Stake `deposit` DAC, hiding the YES share of it in `yes_stake`
+///
+/// `encrypted_volume` is the same YES stake encrypted to the reporting key,
+/// added to the market's volume counter. `range_proof` shows the hidden
+/// split is within the bet limits. `audit_memo` is required exactly when the
+/// owner has a viewing key.
+pub fn open_confidential_position(
+    ctx: Context<OpenConfidentialPosition>,
+    deposit: u64,
+    yes_stake: ElGamalCiphertext,
+    encrypted_volume: ElGamalCiphertext,
+    range_proof: Groth16Proof,
+    audit_memo: Vec<u8>,
+) -> Result<()> {
+    require!(deposit > 0, MarketError::ZeroAmount);
+    let inputs = ctx.accounts.confidential_market.range_inputs(deposit, &yes_stake);
+    require!(
+        ctx.accounts.range_key.verify(&range_proof, &inputs),
+        MarketError::InvalidProof
+    );
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_dac.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, deposit)?;
+
+    let confidential = &mut ctx.accounts.confidential_market;
+    let position = &mut ctx.accounts.position;
+    position.confidential_market = confidential.key();
+    position.owner = ctx.accounts.owner.key();
+    position.id = confidential.position_count;
+    position.deposit = deposit;
+    position.yes_stake = yes_stake;
+    position.shares = [[0u8; 32]; MAX_COMMITTEE_SIZE];
+    position.attestations = [0; MAX_COMMITTEE_SIZE];
+    position.attested = 0;
+    position.decrypted = None;
+    position.bump = ctx.bumps.position;
+    confidential.position_count = confidential.position_count.checked_add(1)
+        .ok_or(MarketError::Overflow)?;
+    ctx.accounts.volume_counter.record(&encrypted_volume)
+        .ok_or(MarketError::InvalidCiphertext)?;
+    emit_audit_memo(
+        &ctx.accounts.viewing_key,
+        position.owner,
+        PrivateAction::ConfidentialPosition,
+        position.key(),
+        audit_memo,
+    )?;
+
+    msg!("Confidential position {} opened with {} DAC", position.id, deposit);
+    Ok(())
+}
+
+/// Post a committee member's decryption share of a position and the plaintext it yields
+///
+/// Shares are published so anyone can recombine them off-chain and check
+/// the attested plaintexts against the ciphertext.
+pub fn post_decryption_share(
+    ctx: Context<PostDecryptionShare>,
+    share: [u8; 32],
+    yes_stake: u64,
+) -> Result<()> {
+    let confidential = &mut ctx.accounts.confidential_market;
+    let index = confidential.member_index(&ctx.accounts.member.key())
+        .ok_or(MarketError::Unauthorized)?;
+
+    let position = &mut ctx.accounts.position;
+    position.shares[index] = share;
+    position.attestations[index] = yes_stake;
+    position.attested |= 1 << index;
+
+    if position.attestations_for(yes_stake) >= confidential.threshold as usize {
+        position.decrypted = Some(yes_stake);
+        if position.is_valid() {
+            confidential.yes_total = confidential.yes_total.checked_add(yes_stake)
+                .ok_or(MarketError::Overflow)?;
+            confidential.no_total = confidential.no_total
+                .checked_add(position.deposit - yes_stake)
+                .ok_or(MarketError::Overflow)?;
+        }
+        confidential.decrypted_count += 1;
+        msg!("Confidential position {} decrypted", position.id);
+    }
+
+    msg!("Decryption share {} posted for position {}", index, position.id);
+    Ok(())
+}
+
+/// Pay out a position once every position of the market is decrypted
+pub fn claim_confidential_payout(ctx: Context<ClaimConfidentialPayout>) -> Result<()> {
+    let confidential = &ctx.accounts.confidential_market;
+    let position = &ctx.accounts.position;
+    let outcome = ctx.accounts.market.load()?.outcome().ok_or(MarketError::MarketNotResolved)?;
+
+    let payout = match position.decrypted {
+        Some(yes_stake) if position.is_valid() => {
+            confidential.payout(outcome, position.deposit, yes_stake)
+                .ok_or(MarketError::Overflow)?
+        }
+        _ => position.deposit,
+    };
+
+    if payout > 0 {
+        let seeds = &[
+            CONFIDENTIAL_MARKET_SEED,
+            confidential.market.as_ref(),
+            &[confidential.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.owner_dac.to_account_info(),
+                authority: confidential.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, payout)?;
+    }
+
+    msg!("Confidential position {} paid {} DAC", position.id, payout);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreateConfidentialMarket<'info> {
+    /// The market to add a confidential pool to
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = creator @ MarketError::Unauthorized,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The confidential market to create
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + ConfidentialMarket::LEN,
+        seeds = [CONFIDENTIAL_MARKET_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub confidential_market: Box<Account<'info, ConfidentialMarket>>,
+
+    /// Verifying key of the range circuit positions must prove against
+    #[account(
+        seeds = [VERIFYING_KEY_SEED, range_key.circuit_id.to_le_bytes().as_ref()],
+        bump = range_key.bump,
+        constraint = range_key.is_complete()
+            && range_key.ic_len as usize == RANGE_PUBLIC_INPUTS + 1 @ MarketError::InvalidVerifyingKey,
+    )]
+    pub range_key: Box<Account<'info, VerifyingKey>>,
+
+    /// Protocol volume reporting settings
+    #[account(
+        seeds = [VOLUME_REPORTING_SEED],
+        bump = volume_reporting.bump,
+    )]
+    pub volume_reporting: Box<Account<'info, VolumeReporting>>,
+
+    /// The market's encrypted volume counter to create
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + VolumeCounter::LEN,
+        seeds = [VOLUME_COUNTER_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub volume_counter: Box<Account<'info, VolumeCounter>>,
+
+    /// DAC vault holding the stakes
+    #[account(
+        init,
+        payer = creator,
+        seeds = [CONFIDENTIAL_VAULT_SEED, confidential_market.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = confidential_market,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// The market's DAC escrow (identifies the DAC mint)
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct OpenConfidentialPosition<'info> {
+    /// The market, still trading
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        constraint = market.load()?.is_trading(Clock::get()?.unix_timestamp) @ MarketError::MarketClosed,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The confidential market to stake in
+    #[account(
+        mut,
+        seeds = [CONFIDENTIAL_MARKET_SEED, market.key().as_ref()],
+        bump = confidential_market.bump,
+        has_one = vault @ MarketError::MintMismatch,
+    )]
+    pub confidential_market: Box<Account<'info, ConfidentialMarket>>,
+
+    /// The market's range circuit verifying key
+    #[account(
+        seeds = [VERIFYING_KEY_SEED, confidential_market.range_circuit.to_le_bytes().as_ref()],
+        bump = range_key.bump,
+    )]
+    pub range_key: Box<Account<'info, VerifyingKey>>,
+
+    /// The position to create
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ConfidentialPosition::LEN,
+        seeds = [
+            CONFIDENTIAL_POSITION_SEED,
+            confidential_market.key().as_ref(),
+            confidential_market.position_count.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub position: Box<Account<'info, ConfidentialPosition>>,
+
+    /// The market's encrypted volume counter
+    #[account(
+        mut,
+        seeds = [VOLUME_COUNTER_SEED, market.key().as_ref()],
+        bump = volume_counter.bump,
+    )]
+    pub volume_counter: Box<Account<'info, VolumeCounter>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account (source of the stake)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: the owner's viewing key PDA, empty if none is registered (read by `emit_audit_memo`)
+    #[account(seeds = [VIEWING_KEY_SEED, owner.key().as_ref()], bump)]
+    pub viewing_key: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PostDecryptionShare<'info> {
+    /// The resolved market
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        constraint = market.load()?.status() == MarketStatus::Resolved @ MarketError::MarketNotResolved,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [CONFIDENTIAL_MARKET_SEED, market.key().as_ref()],
+        bump = confidential_market.bump,
+    )]
+    pub confidential_market: Box<Account<'info, ConfidentialMarket>>,
+
+    /// The position to decrypt
+    #[account(
+        mut,
+        seeds = [
+            CONFIDENTIAL_POSITION_SEED,
+            confidential_market.key().as_ref(),
+            position.id.to_le_bytes().as_ref(),
+        ],
+        bump = position.bump,
+        constraint = position.decrypted.is_none() @ MarketError::AlreadyDecrypted,
+    )]
+    pub position: Box<Account<'info, ConfidentialPosition>>,
+
+    /// A committee member
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimConfidentialPayout<'info> {
+    /// The resolved market
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        constraint = market.load()?.status() == MarketStatus::Resolved @ MarketError::MarketNotResolved,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The fully decrypted confidential market
+    #[account(
+        seeds = [CONFIDENTIAL_MARKET_SEED, market.key().as_ref()],
+        bump = confidential_market.bump,
+        has_one = vault @ MarketError::MintMismatch,
+        constraint = confidential_market.fully_decrypted() @ MarketError::DecryptionPending,
+    )]
+    pub confidential_market: Box<Account<'info, ConfidentialMarket>>,
+
+    /// The position to pay out (closed)
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            CONFIDENTIAL_POSITION_SEED,
+            confidential_market.key().as_ref(),
+            position.id.to_le_bytes().as_ref(),
+        ],
+        bump = position.bump,
+        has_one = owner @ MarketError::Unauthorized,
+    )]
+    pub position: Box<Account<'info, ConfidentialPosition>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account (receives the payout)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,249 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::instructions::open_market;
+use crate::state::*;
+use crate::{
+    CREATOR_ESCROW_SEED, CREATOR_VAULT_SEED, ESCROW_SEED, MARKET_CONFIG_SEED, MARKET_SEED,
+    NO_MINT_SEED, VERIFYING_KEY_SEED, YES_MINT_SEED,
+};
+
+/// Create a market whose creator is a secret-controlled escrow instead of the signer
+///
+/// The signer only pays rent and the bond, so it can be a fresh wallet or a
+/// relayer with no link to the creator.
+pub fn create_anonymous_market(
+    ctx: Context<CreateAnonymousMarket>,
+    question: String,
+    end_time: i64,
+    resolver: Pubkey,
+    commitment: [u8; 32],
+    bond: u64,
+) -> Result<()> {
+    require!(question.len() <= MAX_QUESTION_LEN, MarketError::QuestionTooLong);
+    require!(
+        end_time > Clock::get()?.unix_timestamp,
+        MarketError::InvalidEndTime
+    );
+    require!(bond >= MIN_CREATION_BOND, MarketError::CreationBondTooLow);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.payer_dac.to_account_info(),
+            to: ctx.accounts.creator_vault.to_account_info(),
+            authority: ctx.accounts.payer.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, bond)?;
+
+    let creator_escrow = &mut ctx.accounts.creator_escrow;
+    creator_escrow.market = ctx.accounts.market.key();
+    creator_escrow.commitment = commitment;
+    creator_escrow.claim_circuit = ctx.accounts.claim_key.circuit_id;
+    creator_escrow.vault = ctx.accounts.creator_vault.key();
+    creator_escrow.bond = bond;
+    creator_escrow.bump = ctx.bumps.creator_escrow;
+
+    let market = &mut ctx.accounts.market.load_init()?;
+    open_market(
+        &mut ctx.accounts.config,
+        market,
+        creator_escrow.key(),
+        resolver,
+        &question,
+        end_time,
+        Pubkey::default(),
+    )?;
+    market.yes_mint = ctx.accounts.yes_mint.key();
+    market.no_mint = ctx.accounts.no_mint.key();
+    market.escrow = ctx.accounts.escrow.key();
+    market.bump = ctx.bumps.market;
+
+    msg!("Market {} created anonymously: {}", market.id, ctx.accounts.market.key());
+    Ok(())
+}
+
+/// Withdraw the creator vault to `recipient` with a proof of the creator secret
+///
+/// The bond stays locked until the market is resolved; proceeds above it
+/// can be claimed at any time.
+pub fn claim_creator_escrow(ctx: Context<ClaimCreatorEscrow>, proof: Groth16Proof) -> Result<()> {
+    let creator_escrow = &ctx.accounts.creator_escrow;
+    let inputs = creator_escrow.claim_inputs(&ctx.accounts.recipient.key());
+    require!(ctx.accounts.claim_key.verify(&proof, &inputs), MarketError::InvalidProof);
+
+    let settled = matches!(
+        ctx.accounts.market.load()?.status(),
+        MarketStatus::Resolved | MarketStatus::Swept
+    );
+    let locked = if settled { 0 } else { creator_escrow.bond };
+    let amount = ctx.accounts.vault.amount.saturating_sub(locked);
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    let market_key = creator_escrow.market;
+    let seeds = &[
+        CREATOR_ESCROW_SEED,
+        market_key.as_ref(),
+        &[creator_escrow.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.recipient.to_account_info(),
+            authority: ctx.accounts.creator_escrow.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    msg!("Creator escrow of market {} paid {} DAC", ctx.accounts.market.load()?.id, amount);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreateAnonymousMarket<'info> {
+    /// The market config (assigns the market id)
+    #[account(
+        mut,
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The market account to create
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Market::LEN,
+        seeds = [MARKET_SEED, config.market_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// YES outcome mint, controlled by the market PDA
+    #[account(
+        init,
+        payer = payer,
+        seeds = [YES_MINT_SEED, market.key().as_ref()],
+        bump,
+        mint::decimals = dac_mint.decimals,
+        mint::authority = market,
+    )]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    /// NO outcome mint, controlled by the market PDA
+    #[account(
+        init,
+        payer = payer,
+        seeds = [NO_MINT_SEED, market.key().as_ref()],
+        bump,
+        mint::decimals = dac_mint.decimals,
+        mint::authority = market,
+    )]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// DAC escrow backing the outcome tokens
+    #[account(
+        init,
+        payer = payer,
+        seeds = [ESCROW_SEED, market.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = market,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The creator escrow, recorded as the market's creator
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CreatorEscrow::LEN,
+        seeds = [CREATOR_ESCROW_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub creator_escrow: Box<Account<'info, CreatorEscrow>>,
+
+    /// DAC vault of the creator escrow
+    #[account(
+        init,
+        payer = payer,
+        seeds = [CREATOR_VAULT_SEED, market.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = creator_escrow,
+    )]
+    pub creator_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Verifying key of the claim circuit
+    #[account(
+        seeds = [VERIFYING_KEY_SEED, claim_key.circuit_id.to_le_bytes().as_ref()],
+        bump = claim_key.bump,
+        constraint = claim_key.is_complete()
+            && claim_key.ic_len as usize == CREATOR_CLAIM_PUBLIC_INPUTS + 1 @ MarketError::InvalidVerifyingKey,
+    )]
+    pub claim_key: Box<Account<'info, VerifyingKey>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == config.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    /// Payer's DAC token account (source of the bond)
+    #[account(
+        mut,
+        constraint = payer_dac.mint == dac_mint.key() @ MarketError::MintMismatch,
+    )]
+    pub payer_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorEscrow<'info> {
+    /// The anonymously created market
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        seeds = [CREATOR_ESCROW_SEED, market.key().as_ref()],
+        bump = creator_escrow.bump,
+        has_one = vault @ MarketError::MintMismatch,
+    )]
+    pub creator_escrow: Box<Account<'info, CreatorEscrow>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Verifying key of the escrow's claim circuit
+    #[account(
+        seeds = [VERIFYING_KEY_SEED, creator_escrow.claim_circuit.to_le_bytes().as_ref()],
+        bump = claim_key.bump,
+    )]
+    pub claim_key: Box<Account<'info, VerifyingKey>>,
+
+    /// DAC token account receiving the claim (bound by the proof)
+    #[account(
+        mut,
+        constraint = recipient.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub recipient: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
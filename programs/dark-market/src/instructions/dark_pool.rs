@@ -0,0 +1,402 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as ix_sysvar, get_instruction_relative,
+};
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::events::PrivateAction;
+use crate::instructions::emit_audit_memo;
+use crate::state::*;
+use crate::{
+    DARK_ORDER_SEED, DARK_POOL_SEED, DARK_POOL_VAULT_SEED, MARKET_CONFIG_SEED, MARKET_SEED,
+    VIEWING_KEY_SEED,
+};
+
+pub fn create_dark_pool(
+    ctx: Context<CreateDarkPool>,
+    arcium_program: Pubkey,
+    callback_authority: Pubkey,
+) -> Result<()> {
+    let dark_pool = &mut ctx.accounts.dark_pool;
+    dark_pool.market = ctx.accounts.market.key();
+    dark_pool.arcium_program = arcium_program;
+    dark_pool.callback_authority = callback_authority;
+    dark_pool.vault = ctx.accounts.vault.key();
+    dark_pool.order_count = 0;
+    dark_pool.bump = ctx.bumps.dark_pool;
+
+    msg!("Dark pool created for market {}", dark_pool.market);
+    Ok(())
+}
+
+/// Escrow `budget` DAC behind an order encrypted to the MPC cluster
+pub fn submit_dark_order(
+    ctx: Context<SubmitDarkOrder>,
+    encryption_key: [u8; 32],
+    nonce: [u8; 16],
+    ciphertext: [u8; DARK_ORDER_CIPHERTEXT_LEN],
+    budget: u64,
+    audit_memo: Vec<u8>,
+) -> Result<()> {
+    require!(budget > 0, MarketError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_dac.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, budget)?;
+
+    let dark_pool = &mut ctx.accounts.dark_pool;
+    let order = &mut ctx.accounts.order;
+    order.dark_pool = dark_pool.key();
+    order.owner = ctx.accounts.owner.key();
+    order.id = dark_pool.order_count;
+    order.encryption_key = encryption_key;
+    order.nonce = nonce;
+    order.ciphertext = ciphertext;
+    order.budget = budget;
+    order.spent = 0;
+    order.bump = ctx.bumps.order;
+    dark_pool.order_count = dark_pool.order_count.checked_add(1)
+        .ok_or(MarketError::Overflow)?;
+    emit_audit_memo(
+        &ctx.accounts.viewing_key,
+        order.owner,
+        PrivateAction::DarkOrder,
+        order.key(),
+        audit_memo,
+    )?;
+
+    msg!("Dark order {} submitted", order.id);
+    Ok(())
+}
+
+/// Settle one match reported by the MPC cluster (cluster callback only)
+///
+/// `size` complete sets are created: the YES order pays `yes_cost` DAC and
+/// receives `size` YES, the NO order pays the rest and receives `size` NO.
+pub fn settle_dark_fill(ctx: Context<SettleDarkFill>, size: u64, yes_cost: u64) -> Result<()> {
+    require!(size > 0 && yes_cost <= size, MarketError::InvalidDarkFill);
+    let no_cost = size - yes_cost;
+    require!(
+        yes_cost <= ctx.accounts.yes_order.remaining() && no_cost <= ctx.accounts.no_order.remaining(),
+        MarketError::InvalidDarkFill
+    );
+
+    // Callbacks arrive by CPI from the Arcium program, never top-level
+    let current = get_instruction_relative(0, &ctx.accounts.instructions.to_account_info())?;
+    require_keys_eq!(
+        current.program_id,
+        ctx.accounts.dark_pool.arcium_program,
+        MarketError::Unauthorized
+    );
+
+    let dark_pool = &ctx.accounts.dark_pool;
+    let seeds = &[DARK_POOL_SEED, dark_pool.market.as_ref(), &[dark_pool.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.escrow.to_account_info(),
+            authority: dark_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, size)?;
+
+    let (id, bump) = {
+        let market = ctx.accounts.market.load()?;
+        (market.id, market.bump)
+    };
+    let id_bytes = id.to_le_bytes();
+    let seeds = &[MARKET_SEED, id_bytes.as_ref(), &[bump]];
+    let signer_seeds = &[&seeds[..]];
+    for (mint, to) in [
+        (&ctx.accounts.yes_mint, &ctx.accounts.yes_owner_tokens),
+        (&ctx.accounts.no_mint, &ctx.accounts.no_owner_tokens),
+    ] {
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: ctx.accounts.market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::mint_to(mint_ctx, size)?;
+    }
+
+    ctx.accounts.yes_order.spent += yes_cost;
+    ctx.accounts.no_order.spent += no_cost;
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_add(size)
+        .ok_or(MarketError::Overflow)?;
+
+    msg!(
+        "Dark fill: orders {} (YES) and {} (NO) matched for {}",
+        ctx.accounts.yes_order.id,
+        ctx.accounts.no_order.id,
+        size
+    );
+    Ok(())
+}
+
+/// Withdraw a dark order and its unspent budget
+pub fn cancel_dark_order(ctx: Context<CancelDarkOrder>) -> Result<()> {
+    let refund = ctx.accounts.order.remaining();
+    if refund > 0 {
+        let dark_pool = &ctx.accounts.dark_pool;
+        let seeds = &[DARK_POOL_SEED, dark_pool.market.as_ref(), &[dark_pool.bump]];
+        let signer_seeds = &[&seeds[..]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.owner_dac.to_account_info(),
+                authority: dark_pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, refund)?;
+    }
+
+    msg!("Dark order {} cancelled, {} DAC refunded", ctx.accounts.order.id, refund);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreateDarkPool<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The market to open a dark pool for
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = escrow @ MarketError::MintMismatch,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The dark pool to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DarkPool::LEN,
+        seeds = [DARK_POOL_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub dark_pool: Box<Account<'info, DarkPool>>,
+
+    /// DAC vault escrowing order budgets
+    #[account(
+        init,
+        payer = authority,
+        seeds = [DARK_POOL_VAULT_SEED, dark_pool.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = dark_pool,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// The market's DAC escrow (identifies the DAC mint)
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitDarkOrder<'info> {
+    /// The order's market
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        constraint = market.load()?.is_trading(Clock::get()?.unix_timestamp) @ MarketError::MarketClosed,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The dark pool to submit to
+    #[account(
+        mut,
+        seeds = [DARK_POOL_SEED, market.key().as_ref()],
+        bump = dark_pool.bump,
+        has_one = vault @ MarketError::MintMismatch,
+    )]
+    pub dark_pool: Box<Account<'info, DarkPool>>,
+
+    /// The order to create
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + DarkOrder::LEN,
+        seeds = [DARK_ORDER_SEED, dark_pool.key().as_ref(), dark_pool.order_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub order: Box<Account<'info, DarkOrder>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account (source of the budget)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: the owner's viewing key PDA, empty if none is registered (read by `emit_audit_memo`)
+    #[account(seeds = [VIEWING_KEY_SEED, owner.key().as_ref()], bump)]
+    pub viewing_key: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts of the MPC cluster's matching callback
+///
+/// A callback is only accepted when invoked by the dark pool's Arcium
+/// program (checked through the instructions sysvar) and signed by the
+/// pool's callback authority, the MXE signer PDA the Arcium program signs
+/// with once the cluster has finalized a computation's output.
+#[derive(Accounts)]
+pub struct SettleDarkFill<'info> {
+    /// The market traded
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_trading(Clock::get()?.unix_timestamp) @ MarketError::MarketClosed,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The market's dark pool
+    #[account(
+        seeds = [DARK_POOL_SEED, market.key().as_ref()],
+        bump = dark_pool.bump,
+        has_one = vault @ MarketError::MintMismatch,
+        has_one = callback_authority @ MarketError::Unauthorized,
+    )]
+    pub dark_pool: Box<Account<'info, DarkPool>>,
+
+    /// The MXE's callback signer
+    pub callback_authority: Signer<'info>,
+
+    /// The order buying YES
+    #[account(
+        mut,
+        seeds = [DARK_ORDER_SEED, dark_pool.key().as_ref(), yes_order.id.to_le_bytes().as_ref()],
+        bump = yes_order.bump,
+    )]
+    pub yes_order: Box<Account<'info, DarkOrder>>,
+
+    /// The order buying NO
+    #[account(
+        mut,
+        seeds = [DARK_ORDER_SEED, dark_pool.key().as_ref(), no_order.id.to_le_bytes().as_ref()],
+        bump = no_order.bump,
+        constraint = no_order.id != yes_order.id @ MarketError::InvalidDarkFill,
+    )]
+    pub no_order: Box<Account<'info, DarkOrder>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// YES order owner's YES token account
+    #[account(
+        mut,
+        constraint = yes_owner_tokens.owner == yes_order.owner @ MarketError::Unauthorized,
+        constraint = yes_owner_tokens.mint == yes_mint.key() @ MarketError::MintMismatch,
+    )]
+    pub yes_owner_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// NO order owner's NO token account
+    #[account(
+        mut,
+        constraint = no_owner_tokens.owner == no_order.owner @ MarketError::Unauthorized,
+        constraint = no_owner_tokens.mint == no_mint.key() @ MarketError::MintMismatch,
+    )]
+    pub no_owner_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The instructions sysvar
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelDarkOrder<'info> {
+    /// The order's dark pool
+    #[account(
+        seeds = [DARK_POOL_SEED, dark_pool.market.as_ref()],
+        bump = dark_pool.bump,
+        has_one = vault @ MarketError::MintMismatch,
+    )]
+    pub dark_pool: Box<Account<'info, DarkPool>>,
+
+    /// The order to cancel (closed)
+    #[account(
+        mut,
+        close = owner,
+        seeds = [DARK_ORDER_SEED, dark_pool.key().as_ref(), order.id.to_le_bytes().as_ref()],
+        bump = order.bump,
+        has_one = owner @ MarketError::Unauthorized,
+    )]
+    pub order: Box<Account<'info, DarkOrder>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account (receives the refund)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
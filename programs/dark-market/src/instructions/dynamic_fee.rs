@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{MARKET_CONFIG_SEED, POOL_SEED};
+
+pub fn set_dynamic_fee(
+    ctx: Context<SetDynamicFee>,
+    window: i64,
+    multiplier_bps: u32,
+    max_fee_bps: u16,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    require!(
+        window >= 0
+            && multiplier_bps <= MAX_FEE_MULTIPLIER_BPS
+            && (pool.fee_bps..=MAX_POOL_FEE_BPS).contains(&max_fee_bps),
+        MarketError::InvalidDynamicFee
+    );
+
+    pool.dynamic_fee = DynamicFee {
+        window,
+        multiplier_bps,
+        max_fee_bps,
+        surcharge_bps: 0,
+    };
+
+    msg!(
+        "Dynamic fee set: {} bps per unit of volatility over {}s, capped at {} bps",
+        multiplier_bps,
+        window,
+        max_fee_bps
+    );
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetDynamicFee<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [POOL_SEED, pool.market.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub authority: Signer<'info>,
+}
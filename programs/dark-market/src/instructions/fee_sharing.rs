@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{FEE_SHARING_SEED, MARKET_CONFIG_SEED};
+
+/// Set the stakers' share of swap fees and the vault receiving it (authority only)
+pub fn configure_fee_sharing(
+    ctx: Context<ConfigureFeeSharing>,
+    fee_vault: Pubkey,
+    share_bps: u16,
+) -> Result<()> {
+    require!(share_bps as u64 <= BPS_DENOMINATOR, MarketError::InvalidFeeShare);
+
+    let fee_sharing = &mut ctx.accounts.fee_sharing;
+    fee_sharing.fee_vault = fee_vault;
+    fee_sharing.share_bps = share_bps;
+    fee_sharing.bump = ctx.bumps.fee_sharing;
+
+    msg!("Stakers receive {} bps of swap fees in {}", share_bps, fee_vault);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ConfigureFeeSharing<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The fee sharing setting (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeeSharing::LEN,
+        seeds = [FEE_SHARING_SEED],
+        bump
+    )]
+    pub fee_sharing: Box<Account<'info, FeeSharing>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
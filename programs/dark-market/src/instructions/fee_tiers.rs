@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{FEE_TIERS_SEED, MARKET_CONFIG_SEED};
+
+/// Set the staking program and the tiers (authority only)
+///
+/// The staking program must already be deployed, so the tiers never point
+/// trades at stake accounts of a layout that does not exist.
+pub fn set_fee_tiers(ctx: Context<SetFeeTiers>, tiers: [FeeTier; FEE_TIER_COUNT]) -> Result<()> {
+    require!(FeeTiers::is_valid(&tiers), MarketError::InvalidFeeTiers);
+
+    let staking_program = ctx.accounts.staking_program.key();
+    let fee_tiers = &mut ctx.accounts.fee_tiers;
+    fee_tiers.staking_program = staking_program;
+    fee_tiers.tiers = tiers;
+    fee_tiers.bump = ctx.bumps.fee_tiers;
+
+    msg!("Fee tiers updated, staking program {}", staking_program);
+    Ok(())
+}
+
+/// Swap fee discount for `trader`, or none unless both the tiers and a stake account are passed
+///
+/// Only stake still locked counts, so a discount cannot be flash-staked
+/// around a trade.
+pub(crate) fn fee_discount(
+    fee_tiers: Option<&Account<'_, FeeTiers>>,
+    stake: Option<&AccountInfo>,
+    trader: &Pubkey,
+) -> Result<u16> {
+    let (Some(fee_tiers), Some(stake)) = (fee_tiers, stake) else {
+        return Ok(0);
+    };
+    let view = StakeView::read(stake, &fee_tiers.staking_program)
+        .ok_or(MarketError::InvalidStakeAccount)?;
+    require_keys_eq!(view.staker, *trader, MarketError::InvalidStakeAccount);
+    if !view.is_locked(Clock::get()?.unix_timestamp) {
+        return Ok(0);
+    }
+    Ok(fee_tiers.discount_bps(view.amount))
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The fee tiers (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeeTiers::LEN,
+        seeds = [FEE_TIERS_SEED],
+        bump
+    )]
+    pub fee_tiers: Account<'info, FeeTiers>,
+
+    /// CHECK: The deployed staking program owning the stake accounts trades read
+    #[account(constraint = staking_program.executable @ MarketError::InvalidStakingProgram)]
+    pub staking_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,417 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{
+    GAUGE_CONTROLLER_SEED, GAUGE_LP_VAULT_SEED, GAUGE_REWARD_VAULT_SEED, GAUGE_SEED,
+    GAUGE_STAKE_SEED, MARKET_CONFIG_SEED, POOL_SEED,
+};
+
+/// Set the liquidity mining emission rate, creating the controller on first use (authority only)
+pub fn configure_gauges(ctx: Context<ConfigureGauges>, emission_rate: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let controller = &mut ctx.accounts.controller;
+    if controller.dac_mint == Pubkey::default() {
+        controller.dac_mint = ctx.accounts.dac_mint.key();
+        controller.reward_vault = ctx.accounts.reward_vault.key();
+        controller.total_weight = 0;
+        controller.reward_per_weight = 0;
+        controller.last_update = now;
+        controller.bump = ctx.bumps.controller;
+    }
+    controller.update(now).ok_or(MarketError::Overflow)?;
+    controller.emission_rate = emission_rate;
+
+    msg!("Gauge emissions set to {} DAC/s", emission_rate);
+    Ok(())
+}
+
+/// Set a pool's share of the emissions, creating its gauge on first use (authority only)
+pub fn set_gauge_weight(ctx: Context<SetGaugeWeight>, weight: u64) -> Result<()> {
+    let controller = &mut ctx.accounts.controller;
+    controller.update(Clock::get()?.unix_timestamp).ok_or(MarketError::Overflow)?;
+
+    let gauge = &mut ctx.accounts.gauge;
+    if gauge.pool == Pubkey::default() {
+        gauge.pool = ctx.accounts.pool.key();
+        gauge.lp_vault = ctx.accounts.lp_vault.key();
+        gauge.weight = 0;
+        gauge.weight_index = controller.reward_per_weight;
+        gauge.reward_per_share = 0;
+        gauge.total_staked = 0;
+        gauge.bump = ctx.bumps.gauge;
+    }
+    gauge.update(controller.reward_per_weight).ok_or(MarketError::Overflow)?;
+    controller.total_weight = (controller.total_weight - gauge.weight)
+        .checked_add(weight)
+        .ok_or(MarketError::Overflow)?;
+    gauge.weight = weight;
+
+    msg!("Gauge of pool {} weighted {} of {}", gauge.pool, weight, controller.total_weight);
+    Ok(())
+}
+
+/// Add DAC to the liquidity mining reward vault (anyone)
+pub fn fund_gauges(ctx: Context<FundGauges>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.funder_dac.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    msg!("Gauge reward vault funded with {} DAC", amount);
+    Ok(())
+}
+
+pub fn deposit_lp(ctx: Context<DepositLp>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_lp.to_account_info(),
+            to: ctx.accounts.lp_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let accounts = &mut ctx.accounts;
+    let stake = &mut accounts.stake;
+    if stake.owner == Pubkey::default() {
+        stake.gauge = accounts.gauge.key();
+        stake.owner = accounts.owner.key();
+        stake.amount = 0;
+        stake.reward_debt = accounts.gauge.reward_per_share;
+        stake.pending_rewards = 0;
+        stake.bump = ctx.bumps.stake;
+    }
+    sync_gauge(&mut accounts.controller, &mut accounts.gauge, stake)?;
+    stake.amount = stake.amount.checked_add(amount).ok_or(MarketError::Overflow)?;
+    accounts.gauge.total_staked = accounts.gauge.total_staked.checked_add(amount)
+        .ok_or(MarketError::Overflow)?;
+
+    msg!("Deposited {} LP shares in the gauge", amount);
+    Ok(())
+}
+
+pub fn withdraw_lp(ctx: Context<WithdrawLp>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+    require!(amount <= ctx.accounts.stake.amount, MarketError::InsufficientGaugeStake);
+
+    let accounts = &mut ctx.accounts;
+    sync_gauge(&mut accounts.controller, &mut accounts.gauge, &mut accounts.stake)?;
+    accounts.stake.amount -= amount;
+    accounts.gauge.total_staked -= amount;
+
+    let gauge = &accounts.gauge;
+    let seeds = &[GAUGE_SEED, gauge.pool.as_ref(), &[gauge.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        Transfer {
+            from: accounts.lp_vault.to_account_info(),
+            to: accounts.owner_lp.to_account_info(),
+            authority: gauge.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    msg!("Withdrew {} LP shares from the gauge", amount);
+    Ok(())
+}
+
+/// Pay out a deposit's liquidity mining rewards, as far as the reward vault covers them
+pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let accounts = &mut ctx.accounts;
+    sync_gauge(&mut accounts.controller, &mut accounts.gauge, &mut accounts.stake)?;
+
+    let payout = accounts.stake.pending_rewards.min(accounts.reward_vault.amount);
+    require!(payout > 0, MarketError::ZeroAmount);
+    accounts.stake.pending_rewards -= payout;
+
+    let controller = &accounts.controller;
+    let seeds = &[GAUGE_CONTROLLER_SEED, &[controller.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        Transfer {
+            from: accounts.reward_vault.to_account_info(),
+            to: accounts.owner_dac.to_account_info(),
+            authority: controller.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, payout)?;
+
+    msg!("Claimed {} DAC of liquidity mining rewards", payout);
+    Ok(())
+}
+
+/// Bring the controller, the gauge and the deposit up to date
+fn sync_gauge(
+    controller: &mut GaugeController,
+    gauge: &mut Gauge,
+    stake: &mut GaugeStake,
+) -> Result<()> {
+    controller.update(Clock::get()?.unix_timestamp).ok_or(MarketError::Overflow)?;
+    gauge.update(controller.reward_per_weight).ok_or(MarketError::Overflow)?;
+    stake.settle(gauge.reward_per_share).ok_or(MarketError::Overflow)?;
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ConfigureGauges<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+        has_one = dac_mint @ MarketError::MintMismatch,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The gauge controller (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GaugeController::LEN,
+        seeds = [GAUGE_CONTROLLER_SEED],
+        bump
+    )]
+    pub controller: Box<Account<'info, GaugeController>>,
+
+    /// DAC vault paying the rewards (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [GAUGE_REWARD_VAULT_SEED],
+        bump,
+        token::mint = dac_mint,
+        token::authority = controller,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetGaugeWeight<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [GAUGE_CONTROLLER_SEED],
+        bump = controller.bump,
+    )]
+    pub controller: Box<Account<'info, GaugeController>>,
+
+    /// The pool to incentivize
+    #[account(
+        seeds = [POOL_SEED, pool.market.as_ref()],
+        bump = pool.bump,
+        has_one = lp_mint @ MarketError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The pool's gauge (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Gauge::LEN,
+        seeds = [GAUGE_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub gauge: Box<Account<'info, Gauge>>,
+
+    /// Vault for the deposited LP tokens (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [GAUGE_LP_VAULT_SEED, gauge.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = gauge,
+    )]
+    pub lp_vault: Box<Account<'info, TokenAccount>>,
+
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct FundGauges<'info> {
+    #[account(
+        seeds = [GAUGE_CONTROLLER_SEED],
+        bump = controller.bump,
+        has_one = reward_vault @ MarketError::MintMismatch,
+    )]
+    pub controller: Box<Account<'info, GaugeController>>,
+
+    #[account(mut)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Funder's DAC token account
+    #[account(
+        mut,
+        constraint = funder_dac.mint == controller.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub funder_dac: Box<Account<'info, TokenAccount>>,
+
+    pub funder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DepositLp<'info> {
+    #[account(
+        mut,
+        seeds = [GAUGE_CONTROLLER_SEED],
+        bump = controller.bump,
+    )]
+    pub controller: Box<Account<'info, GaugeController>>,
+
+    #[account(
+        mut,
+        seeds = [GAUGE_SEED, gauge.pool.as_ref()],
+        bump = gauge.bump,
+        has_one = lp_vault @ MarketError::MintMismatch,
+    )]
+    pub gauge: Box<Account<'info, Gauge>>,
+
+    /// The owner's deposit (created on first use)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + GaugeStake::LEN,
+        seeds = [GAUGE_STAKE_SEED, gauge.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub stake: Box<Account<'info, GaugeStake>>,
+
+    #[account(mut)]
+    pub lp_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's LP token account (source)
+    #[account(
+        mut,
+        constraint = owner_lp.mint == lp_vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_lp: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawLp<'info> {
+    #[account(
+        mut,
+        seeds = [GAUGE_CONTROLLER_SEED],
+        bump = controller.bump,
+    )]
+    pub controller: Box<Account<'info, GaugeController>>,
+
+    #[account(
+        mut,
+        seeds = [GAUGE_SEED, gauge.pool.as_ref()],
+        bump = gauge.bump,
+        has_one = lp_vault @ MarketError::MintMismatch,
+    )]
+    pub gauge: Box<Account<'info, Gauge>>,
+
+    #[account(
+        mut,
+        seeds = [GAUGE_STAKE_SEED, gauge.key().as_ref(), owner.key().as_ref()],
+        bump = stake.bump,
+        has_one = owner @ MarketError::Unauthorized,
+    )]
+    pub stake: Box<Account<'info, GaugeStake>>,
+
+    #[account(mut)]
+    pub lp_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's LP token account (destination)
+    #[account(
+        mut,
+        constraint = owner_lp.mint == lp_vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_lp: Box<Account<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        seeds = [GAUGE_CONTROLLER_SEED],
+        bump = controller.bump,
+        has_one = reward_vault @ MarketError::MintMismatch,
+    )]
+    pub controller: Box<Account<'info, GaugeController>>,
+
+    #[account(
+        mut,
+        seeds = [GAUGE_SEED, gauge.pool.as_ref()],
+        bump = gauge.bump,
+    )]
+    pub gauge: Box<Account<'info, Gauge>>,
+
+    #[account(
+        mut,
+        seeds = [GAUGE_STAKE_SEED, gauge.key().as_ref(), owner.key().as_ref()],
+        bump = stake.bump,
+        has_one = owner @ MarketError::Unauthorized,
+    )]
+    pub stake: Box<Account<'info, GaugeStake>>,
+
+    #[account(mut)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account (destination)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == controller.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
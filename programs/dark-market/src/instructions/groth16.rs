@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{MARKET_CONFIG_SEED, VERIFYING_KEY_SEED};
+
+pub fn register_verifying_key(
+    ctx: Context<RegisterVerifyingKey>,
+    circuit_id: u32,
+    alpha_g1: [u8; 64],
+    beta_g2: [u8; 128],
+    gamma_g2: [u8; 128],
+    delta_g2: [u8; 128],
+    public_inputs: u8,
+) -> Result<()> {
+    require!(
+        public_inputs as usize <= MAX_PUBLIC_INPUTS,
+        MarketError::InvalidVerifyingKey
+    );
+
+    let key = &mut ctx.accounts.verifying_key;
+    key.circuit_id = circuit_id;
+    key.alpha_g1 = alpha_g1;
+    key.beta_g2 = beta_g2;
+    key.gamma_g2 = gamma_g2;
+    key.delta_g2 = delta_g2;
+    key.ic_len = public_inputs + 1;
+    key.ic = Vec::new();
+    key.bump = ctx.bumps.verifying_key;
+
+    msg!("Verifying key registered for circuit {} ({} public inputs)", circuit_id, public_inputs);
+    Ok(())
+}
+
+/// Append `ic` points to a registered verifying key until it is complete (authority only)
+pub fn append_verifying_key_ic(ctx: Context<AppendVerifyingKeyIc>, points: Vec<[u8; 64]>) -> Result<()> {
+    let key = &mut ctx.accounts.verifying_key;
+    require!(
+        key.ic.len() + points.len() <= key.ic_len as usize,
+        MarketError::InvalidVerifyingKey
+    );
+    key.ic.extend(points);
+
+    msg!("Circuit {} verifying key: {}/{} ic points", key.circuit_id, key.ic.len(), key.ic_len);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(circuit_id: u32)]
+pub struct RegisterVerifyingKey<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The verifying key to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VerifyingKey::LEN,
+        seeds = [VERIFYING_KEY_SEED, circuit_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKey>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AppendVerifyingKeyIc<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The incomplete verifying key
+    #[account(
+        mut,
+        seeds = [VERIFYING_KEY_SEED, verifying_key.circuit_id.to_le_bytes().as_ref()],
+        bump = verifying_key.bump,
+        constraint = !verifying_key.is_complete() @ MarketError::InvalidVerifyingKey,
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKey>>,
+
+    pub authority: Signer<'info>,
+}
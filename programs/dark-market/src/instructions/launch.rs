@@ -0,0 +1,550 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, CloseAccount, Mint, MintTo, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::instructions::{
+    check_expiry, impl_pool_vaults, mint_lp_shares, open_pool, pool_fee_bps, record_trade,
+    PoolVaults,
+};
+use crate::state::*;
+use crate::{
+    LAUNCH_NO_SEED, LAUNCH_SEED, LAUNCH_VAULT_SEED, LAUNCH_YES_SEED, LP_MINT_SEED, MARKET_SEED,
+    OBSERVATIONS_SEED, POOL_NO_SEED, POOL_SEED, POOL_YES_SEED, PORTFOLIO_SEED,
+};
+
+pub fn create_launch(
+    ctx: Context<CreateLaunch>,
+    start_price: u64,
+    slope: u64,
+    end_time: i64,
+    fee_bps: u16,
+    seed: u64,
+) -> Result<()> {
+    require!(
+        start_price > 0 && start_price < PRICE_SCALE && slope > 0,
+        MarketError::InvalidLaunchCurve
+    );
+    require!(
+        end_time > Clock::get()?.unix_timestamp && end_time < ctx.accounts.market.load()?.end_time,
+        MarketError::InvalidEndTime
+    );
+    require!(fee_bps <= MAX_POOL_FEE_BPS, MarketError::FeeTooHigh);
+    let fee_bps = pool_fee_bps(ctx.accounts.series.as_deref(), &ctx.accounts.market.load()?, fee_bps)?;
+
+    let launch = &mut ctx.accounts.launch;
+    launch.market = ctx.accounts.market.key();
+    launch.creator = ctx.accounts.creator.key();
+    launch.vault = ctx.accounts.vault.key();
+    launch.yes_inventory = ctx.accounts.yes_inventory.key();
+    launch.no_inventory = ctx.accounts.no_inventory.key();
+    launch.start_price = start_price;
+    launch.slope = slope;
+    launch.yes_sold = 0;
+    launch.no_sold = 0;
+    launch.minted = 0;
+    launch.end_time = end_time;
+    launch.fee_bps = fee_bps;
+    launch.bump = ctx.bumps.launch;
+
+    // The seed must cover the discount below one DAC on either curve
+    require!(seed >= launch.subsidy_cap(), MarketError::LaunchSeedTooLow);
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.creator_dac.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.creator.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, seed)?;
+
+    msg!(
+        "Launch opened for market {}: start {} slope {}, seeded {} DAC",
+        ctx.accounts.market.load()?.id,
+        start_price,
+        slope,
+        seed
+    );
+    Ok(())
+}
+
+pub fn buy_launch(
+    ctx: Context<BuyLaunch>,
+    outcome: Outcome,
+    amount: u64,
+    max_cost: u64,
+    expiry_slot: u64,
+) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+    check_expiry(expiry_slot)?;
+
+    let cost = ctx.accounts.launch.quote(outcome, amount)
+        .ok_or(MarketError::Overflow)?;
+    require!(cost <= max_cost, MarketError::SlippageExceeded);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_dac.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, cost)?;
+
+    let new_sets = ctx.accounts.launch.apply_sale(outcome, amount)
+        .ok_or(MarketError::Overflow)?;
+
+    let launch = &ctx.accounts.launch;
+    let seeds = &[LAUNCH_SEED, launch.market.as_ref(), &[launch.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    if new_sets > 0 {
+        // The seed guarantees the vault holds enough DAC to back every set
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: launch.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, new_sets)?;
+
+        let (id, bump) = {
+            let market = ctx.accounts.market.load()?;
+            (market.id, market.bump)
+        };
+        let id_bytes = id.to_le_bytes();
+        let market_seeds = &[MARKET_SEED, id_bytes.as_ref(), &[bump]];
+        let market_signer = &[&market_seeds[..]];
+
+        for (mint, to) in [
+            (&ctx.accounts.yes_mint, &ctx.accounts.yes_inventory),
+            (&ctx.accounts.no_mint, &ctx.accounts.no_inventory),
+        ] {
+            let mint_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: ctx.accounts.market.to_account_info(),
+                },
+                market_signer,
+            );
+            token::mint_to(mint_ctx, new_sets)?;
+        }
+    }
+
+    let inventory = match outcome {
+        Outcome::No => &ctx.accounts.no_inventory,
+        _ => &ctx.accounts.yes_inventory,
+    };
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: inventory.to_account_info(),
+            to: ctx.accounts.user_outcome.to_account_info(),
+            authority: launch.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_add(new_sets)
+        .ok_or(MarketError::Overflow)?;
+    let market_key = ctx.accounts.market.key();
+    record_trade(ctx.accounts.portfolio.as_mut(), |p| {
+        p.record_buy(market_key, outcome, amount, cost)
+    })?;
+
+    msg!("Launch sold {} {:?} for {} DAC", amount, outcome, cost);
+    Ok(())
+}
+
+/// Open the market's pool from a launch that has ended (permissionless)
+///
+/// The unsold inventory and the leftover DAC, split into YES+NO, become the
+/// reserves. The creator receives the LP shares and the launch accounts' rent.
+pub fn finalize_launch(ctx: Context<FinalizeLaunch>) -> Result<()> {
+    let launch = &ctx.accounts.launch;
+    let leftover = ctx.accounts.vault.amount;
+    let yes_reserve = (launch.minted - launch.yes_sold).checked_add(leftover)
+        .ok_or(MarketError::Overflow)?;
+    let no_reserve = (launch.minted - launch.no_sold).checked_add(leftover)
+        .ok_or(MarketError::Overflow)?;
+    require!(yes_reserve > 0 && no_reserve > 0, MarketError::ZeroAmount);
+
+    let pool = &mut ctx.accounts.pool;
+    pool.market = ctx.accounts.market.key();
+    pool.yes_vault = ctx.accounts.yes_vault.key();
+    pool.no_vault = ctx.accounts.no_vault.key();
+    pool.lp_mint = ctx.accounts.lp_mint.key();
+    pool.bump = ctx.bumps.pool;
+    let observations = &mut ctx.accounts.observations.load_init()?;
+    observations.bump = ctx.bumps.observations;
+    open_pool(
+        &mut ctx.accounts.pool,
+        observations,
+        ctx.accounts.launch.fee_bps,
+        yes_reserve,
+        no_reserve,
+    )?;
+
+    let accounts = &ctx.accounts;
+    let launch = &accounts.launch;
+    let seeds = &[LAUNCH_SEED, launch.market.as_ref(), &[launch.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let launch_info = launch.to_account_info();
+
+    let vaults = accounts.vaults();
+    for (outcome, inventory) in [
+        (Outcome::Yes, &accounts.yes_inventory),
+        (Outcome::No, &accounts.no_inventory),
+    ] {
+        if inventory.amount > 0 {
+            vaults.deposit_outcome(
+                outcome,
+                &inventory.to_account_info(),
+                &launch_info,
+                signer_seeds,
+                inventory.amount,
+            )?;
+        }
+    }
+    if leftover > 0 {
+        vaults.deposit_dac(&accounts.vault.to_account_info(), &launch_info, signer_seeds, leftover)?;
+    }
+    mint_lp_shares(
+        &accounts.pool,
+        &accounts.lp_mint,
+        &accounts.creator_lp,
+        &accounts.token_program,
+        yes_reserve.max(no_reserve),
+    )?;
+
+    for account in [&accounts.vault, &accounts.yes_inventory, &accounts.no_inventory] {
+        let close_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: account.to_account_info(),
+                destination: accounts.creator.to_account_info(),
+                authority: launch_info.clone(),
+            },
+            signer_seeds,
+        );
+        token::close_account(close_ctx)?;
+    }
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_add(leftover)
+        .ok_or(MarketError::Overflow)?;
+
+    msg!(
+        "Launch finalized for market {}: pool opened with {} YES / {} NO",
+        market.id,
+        yes_reserve,
+        no_reserve
+    );
+    Ok(())
+}
+
+impl_pool_vaults!(FinalizeLaunch);
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreateLaunch<'info> {
+    /// The market to launch
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = creator @ MarketError::Unauthorized,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The series that opened the market, if any (its fee overrides `fee_bps`)
+    pub series: Option<Box<Account<'info, MarketSeries>>>,
+
+    /// CHECK: The market's pool PDA, which must not exist yet
+    #[account(
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump,
+        constraint = pool.data_is_empty() @ MarketError::PoolAlreadyOpen,
+    )]
+    pub pool: UncheckedAccount<'info>,
+
+    /// The launch account to create
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Launch::LEN,
+        seeds = [LAUNCH_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub launch: Box<Account<'info, Launch>>,
+
+    /// DAC vault holding the seed and sale proceeds
+    #[account(
+        init,
+        payer = creator,
+        seeds = [LAUNCH_VAULT_SEED, launch.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = launch,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Inventory of minted YES not yet sold
+    #[account(
+        init,
+        payer = creator,
+        seeds = [LAUNCH_YES_SEED, launch.key().as_ref()],
+        bump,
+        token::mint = yes_mint,
+        token::authority = launch,
+    )]
+    pub yes_inventory: Box<Account<'info, TokenAccount>>,
+
+    /// Inventory of minted NO not yet sold
+    #[account(
+        init,
+        payer = creator,
+        seeds = [LAUNCH_NO_SEED, launch.key().as_ref()],
+        bump,
+        token::mint = no_mint,
+        token::authority = launch,
+    )]
+    pub no_inventory: Box<Account<'info, TokenAccount>>,
+
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow (identifies the DAC mint)
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    /// Creator's DAC token account (source of the seed)
+    #[account(
+        mut,
+        constraint = creator_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub creator_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome: Outcome)]
+pub struct BuyLaunch<'info> {
+    /// The market being launched
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The launch to buy from
+    #[account(
+        mut,
+        seeds = [LAUNCH_SEED, market.key().as_ref()],
+        bump = launch.bump,
+        has_one = vault @ MarketError::MintMismatch,
+        has_one = yes_inventory @ MarketError::MintMismatch,
+        has_one = no_inventory @ MarketError::MintMismatch,
+        constraint = Clock::get()?.unix_timestamp < launch.end_time @ MarketError::LaunchEnded,
+    )]
+    pub launch: Box<Account<'info, Launch>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub yes_inventory: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub no_inventory: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// User's DAC token account (source)
+    #[account(
+        mut,
+        constraint = user_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub user_dac: Box<Account<'info, TokenAccount>>,
+
+    /// User's token account for the bought outcome
+    #[account(
+        mut,
+        constraint = market.load()?.outcome_mint(outcome) == Some(user_outcome.mint) @ MarketError::MintMismatch,
+    )]
+    pub user_outcome: Box<Account<'info, TokenAccount>>,
+
+    /// The user's portfolio, updated when passed
+    #[account(
+        mut,
+        seeds = [PORTFOLIO_SEED, user.key().as_ref()],
+        bump = portfolio.bump,
+    )]
+    pub portfolio: Option<Box<Account<'info, Portfolio>>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeLaunch<'info> {
+    /// The launched market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The ended launch (closed)
+    #[account(
+        mut,
+        close = creator,
+        seeds = [LAUNCH_SEED, market.key().as_ref()],
+        bump = launch.bump,
+        has_one = creator @ MarketError::Unauthorized,
+        has_one = vault @ MarketError::MintMismatch,
+        has_one = yes_inventory @ MarketError::MintMismatch,
+        has_one = no_inventory @ MarketError::MintMismatch,
+        constraint = Clock::get()?.unix_timestamp >= launch.end_time @ MarketError::LaunchActive,
+    )]
+    pub launch: Box<Account<'info, Launch>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub yes_inventory: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub no_inventory: Box<Account<'info, TokenAccount>>,
+
+    /// The pool account to create
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Pool::LEN,
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The pool's price observation buffer
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PoolObservations::LEN,
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub observations: AccountLoader<'info, PoolObservations>,
+
+    /// Vault holding the YES reserve
+    #[account(
+        init,
+        payer = payer,
+        seeds = [POOL_YES_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = yes_mint,
+        token::authority = pool,
+    )]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Vault holding the NO reserve
+    #[account(
+        init,
+        payer = payer,
+        seeds = [POOL_NO_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = no_mint,
+        token::authority = pool,
+    )]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    /// LP share mint
+    #[account(
+        init,
+        payer = payer,
+        seeds = [LP_MINT_SEED, pool.key().as_ref()],
+        bump,
+        mint::decimals = yes_mint.decimals,
+        mint::authority = pool,
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Launch creator, receives the LP shares and the reclaimed rent
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+
+    /// Creator's LP token account
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_lp: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
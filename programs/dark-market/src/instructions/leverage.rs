@@ -0,0 +1,598 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::errors::MarketError;
+use crate::instructions::{
+    check_expiry, impl_pool_vaults, pay_from_margin_vault, track_price_move, PoolVaults,
+};
+use crate::state::*;
+use crate::{
+    INSURANCE_FUND_SEED, LEVERAGED_POSITION_SEED, LEVERAGE_PARAMS_SEED, MARGIN_ACCOUNT_SEED,
+    MARGIN_CUSTODY_SEED, MARGIN_VAULT_SEED, MARKET_CONFIG_SEED, MARKET_SEED, OBSERVATIONS_SEED,
+    POOL_SEED,
+};
+
+pub fn set_leverage_params(
+    ctx: Context<SetLeverageParams>,
+    max_leverage_bps: u32,
+    funding_rate_bps: u16,
+    delever_window: i64,
+) -> Result<()> {
+    require!(
+        max_leverage_bps == 0
+            || (BPS_DENOMINATOR as u32..=MAX_LEVERAGE_BPS).contains(&max_leverage_bps),
+        MarketError::InvalidLeverageParams
+    );
+    require!(funding_rate_bps <= MAX_FUNDING_RATE_BPS, MarketError::InvalidLeverageParams);
+    require!(delever_window >= 0, MarketError::InvalidLeverageParams);
+
+    let params = &mut ctx.accounts.leverage_params;
+    params.market = ctx.accounts.market.key();
+    params.max_leverage_bps = max_leverage_bps;
+    params.funding_rate_bps = funding_rate_bps;
+    params.delever_window = delever_window;
+    params.bump = ctx.bumps.leverage_params;
+
+    msg!(
+        "Market {} leverage: max {} bps, funding {} bps/yr, delever window {}s",
+        ctx.accounts.market.load()?.id,
+        max_leverage_bps,
+        funding_rate_bps,
+        delever_window
+    );
+    Ok(())
+}
+
+/// Buy `outcome` with `margin` DAC of collateral plus DAC borrowed from the margin vault
+pub fn open_leveraged(
+    ctx: Context<OpenLeveraged>,
+    outcome: Outcome,
+    margin: u64,
+    leverage_bps: u32,
+    min_amount_out: u64,
+    expiry_slot: u64,
+) -> Result<()> {
+    require!(margin > 0, MarketError::ZeroAmount);
+    check_expiry(expiry_slot)?;
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.pool.refresh_fee(&ctx.accounts.observations.load()?, now);
+    let accounts = &ctx.accounts;
+    let params = &accounts.leverage_params;
+    require!(
+        leverage_bps as u64 >= BPS_DENOMINATOR && leverage_bps <= params.max_leverage_bps,
+        MarketError::InvalidLeverage
+    );
+    require!(
+        now < params.delever_start(accounts.market.load()?.end_time),
+        MarketError::DeleverWindow
+    );
+
+    let notional = u64::try_from(margin as u128 * leverage_bps as u128 / BPS_DENOMINATOR as u128)
+        .map_err(|_| MarketError::Overflow)?;
+    let borrowed = notional - margin;
+    let funding = accounts.leveraged_position.accrued_funding(now, params.funding_rate_bps);
+    let out = accounts.pool.quote_buy(outcome, notional, 0)
+        .ok_or(MarketError::Overflow)?;
+    require!(out > 0, MarketError::ZeroAmount);
+    require!(out >= min_amount_out, MarketError::SlippageExceeded);
+
+    if funding > 0 {
+        pay_from_margin_vault(
+            &accounts.config,
+            &accounts.vault,
+            &accounts.insurance_fund,
+            &accounts.token_program,
+            funding,
+        )?;
+    }
+
+    // The config PDA signs for the margin vault
+    let seeds = &[MARKET_CONFIG_SEED, &[accounts.config.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let vaults = accounts.vaults();
+    vaults.deposit_dac(
+        &accounts.vault.to_account_info(),
+        &accounts.config.to_account_info(),
+        signer_seeds,
+        notional,
+    )?;
+    vaults.withdraw_outcome(outcome, &accounts.custody, out)?;
+
+    let price_before = ctx.accounts.pool.price(Outcome::Yes);
+    ctx.accounts.pool.apply_buy(outcome, notional, out)
+        .ok_or(MarketError::Overflow)?;
+    track_price_move(&mut ctx.accounts.pool, &mut ctx.accounts.observations.load_mut()?, price_before)?;
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_add(notional)
+        .ok_or(MarketError::Overflow)?;
+    let market_key = ctx.accounts.market.key();
+
+    let margin_account = &mut ctx.accounts.margin_account;
+    margin_account.debit(funding).ok_or(MarketError::Overflow)?;
+    margin_account.collateral = margin_account.collateral.checked_sub(margin)
+        .ok_or(MarketError::InsufficientCollateral)?;
+    margin_account.debt = margin_account.debt.checked_add(borrowed)
+        .ok_or(MarketError::Overflow)?;
+    let position = margin_account.position_mut(market_key)
+        .ok_or(MarketError::TooManyMarginPositions)?;
+    let held = position.amount_mut(outcome);
+    *held = held.checked_add(out).ok_or(MarketError::Overflow)?;
+    require!(margin_account.is_healthy(), MarketError::MarginUnhealthy);
+
+    let leveraged = &mut ctx.accounts.leveraged_position;
+    leveraged.margin_account = margin_account.key();
+    leveraged.market = market_key;
+    leveraged.outcome = outcome;
+    leveraged.size = leveraged.size.checked_add(out).ok_or(MarketError::Overflow)?;
+    leveraged.borrowed = leveraged.borrowed.checked_add(borrowed)
+        .ok_or(MarketError::Overflow)?;
+    leveraged.funding_paid = leveraged.funding_paid.checked_add(funding)
+        .ok_or(MarketError::Overflow)?;
+    leveraged.last_funding = now;
+    leveraged.bump = ctx.bumps.leveraged_position;
+
+    msg!(
+        "Leveraged {:?}: {} DAC at {} bps bought {} (borrowed {})",
+        outcome,
+        margin,
+        leverage_bps,
+        out,
+        borrowed
+    );
+    Ok(())
+}
+
+/// Sell a leveraged position back to the pool and repay the margin account's debt
+pub fn close_leveraged(
+    ctx: Context<UnwindLeveraged>,
+    min_amount_out: u64,
+    expiry_slot: u64,
+) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.caller.key(),
+        ctx.accounts.margin_account.owner,
+        MarketError::Unauthorized
+    );
+    check_expiry(expiry_slot)?;
+    unwind_leveraged(ctx, min_amount_out)
+}
+
+/// Unwind a leveraged position once its market enters the delever window (permissionless)
+pub fn force_delever(ctx: Context<UnwindLeveraged>) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp
+            >= ctx.accounts.leverage_params.delever_start(ctx.accounts.market.load()?.end_time),
+        MarketError::DeleverNotStarted
+    );
+    unwind_leveraged(ctx, 0)
+}
+
+/// Charge a leveraged position's accrued funding to its margin account (permissionless)
+pub fn accrue_funding(ctx: Context<AccrueFunding>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let funding = ctx.accounts.leveraged_position
+        .accrued_funding(now, ctx.accounts.leverage_params.funding_rate_bps);
+    if funding > 0 {
+        pay_from_margin_vault(
+            &ctx.accounts.config,
+            &ctx.accounts.vault,
+            &ctx.accounts.insurance_fund,
+            &ctx.accounts.token_program,
+            funding,
+        )?;
+    }
+
+    ctx.accounts.margin_account.debit(funding).ok_or(MarketError::Overflow)?;
+    let leveraged = &mut ctx.accounts.leveraged_position;
+    leveraged.funding_paid = leveraged.funding_paid.checked_add(funding)
+        .ok_or(MarketError::Overflow)?;
+    leveraged.last_funding = now;
+
+    msg!("Funding charged: {} DAC (total {})", funding, leveraged.funding_paid);
+    Ok(())
+}
+
+fn unwind_leveraged(ctx: Context<UnwindLeveraged>, min_amount_out: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.pool.refresh_fee(&ctx.accounts.observations.load()?, now);
+    let accounts = &ctx.accounts;
+    let leveraged = &accounts.leveraged_position;
+    let outcome = leveraged.outcome;
+    // Liquidations may already have seized part of the position
+    let size = leveraged.size.min(accounts.custody.amount);
+    let funding = leveraged.accrued_funding(now, accounts.leverage_params.funding_rate_bps);
+    let out = accounts.pool.quote_sell(outcome, size, 0)
+        .ok_or(MarketError::Overflow)?;
+    require!(out >= min_amount_out, MarketError::SlippageExceeded);
+
+    if funding > 0 {
+        pay_from_margin_vault(
+            &accounts.config,
+            &accounts.vault,
+            &accounts.insurance_fund,
+            &accounts.token_program,
+            funding,
+        )?;
+    }
+
+    if size > 0 {
+        let margin_account = &accounts.margin_account;
+        let seeds = &[MARGIN_ACCOUNT_SEED, margin_account.owner.as_ref(), &[margin_account.bump]];
+        let signer_seeds = &[&seeds[..]];
+        let vaults = accounts.vaults();
+        vaults.deposit_outcome(
+            outcome,
+            &accounts.custody.to_account_info(),
+            &margin_account.to_account_info(),
+            signer_seeds,
+            size,
+        )?;
+        vaults.withdraw_dac(&accounts.vault, out)?;
+    }
+
+    let price_before = ctx.accounts.pool.price(Outcome::Yes);
+    ctx.accounts.pool.apply_sell(outcome, size, out)
+        .ok_or(MarketError::Underflow)?;
+    track_price_move(&mut ctx.accounts.pool, &mut ctx.accounts.observations.load_mut()?, price_before)?;
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_sub(out)
+        .ok_or(MarketError::Underflow)?;
+    let market_key = ctx.accounts.market.key();
+
+    let margin_account = &mut ctx.accounts.margin_account;
+    if let Some(position) = margin_account.positions.iter_mut().find(|p| p.market == market_key) {
+        let held = position.amount_mut(outcome);
+        *held = held.checked_sub(size).ok_or(MarketError::InsufficientPosition)?;
+    }
+    margin_account.prune_positions();
+    margin_account.debit(funding).ok_or(MarketError::Overflow)?;
+    margin_account.credit(out).ok_or(MarketError::Overflow)?;
+
+    msg!(
+        "Leveraged {:?} unwound: sold {} for {} DAC, funding {}, debt now {}",
+        outcome,
+        size,
+        out,
+        funding,
+        margin_account.debt
+    );
+    Ok(())
+}
+
+impl_pool_vaults!(OpenLeveraged, UnwindLeveraged);
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetLeverageParams<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The market's leverage settings (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + LeverageParams::LEN,
+        seeds = [LEVERAGE_PARAMS_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub leverage_params: Account<'info, LeverageParams>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome: Outcome)]
+pub struct OpenLeveraged<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, MarketConfig>>,
+
+    /// The pool's market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_trading(Clock::get()?.unix_timestamp) @ MarketError::MarketClosed,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The pool to buy from
+    #[account(
+        mut,
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump = pool.bump,
+        has_one = yes_vault @ MarketError::MintMismatch,
+        has_one = no_vault @ MarketError::MintMismatch,
+        constraint = !pool.breaker.is_halted(Clock::get()?.slot) @ MarketError::TradingHalted,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The pool's price observation buffer
+    #[account(
+        mut,
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump = observations.load()?.bump,
+    )]
+    pub observations: AccountLoader<'info, PoolObservations>,
+
+    #[account(mut)]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The outcome token mint being bought
+    #[account(
+        constraint = market.load()?.outcome_mint(outcome) == Some(outcome_mint.key()) @ MarketError::InvalidMarginOutcome,
+    )]
+    pub outcome_mint: Box<Account<'info, Mint>>,
+
+    /// The market's leverage settings
+    #[account(
+        seeds = [LEVERAGE_PARAMS_SEED, market.key().as_ref()],
+        bump = leverage_params.bump,
+    )]
+    pub leverage_params: Box<Account<'info, LeverageParams>>,
+
+    /// The owner's margin account
+    #[account(
+        mut,
+        seeds = [MARGIN_ACCOUNT_SEED, owner.key().as_ref()],
+        bump = margin_account.bump,
+    )]
+    pub margin_account: Box<Account<'info, MarginAccount>>,
+
+    /// The leveraged position (created on first open, added to afterwards)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + LeveragedPosition::LEN,
+        seeds = [
+            LEVERAGED_POSITION_SEED,
+            margin_account.key().as_ref(),
+            market.key().as_ref(),
+            &[outcome as u8],
+        ],
+        bump
+    )]
+    pub leveraged_position: Box<Account<'info, LeveragedPosition>>,
+
+    /// Custody of the margin account's tokens of this outcome
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [MARGIN_CUSTODY_SEED, margin_account.key().as_ref(), outcome_mint.key().as_ref()],
+        bump,
+        token::mint = outcome_mint,
+        token::authority = margin_account,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    /// The shared margin vault (source of the notional)
+    #[account(
+        mut,
+        seeds = [MARGIN_VAULT_SEED],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// The insurance fund (receives funding)
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump,
+    )]
+    pub insurance_fund: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UnwindLeveraged<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, MarketConfig>>,
+
+    /// The pool's market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_trading(Clock::get()?.unix_timestamp) @ MarketError::MarketClosed,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The pool to sell to
+    #[account(
+        mut,
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump = pool.bump,
+        has_one = yes_vault @ MarketError::MintMismatch,
+        has_one = no_vault @ MarketError::MintMismatch,
+        constraint = !pool.breaker.is_halted(Clock::get()?.slot) @ MarketError::TradingHalted,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The pool's price observation buffer
+    #[account(
+        mut,
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump = observations.load()?.bump,
+    )]
+    pub observations: AccountLoader<'info, PoolObservations>,
+
+    #[account(mut)]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The market's leverage settings
+    #[account(
+        seeds = [LEVERAGE_PARAMS_SEED, market.key().as_ref()],
+        bump = leverage_params.bump,
+    )]
+    pub leverage_params: Box<Account<'info, LeverageParams>>,
+
+    /// The margin account funding the position
+    #[account(
+        mut,
+        seeds = [MARGIN_ACCOUNT_SEED, owner.key().as_ref()],
+        bump = margin_account.bump,
+    )]
+    pub margin_account: Box<Account<'info, MarginAccount>>,
+
+    /// The leveraged position to unwind
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            LEVERAGED_POSITION_SEED,
+            margin_account.key().as_ref(),
+            market.key().as_ref(),
+            &[leveraged_position.outcome as u8],
+        ],
+        bump = leveraged_position.bump,
+    )]
+    pub leveraged_position: Box<Account<'info, LeveragedPosition>>,
+
+    /// Custody of the margin account's tokens of the position's outcome
+    #[account(
+        mut,
+        seeds = [MARGIN_CUSTODY_SEED, margin_account.key().as_ref(), custody.mint.as_ref()],
+        bump,
+        constraint = market.load()?.outcome_mint(leveraged_position.outcome) == Some(custody.mint) @ MarketError::MintMismatch,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    /// The shared margin vault (receives the proceeds)
+    #[account(
+        mut,
+        seeds = [MARGIN_VAULT_SEED],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// The insurance fund (receives funding)
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump,
+    )]
+    pub insurance_fund: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Margin account owner, receives the position's rent; bound by the margin account seeds
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+
+    /// The owner when closing, anyone when force-deleveraging
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AccrueFunding<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, MarketConfig>>,
+
+    /// The position's market leverage settings
+    #[account(
+        seeds = [LEVERAGE_PARAMS_SEED, leveraged_position.market.as_ref()],
+        bump = leverage_params.bump,
+    )]
+    pub leverage_params: Box<Account<'info, LeverageParams>>,
+
+    /// The margin account funding the position
+    #[account(
+        mut,
+        seeds = [MARGIN_ACCOUNT_SEED, margin_account.owner.as_ref()],
+        bump = margin_account.bump,
+    )]
+    pub margin_account: Box<Account<'info, MarginAccount>>,
+
+    /// The leveraged position to charge
+    #[account(
+        mut,
+        seeds = [
+            LEVERAGED_POSITION_SEED,
+            margin_account.key().as_ref(),
+            leveraged_position.market.as_ref(),
+            &[leveraged_position.outcome as u8],
+        ],
+        bump = leveraged_position.bump,
+    )]
+    pub leveraged_position: Box<Account<'info, LeveragedPosition>>,
+
+    /// The shared margin vault
+    #[account(
+        mut,
+        seeds = [MARGIN_VAULT_SEED],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// The insurance fund (receives funding)
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump,
+    )]
+    pub insurance_fund: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
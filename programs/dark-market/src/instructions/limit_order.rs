@@ -0,0 +1,467 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::instructions::{
+    accrue_maker_rebate, check_attested, fee_discount, impl_pool_vaults, record_trade, track_price_move,
+    PoolVaults,
+};
+use crate::state::*;
+use crate::{
+    FEE_TIERS_SEED, LIMIT_ORDER_SEED, LIMIT_ORDER_VAULT_SEED, MAKER_REBATE_SEED, MARKET_SEED,
+    OBSERVATIONS_SEED, POOL_SEED, PORTFOLIO_SEED, REBATE_POOL_SEED,
+};
+
+pub fn place_limit_order(
+    ctx: Context<PlaceLimitOrder>,
+    nonce: u64,
+    side: OrderSide,
+    outcome: Outcome,
+    amount: u64,
+    limit_price: u64,
+    tip: u64,
+) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+    require!(
+        limit_price > 0 && limit_price < PRICE_SCALE,
+        MarketError::InvalidLimitPrice
+    );
+    require!(
+        locked_mint(&ctx.accounts.market.load()?, &ctx.accounts.escrow, side, outcome)
+            == Some(ctx.accounts.vault_mint.key()),
+        MarketError::InvalidOrderOutcome
+    );
+    check_attested(&ctx.accounts.market.load()?, ctx.accounts.attestation.as_deref(), &ctx.accounts.owner.key())?;
+
+    // Buys lock the tip alongside the DAC; sells pay it out of the proceeds
+    let locked = match side {
+        OrderSide::Buy => amount.checked_add(tip).ok_or(MarketError::Overflow)?,
+        OrderSide::Sell => amount,
+    };
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_source.to_account_info(),
+            to: ctx.accounts.order_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, locked)?;
+
+    let order = &mut ctx.accounts.order;
+    order.owner = ctx.accounts.owner.key();
+    order.pool = ctx.accounts.pool.key();
+    order.nonce = nonce;
+    order.side = side;
+    order.outcome = outcome;
+    order.amount = amount;
+    order.limit_price = limit_price;
+    order.tip = tip;
+    order.bump = ctx.bumps.order;
+    order.vault_bump = ctx.bumps.order_vault;
+
+    msg!("Limit order placed: {:?} {} {:?} @ {}", side, amount, outcome, limit_price);
+    Ok(())
+}
+
+pub fn fill_limit_order(ctx: Context<FillLimitOrder>) -> Result<()> {
+    ctx.accounts.pool.refresh_fee(&ctx.accounts.observations.load()?, Clock::get()?.unix_timestamp);
+    let accounts = &ctx.accounts;
+    let order = &accounts.order;
+    require!(
+        proceeds_mint(&accounts.market.load()?, &accounts.escrow, order.side, order.outcome)
+            == Some(accounts.owner_proceeds.mint),
+        MarketError::MintMismatch
+    );
+
+    let min_out = order.min_amount_out().ok_or(MarketError::Overflow)?;
+    let discount = fee_discount(
+        accounts.fee_tiers.as_deref(),
+        accounts.stake.as_deref(),
+        &order.owner,
+    )?;
+    let out = match order.side {
+        OrderSide::Buy => accounts.pool.quote_buy(order.outcome, order.amount, discount),
+        OrderSide::Sell => accounts.pool.quote_sell(order.outcome, order.amount, discount),
+    }
+    .ok_or(MarketError::Overflow)?;
+    require!(out >= min_out, MarketError::LimitNotReached);
+
+    let pool_key = accounts.pool.key();
+    let owner_key = order.owner;
+    let nonce_bytes = order.nonce.to_le_bytes();
+    let seeds = &[
+        LIMIT_ORDER_SEED,
+        pool_key.as_ref(),
+        owner_key.as_ref(),
+        nonce_bytes.as_ref(),
+        &[order.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let vaults = accounts.vaults();
+    let order_info = order.to_account_info();
+    let vault_info = accounts.order_vault.to_account_info();
+    match order.side {
+        OrderSide::Buy => {
+            vaults.deposit_dac(&vault_info, &order_info, signer_seeds, order.amount)?;
+            vaults.withdraw_outcome(order.outcome, &accounts.owner_proceeds, out)?;
+
+            let tip_ctx = CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault_info.clone(),
+                    to: accounts.cranker_dac.to_account_info(),
+                    authority: order_info.clone(),
+                },
+                signer_seeds,
+            );
+            token::transfer(tip_ctx, order.tip)?;
+        }
+        OrderSide::Sell => {
+            vaults.deposit_outcome(order.outcome, &vault_info, &order_info, signer_seeds, order.amount)?;
+            vaults.withdraw_dac(&accounts.owner_proceeds, out - order.tip)?;
+            vaults.withdraw_dac(&accounts.cranker_dac, order.tip)?;
+        }
+    }
+
+    let close_ctx = CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: vault_info,
+            destination: accounts.owner.to_account_info(),
+            authority: order_info,
+        },
+        signer_seeds,
+    );
+    token::close_account(close_ctx)?;
+
+    let (side, outcome, amount, tip) = (order.side, order.outcome, order.amount, order.tip);
+    let price_before = ctx.accounts.pool.price(Outcome::Yes);
+    let pool = &mut ctx.accounts.pool;
+    let market = &mut ctx.accounts.market.load_mut()?;
+    match side {
+        OrderSide::Buy => {
+            pool.apply_buy(outcome, amount, out).ok_or(MarketError::Overflow)?;
+            market.total_collateral = market.total_collateral.checked_add(amount)
+                .ok_or(MarketError::Overflow)?;
+        }
+        OrderSide::Sell => {
+            pool.apply_sell(outcome, amount, out).ok_or(MarketError::Underflow)?;
+            market.total_collateral = market.total_collateral.checked_sub(out)
+                .ok_or(MarketError::Underflow)?;
+        }
+    }
+    track_price_move(pool, &mut ctx.accounts.observations.load_mut()?, price_before)?;
+    let market_key = ctx.accounts.market.key();
+    record_trade(ctx.accounts.portfolio.as_mut(), |p| match side {
+        OrderSide::Buy => p.record_buy(market_key, outcome, out, amount.checked_add(tip)?),
+        OrderSide::Sell => p.record_sell(market_key, outcome, amount, out - tip),
+    })?;
+
+    // The resting order is the maker: rebate its DAC notional
+    let notional = match side {
+        OrderSide::Buy => amount,
+        OrderSide::Sell => out,
+    };
+    let rebate = accrue_maker_rebate(
+        &ctx.accounts.rebate_pool,
+        ctx.accounts.rebate_vault.as_ref(),
+        &ctx.accounts.maker_rebate,
+        notional,
+    )?;
+
+    msg!(
+        "Limit order filled: {:?} {} {:?} -> {} (maker rebate {})",
+        side,
+        amount,
+        outcome,
+        out,
+        rebate
+    );
+    Ok(())
+}
+
+pub fn cancel_limit_order(ctx: Context<CancelLimitOrder>) -> Result<()> {
+    let order = &ctx.accounts.order;
+    let nonce_bytes = order.nonce.to_le_bytes();
+    let seeds = &[
+        LIMIT_ORDER_SEED,
+        order.pool.as_ref(),
+        order.owner.as_ref(),
+        nonce_bytes.as_ref(),
+        &[order.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let refund = ctx.accounts.order_vault.amount;
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.order_vault.to_account_info(),
+            to: ctx.accounts.owner_destination.to_account_info(),
+            authority: order.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, refund)?;
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.order_vault.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: order.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::close_account(close_ctx)?;
+
+    msg!("Limit order cancelled, refunded {}", refund);
+    Ok(())
+}
+
+/// Mint of the asset an order locks: DAC for buys, the outcome for sells
+fn locked_mint(market: &Market, escrow: &TokenAccount, side: OrderSide, outcome: Outcome) -> Option<Pubkey> {
+    let outcome_mint = market.outcome_mint(outcome)?;
+    match side {
+        OrderSide::Buy => Some(escrow.mint),
+        OrderSide::Sell => Some(outcome_mint),
+    }
+}
+
+/// Mint of the asset a filled order pays out: the outcome for buys, DAC for sells
+fn proceeds_mint(market: &Market, escrow: &TokenAccount, side: OrderSide, outcome: Outcome) -> Option<Pubkey> {
+    let outcome_mint = market.outcome_mint(outcome)?;
+    match side {
+        OrderSide::Buy => Some(outcome_mint),
+        OrderSide::Sell => Some(escrow.mint),
+    }
+}
+
+impl_pool_vaults!(FillLimitOrder);
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct PlaceLimitOrder<'info> {
+    /// The pool's market
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_trading(Clock::get()?.unix_timestamp) @ MarketError::MarketClosed,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The pool the order will fill against
+    #[account(
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The market's DAC escrow (identifies the DAC mint)
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The order account to create
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + LimitOrder::LEN,
+        seeds = [LIMIT_ORDER_SEED, pool.key().as_ref(), owner.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub order: Box<Account<'info, LimitOrder>>,
+
+    /// Mint of the locked asset (DAC for buys, the outcome for sells)
+    pub vault_mint: Box<Account<'info, Mint>>,
+
+    /// Vault holding the locked asset
+    #[account(
+        init,
+        payer = owner,
+        seeds = [LIMIT_ORDER_VAULT_SEED, order.key().as_ref()],
+        bump,
+        token::mint = vault_mint,
+        token::authority = order,
+    )]
+    pub order_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's token account for the locked asset (source)
+    #[account(
+        mut,
+        constraint = owner_source.mint == vault_mint.key() @ MarketError::MintMismatch,
+    )]
+    pub owner_source: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The owner's SAS attestation (required when the market requires one), decoded and checked
+    pub attestation: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct FillLimitOrder<'info> {
+    /// The pool's market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_trading(Clock::get()?.unix_timestamp) @ MarketError::MarketClosed,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The pool to fill against
+    #[account(
+        mut,
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump = pool.bump,
+        has_one = yes_vault @ MarketError::MintMismatch,
+        has_one = no_vault @ MarketError::MintMismatch,
+        constraint = !pool.breaker.is_halted(Clock::get()?.slot) @ MarketError::TradingHalted,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The pool's price observation buffer
+    #[account(
+        mut,
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump = observations.load()?.bump,
+    )]
+    pub observations: AccountLoader<'info, PoolObservations>,
+
+    #[account(mut)]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The order to fill
+    #[account(
+        mut,
+        close = owner,
+        has_one = pool @ MarketError::InvalidOrderOutcome,
+        has_one = owner @ MarketError::Unauthorized,
+    )]
+    pub order: Box<Account<'info, LimitOrder>>,
+
+    /// Vault holding the locked asset
+    #[account(
+        mut,
+        seeds = [LIMIT_ORDER_VAULT_SEED, order.key().as_ref()],
+        bump = order.vault_bump,
+    )]
+    pub order_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's token account for the proceeds (the outcome for buys, DAC for sells)
+    #[account(
+        mut,
+        constraint = owner_proceeds.owner == order.owner @ MarketError::Unauthorized,
+    )]
+    pub owner_proceeds: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Order owner, receives the reclaimed rent
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+
+    /// The owner's portfolio, updated when passed
+    #[account(
+        mut,
+        seeds = [PORTFOLIO_SEED, order.owner.as_ref()],
+        bump = portfolio.bump,
+    )]
+    pub portfolio: Option<Box<Account<'info, Portfolio>>>,
+
+    /// Swap fee discount tiers, applied when a stake account is also passed
+    #[account(
+        seeds = [FEE_TIERS_SEED],
+        bump = fee_tiers.bump,
+    )]
+    pub fee_tiers: Option<Box<Account<'info, FeeTiers>>>,
+
+    /// CHECK: The trader's stake account, parsed against the tiers' staking program
+    pub stake: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The pool's maker rebate settings, accruing a rebate to the owner once configured
+    #[account(
+        mut,
+        seeds = [REBATE_POOL_SEED, pool.key().as_ref()],
+        bump,
+    )]
+    pub rebate_pool: UncheckedAccount<'info>,
+
+    /// DAC vault funding the maker rebates, required once rebates are configured
+    pub rebate_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: The owner's accrued maker rebates, credited once the owner has opened them
+    #[account(
+        mut,
+        seeds = [MAKER_REBATE_SEED, pool.key().as_ref(), order.owner.as_ref()],
+        bump,
+    )]
+    pub maker_rebate: UncheckedAccount<'info>,
+
+    /// Cranker's DAC token account (receives the tip)
+    #[account(
+        mut,
+        constraint = cranker_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub cranker_dac: Box<Account<'info, TokenAccount>>,
+
+    pub cranker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelLimitOrder<'info> {
+    /// The order to cancel
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ MarketError::Unauthorized,
+    )]
+    pub order: Box<Account<'info, LimitOrder>>,
+
+    /// Vault holding the locked asset
+    #[account(
+        mut,
+        seeds = [LIMIT_ORDER_VAULT_SEED, order.key().as_ref()],
+        bump = order.vault_bump,
+    )]
+    pub order_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's token account for the locked asset (refund destination)
+    #[account(
+        mut,
+        constraint = owner_destination.mint == order_vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_destination: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
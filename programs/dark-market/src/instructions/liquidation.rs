@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::instructions::pay_from_margin_vault;
+use crate::state::*;
+use crate::{
+    INSURANCE_FUND_SEED, MARGIN_ACCOUNT_SEED, MARGIN_CUSTODY_SEED, MARGIN_VAULT_SEED,
+    MARKET_CONFIG_SEED, MARKET_SEED, OBSERVATIONS_SEED, POOL_SEED,
+};
+
+pub fn initialize_insurance_fund(_ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+    msg!("Insurance fund initialized");
+    Ok(())
+}
+
+/// Repay part of an under-collateralized account's debt for its outcome tokens
+///
+/// The seized tokens are priced at the pool's TWAP so a single trade cannot
+/// push an account into liquidation or inflate the liquidator's take.
+pub fn liquidate(ctx: Context<Liquidate>, outcome: Outcome, repay: u64) -> Result<()> {
+    require!(repay > 0, MarketError::ZeroAmount);
+    let margin = &ctx.accounts.margin_account;
+    require!(!margin.is_healthy(), MarketError::MarginHealthy);
+
+    let yes_twap = ctx.accounts.observations
+        .load()?
+        .twap(
+            Clock::get()?.unix_timestamp,
+            LIQUIDATION_TWAP_WINDOW,
+            ctx.accounts.pool.price(Outcome::Yes),
+        )
+        .ok_or(MarketError::InsufficientObservations)?;
+    let price = match outcome {
+        Outcome::No => PRICE_SCALE - yes_twap,
+        _ => yes_twap,
+    };
+
+    let liquidation = margin
+        .quote_liquidation(repay, price, ctx.accounts.custody.amount)
+        .ok_or(MarketError::NothingToLiquidate)?;
+    require!(
+        liquidation.repaid > 0 && liquidation.seized > 0,
+        MarketError::NothingToLiquidate
+    );
+
+    // Liquidator repays the debt into the margin vault
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.liquidator_dac.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.liquidator.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, liquidation.repaid)?;
+
+    // Seized tokens go to the liquidator
+    let seeds = &[MARGIN_ACCOUNT_SEED, margin.owner.as_ref(), &[margin.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.custody.to_account_info(),
+            to: ctx.accounts.liquidator_outcome.to_account_info(),
+            authority: margin.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, liquidation.seized)?;
+
+    if liquidation.insurance_fee > 0 {
+        pay_from_margin_vault(
+            &ctx.accounts.config,
+            &ctx.accounts.vault,
+            &ctx.accounts.insurance_fund,
+            &ctx.accounts.token_program,
+            liquidation.insurance_fee,
+        )?;
+    }
+
+    let market_key = ctx.accounts.market.key();
+    let margin = &mut ctx.accounts.margin_account;
+    let position = margin.positions.iter_mut()
+        .find(|p| p.market == market_key)
+        .ok_or(MarketError::InsufficientPosition)?;
+    let held = position.amount_mut(outcome);
+    *held = held.checked_sub(liquidation.seized).ok_or(MarketError::InsufficientPosition)?;
+    margin.prune_positions();
+    margin.credit(liquidation.repaid).ok_or(MarketError::Overflow)?;
+    margin.debit(liquidation.insurance_fee).ok_or(MarketError::Overflow)?;
+
+    msg!(
+        "Liquidated {} {:?} for {} DAC (insurance fee {}), debt now {}",
+        liquidation.seized,
+        outcome,
+        liquidation.repaid,
+        liquidation.insurance_fee,
+        margin.debt
+    );
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// DAC vault collecting the insurance share of liquidation penalties
+    #[account(
+        init,
+        payer = authority,
+        seeds = [INSURANCE_FUND_SEED],
+        bump,
+        token::mint = dac_mint,
+        token::authority = config,
+    )]
+    pub insurance_fund: Account<'info, TokenAccount>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == config.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome: Outcome)]
+pub struct Liquidate<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The under-collateralized margin account
+    #[account(
+        mut,
+        seeds = [MARGIN_ACCOUNT_SEED, margin_account.owner.as_ref()],
+        bump = margin_account.bump,
+    )]
+    pub margin_account: Box<Account<'info, MarginAccount>>,
+
+    /// The market of the seized position
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The market's pool, whose TWAP prices the seized tokens
+    #[account(
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The pool's observation buffer
+    #[account(
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump = observations.load()?.bump,
+    )]
+    pub observations: AccountLoader<'info, PoolObservations>,
+
+    /// The outcome token mint being seized
+    #[account(
+        constraint = market.load()?.outcome_mint(outcome) == Some(outcome_mint.key()) @ MarketError::InvalidMarginOutcome,
+    )]
+    pub outcome_mint: Box<Account<'info, Mint>>,
+
+    /// Custody of the margin account's tokens of this outcome
+    #[account(
+        mut,
+        seeds = [MARGIN_CUSTODY_SEED, margin_account.key().as_ref(), outcome_mint.key().as_ref()],
+        bump,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    /// The shared margin vault (receives the repayment)
+    #[account(
+        mut,
+        seeds = [MARGIN_VAULT_SEED],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// The insurance fund (receives its share of the penalty)
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump,
+    )]
+    pub insurance_fund: Box<Account<'info, TokenAccount>>,
+
+    /// Liquidator's DAC token account (source of the repayment)
+    #[account(
+        mut,
+        constraint = liquidator_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub liquidator_dac: Box<Account<'info, TokenAccount>>,
+
+    /// Liquidator's outcome token account (destination of the seized tokens)
+    #[account(
+        mut,
+        constraint = liquidator_outcome.mint == outcome_mint.key() @ MarketError::MintMismatch,
+    )]
+    pub liquidator_outcome: Box<Account<'info, TokenAccount>>,
+
+    pub liquidator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
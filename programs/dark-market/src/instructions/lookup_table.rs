@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::address_lookup_table::{self, instruction as lookup_instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{
+    LOOKUP_TABLE_AUTHORITY_SEED, LOOKUP_TABLE_REGISTRY_SEED, LOOKUP_TABLE_SEED, MARKET_CONFIG_SEED, MARKET_SEED,
+    POOL_NO_SEED, POOL_SEED, POOL_YES_SEED,
+};
+
+/// Create the next protocol lookup table, owned by the lookup table authority PDA
+///
+/// Permissionless, but only once the latest table has no room for another
+/// market, so tables are always filled in order.
+pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    if registry.table_count > 0 {
+        let latest = ctx.accounts.latest.as_ref().ok_or(MarketError::LookupTableNotFull)?;
+        require!(
+            latest.index == registry.table_count - 1 && latest.is_full(),
+            MarketError::LookupTableNotFull
+        );
+    }
+
+    let authority = ctx.accounts.lookup_table_authority.key();
+    let (ix, table) =
+        lookup_instruction::create_lookup_table_signed(authority, ctx.accounts.payer.key(), recent_slot);
+    require_keys_eq!(table, ctx.accounts.table.key(), MarketError::InvalidLookupTable);
+    let seeds = &[LOOKUP_TABLE_AUTHORITY_SEED, &[ctx.bumps.lookup_table_authority]];
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.table.to_account_info(),
+            ctx.accounts.lookup_table_authority.to_account_info(),
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&seeds[..]],
+    )?;
+
+    let record = &mut ctx.accounts.lookup_table;
+    record.index = registry.table_count;
+    record.table = table;
+    record.len = 0;
+    record.bump = ctx.bumps.lookup_table;
+    registry.table_count += 1;
+    registry.bump = ctx.bumps.registry;
+
+    msg!("Lookup table #{} created at {}", record.index, table);
+    Ok(())
+}
+
+/// Add the accounts of the next markets, passed in id order as remaining accounts
+///
+/// Permissionless: the addresses are read from the market accounts and
+/// derived from their seeds, so keepers can keep the tables current.
+pub fn extend_lookup_table_with_markets<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExtendLookupTableWithMarkets<'info>>,
+) -> Result<()> {
+    let markets = ctx.remaining_accounts;
+    require!(!markets.is_empty(), MarketError::InvalidLookupTable);
+    let record = &ctx.accounts.lookup_table;
+    require!(
+        record.has_room(markets.len() * MARKET_LOOKUP_ADDRESSES),
+        MarketError::LookupTableFull
+    );
+
+    let registry = &mut ctx.accounts.registry;
+    let mut addresses = Vec::with_capacity(markets.len() * MARKET_LOOKUP_ADDRESSES);
+    for info in markets {
+        let loader = AccountLoader::<Market>::try_from(info)?;
+        let market = loader.load()?;
+        require!(market.id == registry.next_market, MarketError::InvalidLookupTable);
+        let (expected, _) = Pubkey::find_program_address(&[MARKET_SEED, &market.id.to_le_bytes()], &crate::ID);
+        require_keys_eq!(expected, info.key(), MarketError::InvalidLookupTable);
+        addresses.extend(market_addresses(&info.key(), &market));
+        registry.next_market += 1;
+    }
+    let last = registry.next_market - 1;
+
+    let accounts = &ctx.accounts;
+    extend(
+        &accounts.table,
+        &accounts.lookup_table_authority,
+        ctx.bumps.lookup_table_authority,
+        &accounts.payer,
+        &accounts.system_program,
+        addresses,
+    )?;
+    let record = &mut ctx.accounts.lookup_table;
+    record.len += (markets.len() * MARKET_LOOKUP_ADDRESSES) as u16;
+
+    msg!("Lookup table #{} extended with markets up to #{}", record.index, last);
+    Ok(())
+}
+
+/// Add protocol-wide addresses such as mints, programs and vault PDAs (authority only)
+pub fn extend_lookup_table(ctx: Context<ExtendLookupTable>, addresses: Vec<Pubkey>) -> Result<()> {
+    require!(!addresses.is_empty(), MarketError::InvalidLookupTable);
+    require!(ctx.accounts.lookup_table.has_room(addresses.len()), MarketError::LookupTableFull);
+
+    let count = addresses.len();
+    let accounts = &ctx.accounts;
+    extend(
+        &accounts.table,
+        &accounts.lookup_table_authority,
+        ctx.bumps.lookup_table_authority,
+        &accounts.authority,
+        &accounts.system_program,
+        addresses,
+    )?;
+    let record = &mut ctx.accounts.lookup_table;
+    record.len += count as u16;
+
+    msg!("Lookup table #{} extended with {} addresses", record.index, count);
+    Ok(())
+}
+
+/// The accounts of `market` that settlement and trading touch
+fn market_addresses(key: &Pubkey, market: &Market) -> [Pubkey; MARKET_LOOKUP_ADDRESSES] {
+    let (pool, _) = Pubkey::find_program_address(&[POOL_SEED, key.as_ref()], &crate::ID);
+    let (pool_yes, _) = Pubkey::find_program_address(&[POOL_YES_SEED, pool.as_ref()], &crate::ID);
+    let (pool_no, _) = Pubkey::find_program_address(&[POOL_NO_SEED, pool.as_ref()], &crate::ID);
+    [*key, market.yes_mint, market.no_mint, market.escrow, pool, pool_yes, pool_no]
+}
+
+/// Extend `table` with `addresses`, signed by the lookup table authority
+fn extend<'info>(
+    table: &UncheckedAccount<'info>,
+    authority: &UncheckedAccount<'info>,
+    bump: u8,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    addresses: Vec<Pubkey>,
+) -> Result<()> {
+    let ix = lookup_instruction::extend_lookup_table(table.key(), authority.key(), Some(payer.key()), addresses);
+    let seeds = &[LOOKUP_TABLE_AUTHORITY_SEED, &[bump]];
+    invoke_signed(
+        &ix,
+        &[
+            table.to_account_info(),
+            authority.to_account_info(),
+            payer.to_account_info(),
+            system_program.to_account_info(),
+        ],
+        &[&seeds[..]],
+    )?;
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreateLookupTable<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + LookupTableRegistry::LEN,
+        seeds = [LOOKUP_TABLE_REGISTRY_SEED],
+        bump
+    )]
+    pub registry: Box<Account<'info, LookupTableRegistry>>,
+
+    /// The latest table, required once one exists
+    pub latest: Option<Box<Account<'info, ProtocolLookupTable>>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProtocolLookupTable::LEN,
+        seeds = [LOOKUP_TABLE_SEED, registry.table_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub lookup_table: Box<Account<'info, ProtocolLookupTable>>,
+
+    /// CHECK: created by the address lookup table program, checked against the derived address
+    #[account(mut)]
+    pub table: UncheckedAccount<'info>,
+
+    /// CHECK: PDA owning every protocol lookup table
+    #[account(seeds = [LOOKUP_TABLE_AUTHORITY_SEED], bump)]
+    pub lookup_table_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: the address lookup table program
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendLookupTableWithMarkets<'info> {
+    #[account(mut, seeds = [LOOKUP_TABLE_REGISTRY_SEED], bump = registry.bump)]
+    pub registry: Box<Account<'info, LookupTableRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [LOOKUP_TABLE_SEED, lookup_table.index.to_le_bytes().as_ref()],
+        bump = lookup_table.bump,
+        has_one = table @ MarketError::InvalidLookupTable,
+    )]
+    pub lookup_table: Box<Account<'info, ProtocolLookupTable>>,
+
+    /// CHECK: the recorded lookup table
+    #[account(mut)]
+    pub table: UncheckedAccount<'info>,
+
+    /// CHECK: PDA owning every protocol lookup table
+    #[account(seeds = [LOOKUP_TABLE_AUTHORITY_SEED], bump)]
+    pub lookup_table_authority: UncheckedAccount<'info>,
+
+    /// Pays the rent of the added addresses
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: the address lookup table program
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendLookupTable<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [LOOKUP_TABLE_SEED, lookup_table.index.to_le_bytes().as_ref()],
+        bump = lookup_table.bump,
+        has_one = table @ MarketError::InvalidLookupTable,
+    )]
+    pub lookup_table: Box<Account<'info, ProtocolLookupTable>>,
+
+    /// CHECK: the recorded lookup table
+    #[account(mut)]
+    pub table: UncheckedAccount<'info>,
+
+    /// CHECK: PDA owning every protocol lookup table
+    #[account(seeds = [LOOKUP_TABLE_AUTHORITY_SEED], bump)]
+    pub lookup_table_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: the address lookup table program
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,588 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::instructions::transfer_from_escrow;
+use crate::state::*;
+use crate::{
+    MARGIN_ACCOUNT_SEED, MARGIN_CUSTODY_SEED, MARGIN_VAULT_SEED, MARKET_CONFIG_SEED,
+    MARKET_LINK_SEED, MARKET_SEED,
+};
+
+pub fn initialize_margin_vault(_ctx: Context<InitializeMarginVault>) -> Result<()> {
+    msg!("Margin vault initialized");
+    Ok(())
+}
+
+pub fn create_market_link(ctx: Context<CreateMarketLink>, kind: LinkKind) -> Result<()> {
+    let link = &mut ctx.accounts.link;
+    link.market_a = ctx.accounts.market_a.key();
+    link.market_b = ctx.accounts.market_b.key();
+    link.kind = kind;
+    link.bump = ctx.bumps.link;
+
+    msg!("Market link {:?}: {} -> {}", kind, link.market_a, link.market_b);
+    Ok(())
+}
+
+pub fn open_margin_account(ctx: Context<OpenMarginAccount>) -> Result<()> {
+    let margin = &mut ctx.accounts.margin_account;
+    margin.owner = ctx.accounts.owner.key();
+    margin.collateral = 0;
+    margin.debt = 0;
+    margin.positions = Vec::new();
+    margin.links = Vec::new();
+    margin.bump = ctx.bumps.margin_account;
+
+    msg!("Margin account opened for {}", margin.owner);
+    Ok(())
+}
+
+pub fn deposit_margin(ctx: Context<DepositMargin>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_dac.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let margin = &mut ctx.accounts.margin_account;
+    margin.credit(amount).ok_or(MarketError::Overflow)?;
+
+    msg!("Margin deposit {}: collateral {}, debt {}", amount, margin.collateral, margin.debt);
+    Ok(())
+}
+
+pub fn withdraw_margin(ctx: Context<WithdrawMargin>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    let margin = &mut ctx.accounts.margin_account;
+    margin.debit(amount).ok_or(MarketError::Overflow)?;
+    require!(margin.is_healthy(), MarketError::MarginUnhealthy);
+
+    pay_from_margin_vault(
+        &ctx.accounts.config,
+        &ctx.accounts.vault,
+        &ctx.accounts.owner_dac,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    msg!("Margin withdrawal {}: collateral {}, debt {}", amount, margin.collateral, margin.debt);
+    Ok(())
+}
+
+pub fn deposit_position(ctx: Context<DepositPosition>, outcome: Outcome, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_outcome.to_account_info(),
+            to: ctx.accounts.custody.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let position = ctx.accounts.margin_account
+        .position_mut(ctx.accounts.market.key())
+        .ok_or(MarketError::TooManyMarginPositions)?;
+    let held = position.amount_mut(outcome);
+    *held = held.checked_add(amount).ok_or(MarketError::Overflow)?;
+
+    msg!("Margin position deposit: {} {:?} of market {}", amount, outcome, ctx.accounts.market.load()?.id);
+    Ok(())
+}
+
+pub fn withdraw_position(ctx: Context<WithdrawPosition>, outcome: Outcome, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    let market_key = ctx.accounts.market.key();
+    let margin = &mut ctx.accounts.margin_account;
+    let position = margin.positions.iter_mut()
+        .find(|p| p.market == market_key)
+        .ok_or(MarketError::InsufficientPosition)?;
+    let held = position.amount_mut(outcome);
+    *held = held.checked_sub(amount).ok_or(MarketError::InsufficientPosition)?;
+    margin.prune_positions();
+    require!(margin.is_healthy(), MarketError::MarginUnhealthy);
+
+    let margin = &ctx.accounts.margin_account;
+    let seeds = &[MARGIN_ACCOUNT_SEED, margin.owner.as_ref(), &[margin.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.custody.to_account_info(),
+            to: ctx.accounts.owner_outcome.to_account_info(),
+            authority: margin.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    msg!("Margin position withdrawal: {} {:?} of market {}", amount, outcome, ctx.accounts.market.load()?.id);
+    Ok(())
+}
+
+pub fn attach_market_link(ctx: Context<AttachMarketLink>) -> Result<()> {
+    let link = &ctx.accounts.link;
+    let link_ref = LinkRef {
+        market_a: link.market_a,
+        market_b: link.market_b,
+        kind: link.kind,
+    };
+
+    let margin = &mut ctx.accounts.margin_account;
+    require!(!margin.links.contains(&link_ref), MarketError::LinkAlreadyAttached);
+    require!(margin.links.len() < MAX_MARGIN_LINKS, MarketError::TooManyMarginLinks);
+    margin.links.push(link_ref);
+
+    msg!("Market link attached ({} links)", margin.links.len());
+    Ok(())
+}
+
+pub fn detach_market_link(ctx: Context<DetachMarketLink>, index: u8) -> Result<()> {
+    let margin = &mut ctx.accounts.margin_account;
+    require!((index as usize) < margin.links.len(), MarketError::LinkNotAttached);
+    margin.links.remove(index as usize);
+    require!(margin.is_healthy(), MarketError::MarginUnhealthy);
+
+    msg!("Market link detached ({} links)", margin.links.len());
+    Ok(())
+}
+
+pub fn settle_margin_position(ctx: Context<SettleMarginPosition>, outcome: Outcome) -> Result<()> {
+    let amount = ctx.accounts.custody.amount;
+    let payout = match outcome {
+        Outcome::Yes => ctx.accounts.market.load()?.redemption_value(amount, 0),
+        _ => ctx.accounts.market.load()?.redemption_value(0, amount),
+    }
+    .ok_or(MarketError::MarketNotResolved)?;
+
+    let margin = &ctx.accounts.margin_account;
+    let seeds = &[MARGIN_ACCOUNT_SEED, margin.owner.as_ref(), &[margin.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    if amount > 0 {
+        let burn_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.outcome_mint.to_account_info(),
+                from: ctx.accounts.custody.to_account_info(),
+                authority: margin.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::burn(burn_ctx, amount)?;
+    }
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.custody.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: margin.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::close_account(close_ctx)?;
+
+    if payout > 0 {
+        transfer_from_escrow(
+            &ctx.accounts.market,
+            &ctx.accounts.escrow,
+            &ctx.accounts.vault,
+            &ctx.accounts.token_program,
+            payout,
+        )?;
+    }
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_sub(payout)
+        .ok_or(MarketError::Underflow)?;
+
+    let market_key = ctx.accounts.market.key();
+    let margin = &mut ctx.accounts.margin_account;
+    if let Some(position) = margin.positions.iter_mut().find(|p| p.market == market_key) {
+        *position.amount_mut(outcome) = 0;
+    }
+    margin.prune_positions();
+    margin.credit(payout).ok_or(MarketError::Overflow)?;
+
+    msg!("Margin position settled: {} {:?} for {} DAC", amount, outcome, payout);
+    Ok(())
+}
+
+/// Transfer DAC out of the margin vault, signed by the config PDA
+pub(crate) fn pay_from_margin_vault<'info>(
+    config: &Account<'info, MarketConfig>,
+    vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[MARKET_CONFIG_SEED, &[config.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: vault.to_account_info(),
+            to: to.to_account_info(),
+            authority: config.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeMarginVault<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// DAC vault shared by every margin account, controlled by the config PDA
+    #[account(
+        init,
+        payer = authority,
+        seeds = [MARGIN_VAULT_SEED],
+        bump,
+        token::mint = dac_mint,
+        token::authority = config,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == config.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMarketLink<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        seeds = [MARKET_SEED, market_a.load()?.id.to_le_bytes().as_ref()],
+        bump = market_a.load()?.bump,
+    )]
+    pub market_a: AccountLoader<'info, Market>,
+
+    #[account(
+        seeds = [MARKET_SEED, market_b.load()?.id.to_le_bytes().as_ref()],
+        bump = market_b.load()?.bump,
+        constraint = market_b.key() != market_a.key() @ MarketError::InvalidMarketLink,
+    )]
+    pub market_b: AccountLoader<'info, Market>,
+
+    /// The link to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MarketLink::LEN,
+        seeds = [MARKET_LINK_SEED, market_a.key().as_ref(), market_b.key().as_ref()],
+        bump
+    )]
+    pub link: Account<'info, MarketLink>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenMarginAccount<'info> {
+    /// The margin account to create
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + MarginAccount::LEN,
+        seeds = [MARGIN_ACCOUNT_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub margin_account: Box<Account<'info, MarginAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositMargin<'info> {
+    /// The owner's margin account
+    #[account(
+        mut,
+        seeds = [MARGIN_ACCOUNT_SEED, owner.key().as_ref()],
+        bump = margin_account.bump,
+    )]
+    pub margin_account: Box<Account<'info, MarginAccount>>,
+
+    /// The shared margin vault
+    #[account(
+        mut,
+        seeds = [MARGIN_VAULT_SEED],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account (source)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawMargin<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The owner's margin account
+    #[account(
+        mut,
+        seeds = [MARGIN_ACCOUNT_SEED, owner.key().as_ref()],
+        bump = margin_account.bump,
+    )]
+    pub margin_account: Box<Account<'info, MarginAccount>>,
+
+    /// The shared margin vault
+    #[account(
+        mut,
+        seeds = [MARGIN_VAULT_SEED],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account (destination)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome: Outcome)]
+pub struct DepositPosition<'info> {
+    /// The market whose outcome tokens are deposited
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The outcome token mint being deposited
+    #[account(
+        constraint = market.load()?.outcome_mint(outcome) == Some(outcome_mint.key()) @ MarketError::InvalidMarginOutcome,
+    )]
+    pub outcome_mint: Box<Account<'info, Mint>>,
+
+    /// The owner's margin account
+    #[account(
+        mut,
+        seeds = [MARGIN_ACCOUNT_SEED, owner.key().as_ref()],
+        bump = margin_account.bump,
+    )]
+    pub margin_account: Box<Account<'info, MarginAccount>>,
+
+    /// Custody of the margin account's tokens of this outcome (created on first deposit)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [MARGIN_CUSTODY_SEED, margin_account.key().as_ref(), outcome_mint.key().as_ref()],
+        bump,
+        token::mint = outcome_mint,
+        token::authority = margin_account,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's outcome token account (source)
+    #[account(
+        mut,
+        constraint = owner_outcome.mint == outcome_mint.key() @ MarketError::MintMismatch,
+    )]
+    pub owner_outcome: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome: Outcome)]
+pub struct WithdrawPosition<'info> {
+    /// The market whose outcome tokens are withdrawn
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The outcome token mint being withdrawn
+    #[account(
+        constraint = market.load()?.outcome_mint(outcome) == Some(outcome_mint.key()) @ MarketError::InvalidMarginOutcome,
+    )]
+    pub outcome_mint: Box<Account<'info, Mint>>,
+
+    /// The owner's margin account
+    #[account(
+        mut,
+        seeds = [MARGIN_ACCOUNT_SEED, owner.key().as_ref()],
+        bump = margin_account.bump,
+    )]
+    pub margin_account: Box<Account<'info, MarginAccount>>,
+
+    /// Custody of the margin account's tokens of this outcome
+    #[account(
+        mut,
+        seeds = [MARGIN_CUSTODY_SEED, margin_account.key().as_ref(), outcome_mint.key().as_ref()],
+        bump,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's outcome token account (destination)
+    #[account(
+        mut,
+        constraint = owner_outcome.mint == outcome_mint.key() @ MarketError::MintMismatch,
+    )]
+    pub owner_outcome: Box<Account<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AttachMarketLink<'info> {
+    /// The owner's margin account
+    #[account(
+        mut,
+        seeds = [MARGIN_ACCOUNT_SEED, owner.key().as_ref()],
+        bump = margin_account.bump,
+    )]
+    pub margin_account: Box<Account<'info, MarginAccount>>,
+
+    /// The link attested by the config authority
+    #[account(
+        seeds = [MARKET_LINK_SEED, link.market_a.as_ref(), link.market_b.as_ref()],
+        bump = link.bump,
+    )]
+    pub link: Account<'info, MarketLink>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DetachMarketLink<'info> {
+    /// The owner's margin account
+    #[account(
+        mut,
+        seeds = [MARGIN_ACCOUNT_SEED, owner.key().as_ref()],
+        bump = margin_account.bump,
+    )]
+    pub margin_account: Box<Account<'info, MarginAccount>>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome: Outcome)]
+pub struct SettleMarginPosition<'info> {
+    /// The resolved market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.status() == MarketStatus::Resolved @ MarketError::MarketNotResolved,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The outcome token mint being settled
+    #[account(
+        mut,
+        constraint = market.load()?.outcome_mint(outcome) == Some(outcome_mint.key()) @ MarketError::InvalidMarginOutcome,
+    )]
+    pub outcome_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The margin account holding the position
+    #[account(
+        mut,
+        seeds = [MARGIN_ACCOUNT_SEED, owner.key().as_ref()],
+        bump = margin_account.bump,
+    )]
+    pub margin_account: Box<Account<'info, MarginAccount>>,
+
+    /// Custody of the settled outcome tokens (closed)
+    #[account(
+        mut,
+        seeds = [MARGIN_CUSTODY_SEED, margin_account.key().as_ref(), outcome_mint.key().as_ref()],
+        bump,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    /// The shared margin vault (receives the redemption)
+    #[account(
+        mut,
+        seeds = [MARGIN_VAULT_SEED],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Margin account owner, receives the custody rent; bound by the margin account seeds
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
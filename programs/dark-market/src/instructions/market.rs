@@ -0,0 +1,605 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::events::{MarketResolved, Redeemed};
+use crate::instructions::{check_attested, check_wrap_bundled, record_trade};
+use crate::state::*;
+use crate::{
+    ESCROW_SEED, MARKET_CONFIG_SEED, MARKET_SEED, NO_MINT_SEED, PORTFOLIO_SEED, SEALED_RESOLUTION_SEED,
+    WRAP_BUNDLING_SEED, YES_MINT_SEED,
+};
+
+pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.authority = ctx.accounts.authority.key();
+    config.guardian = ctx.accounts.authority.key();
+    config.dac_mint = ctx.accounts.dac_mint.key();
+    config.market_count = 0;
+    config.bump = ctx.bumps.config;
+
+    msg!("Market config initialized");
+    msg!("DAC Mint: {}", config.dac_mint);
+    Ok(())
+}
+
+/// Hand the config authority to a new key, such as the governance PDA
+pub fn set_config_authority(ctx: Context<SetConfigAuthority>, new_authority: Pubkey) -> Result<()> {
+    ctx.accounts.config.authority = new_authority;
+
+    msg!("Config authority set to {}", new_authority);
+    Ok(())
+}
+
+pub fn create_market(
+    ctx: Context<CreateMarket>,
+    question: String,
+    end_time: i64,
+    resolver: Pubkey,
+) -> Result<()> {
+    require!(question.len() <= MAX_QUESTION_LEN, MarketError::QuestionTooLong);
+    require!(
+        end_time > Clock::get()?.unix_timestamp,
+        MarketError::InvalidEndTime
+    );
+
+    let market = &mut ctx.accounts.market.load_init()?;
+    open_market(
+        &mut ctx.accounts.config,
+        market,
+        ctx.accounts.creator.key(),
+        resolver,
+        &question,
+        end_time,
+        Pubkey::default(),
+    )?;
+    market.yes_mint = ctx.accounts.yes_mint.key();
+    market.no_mint = ctx.accounts.no_mint.key();
+    market.escrow = ctx.accounts.escrow.key();
+    market.bump = ctx.bumps.market;
+
+    msg!("Market {} created: {}", market.id, ctx.accounts.market.key());
+    Ok(())
+}
+
+/// Fill in a freshly created market and assign it the next market id
+///
+/// The caller sets the mints, escrow and bump.
+pub(crate) fn open_market(
+    config: &mut MarketConfig,
+    market: &mut Market,
+    creator: Pubkey,
+    resolver: Pubkey,
+    question: &str,
+    end_time: i64,
+    series: Pubkey,
+) -> Result<()> {
+    market.id = config.market_count;
+    market.creator = creator;
+    market.resolver = resolver;
+    market.set_question(question);
+    market.end_time = end_time;
+    market.set_status(MarketStatus::Open);
+    market.set_outcome(None);
+    market.total_collateral = 0;
+    market.series = series;
+    market.resolved_at = 0;
+    market.claim_window = 0;
+    market.sweep_to_creator = 0;
+    market.set_attestation(AttestationRequirement::default());
+
+    config.market_count = config.market_count.checked_add(1)
+        .ok_or(MarketError::Overflow)?;
+    Ok(())
+}
+
+pub fn split(ctx: Context<Split>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+    check_wrap_bundled(&ctx.accounts.wrap_bundling, &ctx.accounts.instructions)?;
+    check_attested(&ctx.accounts.market.load()?, ctx.accounts.attestation.as_deref(), &ctx.accounts.user.key())?;
+
+    // Transfer DAC from user to escrow
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_dac.to_account_info(),
+            to: ctx.accounts.escrow.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    // Mint one YES and one NO per DAC
+    let (id, bump) = {
+        let market = ctx.accounts.market.load()?;
+        (market.id, market.bump)
+    };
+    let id_bytes = id.to_le_bytes();
+    let seeds = &[MARKET_SEED, id_bytes.as_ref(), &[bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    for (mint, to) in [
+        (&ctx.accounts.yes_mint, &ctx.accounts.user_yes),
+        (&ctx.accounts.no_mint, &ctx.accounts.user_no),
+    ] {
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: ctx.accounts.market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::mint_to(mint_ctx, amount)?;
+    }
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_add(amount)
+        .ok_or(MarketError::Overflow)?;
+    let market_key = ctx.accounts.market.key();
+    record_trade(ctx.accounts.portfolio.as_mut(), |p| p.record_split(market_key, amount))?;
+
+    msg!("Split {} DAC into YES/NO", amount);
+    Ok(())
+}
+
+pub fn merge(ctx: Context<Merge>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    // Burn one YES and one NO per DAC
+    for (mint, from) in [
+        (&ctx.accounts.yes_mint, &ctx.accounts.user_yes),
+        (&ctx.accounts.no_mint, &ctx.accounts.user_no),
+    ] {
+        let burn_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: mint.to_account_info(),
+                from: from.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        token::burn(burn_ctx, amount)?;
+    }
+
+    transfer_from_escrow(
+        &ctx.accounts.market,
+        &ctx.accounts.escrow,
+        &ctx.accounts.user_dac,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_sub(amount)
+        .ok_or(MarketError::Underflow)?;
+    let market_key = ctx.accounts.market.key();
+    record_trade(ctx.accounts.portfolio.as_mut(), |p| p.record_merge(market_key, amount))?;
+
+    msg!("Merged {} YES/NO into DAC", amount);
+    Ok(())
+}
+
+pub fn resolve_market(ctx: Context<ResolveMarket>, outcome: Outcome) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= market.end_time, MarketError::MarketNotEnded);
+
+    market.resolve(outcome, now);
+
+    emit!(MarketResolved {
+        market: ctx.accounts.market.key(),
+        outcome,
+        resolved_at: now,
+    });
+
+    msg!("Market {} resolved: {:?}", market.id, outcome);
+    Ok(())
+}
+
+pub fn redeem(ctx: Context<Redeem>) -> Result<()> {
+    let yes = ctx.accounts.user_yes.amount;
+    let no = ctx.accounts.user_no.amount;
+    require!(yes > 0 || no > 0, MarketError::ZeroAmount);
+
+    let payout = ctx.accounts.market.load()?.redemption_value(yes, no)
+        .ok_or(MarketError::MarketNotResolved)?;
+
+    // Burn every outcome token the user holds
+    for (mint, from, amount) in [
+        (&ctx.accounts.yes_mint, &ctx.accounts.user_yes, yes),
+        (&ctx.accounts.no_mint, &ctx.accounts.user_no, no),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let burn_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: mint.to_account_info(),
+                from: from.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        token::burn(burn_ctx, amount)?;
+    }
+
+    if payout > 0 {
+        transfer_from_escrow(
+            &ctx.accounts.market,
+            &ctx.accounts.escrow,
+            &ctx.accounts.user_dac,
+            &ctx.accounts.token_program,
+            payout,
+        )?;
+    }
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_sub(payout)
+        .ok_or(MarketError::Underflow)?;
+    let market_key = ctx.accounts.market.key();
+    let yes_value = market.redemption_value(yes, 0).unwrap_or_default();
+    record_trade(ctx.accounts.portfolio.as_mut(), |p| {
+        p.record_sell(market_key, Outcome::Yes, yes, yes_value)?;
+        p.record_sell(market_key, Outcome::No, no, payout - yes_value)
+    })?;
+
+    emit!(Redeemed {
+        market: market_key,
+        user: ctx.accounts.user.key(),
+        yes_burned: yes,
+        no_burned: no,
+        payout,
+    });
+
+    msg!("Redeemed {} YES / {} NO for {} DAC", yes, no, payout);
+    Ok(())
+}
+
+/// Transfer DAC out of a market's escrow, signed by the market PDA
+///
+/// The market must not be loaded by the caller while the transfer runs.
+pub(crate) fn transfer_from_escrow<'info>(
+    market: &AccountLoader<'info, Market>,
+    escrow: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let (id, bump) = {
+        let market = market.load()?;
+        (market.id, market.bump)
+    };
+    let id_bytes = id.to_le_bytes();
+    let seeds = &[MARKET_SEED, id_bytes.as_ref(), &[bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: escrow.to_account_info(),
+            to: to.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    /// The config account to initialize
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MarketConfig::LEN,
+        seeds = [MARKET_CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The DAC mint used as collateral
+    pub dac_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetConfigAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMarket<'info> {
+    /// The market config (assigns the market id)
+    #[account(
+        mut,
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The market account to create
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Market::LEN,
+        seeds = [MARKET_SEED, config.market_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// YES outcome mint, controlled by the market PDA
+    #[account(
+        init,
+        payer = creator,
+        seeds = [YES_MINT_SEED, market.key().as_ref()],
+        bump,
+        mint::decimals = dac_mint.decimals,
+        mint::authority = market,
+    )]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    /// NO outcome mint, controlled by the market PDA
+    #[account(
+        init,
+        payer = creator,
+        seeds = [NO_MINT_SEED, market.key().as_ref()],
+        bump,
+        mint::decimals = dac_mint.decimals,
+        mint::authority = market,
+    )]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// DAC escrow backing the outcome tokens
+    #[account(
+        init,
+        payer = creator,
+        seeds = [ESCROW_SEED, market.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = market,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == config.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Split<'info> {
+    /// The market to split into
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// User's DAC token account (source)
+    #[account(
+        mut,
+        constraint = user_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub user_dac: Box<Account<'info, TokenAccount>>,
+
+    /// User's YES token account (destination)
+    #[account(
+        mut,
+        constraint = user_yes.mint == market.load()?.yes_mint @ MarketError::MintMismatch,
+    )]
+    pub user_yes: Box<Account<'info, TokenAccount>>,
+
+    /// User's NO token account (destination)
+    #[account(
+        mut,
+        constraint = user_no.mint == market.load()?.no_mint @ MarketError::MintMismatch,
+    )]
+    pub user_no: Box<Account<'info, TokenAccount>>,
+
+    /// The user's portfolio, updated when passed
+    #[account(
+        mut,
+        seeds = [PORTFOLIO_SEED, user.key().as_ref()],
+        bump = portfolio.bump,
+    )]
+    pub portfolio: Option<Box<Account<'info, Portfolio>>>,
+
+    /// The wrap bundling setting
+    #[account(
+        seeds = [WRAP_BUNDLING_SEED],
+        bump = wrap_bundling.bump,
+    )]
+    pub wrap_bundling: Box<Account<'info, WrapBundling>>,
+
+    /// CHECK: The instructions sysvar
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: The user's SAS attestation (required when the market requires one), decoded and checked
+    pub attestation: Option<UncheckedAccount<'info>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Merge<'info> {
+    /// The market to merge out of
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// User's DAC token account (destination)
+    #[account(
+        mut,
+        constraint = user_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub user_dac: Box<Account<'info, TokenAccount>>,
+
+    /// User's YES token account (will be burned)
+    #[account(
+        mut,
+        constraint = user_yes.mint == market.load()?.yes_mint @ MarketError::MintMismatch,
+    )]
+    pub user_yes: Box<Account<'info, TokenAccount>>,
+
+    /// User's NO token account (will be burned)
+    #[account(
+        mut,
+        constraint = user_no.mint == market.load()?.no_mint @ MarketError::MintMismatch,
+    )]
+    pub user_no: Box<Account<'info, TokenAccount>>,
+
+    /// The user's portfolio, updated when passed
+    #[account(
+        mut,
+        seeds = [PORTFOLIO_SEED, user.key().as_ref()],
+        bump = portfolio.bump,
+    )]
+    pub portfolio: Option<Box<Account<'info, Portfolio>>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    /// The market to resolve
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = resolver @ MarketError::Unauthorized,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// CHECK: The market's private resolution settings, which must not exist
+    #[account(
+        seeds = [SEALED_RESOLUTION_SEED, market.key().as_ref()],
+        bump,
+        constraint = sealed_resolution.data_is_empty() @ MarketError::ResolvesPrivately,
+    )]
+    pub sealed_resolution: UncheckedAccount<'info>,
+
+    pub resolver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    /// The resolved market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.status() == MarketStatus::Resolved @ MarketError::MarketNotResolved,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// User's DAC token account (destination)
+    #[account(
+        mut,
+        constraint = user_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub user_dac: Box<Account<'info, TokenAccount>>,
+
+    /// User's YES token account (will be burned)
+    #[account(
+        mut,
+        constraint = user_yes.mint == market.load()?.yes_mint @ MarketError::MintMismatch,
+    )]
+    pub user_yes: Box<Account<'info, TokenAccount>>,
+
+    /// User's NO token account (will be burned)
+    #[account(
+        mut,
+        constraint = user_no.mint == market.load()?.no_mint @ MarketError::MintMismatch,
+    )]
+    pub user_no: Box<Account<'info, TokenAccount>>,
+
+    /// The user's portfolio, updated when passed
+    #[account(
+        mut,
+        seeds = [PORTFOLIO_SEED, user.key().as_ref()],
+        bump = portfolio.bump,
+    )]
+    pub portfolio: Option<Box<Account<'info, Portfolio>>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,75 @@
+pub mod arbitration;
+pub mod attestation;
+pub mod auction;
+pub mod circuit_breaker;
+pub mod commit_reveal;
+pub mod compressed_receipt;
+pub mod confidential;
+pub mod creator_escrow;
+pub mod dark_pool;
+pub mod dynamic_fee;
+pub mod fee_sharing;
+pub mod fee_tiers;
+pub mod gauge;
+pub mod groth16;
+pub mod launch;
+pub mod leverage;
+pub mod limit_order;
+pub mod lookup_table;
+pub mod liquidation;
+pub mod margin;
+pub mod market;
+pub mod oracle;
+pub mod parlay;
+pub mod pool;
+pub mod portfolio;
+pub mod position_tree;
+pub mod private_resolution;
+pub mod rebate;
+pub mod receipt;
+pub mod relayer;
+pub mod resolver;
+pub mod series;
+pub mod stealth;
+pub mod sweep;
+pub mod viewing_key;
+pub mod volume;
+pub mod wrap_bundling;
+
+pub use arbitration::*;
+pub use attestation::*;
+pub use auction::*;
+pub use circuit_breaker::*;
+pub use commit_reveal::*;
+pub use compressed_receipt::*;
+pub use confidential::*;
+pub use creator_escrow::*;
+pub use dark_pool::*;
+pub use dynamic_fee::*;
+pub use fee_sharing::*;
+pub use fee_tiers::*;
+pub use gauge::*;
+pub use groth16::*;
+pub use launch::*;
+pub use leverage::*;
+pub use limit_order::*;
+pub use lookup_table::*;
+pub use liquidation::*;
+pub use margin::*;
+pub use market::*;
+pub use oracle::*;
+pub use parlay::*;
+pub use pool::*;
+pub use portfolio::*;
+pub use position_tree::*;
+pub use private_resolution::*;
+pub use rebate::*;
+pub use receipt::*;
+pub use relayer::*;
+pub use resolver::*;
+pub use series::*;
+pub use stealth::*;
+pub use sweep::*;
+pub use viewing_key::*;
+pub use volume::*;
+pub use wrap_bundling::*;
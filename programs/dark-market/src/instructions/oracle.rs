@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{OBSERVATIONS_SEED, POOL_SEED};
+
+/// Time-weighted average price of `outcome` over at least the last `window` seconds
+///
+/// Returned via return data so other programs can read it over CPI.
+pub fn read_twap(ctx: Context<ReadTwap>, outcome: Outcome, window: i64) -> Result<u64> {
+    require!(window > 0, MarketError::InvalidTwapWindow);
+    require!(outcome != Outcome::Invalid, MarketError::InvalidTwapWindow);
+
+    let pool = &ctx.accounts.pool;
+    let yes_twap = ctx.accounts.observations
+        .load()?
+        .twap(Clock::get()?.unix_timestamp, window, pool.price(Outcome::Yes))
+        .ok_or(MarketError::InsufficientObservations)?;
+
+    Ok(match outcome {
+        Outcome::No => PRICE_SCALE - yes_twap,
+        _ => yes_twap,
+    })
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ReadTwap<'info> {
+    /// The pool to read
+    #[account(
+        seeds = [POOL_SEED, pool.market.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// The pool's observation buffer
+    #[account(
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump = observations.load()?.bump,
+    )]
+    pub observations: AccountLoader<'info, PoolObservations>,
+}
@@ -0,0 +1,380 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{MARKET_CONFIG_SEED, PARLAY_ESCROW_SEED, PARLAY_SEED};
+
+pub fn open_parlay<'info>(
+    ctx: Context<'_, '_, 'info, 'info, OpenParlay<'info>>,
+    nonce: u64,
+    legs: Vec<ParlayLeg>,
+    stake: u64,
+    payout: u64,
+) -> Result<()> {
+    require!(stake > 0, MarketError::ZeroAmount);
+    require!(payout > stake, MarketError::InvalidPayout);
+    require!(
+        (MIN_PARLAY_LEGS..=MAX_PARLAY_LEGS).contains(&legs.len()),
+        MarketError::InvalidLegCount
+    );
+    for (i, leg) in legs.iter().enumerate() {
+        require!(leg.outcome != Outcome::Invalid, MarketError::InvalidLegOutcome);
+        require!(
+            legs[..i].iter().all(|other| other.market != leg.market),
+            MarketError::DuplicateLeg
+        );
+    }
+    require_legs_open(&legs, ctx.remaining_accounts)?;
+
+    // Lock the owner's stake
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_dac.to_account_info(),
+            to: ctx.accounts.escrow.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, stake)?;
+
+    let parlay = &mut ctx.accounts.parlay;
+    parlay.owner = ctx.accounts.owner.key();
+    parlay.backer = Pubkey::default();
+    parlay.nonce = nonce;
+    parlay.legs = legs;
+    parlay.stake = stake;
+    parlay.payout = payout;
+    parlay.status = ParlayStatus::Open;
+    parlay.bump = ctx.bumps.parlay;
+    parlay.escrow_bump = ctx.bumps.escrow;
+
+    msg!("Parlay opened: {} legs, stake {}, payout {}", parlay.legs.len(), stake, payout);
+    Ok(())
+}
+
+pub fn back_parlay<'info>(ctx: Context<'_, '_, 'info, 'info, BackParlay<'info>>) -> Result<()> {
+    require_legs_open(&ctx.accounts.parlay.legs, ctx.remaining_accounts)?;
+
+    let backing = ctx.accounts.parlay.backing();
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.backer_dac.to_account_info(),
+            to: ctx.accounts.escrow.to_account_info(),
+            authority: ctx.accounts.backer.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, backing)?;
+
+    let parlay = &mut ctx.accounts.parlay;
+    parlay.backer = ctx.accounts.backer.key();
+    parlay.status = ParlayStatus::Backed;
+
+    msg!("Parlay backed with {} DAC", backing);
+    Ok(())
+}
+
+pub fn cancel_parlay(ctx: Context<CancelParlay>) -> Result<()> {
+    let parlay = &ctx.accounts.parlay;
+    pay_from_parlay_escrow(
+        parlay,
+        &ctx.accounts.escrow,
+        &ctx.accounts.owner_dac,
+        &ctx.accounts.token_program,
+        parlay.stake,
+    )?;
+    close_parlay_escrow(
+        parlay,
+        &ctx.accounts.escrow,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    msg!("Parlay cancelled, refunded {} DAC", parlay.stake);
+    Ok(())
+}
+
+pub fn settle_parlay<'info>(ctx: Context<'_, '_, 'info, 'info, SettleParlay<'info>>) -> Result<()> {
+    let parlay = &ctx.accounts.parlay;
+    let markets = load_leg_markets(&parlay.legs, ctx.remaining_accounts)?;
+    let outcomes = markets
+        .iter()
+        .map(|market| market.outcome().ok_or(error!(MarketError::MarketNotResolved)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let result = parlay.result(&outcomes);
+    let (to_owner, to_backer) = match result {
+        ParlayResult::Won => (parlay.payout, 0),
+        ParlayResult::Lost => (0, parlay.payout),
+        ParlayResult::Refunded => (parlay.stake, parlay.backing()),
+    };
+
+    for (to, amount) in [
+        (&ctx.accounts.owner_dac, to_owner),
+        (&ctx.accounts.backer_dac, to_backer),
+    ] {
+        if amount > 0 {
+            pay_from_parlay_escrow(
+                parlay,
+                &ctx.accounts.escrow,
+                to,
+                &ctx.accounts.token_program,
+                amount,
+            )?;
+        }
+    }
+    close_parlay_escrow(
+        parlay,
+        &ctx.accounts.escrow,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    msg!("Parlay settled: {:?}", result);
+    Ok(())
+}
+
+/// Load the leg markets passed as remaining accounts, in leg order
+fn load_leg_markets<'info>(
+    legs: &[ParlayLeg],
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<Market>> {
+    require!(accounts.len() == legs.len(), MarketError::LegMarketMismatch);
+    legs.iter()
+        .zip(accounts)
+        .map(|(leg, info)| {
+            require_keys_eq!(info.key(), leg.market, MarketError::LegMarketMismatch);
+            let market = AccountLoader::<Market>::try_from(info)?;
+            let market = *market.load()?;
+            Ok(market)
+        })
+        .collect()
+}
+
+/// Every leg market must still be open for trading
+fn require_legs_open<'info>(legs: &[ParlayLeg], accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    for market in load_leg_markets(legs, accounts)? {
+        require!(market.is_trading(now), MarketError::MarketClosed);
+    }
+    Ok(())
+}
+
+fn pay_from_parlay_escrow<'info>(
+    parlay: &Account<'info, Parlay>,
+    escrow: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let nonce_bytes = parlay.nonce.to_le_bytes();
+    let seeds = &[
+        PARLAY_SEED,
+        parlay.owner.as_ref(),
+        nonce_bytes.as_ref(),
+        &[parlay.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: escrow.to_account_info(),
+            to: to.to_account_info(),
+            authority: parlay.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)
+}
+
+fn close_parlay_escrow<'info>(
+    parlay: &Account<'info, Parlay>,
+    escrow: &Account<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let nonce_bytes = parlay.nonce.to_le_bytes();
+    let seeds = &[
+        PARLAY_SEED,
+        parlay.owner.as_ref(),
+        nonce_bytes.as_ref(),
+        &[parlay.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let close_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow.to_account_info(),
+            destination: destination.clone(),
+            authority: parlay.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::close_account(close_ctx)
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct OpenParlay<'info> {
+    /// The market config
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The parlay position to create
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Parlay::LEN,
+        seeds = [PARLAY_SEED, owner.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub parlay: Box<Account<'info, Parlay>>,
+
+    /// DAC escrow holding both sides of the parlay
+    #[account(
+        init,
+        payer = owner,
+        seeds = [PARLAY_ESCROW_SEED, parlay.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = parlay,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == config.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    /// Owner's DAC token account (source of the stake)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == config.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct BackParlay<'info> {
+    /// The parlay to back
+    #[account(
+        mut,
+        constraint = parlay.status == ParlayStatus::Open @ MarketError::ParlayNotOpen,
+    )]
+    pub parlay: Box<Account<'info, Parlay>>,
+
+    /// The parlay's DAC escrow
+    #[account(
+        mut,
+        seeds = [PARLAY_ESCROW_SEED, parlay.key().as_ref()],
+        bump = parlay.escrow_bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// Backer's DAC token account (source of the backing)
+    #[account(
+        mut,
+        constraint = backer_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub backer_dac: Box<Account<'info, TokenAccount>>,
+
+    pub backer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelParlay<'info> {
+    /// The parlay to cancel
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ MarketError::Unauthorized,
+        constraint = parlay.status == ParlayStatus::Open @ MarketError::ParlayNotOpen,
+    )]
+    pub parlay: Box<Account<'info, Parlay>>,
+
+    /// The parlay's DAC escrow
+    #[account(
+        mut,
+        seeds = [PARLAY_ESCROW_SEED, parlay.key().as_ref()],
+        bump = parlay.escrow_bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account (refund destination)
+    #[account(
+        mut,
+        constraint = owner_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SettleParlay<'info> {
+    /// The parlay to settle
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner @ MarketError::Unauthorized,
+        has_one = backer @ MarketError::Unauthorized,
+        constraint = parlay.status == ParlayStatus::Backed @ MarketError::ParlayNotBacked,
+    )]
+    pub parlay: Box<Account<'info, Parlay>>,
+
+    /// The parlay's DAC escrow
+    #[account(
+        mut,
+        seeds = [PARLAY_ESCROW_SEED, parlay.key().as_ref()],
+        bump = parlay.escrow_bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account
+    #[account(
+        mut,
+        constraint = owner_dac.mint == escrow.mint @ MarketError::MintMismatch,
+        constraint = owner_dac.owner == parlay.owner @ MarketError::Unauthorized,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    /// Backer's DAC token account
+    #[account(
+        mut,
+        constraint = backer_dac.mint == escrow.mint @ MarketError::MintMismatch,
+        constraint = backer_dac.owner == parlay.backer @ MarketError::Unauthorized,
+    )]
+    pub backer_dac: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Parlay owner, receives the reclaimed rent
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+
+    /// CHECK: Parlay backer, checked against the parlay
+    pub backer: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
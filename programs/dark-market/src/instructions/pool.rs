@@ -0,0 +1,947 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::events::{PoolUpdated, Trade};
+use crate::instructions::{
+    check_attested, check_wrap_bundled, fee_discount, load_rebate_pool, record_trade, taker_rebate,
+    transfer_from_escrow,
+};
+use crate::state::*;
+use crate::{
+    FEE_SHARING_SEED, FEE_TIERS_SEED, LAUNCH_SEED, LP_MINT_SEED, MARKET_SEED, OBSERVATIONS_SEED,
+    POOL_NO_SEED, POOL_SEED, POOL_YES_SEED, PORTFOLIO_SEED, REBATE_POOL_SEED, WRAP_BUNDLING_SEED,
+};
+
+pub fn create_pool(ctx: Context<CreatePool>, fee_bps: u16, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+    require!(fee_bps <= MAX_POOL_FEE_BPS, MarketError::FeeTooHigh);
+
+    let fee_bps = pool_fee_bps(ctx.accounts.series.as_deref(), &ctx.accounts.market.load()?, fee_bps)?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.market = ctx.accounts.market.key();
+    pool.yes_vault = ctx.accounts.yes_vault.key();
+    pool.no_vault = ctx.accounts.no_vault.key();
+    pool.lp_mint = ctx.accounts.lp_mint.key();
+    pool.bump = ctx.bumps.pool;
+    let observations = &mut ctx.accounts.observations.load_init()?;
+    observations.bump = ctx.bumps.observations;
+    open_pool(&mut ctx.accounts.pool, observations, fee_bps, amount, amount)?;
+
+    let accounts = &ctx.accounts;
+    accounts.vaults().deposit_dac(
+        &accounts.user_dac.to_account_info(),
+        &accounts.user.to_account_info(),
+        &[],
+        amount,
+    )?;
+    mint_lp_shares(
+        &accounts.pool,
+        &accounts.lp_mint,
+        &accounts.user_lp,
+        &accounts.token_program,
+        amount,
+    )?;
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_add(amount)
+        .ok_or(MarketError::Overflow)?;
+
+    msg!("Pool created for market {} with {} DAC", market.id, amount);
+    Ok(())
+}
+
+pub fn add_liquidity(
+    ctx: Context<AddLiquidity>,
+    amount: u64,
+    min_shares: u64,
+    expiry_slot: u64,
+) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+    check_expiry(expiry_slot)?;
+
+    let accounts = &ctx.accounts;
+    let (shares, yes_back, no_back) = accounts.pool
+        .quote_add_liquidity(amount, accounts.lp_mint.supply)
+        .ok_or(MarketError::Overflow)?;
+    require!(shares > 0, MarketError::ZeroAmount);
+    require!(shares >= min_shares, MarketError::SlippageExceeded);
+
+    let vaults = accounts.vaults();
+    vaults.deposit_dac(
+        &accounts.user_dac.to_account_info(),
+        &accounts.user.to_account_info(),
+        &[],
+        amount,
+    )?;
+    vaults.withdraw_outcome(Outcome::Yes, &accounts.user_yes, yes_back)?;
+    vaults.withdraw_outcome(Outcome::No, &accounts.user_no, no_back)?;
+    mint_lp_shares(
+        &accounts.pool,
+        &accounts.lp_mint,
+        &accounts.user_lp,
+        &accounts.token_program,
+        shares,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.yes_reserve = pool.yes_reserve.checked_add(amount - yes_back)
+        .ok_or(MarketError::Overflow)?;
+    pool.no_reserve = pool.no_reserve.checked_add(amount - no_back)
+        .ok_or(MarketError::Overflow)?;
+    emit_pool_update(pool);
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_add(amount)
+        .ok_or(MarketError::Overflow)?;
+
+    msg!("Added {} DAC of liquidity for {} shares", amount, shares);
+    Ok(())
+}
+
+pub fn remove_liquidity(
+    ctx: Context<RemoveLiquidity>,
+    shares: u64,
+    min_yes_out: u64,
+    min_no_out: u64,
+    expiry_slot: u64,
+) -> Result<()> {
+    require!(shares > 0, MarketError::ZeroAmount);
+    check_expiry(expiry_slot)?;
+
+    let accounts = &ctx.accounts;
+    let (yes_out, no_out) = accounts.pool
+        .quote_remove_liquidity(shares, accounts.lp_mint.supply)
+        .ok_or(MarketError::Underflow)?;
+    require!(
+        yes_out >= min_yes_out && no_out >= min_no_out,
+        MarketError::SlippageExceeded
+    );
+
+    let burn_ctx = CpiContext::new(
+        accounts.token_program.to_account_info(),
+        Burn {
+            mint: accounts.lp_mint.to_account_info(),
+            from: accounts.user_lp.to_account_info(),
+            authority: accounts.user.to_account_info(),
+        },
+    );
+    token::burn(burn_ctx, shares)?;
+
+    let vaults = accounts.vaults();
+    vaults.withdraw_outcome(Outcome::Yes, &accounts.user_yes, yes_out)?;
+    vaults.withdraw_outcome(Outcome::No, &accounts.user_no, no_out)?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.yes_reserve -= yes_out;
+    pool.no_reserve -= no_out;
+    emit_pool_update(pool);
+
+    msg!("Removed {} shares for {} YES / {} NO", shares, yes_out, no_out);
+    Ok(())
+}
+
+pub fn buy(
+    ctx: Context<Swap>,
+    outcome: Outcome,
+    amount_in: u64,
+    min_amount_out: u64,
+    expiry_slot: u64,
+) -> Result<()> {
+    require!(amount_in > 0, MarketError::ZeroAmount);
+    check_expiry(expiry_slot)?;
+    check_wrap_bundled(&ctx.accounts.wrap_bundling, &ctx.accounts.instructions)?;
+    check_attested(&ctx.accounts.market.load()?, ctx.accounts.attestation.as_deref(), &ctx.accounts.user.key())?;
+    ctx.accounts.pool.refresh_fee(&ctx.accounts.observations.load()?, Clock::get()?.unix_timestamp);
+
+    let accounts = &ctx.accounts;
+    let discount = fee_discount(
+        accounts.fee_tiers.as_deref(),
+        accounts.stake.as_deref(),
+        &accounts.user.key(),
+    )?;
+    let out = accounts.pool.quote_buy(outcome, amount_in, discount)
+        .ok_or(MarketError::Overflow)?;
+    require!(out > 0, MarketError::ZeroAmount);
+    require!(out >= min_amount_out, MarketError::SlippageExceeded);
+
+    // The maker rebate and staker shares of the fee skip the reserves
+    let fee = accounts.pool.fee(amount_in, discount);
+    let rebate_pool = load_rebate_pool(&accounts.rebate_pool, accounts.rebate_vault.as_ref())?;
+    let rebate = taker_rebate(rebate_pool.as_ref(), fee);
+    let shared = accounts.fee_sharing.share(fee - rebate);
+    let invested = amount_in - rebate - shared;
+    if rebate > 0 {
+        let rebate_vault = accounts.rebate_vault.as_ref().ok_or(MarketError::InvalidRebateVault)?;
+        let transfer_ctx = CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.user_dac.to_account_info(),
+                to: rebate_vault.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+        );
+        token::transfer(transfer_ctx, rebate)?;
+    }
+    if shared > 0 {
+        let transfer_ctx = CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.user_dac.to_account_info(),
+                to: accounts.fee_vault.to_account_info(),
+                authority: accounts.user.to_account_info(),
+            },
+        );
+        token::transfer(transfer_ctx, shared)?;
+    }
+
+    let vaults = accounts.vaults();
+    vaults.deposit_dac(
+        &accounts.user_dac.to_account_info(),
+        &accounts.user.to_account_info(),
+        &[],
+        invested,
+    )?;
+    vaults.withdraw_outcome(outcome, &accounts.user_outcome, out)?;
+
+    let price_before = ctx.accounts.pool.price(Outcome::Yes);
+    ctx.accounts.pool.apply_buy(outcome, invested, out)
+        .ok_or(MarketError::Overflow)?;
+    track_price_move(&mut ctx.accounts.pool, &mut ctx.accounts.observations.load_mut()?, price_before)?;
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_add(invested)
+        .ok_or(MarketError::Overflow)?;
+    let market_key = ctx.accounts.market.key();
+    record_trade(ctx.accounts.portfolio.as_mut(), |p| {
+        p.record_buy(market_key, outcome, out, amount_in)
+    })?;
+
+    emit!(Trade {
+        market: market_key,
+        user: ctx.accounts.user.key(),
+        outcome,
+        is_buy: true,
+        amount_in,
+        amount_out: out,
+        fee,
+    });
+
+    msg!("Bought {} {:?} for {} DAC", out, outcome, amount_in);
+    Ok(())
+}
+
+pub fn sell(
+    ctx: Context<Swap>,
+    outcome: Outcome,
+    amount_in: u64,
+    min_amount_out: u64,
+    expiry_slot: u64,
+) -> Result<()> {
+    require!(amount_in > 0, MarketError::ZeroAmount);
+    check_expiry(expiry_slot)?;
+    ctx.accounts.pool.refresh_fee(&ctx.accounts.observations.load()?, Clock::get()?.unix_timestamp);
+
+    let accounts = &ctx.accounts;
+    let discount = fee_discount(
+        accounts.fee_tiers.as_deref(),
+        accounts.stake.as_deref(),
+        &accounts.user.key(),
+    )?;
+    let released = accounts.pool.quote_sell_gross(outcome, amount_in)
+        .ok_or(MarketError::Overflow)?;
+    let fee = accounts.pool.fee(released, discount);
+    let out = released - fee;
+    require!(out > 0, MarketError::ZeroAmount);
+    require!(out >= min_amount_out, MarketError::SlippageExceeded);
+
+    // The maker rebate and staker shares of the fee leave the reserves alongside the proceeds
+    let rebate_pool = load_rebate_pool(&accounts.rebate_pool, accounts.rebate_vault.as_ref())?;
+    let rebate = taker_rebate(rebate_pool.as_ref(), fee);
+    let shared = accounts.fee_sharing.share(fee - rebate);
+    let withdrawn = out + rebate + shared;
+
+    let vaults = accounts.vaults();
+    vaults.deposit_outcome(
+        outcome,
+        &accounts.user_outcome.to_account_info(),
+        &accounts.user.to_account_info(),
+        &[],
+        amount_in,
+    )?;
+    vaults.withdraw_dac(&accounts.user_dac, out)?;
+    if rebate > 0 {
+        let rebate_vault = accounts.rebate_vault.as_ref().ok_or(MarketError::InvalidRebateVault)?;
+        vaults.withdraw_dac(rebate_vault, rebate)?;
+    }
+    vaults.withdraw_dac(&accounts.fee_vault, shared)?;
+
+    let price_before = ctx.accounts.pool.price(Outcome::Yes);
+    ctx.accounts.pool.apply_sell(outcome, amount_in, withdrawn)
+        .ok_or(MarketError::Underflow)?;
+    track_price_move(&mut ctx.accounts.pool, &mut ctx.accounts.observations.load_mut()?, price_before)?;
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_sub(withdrawn)
+        .ok_or(MarketError::Underflow)?;
+    let market_key = ctx.accounts.market.key();
+    record_trade(ctx.accounts.portfolio.as_mut(), |p| {
+        p.record_sell(market_key, outcome, amount_in, out)
+    })?;
+
+    emit!(Trade {
+        market: market_key,
+        user: ctx.accounts.user.key(),
+        outcome,
+        is_buy: false,
+        amount_in,
+        amount_out: out,
+        fee,
+    });
+
+    msg!("Sold {} {:?} for {} DAC", amount_in, outcome, out);
+    Ok(())
+}
+
+/// Reject a trade submitted for execution no later than `expiry_slot` once that slot has passed
+pub(crate) fn check_expiry(expiry_slot: u64) -> Result<()> {
+    require!(Clock::get()?.slot <= expiry_slot, MarketError::TradeExpired);
+    Ok(())
+}
+
+/// Swap fee of a new pool on `market`: a series market must pass its series and inherits its fee
+pub(crate) fn pool_fee_bps(
+    series: Option<&Account<'_, MarketSeries>>,
+    market: &Market,
+    fee_bps: u16,
+) -> Result<u16> {
+    require!(
+        series.map(|s| s.key()).unwrap_or_default() == market.series,
+        MarketError::SeriesMismatch
+    );
+    Ok(series.map_or(fee_bps, |s| s.fee_bps))
+}
+
+/// Fill in a freshly created pool's reserves, fee and breaker and start its observations
+///
+/// The caller sets the market, vaults, LP mint and bumps.
+pub(crate) fn open_pool(
+    pool: &mut Account<'_, Pool>,
+    observations: &mut PoolObservations,
+    fee_bps: u16,
+    yes_reserve: u64,
+    no_reserve: u64,
+) -> Result<()> {
+    pool.yes_reserve = yes_reserve;
+    pool.no_reserve = no_reserve;
+    pool.fee_bps = fee_bps;
+    pool.breaker = CircuitBreaker::new(
+        DEFAULT_BREAKER_THRESHOLD_BPS,
+        DEFAULT_BREAKER_WINDOW_SLOTS,
+        DEFAULT_BREAKER_COOLOFF_SLOTS,
+    );
+    pool.dynamic_fee = DynamicFee::default();
+    emit_pool_update(pool);
+
+    observations.pool = pool.key();
+    observations.index = 0;
+    observations.count = 1;
+    observations.observations[0] = Observation {
+        timestamp: Clock::get()?.unix_timestamp,
+        yes_price_cumulative: 0,
+    };
+    Ok(())
+}
+
+/// Checkpoint the oracle and circuit breaker after a trade that started at `price_before`
+pub(crate) fn track_price_move(
+    pool: &mut Pool,
+    observations: &mut PoolObservations,
+    price_before: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    observations.record(clock.unix_timestamp, price_before);
+    if pool.breaker.observe(clock.slot, price_before, pool.price(Outcome::Yes)) {
+        msg!("Circuit breaker tripped, trading halted until slot {}", pool.breaker.halted_until);
+    }
+    emit_pool_update(pool);
+    Ok(())
+}
+
+/// Publish the pool's reserves after they changed
+pub(crate) fn emit_pool_update(pool: &Pool) {
+    emit!(PoolUpdated {
+        market: pool.market,
+        yes_reserve: pool.yes_reserve,
+        no_reserve: pool.no_reserve,
+        yes_price: pool.price(Outcome::Yes),
+    });
+}
+
+pub(crate) fn mint_lp_shares<'info>(
+    pool: &Account<'info, Pool>,
+    lp_mint: &Account<'info, Mint>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[POOL_SEED, pool.market.as_ref(), &[pool.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let mint_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        MintTo {
+            mint: lp_mint.to_account_info(),
+            to: to.to_account_info(),
+            authority: pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::mint_to(mint_ctx, amount)
+}
+
+/// The accounts every pool trade moves tokens through
+///
+/// Reserve bookkeeping on `Pool` and `Market` is left to the caller, which
+/// must not hold the market loaded while moving tokens.
+pub(crate) struct PoolVaults<'a, 'info> {
+    pub market: &'a AccountLoader<'info, Market>,
+    pub pool: &'a Account<'info, Pool>,
+    pub escrow: &'a Account<'info, TokenAccount>,
+    pub yes_mint: &'a Account<'info, Mint>,
+    pub no_mint: &'a Account<'info, Mint>,
+    pub yes_vault: &'a Account<'info, TokenAccount>,
+    pub no_vault: &'a Account<'info, TokenAccount>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+impl<'info> PoolVaults<'_, 'info> {
+    fn vault(&self, outcome: Outcome) -> &Account<'info, TokenAccount> {
+        match outcome {
+            Outcome::No => self.no_vault,
+            _ => self.yes_vault,
+        }
+    }
+
+    /// Move DAC into the market escrow and mint the matching YES+NO into the reserves
+    pub fn deposit_dac(
+        &self,
+        from: &AccountInfo<'info>,
+        authority: &AccountInfo<'info>,
+        authority_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<()> {
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: from.clone(),
+                to: self.escrow.to_account_info(),
+                authority: authority.clone(),
+            },
+            authority_seeds,
+        );
+        token::transfer(transfer_ctx, amount)?;
+
+        let (id, bump) = {
+            let market = self.market.load()?;
+            (market.id, market.bump)
+        };
+        let id_bytes = id.to_le_bytes();
+        let seeds = &[MARKET_SEED, id_bytes.as_ref(), &[bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        for (mint, to) in [(self.yes_mint, self.yes_vault), (self.no_mint, self.no_vault)] {
+            let mint_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: self.market.to_account_info(),
+                },
+                signer_seeds,
+            );
+            token::mint_to(mint_ctx, amount)?;
+        }
+        Ok(())
+    }
+
+    /// Move outcome tokens into the reserve
+    pub fn deposit_outcome(
+        &self,
+        outcome: Outcome,
+        from: &AccountInfo<'info>,
+        authority: &AccountInfo<'info>,
+        authority_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<()> {
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: from.clone(),
+                to: self.vault(outcome).to_account_info(),
+                authority: authority.clone(),
+            },
+            authority_seeds,
+        );
+        token::transfer(transfer_ctx, amount)
+    }
+
+    /// Pay outcome tokens out of the reserve
+    pub fn withdraw_outcome(
+        &self,
+        outcome: Outcome,
+        to: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let seeds = &[POOL_SEED, self.pool.market.as_ref(), &[self.pool.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.vault(outcome).to_account_info(),
+                to: to.to_account_info(),
+                authority: self.pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, amount)
+    }
+
+    /// Burn YES+NO pairs from the reserves and pay the released DAC out of escrow
+    pub fn withdraw_dac(&self, to: &Account<'info, TokenAccount>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let seeds = &[POOL_SEED, self.pool.market.as_ref(), &[self.pool.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        for (mint, from) in [(self.yes_mint, self.yes_vault), (self.no_mint, self.no_vault)] {
+            let burn_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: mint.to_account_info(),
+                    from: from.to_account_info(),
+                    authority: self.pool.to_account_info(),
+                },
+                signer_seeds,
+            );
+            token::burn(burn_ctx, amount)?;
+        }
+        transfer_from_escrow(self.market, self.escrow, to, self.token_program, amount)
+    }
+}
+
+macro_rules! impl_pool_vaults {
+    ($($accounts:ident),*) => {$(
+        impl<'info> $accounts<'info> {
+            pub(crate) fn vaults(&self) -> PoolVaults<'_, 'info> {
+                PoolVaults {
+                    market: &self.market,
+                    pool: &self.pool,
+                    escrow: &self.escrow,
+                    yes_mint: &self.yes_mint,
+                    no_mint: &self.no_mint,
+                    yes_vault: &self.yes_vault,
+                    no_vault: &self.no_vault,
+                    token_program: &self.token_program,
+                }
+            }
+        }
+    )*};
+}
+pub(crate) use impl_pool_vaults;
+
+impl_pool_vaults!(CreatePool, AddLiquidity, RemoveLiquidity, Swap);
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreatePool<'info> {
+    /// The market to create a pool for
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The series that opened the market, if any (its fee overrides `fee_bps`)
+    pub series: Option<Box<Account<'info, MarketSeries>>>,
+
+    /// CHECK: The market's launch PDA; a market with a launch opens its pool through it
+    #[account(
+        seeds = [LAUNCH_SEED, market.key().as_ref()],
+        bump,
+        constraint = launch.data_is_empty() @ MarketError::LaunchActive,
+    )]
+    pub launch: UncheckedAccount<'info>,
+
+    /// The pool account to create
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Pool::LEN,
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The pool's price observation buffer
+    #[account(
+        init,
+        payer = user,
+        space = 8 + PoolObservations::LEN,
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub observations: AccountLoader<'info, PoolObservations>,
+
+    /// Vault holding the YES reserve
+    #[account(
+        init,
+        payer = user,
+        seeds = [POOL_YES_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = yes_mint,
+        token::authority = pool,
+    )]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Vault holding the NO reserve
+    #[account(
+        init,
+        payer = user,
+        seeds = [POOL_NO_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = no_mint,
+        token::authority = pool,
+    )]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    /// LP share mint
+    #[account(
+        init,
+        payer = user,
+        seeds = [LP_MINT_SEED, pool.key().as_ref()],
+        bump,
+        mint::decimals = yes_mint.decimals,
+        mint::authority = pool,
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// User's DAC token account (initial liquidity)
+    #[account(
+        mut,
+        constraint = user_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub user_dac: Box<Account<'info, TokenAccount>>,
+
+    /// User's LP token account
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_mint,
+        associated_token::authority = user,
+    )]
+    pub user_lp: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    /// The pool's market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The pool to add liquidity to
+    #[account(
+        mut,
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump = pool.bump,
+        has_one = yes_vault @ MarketError::MintMismatch,
+        has_one = no_vault @ MarketError::MintMismatch,
+        has_one = lp_mint @ MarketError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(mut)]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// User's DAC token account (source)
+    #[account(
+        mut,
+        constraint = user_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub user_dac: Box<Account<'info, TokenAccount>>,
+
+    /// User's YES token account (receives unmatched YES)
+    #[account(
+        mut,
+        constraint = user_yes.mint == market.load()?.yes_mint @ MarketError::MintMismatch,
+    )]
+    pub user_yes: Box<Account<'info, TokenAccount>>,
+
+    /// User's NO token account (receives unmatched NO)
+    #[account(
+        mut,
+        constraint = user_no.mint == market.load()?.no_mint @ MarketError::MintMismatch,
+    )]
+    pub user_no: Box<Account<'info, TokenAccount>>,
+
+    /// User's LP token account
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_mint,
+        associated_token::authority = user,
+    )]
+    pub user_lp: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidity<'info> {
+    /// The pool's market
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The pool to remove liquidity from
+    #[account(
+        mut,
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump = pool.bump,
+        has_one = yes_vault @ MarketError::MintMismatch,
+        has_one = no_vault @ MarketError::MintMismatch,
+        has_one = lp_mint @ MarketError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(mut)]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// User's YES token account (destination)
+    #[account(
+        mut,
+        constraint = user_yes.mint == market.load()?.yes_mint @ MarketError::MintMismatch,
+    )]
+    pub user_yes: Box<Account<'info, TokenAccount>>,
+
+    /// User's NO token account (destination)
+    #[account(
+        mut,
+        constraint = user_no.mint == market.load()?.no_mint @ MarketError::MintMismatch,
+    )]
+    pub user_no: Box<Account<'info, TokenAccount>>,
+
+    /// User's LP token account (will be burned)
+    #[account(
+        mut,
+        constraint = user_lp.mint == lp_mint.key() @ MarketError::MintMismatch,
+    )]
+    pub user_lp: Box<Account<'info, TokenAccount>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome: Outcome)]
+pub struct Swap<'info> {
+    /// The pool's market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_trading(Clock::get()?.unix_timestamp) @ MarketError::MarketClosed,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The pool to trade against
+    #[account(
+        mut,
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump = pool.bump,
+        has_one = yes_vault @ MarketError::MintMismatch,
+        has_one = no_vault @ MarketError::MintMismatch,
+        constraint = !pool.breaker.is_halted(Clock::get()?.slot) @ MarketError::TradingHalted,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The pool's price observation buffer
+    #[account(
+        mut,
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump = observations.load()?.bump,
+    )]
+    pub observations: AccountLoader<'info, PoolObservations>,
+
+    #[account(mut)]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// User's DAC token account
+    #[account(
+        mut,
+        constraint = user_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub user_dac: Box<Account<'info, TokenAccount>>,
+
+    /// User's token account for the traded outcome
+    #[account(
+        mut,
+        constraint = market.load()?.outcome_mint(outcome) == Some(user_outcome.mint) @ MarketError::MintMismatch,
+    )]
+    pub user_outcome: Box<Account<'info, TokenAccount>>,
+
+    /// Swap fee discount tiers, applied when a stake account is also passed
+    #[account(
+        seeds = [FEE_TIERS_SEED],
+        bump = fee_tiers.bump,
+    )]
+    pub fee_tiers: Option<Box<Account<'info, FeeTiers>>>,
+
+    /// CHECK: The trader's stake account, parsed against the tiers' staking program
+    pub stake: Option<UncheckedAccount<'info>>,
+
+    /// The user's portfolio, updated when passed
+    #[account(
+        mut,
+        seeds = [PORTFOLIO_SEED, user.key().as_ref()],
+        bump = portfolio.bump,
+    )]
+    pub portfolio: Option<Box<Account<'info, Portfolio>>>,
+
+    /// CHECK: The pool's maker rebate settings, taking a share of the fee once configured
+    #[account(
+        seeds = [REBATE_POOL_SEED, pool.key().as_ref()],
+        bump,
+    )]
+    pub rebate_pool: UncheckedAccount<'info>,
+
+    /// DAC vault receiving the maker rebate share of the fee, required once rebates are configured
+    #[account(mut)]
+    pub rebate_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The wrap bundling setting
+    #[account(
+        seeds = [WRAP_BUNDLING_SEED],
+        bump = wrap_bundling.bump,
+    )]
+    pub wrap_bundling: Box<Account<'info, WrapBundling>>,
+
+    /// The stakers' share of swap fees
+    #[account(
+        seeds = [FEE_SHARING_SEED],
+        bump = fee_sharing.bump,
+        has_one = fee_vault @ MarketError::InvalidFeeVault,
+    )]
+    pub fee_sharing: Box<Account<'info, FeeSharing>>,
+
+    /// DAC vault of the stakers' fee distributor
+    #[account(mut)]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The instructions sysvar
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: The trader's SAS attestation (required when the market requires one), checked on buys
+    pub attestation: Option<UncheckedAccount<'info>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::PORTFOLIO_SEED;
+
+pub fn open_portfolio(ctx: Context<OpenPortfolio>) -> Result<()> {
+    let portfolio = &mut ctx.accounts.portfolio;
+    portfolio.owner = ctx.accounts.owner.key();
+    portfolio.realized_pnl = 0;
+    portfolio.volume = 0;
+    portfolio.positions = Vec::new();
+    portfolio.bump = ctx.bumps.portfolio;
+
+    msg!("Portfolio opened for {}", portfolio.owner);
+    Ok(())
+}
+
+/// Unrealized P&L of a portfolio's open positions at pool spot prices (view)
+///
+/// The pools of every open position are passed as remaining accounts, in
+/// any order. Returned via return data like `read_twap`.
+pub fn read_unrealized_pnl<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReadPortfolio<'info>>,
+) -> Result<i64> {
+    let pools = ctx.remaining_accounts
+        .iter()
+        .map(Account::<Pool>::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut pnl: i64 = 0;
+    for position in &ctx.accounts.portfolio.positions {
+        let pool = pools.iter()
+            .find(|pool| pool.market == position.market)
+            .ok_or(MarketError::PortfolioPoolMissing)?;
+        pnl = pnl.checked_add(position.unrealized_pnl(pool.price(Outcome::Yes)))
+            .ok_or(MarketError::Overflow)?;
+    }
+    Ok(pnl)
+}
+
+/// Apply `record` to the trader's portfolio, if one was passed
+pub(crate) fn record_trade(
+    portfolio: Option<&mut Box<Account<'_, Portfolio>>>,
+    record: impl FnOnce(&mut Portfolio) -> Option<()>,
+) -> Result<()> {
+    if let Some(portfolio) = portfolio {
+        let portfolio: &mut Portfolio = portfolio;
+        record(portfolio).ok_or(MarketError::PortfolioFull)?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct OpenPortfolio<'info> {
+    /// The portfolio to create
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Portfolio::LEN,
+        seeds = [PORTFOLIO_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Box<Account<'info, Portfolio>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReadPortfolio<'info> {
+    /// The portfolio to value
+    #[account(
+        seeds = [PORTFOLIO_SEED, portfolio.owner.as_ref()],
+        bump = portfolio.bump,
+    )]
+    pub portfolio: Box<Account<'info, Portfolio>>,
+}
@@ -0,0 +1,728 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+
+use crate::bubblegum::{ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID};
+use crate::compression::{self, InitEmptyMerkleTreeArgs, ReplaceLeafArgs, EMPTY_LEAF};
+use crate::errors::MarketError;
+use crate::events::{BatchSettled, CompressedPositionChanged, Redeemed};
+use crate::instructions::transfer_from_escrow;
+use crate::state::*;
+use crate::{MARKET_SEED, POSITION_TREE_SEED, POSITION_TREE_VAULT_SEED};
+
+/// Give a market a concurrent Merkle tree of compressed positions (market creator only)
+///
+/// `merkle_tree` must already be allocated and owned by the account
+/// compression program, sized for exactly `max_depth`, `max_buffer_size`
+/// and `canopy_depth`: a deeper canopy costs more rent up front and saves
+/// every later caller as many proof nodes. The buffer must hold at least
+/// `MIN_POSITION_TREE_BUFFER_SIZE` changes for `settle_batch` to go through.
+pub fn enable_position_tree(
+    ctx: Context<EnablePositionTree>,
+    max_depth: u32,
+    max_buffer_size: u32,
+    canopy_depth: u32,
+) -> Result<()> {
+    require!(
+        (MIN_POSITION_TREE_DEPTH..=MAX_POSITION_TREE_DEPTH).contains(&max_depth) && canopy_depth < max_depth,
+        MarketError::InvalidPositionTree
+    );
+    require!(max_buffer_size >= MIN_POSITION_TREE_BUFFER_SIZE, MarketError::InvalidPositionTree);
+    let expected_len = compression::tree_account_len(max_depth, max_buffer_size, canopy_depth);
+    require!(ctx.accounts.merkle_tree.data_len() == expected_len, MarketError::InvalidPositionTree);
+
+    let position_tree = &mut ctx.accounts.position_tree;
+    position_tree.market = ctx.accounts.market.key();
+    position_tree.merkle_tree = ctx.accounts.merkle_tree.key();
+    position_tree.max_depth = max_depth;
+    position_tree.canopy_depth = canopy_depth;
+    position_tree.appended = 0;
+    position_tree.yes_amount = 0;
+    position_tree.no_amount = 0;
+    position_tree.bump = ctx.bumps.position_tree;
+
+    // The position tree PDA becomes the tree's authority, the only one that can change its leaves
+    let ix = compression::init_empty_merkle_tree(
+        &position_tree.merkle_tree,
+        &position_tree.key(),
+        InitEmptyMerkleTreeArgs { max_depth, max_buffer_size },
+    );
+    invoke_as_tree(
+        &ix,
+        position_tree,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.log_wrapper,
+        &ctx.accounts.compression_program,
+        &[],
+    )?;
+
+    msg!(
+        "Market #{} keeps compressed positions in tree {} (canopy depth {})",
+        ctx.accounts.market.load()?.id,
+        position_tree.merkle_tree,
+        canopy_depth
+    );
+    Ok(())
+}
+
+/// Deposit outcome tokens into a new compressed position appended to the tree
+pub fn deposit_compressed_position(
+    ctx: Context<DepositCompressedPosition>,
+    outcome: Outcome,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_outcome.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let owner_key = ctx.accounts.owner.key();
+    let position_tree = &mut ctx.accounts.position_tree;
+    let index = position_tree.appended;
+    let leaf = PositionTree::leaf(&owner_key, outcome, amount, index);
+    let ix = compression::append(&position_tree.merkle_tree, &position_tree.key(), leaf);
+    invoke_as_tree(
+        &ix,
+        position_tree,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.log_wrapper,
+        &ctx.accounts.compression_program,
+        &[],
+    )?;
+
+    position_tree.appended = index.checked_add(1).ok_or(MarketError::Overflow)?;
+    let wrapped = position_tree.wrapped(outcome);
+    *wrapped = wrapped.checked_add(amount).ok_or(MarketError::Overflow)?;
+
+    emit!(CompressedPositionChanged {
+        market: position_tree.market,
+        merkle_tree: position_tree.merkle_tree,
+        index,
+        owner: owner_key,
+        outcome,
+        amount,
+        leaf,
+    });
+    msg!("Deposited {} outcome tokens into compressed position #{}", amount, index);
+    Ok(())
+}
+
+/// Resize a compressed position to `new_amount`, depositing or withdrawing the difference
+///
+/// The owner restates the position's side, amount and index and passes the
+/// leaf's proof below the canopy as remaining accounts. Resizing to zero
+/// empties the leaf and closes the position.
+pub fn update_compressed_position<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateCompressedPosition<'info>>,
+    root: [u8; 32],
+    outcome: Outcome,
+    amount: u64,
+    index: u32,
+    new_amount: u64,
+) -> Result<()> {
+    require!(new_amount != amount, MarketError::ZeroAmount);
+
+    let owner_key = ctx.accounts.owner.key();
+    let new_leaf = if new_amount == 0 {
+        EMPTY_LEAF
+    } else {
+        PositionTree::leaf(&owner_key, outcome, new_amount, index)
+    };
+    replace_position_leaf(
+        &ctx.accounts.position_tree,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.log_wrapper,
+        &ctx.accounts.compression_program,
+        ctx.remaining_accounts,
+        ReplaceLeafArgs {
+            root,
+            previous_leaf: PositionTree::leaf(&owner_key, outcome, amount, index),
+            new_leaf,
+            index,
+        },
+    )?;
+
+    if new_amount > amount {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner_outcome.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        );
+        token::transfer(transfer_ctx, new_amount - amount)?;
+    } else {
+        let position_tree = &ctx.accounts.position_tree;
+        let seeds = &[POSITION_TREE_SEED, position_tree.market.as_ref(), &[position_tree.bump]];
+        let signer_seeds = &[&seeds[..]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.owner_outcome.to_account_info(),
+                authority: position_tree.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, amount - new_amount)?;
+    }
+
+    let position_tree = &mut ctx.accounts.position_tree;
+    let wrapped = position_tree.wrapped(outcome);
+    *wrapped = wrapped
+        .checked_add(new_amount)
+        .and_then(|total| total.checked_sub(amount))
+        .ok_or(MarketError::Overflow)?;
+
+    emit!(CompressedPositionChanged {
+        market: position_tree.market,
+        merkle_tree: position_tree.merkle_tree,
+        index,
+        owner: owner_key,
+        outcome,
+        amount: new_amount,
+        leaf: new_leaf,
+    });
+    msg!("Compressed position #{} resized from {} to {}", index, amount, new_amount);
+    Ok(())
+}
+
+/// Redeem a compressed position of a resolved market for DAC
+///
+/// The holder proves the leaf as in `update_compressed_position`; it is
+/// emptied, its outcome tokens burned from the vault and the payout sent
+/// from the escrow.
+pub fn redeem_compressed_position<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RedeemCompressedPosition<'info>>,
+    root: [u8; 32],
+    outcome: Outcome,
+    amount: u64,
+    index: u32,
+) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    let (yes, no) = if outcome == Outcome::Yes { (amount, 0) } else { (0, amount) };
+    let payout = ctx.accounts.market.load()?.redemption_value(yes, no)
+        .ok_or(MarketError::MarketNotResolved)?;
+
+    let holder_key = ctx.accounts.holder.key();
+    replace_position_leaf(
+        &ctx.accounts.position_tree,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.log_wrapper,
+        &ctx.accounts.compression_program,
+        ctx.remaining_accounts,
+        ReplaceLeafArgs {
+            root,
+            previous_leaf: PositionTree::leaf(&holder_key, outcome, amount, index),
+            new_leaf: EMPTY_LEAF,
+            index,
+        },
+    )?;
+
+    let position_tree = &ctx.accounts.position_tree;
+    let seeds = &[POSITION_TREE_SEED, position_tree.market.as_ref(), &[position_tree.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let burn_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.outcome_mint.to_account_info(),
+            from: ctx.accounts.vault.to_account_info(),
+            authority: position_tree.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::burn(burn_ctx, amount)?;
+
+    if payout > 0 {
+        transfer_from_escrow(
+            &ctx.accounts.market,
+            &ctx.accounts.escrow,
+            &ctx.accounts.holder_dac,
+            &ctx.accounts.token_program,
+            payout,
+        )?;
+    }
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_sub(payout)
+        .ok_or(MarketError::Underflow)?;
+    let position_tree = &mut ctx.accounts.position_tree;
+    let wrapped = position_tree.wrapped(outcome);
+    *wrapped = wrapped.checked_sub(amount).ok_or(MarketError::Underflow)?;
+
+    emit!(CompressedPositionChanged {
+        market: position_tree.market,
+        merkle_tree: position_tree.merkle_tree,
+        index,
+        owner: holder_key,
+        outcome,
+        amount: 0,
+        leaf: EMPTY_LEAF,
+    });
+    emit!(Redeemed {
+        market: position_tree.market,
+        user: holder_key,
+        yes_burned: yes,
+        no_burned: no,
+        payout,
+    });
+    msg!("Redeemed compressed position #{} for {} DAC", index, payout);
+    Ok(())
+}
+
+/// Redeem a batch of compressed positions of a resolved market for their owners (permissionless)
+///
+/// Lets a keeper pay out a market without its participants signing: each
+/// payout can only go to the leaf's owner. Remaining accounts hold, per
+/// position, the owner's DAC token account followed by the leaf's proof
+/// below the canopy. Every proof is read against `root`, which the tree
+/// still accepts while the batch fits in its change log buffer; a position
+/// already redeemed fails the whole batch.
+pub fn settle_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleBatch<'info>>,
+    root: [u8; 32],
+    positions: Vec<SettledPosition>,
+) -> Result<()> {
+    require!(
+        !positions.is_empty() && positions.len() <= MAX_SETTLE_BATCH,
+        MarketError::InvalidSettleBatch
+    );
+    let stride = 1 + ctx.accounts.position_tree.proof_len();
+    require!(
+        ctx.remaining_accounts.len() == stride * positions.len(),
+        MarketError::InvalidSettleBatch
+    );
+
+    let market_key = ctx.accounts.market.key();
+    let (mut yes_burned, mut no_burned, mut total_payout) = (0u64, 0u64, 0u64);
+    for (position, accounts) in positions.iter().zip(ctx.remaining_accounts.chunks(stride)) {
+        let &SettledPosition { outcome, amount, index } = position;
+        require!(amount > 0, MarketError::ZeroAmount);
+        let owner_dac = Account::<TokenAccount>::try_from(&accounts[0])?;
+        require_keys_eq!(owner_dac.mint, ctx.accounts.escrow.mint, MarketError::MintMismatch);
+        replace_position_leaf(
+            &ctx.accounts.position_tree,
+            &ctx.accounts.merkle_tree,
+            &ctx.accounts.log_wrapper,
+            &ctx.accounts.compression_program,
+            &accounts[1..],
+            ReplaceLeafArgs {
+                root,
+                previous_leaf: PositionTree::leaf(&owner_dac.owner, outcome, amount, index),
+                new_leaf: EMPTY_LEAF,
+                index,
+            },
+        )?;
+
+        let (yes, no) = if outcome == Outcome::Yes { (amount, 0) } else { (0, amount) };
+        let payout = ctx.accounts.market.load()?.redemption_value(yes, no)
+            .ok_or(MarketError::MarketNotResolved)?;
+        if payout > 0 {
+            transfer_from_escrow(
+                &ctx.accounts.market,
+                &ctx.accounts.escrow,
+                &owner_dac,
+                &ctx.accounts.token_program,
+                payout,
+            )?;
+        }
+        yes_burned = yes_burned.checked_add(yes).ok_or(MarketError::Overflow)?;
+        no_burned = no_burned.checked_add(no).ok_or(MarketError::Overflow)?;
+        total_payout = total_payout.checked_add(payout).ok_or(MarketError::Overflow)?;
+
+        emit!(CompressedPositionChanged {
+            market: market_key,
+            merkle_tree: ctx.accounts.merkle_tree.key(),
+            index,
+            owner: owner_dac.owner,
+            outcome,
+            amount: 0,
+            leaf: EMPTY_LEAF,
+        });
+    }
+
+    // The batch's outcome tokens are burned once per side
+    let position_tree = &ctx.accounts.position_tree;
+    let seeds = &[POSITION_TREE_SEED, position_tree.market.as_ref(), &[position_tree.bump]];
+    let signer_seeds = &[&seeds[..]];
+    for (mint, vault, amount) in [
+        (&ctx.accounts.yes_mint, &ctx.accounts.yes_vault, yes_burned),
+        (&ctx.accounts.no_mint, &ctx.accounts.no_vault, no_burned),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let burn_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: mint.to_account_info(),
+                from: vault.to_account_info(),
+                authority: position_tree.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::burn(burn_ctx, amount)?;
+    }
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_sub(total_payout)
+        .ok_or(MarketError::Underflow)?;
+    let position_tree = &mut ctx.accounts.position_tree;
+    position_tree.yes_amount = position_tree.yes_amount.checked_sub(yes_burned)
+        .ok_or(MarketError::Underflow)?;
+    position_tree.no_amount = position_tree.no_amount.checked_sub(no_burned)
+        .ok_or(MarketError::Underflow)?;
+
+    emit!(BatchSettled {
+        market: market_key,
+        positions: positions.len() as u32,
+        yes_burned,
+        no_burned,
+        payout: total_payout,
+    });
+    msg!("Settled {} compressed positions for {} DAC", positions.len(), total_payout);
+    Ok(())
+}
+
+/// Replace a position's leaf, checking the proof has one node per level below the canopy
+pub(crate) fn replace_position_leaf<'info>(
+    position_tree: &Account<'info, PositionTree>,
+    merkle_tree: &UncheckedAccount<'info>,
+    log_wrapper: &UncheckedAccount<'info>,
+    compression_program: &UncheckedAccount<'info>,
+    proof: &[AccountInfo<'info>],
+    args: ReplaceLeafArgs,
+) -> Result<()> {
+    require!(proof.len() == position_tree.proof_len(), MarketError::InvalidProofLength);
+    let nodes: Vec<Pubkey> = proof.iter().map(|node| node.key()).collect();
+    let ix = compression::replace_leaf(&position_tree.merkle_tree, &position_tree.key(), &nodes, args);
+    invoke_as_tree(&ix, position_tree, merkle_tree, log_wrapper, compression_program, proof)
+}
+
+/// Invoke the compression program, signed by the position tree as the tree's authority
+fn invoke_as_tree<'info>(
+    ix: &Instruction,
+    position_tree: &Account<'info, PositionTree>,
+    merkle_tree: &UncheckedAccount<'info>,
+    log_wrapper: &UncheckedAccount<'info>,
+    compression_program: &UncheckedAccount<'info>,
+    proof: &[AccountInfo<'info>],
+) -> Result<()> {
+    let mut infos = vec![
+        merkle_tree.to_account_info(),
+        position_tree.to_account_info(),
+        log_wrapper.to_account_info(),
+        compression_program.to_account_info(),
+    ];
+    infos.extend(proof.iter().cloned());
+    let seeds = &[POSITION_TREE_SEED, position_tree.market.as_ref(), &[position_tree.bump]];
+    invoke_signed(ix, &infos, &[&seeds[..]])?;
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct EnablePositionTree<'info> {
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = creator @ MarketError::Unauthorized,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PositionTree::LEN,
+        seeds = [POSITION_TREE_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub position_tree: Box<Account<'info, PositionTree>>,
+
+    #[account(address = market.load()?.yes_mint @ MarketError::MintMismatch)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(address = market.load()?.no_mint @ MarketError::MintMismatch)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// Vault holding the YES tokens of every compressed position
+    #[account(
+        init,
+        payer = creator,
+        seeds = [POSITION_TREE_VAULT_SEED, position_tree.key().as_ref(), yes_mint.key().as_ref()],
+        bump,
+        token::mint = yes_mint,
+        token::authority = position_tree,
+    )]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Vault holding the NO tokens of every compressed position
+    #[account(
+        init,
+        payer = creator,
+        seeds = [POSITION_TREE_VAULT_SEED, position_tree.key().as_ref(), no_mint.key().as_ref()],
+        bump,
+        token::mint = no_mint,
+        token::authority = position_tree,
+    )]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: pre-allocated tree, initialized through the compression program
+    #[account(mut, owner = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: the noop program the compression program logs change logs through
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: the account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome: Outcome)]
+pub struct DepositCompressedPosition<'info> {
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_TREE_SEED, market.key().as_ref()],
+        bump = position_tree.bump,
+        has_one = merkle_tree @ MarketError::InvalidPositionTree,
+    )]
+    pub position_tree: Box<Account<'info, PositionTree>>,
+
+    /// Owner's outcome token account (source)
+    #[account(
+        mut,
+        constraint = market.load()?.outcome_mint(outcome) == Some(owner_outcome.mint) @ MarketError::InvalidReceiptOutcome,
+    )]
+    pub owner_outcome: Box<Account<'info, TokenAccount>>,
+
+    /// Vault of the deposited side
+    #[account(
+        mut,
+        seeds = [POSITION_TREE_VAULT_SEED, position_tree.key().as_ref(), owner_outcome.mint.as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: the position tree's Merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub owner: Signer<'info>,
+
+    /// CHECK: the noop program the compression program logs change logs through
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: the account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32], outcome: Outcome)]
+pub struct UpdateCompressedPosition<'info> {
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_TREE_SEED, market.key().as_ref()],
+        bump = position_tree.bump,
+        has_one = merkle_tree @ MarketError::InvalidPositionTree,
+    )]
+    pub position_tree: Box<Account<'info, PositionTree>>,
+
+    /// Owner's outcome token account, paying in or receiving the difference
+    #[account(
+        mut,
+        constraint = market.load()?.outcome_mint(outcome) == Some(owner_outcome.mint) @ MarketError::InvalidReceiptOutcome,
+    )]
+    pub owner_outcome: Box<Account<'info, TokenAccount>>,
+
+    /// Vault of the position's side
+    #[account(
+        mut,
+        seeds = [POSITION_TREE_VAULT_SEED, position_tree.key().as_ref(), owner_outcome.mint.as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: the position tree's Merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// Owner of the position leaf
+    pub owner: Signer<'info>,
+
+    /// CHECK: the noop program the compression program logs change logs through
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: the account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32], outcome: Outcome)]
+pub struct RedeemCompressedPosition<'info> {
+    /// The resolved market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.status() == MarketStatus::Resolved @ MarketError::MarketNotResolved,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_TREE_SEED, market.key().as_ref()],
+        bump = position_tree.bump,
+        has_one = merkle_tree @ MarketError::InvalidPositionTree,
+    )]
+    pub position_tree: Box<Account<'info, PositionTree>>,
+
+    /// Mint of the position's side, burned from the vault
+    #[account(
+        mut,
+        constraint = market.load()?.outcome_mint(outcome) == Some(outcome_mint.key()) @ MarketError::InvalidReceiptOutcome,
+    )]
+    pub outcome_mint: Box<Account<'info, Mint>>,
+
+    /// Vault of the position's side
+    #[account(
+        mut,
+        seeds = [POSITION_TREE_VAULT_SEED, position_tree.key().as_ref(), outcome_mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// Holder's DAC token account (destination)
+    #[account(
+        mut,
+        constraint = holder_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub holder_dac: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: the position tree's Merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// Owner of the position leaf
+    pub holder: Signer<'info>,
+
+    /// CHECK: the noop program the compression program logs change logs through
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: the account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SettleBatch<'info> {
+    /// The resolved market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.status() == MarketStatus::Resolved @ MarketError::MarketNotResolved,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_TREE_SEED, market.key().as_ref()],
+        bump = position_tree.bump,
+        has_one = merkle_tree @ MarketError::InvalidPositionTree,
+    )]
+    pub position_tree: Box<Account<'info, PositionTree>>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// Vault of the compressed positions' YES tokens
+    #[account(
+        mut,
+        seeds = [POSITION_TREE_VAULT_SEED, position_tree.key().as_ref(), yes_mint.key().as_ref()],
+        bump,
+    )]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Vault of the compressed positions' NO tokens
+    #[account(
+        mut,
+        seeds = [POSITION_TREE_VAULT_SEED, position_tree.key().as_ref(), no_mint.key().as_ref()],
+        bump,
+    )]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: the position tree's Merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: the noop program the compression program logs change logs through
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: the account compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::MarketError;
+use crate::events::MarketResolved;
+use crate::state::*;
+use crate::{MARKET_SEED, SEALED_RESOLUTION_SEED};
+
+/// Switch a market to private resolution (resolver only)
+pub fn enable_private_resolution(
+    ctx: Context<EnablePrivateResolution>,
+    encryption_key: [u8; 32],
+    reveal_delay: i64,
+) -> Result<()> {
+    require!(
+        reveal_delay > 0 && reveal_delay <= MAX_REVEAL_DELAY,
+        MarketError::InvalidRevealDelay
+    );
+
+    let sealed = &mut ctx.accounts.sealed_resolution;
+    sealed.market = ctx.accounts.market.key();
+    sealed.encryption_key = encryption_key;
+    sealed.reveal_delay = reveal_delay;
+    sealed.ciphertext = Vec::new();
+    sealed.commitment = [0u8; 32];
+    sealed.sealed_at = 0;
+    sealed.bump = ctx.bumps.sealed_resolution;
+
+    msg!(
+        "Market {} resolves privately ({}s reveal delay)",
+        ctx.accounts.market.load()?.id,
+        reveal_delay
+    );
+    Ok(())
+}
+
+/// Post the encrypted outcome, freezing the market until the reveal (resolver only)
+///
+/// Unlike `resolve_market` this is allowed before the end time, so trading
+/// can stop the moment the outcome becomes known.
+pub fn seal_resolution(
+    ctx: Context<SealResolution>,
+    ciphertext: Vec<u8>,
+    commitment: [u8; 32],
+) -> Result<()> {
+    require!(
+        !ciphertext.is_empty() && ciphertext.len() <= MAX_SEALED_OUTCOME_LEN,
+        MarketError::InvalidSealedOutcome
+    );
+
+    let sealed = &mut ctx.accounts.sealed_resolution;
+    sealed.ciphertext = ciphertext;
+    sealed.commitment = commitment;
+    sealed.sealed_at = Clock::get()?.unix_timestamp;
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.set_status(MarketStatus::Sealed);
+
+    msg!("Market {} sealed, reveal at {}", market.id, sealed.reveal_time());
+    Ok(())
+}
+
+/// Reveal a sealed outcome after its delay, resolving the market (anyone)
+pub fn reveal_resolution(
+    ctx: Context<RevealResolution>,
+    outcome: Outcome,
+    salt: [u8; 32],
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let sealed = &ctx.accounts.sealed_resolution;
+    require!(now >= sealed.reveal_time(), MarketError::RevealTooEarly);
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    require!(
+        SealedResolution::outcome_hash(&ctx.accounts.market.key(), outcome, &salt) == sealed.commitment,
+        MarketError::InvalidSealedOutcome
+    );
+    market.resolve(outcome, now);
+
+    emit!(MarketResolved {
+        market: ctx.accounts.market.key(),
+        outcome,
+        resolved_at: now,
+    });
+
+    msg!("Market {} revealed: {:?}", market.id, outcome);
+    Ok(())
+}
+
+/// Resolve a sealed market as invalid once its reveal deadline has passed (anyone)
+///
+/// Only the resolver and the key holders can reveal, so without this a
+/// sealed outcome nobody reveals would lock the escrow for good.
+pub fn void_sealed_resolution(ctx: Context<RevealResolution>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= ctx.accounts.sealed_resolution.reveal_deadline(),
+        MarketError::RevealDeadlineNotPassed
+    );
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.resolve(Outcome::Invalid, now);
+
+    emit!(MarketResolved {
+        market: ctx.accounts.market.key(),
+        outcome: Outcome::Invalid,
+        resolved_at: now,
+    });
+
+    msg!("Market {} voided, its sealed outcome was never revealed", market.id);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct EnablePrivateResolution<'info> {
+    /// The market to resolve privately
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = resolver @ MarketError::Unauthorized,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The private resolution settings to create
+    #[account(
+        init,
+        payer = resolver,
+        space = 8 + SealedResolution::LEN,
+        seeds = [SEALED_RESOLUTION_SEED, market.key().as_ref()],
+        bump
+    )]
+    pub sealed_resolution: Box<Account<'info, SealedResolution>>,
+
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SealResolution<'info> {
+    /// The market to freeze
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = resolver @ MarketError::Unauthorized,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [SEALED_RESOLUTION_SEED, market.key().as_ref()],
+        bump = sealed_resolution.bump,
+    )]
+    pub sealed_resolution: Box<Account<'info, SealedResolution>>,
+
+    pub resolver: Signer<'info>,
+}
+
+/// Accounts of both `reveal_resolution` and `void_sealed_resolution`
+#[derive(Accounts)]
+pub struct RevealResolution<'info> {
+    /// The sealed market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        constraint = market.load()?.status() == MarketStatus::Sealed @ MarketError::MarketNotSealed,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        seeds = [SEALED_RESOLUTION_SEED, market.key().as_ref()],
+        bump = sealed_resolution.bump,
+    )]
+    pub sealed_resolution: Box<Account<'info, SealedResolution>>,
+}
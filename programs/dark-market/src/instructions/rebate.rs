@@ -0,0 +1,239 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{MAKER_REBATE_SEED, MARKET_CONFIG_SEED, POOL_SEED, REBATE_POOL_SEED, REBATE_VAULT_SEED};
+
+pub fn configure_maker_rebates(
+    ctx: Context<ConfigureMakerRebates>,
+    taker_share_bps: u16,
+    maker_rebate_bps: u16,
+) -> Result<()> {
+    require!(taker_share_bps as u64 <= BPS_DENOMINATOR, MarketError::InvalidRebateParams);
+    require!(maker_rebate_bps <= MAX_MAKER_REBATE_BPS, MarketError::InvalidRebateParams);
+
+    let rebate_pool = &mut ctx.accounts.rebate_pool;
+    rebate_pool.pool = ctx.accounts.pool.key();
+    rebate_pool.vault = ctx.accounts.vault.key();
+    rebate_pool.taker_share_bps = taker_share_bps;
+    rebate_pool.maker_rebate_bps = maker_rebate_bps;
+    rebate_pool.bump = ctx.bumps.rebate_pool;
+    rebate_pool.vault_bump = ctx.bumps.vault;
+
+    msg!(
+        "Maker rebates: {} bps of taker fees, {} bps to makers",
+        taker_share_bps,
+        maker_rebate_bps
+    );
+    Ok(())
+}
+
+pub fn open_maker_rebate(ctx: Context<OpenMakerRebate>) -> Result<()> {
+    let rebate = &mut ctx.accounts.maker_rebate;
+    rebate.pool = ctx.accounts.rebate_pool.pool;
+    rebate.maker = ctx.accounts.maker.key();
+    rebate.accrued = 0;
+    rebate.claimed = 0;
+    rebate.bump = ctx.bumps.maker_rebate;
+
+    msg!("Maker rebate account opened for {}", rebate.maker);
+    Ok(())
+}
+
+pub fn claim_maker_rebate(ctx: Context<ClaimMakerRebate>) -> Result<()> {
+    let amount = ctx.accounts.maker_rebate.accrued;
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    let rebate_pool = &ctx.accounts.rebate_pool;
+    let seeds = &[REBATE_POOL_SEED, rebate_pool.pool.as_ref(), &[rebate_pool.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.maker_dac.to_account_info(),
+            authority: rebate_pool.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let rebate_pool = &mut ctx.accounts.rebate_pool;
+    rebate_pool.reserved = rebate_pool.reserved.checked_sub(amount)
+        .ok_or(MarketError::Underflow)?;
+    let rebate = &mut ctx.accounts.maker_rebate;
+    rebate.accrued = 0;
+    rebate.claimed = rebate.claimed.checked_add(amount).ok_or(MarketError::Overflow)?;
+
+    msg!("Maker rebate claimed: {} DAC (total {})", amount, rebate.claimed);
+    Ok(())
+}
+
+/// The pool's rebate settings, none until `configure_maker_rebates` has run on it
+///
+/// Trades always pass the rebate pool PDA rather than an optional account,
+/// so a taker or cranker cannot skip the rebate by leaving it out. Once it
+/// exists, its vault must be passed too.
+pub(crate) fn load_rebate_pool(
+    rebate_pool: &AccountInfo,
+    rebate_vault: Option<&Box<Account<'_, TokenAccount>>>,
+) -> Result<Option<RebatePool>> {
+    if rebate_pool.owner != &crate::ID {
+        return Ok(None);
+    }
+    let rebate_pool = RebatePool::try_deserialize(&mut &rebate_pool.try_borrow_data()?[..])?;
+    require!(
+        rebate_vault.map(|vault| vault.key()) == Some(rebate_pool.vault),
+        MarketError::InvalidRebateVault
+    );
+    Ok(Some(rebate_pool))
+}
+
+/// Share of a taker `fee` paid into the rebate vault, none while the pool has no rebates
+pub(crate) fn taker_rebate(rebate_pool: Option<&RebatePool>, fee: u64) -> u64 {
+    rebate_pool.map_or(0, |rebate_pool| rebate_pool.taker_share(fee))
+}
+
+/// Accrue the rebate on a maker fill of `notional` DAC, if the pool has rebates
+///
+/// Makers who never opened their rebate account forgo the rebate.
+pub(crate) fn accrue_maker_rebate(
+    rebate_pool: &AccountInfo,
+    rebate_vault: Option<&Box<Account<'_, TokenAccount>>>,
+    maker_rebate: &AccountInfo,
+    notional: u64,
+) -> Result<u64> {
+    let Some(mut pool_rebates) = load_rebate_pool(rebate_pool, rebate_vault)? else {
+        return Ok(0);
+    };
+    if maker_rebate.owner != &crate::ID {
+        return Ok(0);
+    }
+    let mut maker = MakerRebate::try_deserialize(&mut &maker_rebate.try_borrow_data()?[..])?;
+    let vault = rebate_vault.ok_or(MarketError::InvalidRebateVault)?;
+    let rebate = pool_rebates.maker_rebate(notional, vault.amount);
+    pool_rebates.reserved = pool_rebates.reserved.checked_add(rebate)
+        .ok_or(MarketError::Overflow)?;
+    maker.accrued = maker.accrued.checked_add(rebate)
+        .ok_or(MarketError::Overflow)?;
+    pool_rebates.try_serialize(&mut &mut rebate_pool.try_borrow_mut_data()?[..])?;
+    maker.try_serialize(&mut &mut maker_rebate.try_borrow_mut_data()?[..])?;
+    Ok(rebate)
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ConfigureMakerRebates<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The pool to run rebates on
+    #[account(
+        seeds = [POOL_SEED, pool.market.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The pool's rebate settings (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RebatePool::LEN,
+        seeds = [REBATE_POOL_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub rebate_pool: Box<Account<'info, RebatePool>>,
+
+    /// DAC vault funding the rebates (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [REBATE_VAULT_SEED, rebate_pool.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = rebate_pool,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == config.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct OpenMakerRebate<'info> {
+    /// The pool's rebate settings
+    #[account(
+        seeds = [REBATE_POOL_SEED, rebate_pool.pool.as_ref()],
+        bump = rebate_pool.bump,
+    )]
+    pub rebate_pool: Box<Account<'info, RebatePool>>,
+
+    /// The maker's rebate account to create
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + MakerRebate::LEN,
+        seeds = [MAKER_REBATE_SEED, rebate_pool.pool.as_ref(), maker.key().as_ref()],
+        bump
+    )]
+    pub maker_rebate: Box<Account<'info, MakerRebate>>,
+
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimMakerRebate<'info> {
+    /// The pool's rebate settings
+    #[account(
+        mut,
+        seeds = [REBATE_POOL_SEED, rebate_pool.pool.as_ref()],
+        bump = rebate_pool.bump,
+        has_one = vault @ MarketError::InvalidRebateVault,
+    )]
+    pub rebate_pool: Box<Account<'info, RebatePool>>,
+
+    /// DAC vault funding the rebates
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// The maker's rebate account
+    #[account(
+        mut,
+        seeds = [MAKER_REBATE_SEED, rebate_pool.pool.as_ref(), maker.key().as_ref()],
+        bump = maker_rebate.bump,
+    )]
+    pub maker_rebate: Box<Account<'info, MakerRebate>>,
+
+    /// Maker's DAC token account (destination)
+    #[account(
+        mut,
+        constraint = maker_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub maker_dac: Box<Account<'info, TokenAccount>>,
+
+    pub maker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
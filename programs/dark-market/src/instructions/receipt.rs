@@ -0,0 +1,332 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use anchor_spl::metadata::{self, BurnNft, CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata};
+use anchor_spl::token::{self, CloseAccount, Mint, MintTo, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{MARKET_SEED, RECEIPT_MINT_SEED, RECEIPT_SEED, RECEIPT_VAULT_SEED};
+
+pub fn wrap_position(
+    ctx: Context<WrapPosition>,
+    nonce: u64,
+    outcome: Outcome,
+    amount: u64,
+    uri: String,
+) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+    require!(uri.len() <= MAX_RECEIPT_URI_LEN, MarketError::UriTooLong);
+
+    // Move the outcome tokens into the receipt vault
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_outcome.to_account_info(),
+            to: ctx.accounts.receipt_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let market_key = ctx.accounts.market.key();
+    let owner_key = ctx.accounts.owner.key();
+    let nonce_bytes = nonce.to_le_bytes();
+    let seeds = &[
+        RECEIPT_SEED,
+        market_key.as_ref(),
+        owner_key.as_ref(),
+        nonce_bytes.as_ref(),
+        &[ctx.bumps.receipt],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Mint the single receipt token to the owner
+    let mint_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.receipt_mint.to_account_info(),
+            to: ctx.accounts.owner_receipt.to_account_info(),
+            authority: ctx.accounts.receipt.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::mint_to(mint_ctx, 1)?;
+
+    // Attach Metaplex metadata and lock supply with a master edition
+    let side = if outcome == Outcome::Yes { "YES" } else { "NO" };
+    let data = DataV2 {
+        name: format!("DM #{} {}", ctx.accounts.market.load()?.id, side),
+        symbol: RECEIPT_SYMBOL.to_string(),
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+    let metadata_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_metadata_program.to_account_info(),
+        CreateMetadataAccountsV3 {
+            metadata: ctx.accounts.metadata.to_account_info(),
+            mint: ctx.accounts.receipt_mint.to_account_info(),
+            mint_authority: ctx.accounts.receipt.to_account_info(),
+            payer: ctx.accounts.owner.to_account_info(),
+            update_authority: ctx.accounts.receipt.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        signer_seeds,
+    );
+    metadata::create_metadata_accounts_v3(metadata_ctx, data, false, true, None)?;
+
+    let edition_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_metadata_program.to_account_info(),
+        CreateMasterEditionV3 {
+            edition: ctx.accounts.master_edition.to_account_info(),
+            mint: ctx.accounts.receipt_mint.to_account_info(),
+            update_authority: ctx.accounts.receipt.to_account_info(),
+            mint_authority: ctx.accounts.receipt.to_account_info(),
+            payer: ctx.accounts.owner.to_account_info(),
+            metadata: ctx.accounts.metadata.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        signer_seeds,
+    );
+    metadata::create_master_edition_v3(edition_ctx, Some(0))?;
+
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.market = market_key;
+    receipt.receipt_mint = ctx.accounts.receipt_mint.key();
+    receipt.outcome = outcome;
+    receipt.amount = amount;
+    receipt.original_owner = owner_key;
+    receipt.wrapped_at = Clock::get()?.unix_timestamp;
+    receipt.nonce = nonce;
+    receipt.bump = ctx.bumps.receipt;
+    receipt.vault_bump = ctx.bumps.receipt_vault;
+
+    msg!("Wrapped {} {} into receipt {}", amount, side, receipt.receipt_mint);
+    Ok(())
+}
+
+pub fn unwrap_position(ctx: Context<UnwrapPosition>) -> Result<()> {
+    let receipt = &ctx.accounts.receipt;
+    let nonce_bytes = receipt.nonce.to_le_bytes();
+    let seeds = &[
+        RECEIPT_SEED,
+        receipt.market.as_ref(),
+        receipt.original_owner.as_ref(),
+        nonce_bytes.as_ref(),
+        &[receipt.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Release the outcome tokens to the current holder
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.receipt_vault.to_account_info(),
+            to: ctx.accounts.holder_outcome.to_account_info(),
+            authority: receipt.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, receipt.amount)?;
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.receipt_vault.to_account_info(),
+            destination: ctx.accounts.holder.to_account_info(),
+            authority: receipt.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::close_account(close_ctx)?;
+
+    // Burn the NFT; Metaplex closes the metadata, edition and token account
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_metadata_program.to_account_info(),
+        BurnNft {
+            metadata: ctx.accounts.metadata.to_account_info(),
+            owner: ctx.accounts.holder.to_account_info(),
+            mint: ctx.accounts.receipt_mint.to_account_info(),
+            token: ctx.accounts.holder_receipt.to_account_info(),
+            edition: ctx.accounts.master_edition.to_account_info(),
+            spl_token: ctx.accounts.token_program.to_account_info(),
+        },
+    );
+    metadata::burn_nft(burn_ctx, None)?;
+
+    msg!("Unwrapped receipt {} into {} outcome tokens", receipt.receipt_mint, receipt.amount);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(nonce: u64, outcome: Outcome)]
+pub struct WrapPosition<'info> {
+    /// The market the position belongs to
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The outcome token mint being wrapped
+    #[account(
+        constraint = market.load()?.outcome_mint(outcome) == Some(outcome_mint.key()) @ MarketError::InvalidReceiptOutcome,
+    )]
+    pub outcome_mint: Box<Account<'info, Mint>>,
+
+    /// Owner's outcome token account (source)
+    #[account(
+        mut,
+        constraint = owner_outcome.mint == outcome_mint.key() @ MarketError::MintMismatch,
+    )]
+    pub owner_outcome: Box<Account<'info, TokenAccount>>,
+
+    /// The receipt account to create
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + PositionReceipt::LEN,
+        seeds = [RECEIPT_SEED, market.key().as_ref(), owner.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub receipt: Box<Account<'info, PositionReceipt>>,
+
+    /// The receipt NFT mint
+    #[account(
+        init,
+        payer = owner,
+        seeds = [RECEIPT_MINT_SEED, receipt.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = receipt,
+        mint::freeze_authority = receipt,
+    )]
+    pub receipt_mint: Box<Account<'info, Mint>>,
+
+    /// Vault holding the wrapped outcome tokens
+    #[account(
+        init,
+        payer = owner,
+        seeds = [RECEIPT_VAULT_SEED, receipt.key().as_ref()],
+        bump,
+        token::mint = outcome_mint,
+        token::authority = receipt,
+    )]
+    pub receipt_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's token account for the receipt NFT
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_receipt: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Metaplex metadata PDA, created by the metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), receipt_mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex master edition PDA, created by the metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), receipt_mint.key().as_ref(), b"edition"],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UnwrapPosition<'info> {
+    /// The receipt being unwrapped
+    #[account(
+        mut,
+        close = holder,
+        seeds = [
+            RECEIPT_SEED,
+            receipt.market.as_ref(),
+            receipt.original_owner.as_ref(),
+            receipt.nonce.to_le_bytes().as_ref(),
+        ],
+        bump = receipt.bump,
+        has_one = receipt_mint @ MarketError::MintMismatch,
+    )]
+    pub receipt: Box<Account<'info, PositionReceipt>>,
+
+    /// The receipt NFT mint
+    #[account(mut)]
+    pub receipt_mint: Box<Account<'info, Mint>>,
+
+    /// Vault holding the wrapped outcome tokens
+    #[account(
+        mut,
+        seeds = [RECEIPT_VAULT_SEED, receipt.key().as_ref()],
+        bump = receipt.vault_bump,
+    )]
+    pub receipt_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Holder's token account containing the receipt NFT (will be burned)
+    #[account(
+        mut,
+        constraint = holder_receipt.mint == receipt_mint.key() @ MarketError::MintMismatch,
+        constraint = holder_receipt.owner == holder.key() @ MarketError::NotReceiptHolder,
+        constraint = holder_receipt.amount == 1 @ MarketError::NotReceiptHolder,
+    )]
+    pub holder_receipt: Box<Account<'info, TokenAccount>>,
+
+    /// Holder's outcome token account (destination)
+    #[account(
+        mut,
+        constraint = holder_outcome.mint == receipt_vault.mint @ MarketError::MintMismatch,
+    )]
+    pub holder_outcome: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Metaplex metadata PDA, closed by the metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), receipt_mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex master edition PDA, closed by the metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), receipt_mint.key().as_ref(), b"edition"],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
@@ -0,0 +1,428 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as ix_sysvar, load_instruction_at_checked,
+};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::errors::MarketError;
+use crate::instructions::{
+    check_expiry, impl_pool_vaults, pay_from_margin_vault, track_price_move, PoolVaults,
+};
+use crate::state::*;
+use crate::{
+    MARKET_CONFIG_SEED, MARKET_SEED, OBSERVATIONS_SEED, POOL_SEED, RELAYER_SEED,
+    RELAY_ACCOUNT_SEED, RELAY_VAULT_SEED,
+};
+
+pub fn register_relayer(ctx: Context<RegisterRelayer>, fee_bps: u16) -> Result<()> {
+    require!(fee_bps <= MAX_RELAYER_FEE_BPS, MarketError::InvalidRelayerFee);
+
+    let relayer = &mut ctx.accounts.relayer;
+    relayer.authority = ctx.accounts.relayer_authority.key();
+    relayer.fee_bps = fee_bps;
+    relayer.fees_earned = 0;
+    relayer.approved = true;
+    relayer.bump = ctx.bumps.relayer;
+
+    msg!("Relayer {} registered with a {} bps fee", relayer.authority, fee_bps);
+    Ok(())
+}
+
+pub fn set_relayer_approval(ctx: Context<SetRelayerApproval>, approved: bool) -> Result<()> {
+    let relayer = &mut ctx.accounts.relayer;
+    relayer.approved = approved;
+
+    msg!("Relayer {} approved: {}", relayer.authority, approved);
+    Ok(())
+}
+
+pub fn open_relay_account(ctx: Context<OpenRelayAccount>) -> Result<()> {
+    let account = &mut ctx.accounts.relay_account;
+    account.owner = ctx.accounts.owner.key();
+    account.balance = 0;
+    account.nonce = 0;
+    account.bump = ctx.bumps.relay_account;
+
+    msg!("Relay account opened");
+    Ok(())
+}
+
+pub fn deposit_relay(ctx: Context<MoveRelayBalance>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.owner_dac.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let account = &mut ctx.accounts.relay_account;
+    account.balance = account.balance.checked_add(amount).ok_or(MarketError::Overflow)?;
+
+    msg!("Deposited {} DAC for relayed orders", amount);
+    Ok(())
+}
+
+pub fn withdraw_relay(ctx: Context<MoveRelayBalance>, amount: u64) -> Result<()> {
+    require!(amount > 0, MarketError::ZeroAmount);
+    let account = &mut ctx.accounts.relay_account;
+    account.balance = account.balance.checked_sub(amount)
+        .ok_or(MarketError::InsufficientRelayBalance)?;
+
+    pay_from_margin_vault(
+        &ctx.accounts.config,
+        &ctx.accounts.vault,
+        &ctx.accounts.owner_dac,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    msg!("Withdrew {} DAC from relayed orders", amount);
+    Ok(())
+}
+
+/// Execute a batch of user-signed buy orders against the pool (approved relayer only)
+///
+/// Each order's signature must be checked by an ed25519 program instruction
+/// earlier in the transaction. Remaining accounts hold, per order, the
+/// owner's relay account and the recipient token account. Orders are netted
+/// per side and each side trades once, smaller side first, with every order
+/// on a side getting the same price.
+pub fn settle_relay_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleRelayBatch<'info>>,
+    orders: Vec<SignedOrder>,
+) -> Result<()> {
+    require!(
+        !orders.is_empty() && orders.len() <= MAX_RELAY_BATCH,
+        MarketError::InvalidRelayBatch
+    );
+    require!(
+        ctx.remaining_accounts.len() == 2 * orders.len(),
+        MarketError::InvalidRelayBatch
+    );
+    ctx.accounts.pool.refresh_fee(&ctx.accounts.observations.load()?, Clock::get()?.unix_timestamp);
+
+    let relayer = &ctx.accounts.relayer;
+    let market_key = ctx.accounts.market.key();
+    let instructions = ctx.accounts.instructions.to_account_info();
+    let mut recipients = Vec::with_capacity(orders.len());
+    let mut nets = Vec::with_capacity(orders.len());
+    let (mut yes_in, mut no_in, mut fees) = (0u64, 0u64, 0u64);
+    for (signed, accounts) in orders.iter().zip(ctx.remaining_accounts.chunks(2)) {
+        let order = &signed.order;
+        require!(
+            order.relayer == relayer.authority
+                && order.market == market_key
+                && order.outcome != Outcome::Invalid,
+            MarketError::InvalidRelayBatch
+        );
+        check_expiry(order.expiry_slot)?;
+        let signature_ix = load_instruction_at_checked(signed.signature_ix as usize, &instructions)?;
+        require!(
+            ed25519_verifies(&signature_ix, &order.owner, &order.message()),
+            MarketError::InvalidOrderSignature
+        );
+
+        let mut account = Account::<RelayAccount>::try_from(&accounts[0])?;
+        require_keys_eq!(account.owner, order.owner, MarketError::InvalidRelayBatch);
+        require!(account.nonce == order.nonce, MarketError::InvalidOrderSignature);
+        account.nonce += 1;
+        account.balance = account.balance.checked_sub(order.amount_in)
+            .ok_or(MarketError::InsufficientRelayBalance)?;
+        account.exit(&crate::ID)?;
+
+        let recipient = Account::<TokenAccount>::try_from(&accounts[1])?;
+        require_keys_eq!(recipient.key(), order.recipient, MarketError::InvalidRelayBatch);
+        recipients.push(recipient);
+
+        let fee = relayer.fee(order.amount_in);
+        let net = order.amount_in - fee;
+        fees += fee;
+        match order.outcome {
+            Outcome::No => no_in = no_in.checked_add(net).ok_or(MarketError::Overflow)?,
+            _ => yes_in = yes_in.checked_add(net).ok_or(MarketError::Overflow)?,
+        }
+        nets.push(net);
+    }
+
+    let seeds = &[MARKET_CONFIG_SEED, &[ctx.accounts.config.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let config_info = ctx.accounts.config.to_account_info();
+    let legs = if yes_in <= no_in {
+        [(Outcome::Yes, yes_in), (Outcome::No, no_in)]
+    } else {
+        [(Outcome::No, no_in), (Outcome::Yes, yes_in)]
+    };
+    for (outcome, side_in) in legs {
+        if side_in == 0 {
+            continue;
+        }
+        let accounts = &ctx.accounts;
+        let out = accounts.pool.quote_buy(outcome, side_in, 0)
+            .ok_or(MarketError::Overflow)?;
+        let vaults = accounts.vaults();
+        vaults.deposit_dac(&accounts.vault.to_account_info(), &config_info, signer_seeds, side_in)?;
+
+        let mut paid = 0u64;
+        for ((signed, recipient), net) in orders.iter().zip(&recipients).zip(&nets) {
+            if signed.order.outcome != outcome {
+                continue;
+            }
+            let share = (*net as u128 * out as u128 / side_in as u128) as u64;
+            require!(share >= signed.order.min_amount_out, MarketError::SlippageExceeded);
+            vaults.withdraw_outcome(outcome, recipient, share)?;
+            paid += share;
+        }
+
+        let price_before = ctx.accounts.pool.price(Outcome::Yes);
+        ctx.accounts.pool.apply_buy(outcome, side_in, paid)
+            .ok_or(MarketError::Overflow)?;
+        track_price_move(&mut ctx.accounts.pool, &mut ctx.accounts.observations.load_mut()?, price_before)?;
+        let market = &mut ctx.accounts.market.load_mut()?;
+        market.total_collateral = market.total_collateral.checked_add(side_in)
+            .ok_or(MarketError::Overflow)?;
+    }
+
+    if fees > 0 {
+        pay_from_margin_vault(
+            &ctx.accounts.config,
+            &ctx.accounts.vault,
+            &ctx.accounts.relayer_dac,
+            &ctx.accounts.token_program,
+            fees,
+        )?;
+    }
+    let relayer = &mut ctx.accounts.relayer;
+    relayer.fees_earned = relayer.fees_earned.checked_add(fees).ok_or(MarketError::Overflow)?;
+
+    msg!(
+        "Relayed batch of {} orders: {} DAC on YES, {} DAC on NO, {} DAC in fees",
+        orders.len(),
+        yes_in,
+        no_in,
+        fees
+    );
+    Ok(())
+}
+
+impl_pool_vaults!(SettleRelayBatch);
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct RegisterRelayer<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The relayer to register
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Relayer::LEN,
+        seeds = [RELAYER_SEED, relayer_authority.key().as_ref()],
+        bump
+    )]
+    pub relayer: Account<'info, Relayer>,
+
+    /// CHECK: Key the relayer signs batches with
+    pub relayer_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRelayerApproval<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The relayer to update
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer.authority.as_ref()],
+        bump = relayer.bump,
+    )]
+    pub relayer: Account<'info, Relayer>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenRelayAccount<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = dac_mint @ MarketError::MintMismatch,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The relay account to create
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + RelayAccount::LEN,
+        seeds = [RELAY_ACCOUNT_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub relay_account: Account<'info, RelayAccount>,
+
+    /// DAC vault shared by every relay account
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [RELAY_VAULT_SEED],
+        bump,
+        token::mint = dac_mint,
+        token::authority = config,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MoveRelayBalance<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The owner's relay account
+    #[account(
+        mut,
+        seeds = [RELAY_ACCOUNT_SEED, owner.key().as_ref()],
+        bump = relay_account.bump,
+    )]
+    pub relay_account: Account<'info, RelayAccount>,
+
+    /// DAC vault shared by every relay account
+    #[account(
+        mut,
+        seeds = [RELAY_VAULT_SEED],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Owner's DAC token account
+    #[account(
+        mut,
+        constraint = owner_dac.mint == vault.mint @ MarketError::MintMismatch,
+    )]
+    pub owner_dac: Box<Account<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SettleRelayBatch<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The pool's market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.is_trading(Clock::get()?.unix_timestamp) @ MarketError::MarketClosed,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The pool to trade against
+    #[account(
+        mut,
+        seeds = [POOL_SEED, market.key().as_ref()],
+        bump = pool.bump,
+        has_one = yes_vault @ MarketError::MintMismatch,
+        has_one = no_vault @ MarketError::MintMismatch,
+        constraint = !pool.breaker.is_halted(Clock::get()?.slot) @ MarketError::TradingHalted,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The pool's price observation buffer
+    #[account(
+        mut,
+        seeds = [OBSERVATIONS_SEED, pool.key().as_ref()],
+        bump = observations.load()?.bump,
+    )]
+    pub observations: AccountLoader<'info, PoolObservations>,
+
+    #[account(mut)]
+    pub yes_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub no_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The submitting relayer
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer_authority.key().as_ref()],
+        bump = relayer.bump,
+        constraint = relayer.approved @ MarketError::RelayerNotApproved,
+    )]
+    pub relayer: Box<Account<'info, Relayer>>,
+
+    pub relayer_authority: Signer<'info>,
+
+    /// Relayer's DAC token account (receives the fees)
+    #[account(
+        mut,
+        constraint = relayer_dac.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub relayer_dac: Box<Account<'info, TokenAccount>>,
+
+    /// DAC vault shared by every relay account
+    #[account(
+        mut,
+        seeds = [RELAY_VAULT_SEED],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The instructions sysvar
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
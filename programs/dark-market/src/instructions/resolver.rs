@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{get_return_data, invoke};
+
+use crate::errors::MarketError;
+use crate::events::MarketResolved;
+use crate::state::*;
+use crate::{MARKET_CONFIG_SEED, MARKET_SEED, RESOLVER_SEED};
+
+pub fn register_resolver(
+    ctx: Context<RegisterResolver>,
+    id: u32,
+    program: Pubkey,
+    name: String,
+) -> Result<()> {
+    require!(name.len() <= MAX_RESOLVER_NAME_LEN, MarketError::ResolverNameTooLong);
+
+    let entry = &mut ctx.accounts.entry;
+    entry.id = id;
+    entry.program = program;
+    entry.name = name;
+    entry.approved = true;
+    entry.bump = ctx.bumps.entry;
+
+    msg!("Resolver {} registered: {} ({})", id, entry.name, program);
+    Ok(())
+}
+
+pub fn set_resolver_approval(ctx: Context<SetResolverApproval>, approved: bool) -> Result<()> {
+    let entry = &mut ctx.accounts.entry;
+    entry.approved = approved;
+
+    msg!("Resolver {} approved: {}", entry.id, approved);
+    Ok(())
+}
+
+/// Resolve a market by asking its resolver adapter for the outcome (permissionless)
+///
+/// Accounts for the adapter are passed as remaining accounts and forwarded
+/// after the market, without signer privileges.
+pub fn resolve_with_adapter<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveWithAdapter<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let end_time = ctx.accounts.market.load()?.end_time;
+    require!(now >= end_time, MarketError::MarketNotEnded);
+
+    let program = ctx.accounts.entry.program;
+    let market_info = ctx.accounts.market.to_account_info();
+    let mut accounts = vec![AccountMeta::new_readonly(market_info.key(), false)];
+    let mut infos = vec![market_info];
+    for info in ctx.remaining_accounts {
+        accounts.push(if info.is_writable {
+            AccountMeta::new(info.key(), false)
+        } else {
+            AccountMeta::new_readonly(info.key(), false)
+        });
+        infos.push(info.clone());
+    }
+    infos.push(ctx.accounts.adapter_program.to_account_info());
+
+    let ix = Instruction {
+        program_id: program,
+        accounts,
+        data: ResolverInterface::data(&ctx.accounts.market.key(), end_time),
+    };
+    invoke(&ix, &infos)?;
+
+    let (returned_by, data) = get_return_data().ok_or(MarketError::InvalidResolverResponse)?;
+    require_keys_eq!(returned_by, program, MarketError::InvalidResolverResponse);
+    let outcome = Option::<Outcome>::try_from_slice(&data)
+        .map_err(|_| MarketError::InvalidResolverResponse)?
+        .ok_or(MarketError::ResolutionPending)?;
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.resolve(outcome, now);
+
+    emit!(MarketResolved {
+        market: ctx.accounts.market.key(),
+        outcome,
+        resolved_at: now,
+    });
+
+    msg!("Market {} resolved by adapter {}: {:?}", market.id, program, outcome);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(id: u32)]
+pub struct RegisterResolver<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The registry entry to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ResolverEntry::LEN,
+        seeds = [RESOLVER_SEED, id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, ResolverEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetResolverApproval<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The registry entry to update
+    #[account(
+        mut,
+        seeds = [RESOLVER_SEED, entry.id.to_le_bytes().as_ref()],
+        bump = entry.bump,
+    )]
+    pub entry: Account<'info, ResolverEntry>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveWithAdapter<'info> {
+    /// The market to resolve, naming the registry entry as its resolver
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        constraint = market.load()?.resolver == entry.key() @ MarketError::Unauthorized,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The market's resolver adapter entry
+    #[account(
+        seeds = [RESOLVER_SEED, entry.id.to_le_bytes().as_ref()],
+        bump = entry.bump,
+        constraint = entry.approved @ MarketError::ResolverNotApproved,
+    )]
+    pub entry: Account<'info, ResolverEntry>,
+
+    /// CHECK: The adapter program, matched against the entry
+    #[account(
+        executable,
+        address = entry.program @ MarketError::ResolverNotApproved,
+    )]
+    pub adapter_program: UncheckedAccount<'info>,
+}
@@ -0,0 +1,252 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::errors::MarketError;
+use crate::instructions::open_market;
+use crate::state::*;
+use crate::{ESCROW_SEED, MARKET_CONFIG_SEED, MARKET_SEED, NO_MINT_SEED, SERIES_SEED, YES_MINT_SEED};
+
+pub fn create_market_series(
+    ctx: Context<CreateMarketSeries>,
+    nonce: u64,
+    question: String,
+    first_end_time: i64,
+    interval: i64,
+    resolver: Pubkey,
+    fee_bps: u16,
+) -> Result<()> {
+    require!(question.len() <= MAX_QUESTION_LEN, MarketError::QuestionTooLong);
+    require!(
+        first_end_time > Clock::get()?.unix_timestamp,
+        MarketError::InvalidEndTime
+    );
+    require!(interval > 0, MarketError::InvalidSeriesInterval);
+    require!(fee_bps <= MAX_POOL_FEE_BPS, MarketError::FeeTooHigh);
+
+    let series_key = ctx.accounts.series.key();
+    let market = &mut ctx.accounts.market.load_init()?;
+    open_market(
+        &mut ctx.accounts.config,
+        market,
+        ctx.accounts.authority.key(),
+        resolver,
+        &question,
+        first_end_time,
+        series_key,
+    )?;
+    market.yes_mint = ctx.accounts.yes_mint.key();
+    market.no_mint = ctx.accounts.no_mint.key();
+    market.escrow = ctx.accounts.escrow.key();
+    market.bump = ctx.bumps.market;
+
+    let series = &mut ctx.accounts.series;
+    series.authority = ctx.accounts.authority.key();
+    series.nonce = nonce;
+    series.question = question;
+    series.resolver = resolver;
+    series.interval = interval;
+    series.fee_bps = fee_bps;
+    series.current_market = ctx.accounts.market.key();
+    series.current_end_time = first_end_time;
+    series.markets_opened = 1;
+    series.bump = ctx.bumps.series;
+
+    msg!("Market series created: {} every {}s", series_key, interval);
+    msg!("Market {} opened: {}", market.id, ctx.accounts.market.key());
+    Ok(())
+}
+
+pub fn roll_series(ctx: Context<RollSeries>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= ctx.accounts.current_market.load()?.end_time,
+        MarketError::MarketNotEnded
+    );
+
+    let series = &mut ctx.accounts.series;
+    let end_time = series.next_end_time(now).ok_or(MarketError::Overflow)?;
+    let market = &mut ctx.accounts.market.load_init()?;
+    open_market(
+        &mut ctx.accounts.config,
+        market,
+        series.authority,
+        series.resolver,
+        &series.question,
+        end_time,
+        series.key(),
+    )?;
+    market.yes_mint = ctx.accounts.yes_mint.key();
+    market.no_mint = ctx.accounts.no_mint.key();
+    market.escrow = ctx.accounts.escrow.key();
+    market.bump = ctx.bumps.market;
+
+    series.current_market = ctx.accounts.market.key();
+    series.current_end_time = end_time;
+    series.markets_opened = series.markets_opened.checked_add(1)
+        .ok_or(MarketError::Overflow)?;
+
+    msg!("Series rolled into market {} ending {}", market.id, end_time);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CreateMarketSeries<'info> {
+    /// The market config (assigns the market id)
+    #[account(
+        mut,
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The series to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MarketSeries::LEN,
+        seeds = [SERIES_SEED, authority.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub series: Box<Account<'info, MarketSeries>>,
+
+    /// The series' first market
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Market::LEN,
+        seeds = [MARKET_SEED, config.market_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// YES outcome mint, controlled by the market PDA
+    #[account(
+        init,
+        payer = authority,
+        seeds = [YES_MINT_SEED, market.key().as_ref()],
+        bump,
+        mint::decimals = dac_mint.decimals,
+        mint::authority = market,
+    )]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    /// NO outcome mint, controlled by the market PDA
+    #[account(
+        init,
+        payer = authority,
+        seeds = [NO_MINT_SEED, market.key().as_ref()],
+        bump,
+        mint::decimals = dac_mint.decimals,
+        mint::authority = market,
+    )]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// DAC escrow backing the outcome tokens
+    #[account(
+        init,
+        payer = authority,
+        seeds = [ESCROW_SEED, market.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = market,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == config.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RollSeries<'info> {
+    /// The market config (assigns the market id)
+    #[account(
+        mut,
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The series to roll
+    #[account(
+        mut,
+        seeds = [SERIES_SEED, series.authority.as_ref(), series.nonce.to_le_bytes().as_ref()],
+        bump = series.bump,
+        has_one = current_market @ MarketError::SeriesMismatch,
+    )]
+    pub series: Box<Account<'info, MarketSeries>>,
+
+    /// The series' current market, which must have stopped trading
+    pub current_market: AccountLoader<'info, Market>,
+
+    /// The next market in the series
+    #[account(
+        init,
+        payer = keeper,
+        space = 8 + Market::LEN,
+        seeds = [MARKET_SEED, config.market_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// YES outcome mint, controlled by the market PDA
+    #[account(
+        init,
+        payer = keeper,
+        seeds = [YES_MINT_SEED, market.key().as_ref()],
+        bump,
+        mint::decimals = dac_mint.decimals,
+        mint::authority = market,
+    )]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    /// NO outcome mint, controlled by the market PDA
+    #[account(
+        init,
+        payer = keeper,
+        seeds = [NO_MINT_SEED, market.key().as_ref()],
+        bump,
+        mint::decimals = dac_mint.decimals,
+        mint::authority = market,
+    )]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// DAC escrow backing the outcome tokens
+    #[account(
+        init,
+        payer = keeper,
+        seeds = [ESCROW_SEED, market.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = market,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == config.dac_mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    /// Anyone can roll a series; the keeper pays the new market's rent
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+
+use crate::errors::MarketError;
+use crate::events::{StealthPayment, MAX_STEALTH_HINT_LEN};
+use crate::instructions::transfer_from_escrow;
+use crate::state::*;
+use crate::{MARKET_SEED, STEALTH_PAYOUT_SEED};
+
+/// Redeem every outcome token held into a fresh stealth token account
+///
+/// The winner derives a one-time owner key from an ephemeral key and the
+/// recipient's stealth meta-address off-chain; the program creates the
+/// payout account at a PDA of the ephemeral key, so it shares nothing with
+/// the recipient's main wallet. Signing with a wallet that is itself
+/// unlinked (or redeeming through a relayer) keeps the winner private too.
+pub fn redeem_to_stealth(
+    ctx: Context<RedeemToStealth>,
+    ephemeral_pubkey: [u8; 32],
+    view_tag: u8,
+    encrypted_hint: Vec<u8>,
+) -> Result<()> {
+    require!(
+        encrypted_hint.len() <= MAX_STEALTH_HINT_LEN,
+        MarketError::StealthHintTooLong
+    );
+    let yes = ctx.accounts.user_yes.amount;
+    let no = ctx.accounts.user_no.amount;
+    require!(yes > 0 || no > 0, MarketError::ZeroAmount);
+
+    let payout = ctx.accounts.market.load()?.redemption_value(yes, no)
+        .ok_or(MarketError::MarketNotResolved)?;
+
+    for (mint, from, amount) in [
+        (&ctx.accounts.yes_mint, &ctx.accounts.user_yes, yes),
+        (&ctx.accounts.no_mint, &ctx.accounts.user_no, no),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let burn_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: mint.to_account_info(),
+                from: from.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        token::burn(burn_ctx, amount)?;
+    }
+
+    if payout > 0 {
+        transfer_from_escrow(
+            &ctx.accounts.market,
+            &ctx.accounts.escrow,
+            &ctx.accounts.stealth_payout,
+            &ctx.accounts.token_program,
+            payout,
+        )?;
+    }
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.total_collateral = market.total_collateral.checked_sub(payout)
+        .ok_or(MarketError::Underflow)?;
+
+    emit!(StealthPayment {
+        market: ctx.accounts.market.key(),
+        payout_account: ctx.accounts.stealth_payout.key(),
+        ephemeral_pubkey,
+        view_tag,
+        encrypted_hint,
+        amount: payout,
+    });
+
+    msg!("Redeemed {} YES / {} NO for {} DAC to a stealth account", yes, no, payout);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(ephemeral_pubkey: [u8; 32])]
+pub struct RedeemToStealth<'info> {
+    /// The resolved market
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = yes_mint @ MarketError::MintMismatch,
+        has_one = no_mint @ MarketError::MintMismatch,
+        has_one = escrow @ MarketError::MintMismatch,
+        constraint = market.load()?.status() == MarketStatus::Resolved @ MarketError::MarketNotResolved,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut)]
+    pub yes_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub no_mint: Box<Account<'info, Mint>>,
+
+    /// The market's DAC escrow
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The DAC mint
+    #[account(
+        constraint = dac_mint.key() == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: One-time owner key of the payout, derived off-chain by the winner
+    pub stealth_owner: UncheckedAccount<'info>,
+
+    /// Fresh DAC account receiving the payout (a reused ephemeral key fails here)
+    #[account(
+        init,
+        payer = user,
+        seeds = [STEALTH_PAYOUT_SEED, ephemeral_pubkey.as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = stealth_owner,
+    )]
+    pub stealth_payout: Box<Account<'info, TokenAccount>>,
+
+    /// User's YES token account (will be burned)
+    #[account(
+        mut,
+        constraint = user_yes.mint == market.load()?.yes_mint @ MarketError::MintMismatch,
+    )]
+    pub user_yes: Box<Account<'info, TokenAccount>>,
+
+    /// User's NO token account (will be burned)
+    #[account(
+        mut,
+        constraint = user_no.mint == market.load()?.no_mint @ MarketError::MintMismatch,
+    )]
+    pub user_no: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
+
+use crate::errors::MarketError;
+use crate::instructions::transfer_from_escrow;
+use crate::state::*;
+use crate::{INSURANCE_FUND_SEED, MARKET_SEED};
+
+pub fn set_claim_window(
+    ctx: Context<SetClaimWindow>,
+    claim_window: i64,
+    sweep_to_creator: bool,
+) -> Result<()> {
+    require!(
+        claim_window == 0 || claim_window >= MIN_CLAIM_WINDOW,
+        MarketError::InvalidClaimWindow
+    );
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.claim_window = claim_window;
+    market.sweep_to_creator = sweep_to_creator as u8;
+
+    msg!(
+        "Market {} claim window: {}s, unclaimed to {}",
+        market.id,
+        claim_window,
+        if sweep_to_creator { "creator" } else { "insurance fund" }
+    );
+    Ok(())
+}
+
+/// Move a market's unredeemed collateral out once its claim deadline has passed
+///
+/// Permissionless. The escrow is closed and its rent returned to the
+/// creator; the market account itself stays, as pools and positions
+/// still reference it.
+pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+    let (id, bump, sweep_to_creator, creator) = {
+        let market = ctx.accounts.market.load()?;
+        (market.id, market.bump, market.sweeps_to_creator(), market.creator)
+    };
+    let destination = &ctx.accounts.destination;
+    let valid_destination = if sweep_to_creator {
+        destination.owner == creator
+    } else {
+        let (insurance_fund, _) = Pubkey::find_program_address(&[INSURANCE_FUND_SEED], &crate::ID);
+        destination.key() == insurance_fund
+    };
+    require!(valid_destination, MarketError::InvalidSweepDestination);
+
+    let swept = ctx.accounts.escrow.amount;
+    if swept > 0 {
+        transfer_from_escrow(
+            &ctx.accounts.market,
+            &ctx.accounts.escrow,
+            destination,
+            &ctx.accounts.token_program,
+            swept,
+        )?;
+    }
+
+    let id_bytes = id.to_le_bytes();
+    let seeds = &[MARKET_SEED, id_bytes.as_ref(), &[bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: ctx.accounts.market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::close_account(close_ctx)?;
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    market.set_status(MarketStatus::Swept);
+    market.total_collateral = 0;
+
+    msg!("Market {} swept: {} unclaimed DAC", market.id, swept);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetClaimWindow<'info> {
+    /// The market to configure
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = creator @ MarketError::Unauthorized,
+        constraint = market.load()?.is_open() @ MarketError::MarketNotOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SweepUnclaimed<'info> {
+    /// The resolved market past its claim deadline
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        has_one = escrow @ MarketError::MintMismatch,
+        has_one = creator @ MarketError::Unauthorized,
+        constraint = market.load()?.claim_expired(Clock::get()?.unix_timestamp) @ MarketError::ClaimWindowOpen,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The market's DAC escrow (closed)
+    #[account(mut)]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// Receives the unclaimed DAC: the insurance fund, or a creator token account
+    #[account(
+        mut,
+        constraint = destination.mint == escrow.mint @ MarketError::MintMismatch,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Market creator, receives the escrow rent
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
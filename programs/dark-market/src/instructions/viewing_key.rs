@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::MarketError;
+use crate::events::{AuditMemo, PrivateAction, MAX_AUDIT_MEMO_LEN};
+use crate::state::*;
+use crate::VIEWING_KEY_SEED;
+
+/// Register or replace the auditor of the signer's private activity
+pub fn register_viewing_key(
+    ctx: Context<RegisterViewingKey>,
+    auditor: Pubkey,
+    encryption_key: [u8; 32],
+) -> Result<()> {
+    let viewing_key = &mut ctx.accounts.viewing_key;
+    viewing_key.owner = ctx.accounts.owner.key();
+    viewing_key.auditor = auditor;
+    viewing_key.encryption_key = encryption_key;
+    viewing_key.bump = ctx.bumps.viewing_key;
+
+    msg!("Viewing key registered for auditor {}", auditor);
+    Ok(())
+}
+
+pub fn remove_viewing_key(ctx: Context<RemoveViewingKey>) -> Result<()> {
+    msg!("Viewing key for auditor {} removed", ctx.accounts.viewing_key.auditor);
+    Ok(())
+}
+
+/// Emit the auditor's memo of a private action, if the owner registered a viewing key
+///
+/// `viewing_key` is the owner's viewing key PDA, passed whether or not it
+/// exists so the memo cannot be skipped by leaving it out. Without a key
+/// the memo must be empty.
+pub(crate) fn emit_audit_memo<'info>(
+    viewing_key: &AccountInfo<'info>,
+    owner: Pubkey,
+    action: PrivateAction,
+    subject: Pubkey,
+    memo: Vec<u8>,
+) -> Result<()> {
+    if viewing_key.data_is_empty() {
+        require!(memo.is_empty(), MarketError::InvalidAuditMemo);
+        return Ok(());
+    }
+    let viewing_key = Account::<ViewingKey>::try_from(viewing_key)?;
+    require!(
+        !memo.is_empty() && memo.len() <= MAX_AUDIT_MEMO_LEN,
+        MarketError::InvalidAuditMemo
+    );
+
+    emit!(AuditMemo {
+        owner,
+        auditor: viewing_key.auditor,
+        action,
+        subject,
+        memo,
+    });
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct RegisterViewingKey<'info> {
+    /// The viewing key (created on first use)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + ViewingKey::LEN,
+        seeds = [VIEWING_KEY_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub viewing_key: Account<'info, ViewingKey>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveViewingKey<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [VIEWING_KEY_SEED, owner.key().as_ref()],
+        bump = viewing_key.bump,
+        has_one = owner @ MarketError::Unauthorized,
+    )]
+    pub viewing_key: Account<'info, ViewingKey>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{MARKET_CONFIG_SEED, MARKET_SEED, VOLUME_COUNTER_SEED, VOLUME_REPORTING_SEED};
+
+/// Set the reporting key for counters created from now on (existing counters keep theirs)
+pub fn configure_volume_reporting(
+    ctx: Context<ConfigureVolumeReporting>,
+    reporting_authority: Pubkey,
+    reporting_key: [u8; 32],
+) -> Result<()> {
+    let reporting = &mut ctx.accounts.volume_reporting;
+    reporting.authority = reporting_authority;
+    reporting.reporting_key = reporting_key;
+    reporting.bump = ctx.bumps.volume_reporting;
+
+    msg!("Volume reporting configured for {}", reporting_authority);
+    Ok(())
+}
+
+pub fn publish_volume_report(ctx: Context<PublishVolumeReport>, volume: u64) -> Result<()> {
+    let counter = &mut ctx.accounts.volume_counter;
+    counter.reported = Some(volume);
+
+    msg!(
+        "Market {} private volume: {} DAC over {} trades",
+        counter.market,
+        volume,
+        counter.trades
+    );
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ConfigureVolumeReporting<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The reporting settings (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + VolumeReporting::LEN,
+        seeds = [VOLUME_REPORTING_SEED],
+        bump
+    )]
+    pub volume_reporting: Account<'info, VolumeReporting>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PublishVolumeReport<'info> {
+    /// The market, past its end time
+    #[account(
+        seeds = [MARKET_SEED, market.load()?.id.to_le_bytes().as_ref()],
+        bump = market.load()?.bump,
+        constraint = Clock::get()?.unix_timestamp >= market.load()?.end_time @ MarketError::MarketNotEnded,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        seeds = [VOLUME_REPORTING_SEED],
+        bump = volume_reporting.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub volume_reporting: Account<'info, VolumeReporting>,
+
+    /// The counter to publish
+    #[account(
+        mut,
+        seeds = [VOLUME_COUNTER_SEED, market.key().as_ref()],
+        bump = volume_counter.bump,
+        constraint = volume_counter.reported.is_none() @ MarketError::VolumeAlreadyReported,
+    )]
+    pub volume_counter: Box<Account<'info, VolumeCounter>>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
+
+use crate::errors::MarketError;
+use crate::state::*;
+use crate::{MARKET_CONFIG_SEED, WRAP_BUNDLING_SEED};
+
+/// Set whether bets must be bundled with a wrap of `wrap_program` (authority only)
+///
+/// The bundling account must exist before anyone can split or buy, so it is
+/// created here (disabled, if need be) as part of deployment.
+pub fn configure_wrap_bundling(
+    ctx: Context<ConfigureWrapBundling>,
+    wrap_program: Pubkey,
+    enabled: bool,
+) -> Result<()> {
+    let bundling = &mut ctx.accounts.wrap_bundling;
+    bundling.wrap_program = wrap_program;
+    bundling.enabled = enabled;
+    bundling.bump = ctx.bumps.wrap_bundling;
+
+    msg!(
+        "Wrap bundling {} for {}",
+        if enabled { "enabled" } else { "disabled" },
+        wrap_program
+    );
+    Ok(())
+}
+
+/// Fail if bundling is on and the transaction has no `wrap` instruction
+pub(crate) fn check_wrap_bundled(bundling: &WrapBundling, instructions: &AccountInfo) -> Result<()> {
+    if !bundling.enabled {
+        return Ok(());
+    }
+    let discriminator = WrapBundling::wrap_discriminator();
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+        if ix.program_id == bundling.wrap_program && ix.data.starts_with(&discriminator) {
+            return Ok(());
+        }
+        index += 1;
+    }
+    err!(MarketError::BetNotBundled)
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ConfigureWrapBundling<'info> {
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        has_one = authority @ MarketError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The bundling setting (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + WrapBundling::LEN,
+        seeds = [WRAP_BUNDLING_SEED],
+        bump
+    )]
+    pub wrap_bundling: Account<'info, WrapBundling>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,1182 @@
+//! Dark Market Program
+//!
+//! Binary prediction markets that use DAC as collateral. Every market escrows
+//! DAC and issues a YES and a NO outcome token; one DAC always splits into one
+//! YES plus one NO, so the escrow fully backs every outstanding token.
+//!
+//! Key features:
+//! - Split/merge DAC into complete sets of outcome tokens
+//! - Resolution by the market's designated resolver after the end time
+//! - Redemption of winning tokens (invalid markets redeem both sides at 50%)
+//! - Parlays: peer-to-peer combinatorial positions across several markets
+//! - Position receipts: outcome tokens wrapped into transferable Metaplex NFTs
+//! - Compressed Bubblegum receipts for very large markets, verified by proof at unwrap
+//! - Compressed positions as bare concurrent Merkle tree leaves, with a canopy sized per market
+//! - Permissionless batch settlement paying out many compressed positions per transaction
+//! - Constant-product YES/NO pools with resting limit orders filled by crankers
+//! - TWAP oracle of implied probabilities from per-pool price observations
+//! - Per-pool circuit breaker halting trades on extreme price moves
+//! - Optimistic resolution with disputes escalated to a DAC-staked arbitration council
+//! - Recurring market series rolled into the next market by permissionless keepers
+//! - Cross-margin accounts valuing positions at their worst case across linked markets
+//! - Permissionless partial liquidation of under-collateralized margin accounts
+//! - Leveraged outcome positions with funding and forced deleveraging before resolution
+//! - Per-user portfolio accounts tracking cost basis and realized/unrealized P&L
+//! - Tiered swap fee discounts for traders staking DAC
+//! - Maker rebates on limit order fills, funded from a share of taker fees
+//! - Claim deadlines after which unredeemed collateral is swept and the escrow closed
+//! - Bonding-curve launches selling outcome tokens and seeding the pool with the proceeds
+//! - Registry of approved resolver adapters, resolving markets by CPI (Pyth, Switchboard, ...)
+//! - Volatility-aware swap fee surcharges computed from the TWAP observations
+//! - Commit-reveal betting in batches, hiding bet direction and size until execution
+//! - Groth16 proof verification over alt_bn128, with a verifying key per circuit
+//! - Dark pools of encrypted orders matched by an Arcium MPC cluster
+//! - Sealed-bid auctions of large outcome token positions
+//! - Redemption to one-time stealth accounts with encrypted scanning hints
+//! - ElGamal-encrypted positions decrypted by a threshold committee after resolution
+//! - Relayer-submitted batches of user-signed orders, netted against the pool
+//! - Homomorphically aggregated encrypted volume, reported only in total after close
+//! - Viewing keys disclosing a user's private activity to their auditor
+//! - Range proofs holding hidden confidential stakes to per-side bet limits
+//! - Private resolution: sealed outcomes freeze trading and are revealed after a delay,
+//!   or void the market if nobody reveals them in time
+//! - Anonymous market creation through a creator escrow claimed with a ZK proof
+//! - Optional bundling of bets with DAC wraps, checked by instruction introspection
+//! - Per-market KYC gating on Solana Attestation Service attestations of a chosen schema and issuer
+//! - Liquidity mining: weighted gauges streaming DAC rewards to deposited LP shares
+//! - A configurable share of swap fees paid to DAC stakers
+//! - Protocol-owned address lookup tables of every market, extended by keepers
+//! - Zero-copy markets and pool observations
+
+// Anchor 0.31's `#[program]` expansion still calls the deprecated `AccountInfo::realloc`
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod bubblegum;
+pub mod compression;
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+use state::*;
+
+declare_id!("GnP12qDqpY2by1bPQpqTEkZUaqpAPF8J5FW1Ls8oecKb");
+
+/// Seeds for the protocol-wide market config
+pub const MARKET_CONFIG_SEED: &[u8] = b"market_config";
+/// Seeds for a market account (followed by the market id)
+pub const MARKET_SEED: &[u8] = b"market";
+/// Seeds for a market's YES outcome mint
+pub const YES_MINT_SEED: &[u8] = b"yes_mint";
+/// Seeds for a market's NO outcome mint
+pub const NO_MINT_SEED: &[u8] = b"no_mint";
+/// Seeds for a market's DAC escrow
+pub const ESCROW_SEED: &[u8] = b"escrow";
+/// Seeds for a parlay position (followed by owner and nonce)
+pub const PARLAY_SEED: &[u8] = b"parlay";
+/// Seeds for a parlay's DAC escrow
+pub const PARLAY_ESCROW_SEED: &[u8] = b"parlay_escrow";
+/// Seeds for a position receipt (followed by market, owner and nonce)
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+/// Seeds for a position receipt's NFT mint
+pub const RECEIPT_MINT_SEED: &[u8] = b"receipt_mint";
+/// Seeds for the vault holding a receipt's outcome tokens
+pub const RECEIPT_VAULT_SEED: &[u8] = b"receipt_vault";
+/// Seeds for a market's compressed receipt tree (followed by the market)
+pub const RECEIPT_TREE_SEED: &[u8] = b"receipt_tree";
+/// Seeds for a compressed receipt vault (followed by the receipt tree and the outcome mint)
+pub const COMPRESSED_RECEIPT_VAULT_SEED: &[u8] = b"compressed_receipt_vault";
+/// Seeds for a market's compressed position tree (followed by the market)
+pub const POSITION_TREE_SEED: &[u8] = b"position_tree";
+/// Seeds for a position tree vault (followed by the position tree and the outcome mint)
+pub const POSITION_TREE_VAULT_SEED: &[u8] = b"position_tree_vault";
+/// Seeds for a market's pool
+pub const POOL_SEED: &[u8] = b"pool";
+/// Seeds for a pool's YES reserve vault
+pub const POOL_YES_SEED: &[u8] = b"pool_yes";
+/// Seeds for a pool's NO reserve vault
+pub const POOL_NO_SEED: &[u8] = b"pool_no";
+/// Seeds for a pool's LP share mint
+pub const LP_MINT_SEED: &[u8] = b"lp_mint";
+/// Seeds for a limit order (followed by pool, owner and nonce)
+pub const LIMIT_ORDER_SEED: &[u8] = b"limit_order";
+/// Seeds for a limit order's vault
+pub const LIMIT_ORDER_VAULT_SEED: &[u8] = b"limit_order_vault";
+/// Seeds for a pool's price observation buffer
+pub const OBSERVATIONS_SEED: &[u8] = b"observations";
+/// Seeds for the arbitration council
+pub const COUNCIL_SEED: &[u8] = b"council";
+/// Seeds for the council's stake vault
+pub const COUNCIL_VAULT_SEED: &[u8] = b"council_vault";
+/// Seeds for a council member's stake (followed by the member)
+pub const COUNCIL_MEMBER_SEED: &[u8] = b"council_member";
+/// Seeds for a council vote (followed by proposal and member)
+pub const COUNCIL_VOTE_SEED: &[u8] = b"council_vote";
+/// Seeds for a market's resolution proposal (followed by the market)
+pub const RESOLUTION_SEED: &[u8] = b"resolution";
+/// Seeds for a resolution proposal's bond vault
+pub const RESOLUTION_VAULT_SEED: &[u8] = b"resolution_vault";
+/// Seeds for a market series (followed by authority and nonce)
+pub const SERIES_SEED: &[u8] = b"series";
+/// Seeds for the DAC vault shared by every margin account
+pub const MARGIN_VAULT_SEED: &[u8] = b"margin_vault";
+/// Seeds for a margin account (followed by the owner)
+pub const MARGIN_ACCOUNT_SEED: &[u8] = b"margin_account";
+/// Seeds for a margin account's outcome token custody (followed by margin account and mint)
+pub const MARGIN_CUSTODY_SEED: &[u8] = b"margin_custody";
+/// Seeds for a market link (followed by both markets)
+pub const MARKET_LINK_SEED: &[u8] = b"market_link";
+/// Seeds for the insurance fund collecting liquidation penalties
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund";
+/// Seeds for a market's leverage settings (followed by the market)
+pub const LEVERAGE_PARAMS_SEED: &[u8] = b"leverage_params";
+/// Seeds for a leveraged position (followed by margin account, market and outcome)
+pub const LEVERAGED_POSITION_SEED: &[u8] = b"leveraged_position";
+/// Seeds for a user's portfolio (followed by the owner)
+pub const PORTFOLIO_SEED: &[u8] = b"portfolio";
+/// Seeds for the swap fee discount tiers
+pub const FEE_TIERS_SEED: &[u8] = b"fee_tiers";
+/// Seeds for a pool's maker rebate settings (followed by the pool)
+pub const REBATE_POOL_SEED: &[u8] = b"rebate_pool";
+/// Seeds for a pool's maker rebate vault (followed by the rebate pool)
+pub const REBATE_VAULT_SEED: &[u8] = b"rebate_vault";
+/// Seeds for a maker's accrued rebates (followed by the pool and the maker)
+pub const MAKER_REBATE_SEED: &[u8] = b"maker_rebate";
+/// Seeds for a market's bonding-curve launch (followed by the market)
+pub const LAUNCH_SEED: &[u8] = b"launch";
+/// Seeds for a launch's DAC vault (followed by the launch)
+pub const LAUNCH_VAULT_SEED: &[u8] = b"launch_vault";
+/// Seeds for a launch's unsold YES (followed by the launch)
+pub const LAUNCH_YES_SEED: &[u8] = b"launch_yes";
+/// Seeds for a launch's unsold NO (followed by the launch)
+pub const LAUNCH_NO_SEED: &[u8] = b"launch_no";
+/// Seeds for a resolver registry entry (followed by the resolver id)
+pub const RESOLVER_SEED: &[u8] = b"resolver";
+/// Seeds for a bet batch (followed by the pool and the batch id)
+pub const BET_BATCH_SEED: &[u8] = b"bet_batch";
+/// Seeds for a bet batch's DAC vault (followed by the batch)
+pub const BET_BATCH_VAULT_SEED: &[u8] = b"bet_batch_vault";
+/// Seeds for a bet batch's YES holding (followed by the batch)
+pub const BET_BATCH_YES_SEED: &[u8] = b"bet_batch_yes";
+/// Seeds for a bet batch's NO holding (followed by the batch)
+pub const BET_BATCH_NO_SEED: &[u8] = b"bet_batch_no";
+/// Seeds for a hidden bet (followed by the batch and the owner)
+pub const BET_COMMITMENT_SEED: &[u8] = b"bet_commitment";
+/// Seeds for a Groth16 circuit's verifying key (followed by the circuit id)
+pub const VERIFYING_KEY_SEED: &[u8] = b"verifying_key";
+/// Seeds for a market's dark pool (followed by the market)
+pub const DARK_POOL_SEED: &[u8] = b"dark_pool";
+/// Seeds for a dark pool's DAC vault (followed by the dark pool)
+pub const DARK_POOL_VAULT_SEED: &[u8] = b"dark_pool_vault";
+/// Seeds for a dark order (followed by the dark pool and the order id)
+pub const DARK_ORDER_SEED: &[u8] = b"dark_order";
+/// Seeds for a sealed-bid auction (followed by the seller and the auction id)
+pub const AUCTION_SEED: &[u8] = b"auction";
+/// Seeds for an auction's token vault (followed by the auction)
+pub const AUCTION_TOKENS_SEED: &[u8] = b"auction_tokens";
+/// Seeds for an auction's deposit vault (followed by the auction)
+pub const AUCTION_DAC_SEED: &[u8] = b"auction_dac";
+/// Seeds for a sealed bid (followed by the auction and the bidder)
+pub const SEALED_BID_SEED: &[u8] = b"sealed_bid";
+/// Seeds for a stealth payout account (followed by the ephemeral public key)
+pub const STEALTH_PAYOUT_SEED: &[u8] = b"stealth_payout";
+/// Seeds for a market's confidential pool (followed by the market)
+pub const CONFIDENTIAL_MARKET_SEED: &[u8] = b"confidential_market";
+/// Seeds for a confidential pool's DAC vault (followed by the confidential market)
+pub const CONFIDENTIAL_VAULT_SEED: &[u8] = b"confidential_vault";
+/// Seeds for a confidential position (followed by the confidential market and the position id)
+pub const CONFIDENTIAL_POSITION_SEED: &[u8] = b"confidential_position";
+/// Seeds for an approved relayer (followed by the relayer's signing key)
+pub const RELAYER_SEED: &[u8] = b"relayer";
+/// Seeds for a user's relay account (followed by the owner)
+pub const RELAY_ACCOUNT_SEED: &[u8] = b"relay_account";
+/// Seeds for the DAC vault backing relay accounts
+pub const RELAY_VAULT_SEED: &[u8] = b"relay_vault";
+/// Seeds for the protocol volume reporting settings
+pub const VOLUME_REPORTING_SEED: &[u8] = b"volume_reporting";
+/// Seeds for a market's encrypted volume counter (followed by the market)
+pub const VOLUME_COUNTER_SEED: &[u8] = b"volume_counter";
+/// Seeds for a user's viewing key (followed by the owner)
+pub const VIEWING_KEY_SEED: &[u8] = b"viewing_key";
+/// Seeds for a market's private resolution (followed by the market)
+pub const SEALED_RESOLUTION_SEED: &[u8] = b"sealed_resolution";
+/// Seeds for an anonymous market's creator escrow (followed by the market)
+pub const CREATOR_ESCROW_SEED: &[u8] = b"creator_escrow";
+/// Seeds for a creator escrow's DAC vault (followed by the market)
+pub const CREATOR_VAULT_SEED: &[u8] = b"creator_vault";
+/// Seeds for the wrap bundling setting
+pub const WRAP_BUNDLING_SEED: &[u8] = b"wrap_bundling";
+/// Seeds for the liquidity mining gauge controller
+pub const GAUGE_CONTROLLER_SEED: &[u8] = b"gauge_controller";
+/// Seeds for the liquidity mining reward vault
+pub const GAUGE_REWARD_VAULT_SEED: &[u8] = b"gauge_reward_vault";
+/// Seeds for a pool's gauge (followed by the pool)
+pub const GAUGE_SEED: &[u8] = b"gauge";
+/// Seeds for a gauge's LP token vault (followed by the gauge)
+pub const GAUGE_LP_VAULT_SEED: &[u8] = b"gauge_lp_vault";
+/// Seeds for an LP's gauge deposit (followed by the gauge and the owner)
+pub const GAUGE_STAKE_SEED: &[u8] = b"gauge_stake";
+/// Seeds for the stakers' swap fee share
+pub const FEE_SHARING_SEED: &[u8] = b"fee_sharing";
+/// Seeds for the lookup table registry
+pub const LOOKUP_TABLE_REGISTRY_SEED: &[u8] = b"lookup_table_registry";
+/// Seeds for a protocol lookup table's record (followed by the table index)
+pub const LOOKUP_TABLE_SEED: &[u8] = b"lookup_table";
+/// Seeds for the authority of every protocol lookup table
+pub const LOOKUP_TABLE_AUTHORITY_SEED: &[u8] = b"lookup_table_authority";
+
+#[program]
+pub mod dark_market {
+    use super::*;
+
+    /// Initialize the protocol-wide market configuration
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        instructions::initialize_config(ctx)
+    }
+
+    /// Hand the config authority to a new key (authority only)
+    pub fn set_config_authority(ctx: Context<SetConfigAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::set_config_authority(ctx, new_authority)
+    }
+
+    /// Create a new binary market with its outcome mints and DAC escrow
+    pub fn create_market(
+        ctx: Context<CreateMarket>,
+        question: String,
+        end_time: i64,
+        resolver: Pubkey,
+    ) -> Result<()> {
+        instructions::create_market(ctx, question, end_time, resolver)
+    }
+
+    /// Split DAC into an equal amount of YES and NO tokens
+    pub fn split(ctx: Context<Split>, amount: u64) -> Result<()> {
+        instructions::split(ctx, amount)
+    }
+
+    /// Merge equal amounts of YES and NO tokens back into DAC
+    pub fn merge(ctx: Context<Merge>, amount: u64) -> Result<()> {
+        instructions::merge(ctx, amount)
+    }
+
+    /// Resolve a market once its end time has passed
+    pub fn resolve_market(ctx: Context<ResolveMarket>, outcome: Outcome) -> Result<()> {
+        instructions::resolve_market(ctx, outcome)
+    }
+
+    /// Burn the caller's outcome tokens of a resolved market for DAC
+    pub fn redeem(ctx: Context<Redeem>) -> Result<()> {
+        instructions::redeem(ctx)
+    }
+
+    /// Open a parlay: lock a DAC stake on a basket of market outcomes
+    pub fn open_parlay<'info>(
+        ctx: Context<'_, '_, 'info, 'info, OpenParlay<'info>>,
+        nonce: u64,
+        legs: Vec<ParlayLeg>,
+        stake: u64,
+        payout: u64,
+    ) -> Result<()> {
+        instructions::open_parlay(ctx, nonce, legs, stake, payout)
+    }
+
+    /// Take the other side of an open parlay by funding the rest of its payout
+    pub fn back_parlay<'info>(ctx: Context<'_, '_, 'info, 'info, BackParlay<'info>>) -> Result<()> {
+        instructions::back_parlay(ctx)
+    }
+
+    /// Cancel a parlay that has not been backed yet and refund the stake
+    pub fn cancel_parlay(ctx: Context<CancelParlay>) -> Result<()> {
+        instructions::cancel_parlay(ctx)
+    }
+
+    /// Settle a backed parlay once every leg's market has resolved
+    pub fn settle_parlay<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleParlay<'info>>,
+    ) -> Result<()> {
+        instructions::settle_parlay(ctx)
+    }
+
+    /// Wrap outcome tokens into a transferable position receipt NFT
+    pub fn wrap_position(
+        ctx: Context<WrapPosition>,
+        nonce: u64,
+        outcome: Outcome,
+        amount: u64,
+        uri: String,
+    ) -> Result<()> {
+        instructions::wrap_position(ctx, nonce, outcome, amount, uri)
+    }
+
+    /// Burn a position receipt NFT and release its outcome tokens to the holder
+    pub fn unwrap_position(ctx: Context<UnwrapPosition>) -> Result<()> {
+        instructions::unwrap_position(ctx)
+    }
+
+    /// Give a market a Bubblegum tree of compressed position receipts (market creator only)
+    pub fn enable_compressed_receipts(
+        ctx: Context<EnableCompressedReceipts>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        instructions::enable_compressed_receipts(ctx, max_depth, max_buffer_size)
+    }
+
+    /// Wrap outcome tokens into a compressed position receipt
+    pub fn wrap_position_compressed(
+        ctx: Context<WrapPositionCompressed>,
+        outcome: Outcome,
+        amount: u64,
+        uri: String,
+    ) -> Result<()> {
+        instructions::wrap_position_compressed(ctx, outcome, amount, uri)
+    }
+
+    /// Burn a compressed position receipt, proven against the tree root, and release its outcome tokens
+    #[allow(clippy::too_many_arguments)]
+    pub fn unwrap_compressed_position<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UnwrapCompressedPosition<'info>>,
+        root: [u8; 32],
+        outcome: Outcome,
+        amount: u64,
+        uri: String,
+        nonce: u64,
+        index: u32,
+    ) -> Result<()> {
+        instructions::unwrap_compressed_position(ctx, root, outcome, amount, uri, nonce, index)
+    }
+
+    /// Give a market a concurrent Merkle tree of compressed positions with a canopy of `canopy_depth`
+    pub fn enable_position_tree(
+        ctx: Context<EnablePositionTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+        canopy_depth: u32,
+    ) -> Result<()> {
+        instructions::enable_position_tree(ctx, max_depth, max_buffer_size, canopy_depth)
+    }
+
+    /// Deposit outcome tokens into a new compressed position
+    pub fn deposit_compressed_position(
+        ctx: Context<DepositCompressedPosition>,
+        outcome: Outcome,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::deposit_compressed_position(ctx, outcome, amount)
+    }
+
+    /// Resize a compressed position, proven against the tree root
+    pub fn update_compressed_position<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateCompressedPosition<'info>>,
+        root: [u8; 32],
+        outcome: Outcome,
+        amount: u64,
+        index: u32,
+        new_amount: u64,
+    ) -> Result<()> {
+        instructions::update_compressed_position(ctx, root, outcome, amount, index, new_amount)
+    }
+
+    /// Redeem a compressed position of a resolved market, proven against the tree root
+    pub fn redeem_compressed_position<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RedeemCompressedPosition<'info>>,
+        root: [u8; 32],
+        outcome: Outcome,
+        amount: u64,
+        index: u32,
+    ) -> Result<()> {
+        instructions::redeem_compressed_position(ctx, root, outcome, amount, index)
+    }
+
+    /// Redeem a batch of compressed positions of a resolved market for their owners (permissionless)
+    pub fn settle_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleBatch<'info>>,
+        root: [u8; 32],
+        positions: Vec<SettledPosition>,
+    ) -> Result<()> {
+        instructions::settle_batch(ctx, root, positions)
+    }
+
+    /// Create a market's constant-product pool seeded with DAC liquidity
+    pub fn create_pool(ctx: Context<CreatePool>, fee_bps: u16, amount: u64) -> Result<()> {
+        instructions::create_pool(ctx, fee_bps, amount)
+    }
+
+    /// Add DAC liquidity to a pool for LP shares
+    pub fn add_liquidity(
+        ctx: Context<AddLiquidity>,
+        amount: u64,
+        min_shares: u64,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        instructions::add_liquidity(ctx, amount, min_shares, expiry_slot)
+    }
+
+    /// Burn LP shares for a proportional share of the YES and NO reserves
+    pub fn remove_liquidity(
+        ctx: Context<RemoveLiquidity>,
+        shares: u64,
+        min_yes_out: u64,
+        min_no_out: u64,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        instructions::remove_liquidity(ctx, shares, min_yes_out, min_no_out, expiry_slot)
+    }
+
+    /// Buy outcome tokens from the pool with DAC
+    pub fn buy(
+        ctx: Context<Swap>,
+        outcome: Outcome,
+        amount_in: u64,
+        min_amount_out: u64,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        instructions::buy(ctx, outcome, amount_in, min_amount_out, expiry_slot)
+    }
+
+    /// Sell outcome tokens to the pool for DAC
+    pub fn sell(
+        ctx: Context<Swap>,
+        outcome: Outcome,
+        amount_in: u64,
+        min_amount_out: u64,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        instructions::sell(ctx, outcome, amount_in, min_amount_out, expiry_slot)
+    }
+
+    /// Lock DAC or outcome tokens in a limit order against a pool
+    pub fn place_limit_order(
+        ctx: Context<PlaceLimitOrder>,
+        nonce: u64,
+        side: OrderSide,
+        outcome: Outcome,
+        amount: u64,
+        limit_price: u64,
+        tip: u64,
+    ) -> Result<()> {
+        instructions::place_limit_order(ctx, nonce, side, outcome, amount, limit_price, tip)
+    }
+
+    /// Fill a limit order against the pool once its price is reached (permissionless)
+    pub fn fill_limit_order(ctx: Context<FillLimitOrder>) -> Result<()> {
+        instructions::fill_limit_order(ctx)
+    }
+
+    /// Cancel a limit order and refund the locked asset
+    pub fn cancel_limit_order(ctx: Context<CancelLimitOrder>) -> Result<()> {
+        instructions::cancel_limit_order(ctx)
+    }
+
+    /// Read the time-weighted average price of an outcome (view)
+    pub fn read_twap(ctx: Context<ReadTwap>, outcome: Outcome, window: i64) -> Result<u64> {
+        instructions::read_twap(ctx, outcome, window)
+    }
+
+    /// Hand the guardian role to a new key
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        instructions::set_guardian(ctx, guardian)
+    }
+
+    /// Configure a pool's circuit breaker (a zero threshold disables it)
+    pub fn set_circuit_breaker(
+        ctx: Context<SetCircuitBreaker>,
+        threshold_bps: u16,
+        window_slots: u64,
+        cooloff_slots: u64,
+    ) -> Result<()> {
+        instructions::set_circuit_breaker(ctx, threshold_bps, window_slots, cooloff_slots)
+    }
+
+    /// Lift a pool's trading halt before its cool-off ends
+    pub fn resume_trading(ctx: Context<ResumeTrading>) -> Result<()> {
+        instructions::resume_trading(ctx)
+    }
+
+    /// Configure a pool's volatility fee surcharge (a zero window disables it)
+    pub fn set_dynamic_fee(
+        ctx: Context<SetDynamicFee>,
+        window: i64,
+        multiplier_bps: u32,
+        max_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_dynamic_fee(ctx, window, multiplier_bps, max_fee_bps)
+    }
+
+    /// Create the arbitration council and its stake vault
+    pub fn initialize_council(ctx: Context<InitializeCouncil>) -> Result<()> {
+        instructions::initialize_council(ctx)
+    }
+
+    /// Stake DAC to join (or add to a seat on) the arbitration council
+    pub fn join_council(ctx: Context<JoinCouncil>, amount: u64) -> Result<()> {
+        instructions::join_council(ctx, amount)
+    }
+
+    /// Withdraw council stake once every vote has been settled
+    pub fn leave_council(ctx: Context<LeaveCouncil>, amount: u64) -> Result<()> {
+        instructions::leave_council(ctx, amount)
+    }
+
+    /// Propose the outcome of an optimistically resolved market with a DAC bond
+    pub fn propose_resolution(
+        ctx: Context<ProposeResolution>,
+        outcome: Outcome,
+        bond: u64,
+    ) -> Result<()> {
+        instructions::propose_resolution(ctx, outcome, bond)
+    }
+
+    /// Dispute a proposal within its challenge period by matching the bond
+    pub fn dispute_resolution(ctx: Context<DisputeResolution>) -> Result<()> {
+        instructions::dispute_resolution(ctx)
+    }
+
+    /// Vote on a disputed proposal with the member's staked DAC
+    pub fn cast_vote(ctx: Context<CastVote>, outcome: Outcome) -> Result<()> {
+        instructions::cast_vote(ctx, outcome)
+    }
+
+    /// Resolve the market from an unchallenged proposal or the council's vote
+    pub fn finalize_resolution(ctx: Context<FinalizeResolution>) -> Result<()> {
+        instructions::finalize_resolution(ctx)
+    }
+
+    /// Settle a council vote, slashing it if it went against the final majority (permissionless)
+    pub fn settle_vote(ctx: Context<SettleVote>) -> Result<()> {
+        instructions::settle_vote(ctx)
+    }
+
+    /// Move slashed council stake out of the vault
+    pub fn sweep_slashed(ctx: Context<SweepSlashed>, amount: u64) -> Result<()> {
+        instructions::sweep_slashed(ctx, amount)
+    }
+
+    /// Create a recurring market series and open its first market
+    pub fn create_market_series(
+        ctx: Context<CreateMarketSeries>,
+        nonce: u64,
+        question: String,
+        first_end_time: i64,
+        interval: i64,
+        resolver: Pubkey,
+        fee_bps: u16,
+    ) -> Result<()> {
+        instructions::create_market_series(
+            ctx,
+            nonce,
+            question,
+            first_end_time,
+            interval,
+            resolver,
+            fee_bps,
+        )
+    }
+
+    /// Open the next market of a series once the current one stops trading (permissionless)
+    pub fn roll_series(ctx: Context<RollSeries>) -> Result<()> {
+        instructions::roll_series(ctx)
+    }
+
+    /// Create the DAC vault shared by every margin account
+    pub fn initialize_margin_vault(ctx: Context<InitializeMarginVault>) -> Result<()> {
+        instructions::initialize_margin_vault(ctx)
+    }
+
+    /// Attest a correlation between the outcomes of two markets
+    pub fn create_market_link(ctx: Context<CreateMarketLink>, kind: LinkKind) -> Result<()> {
+        instructions::create_market_link(ctx, kind)
+    }
+
+    /// Open the caller's cross-margin account
+    pub fn open_margin_account(ctx: Context<OpenMarginAccount>) -> Result<()> {
+        instructions::open_margin_account(ctx)
+    }
+
+    /// Deposit DAC into a margin account, repaying debt first
+    pub fn deposit_margin(ctx: Context<DepositMargin>, amount: u64) -> Result<()> {
+        instructions::deposit_margin(ctx, amount)
+    }
+
+    /// Withdraw DAC from a margin account, borrowing against its positions past the collateral
+    pub fn withdraw_margin(ctx: Context<WithdrawMargin>, amount: u64) -> Result<()> {
+        instructions::withdraw_margin(ctx, amount)
+    }
+
+    /// Move outcome tokens into a margin account's custody
+    pub fn deposit_position(
+        ctx: Context<DepositPosition>,
+        outcome: Outcome,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::deposit_position(ctx, outcome, amount)
+    }
+
+    /// Move outcome tokens out of a margin account's custody
+    pub fn withdraw_position(
+        ctx: Context<WithdrawPosition>,
+        outcome: Outcome,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_position(ctx, outcome, amount)
+    }
+
+    /// Use a market link when valuing a margin account's positions
+    pub fn attach_market_link(ctx: Context<AttachMarketLink>) -> Result<()> {
+        instructions::attach_market_link(ctx)
+    }
+
+    /// Stop using a market link when valuing a margin account's positions
+    pub fn detach_market_link(ctx: Context<DetachMarketLink>, index: u8) -> Result<()> {
+        instructions::detach_market_link(ctx, index)
+    }
+
+    /// Redeem a margin account's tokens of a resolved market into its collateral (permissionless)
+    pub fn settle_margin_position(
+        ctx: Context<SettleMarginPosition>,
+        outcome: Outcome,
+    ) -> Result<()> {
+        instructions::settle_margin_position(ctx, outcome)
+    }
+
+    /// Create the insurance fund collecting part of every liquidation penalty
+    pub fn initialize_insurance_fund(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+        instructions::initialize_insurance_fund(ctx)
+    }
+
+    /// Repay part of an under-collateralized margin account's debt for its outcome tokens (permissionless)
+    pub fn liquidate(ctx: Context<Liquidate>, outcome: Outcome, repay: u64) -> Result<()> {
+        instructions::liquidate(ctx, outcome, repay)
+    }
+
+    /// Configure a market's leverage limit, funding rate and delever window
+    pub fn set_leverage_params(
+        ctx: Context<SetLeverageParams>,
+        max_leverage_bps: u32,
+        funding_rate_bps: u16,
+        delever_window: i64,
+    ) -> Result<()> {
+        instructions::set_leverage_params(ctx, max_leverage_bps, funding_rate_bps, delever_window)
+    }
+
+    /// Buy an outcome with margin collateral plus DAC borrowed from the margin vault
+    pub fn open_leveraged(
+        ctx: Context<OpenLeveraged>,
+        outcome: Outcome,
+        margin: u64,
+        leverage_bps: u32,
+        min_amount_out: u64,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        instructions::open_leveraged(ctx, outcome, margin, leverage_bps, min_amount_out, expiry_slot)
+    }
+
+    /// Sell a leveraged position back to the pool and repay its debt
+    pub fn close_leveraged(
+        ctx: Context<UnwindLeveraged>,
+        min_amount_out: u64,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        instructions::close_leveraged(ctx, min_amount_out, expiry_slot)
+    }
+
+    /// Unwind a leveraged position inside its market's delever window (permissionless)
+    pub fn force_delever(ctx: Context<UnwindLeveraged>) -> Result<()> {
+        instructions::force_delever(ctx)
+    }
+
+    /// Charge a leveraged position's accrued funding (permissionless)
+    pub fn accrue_funding(ctx: Context<AccrueFunding>) -> Result<()> {
+        instructions::accrue_funding(ctx)
+    }
+
+    /// Open the caller's portfolio, updated by every trade that passes it
+    pub fn open_portfolio(ctx: Context<OpenPortfolio>) -> Result<()> {
+        instructions::open_portfolio(ctx)
+    }
+
+    /// Unrealized P&L of a portfolio's open positions at pool spot prices (view)
+    pub fn read_unrealized_pnl<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReadPortfolio<'info>>,
+    ) -> Result<i64> {
+        instructions::read_unrealized_pnl(ctx)
+    }
+
+    /// Set the staking program and the stake thresholds of the swap fee discount tiers
+    pub fn set_fee_tiers(ctx: Context<SetFeeTiers>, tiers: [FeeTier; FEE_TIER_COUNT]) -> Result<()> {
+        instructions::set_fee_tiers(ctx, tiers)
+    }
+
+    /// Set the share of taker fees funding a pool's maker rebates (authority only)
+    pub fn configure_maker_rebates(
+        ctx: Context<ConfigureMakerRebates>,
+        taker_share_bps: u16,
+        maker_rebate_bps: u16,
+    ) -> Result<()> {
+        instructions::configure_maker_rebates(ctx, taker_share_bps, maker_rebate_bps)
+    }
+
+    /// Open a maker's rebate account on a pool
+    pub fn open_maker_rebate(ctx: Context<OpenMakerRebate>) -> Result<()> {
+        instructions::open_maker_rebate(ctx)
+    }
+
+    /// Claim a maker's accrued rebates
+    pub fn claim_maker_rebate(ctx: Context<ClaimMakerRebate>) -> Result<()> {
+        instructions::claim_maker_rebate(ctx)
+    }
+
+    /// Set how long winnings stay redeemable after resolution and where leftovers go (creator only)
+    pub fn set_claim_window(
+        ctx: Context<SetClaimWindow>,
+        claim_window: i64,
+        sweep_to_creator: bool,
+    ) -> Result<()> {
+        instructions::set_claim_window(ctx, claim_window, sweep_to_creator)
+    }
+
+    /// Require participants to hold an attestation of a schema from a credential (creator only)
+    pub fn set_market_attestation(
+        ctx: Context<SetMarketAttestation>,
+        credential: Pubkey,
+        schema: Pubkey,
+    ) -> Result<()> {
+        instructions::set_market_attestation(ctx, credential, schema)
+    }
+
+    /// Sweep a market's unredeemed collateral after its claim deadline (permissionless)
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+        instructions::sweep_unclaimed(ctx)
+    }
+
+    /// Open a bonding-curve sale of a market's outcome tokens ahead of its pool (creator only)
+    pub fn create_launch(
+        ctx: Context<CreateLaunch>,
+        start_price: u64,
+        slope: u64,
+        end_time: i64,
+        fee_bps: u16,
+        seed: u64,
+    ) -> Result<()> {
+        instructions::create_launch(ctx, start_price, slope, end_time, fee_bps, seed)
+    }
+
+    /// Buy outcome tokens along a launch's bonding curve
+    pub fn buy_launch(
+        ctx: Context<BuyLaunch>,
+        outcome: Outcome,
+        amount: u64,
+        max_cost: u64,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        instructions::buy_launch(ctx, outcome, amount, max_cost, expiry_slot)
+    }
+
+    /// Open the pool of an ended launch from its proceeds and unsold tokens (permissionless)
+    pub fn finalize_launch(ctx: Context<FinalizeLaunch>) -> Result<()> {
+        instructions::finalize_launch(ctx)
+    }
+
+    /// Register an approved resolver adapter program under an id (authority only)
+    pub fn register_resolver(
+        ctx: Context<RegisterResolver>,
+        id: u32,
+        program: Pubkey,
+        name: String,
+    ) -> Result<()> {
+        instructions::register_resolver(ctx, id, program, name)
+    }
+
+    /// Approve or revoke a registered resolver adapter (authority only)
+    pub fn set_resolver_approval(ctx: Context<SetResolverApproval>, approved: bool) -> Result<()> {
+        instructions::set_resolver_approval(ctx, approved)
+    }
+
+    /// Resolve a market by CPI into its resolver adapter (permissionless)
+    pub fn resolve_with_adapter<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveWithAdapter<'info>>,
+    ) -> Result<()> {
+        instructions::resolve_with_adapter(ctx)
+    }
+
+    /// Open a commit-reveal bet batch on a pool (permissionless)
+    pub fn open_bet_batch(ctx: Context<OpenBetBatch>, id: u64) -> Result<()> {
+        instructions::open_bet_batch(ctx, id)
+    }
+
+    /// Commit to a hidden bet, escrowing up to `deposit` DAC
+    pub fn commit_bet(
+        ctx: Context<CommitBet>,
+        hash: [u8; 32],
+        deposit: u64,
+        audit_memo: Vec<u8>,
+    ) -> Result<()> {
+        instructions::commit_bet(ctx, hash, deposit, audit_memo)
+    }
+
+    /// Reveal a committed bet during the batch's reveal window
+    pub fn reveal_bet(
+        ctx: Context<RevealBet>,
+        amount: u64,
+        outcome: Outcome,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_bet(ctx, amount, outcome, salt)
+    }
+
+    /// Execute a batch's revealed bets against the pool (permissionless)
+    pub fn settle_bet_batch(ctx: Context<SettleBetBatch>) -> Result<()> {
+        instructions::settle_bet_batch(ctx)
+    }
+
+    /// Collect the outcome tokens bought for a revealed bet
+    pub fn claim_bet(ctx: Context<ClaimBet>) -> Result<()> {
+        instructions::claim_bet(ctx)
+    }
+
+    /// Recover the deposit of an unrevealed or unsettleable bet
+    pub fn refund_bet(ctx: Context<RefundBet>) -> Result<()> {
+        instructions::refund_bet(ctx)
+    }
+
+    /// Register a Groth16 circuit's verifying key, without its `ic` points (authority only)
+    pub fn register_verifying_key(
+        ctx: Context<RegisterVerifyingKey>,
+        circuit_id: u32,
+        alpha_g1: [u8; 64],
+        beta_g2: [u8; 128],
+        gamma_g2: [u8; 128],
+        delta_g2: [u8; 128],
+        public_inputs: u8,
+    ) -> Result<()> {
+        instructions::register_verifying_key(
+            ctx,
+            circuit_id,
+            alpha_g1,
+            beta_g2,
+            gamma_g2,
+            delta_g2,
+            public_inputs,
+        )
+    }
+
+    /// Append `ic` points to an incomplete verifying key (authority only)
+    pub fn append_verifying_key_ic(
+        ctx: Context<AppendVerifyingKeyIc>,
+        points: Vec<[u8; 64]>,
+    ) -> Result<()> {
+        instructions::append_verifying_key_ic(ctx, points)
+    }
+
+    /// Open a market's dark pool matched by an Arcium MPC cluster (authority only)
+    pub fn create_dark_pool(
+        ctx: Context<CreateDarkPool>,
+        arcium_program: Pubkey,
+        callback_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::create_dark_pool(ctx, arcium_program, callback_authority)
+    }
+
+    /// Submit an order encrypted to the MPC cluster, escrowing its DAC budget
+    pub fn submit_dark_order(
+        ctx: Context<SubmitDarkOrder>,
+        encryption_key: [u8; 32],
+        nonce: [u8; 16],
+        ciphertext: [u8; DARK_ORDER_CIPHERTEXT_LEN],
+        budget: u64,
+        audit_memo: Vec<u8>,
+    ) -> Result<()> {
+        instructions::submit_dark_order(ctx, encryption_key, nonce, ciphertext, budget, audit_memo)
+    }
+
+    /// Settle a match between a YES and a NO dark order (MPC cluster callback)
+    pub fn settle_dark_fill(ctx: Context<SettleDarkFill>, size: u64, yes_cost: u64) -> Result<()> {
+        instructions::settle_dark_fill(ctx, size, yes_cost)
+    }
+
+    /// Cancel a dark order, refunding its unspent budget
+    pub fn cancel_dark_order(ctx: Context<CancelDarkOrder>) -> Result<()> {
+        instructions::cancel_dark_order(ctx)
+    }
+
+    /// Auction a block of outcome tokens to sealed bids
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        id: u64,
+        outcome: Outcome,
+        amount: u64,
+        reserve_price: u64,
+        commit_duration: i64,
+        reveal_duration: i64,
+    ) -> Result<()> {
+        instructions::create_auction(
+            ctx,
+            id,
+            outcome,
+            amount,
+            reserve_price,
+            commit_duration,
+            reveal_duration,
+        )
+    }
+
+    /// Commit to a hidden bid, escrowing at least the bid in DAC
+    pub fn commit_bid(ctx: Context<CommitBid>, hash: [u8; 32], deposit: u64) -> Result<()> {
+        instructions::commit_bid(ctx, hash, deposit)
+    }
+
+    /// Reveal a sealed bid during the auction's reveal window
+    pub fn reveal_bid(ctx: Context<RevealBid>, bid: u64, salt: [u8; 32]) -> Result<()> {
+        instructions::reveal_bid(ctx, bid, salt)
+    }
+
+    /// Settle an auction with its highest revealed bid (permissionless)
+    pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+        instructions::settle_auction(ctx)
+    }
+
+    /// Refund or forfeit a bid's deposit after settlement (permissionless)
+    pub fn close_bid(ctx: Context<CloseBid>) -> Result<()> {
+        instructions::close_bid(ctx)
+    }
+
+    /// Redeem winnings into a fresh stealth account derived from an ephemeral key
+    pub fn redeem_to_stealth(
+        ctx: Context<RedeemToStealth>,
+        ephemeral_pubkey: [u8; 32],
+        view_tag: u8,
+        encrypted_hint: Vec<u8>,
+    ) -> Result<()> {
+        instructions::redeem_to_stealth(ctx, ephemeral_pubkey, view_tag, encrypted_hint)
+    }
+
+    /// Add a confidential pool with a threshold decryption committee to a market (creator only)
+    pub fn create_confidential_market(
+        ctx: Context<CreateConfidentialMarket>,
+        committee: Vec<Pubkey>,
+        threshold: u8,
+        encryption_key: [u8; 32],
+        min_bet: u64,
+        max_bet: u64,
+    ) -> Result<()> {
+        instructions::create_confidential_market(
+            ctx,
+            committee,
+            threshold,
+            encryption_key,
+            min_bet,
+            max_bet,
+        )
+    }
+
+    /// Stake DAC with the YES share encrypted to the committee
+    pub fn open_confidential_position(
+        ctx: Context<OpenConfidentialPosition>,
+        deposit: u64,
+        yes_stake: ElGamalCiphertext,
+        encrypted_volume: ElGamalCiphertext,
+        range_proof: Groth16Proof,
+        audit_memo: Vec<u8>,
+    ) -> Result<()> {
+        instructions::open_confidential_position(
+            ctx,
+            deposit,
+            yes_stake,
+            encrypted_volume,
+            range_proof,
+            audit_memo,
+        )
+    }
+
+    /// Post a decryption share and attested plaintext after resolution (committee only)
+    pub fn post_decryption_share(
+        ctx: Context<PostDecryptionShare>,
+        share: [u8; 32],
+        yes_stake: u64,
+    ) -> Result<()> {
+        instructions::post_decryption_share(ctx, share, yes_stake)
+    }
+
+    /// Collect a decrypted position's share of the pot
+    pub fn claim_confidential_payout(ctx: Context<ClaimConfidentialPayout>) -> Result<()> {
+        instructions::claim_confidential_payout(ctx)
+    }
+
+    /// Approve a relayer to submit users' signed orders (authority only)
+    pub fn register_relayer(ctx: Context<RegisterRelayer>, fee_bps: u16) -> Result<()> {
+        instructions::register_relayer(ctx, fee_bps)
+    }
+
+    /// Approve or revoke a relayer (authority only)
+    pub fn set_relayer_approval(ctx: Context<SetRelayerApproval>, approved: bool) -> Result<()> {
+        instructions::set_relayer_approval(ctx, approved)
+    }
+
+    /// Open the caller's relay account for relayed orders
+    pub fn open_relay_account(ctx: Context<OpenRelayAccount>) -> Result<()> {
+        instructions::open_relay_account(ctx)
+    }
+
+    /// Fund the caller's relay account
+    pub fn deposit_relay(ctx: Context<MoveRelayBalance>, amount: u64) -> Result<()> {
+        instructions::deposit_relay(ctx, amount)
+    }
+
+    /// Withdraw from the caller's relay account
+    pub fn withdraw_relay(ctx: Context<MoveRelayBalance>, amount: u64) -> Result<()> {
+        instructions::withdraw_relay(ctx, amount)
+    }
+
+    /// Settle a batch of user-signed orders against the pool (approved relayer only)
+    pub fn settle_relay_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleRelayBatch<'info>>,
+        orders: Vec<SignedOrder>,
+    ) -> Result<()> {
+        instructions::settle_relay_batch(ctx, orders)
+    }
+
+    /// Set the volume reporting key and the authority publishing totals (authority only)
+    pub fn configure_volume_reporting(
+        ctx: Context<ConfigureVolumeReporting>,
+        reporting_authority: Pubkey,
+        reporting_key: [u8; 32],
+    ) -> Result<()> {
+        instructions::configure_volume_reporting(ctx, reporting_authority, reporting_key)
+    }
+
+    /// Publish a closed market's decrypted volume total (reporting authority only)
+    pub fn publish_volume_report(ctx: Context<PublishVolumeReport>, volume: u64) -> Result<()> {
+        instructions::publish_volume_report(ctx, volume)
+    }
+
+    /// Grant an auditor read access to the signer's private activity
+    pub fn register_viewing_key(
+        ctx: Context<RegisterViewingKey>,
+        auditor: Pubkey,
+        encryption_key: [u8; 32],
+    ) -> Result<()> {
+        instructions::register_viewing_key(ctx, auditor, encryption_key)
+    }
+
+    /// Stop disclosing new private activity to the auditor
+    pub fn remove_viewing_key(ctx: Context<RemoveViewingKey>) -> Result<()> {
+        instructions::remove_viewing_key(ctx)
+    }
+
+    /// Resolve a market by sealed outcome and delayed reveal (resolver only)
+    pub fn enable_private_resolution(
+        ctx: Context<EnablePrivateResolution>,
+        encryption_key: [u8; 32],
+        reveal_delay: i64,
+    ) -> Result<()> {
+        instructions::enable_private_resolution(ctx, encryption_key, reveal_delay)
+    }
+
+    /// Post the encrypted outcome and freeze the market (resolver only)
+    pub fn seal_resolution(
+        ctx: Context<SealResolution>,
+        ciphertext: Vec<u8>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::seal_resolution(ctx, ciphertext, commitment)
+    }
+
+    /// Reveal a sealed outcome once its delay has passed, resolving the market
+    pub fn reveal_resolution(
+        ctx: Context<RevealResolution>,
+        outcome: Outcome,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_resolution(ctx, outcome, salt)
+    }
+
+    /// Resolve a sealed market as invalid once nobody revealed it by the deadline
+    pub fn void_sealed_resolution(ctx: Context<RevealResolution>) -> Result<()> {
+        instructions::void_sealed_resolution(ctx)
+    }
+
+    /// Create a market owned by a creator escrow, locking a bond in its vault
+    pub fn create_anonymous_market(
+        ctx: Context<CreateAnonymousMarket>,
+        question: String,
+        end_time: i64,
+        resolver: Pubkey,
+        commitment: [u8; 32],
+        bond: u64,
+    ) -> Result<()> {
+        instructions::create_anonymous_market(ctx, question, end_time, resolver, commitment, bond)
+    }
+
+    /// Withdraw a creator escrow with a proof of the creator secret
+    pub fn claim_creator_escrow(ctx: Context<ClaimCreatorEscrow>, proof: Groth16Proof) -> Result<()> {
+        instructions::claim_creator_escrow(ctx, proof)
+    }
+
+    /// Require splits and buys to be bundled with a DAC wrap, or lift the requirement (authority only)
+    pub fn configure_wrap_bundling(
+        ctx: Context<ConfigureWrapBundling>,
+        wrap_program: Pubkey,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::configure_wrap_bundling(ctx, wrap_program, enabled)
+    }
+
+    /// Set the liquidity mining emission rate (authority only)
+    pub fn configure_gauges(ctx: Context<ConfigureGauges>, emission_rate: u64) -> Result<()> {
+        instructions::configure_gauges(ctx, emission_rate)
+    }
+
+    /// Set a pool gauge's share of the emissions (authority only)
+    pub fn set_gauge_weight(ctx: Context<SetGaugeWeight>, weight: u64) -> Result<()> {
+        instructions::set_gauge_weight(ctx, weight)
+    }
+
+    /// Add DAC to the liquidity mining reward vault
+    pub fn fund_gauges(ctx: Context<FundGauges>, amount: u64) -> Result<()> {
+        instructions::fund_gauges(ctx, amount)
+    }
+
+    /// Deposit LP shares into a pool's gauge to earn rewards
+    pub fn deposit_lp(ctx: Context<DepositLp>, amount: u64) -> Result<()> {
+        instructions::deposit_lp(ctx, amount)
+    }
+
+    /// Withdraw LP shares from a gauge
+    pub fn withdraw_lp(ctx: Context<WithdrawLp>, amount: u64) -> Result<()> {
+        instructions::withdraw_lp(ctx, amount)
+    }
+
+    /// Claim a gauge deposit's liquidity mining rewards
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::claim_rewards(ctx)
+    }
+
+    /// Set the stakers' share of swap fees and their fee vault (authority only)
+    pub fn configure_fee_sharing(
+        ctx: Context<ConfigureFeeSharing>,
+        fee_vault: Pubkey,
+        share_bps: u16,
+    ) -> Result<()> {
+        instructions::configure_fee_sharing(ctx, fee_vault, share_bps)
+    }
+
+    /// Create the next protocol lookup table once the latest one is full
+    pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+        instructions::create_lookup_table(ctx, recent_slot)
+    }
+
+    /// Add the next markets' accounts to a protocol lookup table
+    pub fn extend_lookup_table_with_markets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExtendLookupTableWithMarkets<'info>>,
+    ) -> Result<()> {
+        instructions::extend_lookup_table_with_markets(ctx)
+    }
+
+    /// Add protocol-wide addresses to a protocol lookup table (authority only)
+    pub fn extend_lookup_table(ctx: Context<ExtendLookupTable>, addresses: Vec<Pubkey>) -> Result<()> {
+        instructions::extend_lookup_table(ctx, addresses)
+    }
+}
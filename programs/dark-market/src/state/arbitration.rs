@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+
+use super::{Outcome, BPS_DENOMINATOR};
+
+/// Time after a proposal during which anyone can dispute it (2 hours)
+pub const CHALLENGE_PERIOD: i64 = 2 * 60 * 60;
+/// Fixed council voting window once a proposal is disputed (3 days)
+pub const COUNCIL_VOTING_PERIOD: i64 = 3 * 24 * 60 * 60;
+/// Minimum DAC bond posted by a proposer (100 DAC); challengers match the proposer's bond
+pub const MIN_RESOLUTION_BOND: u64 = 100_000_000;
+/// Share of the council's stake that must vote for the council's decision to count
+pub const COUNCIL_QUORUM_BPS: u64 = 3_000;
+/// Share of a member's stake slashed for voting against the final majority
+pub const COUNCIL_SLASH_BPS: u64 = 1_000;
+
+/// The arbitration council: DAC-staked members voting on disputed resolutions
+#[account]
+pub struct Council {
+    /// Vault holding every member's stake
+    pub vault: Pubkey,
+    /// DAC staked by all members (excluding slashed stake)
+    pub total_stake: u64,
+    /// Slashed DAC held in the vault until swept by the config authority
+    pub slashed: u64,
+    /// Bump for the council PDA
+    pub bump: u8,
+    /// Bump for the council vault PDA
+    pub vault_bump: u8,
+}
+
+impl Council {
+    pub const LEN: usize = 32 + 8 + 8 + 1 + 1; // 50 bytes
+}
+
+/// A council member's stake
+#[account]
+pub struct CouncilMember {
+    /// Wallet of the member
+    pub member: Pubkey,
+    /// DAC staked, which is also the member's vote weight
+    pub stake: u64,
+    /// Last time the stake increased; only stake older than a dispute can vote on it
+    pub staked_at: i64,
+    /// Votes cast but not yet settled; stake is locked while non-zero
+    pub pending_votes: u32,
+    /// Bump for the member PDA
+    pub bump: u8,
+}
+
+impl CouncilMember {
+    pub const LEN: usize = 32 + 8 + 8 + 4 + 1; // 53 bytes
+}
+
+/// Lifecycle of a resolution proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResolutionStatus {
+    /// Bonded proposal inside its challenge period
+    Proposed,
+    /// Challenged and escalated to the council
+    Disputed,
+    /// Market resolved and bonds paid out
+    Finalized,
+}
+
+/// An optimistic resolution of a market
+///
+/// Markets whose resolver is the config PDA are resolved this way: anyone
+/// proposes an outcome with a bond and, unless challenged within the
+/// challenge period, it becomes final. A challenger matching the bond
+/// escalates to the council, whose stake-weighted plurality decides if quorum
+/// is reached; otherwise the proposal stands. The side that was right takes
+/// both bonds.
+#[account]
+pub struct ResolutionProposal {
+    /// The market being resolved
+    pub market: Pubkey,
+    /// Wallet that proposed the outcome
+    pub proposer: Pubkey,
+    /// Wallet that disputed the proposal (default until disputed)
+    pub challenger: Pubkey,
+    /// Proposed outcome
+    pub outcome: Outcome,
+    /// DAC bond posted by each side
+    pub bond: u64,
+    /// When the proposal was made
+    pub proposed_at: i64,
+    /// When the proposal was disputed
+    pub disputed_at: i64,
+    /// Council stake when disputed, the base for quorum
+    pub council_stake: u64,
+    /// Vote weight per outcome (YES, NO, INVALID)
+    pub votes: [u64; 3],
+    /// Current lifecycle status
+    pub status: ResolutionStatus,
+    /// Whether the council reached a decision (only then are minority votes slashed)
+    pub council_decided: bool,
+    /// Final outcome, set once finalized
+    pub final_outcome: Option<Outcome>,
+    /// Bump for the proposal PDA
+    pub bump: u8,
+    /// Bump for the bond vault PDA
+    pub vault_bump: u8,
+}
+
+impl ResolutionProposal {
+    pub const LEN: usize = 32 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 24 + 1 + 1 + 2 + 1 + 1; // 159 bytes
+
+    pub fn challenge_ends(&self) -> i64 {
+        self.proposed_at.saturating_add(CHALLENGE_PERIOD)
+    }
+
+    pub fn voting_ends(&self) -> i64 {
+        self.disputed_at.saturating_add(COUNCIL_VOTING_PERIOD)
+    }
+
+    /// Record `weight` votes for `outcome`
+    pub fn add_votes(&mut self, outcome: Outcome, weight: u64) -> Option<()> {
+        let tally = &mut self.votes[vote_index(outcome)];
+        *tally = tally.checked_add(weight)?;
+        Some(())
+    }
+
+    /// The council's plurality outcome, if quorum was reached without a tie
+    pub fn council_decision(&self) -> Option<Outcome> {
+        let cast: u128 = self.votes.iter().map(|&v| v as u128).sum();
+        let quorum = self.council_stake as u128 * COUNCIL_QUORUM_BPS as u128 / BPS_DENOMINATOR as u128;
+        if cast == 0 || cast < quorum {
+            return None;
+        }
+        let top = *self.votes.iter().max()?;
+        if self.votes.iter().filter(|&&v| v == top).count() > 1 {
+            return None;
+        }
+        [Outcome::Yes, Outcome::No, Outcome::Invalid]
+            .into_iter()
+            .find(|&o| self.votes[vote_index(o)] == top)
+    }
+}
+
+/// A council member's vote on a disputed proposal
+#[account]
+pub struct CouncilVote {
+    /// The disputed proposal
+    pub proposal: Pubkey,
+    /// The member's wallet
+    pub member: Pubkey,
+    /// Outcome voted for
+    pub outcome: Outcome,
+    /// Stake counted for the vote
+    pub weight: u64,
+    /// Bump for the vote PDA
+    pub bump: u8,
+}
+
+impl CouncilVote {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 1; // 74 bytes
+}
+
+/// DAC slashed from `stake` for a minority vote
+pub fn slash_amount(stake: u64) -> u64 {
+    (stake as u128 * COUNCIL_SLASH_BPS as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+fn vote_index(outcome: Outcome) -> usize {
+    match outcome {
+        Outcome::Yes => 0,
+        Outcome::No => 1,
+        Outcome::Invalid => 2,
+    }
+}
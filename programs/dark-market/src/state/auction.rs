@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use super::Outcome;
+
+/// A sealed-bid auction of a block of outcome tokens
+///
+/// Bidders commit to a hidden DAC bid with a deposit of at least the bid,
+/// reveal it once commits close, and the highest revealed bid at or above
+/// the reserve buys the whole block when the auction settles. Deposits of
+/// bids never revealed are forfeited to the seller, so bidding is a
+/// commitment: a bidder cannot watch the others and withhold a losing bid.
+#[account]
+pub struct Auction {
+    /// Wallet selling the tokens
+    pub seller: Pubkey,
+    /// Seller-chosen id (part of the PDA seeds)
+    pub id: u64,
+    /// The market of the tokens
+    pub market: Pubkey,
+    /// The outcome sold (`Yes` or `No`)
+    pub outcome: Outcome,
+    /// Outcome tokens sold, held in `token_vault`
+    pub amount: u64,
+    /// Lowest total DAC bid the seller accepts
+    pub reserve_price: u64,
+    /// Vault holding the tokens for sale
+    pub token_vault: Pubkey,
+    /// Vault holding the bid deposits
+    pub dac_vault: Pubkey,
+    /// Bids are committed until this timestamp
+    pub commit_end: i64,
+    /// Bids are revealed until this timestamp, after which the auction settles
+    pub reveal_end: i64,
+    /// Highest revealed bidder so far (default while none)
+    pub highest_bidder: Pubkey,
+    /// Highest revealed bid so far
+    pub highest_bid: u64,
+    /// Whether the auction has settled
+    pub settled: bool,
+    /// Bump for the auction PDA
+    pub bump: u8,
+}
+
+impl Auction {
+    pub const LEN: usize = 32 + 8 + 32 + 1 + 8 + 8 + 32 + 32 + 8 + 8 + 32 + 8 + 1 + 1; // 211 bytes
+
+    pub fn is_committing(&self, now: i64) -> bool {
+        now < self.commit_end
+    }
+
+    pub fn is_revealing(&self, now: i64) -> bool {
+        now >= self.commit_end && now < self.reveal_end
+    }
+
+    pub fn has_winner(&self) -> bool {
+        self.highest_bidder != Pubkey::default()
+    }
+}
+
+/// One bidder's sealed bid in an auction
+#[account]
+pub struct SealedBid {
+    /// The auction bid on
+    pub auction: Pubkey,
+    /// Wallet bidding
+    pub bidder: Pubkey,
+    /// `bid_hash` of the hidden bid
+    pub hash: [u8; 32],
+    /// DAC escrowed, an upper bound on the bid
+    pub deposit: u64,
+    /// The bid, set on reveal
+    pub bid: u64,
+    /// Whether the bid was revealed
+    pub revealed: bool,
+    /// Bump for the bid PDA
+    pub bump: u8,
+}
+
+impl SealedBid {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 1 + 1; // 114 bytes
+
+    /// Hash committing `bidder` to a bid of `bid` DAC in `auction`
+    pub fn bid_hash(auction: &Pubkey, bidder: &Pubkey, bid: u64, salt: &[u8; 32]) -> [u8; 32] {
+        hashv(&[auction.as_ref(), bidder.as_ref(), &bid.to_le_bytes(), salt]).to_bytes()
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use super::{BPS_DENOMINATOR, PRICE_SCALE};
+
+/// Default maximum move in implied probability, in basis points of 1.0 (20 points)
+pub const DEFAULT_BREAKER_THRESHOLD_BPS: u16 = 2_000;
+/// Default measurement window (~1 minute of slots)
+pub const DEFAULT_BREAKER_WINDOW_SLOTS: u64 = 150;
+/// Default halt length before trading resumes on its own (~5 minutes of slots)
+pub const DEFAULT_BREAKER_COOLOFF_SLOTS: u64 = 750;
+
+/// Per-pool trading halt on extreme price movement
+///
+/// The price at the start of each window is the reference. The trade that
+/// moves the YES price more than `threshold_bps` away from it still executes,
+/// but trips the breaker: further trades are rejected until `cooloff_slots`
+/// pass or the guardian resumes trading.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct CircuitBreaker {
+    /// Maximum move within a window, in basis points of 1.0 (0 disables the breaker)
+    pub threshold_bps: u16,
+    /// Length of the measurement window in slots
+    pub window_slots: u64,
+    /// Length of a halt in slots
+    pub cooloff_slots: u64,
+    /// YES price at the start of the current window
+    pub reference_price: u64,
+    /// Slot the current window started
+    pub reference_slot: u64,
+    /// Trading is halted while the current slot is below this
+    pub halted_until: u64,
+}
+
+impl CircuitBreaker {
+    pub const LEN: usize = 2 + 8 + 8 + 8 + 8 + 8; // 42 bytes
+
+    pub fn new(threshold_bps: u16, window_slots: u64, cooloff_slots: u64) -> Self {
+        Self {
+            threshold_bps,
+            window_slots,
+            cooloff_slots,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_halted(&self, slot: u64) -> bool {
+        slot < self.halted_until
+    }
+
+    /// Record a trade moving the YES price from `price_before` to `price_after`
+    ///
+    /// Returns true if the trade tripped the breaker.
+    pub fn observe(&mut self, slot: u64, price_before: u64, price_after: u64) -> bool {
+        if self.threshold_bps == 0 {
+            return false;
+        }
+        if self.reference_slot == 0 || slot >= self.reference_slot.saturating_add(self.window_slots) {
+            self.reference_price = price_before;
+            self.reference_slot = slot;
+        }
+        let moved = price_after.abs_diff(self.reference_price) as u128 * BPS_DENOMINATOR as u128
+            / PRICE_SCALE as u128;
+        if moved <= self.threshold_bps as u128 {
+            return false;
+        }
+        self.halted_until = slot.saturating_add(self.cooloff_slots);
+        self.reference_price = price_after;
+        self.reference_slot = slot;
+        true
+    }
+
+    /// Lift a halt and start a fresh window at `price`
+    pub fn resume(&mut self, slot: u64, price: u64) {
+        self.halted_until = 0;
+        self.reference_price = price;
+        self.reference_slot = slot;
+    }
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use super::Outcome;
+
+/// Seconds a bet batch accepts commitments
+pub const BET_COMMIT_WINDOW: i64 = 300;
+/// Seconds after the commit window during which bets are revealed
+pub const BET_REVEAL_WINDOW: i64 = 120;
+
+/// A batch of hidden bets executed together against a pool
+///
+/// Bettors escrow DAC with a hash of their bet during the commit window and
+/// open it during the reveal window. Only then does the batch trade: all
+/// revealed DAC of each side buys that side from the pool in one trade, the
+/// smaller side first, and every bettor on a side receives the same price.
+/// Nobody can see a bet's direction or size in time to trade ahead of it.
+#[account]
+pub struct BetBatch {
+    /// The pool the batch trades against
+    pub pool: Pubkey,
+    /// Opener-chosen id (part of the PDA seeds)
+    pub id: u64,
+    /// Commitments are accepted until this timestamp
+    pub commit_end: i64,
+    /// Reveals are accepted until this timestamp, after which the batch settles
+    pub reveal_end: i64,
+    /// DAC vault holding the escrowed deposits
+    pub vault: Pubkey,
+    /// YES bought for the batch, awaiting claims
+    pub yes_holding: Pubkey,
+    /// NO bought for the batch, awaiting claims
+    pub no_holding: Pubkey,
+    /// Revealed DAC betting on YES
+    pub yes_in: u64,
+    /// Revealed DAC betting on NO
+    pub no_in: u64,
+    /// YES bought at settlement
+    pub yes_out: u64,
+    /// NO bought at settlement
+    pub no_out: u64,
+    /// Whether the batch has traded
+    pub settled: bool,
+    /// Bump for the batch PDA
+    pub bump: u8,
+}
+
+impl BetBatch {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1; // 195 bytes
+
+    pub fn is_committing(&self, now: i64) -> bool {
+        now < self.commit_end
+    }
+
+    pub fn is_revealing(&self, now: i64) -> bool {
+        now >= self.commit_end && now < self.reveal_end
+    }
+
+    /// Share of the batch's purchase owed to a bet of `amount` DAC on `outcome`
+    pub fn payout(&self, outcome: Outcome, amount: u64) -> Option<u64> {
+        let (bet_in, bought) = match outcome {
+            Outcome::Yes => (self.yes_in, self.yes_out),
+            Outcome::No => (self.no_in, self.no_out),
+            Outcome::Invalid => return None,
+        };
+        if bet_in == 0 {
+            return Some(0);
+        }
+        u64::try_from(amount as u128 * bought as u128 / bet_in as u128).ok()
+    }
+}
+
+/// One bettor's hidden bet in a batch
+#[account]
+pub struct BetCommitment {
+    /// The batch committed to
+    pub batch: Pubkey,
+    /// Wallet placing the bet
+    pub owner: Pubkey,
+    /// `bet_hash` of the hidden bet
+    pub hash: [u8; 32],
+    /// DAC escrowed, an upper bound on the bet's size
+    pub deposit: u64,
+    /// DAC bet, set on reveal
+    pub amount: u64,
+    /// Side bet on, set on reveal
+    pub outcome: Option<Outcome>,
+    /// Bump for the commitment PDA
+    pub bump: u8,
+}
+
+impl BetCommitment {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 2 + 1; // 115 bytes
+
+    /// Hash committing `owner` to `amount` DAC on `outcome` in `batch`
+    ///
+    /// The salt must be random and kept secret until the reveal, or the
+    /// commitment can be brute-forced from the small space of bets.
+    pub fn bet_hash(
+        batch: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+        outcome: Outcome,
+        salt: &[u8; 32],
+    ) -> [u8; 32] {
+        hashv(&[
+            batch.as_ref(),
+            owner.as_ref(),
+            &amount.to_le_bytes(),
+            &[outcome as u8],
+            salt,
+        ])
+        .to_bytes()
+    }
+}
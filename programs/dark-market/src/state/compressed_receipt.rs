@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use super::{Outcome, RECEIPT_SYMBOL};
+use crate::bubblegum::{MetadataArgs, TokenProgramVersion, TokenStandard};
+
+/// Smallest Bubblegum tree a market can opt into (2^14 receipts)
+pub const MIN_RECEIPT_TREE_DEPTH: u32 = 14;
+
+/// A market's Bubblegum tree of compressed position receipts
+///
+/// For markets with too many participants for a full NFT each: receipts are
+/// leaves of one tree this PDA creates and alone mints into, and the
+/// outcome tokens they wrap are pooled in one vault per side. A leaf's
+/// metadata encodes its side and amount, so unwrapping rebuilds the leaf
+/// from them and Bubblegum's proof check rejects any other amount.
+#[account]
+pub struct ReceiptTree {
+    /// The market the receipts belong to
+    pub market: Pubkey,
+    /// The concurrent Merkle tree holding the receipts
+    pub merkle_tree: Pubkey,
+    /// Receipts minted so far, which is the next receipt's leaf nonce
+    pub minted: u64,
+    /// YES tokens wrapped in outstanding receipts
+    pub yes_amount: u64,
+    /// NO tokens wrapped in outstanding receipts
+    pub no_amount: u64,
+    /// Bump for the receipt tree PDA
+    pub bump: u8,
+}
+
+impl ReceiptTree {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 1; // 89 bytes
+
+    /// The leaf metadata of a receipt wrapping `amount` of `outcome`
+    pub fn metadata(outcome: Outcome, amount: u64, uri: String) -> MetadataArgs {
+        let side = if outcome == Outcome::Yes { "YES" } else { "NO" };
+        MetadataArgs {
+            name: format!("DM {} {}", side, amount),
+            symbol: RECEIPT_SYMBOL.to_string(),
+            uri,
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: false,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungible),
+            collection: None,
+            uses: None,
+            token_program_version: TokenProgramVersion::Original,
+            creators: Vec::new(),
+        }
+    }
+
+    /// Outcome tokens of `outcome` held for outstanding receipts
+    pub fn wrapped(&mut self, outcome: Outcome) -> &mut u64 {
+        if outcome == Outcome::Yes {
+            &mut self.yes_amount
+        } else {
+            &mut self.no_amount
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use solana_curve25519::ristretto::{add_ristretto, PodRistrettoPoint};
+
+use super::{field_element, Outcome};
+
+/// Largest decryption committee of a confidential market
+pub const MAX_COMMITTEE_SIZE: usize = 7;
+/// Public inputs of the bet range circuit
+pub const RANGE_PUBLIC_INPUTS: usize = 4;
+
+/// Twisted ElGamal ciphertext over Ristretto255 (curve25519)
+///
+/// `commitment = v * G + r * H` and `handle = r * P` for the committee's
+/// joint public key `P`, as in SPL confidential transfers.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ElGamalCiphertext {
+    pub commitment: [u8; 32],
+    pub handle: [u8; 32],
+}
+
+impl ElGamalCiphertext {
+    pub const LEN: usize = 32 + 32; // 64 bytes
+
+    /// Ciphertext of the sum of both plaintexts (the all-zero ciphertext encrypts 0)
+    pub fn add(&self, other: &Self) -> Option<Self> {
+        let sum = |a: [u8; 32], b: [u8; 32]| {
+            add_ristretto(&PodRistrettoPoint(a), &PodRistrettoPoint(b)).map(|p| p.0)
+        };
+        Some(Self {
+            commitment: sum(self.commitment, other.commitment)?,
+            handle: sum(self.handle, other.handle)?,
+        })
+    }
+
+    /// Hash of the ciphertext and the key it is encrypted to, as a BN254 public input
+    pub fn public_input(&self, encryption_key: &[u8; 32]) -> [u8; 32] {
+        let mut hash = hashv(&[encryption_key, &self.commitment, &self.handle]).to_bytes();
+        hash[0] = 0;
+        hash
+    }
+}
+
+/// A parimutuel side pool of a market whose positions stay encrypted until resolution
+///
+/// Each position stakes public DAC but hides how much of it backs YES (the
+/// rest backs NO), encrypted to a key split among a committee. Members post
+/// their decryption shares only after the market resolves; once `threshold`
+/// members attest the same plaintext, the position counts towards the side
+/// totals. When every position is decrypted, each side's stakes share the
+/// whole pot pro-rata, so open interest per side is never visible while the
+/// market trades.
+///
+/// Every position carries a Groth16 proof of the `range_circuit` that its
+/// hidden split respects the bet limits: for public inputs
+/// `[ciphertext hash, deposit, min_bet, max_bet]`, the ciphertext encrypts a
+/// YES stake `v <= deposit`, and each side's stake (`v` and `deposit - v`)
+/// is either zero or within `[min_bet, max_bet]`.
+#[account]
+pub struct ConfidentialMarket {
+    /// The market whose resolution settles the pool
+    pub market: Pubkey,
+    /// Committee members holding shares of the decryption key
+    pub committee: Vec<Pubkey>,
+    /// Matching attestations needed to decrypt a position
+    pub threshold: u8,
+    /// Committee's joint ElGamal public key
+    pub encryption_key: [u8; 32],
+    /// DAC vault holding the stakes
+    pub vault: Pubkey,
+    /// Number of positions opened (next position id)
+    pub position_count: u64,
+    /// Number of positions decrypted
+    pub decrypted_count: u64,
+    /// Decrypted DAC staked on YES
+    pub yes_total: u64,
+    /// Decrypted DAC staked on NO
+    pub no_total: u64,
+    /// Smallest stake on a side
+    pub min_bet: u64,
+    /// Largest stake on a side
+    pub max_bet: u64,
+    /// Circuit id of the range proof positions must carry
+    pub range_circuit: u32,
+    /// Bump for the confidential market PDA
+    pub bump: u8,
+}
+
+impl ConfidentialMarket {
+    pub const LEN: usize = 32 + (4 + MAX_COMMITTEE_SIZE * 32) + 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 1; // 378 bytes
+
+    pub fn member_index(&self, member: &Pubkey) -> Option<usize> {
+        self.committee.iter().position(|m| m == member)
+    }
+
+    /// Public inputs of the range proof for a position
+    pub fn range_inputs(&self, deposit: u64, yes_stake: &ElGamalCiphertext) -> [[u8; 32]; RANGE_PUBLIC_INPUTS] {
+        [
+            yes_stake.public_input(&self.encryption_key),
+            field_element(deposit),
+            field_element(self.min_bet),
+            field_element(self.max_bet),
+        ]
+    }
+
+    pub fn fully_decrypted(&self) -> bool {
+        self.decrypted_count == self.position_count
+    }
+
+    /// DAC owed to a position staking `yes_stake` of its `deposit` on YES
+    ///
+    /// Invalid markets, and sides nobody backed, refund the deposit.
+    pub fn payout(&self, outcome: Outcome, deposit: u64, yes_stake: u64) -> Option<u64> {
+        let (stake, side_total) = match outcome {
+            Outcome::Yes => (yes_stake, self.yes_total),
+            Outcome::No => (deposit - yes_stake, self.no_total),
+            Outcome::Invalid => return Some(deposit),
+        };
+        if side_total == 0 {
+            return Some(deposit);
+        }
+        let pot = self.yes_total as u128 + self.no_total as u128;
+        u64::try_from(stake as u128 * pot / side_total as u128).ok()
+    }
+}
+
+/// One stake in a confidential market
+#[account]
+pub struct ConfidentialPosition {
+    /// The confidential market staked in
+    pub confidential_market: Pubkey,
+    /// Wallet owning the stake
+    pub owner: Pubkey,
+    /// Position id (part of the PDA seeds)
+    pub id: u64,
+    /// DAC staked across both sides
+    pub deposit: u64,
+    /// Encrypted DAC staked on YES
+    pub yes_stake: ElGamalCiphertext,
+    /// Decryption share posted by each committee member
+    pub shares: [[u8; 32]; MAX_COMMITTEE_SIZE],
+    /// Plaintext attested by each committee member
+    pub attestations: [u64; MAX_COMMITTEE_SIZE],
+    /// Bitmask of members who attested
+    pub attested: u8,
+    /// Decrypted YES stake, once `threshold` attestations agree
+    pub decrypted: Option<u64>,
+    /// Bump for the position PDA
+    pub bump: u8,
+}
+
+impl ConfidentialPosition {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + ElGamalCiphertext::LEN
+        + MAX_COMMITTEE_SIZE * 32 + MAX_COMMITTEE_SIZE * 8 + 1 + 9 + 1; // 435 bytes
+
+    /// Number of members who attested `value`
+    pub fn attestations_for(&self, value: u64) -> usize {
+        (0..MAX_COMMITTEE_SIZE)
+            .filter(|&i| self.attested & (1 << i) != 0 && self.attestations[i] == value)
+            .count()
+    }
+
+    /// Whether the decrypted stake fits the deposit (a stake above it voids the position)
+    pub fn is_valid(&self) -> bool {
+        self.decrypted.is_some_and(|yes_stake| yes_stake <= self.deposit)
+    }
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// Smallest bond an anonymous market creator locks
+pub const MIN_CREATION_BOND: u64 = 100_000_000;
+/// Public inputs of the creator claim circuit
+pub const CREATOR_CLAIM_PUBLIC_INPUTS: usize = 2;
+
+/// Stand-in creator of an anonymously created market
+///
+/// The escrow PDA is recorded as the market's creator, so the creation bond
+/// and anything paid to the creator collect in its vault rather than at a
+/// wallet. Withdrawing takes a Groth16 proof of the `claim_circuit` that the
+/// claimant knows the secret behind `commitment`: for public inputs
+/// `[commitment, recipient hash]`, `commitment = Poseidon(secret)`. Binding
+/// the recipient stops a proof seen in flight from being redirected.
+#[account]
+pub struct CreatorEscrow {
+    /// The market created anonymously
+    pub market: Pubkey,
+    /// Commitment to the creator's secret, as a BN254 field element
+    pub commitment: [u8; 32],
+    /// Circuit id of the claim proof
+    pub claim_circuit: u32,
+    /// DAC vault collecting the bond and creator proceeds
+    pub vault: Pubkey,
+    /// Creation bond locked until the market settles
+    pub bond: u64,
+    /// Bump for the escrow PDA
+    pub bump: u8,
+}
+
+impl CreatorEscrow {
+    pub const LEN: usize = 32 + 32 + 4 + 32 + 8 + 1; // 109 bytes
+
+    /// Public inputs of a claim paying out to `recipient`
+    pub fn claim_inputs(&self, recipient: &Pubkey) -> [[u8; 32]; CREATOR_CLAIM_PUBLIC_INPUTS] {
+        let mut recipient_hash = hash(recipient.as_ref()).to_bytes();
+        recipient_hash[0] = 0;
+        [self.commitment, recipient_hash]
+    }
+}
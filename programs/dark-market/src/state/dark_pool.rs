@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+/// Size of an encrypted dark order: side, size and limit price, one 32-byte ciphertext each
+pub const DARK_ORDER_CIPHERTEXT_LEN: usize = 3 * 32;
+
+/// A market's dark pool, matched inside an Arcium MPC cluster
+///
+/// Every dark order is a DAC-funded buy of YES or NO whose side, size and
+/// limit price are encrypted to the cluster. The cluster matches YES buyers
+/// against NO buyers whose limits sum to at least one DAC and reports each
+/// match through `settle_dark_fill`, which turns the two escrowed payments
+/// into a complete set. Only matched quantities ever appear on-chain; an
+/// order's escrowed budget bounds its size but reveals neither side nor limit.
+#[account]
+pub struct DarkPool {
+    /// The market traded
+    pub market: Pubkey,
+    /// Arcium program that delivers computation outputs
+    pub arcium_program: Pubkey,
+    /// Signer of the MXE's matching callback (see `DarkFillCallback`)
+    pub callback_authority: Pubkey,
+    /// DAC vault escrowing order budgets
+    pub vault: Pubkey,
+    /// Number of orders submitted (next order id)
+    pub order_count: u64,
+    /// Bump for the dark pool PDA
+    pub bump: u8,
+}
+
+impl DarkPool {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 1; // 137 bytes
+}
+
+/// An encrypted order resting in a dark pool
+#[account]
+pub struct DarkOrder {
+    /// The dark pool the order rests in
+    pub dark_pool: Pubkey,
+    /// Wallet that placed the order
+    pub owner: Pubkey,
+    /// Order id within the dark pool (part of the PDA seeds)
+    pub id: u64,
+    /// Owner's x25519 public key, shared with the cluster to decrypt the order
+    pub encryption_key: [u8; 32],
+    /// Encryption nonce
+    pub nonce: [u8; 16],
+    /// Encrypted side, size and limit price
+    pub ciphertext: [u8; DARK_ORDER_CIPHERTEXT_LEN],
+    /// DAC escrowed for the order
+    pub budget: u64,
+    /// DAC paid for fills so far
+    pub spent: u64,
+    /// Bump for the order PDA
+    pub bump: u8,
+}
+
+impl DarkOrder {
+    pub const LEN: usize = 32 + 32 + 8 + 32 + 16 + DARK_ORDER_CIPHERTEXT_LEN + 8 + 8 + 1; // 233 bytes
+
+    /// Budget not yet spent on fills
+    pub fn remaining(&self) -> u64 {
+        self.budget - self.spent
+    }
+}
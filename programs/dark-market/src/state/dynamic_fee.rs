@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use super::{PoolObservations, BPS_DENOMINATOR, PRICE_SCALE};
+
+/// Highest surcharge multiplier a pool can set (10x the volatility)
+pub const MAX_FEE_MULTIPLIER_BPS: u32 = 100_000;
+
+/// Per-pool swap fee surcharge scaling with realized volatility
+///
+/// Before each trade the surcharge is recomputed from the pool's observations:
+/// `multiplier_bps` of the mean move in implied probability between
+/// consecutive observation intervals over the last `window` seconds. Toxic
+/// flow near resolution moves the price in jumps, so LPs are paid more to
+/// absorb it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct DynamicFee {
+    /// Lookback for realized volatility in seconds (0 disables the surcharge)
+    pub window: i64,
+    /// Surcharge per unit of volatility, in basis points
+    pub multiplier_bps: u32,
+    /// Cap on the total swap fee
+    pub max_fee_bps: u16,
+    /// Surcharge in effect since the last refresh
+    pub surcharge_bps: u16,
+}
+
+impl DynamicFee {
+    pub const LEN: usize = 8 + 4 + 2 + 2; // 16 bytes
+
+    pub fn is_enabled(&self) -> bool {
+        self.window > 0
+    }
+
+    /// Recompute the surcharge from `observations` at `now`
+    pub fn refresh(&mut self, observations: &PoolObservations, now: i64, current_price: u64) {
+        if !self.is_enabled() {
+            self.surcharge_bps = 0;
+            return;
+        }
+        let volatility_bps = observations.volatility(now, self.window, current_price) as u128
+            * BPS_DENOMINATOR as u128
+            / PRICE_SCALE as u128;
+        let surcharge = volatility_bps * self.multiplier_bps as u128 / BPS_DENOMINATOR as u128;
+        self.surcharge_bps = surcharge.min(u16::MAX as u128) as u16;
+    }
+
+    /// Swap fee given the pool's `base_fee_bps`
+    pub fn fee_bps(&self, base_fee_bps: u16) -> u16 {
+        if !self.is_enabled() {
+            return base_fee_bps;
+        }
+        base_fee_bps
+            .saturating_add(self.surcharge_bps)
+            .min(self.max_fee_bps)
+            .max(base_fee_bps)
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use super::BPS_DENOMINATOR;
+
+/// Share of swap fees paid to DAC stakers
+///
+/// The share is taken from what is left of each swap fee after the maker
+/// rebate, and paid into the fee vault of the staking program, which
+/// distributes it to stakers by epoch.
+#[account]
+pub struct FeeSharing {
+    /// DAC vault of the stakers' fee distributor
+    pub fee_vault: Pubkey,
+    /// Share of each swap fee paid to stakers, in basis points
+    pub share_bps: u16,
+    /// Bump for the fee sharing PDA
+    pub bump: u8,
+}
+
+impl FeeSharing {
+    pub const LEN: usize = 32 + 2 + 1; // 35 bytes
+
+    /// Part of `fee` paid to stakers
+    pub fn share(&self, fee: u64) -> u64 {
+        (fee as u128 * self.share_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use super::BPS_DENOMINATOR;
+
+/// Number of fee discount tiers
+pub const FEE_TIER_COUNT: usize = 3;
+
+/// Stake needed for a swap fee discount
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct FeeTier {
+    /// Minimum staked DAC to qualify
+    pub min_stake: u64,
+    /// Share of the pool fee waived, in basis points
+    pub discount_bps: u16,
+}
+
+impl FeeTier {
+    pub const LEN: usize = 8 + 2; // 10 bytes
+}
+
+/// Swap fee discounts for traders staking DAC
+#[account]
+pub struct FeeTiers {
+    /// Program owning the stake accounts read by trades
+    pub staking_program: Pubkey,
+    /// Tiers in ascending order of `min_stake` (a zero discount disables a tier)
+    pub tiers: [FeeTier; FEE_TIER_COUNT],
+    /// Bump for the fee tiers PDA
+    pub bump: u8,
+}
+
+impl FeeTiers {
+    pub const LEN: usize = 32 + FEE_TIER_COUNT * FeeTier::LEN + 1; // 63 bytes
+
+    /// Tiers are ascending in both stake and discount, and discounts are at most 100%
+    pub fn is_valid(tiers: &[FeeTier; FEE_TIER_COUNT]) -> bool {
+        tiers.iter().all(|t| t.discount_bps as u64 <= BPS_DENOMINATOR)
+            && tiers.windows(2).all(|w| {
+                w[0].min_stake < w[1].min_stake && w[0].discount_bps <= w[1].discount_bps
+            })
+    }
+
+    /// Discount of the highest tier `stake` qualifies for
+    pub fn discount_bps(&self, stake: u64) -> u16 {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| stake >= tier.min_stake)
+            .map_or(0, |tier| tier.discount_bps)
+    }
+}
+
+/// Read-only view of a stake account owned by the staking program
+///
+/// Trades read the stake directly from account data instead of making a CPI,
+/// so the staking program must keep this prefix stable: the Anchor
+/// discriminator of `StakeAccount`, then the staker, the staked amount, the
+/// staking pool and the time the stake unlocks.
+pub struct StakeView {
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub lock_end: i64,
+}
+
+impl StakeView {
+    const DATA_LEN: usize = 8 + 32 + 8 + 32 + 8;
+
+    /// Parse `info` as a `StakeAccount` of `staking_program`
+    ///
+    /// Nothing is read unless the account is owned by the staking program and
+    /// starts with the `StakeAccount` discriminator.
+    pub fn read(info: &AccountInfo, staking_program: &Pubkey) -> Option<Self> {
+        if info.owner != staking_program {
+            return None;
+        }
+        let data = info.try_borrow_data().ok()?;
+        if data.len() < Self::DATA_LEN
+            || data[..8] != hash(b"account:StakeAccount").to_bytes()[..8]
+        {
+            return None;
+        }
+        Some(Self {
+            staker: Pubkey::try_from(&data[8..40]).ok()?,
+            amount: u64::from_le_bytes(data[40..48].try_into().ok()?),
+            lock_end: i64::from_le_bytes(data[80..88].try_into().ok()?),
+        })
+    }
+
+    /// Whether the stake is still locked at `now`, so it cannot be staked and unstaked around a trade
+    pub fn is_locked(&self, now: i64) -> bool {
+        self.lock_end > now
+    }
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+/// Fixed-point scale of the gauge reward indexes
+pub const GAUGE_PRECISION: u128 = 1_000_000_000_000;
+
+/// Liquidity mining emissions, split among pool gauges by weight
+///
+/// `reward_per_weight` accumulates the DAC emitted per unit of gauge weight,
+/// so a gauge's share since its last checkpoint is its weight times the
+/// growth. Weights are set by the config authority (governance).
+#[account]
+pub struct GaugeController {
+    /// The DAC mint rewards are paid in
+    pub dac_mint: Pubkey,
+    /// DAC vault paying the rewards
+    pub reward_vault: Pubkey,
+    /// DAC emitted per second across all gauges
+    pub emission_rate: u64,
+    /// Sum of all gauge weights
+    pub total_weight: u64,
+    /// Rewards per unit of weight since the start, scaled by `GAUGE_PRECISION`
+    pub reward_per_weight: u128,
+    /// Last time `reward_per_weight` was brought up to date
+    pub last_update: i64,
+    /// Bump for the controller PDA
+    pub bump: u8,
+}
+
+impl GaugeController {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 16 + 8 + 1; // 105 bytes
+
+    /// Accrue emissions up to `now`
+    pub fn update(&mut self, now: i64) -> Option<()> {
+        if now > self.last_update && self.total_weight > 0 {
+            let emitted = (now - self.last_update) as u128 * self.emission_rate as u128;
+            self.reward_per_weight = self.reward_per_weight
+                .checked_add(emitted.checked_mul(GAUGE_PRECISION)? / self.total_weight as u128)?;
+        }
+        self.last_update = self.last_update.max(now);
+        Some(())
+    }
+}
+
+/// Rewards of one pool's LPs, streamed to the LP tokens deposited in it
+#[account]
+pub struct Gauge {
+    /// The pool whose LPs earn the rewards
+    pub pool: Pubkey,
+    /// Vault holding the deposited LP tokens
+    pub lp_vault: Pubkey,
+    /// Share of the controller's emissions
+    pub weight: u64,
+    /// Controller `reward_per_weight` at the last checkpoint
+    pub weight_index: u128,
+    /// Rewards per deposited LP share since the start, scaled by `GAUGE_PRECISION`
+    pub reward_per_share: u128,
+    /// LP shares deposited
+    pub total_staked: u64,
+    /// Bump for the gauge PDA
+    pub bump: u8,
+}
+
+impl Gauge {
+    pub const LEN: usize = 32 + 32 + 8 + 16 + 16 + 8 + 1; // 113 bytes
+
+    /// Distribute the gauge's emissions since the last checkpoint over its deposits
+    ///
+    /// Emissions while nothing is deposited stay in the reward vault.
+    pub fn update(&mut self, reward_per_weight: u128) -> Option<()> {
+        let emitted = (reward_per_weight - self.weight_index) * self.weight as u128 / GAUGE_PRECISION;
+        if self.total_staked > 0 {
+            self.reward_per_share = self.reward_per_share
+                .checked_add(emitted.checked_mul(GAUGE_PRECISION)? / self.total_staked as u128)?;
+        }
+        self.weight_index = reward_per_weight;
+        Some(())
+    }
+}
+
+/// One LP's deposit in a gauge
+#[account]
+pub struct GaugeStake {
+    /// The gauge deposited in
+    pub gauge: Pubkey,
+    /// Wallet owning the deposit
+    pub owner: Pubkey,
+    /// LP shares deposited
+    pub amount: u64,
+    /// Gauge `reward_per_share` the deposit last settled at
+    pub reward_debt: u128,
+    /// Settled rewards not yet claimed
+    pub pending_rewards: u64,
+    /// Bump for the gauge stake PDA
+    pub bump: u8,
+}
+
+impl GaugeStake {
+    pub const LEN: usize = 32 + 32 + 8 + 16 + 8 + 1; // 97 bytes
+
+    /// Move rewards earned since the last settlement into `pending_rewards`
+    pub fn settle(&mut self, reward_per_share: u128) -> Option<()> {
+        let earned = (reward_per_share - self.reward_debt) * self.amount as u128 / GAUGE_PRECISION;
+        self.pending_rewards = self.pending_rewards.checked_add(u64::try_from(earned).ok()?)?;
+        self.reward_debt = reward_per_share;
+        Some(())
+    }
+}
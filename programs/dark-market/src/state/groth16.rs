@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::alt_bn128::prelude::{
+    alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing,
+};
+
+/// Most public inputs a registered circuit can take
+pub const MAX_PUBLIC_INPUTS: usize = 8;
+
+/// Order of the BN254 scalar field, big-endian (public inputs must be below it)
+const SCALAR_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+/// Order of the BN254 base field, big-endian (used to negate G1 points)
+const BASE_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// A Groth16 proof over BN254
+///
+/// Points use the big-endian encoding of the `alt_bn128` syscalls: G1 as
+/// `x || y`, G2 as `x_c1 || x_c0 || y_c1 || y_c0`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Groth16Proof {
+    pub a: [u8; 64],
+    pub b: [u8; 128],
+    pub c: [u8; 64],
+}
+
+/// The verifying key of one Groth16 circuit, registered by the config authority
+///
+/// Keys are too large for one transaction, so the `ic` points are appended
+/// after registration; proofs are only accepted once all of them are present.
+/// A complete key can never change, so statements proven against a circuit id
+/// keep their meaning. Instructions that rely on a proof take it as an argument
+/// and call [`VerifyingKey::verify`] themselves; nothing records a past verification.
+#[account]
+pub struct VerifyingKey {
+    /// Circuit id (part of the PDA seeds)
+    pub circuit_id: u32,
+    pub alpha_g1: [u8; 64],
+    pub beta_g2: [u8; 128],
+    pub gamma_g2: [u8; 128],
+    pub delta_g2: [u8; 128],
+    /// Number of `ic` points of the complete key (one more than the public inputs)
+    pub ic_len: u8,
+    /// Points weighting the public inputs, `ic[0]` being the constant term
+    pub ic: Vec<[u8; 64]>,
+    /// Bump for the verifying key PDA
+    pub bump: u8,
+}
+
+impl VerifyingKey {
+    pub const LEN: usize = 4 + 64 + 128 + 128 + 128 + 1 + (4 + (MAX_PUBLIC_INPUTS + 1) * 64) + 1; // 1034 bytes
+
+    pub fn is_complete(&self) -> bool {
+        self.ic.len() == self.ic_len as usize
+    }
+
+    /// Whether `proof` proves the circuit's statement for `public_inputs`
+    ///
+    /// Each public input is a big-endian scalar below the field order.
+    /// Malformed points fail verification rather than erroring.
+    pub fn verify(&self, proof: &Groth16Proof, public_inputs: &[[u8; 32]]) -> bool {
+        self.is_complete()
+            && public_inputs.len() + 1 == self.ic.len()
+            && public_inputs.iter().all(|input| *input < SCALAR_FIELD_MODULUS)
+            && self.check_pairing(proof, public_inputs).unwrap_or(false)
+    }
+
+    /// e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1
+    fn check_pairing(&self, proof: &Groth16Proof, public_inputs: &[[u8; 32]]) -> Option<bool> {
+        let mut vk_x = self.ic[0];
+        for (input, point) in public_inputs.iter().zip(&self.ic[1..]) {
+            let term = alt_bn128_multiplication(&[point.as_slice(), input.as_slice()].concat()).ok()?;
+            let sum = alt_bn128_addition(&[vk_x.as_slice(), term.as_slice()].concat()).ok()?;
+            vk_x = sum.try_into().ok()?;
+        }
+
+        let pairs = [
+            (negate_g1(&proof.a), &proof.b),
+            (self.alpha_g1, &self.beta_g2),
+            (vk_x, &self.gamma_g2),
+            (proof.c, &self.delta_g2),
+        ];
+        let input: Vec<u8> = pairs
+            .iter()
+            .flat_map(|(g1, g2)| g1.iter().chain(g2.iter()).copied())
+            .collect();
+        let result = alt_bn128_pairing(&input).ok()?;
+        Some(result.len() == 32 && result[..31].iter().all(|b| *b == 0) && result[31] == 1)
+    }
+}
+
+/// Negate a G1 point by replacing y with q - y (the point at infinity is its own negation)
+fn negate_g1(point: &[u8; 64]) -> [u8; 64] {
+    let mut negated = *point;
+    if point[32..].iter().all(|b| *b == 0) {
+        return negated;
+    }
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = BASE_FIELD_MODULUS[i] as i16 - point[32 + i] as i16 - borrow;
+        negated[32 + i] = diff.rem_euclid(256) as u8;
+        borrow = (diff < 0) as i16;
+    }
+    negated
+}
+
+/// `value` as a big-endian public input
+pub fn field_element(value: u64) -> [u8; 32] {
+    let mut element = [0u8; 32];
+    element[24..].copy_from_slice(&value.to_be_bytes());
+    element
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use super::{Outcome, PRICE_SCALE};
+
+/// Bonding-curve offering of a market's outcome tokens before its pool opens
+///
+/// Each side sells along its own linear curve: the `x`-th token costs
+/// `start_price + slope * x / PRICE_SCALE` (capped at one DAC), so early
+/// buyers get the best prices. Tokens are minted as complete sets; the side
+/// not sold stays in inventory. A buyer pays less than the full DAC backing a
+/// set, and the creator's seed covers the difference: at most
+/// `subsidy_cap()` per side, since past the cap tokens cost one DAC.
+///
+/// When the launch ends the leftover DAC and the unsold inventory become the
+/// pool's reserves, so the pool opens at the price the curves reached.
+#[account]
+pub struct Launch {
+    /// The market being launched
+    pub market: Pubkey,
+    /// Wallet that seeded the launch, receiving the pool's LP shares
+    pub creator: Pubkey,
+    /// DAC vault holding the seed and sale proceeds
+    pub vault: Pubkey,
+    /// Inventory of minted YES not yet sold
+    pub yes_inventory: Pubkey,
+    /// Inventory of minted NO not yet sold
+    pub no_inventory: Pubkey,
+    /// Price of the first token of each side, scaled by `PRICE_SCALE`
+    pub start_price: u64,
+    /// Price increase per `PRICE_SCALE` tokens sold, scaled by `PRICE_SCALE`
+    pub slope: u64,
+    /// YES tokens sold
+    pub yes_sold: u64,
+    /// NO tokens sold
+    pub no_sold: u64,
+    /// Complete sets minted into the inventories
+    pub minted: u64,
+    /// Unix timestamp at which sales stop and the pool can open
+    pub end_time: i64,
+    /// Swap fee of the pool opened by the launch
+    pub fee_bps: u16,
+    /// Bump for the launch PDA
+    pub bump: u8,
+}
+
+impl Launch {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 2 + 1; // 211 bytes
+
+    /// Tokens sold of one side after which the curve reaches one DAC
+    fn cap(&self) -> u128 {
+        (PRICE_SCALE - self.start_price) as u128 * PRICE_SCALE as u128 / self.slope as u128
+    }
+
+    /// Most DAC the seed can have to contribute to one side's sales
+    pub fn subsidy_cap(&self) -> u64 {
+        let discount = (PRICE_SCALE - self.start_price) as u128;
+        (discount * self.cap()).div_ceil(2 * PRICE_SCALE as u128) as u64
+    }
+
+    /// Area under the curve from 0 to `x`, in DAC scaled by `PRICE_SCALE`
+    fn area(&self, x: u128) -> Option<u128> {
+        let cap = self.cap();
+        let linear = x.min(cap);
+        let area = (self.start_price as u128).checked_mul(linear)?
+            .checked_add(self.slope as u128 * linear.checked_mul(linear)? / (2 * PRICE_SCALE as u128))?;
+        area.checked_add((x - linear).checked_mul(PRICE_SCALE as u128)?)
+    }
+
+    pub fn sold(&self, outcome: Outcome) -> u64 {
+        match outcome {
+            Outcome::No => self.no_sold,
+            _ => self.yes_sold,
+        }
+    }
+
+    /// DAC paid for the next `amount` tokens of `outcome`, rounded up
+    pub fn quote(&self, outcome: Outcome, amount: u64) -> Option<u64> {
+        let sold = self.sold(outcome) as u128;
+        let cost = self.area(sold.checked_add(amount as u128)?)? - self.area(sold)?;
+        u64::try_from(cost.div_ceil(PRICE_SCALE as u128)).ok()
+    }
+
+    /// Record a sale of `amount` tokens of `outcome`, returning the complete sets to mint
+    pub fn apply_sale(&mut self, outcome: Outcome, amount: u64) -> Option<u64> {
+        match outcome {
+            Outcome::No => self.no_sold = self.no_sold.checked_add(amount)?,
+            _ => self.yes_sold = self.yes_sold.checked_add(amount)?,
+        }
+        let needed = self.yes_sold.max(self.no_sold);
+        let new_sets = needed.saturating_sub(self.minted);
+        self.minted = needed.max(self.minted);
+        Some(new_sets)
+    }
+}
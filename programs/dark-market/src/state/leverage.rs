@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use super::{Outcome, BPS_DENOMINATOR};
+
+/// Highest leverage any market can allow (10x)
+pub const MAX_LEVERAGE_BPS: u32 = 100_000;
+/// Highest annual funding rate any market can charge (100%)
+pub const MAX_FUNDING_RATE_BPS: u16 = 10_000;
+/// Seconds in a year, for annualized funding rates
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Leverage settings of one market, set by the config authority
+///
+/// Leveraged positions must be unwound `delever_window` seconds before the
+/// market ends: close to resolution the outcome tokens jump to 0 or 1, so
+/// collateral sized for continuous price moves no longer covers the debt.
+#[account]
+pub struct LeverageParams {
+    /// The market these settings apply to
+    pub market: Pubkey,
+    /// Highest notional-to-margin ratio, in basis points (0 disables leverage)
+    pub max_leverage_bps: u32,
+    /// Annual funding rate charged on borrowed DAC, in basis points
+    pub funding_rate_bps: u16,
+    /// Seconds before the end time from which positions can be force-deleveraged
+    pub delever_window: i64,
+    /// Bump for the params PDA
+    pub bump: u8,
+}
+
+impl LeverageParams {
+    pub const LEN: usize = 32 + 4 + 2 + 8 + 1; // 47 bytes
+
+    /// Time from which new leverage is refused and positions can be force-deleveraged
+    pub fn delever_start(&self, end_time: i64) -> i64 {
+        end_time.saturating_sub(self.delever_window)
+    }
+}
+
+/// A leveraged outcome-token position held through a margin account
+///
+/// The tokens sit in the margin account's custody and the borrowed DAC is
+/// part of its debt, so the position counts towards the account's health and
+/// can be liquidated like any other margin position. A short is a long on
+/// the opposite outcome.
+#[account]
+pub struct LeveragedPosition {
+    /// The margin account funding the position
+    pub margin_account: Pubkey,
+    /// The market traded
+    pub market: Pubkey,
+    /// The outcome held (`Yes` or `No`)
+    pub outcome: Outcome,
+    /// Outcome tokens bought with leverage
+    pub size: u64,
+    /// DAC borrowed from the margin vault to open the position
+    pub borrowed: u64,
+    /// Funding charged over the position's lifetime
+    pub funding_paid: u64,
+    /// Last time funding was charged
+    pub last_funding: i64,
+    /// Bump for the position PDA
+    pub bump: u8,
+}
+
+impl LeveragedPosition {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 8 + 8 + 8 + 1; // 98 bytes
+
+    /// Funding owed on `borrowed` since the last charge
+    pub fn accrued_funding(&self, now: i64, funding_rate_bps: u16) -> u64 {
+        let elapsed = (now - self.last_funding).max(0) as u128;
+        (self.borrowed as u128 * funding_rate_bps as u128 * elapsed
+            / (BPS_DENOMINATOR as u128 * SECONDS_PER_YEAR as u128)) as u64
+    }
+}
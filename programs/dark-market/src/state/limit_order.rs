@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use super::{Outcome, PRICE_SCALE};
+
+/// Direction of a resting limit order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OrderSide {
+    /// Locks DAC, buys the outcome at or below the limit price
+    Buy,
+    /// Locks outcome tokens, sells them at or above the limit price
+    Sell,
+}
+
+/// A limit order resting next to a market's pool
+///
+/// The locked asset sits in the order's vault until a cranker fills it
+/// against the pool (once the pool crosses the limit) or the owner cancels.
+#[account]
+pub struct LimitOrder {
+    /// Wallet that placed the order
+    pub owner: Pubkey,
+    /// The pool the order fills against
+    pub pool: Pubkey,
+    /// Owner-chosen nonce (part of the PDA seeds)
+    pub nonce: u64,
+    /// Buy or sell
+    pub side: OrderSide,
+    /// The outcome being traded (`Yes` or `No`)
+    pub outcome: Outcome,
+    /// Amount locked: DAC for buys (excluding the tip), outcome tokens for sells
+    pub amount: u64,
+    /// Limit price of the outcome, scaled by `PRICE_SCALE`
+    pub limit_price: u64,
+    /// DAC paid to whoever fills the order
+    pub tip: u64,
+    /// Bump for the order PDA
+    pub bump: u8,
+    /// Bump for the order vault PDA
+    pub vault_bump: u8,
+}
+
+impl LimitOrder {
+    pub const LEN: usize = 32 + 32 + 8 + 1 + 1 + 8 + 8 + 8 + 1 + 1; // 100 bytes
+
+    /// Minimum pool output that satisfies the limit price
+    ///
+    /// Buys need at least `amount / limit_price` outcome tokens; sells need at
+    /// least `amount * limit_price` DAC before the tip is taken.
+    pub fn min_amount_out(&self) -> Option<u64> {
+        let amount = self.amount as u128;
+        let price = self.limit_price as u128;
+        let scale = PRICE_SCALE as u128;
+        let out = match self.side {
+            OrderSide::Buy => (amount * scale).div_ceil(price),
+            OrderSide::Sell => (amount * price).div_ceil(scale) + self.tip as u128,
+        };
+        u64::try_from(out).ok()
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+/// Most addresses an address lookup table can hold
+pub const LOOKUP_TABLE_CAPACITY: usize = 256;
+/// Addresses each market adds to a lookup table: the market, its outcome
+/// mints, its escrow, its pool and the pool's reserve vaults
+pub const MARKET_LOOKUP_ADDRESSES: usize = 7;
+
+/// The protocol's address lookup tables
+///
+/// Markets are added in id order, so each one is in exactly one table and
+/// `next_market` tells keepers where to continue.
+#[account]
+pub struct LookupTableRegistry {
+    /// Number of tables created (next table index)
+    pub table_count: u64,
+    /// Id of the next market to add
+    pub next_market: u64,
+    /// Bump for the registry PDA
+    pub bump: u8,
+}
+
+impl LookupTableRegistry {
+    pub const LEN: usize = 8 + 8 + 1; // 17 bytes
+}
+
+/// One protocol-owned address lookup table
+///
+/// The table's authority is the program's lookup table authority PDA, so
+/// addresses can only be added through this program and the table is never
+/// frozen or closed from outside.
+#[account]
+pub struct ProtocolLookupTable {
+    /// Sequential table index (part of the PDA seeds)
+    pub index: u64,
+    /// The address lookup table account
+    pub table: Pubkey,
+    /// Addresses added so far
+    pub len: u16,
+    /// Bump for the table record PDA
+    pub bump: u8,
+}
+
+impl ProtocolLookupTable {
+    pub const LEN: usize = 8 + 32 + 2 + 1; // 43 bytes
+
+    /// Whether `count` more addresses fit
+    pub fn has_room(&self, count: usize) -> bool {
+        self.len as usize + count <= LOOKUP_TABLE_CAPACITY
+    }
+
+    /// Whether another market no longer fits
+    pub fn is_full(&self) -> bool {
+        !self.has_room(MARKET_LOOKUP_ADDRESSES)
+    }
+}
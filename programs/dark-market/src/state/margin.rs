@@ -0,0 +1,242 @@
+use anchor_lang::prelude::*;
+
+use super::{Outcome, BPS_DENOMINATOR, PRICE_SCALE};
+
+/// Maximum number of markets a margin account can hold positions in
+pub const MAX_MARGIN_POSITIONS: usize = 5;
+/// Maximum number of market links a margin account can attach
+pub const MAX_MARGIN_LINKS: usize = 4;
+/// Share of an account's debt a single liquidation can repay
+pub const LIQUIDATION_CLOSE_FACTOR_BPS: u64 = 5_000;
+/// Penalty charged on the debt repaid by a liquidation
+pub const LIQUIDATION_PENALTY_BPS: u64 = 500;
+/// Share of the penalty paid to the liquidator; the rest goes to the insurance fund
+pub const LIQUIDATOR_SHARE_BPS: u64 = 6_000;
+/// TWAP window used to price seized outcome tokens (10 minutes)
+pub const LIQUIDATION_TWAP_WINDOW: i64 = 10 * 60;
+
+/// The terms of one liquidation
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Liquidation {
+    /// DAC the liquidator repays on the account's behalf
+    pub repaid: u64,
+    /// Outcome tokens handed to the liquidator
+    pub seized: u64,
+    /// DAC charged to the account for the insurance fund
+    pub insurance_fee: u64,
+}
+
+/// How the outcomes of two linked markets are correlated
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LinkKind {
+    /// `market_a` resolving YES implies `market_b` resolves YES
+    Implies,
+    /// `market_a` and `market_b` cannot both resolve YES
+    Exclusive,
+}
+
+/// A correlation between two markets attested by the config authority
+///
+/// Links only rule out combinations of YES/NO outcomes; an INVALID outcome is
+/// always considered possible.
+#[account]
+pub struct MarketLink {
+    pub market_a: Pubkey,
+    pub market_b: Pubkey,
+    pub kind: LinkKind,
+    /// Bump for the link PDA
+    pub bump: u8,
+}
+
+impl MarketLink {
+    pub const LEN: usize = 32 + 32 + 1 + 1; // 66 bytes
+}
+
+/// A market link copied into a margin account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LinkRef {
+    pub market_a: Pubkey,
+    pub market_b: Pubkey,
+    pub kind: LinkKind,
+}
+
+impl LinkRef {
+    pub const LEN: usize = 32 + 32 + 1; // 65 bytes
+
+    /// Whether the outcomes of `market_a` and `market_b` are consistent with the link
+    fn allows(&self, a: Outcome, b: Outcome) -> bool {
+        match self.kind {
+            LinkKind::Implies => !(a == Outcome::Yes && b == Outcome::No),
+            LinkKind::Exclusive => !(a == Outcome::Yes && b == Outcome::Yes),
+        }
+    }
+}
+
+/// Outcome tokens of one market held in custody for a margin account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MarginPosition {
+    pub market: Pubkey,
+    pub yes: u64,
+    pub no: u64,
+}
+
+impl MarginPosition {
+    pub const LEN: usize = 32 + 8 + 8; // 48 bytes
+
+    /// Amount held of `outcome` (`Yes` or `No`)
+    pub fn amount_mut(&mut self, outcome: Outcome) -> &mut u64 {
+        match outcome {
+            Outcome::No => &mut self.no,
+            _ => &mut self.yes,
+        }
+    }
+
+    /// DAC the position redeems for if the market resolves to `outcome`
+    pub fn value(&self, outcome: Outcome) -> u128 {
+        match outcome {
+            Outcome::Yes => self.yes as u128,
+            Outcome::No => self.no as u128,
+            // Each side settles separately, rounding down
+            Outcome::Invalid => (self.yes / 2) as u128 + (self.no / 2) as u128,
+        }
+    }
+}
+
+/// A cross-margin account
+///
+/// DAC collateral and outcome tokens held in custody back DAC borrowed from
+/// the shared margin vault. Positions are valued at their worst case over
+/// every combination of outcomes the attached market links allow, so
+/// offsetting positions in correlated markets count towards the collateral.
+#[account]
+pub struct MarginAccount {
+    /// Wallet owning the account
+    pub owner: Pubkey,
+    /// DAC deposited in the margin vault
+    pub collateral: u64,
+    /// DAC borrowed from the margin vault
+    pub debt: u64,
+    /// Outcome tokens held in custody, one entry per market
+    pub positions: Vec<MarginPosition>,
+    /// Market correlations used when valuing positions
+    pub links: Vec<LinkRef>,
+    /// Bump for the margin account PDA
+    pub bump: u8,
+}
+
+impl MarginAccount {
+    pub const LEN: usize = 32 + 8 + 8
+        + (4 + MAX_MARGIN_POSITIONS * MarginPosition::LEN)
+        + (4 + MAX_MARGIN_LINKS * LinkRef::LEN)
+        + 1; // 557 bytes
+
+    /// The position in `market`, opened empty if there is room
+    pub fn position_mut(&mut self, market: Pubkey) -> Option<&mut MarginPosition> {
+        let index = match self.positions.iter().position(|p| p.market == market) {
+            Some(index) => index,
+            None if self.positions.len() < MAX_MARGIN_POSITIONS => {
+                self.positions.push(MarginPosition { market, yes: 0, no: 0 });
+                self.positions.len() - 1
+            }
+            None => return None,
+        };
+        self.positions.get_mut(index)
+    }
+
+    /// Credit DAC returned to the margin vault, repaying debt first
+    pub fn credit(&mut self, amount: u64) -> Option<()> {
+        let repaid = amount.min(self.debt);
+        self.debt -= repaid;
+        self.collateral = self.collateral.checked_add(amount - repaid)?;
+        Some(())
+    }
+
+    /// Draw DAC out of the margin vault, from collateral first and then as debt
+    pub fn debit(&mut self, amount: u64) -> Option<()> {
+        let drawn = amount.min(self.collateral);
+        self.collateral -= drawn;
+        self.debt = self.debt.checked_add(amount - drawn)?;
+        Some(())
+    }
+
+    /// Drop positions that no longer hold any tokens
+    pub fn prune_positions(&mut self) {
+        self.positions.retain(|p| p.yes > 0 || p.no > 0);
+    }
+
+    /// Lowest DAC the positions can redeem for over every allowed combination of outcomes
+    pub fn worst_case_value(&self) -> u128 {
+        const OUTCOMES: [Outcome; 3] = [Outcome::Yes, Outcome::No, Outcome::Invalid];
+        let n = self.positions.len();
+        let links: Vec<(usize, usize, &LinkRef)> = self
+            .links
+            .iter()
+            .filter_map(|link| {
+                let a = self.positions.iter().position(|p| p.market == link.market_a)?;
+                let b = self.positions.iter().position(|p| p.market == link.market_b)?;
+                Some((a, b, link))
+            })
+            .collect();
+
+        let mut worst = u128::MAX;
+        let mut scenario = [Outcome::Invalid; MAX_MARGIN_POSITIONS];
+        for mut code in 0..3usize.pow(n as u32) {
+            for outcome in scenario.iter_mut().take(n) {
+                *outcome = OUTCOMES[code % 3];
+                code /= 3;
+            }
+            if !links.iter().all(|&(a, b, link)| link.allows(scenario[a], scenario[b])) {
+                continue;
+            }
+            let value = self.positions.iter().zip(scenario).map(|(p, o)| p.value(o)).sum();
+            worst = worst.min(value);
+        }
+        worst
+    }
+
+    /// Debt not covered by the worst-case value of the positions
+    pub fn exposure(&self) -> u128 {
+        (self.debt as u128).saturating_sub(self.worst_case_value())
+    }
+
+    /// Collateral covers the worst-case exposure
+    pub fn is_healthy(&self) -> bool {
+        self.exposure() <= self.collateral as u128
+    }
+
+    /// Most debt a single liquidation can repay
+    pub fn max_liquidation(&self) -> u64 {
+        (self.debt as u128 * LIQUIDATION_CLOSE_FACTOR_BPS as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Liquidate up to `repay` DAC of debt against `held` tokens priced at `price`
+    ///
+    /// The liquidator receives tokens worth the repayment plus their share of
+    /// the penalty. When `held` cannot cover that, the repayment shrinks to
+    /// match the tokens available.
+    pub fn quote_liquidation(&self, repay: u64, price: u64, held: u64) -> Option<Liquidation> {
+        if price == 0 {
+            return None;
+        }
+        let bonus_bps = LIQUIDATION_PENALTY_BPS * LIQUIDATOR_SHARE_BPS / BPS_DENOMINATOR;
+        let fee_bps = LIQUIDATION_PENALTY_BPS - bonus_bps;
+        let gross = |repay: u64| repay as u128 * (BPS_DENOMINATOR + bonus_bps) as u128;
+
+        let mut repaid = repay.min(self.max_liquidation());
+        let mut seized = gross(repaid) * PRICE_SCALE as u128
+            / (BPS_DENOMINATOR as u128 * price as u128);
+        if seized > held as u128 {
+            seized = held as u128;
+            repaid = u64::try_from(
+                held as u128 * price as u128 * BPS_DENOMINATOR as u128
+                    / (PRICE_SCALE as u128 * (BPS_DENOMINATOR + bonus_bps) as u128),
+            )
+            .ok()?;
+        }
+        Some(Liquidation {
+            repaid,
+            seized: seized as u64,
+            insurance_fee: (repaid as u128 * fee_bps as u128 / BPS_DENOMINATOR as u128) as u64,
+        })
+    }
+}
@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+pub use dac_token::attestation::AttestationRequirement;
+
+/// Maximum length of a market question in bytes
+pub const MAX_QUESTION_LEN: usize = 200;
+/// Shortest claim window a market can set (30 days), so holders have time to redeem
+pub const MIN_CLAIM_WINDOW: i64 = 30 * 24 * 60 * 60;
+
+/// Protocol-wide configuration for dark markets
+#[account]
+pub struct MarketConfig {
+    /// Authority that can update config
+    pub authority: Pubkey,
+    /// Guardian that can resume halted trading
+    pub guardian: Pubkey,
+    /// The DAC mint used as collateral by every market
+    pub dac_mint: Pubkey,
+    /// Number of markets created (next market id)
+    pub market_count: u64,
+    /// Bump for the config PDA
+    pub bump: u8,
+}
+
+impl MarketConfig {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 1; // 105 bytes
+}
+
+/// Lifecycle of a market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MarketStatus {
+    /// Accepting splits, merges and trades
+    Open,
+    /// Outcome is final, tokens can be redeemed
+    Resolved,
+    /// Unredeemed collateral was swept after the claim deadline and the escrow closed
+    Swept,
+    /// Outcome sealed for private resolution, frozen until it is revealed
+    Sealed,
+}
+
+/// Final outcome of a binary market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Yes,
+    No,
+    /// The question could not be answered; both sides redeem at 50%
+    Invalid,
+}
+
+/// Room left in a market for fields added later
+pub const MARKET_RESERVED_LEN: usize = 67;
+
+/// A binary prediction market collateralized by DAC
+///
+/// Zero-copy with a fixed layout, so instructions read the fields they use
+/// in place instead of deserializing the whole market: the question is a
+/// fixed buffer, and the status, outcome and flags are stored as bytes
+/// behind accessors.
+#[account(zero_copy)]
+pub struct Market {
+    /// Sequential market id (part of the PDA seeds)
+    pub id: u64,
+    /// Unix timestamp after which the market can be resolved
+    pub end_time: i64,
+    /// DAC currently held in escrow (for tracking)
+    pub total_collateral: u64,
+    /// Unix timestamp at which the market was resolved
+    pub resolved_at: i64,
+    /// Seconds after resolution during which winnings can be redeemed (0 never expires)
+    pub claim_window: i64,
+    /// Wallet that created the market
+    pub creator: Pubkey,
+    /// Authority allowed to resolve the market (the config PDA for optimistic resolution)
+    pub resolver: Pubkey,
+    /// YES outcome token mint
+    pub yes_mint: Pubkey,
+    /// NO outcome token mint
+    pub no_mint: Pubkey,
+    /// DAC escrow backing the outcome tokens
+    pub escrow: Pubkey,
+    /// Series that opened this market (default for standalone markets)
+    pub series: Pubkey,
+    /// Credential of the attestation participants must hold
+    pub attestation_credential: Pubkey,
+    /// Schema of the attestation participants must hold (none while default)
+    pub attestation_schema: Pubkey,
+    /// The question being predicted, UTF-8 in its first `question_len` bytes
+    pub question: [u8; MAX_QUESTION_LEN],
+    pub question_len: u8,
+    /// Current lifecycle status (a `MarketStatus`)
+    pub status: u8,
+    /// Resolved outcome (0 until resolved, then 1 + the `Outcome`)
+    pub outcome: u8,
+    /// Whether unclaimed collateral is swept back to the creator instead of the insurance fund
+    pub sweep_to_creator: u8,
+    /// Bump for the market PDA
+    pub bump: u8,
+    pub reserved: [u8; MARKET_RESERVED_LEN],
+}
+
+impl Market {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + 32 * 8 + MAX_QUESTION_LEN + 1 + 1 + 1 + 1 + 1
+        + MARKET_RESERVED_LEN; // 568 bytes
+
+    pub fn question(&self) -> &str {
+        std::str::from_utf8(&self.question[..self.question_len as usize]).unwrap_or_default()
+    }
+
+    /// Store `question`, which must fit `MAX_QUESTION_LEN`
+    pub fn set_question(&mut self, question: &str) {
+        self.question = [0; MAX_QUESTION_LEN];
+        self.question[..question.len()].copy_from_slice(question.as_bytes());
+        self.question_len = question.len() as u8;
+    }
+
+    pub fn status(&self) -> MarketStatus {
+        match self.status {
+            0 => MarketStatus::Open,
+            1 => MarketStatus::Resolved,
+            2 => MarketStatus::Swept,
+            _ => MarketStatus::Sealed,
+        }
+    }
+
+    pub fn set_status(&mut self, status: MarketStatus) {
+        self.status = status as u8;
+    }
+
+    /// Resolved outcome, set once status is `Resolved`
+    pub fn outcome(&self) -> Option<Outcome> {
+        match self.outcome {
+            0 => None,
+            1 => Some(Outcome::Yes),
+            2 => Some(Outcome::No),
+            _ => Some(Outcome::Invalid),
+        }
+    }
+
+    pub fn set_outcome(&mut self, outcome: Option<Outcome>) {
+        self.outcome = outcome.map_or(0, |outcome| outcome as u8 + 1);
+    }
+
+    pub fn sweeps_to_creator(&self) -> bool {
+        self.sweep_to_creator != 0
+    }
+
+    /// Attestation participants must hold (none while its schema is default)
+    pub fn attestation(&self) -> AttestationRequirement {
+        AttestationRequirement { credential: self.attestation_credential, schema: self.attestation_schema }
+    }
+
+    pub fn set_attestation(&mut self, attestation: AttestationRequirement) {
+        self.attestation_credential = attestation.credential;
+        self.attestation_schema = attestation.schema;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status() == MarketStatus::Open
+    }
+
+    /// Open and before the end time
+    pub fn is_trading(&self, now: i64) -> bool {
+        self.is_open() && now < self.end_time
+    }
+
+    /// Finalize the market with `outcome` at `now`
+    pub fn resolve(&mut self, outcome: Outcome, now: i64) {
+        self.set_status(MarketStatus::Resolved);
+        self.set_outcome(Some(outcome));
+        self.resolved_at = now;
+    }
+
+    /// Resolved, with a claim window that has run out by `now`
+    pub fn claim_expired(&self, now: i64) -> bool {
+        self.status() == MarketStatus::Resolved
+            && self.claim_window > 0
+            && now >= self.resolved_at.saturating_add(self.claim_window)
+    }
+
+    /// Outcome token mint for `outcome` (`None` for `Invalid`)
+    pub fn outcome_mint(&self, outcome: Outcome) -> Option<Pubkey> {
+        match outcome {
+            Outcome::Yes => Some(self.yes_mint),
+            Outcome::No => Some(self.no_mint),
+            Outcome::Invalid => None,
+        }
+    }
+
+    /// DAC paid out for burning `yes` and `no` tokens after resolution
+    pub fn redemption_value(&self, yes: u64, no: u64) -> Option<u64> {
+        match self.outcome()? {
+            Outcome::Yes => Some(yes),
+            Outcome::No => Some(no),
+            Outcome::Invalid => Some(((yes as u128 + no as u128) / 2) as u64),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+mod arbitration;
+mod auction;
+mod circuit_breaker;
+mod commit_reveal;
+mod compressed_receipt;
+mod confidential;
+mod creator_escrow;
+mod dark_pool;
+mod dynamic_fee;
+mod fee_sharing;
+mod fee_tiers;
+mod gauge;
+mod groth16;
+mod launch;
+mod leverage;
+mod limit_order;
+mod lookup_table;
+mod margin;
+mod market;
+mod observations;
+mod parlay;
+mod pool;
+mod portfolio;
+mod position_tree;
+mod private_resolution;
+mod rebate;
+mod receipt;
+mod relayer;
+mod resolver;
+mod series;
+mod viewing_key;
+mod volume;
+mod wrap_bundling;
+
+pub use arbitration::*;
+pub use auction::*;
+pub use circuit_breaker::*;
+pub use commit_reveal::*;
+pub use compressed_receipt::*;
+pub use confidential::*;
+pub use creator_escrow::*;
+pub use dark_pool::*;
+pub use dynamic_fee::*;
+pub use fee_sharing::*;
+pub use fee_tiers::*;
+pub use gauge::*;
+pub use groth16::*;
+pub use launch::*;
+pub use leverage::*;
+pub use limit_order::*;
+pub use lookup_table::*;
+pub use margin::*;
+pub use market::*;
+pub use observations::*;
+pub use parlay::*;
+pub use pool::*;
+pub use portfolio::*;
+pub use position_tree::*;
+pub use private_resolution::*;
+pub use rebate::*;
+pub use receipt::*;
+pub use relayer::*;
+pub use resolver::*;
+pub use series::*;
+pub use viewing_key::*;
+pub use volume::*;
+pub use wrap_bundling::*;
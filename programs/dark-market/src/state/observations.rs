@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+
+/// Number of observations kept per pool
+pub const OBSERVATION_CAPACITY: usize = 64;
+
+/// A cumulative-price checkpoint
+#[zero_copy(unsafe)]
+#[repr(packed)]
+#[derive(Default, PartialEq, Eq, Debug)]
+pub struct Observation {
+    /// Unix timestamp of the checkpoint
+    pub timestamp: i64,
+    /// Sum of YES price (scaled by `PRICE_SCALE`) times seconds since pool creation
+    pub yes_price_cumulative: u128,
+}
+
+impl Observation {
+    pub const LEN: usize = 8 + 16; // 24 bytes
+}
+
+/// Ring buffer of cumulative-price observations for one pool (Uniswap-style)
+///
+/// A checkpoint is written at most once per second, on the first trade of
+/// that second, before the trade moves the price. The TWAP between any two
+/// checkpoints is the difference of their cumulatives divided by the elapsed time.
+///
+/// Zero-copy, so a trade updates one checkpoint in place instead of
+/// deserializing and rewriting the whole buffer. The layout is packed and
+/// matches the Borsh encoding byte for byte, so existing accounts load
+/// unchanged.
+#[account(zero_copy(unsafe))]
+#[repr(packed)]
+pub struct PoolObservations {
+    /// The pool being observed
+    pub pool: Pubkey,
+    /// Slot of the most recent observation in `observations`
+    pub index: u16,
+    /// Number of populated observations
+    pub count: u16,
+    /// Bump for the observations PDA
+    pub bump: u8,
+    /// Observation ring buffer
+    pub observations: [Observation; OBSERVATION_CAPACITY],
+}
+
+impl PoolObservations {
+    pub const LEN: usize = 32 + 2 + 2 + 1 + OBSERVATION_CAPACITY * Observation::LEN; // 1573 bytes
+
+    pub fn latest(&self) -> Observation {
+        self.observations[self.index as usize]
+    }
+
+    /// Accumulate `yes_price`, the price in effect since the latest observation, up to `now`
+    pub fn record(&mut self, now: i64, yes_price: u64) {
+        let latest = self.latest();
+        if now <= latest.timestamp {
+            return;
+        }
+        let elapsed = (now - latest.timestamp) as u128;
+        let index = (self.index as usize + 1) % OBSERVATION_CAPACITY;
+        self.observations[index] = Observation {
+            timestamp: now,
+            yes_price_cumulative: latest.yes_price_cumulative
+                .wrapping_add(yes_price as u128 * elapsed),
+        };
+        self.index = index as u16;
+        self.count = (self.count + 1).min(OBSERVATION_CAPACITY as u16);
+    }
+
+    /// Newest observation at or before `target`, if the buffer reaches back that far
+    pub fn at_or_before(&self, target: i64) -> Option<Observation> {
+        (0..self.count as usize)
+            .map(|age| {
+                let slot = (self.index as usize + OBSERVATION_CAPACITY - age) % OBSERVATION_CAPACITY;
+                self.observations[slot]
+            })
+            .find(|observation| observation.timestamp <= target)
+    }
+
+    /// Mean absolute change of the average YES price between consecutive
+    /// observation intervals over the last `window` seconds
+    ///
+    /// The interval since the latest observation counts at `current_price`.
+    /// Returns 0 until two intervals fall inside the window.
+    pub fn volatility(&self, now: i64, window: i64, current_price: u64) -> u64 {
+        let start = now - window;
+        let mut newer = self.latest();
+        let mut previous = (now > newer.timestamp).then_some(current_price);
+        let mut total: u128 = 0;
+        let mut moves: u128 = 0;
+        for age in 1..self.count as usize {
+            if newer.timestamp <= start {
+                break;
+            }
+            let slot = (self.index as usize + OBSERVATION_CAPACITY - age) % OBSERVATION_CAPACITY;
+            let older = self.observations[slot];
+            let elapsed = (newer.timestamp - older.timestamp) as u128;
+            if elapsed > 0 {
+                let average = (newer.yes_price_cumulative.wrapping_sub(older.yes_price_cumulative)
+                    / elapsed) as u64;
+                if let Some(previous) = previous {
+                    total += previous.abs_diff(average) as u128;
+                    moves += 1;
+                }
+                previous = Some(average);
+            }
+            newer = older;
+        }
+        if moves == 0 {
+            return 0;
+        }
+        (total / moves) as u64
+    }
+
+    /// Time-weighted average YES price over at least the last `window` seconds
+    ///
+    /// `current_price` is the pool's spot price, which has been in effect since
+    /// the latest observation. The average starts at the newest observation at
+    /// or before `now - window`, so the effective window can be slightly longer.
+    pub fn twap(&self, now: i64, window: i64, current_price: u64) -> Option<u64> {
+        let latest = self.latest();
+        let cumulative_now = latest.yes_price_cumulative
+            .wrapping_add(current_price as u128 * (now - latest.timestamp).max(0) as u128);
+        let start = self.at_or_before(now - window)?;
+        let elapsed = (now - start.timestamp) as u128;
+        if elapsed == 0 {
+            return Some(current_price);
+        }
+        Some((cumulative_now.wrapping_sub(start.yes_price_cumulative) / elapsed) as u64)
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use super::Outcome;
+
+/// Minimum number of legs in a parlay
+pub const MIN_PARLAY_LEGS: usize = 2;
+/// Maximum number of legs in a parlay
+pub const MAX_PARLAY_LEGS: usize = 8;
+
+/// One leg of a parlay: a market and the outcome the owner needs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParlayLeg {
+    /// The market this leg depends on
+    pub market: Pubkey,
+    /// The outcome the parlay owner is backing (`Yes` or `No`)
+    pub outcome: Outcome,
+}
+
+impl ParlayLeg {
+    pub const LEN: usize = 32 + 1; // 33 bytes
+}
+
+/// Lifecycle of a parlay
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParlayStatus {
+    /// Stake locked, waiting for a backer
+    Open,
+    /// Fully funded, waiting for every leg to resolve
+    Backed,
+}
+
+/// How a parlay settles once every leg has resolved
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParlayResult {
+    /// Every leg resolved in the owner's favor: owner takes the full payout
+    Won,
+    /// At least one leg resolved against the owner: backer takes the full payout
+    Lost,
+    /// No leg lost but at least one resolved invalid: both sides are refunded
+    Refunded,
+}
+
+/// A peer-to-peer parlay position
+///
+/// The owner locks `stake` DAC and a backer locks `payout - stake` DAC. The
+/// whole `payout` goes to the owner only if every leg resolves in their favor.
+#[account]
+pub struct Parlay {
+    /// Wallet holding the parlay position
+    pub owner: Pubkey,
+    /// Wallet that funded the other side (default until backed)
+    pub backer: Pubkey,
+    /// Owner-chosen nonce (part of the PDA seeds)
+    pub nonce: u64,
+    /// Markets and outcomes that must all resolve in the owner's favor
+    pub legs: Vec<ParlayLeg>,
+    /// DAC locked by the owner
+    pub stake: u64,
+    /// Total DAC paid to the winning side
+    pub payout: u64,
+    /// Current lifecycle status
+    pub status: ParlayStatus,
+    /// Bump for the parlay PDA
+    pub bump: u8,
+    /// Bump for the parlay escrow PDA
+    pub escrow_bump: u8,
+}
+
+impl Parlay {
+    pub const LEN: usize = 32 + 32 + 8 + (4 + MAX_PARLAY_LEGS * ParlayLeg::LEN) + 8 + 8 + 1 + 1 + 1; // 359 bytes
+
+    /// Amount the backer must lock to fully fund the payout
+    pub fn backing(&self) -> u64 {
+        self.payout - self.stake
+    }
+
+    /// Settle against the resolved outcome of each leg, in leg order
+    pub fn result(&self, outcomes: &[Outcome]) -> ParlayResult {
+        let mut refunded = false;
+        for (leg, outcome) in self.legs.iter().zip(outcomes) {
+            match outcome {
+                Outcome::Invalid => refunded = true,
+                outcome if *outcome != leg.outcome => return ParlayResult::Lost,
+                _ => {}
+            }
+        }
+        if refunded {
+            ParlayResult::Refunded
+        } else {
+            ParlayResult::Won
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+
+use super::{CircuitBreaker, DynamicFee, Outcome, PoolObservations};
+
+/// Fixed-point scale for prices (1.0 == 1_000_000, matching DAC's 6 decimals)
+pub const PRICE_SCALE: u64 = 1_000_000;
+/// Basis point denominator
+pub const BPS_DENOMINATOR: u64 = 10_000;
+/// Maximum swap fee a pool can charge (10%)
+pub const MAX_POOL_FEE_BPS: u16 = 1_000;
+
+/// Constant-product pool of a market's YES and NO tokens
+///
+/// Buying splits the DAC paid in into YES+NO, adds both to the reserves and
+/// pays out the requested side so that `yes_reserve * no_reserve` never
+/// decreases. Selling runs the same trade in reverse and merges the released
+/// pairs back into DAC. The implied probability of YES is
+/// `no_reserve / (yes_reserve + no_reserve)`.
+#[account]
+pub struct Pool {
+    /// The market this pool trades
+    pub market: Pubkey,
+    /// Vault holding the YES reserve
+    pub yes_vault: Pubkey,
+    /// Vault holding the NO reserve
+    pub no_vault: Pubkey,
+    /// LP share mint
+    pub lp_mint: Pubkey,
+    /// YES tokens in the reserve
+    pub yes_reserve: u64,
+    /// NO tokens in the reserve
+    pub no_reserve: u64,
+    /// Swap fee in basis points, retained in the reserves for LPs
+    pub fee_bps: u16,
+    /// Trading halt on extreme price movement
+    pub breaker: CircuitBreaker,
+    /// Volatility surcharge on `fee_bps`
+    pub dynamic_fee: DynamicFee,
+    /// Bump for the pool PDA
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 8 + 2 + CircuitBreaker::LEN + DynamicFee::LEN + 1; // 205 bytes
+
+    /// Reserves as (side, other side)
+    fn reserves(&self, outcome: Outcome) -> (u64, u64) {
+        match outcome {
+            Outcome::No => (self.no_reserve, self.yes_reserve),
+            _ => (self.yes_reserve, self.no_reserve),
+        }
+    }
+
+    fn set_reserves(&mut self, outcome: Outcome, side: u64, other: u64) {
+        match outcome {
+            Outcome::No => {
+                self.no_reserve = side;
+                self.yes_reserve = other;
+            }
+            _ => {
+                self.yes_reserve = side;
+                self.no_reserve = other;
+            }
+        }
+    }
+
+    /// Spot price of `outcome` scaled by `PRICE_SCALE`
+    pub fn price(&self, outcome: Outcome) -> u64 {
+        let (side, other) = self.reserves(outcome);
+        let total = side as u128 + other as u128;
+        if total == 0 {
+            return 0;
+        }
+        (other as u128 * PRICE_SCALE as u128 / total) as u64
+    }
+
+    /// Recompute the volatility surcharge ahead of a trade at `now`
+    pub fn refresh_fee(&mut self, observations: &PoolObservations, now: i64) {
+        let price = self.price(Outcome::Yes);
+        self.dynamic_fee.refresh(observations, now, price);
+    }
+
+    /// Swap fee on `amount`, reduced by `discount_bps` of itself
+    pub fn fee(&self, amount: u64, discount_bps: u16) -> u64 {
+        let fee_bps = self.dynamic_fee.fee_bps(self.fee_bps) as u128
+            * (BPS_DENOMINATOR - discount_bps as u64) as u128
+            / BPS_DENOMINATOR as u128;
+        (amount as u128 * fee_bps / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Outcome tokens received for paying `amount_in` DAC, with the fee cut by `discount_bps`
+    pub fn quote_buy(&self, outcome: Outcome, amount_in: u64, discount_bps: u16) -> Option<u64> {
+        let (side, other) = self.reserves(outcome);
+        let invested = (amount_in - self.fee(amount_in, discount_bps)) as u128;
+        if invested == 0 {
+            return Some(0);
+        }
+        let k = side as u128 * other as u128;
+        let new_other = other as u128 + invested;
+        // Round the remaining reserve up so the invariant never shrinks
+        let remaining = k.checked_add(new_other - 1)? / new_other;
+        let out = (side as u128 + invested).checked_sub(remaining)?;
+        u64::try_from(out).ok()
+    }
+
+    /// DAC received for selling `amount_in` outcome tokens, with the fee cut by `discount_bps`
+    pub fn quote_sell(&self, outcome: Outcome, amount_in: u64, discount_bps: u16) -> Option<u64> {
+        let released = self.quote_sell_gross(outcome, amount_in)?;
+        Some(released - self.fee(released, discount_bps))
+    }
+
+    /// DAC released from the reserves for selling `amount_in` outcome tokens, before the fee
+    pub fn quote_sell_gross(&self, outcome: Outcome, amount_in: u64) -> Option<u64> {
+        let (side, other) = self.reserves(outcome);
+        // Largest x with (side + amount_in - x) * (other - x) >= side * other
+        let sum = side as u128 + amount_in as u128 + other as u128;
+        let disc = sum.checked_mul(sum)?
+            .checked_sub((amount_in as u128 * other as u128).checked_mul(4)?)?;
+        let released = (sum - isqrt_ceil(disc)) / 2;
+        u64::try_from(released).ok()
+    }
+
+    /// Apply a buy of `out` tokens for `amount_in` DAC to the reserves
+    pub fn apply_buy(&mut self, outcome: Outcome, amount_in: u64, out: u64) -> Option<()> {
+        let (side, other) = self.reserves(outcome);
+        let side = side.checked_add(amount_in)?.checked_sub(out)?;
+        let other = other.checked_add(amount_in)?;
+        self.set_reserves(outcome, side, other);
+        Some(())
+    }
+
+    /// Apply a sale of `amount_in` tokens for `out` DAC to the reserves
+    pub fn apply_sell(&mut self, outcome: Outcome, amount_in: u64, out: u64) -> Option<()> {
+        let (side, other) = self.reserves(outcome);
+        let side = side.checked_add(amount_in)?.checked_sub(out)?;
+        let other = other.checked_sub(out)?;
+        self.set_reserves(outcome, side, other);
+        Some(())
+    }
+
+    /// For a deposit of `amount` YES and NO: (LP shares minted, YES returned, NO returned)
+    ///
+    /// Only the heavier reserve is matched in full, so the spot price is unchanged
+    /// and the surplus of the lighter side goes back to the depositor.
+    pub fn quote_add_liquidity(&self, amount: u64, lp_supply: u64) -> Option<(u64, u64, u64)> {
+        let weight = self.yes_reserve.max(self.no_reserve) as u128;
+        if weight == 0 {
+            return None;
+        }
+        let kept = |reserve: u64| amount as u128 * reserve as u128 / weight;
+        let shares = u64::try_from(amount as u128 * lp_supply as u128 / weight).ok()?;
+        let yes_back = amount - kept(self.yes_reserve) as u64;
+        let no_back = amount - kept(self.no_reserve) as u64;
+        Some((shares, yes_back, no_back))
+    }
+
+    /// YES and NO released for burning `shares` of `lp_supply`
+    pub fn quote_remove_liquidity(&self, shares: u64, lp_supply: u64) -> Option<(u64, u64)> {
+        if lp_supply == 0 {
+            return None;
+        }
+        let share = |reserve: u64| (reserve as u128 * shares as u128 / lp_supply as u128) as u64;
+        Some((share(self.yes_reserve), share(self.no_reserve)))
+    }
+}
+
+/// Smallest integer `r` with `r * r >= n`
+fn isqrt_ceil(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method for floor(sqrt(n))
+    let mut x = 1u128 << ((128 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            break;
+        }
+        x = y;
+    }
+    if x * x < n {
+        x + 1
+    } else {
+        x
+    }
+}
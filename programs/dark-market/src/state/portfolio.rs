@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+use super::{Outcome, PRICE_SCALE};
+
+/// Maximum number of markets a portfolio tracks at once
+pub const MAX_PORTFOLIO_POSITIONS: usize = 16;
+
+/// Holdings and cost basis of one market in a portfolio
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PortfolioPosition {
+    pub market: Pubkey,
+    /// YES tokens acquired through tracked trades
+    pub yes: u64,
+    /// NO tokens acquired through tracked trades
+    pub no: u64,
+    /// DAC paid for the YES tokens still held
+    pub yes_cost: u64,
+    /// DAC paid for the NO tokens still held
+    pub no_cost: u64,
+}
+
+impl PortfolioPosition {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8; // 64 bytes
+
+    /// (amount, cost) of `outcome` (`Yes` or `No`)
+    fn side_mut(&mut self, outcome: Outcome) -> (&mut u64, &mut u64) {
+        match outcome {
+            Outcome::No => (&mut self.no, &mut self.no_cost),
+            _ => (&mut self.yes, &mut self.yes_cost),
+        }
+    }
+
+    /// Holdings valued at `yes_price` (scaled by `PRICE_SCALE`) minus their cost
+    pub fn unrealized_pnl(&self, yes_price: u64) -> i64 {
+        let no_price = PRICE_SCALE.saturating_sub(yes_price);
+        let value = (self.yes as u128 * yes_price as u128 + self.no as u128 * no_price as u128)
+            / PRICE_SCALE as u128;
+        value as i64 - self.yes_cost as i64 - self.no_cost as i64
+    }
+}
+
+/// A user's trading record, updated by every trade that passes it
+///
+/// Cost basis is tracked at average cost per side. Tokens acquired outside
+/// tracked instructions (transfers, untracked trades) carry no basis, so
+/// selling more than the tracked amount realizes the excess at zero cost.
+#[account]
+pub struct Portfolio {
+    /// Wallet whose trades are recorded
+    pub owner: Pubkey,
+    /// Profit or loss locked in by sales, merges and redemptions
+    pub realized_pnl: i64,
+    /// Total DAC spent on tracked purchases
+    pub volume: u64,
+    /// Open positions, one entry per market
+    pub positions: Vec<PortfolioPosition>,
+    /// Bump for the portfolio PDA
+    pub bump: u8,
+}
+
+impl Portfolio {
+    pub const LEN: usize = 32 + 8 + 8 + (4 + MAX_PORTFOLIO_POSITIONS * PortfolioPosition::LEN) + 1; // 1077 bytes
+
+    fn position_mut(&mut self, market: Pubkey) -> Option<&mut PortfolioPosition> {
+        let index = match self.positions.iter().position(|p| p.market == market) {
+            Some(index) => index,
+            None if self.positions.len() < MAX_PORTFOLIO_POSITIONS => {
+                self.positions.push(PortfolioPosition { market, yes: 0, no: 0, yes_cost: 0, no_cost: 0 });
+                self.positions.len() - 1
+            }
+            None => return None,
+        };
+        self.positions.get_mut(index)
+    }
+
+    /// Record `amount` tokens of `outcome` bought for `cost` DAC
+    pub fn record_buy(&mut self, market: Pubkey, outcome: Outcome, amount: u64, cost: u64) -> Option<()> {
+        let (held, basis) = self.position_mut(market)?.side_mut(outcome);
+        *held = held.checked_add(amount)?;
+        *basis = basis.checked_add(cost)?;
+        self.volume = self.volume.checked_add(cost)?;
+        Some(())
+    }
+
+    /// Record `amount` tokens of `outcome` disposed of for `proceeds` DAC
+    pub fn record_sell(&mut self, market: Pubkey, outcome: Outcome, amount: u64, proceeds: u64) -> Option<()> {
+        let released = match self.positions.iter_mut().find(|p| p.market == market) {
+            Some(position) => {
+                let (held, basis) = position.side_mut(outcome);
+                let tracked = amount.min(*held);
+                let released = if tracked == 0 {
+                    0
+                } else {
+                    (*basis as u128 * tracked as u128 / *held as u128) as u64
+                };
+                *held -= tracked;
+                *basis -= released;
+                released
+            }
+            None => 0,
+        };
+        self.realized_pnl = self.realized_pnl
+            .checked_add(proceeds as i64)?
+            .checked_sub(released as i64)?;
+        self.positions.retain(|p| p.yes > 0 || p.no > 0);
+        Some(())
+    }
+
+    /// Record a split of `amount` DAC into YES and NO, costing half a DAC each
+    pub fn record_split(&mut self, market: Pubkey, amount: u64) -> Option<()> {
+        let half = amount / 2;
+        self.record_buy(market, Outcome::Yes, amount, half)?;
+        self.record_buy(market, Outcome::No, amount, amount - half)
+    }
+
+    /// Record a merge of `amount` YES and NO into as much DAC, half a DAC each
+    pub fn record_merge(&mut self, market: Pubkey, amount: u64) -> Option<()> {
+        let half = amount / 2;
+        self.record_sell(market, Outcome::Yes, amount, half)?;
+        self.record_sell(market, Outcome::No, amount, amount - half)
+    }
+}
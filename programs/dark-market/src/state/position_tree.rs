@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use super::Outcome;
+
+/// Smallest position tree a market can opt into (2^14 positions)
+pub const MIN_POSITION_TREE_DEPTH: u32 = 14;
+/// Deepest tree the compression program supports
+pub const MAX_POSITION_TREE_DEPTH: u32 = 30;
+/// Most positions `settle_batch` redeems in one transaction
+pub const MAX_SETTLE_BATCH: usize = 24;
+/// Smallest change log a position tree accepts
+///
+/// Every leaf of a batch is replaced against the one root its proofs were
+/// made for, so the tree must fast-forward them over the batch's own
+/// changes, with room to spare for positions changed concurrently.
+pub const MIN_POSITION_TREE_BUFFER_SIZE: u32 = 64;
+
+/// A market's concurrent Merkle tree of compressed positions
+///
+/// Cheaper than receipts, compressed or not: a position is a bare leaf
+/// hashing its owner, side, amount and index, appended by this PDA as the
+/// tree's authority, while the outcome tokens are pooled in one vault per
+/// side. Changing or redeeming a position restates the leaf with a proof;
+/// the canopy keeps the top levels on chain so proofs stay short.
+#[account]
+pub struct PositionTree {
+    /// The market the positions belong to
+    pub market: Pubkey,
+    /// The concurrent Merkle tree holding the positions
+    pub merkle_tree: Pubkey,
+    /// Depth of the tree
+    pub max_depth: u32,
+    /// Levels of the tree kept in its canopy
+    pub canopy_depth: u32,
+    /// Leaves appended so far, which is the next position's index
+    pub appended: u32,
+    /// YES tokens held for open positions
+    pub yes_amount: u64,
+    /// NO tokens held for open positions
+    pub no_amount: u64,
+    /// Bump for the position tree PDA
+    pub bump: u8,
+}
+
+impl PositionTree {
+    pub const LEN: usize = 32 + 32 + 4 + 4 + 4 + 8 + 8 + 1; // 93 bytes
+
+    /// The leaf of `owner`'s position of `amount` `outcome` tokens at `index`
+    pub fn leaf(owner: &Pubkey, outcome: Outcome, amount: u64, index: u32) -> [u8; 32] {
+        let (amount, index) = (amount.to_le_bytes(), index.to_le_bytes());
+        keccak::hashv(&[owner.as_ref(), &[outcome as u8], &amount, &index]).to_bytes()
+    }
+
+    /// Proof nodes a caller supplies for a leaf, the rest being in the canopy
+    pub fn proof_len(&self) -> usize {
+        (self.max_depth - self.canopy_depth) as usize
+    }
+
+    /// Outcome tokens of `outcome` held for open positions
+    pub fn wrapped(&mut self, outcome: Outcome) -> &mut u64 {
+        if outcome == Outcome::Yes {
+            &mut self.yes_amount
+        } else {
+            &mut self.no_amount
+        }
+    }
+}
+
+/// A compressed position a keeper settles, as restated for its proof
+///
+/// The owner is not restated: it is the owner of the DAC token account the
+/// payout goes to.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SettledPosition {
+    pub outcome: Outcome,
+    pub amount: u64,
+    /// The leaf's index in the tree
+    pub index: u32,
+}
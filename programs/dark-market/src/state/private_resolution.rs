@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use super::Outcome;
+
+/// Longest delay between sealing an outcome and revealing it (7 days)
+pub const MAX_REVEAL_DELAY: i64 = 7 * 24 * 60 * 60;
+/// Time the resolver has to reveal after the reveal time, before anyone can void the market (7 days)
+pub const REVEAL_GRACE_PERIOD: i64 = 7 * 24 * 60 * 60;
+/// Maximum size of a sealed outcome ciphertext in bytes
+pub const MAX_SEALED_OUTCOME_LEN: usize = 128;
+
+/// Private resolution settings and the sealed outcome of a market
+///
+/// The resolver seals the outcome as soon as it is known, which freezes
+/// the market at once; nobody learns which way it went until the reveal
+/// `reveal_delay` seconds later. So nobody who learns the outcome early can
+/// trade on it against stale prices. The ciphertext holds the outcome and
+/// salt encrypted to the market's key, so the key holders can reveal it
+/// even if the resolver does not. If nobody has by `reveal_deadline`, anyone
+/// can resolve the market as invalid so the escrow is not locked forever.
+#[account]
+pub struct SealedResolution {
+    /// The market resolved privately
+    pub market: Pubkey,
+    /// Market-specific public key the outcome is encrypted to
+    pub encryption_key: [u8; 32],
+    /// Seconds between sealing and the earliest reveal
+    pub reveal_delay: i64,
+    /// Outcome and salt encrypted to `encryption_key` (empty until sealed)
+    pub ciphertext: Vec<u8>,
+    /// Hash binding the sealed outcome (see `outcome_hash`)
+    pub commitment: [u8; 32],
+    /// When the outcome was sealed (0 until then)
+    pub sealed_at: i64,
+    /// Bump for the sealed resolution PDA
+    pub bump: u8,
+}
+
+impl SealedResolution {
+    pub const LEN: usize = 32 + 32 + 8 + (4 + MAX_SEALED_OUTCOME_LEN) + 32 + 8 + 1; // 245 bytes
+
+    /// Earliest time the sealed outcome can be revealed
+    pub fn reveal_time(&self) -> i64 {
+        self.sealed_at.saturating_add(self.reveal_delay)
+    }
+
+    /// Time after which an unrevealed market can be voided
+    pub fn reveal_deadline(&self) -> i64 {
+        self.reveal_time().saturating_add(REVEAL_GRACE_PERIOD)
+    }
+
+    /// Hash committing `market` to `outcome`
+    pub fn outcome_hash(market: &Pubkey, outcome: Outcome, salt: &[u8; 32]) -> [u8; 32] {
+        hashv(&[market.as_ref(), &[outcome as u8], salt]).to_bytes()
+    }
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use super::BPS_DENOMINATOR;
+
+/// Highest maker rebate, in basis points of filled notional (1%)
+pub const MAX_MAKER_REBATE_BPS: u16 = 100;
+
+/// Maker rebate program of one pool
+///
+/// Takers trading directly against the pool pay the full fee, of which
+/// `taker_share_bps` is paid into the rebate vault instead of the reserves.
+/// Limit orders filled against the pool are the resting makers: each fill
+/// accrues `maker_rebate_bps` of its DAC notional to the maker, as long as
+/// the vault holds enough unreserved DAC.
+#[account]
+pub struct RebatePool {
+    /// The pool whose fees fund the rebates
+    pub pool: Pubkey,
+    /// DAC vault holding taker fees set aside for makers
+    pub vault: Pubkey,
+    /// Share of each taker fee paid into the vault, in basis points
+    pub taker_share_bps: u16,
+    /// Rebate on a maker fill, in basis points of its DAC notional
+    pub maker_rebate_bps: u16,
+    /// DAC accrued to makers but not yet claimed
+    pub reserved: u64,
+    /// Bump for the rebate pool PDA
+    pub bump: u8,
+    /// Bump for the rebate vault PDA
+    pub vault_bump: u8,
+}
+
+impl RebatePool {
+    pub const LEN: usize = 32 + 32 + 2 + 2 + 8 + 1 + 1; // 78 bytes
+
+    /// Part of a taker's `fee` paid into the vault
+    pub fn taker_share(&self, fee: u64) -> u64 {
+        (fee as u128 * self.taker_share_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    /// Rebate for a maker fill of `notional` DAC, limited to the unreserved `vault_balance`
+    pub fn maker_rebate(&self, notional: u64, vault_balance: u64) -> u64 {
+        let rebate = (notional as u128 * self.maker_rebate_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        rebate.min(vault_balance.saturating_sub(self.reserved))
+    }
+}
+
+/// Rebates accrued to one maker on one pool
+#[account]
+pub struct MakerRebate {
+    /// The pool the maker's orders fill against
+    pub pool: Pubkey,
+    /// Wallet placing the limit orders
+    pub maker: Pubkey,
+    /// DAC accrued and not yet claimed
+    pub accrued: u64,
+    /// DAC claimed so far
+    pub claimed: u64,
+    /// Bump for the maker rebate PDA
+    pub bump: u8,
+}
+
+impl MakerRebate {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 1; // 81 bytes
+}
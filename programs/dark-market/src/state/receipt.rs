@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use super::Outcome;
+
+/// Symbol used for position receipt NFT metadata
+pub const RECEIPT_SYMBOL: &str = "DMPOS";
+/// Maximum length of a receipt metadata URI (Metaplex limit)
+pub const MAX_RECEIPT_URI_LEN: usize = 200;
+
+/// A market position wrapped into a transferable NFT
+///
+/// The outcome tokens sit in a vault owned by this PDA. Whoever holds the
+/// receipt NFT can unwrap it back into fungible outcome tokens.
+#[account]
+pub struct PositionReceipt {
+    /// The market the wrapped position belongs to
+    pub market: Pubkey,
+    /// The receipt NFT mint (supply of one)
+    pub receipt_mint: Pubkey,
+    /// Which outcome token is wrapped (`Yes` or `No`)
+    pub outcome: Outcome,
+    /// Amount of outcome tokens held in the receipt vault
+    pub amount: u64,
+    /// Wallet that wrapped the position
+    pub original_owner: Pubkey,
+    /// Unix timestamp when the position was wrapped
+    pub wrapped_at: i64,
+    /// Wrapper-chosen nonce (part of the PDA seeds)
+    pub nonce: u64,
+    /// Bump for the receipt PDA
+    pub bump: u8,
+    /// Bump for the receipt vault PDA
+    pub vault_bump: u8,
+}
+
+impl PositionReceipt {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 32 + 8 + 8 + 1 + 1; // 123 bytes
+}
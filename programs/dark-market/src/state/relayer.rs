@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+
+use super::{Outcome, BPS_DENOMINATOR};
+
+/// Highest fee a relayer can charge on the orders it submits (1%)
+pub const MAX_RELAYER_FEE_BPS: u16 = 100;
+/// Most orders a relayed batch can carry
+pub const MAX_RELAY_BATCH: usize = 4;
+
+/// A relayer approved by the config authority to submit users' orders
+#[account]
+pub struct Relayer {
+    /// Key signing the relayer's batches
+    pub authority: Pubkey,
+    /// Fee charged on each order's DAC, in basis points
+    pub fee_bps: u16,
+    /// Total fees collected
+    pub fees_earned: u64,
+    /// Whether the relayer can currently submit batches
+    pub approved: bool,
+    /// Bump for the relayer PDA
+    pub bump: u8,
+}
+
+impl Relayer {
+    pub const LEN: usize = 32 + 2 + 8 + 1 + 1; // 44 bytes
+
+    pub fn fee(&self, amount: u64) -> u64 {
+        (amount as u128 * self.fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+}
+
+/// DAC a user keeps with the program for relayers to trade on their behalf
+///
+/// Funding the account is the only transaction the user signs; trades are
+/// authorized by off-chain signatures over `RelayedOrder`s, so they never
+/// show the user's wallet as a signer.
+#[account]
+pub struct RelayAccount {
+    /// Wallet owning the balance and signing its orders
+    pub owner: Pubkey,
+    /// DAC available for relayed orders
+    pub balance: u64,
+    /// Nonce the next order must carry
+    pub nonce: u64,
+    /// Bump for the relay account PDA
+    pub bump: u8,
+}
+
+impl RelayAccount {
+    pub const LEN: usize = 32 + 8 + 8 + 1; // 49 bytes
+}
+
+/// A buy order signed off-chain by a relay account's owner
+///
+/// Users send these to a relayer encrypted to its key; the signed message
+/// is the Borsh encoding behind `RELAYED_ORDER_DOMAIN`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RelayedOrder {
+    /// Relay account owner
+    pub owner: Pubkey,
+    /// The only relayer allowed to submit the order
+    pub relayer: Pubkey,
+    pub market: Pubkey,
+    /// Outcome bought (`Yes` or `No`)
+    pub outcome: Outcome,
+    /// DAC spent, including the relayer fee
+    pub amount_in: u64,
+    /// Fewest outcome tokens accepted
+    pub min_amount_out: u64,
+    /// Must match the relay account's nonce, so an order executes once
+    pub nonce: u64,
+    /// Last slot the order can execute in
+    pub expiry_slot: u64,
+    /// Token account receiving the outcome tokens
+    pub recipient: Pubkey,
+}
+
+/// Prefix of every signed order, so the signature is useless outside this program
+pub const RELAYED_ORDER_DOMAIN: &[u8] = b"dark-market:relayed-order";
+
+impl RelayedOrder {
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = RELAYED_ORDER_DOMAIN.to_vec();
+        // Writing to a Vec cannot fail
+        self.serialize(&mut message).ok();
+        message
+    }
+}
+
+/// A relayed order and where its signature is checked
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SignedOrder {
+    pub order: RelayedOrder,
+    /// Index in the transaction of the ed25519 program instruction checking the signature
+    pub signature_ix: u8,
+}
+
+/// Whether `ix` is an ed25519 program instruction checking a signature by `signer` over `message`
+///
+/// The ed25519 program fails the whole transaction on a bad signature, so
+/// finding the instruction is enough. Only signatures whose key and message
+/// sit in the instruction's own data are considered.
+pub fn ed25519_verifies(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> bool {
+    if ix.program_id != ed25519_program::ID {
+        return false;
+    }
+    let data = &ix.data;
+    let count = data.first().copied().unwrap_or(0) as usize;
+    (0..count).any(|i| {
+        let start = 2 + i * 14;
+        let Some(offsets) = data.get(start..start + 14) else {
+            return false;
+        };
+        let field = |j: usize| u16::from_le_bytes([offsets[2 * j], offsets[2 * j + 1]]);
+        let (key_offset, message_offset, message_len) =
+            (field(2) as usize, field(4) as usize, field(5) as usize);
+        field(1) == u16::MAX
+            && field(3) == u16::MAX
+            && field(6) == u16::MAX
+            && data.get(key_offset..key_offset + 32) == Some(signer.as_ref())
+            && data.get(message_offset..message_offset + message_len) == Some(message)
+    })
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// Maximum length of a resolver's display name in bytes
+pub const MAX_RESOLVER_NAME_LEN: usize = 32;
+
+/// A resolver adapter program approved by the config authority
+///
+/// A market resolved through an adapter names this entry's PDA as its
+/// resolver. Anyone can then trigger `resolve_with_adapter`, which CPIs into
+/// the adapter as described by `ResolverInterface`.
+#[account]
+pub struct ResolverEntry {
+    /// Registry id (part of the PDA seeds)
+    pub id: u32,
+    /// The adapter program
+    pub program: Pubkey,
+    /// Human-readable name (e.g. "pyth", "switchboard", "optimistic", "manual")
+    pub name: String,
+    /// Whether markets can currently resolve through the adapter
+    pub approved: bool,
+    /// Bump for the entry PDA
+    pub bump: u8,
+}
+
+impl ResolverEntry {
+    pub const LEN: usize = 4 + 32 + (4 + MAX_RESOLVER_NAME_LEN) + 1 + 1; // 74 bytes
+}
+
+/// The instruction every resolver adapter implements
+///
+/// The adapter is invoked with instruction data of the Anchor discriminator of
+/// `resolve`, the market's key and its end time, and with the market as its
+/// first (read-only) account followed by any accounts the caller passed for
+/// the adapter (price feeds, proposals, ...). It answers through return data
+/// with a Borsh-encoded `Option<Outcome>`: `None` while the outcome is not yet
+/// known.
+pub struct ResolverInterface;
+
+impl ResolverInterface {
+    pub fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(b"global:resolve").to_bytes()[..8]);
+        discriminator
+    }
+
+    /// Instruction data asking for the outcome of `market`
+    pub fn data(market: &Pubkey, end_time: i64) -> Vec<u8> {
+        let mut data = Vec::with_capacity(8 + 32 + 8);
+        data.extend_from_slice(&Self::discriminator());
+        data.extend_from_slice(market.as_ref());
+        data.extend_from_slice(&end_time.to_le_bytes());
+        data
+    }
+}
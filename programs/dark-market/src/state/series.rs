@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use super::MAX_QUESTION_LEN;
+
+/// A recurring market template (e.g. "BTC above X — weekly")
+///
+/// Each market in the series asks the same question with the same resolver
+/// and ends `interval` seconds after the previous one. Once the current
+/// market stops trading, anyone can roll the series into the next market.
+#[account]
+pub struct MarketSeries {
+    /// Wallet that created the series, recorded as each market's creator
+    pub authority: Pubkey,
+    /// Authority-chosen nonce (part of the PDA seeds)
+    pub nonce: u64,
+    /// Question asked by every market in the series
+    pub question: String,
+    /// Resolver of every market in the series
+    pub resolver: Pubkey,
+    /// Seconds between consecutive end times
+    pub interval: i64,
+    /// Swap fee required of pools on the series' markets
+    pub fee_bps: u16,
+    /// The most recently opened market
+    pub current_market: Pubkey,
+    /// End time of the most recently opened market
+    pub current_end_time: i64,
+    /// Number of markets opened so far
+    pub markets_opened: u64,
+    /// Bump for the series PDA
+    pub bump: u8,
+}
+
+impl MarketSeries {
+    pub const LEN: usize = 32 + 8 + (4 + MAX_QUESTION_LEN) + 32 + 8 + 2 + 32 + 8 + 8 + 1; // 335 bytes
+
+    /// End time of the next market: one interval on, skipping periods already past `now`
+    pub fn next_end_time(&self, now: i64) -> Option<i64> {
+        let mut end_time = self.current_end_time.checked_add(self.interval)?;
+        if end_time <= now {
+            let missed = (now - end_time) / self.interval + 1;
+            end_time = end_time.checked_add(missed.checked_mul(self.interval)?)?;
+        }
+        Some(end_time)
+    }
+}
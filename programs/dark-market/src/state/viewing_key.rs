@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// An auditor a user has granted read access to their private activity
+///
+/// While registered, every private action of the owner must carry a memo of
+/// its details encrypted to `encryption_key`, emitted as an `AuditMemo`
+/// event. Only the auditor can read the memos, and removing the key stops
+/// new ones without revealing past activity to anyone else.
+#[account]
+pub struct ViewingKey {
+    /// User whose activity is disclosed
+    pub owner: Pubkey,
+    /// Auditor holding the decryption key
+    pub auditor: Pubkey,
+    /// X25519 public key memos are encrypted to
+    pub encryption_key: [u8; 32],
+    /// Bump for the viewing key PDA
+    pub bump: u8,
+}
+
+impl ViewingKey {
+    pub const LEN: usize = 32 + 32 + 32 + 1; // 97 bytes
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use super::ElGamalCiphertext;
+
+/// Key and authority for protocol volume reporting, set by the config authority
+#[account]
+pub struct VolumeReporting {
+    /// Holder of the reporting key's secret, who publishes decrypted totals
+    pub authority: Pubkey,
+    /// ElGamal public key every volume counter encrypts to
+    pub reporting_key: [u8; 32],
+    /// Bump for the reporting PDA
+    pub bump: u8,
+}
+
+impl VolumeReporting {
+    pub const LEN: usize = 32 + 32 + 1; // 65 bytes
+}
+
+/// Encrypted running total of a market's private volume
+///
+/// Every private trade adds a ciphertext of its volume under the reporting
+/// key, so the counter always encrypts the total while no single trade's
+/// amount can be read from it. The reporting authority only publishes the
+/// decrypted total after the market's end time. For confidential markets the
+/// published total is also checkable against the committee's decrypted YES
+/// stakes once both are known.
+#[account]
+pub struct VolumeCounter {
+    /// The market counted
+    pub market: Pubkey,
+    /// Reporting key the total is encrypted to (fixed at creation)
+    pub reporting_key: [u8; 32],
+    /// Homomorphic sum of every trade's encrypted volume
+    pub volume: ElGamalCiphertext,
+    /// Number of trades counted
+    pub trades: u64,
+    /// Decrypted total, once published
+    pub reported: Option<u64>,
+    /// Bump for the counter PDA
+    pub bump: u8,
+}
+
+impl VolumeCounter {
+    pub const LEN: usize = 32 + 32 + ElGamalCiphertext::LEN + 8 + 9 + 1; // 146 bytes
+
+    /// Add one trade's encrypted volume
+    pub fn record(&mut self, volume: &ElGamalCiphertext) -> Option<()> {
+        self.volume = self.volume.add(volume)?;
+        self.trades = self.trades.checked_add(1)?;
+        Some(())
+    }
+}
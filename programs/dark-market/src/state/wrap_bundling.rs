@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// Protocol setting requiring bets to share a transaction with a DAC wrap
+///
+/// The counterpart of the DAC token program's bundling mode: with both on,
+/// wraps and bets only ever appear together, so an observer cannot link a
+/// standalone wrap to a bet placed later.
+#[account]
+pub struct WrapBundling {
+    /// The DAC token program whose `wrap` bets must be bundled with
+    pub wrap_program: Pubkey,
+    /// Whether splits and buys must include a wrap
+    pub enabled: bool,
+    /// Bump for the bundling PDA
+    pub bump: u8,
+}
+
+impl WrapBundling {
+    pub const LEN: usize = 32 + 1 + 1; // 34 bytes
+
+    /// Anchor discriminator of the DAC token program's `wrap`
+    pub fn wrap_discriminator() -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(b"global:wrap").to_bytes()[..8]);
+        discriminator
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ShieldedPoolError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Cannot process zero amount")]
+    ZeroAmount,
+    #[msg("Mint mismatch")]
+    MintMismatch,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Note is not a valid field element")]
+    InvalidNote,
+    #[msg("Merkle tree is full")]
+    TreeFull,
+    #[msg("Root is not in the recent root history")]
+    UnknownRoot,
+    #[msg("A note cannot be spent twice in one transaction")]
+    DuplicateNullifier,
+    #[msg("Invalid zero-knowledge proof")]
+    InvalidProof,
+    #[msg("Relayer fee exceeds the withdrawn amount")]
+    FeeTooHigh,
+    #[msg("Note was already spent")]
+    NoteAlreadySpent,
+    #[msg("Batch is empty, too large or its accounts do not match")]
+    InvalidBatch,
+}
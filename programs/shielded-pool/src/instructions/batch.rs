@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::token::{self, Token, TokenAccount};
+use dark_market::state::VerifyingKey;
+use dark_market::VERIFYING_KEY_SEED;
+
+use crate::errors::ShieldedPoolError;
+use crate::instructions::spend;
+use crate::state::*;
+use crate::{NULLIFIER_SEED, SHIELDED_POOL_SEED};
+
+/// Process a relayer's batch of withdrawals
+///
+/// Remaining accounts are `[nullifier_0, nullifier_1, recipient]` per
+/// withdrawal, in order. Each proof binds its own recipient and fee to the
+/// relayer of this batch, so the relayer only collects what every note
+/// agreed to pay. Many exits in one transaction, to fresh addresses with no
+/// SOL, hide which deposit each recipient came from among the whole batch.
+pub fn withdraw_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawBatch<'info>>,
+    withdrawals: Vec<BatchedWithdrawal>,
+) -> Result<()> {
+    require!(
+        !withdrawals.is_empty()
+            && withdrawals.len() <= MAX_BATCH_WITHDRAWALS
+            && ctx.remaining_accounts.len() == withdrawals.len() * 3,
+        ShieldedPoolError::InvalidBatch
+    );
+
+    let pool_key = ctx.accounts.pool.key();
+    let relayer_key = ctx.accounts.relayer.key();
+    let mut payouts = Vec::with_capacity(withdrawals.len());
+    let mut fees: u64 = 0;
+    let accounts = ctx.remaining_accounts.chunks_exact(3);
+    for (withdrawal, accounts) in withdrawals.into_iter().zip(accounts) {
+        require!(withdrawal.amount > 0, ShieldedPoolError::ZeroAmount);
+        require!(withdrawal.fee <= withdrawal.amount, ShieldedPoolError::FeeTooHigh);
+
+        let recipient = Account::<TokenAccount>::try_from(&accounts[2])?;
+        require_keys_eq!(
+            recipient.mint,
+            ctx.accounts.pool.dac_mint,
+            ShieldedPoolError::MintMismatch
+        );
+
+        let ext_data = ExtData {
+            recipient: recipient.key(),
+            relayer: relayer_key,
+            fee: withdrawal.fee,
+            encrypted_outputs: withdrawal.encrypted_outputs,
+        };
+        spend(
+            &mut ctx.accounts.pool,
+            &ctx.accounts.verifying_key,
+            &withdrawal.proof,
+            withdrawal.root,
+            withdrawal.amount,
+            &ext_data,
+            withdrawal.nullifiers,
+            withdrawal.commitments,
+        )?;
+        for (info, nullifier) in accounts[..2].iter().zip(&withdrawal.nullifiers) {
+            create_nullifier(&ctx, &pool_key, nullifier, info)?;
+        }
+
+        payouts.push((accounts[2].clone(), withdrawal.amount - withdrawal.fee));
+        fees = fees.checked_add(withdrawal.fee).ok_or(ShieldedPoolError::Overflow)?;
+    }
+
+    let pool = &ctx.accounts.pool;
+    let seeds = &[SHIELDED_POOL_SEED, pool.dac_mint.as_ref(), &[pool.bump]];
+    let signer_seeds = &[&seeds[..]];
+    let count = payouts.len();
+    payouts.push((ctx.accounts.relayer.to_account_info(), fees));
+    for (to, payout) in payouts {
+        if payout == 0 {
+            continue;
+        }
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to,
+                authority: pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, payout)?;
+    }
+
+    msg!("Batch of {} withdrawals processed, {} DAC in relayer fees", count, fees);
+    Ok(())
+}
+
+/// Create the nullifier account of a spent note, failing if it already exists
+///
+/// The batch equivalent of the `init` constraint on the single-spend
+/// contexts, for a number of notes only known from the instruction data.
+/// Like `init`, it also takes over an address someone already sent lamports
+/// to, which would otherwise make `create_account` fail and block the batch.
+fn create_nullifier<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, WithdrawBatch<'info>>,
+    pool: &Pubkey,
+    nullifier: &[u8; 32],
+    info: &AccountInfo<'info>,
+) -> Result<()> {
+    let (address, bump) = Pubkey::find_program_address(
+        &[NULLIFIER_SEED, pool.as_ref(), nullifier.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(info.key(), address, ShieldedPoolError::InvalidBatch);
+    require!(info.data_is_empty(), ShieldedPoolError::NoteAlreadySpent);
+
+    let space = 8 + Nullifier::LEN;
+    let rent = Rent::get()?.minimum_balance(space);
+    let seeds = &[NULLIFIER_SEED, pool.as_ref(), nullifier.as_ref(), &[bump]];
+    let signer_seeds = &[&seeds[..]];
+    let system = ctx.accounts.system_program.to_account_info();
+    let payer = ctx.accounts.payer.to_account_info();
+    if info.lamports() == 0 {
+        let create_ctx = CpiContext::new_with_signer(
+            system,
+            CreateAccount { from: payer, to: info.clone() },
+            signer_seeds,
+        );
+        system_program::create_account(create_ctx, rent, space as u64, &crate::ID)?;
+    } else {
+        let top_up = rent.saturating_sub(info.lamports());
+        if top_up > 0 {
+            let transfer_ctx =
+                CpiContext::new(system.clone(), Transfer { from: payer, to: info.clone() });
+            system_program::transfer(transfer_ctx, top_up)?;
+        }
+        let allocate_ctx = CpiContext::new_with_signer(
+            system.clone(),
+            Allocate { account_to_allocate: info.clone() },
+            signer_seeds,
+        );
+        system_program::allocate(allocate_ctx, space as u64)?;
+        let assign_ctx = CpiContext::new_with_signer(
+            system,
+            Assign { account_to_assign: info.clone() },
+            signer_seeds,
+        );
+        system_program::assign(assign_ctx, &crate::ID)?;
+    }
+
+    let mut data = info.try_borrow_mut_data()?;
+    Nullifier { bump }.try_serialize(&mut &mut data[..])
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct WithdrawBatch<'info> {
+    /// The pool holding the notes
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, pool.dac_mint.as_ref()],
+        bump = pool.bump,
+        has_one = vault @ ShieldedPoolError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, ShieldedPool>>,
+
+    /// Verifying key of the pool's transaction circuit
+    #[account(
+        seeds = [VERIFYING_KEY_SEED, pool.transaction_circuit.to_le_bytes().as_ref()],
+        bump = verifying_key.bump,
+        seeds::program = dark_market::ID,
+        constraint = verifying_key.is_complete() @ ShieldedPoolError::InvalidProof,
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKey>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// DAC token account receiving every withdrawal's fee
+    #[account(
+        mut,
+        constraint = relayer.mint == pool.dac_mint @ ShieldedPoolError::MintMismatch,
+    )]
+    pub relayer: Box<Account<'info, TokenAccount>>,
+
+    /// The relayer, paying the transaction and the nullifier rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use dark_market::state::field_element;
+
+use crate::errors::ShieldedPoolError;
+use crate::state::*;
+use crate::SHIELDED_POOL_SEED;
+
+/// Move DAC into the pool as a new note
+///
+/// The program computes the leaf from the public amount, so a note can never
+/// claim more DAC than was deposited. The deposit itself is public; privacy
+/// comes from later spends, which do not reveal which note they consume.
+pub fn deposit(ctx: Context<Deposit>, amount: u64, note_hash: [u8; 32]) -> Result<()> {
+    require!(amount > 0, ShieldedPoolError::ZeroAmount);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.depositor_dac.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let commitment = poseidon(&field_element(amount), &note_hash)
+        .ok_or(ShieldedPoolError::InvalidNote)?;
+    let index = ctx.accounts.pool.tree.insert(commitment)
+        .ok_or(ShieldedPoolError::TreeFull)?;
+
+    msg!("Note {} deposited: {:?}", index, commitment);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    /// The pool to deposit into
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, pool.dac_mint.as_ref()],
+        bump = pool.bump,
+        has_one = vault @ ShieldedPoolError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, ShieldedPool>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Depositor's DAC token account
+    #[account(
+        mut,
+        constraint = depositor_dac.mint == pool.dac_mint @ ShieldedPoolError::MintMismatch,
+    )]
+    pub depositor_dac: Box<Account<'info, TokenAccount>>,
+
+    pub depositor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
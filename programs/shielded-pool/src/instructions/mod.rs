@@ -0,0 +1,9 @@
+pub mod batch;
+pub mod deposit;
+pub mod pool;
+pub mod spend;
+
+pub use batch::*;
+pub use deposit::*;
+pub use pool::*;
+pub use spend::*;
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use dark_market::state::MarketConfig;
+use dark_market::MARKET_CONFIG_SEED;
+
+use crate::errors::ShieldedPoolError;
+use crate::state::*;
+use crate::{SHIELDED_POOL_SEED, SHIELDED_VAULT_SEED};
+
+pub fn initialize_pool(ctx: Context<InitializePool>, transaction_circuit: u32) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.dac_mint = ctx.accounts.dac_mint.key();
+    pool.vault = ctx.accounts.vault.key();
+    pool.transaction_circuit = transaction_circuit;
+    pool.tree.initialize().ok_or(ShieldedPoolError::InvalidNote)?;
+    pool.bump = ctx.bumps.pool;
+
+    msg!("Shielded pool initialized for {}", pool.dac_mint);
+    msg!("Empty root: {:?}", pool.tree.root());
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    /// The dark market config, whose authority sets up the pool
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        seeds::program = dark_market::ID,
+        has_one = authority @ ShieldedPoolError::Unauthorized,
+        has_one = dac_mint @ ShieldedPoolError::MintMismatch,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The pool to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ShieldedPool::LEN,
+        seeds = [SHIELDED_POOL_SEED, dac_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Box<Account<'info, ShieldedPool>>,
+
+    /// DAC vault backing the notes
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SHIELDED_VAULT_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = dac_mint,
+        token::authority = pool,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub dac_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -0,0 +1,250 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use dark_market::state::{field_element, Groth16Proof, VerifyingKey};
+use dark_market::VERIFYING_KEY_SEED;
+
+use crate::errors::ShieldedPoolError;
+use crate::state::*;
+use crate::{NULLIFIER_SEED, SHIELDED_POOL_SEED};
+
+pub fn transfer(
+    ctx: Context<ShieldedTransfer>,
+    proof: Groth16Proof,
+    root: [u8; 32],
+    nullifiers: [[u8; 32]; 2],
+    commitments: [[u8; 32]; 2],
+    encrypted_outputs: [Vec<u8>; 2],
+) -> Result<()> {
+    let ext_data = ExtData {
+        recipient: Pubkey::default(),
+        relayer: Pubkey::default(),
+        fee: 0,
+        encrypted_outputs,
+    };
+    let indices = spend(
+        &mut ctx.accounts.pool,
+        &ctx.accounts.verifying_key,
+        &proof,
+        root,
+        0,
+        &ext_data,
+        nullifiers,
+        commitments,
+    )?;
+
+    msg!("Private transfer: notes {} and {} created", indices[0], indices[1]);
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw(
+    ctx: Context<Withdraw>,
+    proof: Groth16Proof,
+    root: [u8; 32],
+    nullifiers: [[u8; 32]; 2],
+    commitments: [[u8; 32]; 2],
+    encrypted_outputs: [Vec<u8>; 2],
+    amount: u64,
+    fee: u64,
+) -> Result<()> {
+    require!(amount > 0, ShieldedPoolError::ZeroAmount);
+    require!(fee <= amount, ShieldedPoolError::FeeTooHigh);
+
+    let ext_data = ExtData {
+        recipient: ctx.accounts.recipient.key(),
+        relayer: ctx.accounts.relayer.key(),
+        fee,
+        encrypted_outputs,
+    };
+    let indices = spend(
+        &mut ctx.accounts.pool,
+        &ctx.accounts.verifying_key,
+        &proof,
+        root,
+        amount,
+        &ext_data,
+        nullifiers,
+        commitments,
+    )?;
+
+    let pool = &ctx.accounts.pool;
+    let seeds = &[SHIELDED_POOL_SEED, pool.dac_mint.as_ref(), &[pool.bump]];
+    let signer_seeds = &[&seeds[..]];
+    for (to, payout) in [(&ctx.accounts.recipient, amount - fee), (&ctx.accounts.relayer, fee)] {
+        if payout == 0 {
+            continue;
+        }
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: to.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, payout)?;
+    }
+
+    msg!(
+        "Withdrew {} DAC ({} relayer fee), change notes {} and {}",
+        amount,
+        fee,
+        indices[0],
+        indices[1]
+    );
+    Ok(())
+}
+
+/// Check a transaction proof and insert its output notes
+///
+/// The input notes' nullifier accounts are created by the instruction
+/// context, which fails if either note was already spent.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn spend(
+    pool: &mut ShieldedPool,
+    verifying_key: &VerifyingKey,
+    proof: &Groth16Proof,
+    root: [u8; 32],
+    public_amount: u64,
+    ext_data: &ExtData,
+    nullifiers: [[u8; 32]; 2],
+    commitments: [[u8; 32]; 2],
+) -> Result<[u64; 2]> {
+    require!(nullifiers[0] != nullifiers[1], ShieldedPoolError::DuplicateNullifier);
+    require!(pool.tree.is_known_root(&root), ShieldedPoolError::UnknownRoot);
+
+    let public_inputs = [
+        root,
+        field_element(public_amount),
+        ext_data.hash(),
+        nullifiers[0],
+        nullifiers[1],
+        commitments[0],
+        commitments[1],
+    ];
+    require!(verifying_key.verify(proof, &public_inputs), ShieldedPoolError::InvalidProof);
+
+    let mut indices = [0; 2];
+    for (index, commitment) in indices.iter_mut().zip(commitments) {
+        *index = pool.tree.insert(commitment).ok_or(ShieldedPoolError::TreeFull)?;
+    }
+    Ok(indices)
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(proof: Groth16Proof, root: [u8; 32], nullifiers: [[u8; 32]; 2])]
+pub struct ShieldedTransfer<'info> {
+    /// The pool holding the notes
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, pool.dac_mint.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, ShieldedPool>>,
+
+    /// Verifying key of the pool's transaction circuit
+    #[account(
+        seeds = [VERIFYING_KEY_SEED, pool.transaction_circuit.to_le_bytes().as_ref()],
+        bump = verifying_key.bump,
+        seeds::program = dark_market::ID,
+        constraint = verifying_key.is_complete() @ ShieldedPoolError::InvalidProof,
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKey>>,
+
+    /// Nullifier of the first input note (created, so it cannot be spent again)
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Nullifier::LEN,
+        seeds = [NULLIFIER_SEED, pool.key().as_ref(), nullifiers[0].as_ref()],
+        bump
+    )]
+    pub nullifier_0: Account<'info, Nullifier>,
+
+    /// Nullifier of the second input note
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Nullifier::LEN,
+        seeds = [NULLIFIER_SEED, pool.key().as_ref(), nullifiers[1].as_ref()],
+        bump
+    )]
+    pub nullifier_1: Account<'info, Nullifier>,
+
+    /// Pays the nullifier rent; need not own the notes
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proof: Groth16Proof, root: [u8; 32], nullifiers: [[u8; 32]; 2])]
+pub struct Withdraw<'info> {
+    /// The pool holding the notes
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, pool.dac_mint.as_ref()],
+        bump = pool.bump,
+        has_one = vault @ ShieldedPoolError::MintMismatch,
+    )]
+    pub pool: Box<Account<'info, ShieldedPool>>,
+
+    /// Verifying key of the pool's transaction circuit
+    #[account(
+        seeds = [VERIFYING_KEY_SEED, pool.transaction_circuit.to_le_bytes().as_ref()],
+        bump = verifying_key.bump,
+        seeds::program = dark_market::ID,
+        constraint = verifying_key.is_complete() @ ShieldedPoolError::InvalidProof,
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKey>>,
+
+    /// Nullifier of the first input note (created, so it cannot be spent again)
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Nullifier::LEN,
+        seeds = [NULLIFIER_SEED, pool.key().as_ref(), nullifiers[0].as_ref()],
+        bump
+    )]
+    pub nullifier_0: Account<'info, Nullifier>,
+
+    /// Nullifier of the second input note
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Nullifier::LEN,
+        seeds = [NULLIFIER_SEED, pool.key().as_ref(), nullifiers[1].as_ref()],
+        bump
+    )]
+    pub nullifier_1: Account<'info, Nullifier>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// DAC token account receiving the withdrawal
+    #[account(
+        mut,
+        constraint = recipient.mint == pool.dac_mint @ ShieldedPoolError::MintMismatch,
+    )]
+    pub recipient: Box<Account<'info, TokenAccount>>,
+
+    /// DAC token account receiving the relayer fee
+    #[account(
+        mut,
+        constraint = relayer.mint == pool.dac_mint @ ShieldedPoolError::MintMismatch,
+    )]
+    pub relayer: Box<Account<'info, TokenAccount>>,
+
+    /// Pays the nullifier rent (the relayer, when one submits the withdrawal)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,86 @@
+//! Shielded Pool Program
+//!
+//! A pool of DAC held as private notes. Depositing DAC inserts a note
+//! commitment into an on-chain incremental Merkle tree; spending a note
+//! publishes its nullifier together with a Groth16 proof that the note is in
+//! the tree, so nobody can tell which deposit a spend consumes.
+//!
+//! Key features:
+//! - Deposits of DAC into note commitments (Poseidon over BN254)
+//! - Private transfers: two notes in, two notes out, amounts never revealed
+//! - Withdrawals to any token account, optionally paying a relayer fee
+//! - Relayer-submitted batches of withdrawals to fresh addresses, fee taken per note
+//! - Double-spend protection through one nullifier account per spent note
+//! - Proofs checked against verifying keys registered in the dark market program
+
+// Anchor 0.31's `#[program]` expansion still calls the deprecated `AccountInfo::realloc`
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+use dark_market::state::Groth16Proof;
+use state::BatchedWithdrawal;
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("GiwkmdHFvMgGT98iH6wtSUcFKaVqdjF2xXgRAhJoSjDk");
+
+/// Seeds for a shielded pool (followed by the DAC mint)
+pub const SHIELDED_POOL_SEED: &[u8] = b"shielded_pool";
+/// Seeds for a shielded pool's DAC vault (followed by the pool)
+pub const SHIELDED_VAULT_SEED: &[u8] = b"shielded_vault";
+/// Seeds for a spent note's nullifier (followed by the pool and the nullifier)
+pub const NULLIFIER_SEED: &[u8] = b"nullifier";
+
+#[program]
+pub mod shielded_pool {
+    use super::*;
+
+    /// Create the shielded pool of the DAC mint (dark market config authority only)
+    pub fn initialize_pool(ctx: Context<InitializePool>, transaction_circuit: u32) -> Result<()> {
+        instructions::initialize_pool(ctx, transaction_circuit)
+    }
+
+    /// Deposit DAC into a new note
+    pub fn deposit(ctx: Context<Deposit>, amount: u64, note_hash: [u8; 32]) -> Result<()> {
+        instructions::deposit(ctx, amount, note_hash)
+    }
+
+    /// Spend two notes into two new notes without revealing any amount
+    pub fn transfer(
+        ctx: Context<ShieldedTransfer>,
+        proof: Groth16Proof,
+        root: [u8; 32],
+        nullifiers: [[u8; 32]; 2],
+        commitments: [[u8; 32]; 2],
+        encrypted_outputs: [Vec<u8>; 2],
+    ) -> Result<()> {
+        instructions::transfer(ctx, proof, root, nullifiers, commitments, encrypted_outputs)
+    }
+
+    /// Spend two notes, paying `amount` DAC out of the pool and keeping the change as notes
+    #[allow(clippy::too_many_arguments)]
+    pub fn withdraw(
+        ctx: Context<Withdraw>,
+        proof: Groth16Proof,
+        root: [u8; 32],
+        nullifiers: [[u8; 32]; 2],
+        commitments: [[u8; 32]; 2],
+        encrypted_outputs: [Vec<u8>; 2],
+        amount: u64,
+        fee: u64,
+    ) -> Result<()> {
+        instructions::withdraw(ctx, proof, root, nullifiers, commitments, encrypted_outputs, amount, fee)
+    }
+
+    /// Process several withdrawals in one relayer transaction, each paying the relayer its fee
+    pub fn withdraw_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawBatch<'info>>,
+        withdrawals: Vec<BatchedWithdrawal>,
+    ) -> Result<()> {
+        instructions::withdraw_batch(ctx, withdrawals)
+    }
+}
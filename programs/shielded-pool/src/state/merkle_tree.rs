@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::poseidon::{hashv, Endianness, Parameters};
+
+/// Levels of the note commitment tree (about a million notes)
+pub const TREE_DEPTH: usize = 20;
+/// Number of recent roots a spend may prove membership against
+pub const ROOT_HISTORY_SIZE: usize = 30;
+
+/// Poseidon hash of two BN254 field elements, big-endian
+pub fn poseidon(left: &[u8; 32], right: &[u8; 32]) -> Option<[u8; 32]> {
+    hashv(Parameters::Bn254X5, Endianness::BigEndian, &[left, right])
+        .ok()
+        .map(|hash| hash.to_bytes())
+}
+
+/// Append-only Merkle tree of note commitments
+///
+/// Only the rightmost filled node of each level is stored, which is all an
+/// insertion needs. Spends prove membership against any of the last
+/// `ROOT_HISTORY_SIZE` roots, so a proof built on a slightly stale root stays
+/// valid while other deposits land.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MerkleTree {
+    /// Index of the next leaf
+    pub next_index: u64,
+    /// Rightmost filled node of each level
+    pub filled_subtrees: [[u8; 32]; TREE_DEPTH],
+    /// Root of an empty subtree of each level
+    pub zeros: [[u8; 32]; TREE_DEPTH],
+    /// Ring buffer of recent roots
+    pub roots: [[u8; 32]; ROOT_HISTORY_SIZE],
+    /// Position of the current root in `roots`
+    pub current_root_index: u8,
+}
+
+impl MerkleTree {
+    pub const LEN: usize = 8 + 32 * TREE_DEPTH + 32 * TREE_DEPTH + 32 * ROOT_HISTORY_SIZE + 1; // 2249 bytes
+
+    /// Reset to the empty tree (leaves of zero)
+    pub fn initialize(&mut self) -> Option<()> {
+        let mut zero = [0u8; 32];
+        for level in 0..TREE_DEPTH {
+            self.zeros[level] = zero;
+            self.filled_subtrees[level] = zero;
+            zero = poseidon(&zero, &zero)?;
+        }
+        self.next_index = 0;
+        self.roots = [[0u8; 32]; ROOT_HISTORY_SIZE];
+        self.roots[0] = zero;
+        self.current_root_index = 0;
+        Some(())
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.roots[self.current_root_index as usize]
+    }
+
+    /// Append `leaf` and return its index, or `None` if the tree is full or the leaf is not a field element
+    pub fn insert(&mut self, leaf: [u8; 32]) -> Option<u64> {
+        let index = self.next_index;
+        if index >= 1 << TREE_DEPTH {
+            return None;
+        }
+        let mut node = leaf;
+        let mut position = index;
+        for level in 0..TREE_DEPTH {
+            node = if position % 2 == 0 {
+                self.filled_subtrees[level] = node;
+                poseidon(&node, &self.zeros[level])?
+            } else {
+                poseidon(&self.filled_subtrees[level], &node)?
+            };
+            position /= 2;
+        }
+        self.current_root_index = ((self.current_root_index as usize + 1) % ROOT_HISTORY_SIZE) as u8;
+        self.roots[self.current_root_index as usize] = node;
+        self.next_index = index + 1;
+        Some(index)
+    }
+
+    /// Whether `root` is one of the recent roots
+    pub fn is_known_root(&self, root: &[u8; 32]) -> bool {
+        *root != [0u8; 32] && self.roots.contains(root)
+    }
+}
@@ -0,0 +1,5 @@
+mod merkle_tree;
+mod pool;
+
+pub use merkle_tree::*;
+pub use pool::*;
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak::hashv;
+use dark_market::state::Groth16Proof;
+
+use super::MerkleTree;
+
+/// The shielded pool of the DAC mint
+///
+/// A note is the leaf `Poseidon(amount, note_hash)`, where the note hash
+/// commits to the owner's key and a blinding factor. The pool's transaction
+/// circuit takes two notes in and two notes out, with public inputs
+/// `[root, public_amount, ext_data_hash, nullifier_0, nullifier_1,
+/// commitment_0, commitment_1]`; it proves the input notes are in the tree
+/// under `root`, that the nullifiers belong to them, and that the inputs
+/// balance the outputs plus `public_amount` DAC leaving the pool.
+#[account]
+pub struct ShieldedPool {
+    /// The DAC mint held by the pool
+    pub dac_mint: Pubkey,
+    /// DAC vault backing every note
+    pub vault: Pubkey,
+    /// Dark market verifying key id of the transaction circuit
+    pub transaction_circuit: u32,
+    /// Note commitment tree
+    pub tree: MerkleTree,
+    /// Bump for the pool PDA
+    pub bump: u8,
+}
+
+impl ShieldedPool {
+    pub const LEN: usize = 32 + 32 + 4 + MerkleTree::LEN + 1; // 2318 bytes
+}
+
+/// Marks a note as spent; its existence makes a second spend fail
+#[account]
+pub struct Nullifier {
+    /// Bump for the nullifier PDA
+    pub bump: u8,
+}
+
+impl Nullifier {
+    pub const LEN: usize = 1; // 1 byte
+}
+
+/// Public parameters of a spend that the proof must commit to
+///
+/// Binding them through `ext_data_hash` stops a relayer from changing the
+/// recipient, its fee or the encrypted notes of a proof it submits.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ExtData {
+    /// Token account receiving withdrawn DAC (default for transfers)
+    pub recipient: Pubkey,
+    /// Token account receiving the fee (default for transfers)
+    pub relayer: Pubkey,
+    /// Part of the withdrawn DAC paid to the relayer
+    pub fee: u64,
+    /// Output notes encrypted to their owners, for wallet scanning
+    pub encrypted_outputs: [Vec<u8>; 2],
+}
+
+impl ExtData {
+    /// Keccak hash of the Borsh encoding, truncated below the field order
+    pub fn hash(&self) -> [u8; 32] {
+        let mut data = Vec::new();
+        // Writing to a Vec cannot fail
+        self.serialize(&mut data).ok();
+        let mut hash = hashv(&[&data]).to_bytes();
+        hash[0] = 0;
+        hash
+    }
+}
+
+/// Most withdrawals one batch can process
+pub const MAX_BATCH_WITHDRAWALS: usize = 4;
+
+/// One withdrawal of a relayer batch, with the arguments of `withdraw`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchedWithdrawal {
+    pub proof: Groth16Proof,
+    pub root: [u8; 32],
+    pub nullifiers: [[u8; 32]; 2],
+    pub commitments: [[u8; 32]; 2],
+    pub encrypted_outputs: [Vec<u8>; 2],
+    pub amount: u64,
+    pub fee: u64,
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum TimelockError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Minimum delay below the floor")]
+    DelayTooShort,
+    #[msg("Account is not a buffer of the upgradeable loader")]
+    InvalidBuffer,
+    #[msg("Buffer authority is not the timelock")]
+    BufferNotOwnedByTimelock,
+    #[msg("Buffer contents do not match the queued hash")]
+    BufferHashMismatch,
+    #[msg("Upgrade delay has not elapsed")]
+    UpgradeNotReady,
+}
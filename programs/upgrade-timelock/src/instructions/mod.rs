@@ -0,0 +1,5 @@
+pub mod timelock;
+pub mod upgrade;
+
+pub use timelock::*;
+pub use upgrade::*;
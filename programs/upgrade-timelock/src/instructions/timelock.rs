@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use dark_market::state::MarketConfig;
+use dark_market::MARKET_CONFIG_SEED;
+
+use crate::errors::TimelockError;
+use crate::state::*;
+use crate::TIMELOCK_SEED;
+
+/// Create the timelock (dark market config authority only)
+///
+/// Programs come under the timelock once their upgrade authority is set to
+/// the timelock PDA.
+pub fn initialize_timelock(
+    ctx: Context<InitializeTimelock>,
+    admin: Pubkey,
+    guardian: Pubkey,
+    min_delay: i64,
+) -> Result<()> {
+    require!(min_delay >= MIN_UPGRADE_DELAY, TimelockError::DelayTooShort);
+
+    let timelock = &mut ctx.accounts.timelock;
+    timelock.admin = admin;
+    timelock.guardian = guardian;
+    timelock.min_delay = min_delay;
+    timelock.bump = ctx.bumps.timelock;
+
+    msg!("Upgrade timelock initialized: {} with a {}s delay", timelock.key(), min_delay);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeTimelock<'info> {
+    /// The dark market config, whose authority sets up the timelock
+    #[account(
+        seeds = [MARKET_CONFIG_SEED],
+        bump = config.bump,
+        seeds::program = dark_market::ID,
+        has_one = authority @ TimelockError::Unauthorized,
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// The timelock to create
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Timelock::LEN,
+        seeds = [TIMELOCK_SEED],
+        bump
+    )]
+    pub timelock: Box<Account<'info, Timelock>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
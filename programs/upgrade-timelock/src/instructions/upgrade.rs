@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::errors::TimelockError;
+use crate::state::*;
+use crate::{TIMELOCK_SEED, UPGRADE_SEED};
+
+/// Queue an upgrade of `program` to the buffer (admin only)
+///
+/// The buffer's authority must already be the timelock, so its contents
+/// cannot change while the upgrade waits.
+pub fn queue_upgrade(ctx: Context<QueueUpgrade>, buffer_hash: [u8; 32]) -> Result<()> {
+    check_buffer(&ctx.accounts.buffer, &ctx.accounts.timelock.key(), &buffer_hash)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let upgrade = &mut ctx.accounts.upgrade;
+    upgrade.program = ctx.accounts.program.key();
+    upgrade.buffer = ctx.accounts.buffer.key();
+    upgrade.buffer_hash = buffer_hash;
+    upgrade.queued_at = now;
+    upgrade.eta = now
+        .checked_add(ctx.accounts.timelock.min_delay)
+        .ok_or(TimelockError::Overflow)?;
+    upgrade.bump = ctx.bumps.upgrade;
+
+    msg!("Upgrade of {} queued until {}", upgrade.program, upgrade.eta);
+    Ok(())
+}
+
+/// Deploy a queued upgrade after its delay (anyone)
+pub fn execute_upgrade(ctx: Context<ExecuteUpgrade>) -> Result<()> {
+    let upgrade = &ctx.accounts.upgrade;
+    require!(Clock::get()?.unix_timestamp >= upgrade.eta, TimelockError::UpgradeNotReady);
+    check_buffer(&ctx.accounts.buffer, &ctx.accounts.timelock.key(), &upgrade.buffer_hash)?;
+
+    let ix = bpf_loader_upgradeable::upgrade(
+        &upgrade.program,
+        &upgrade.buffer,
+        &ctx.accounts.timelock.key(),
+        &ctx.accounts.spill.key(),
+    );
+    let seeds = &[TIMELOCK_SEED, &[ctx.accounts.timelock.bump]];
+    let signer_seeds = &[&seeds[..]];
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.program_data.to_account_info(),
+            ctx.accounts.program.to_account_info(),
+            ctx.accounts.buffer.to_account_info(),
+            ctx.accounts.spill.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.timelock.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    msg!("Upgraded {} from buffer {}", upgrade.program, upgrade.buffer);
+    Ok(())
+}
+
+/// Drop a queued upgrade and close its buffer to the guardian (guardian only)
+pub fn cancel_upgrade(ctx: Context<CancelUpgrade>) -> Result<()> {
+    let ix = bpf_loader_upgradeable::close(
+        &ctx.accounts.buffer.key(),
+        &ctx.accounts.guardian.key(),
+        &ctx.accounts.timelock.key(),
+    );
+    let seeds = &[TIMELOCK_SEED, &[ctx.accounts.timelock.bump]];
+    let signer_seeds = &[&seeds[..]];
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.buffer.to_account_info(),
+            ctx.accounts.guardian.to_account_info(),
+            ctx.accounts.timelock.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    msg!("Upgrade of {} cancelled", ctx.accounts.upgrade.program);
+    Ok(())
+}
+
+/// Require `buffer` to be a loader buffer held by `timelock` whose program data hashes to `expected`
+fn check_buffer(buffer: &AccountInfo, timelock: &Pubkey, expected: &[u8; 32]) -> Result<()> {
+    require_keys_eq!(*buffer.owner, bpf_loader_upgradeable::ID, TimelockError::InvalidBuffer);
+    let (authority, buffer_hash) = read_buffer(&buffer.try_borrow_data()?)
+        .ok_or(TimelockError::InvalidBuffer)?;
+    require!(authority == Some(*timelock), TimelockError::BufferNotOwnedByTimelock);
+    require!(buffer_hash == *expected, TimelockError::BufferHashMismatch);
+    Ok(())
+}
+
+// ============================================================================
+// Instruction Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct QueueUpgrade<'info> {
+    #[account(
+        seeds = [TIMELOCK_SEED],
+        bump = timelock.bump,
+        has_one = admin @ TimelockError::Unauthorized,
+    )]
+    pub timelock: Box<Account<'info, Timelock>>,
+
+    /// The queued upgrade to create
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + QueuedUpgrade::LEN,
+        seeds = [UPGRADE_SEED, program.key().as_ref(), buffer.key().as_ref()],
+        bump
+    )]
+    pub upgrade: Box<Account<'info, QueuedUpgrade>>,
+
+    /// CHECK: The program to upgrade; the loader checks its authority at deployment
+    pub program: UncheckedAccount<'info>,
+
+    /// CHECK: Parsed as a loader buffer by `check_buffer`
+    pub buffer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteUpgrade<'info> {
+    #[account(
+        seeds = [TIMELOCK_SEED],
+        bump = timelock.bump,
+    )]
+    pub timelock: Box<Account<'info, Timelock>>,
+
+    /// The upgrade to deploy, closed to the spill account
+    #[account(
+        mut,
+        close = spill,
+        seeds = [UPGRADE_SEED, program.key().as_ref(), buffer.key().as_ref()],
+        bump = upgrade.bump,
+    )]
+    pub upgrade: Box<Account<'info, QueuedUpgrade>>,
+
+    /// CHECK: The program to upgrade
+    #[account(mut)]
+    pub program: UncheckedAccount<'info>,
+
+    /// CHECK: The program's data account
+    #[account(
+        mut,
+        seeds = [program.key().as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: UncheckedAccount<'info>,
+
+    /// CHECK: Parsed as a loader buffer by `check_buffer`
+    #[account(mut)]
+    pub buffer: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the buffer's and the queued upgrade's lamports
+    #[account(mut)]
+    pub spill: UncheckedAccount<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: The upgradeable loader
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub loader: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelUpgrade<'info> {
+    #[account(
+        seeds = [TIMELOCK_SEED],
+        bump = timelock.bump,
+        has_one = guardian @ TimelockError::Unauthorized,
+    )]
+    pub timelock: Box<Account<'info, Timelock>>,
+
+    /// The upgrade to cancel
+    #[account(
+        mut,
+        close = guardian,
+        seeds = [UPGRADE_SEED, upgrade.program.as_ref(), buffer.key().as_ref()],
+        bump = upgrade.bump,
+    )]
+    pub upgrade: Box<Account<'info, QueuedUpgrade>>,
+
+    /// CHECK: The queued buffer, closed by the loader
+    #[account(mut)]
+    pub buffer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub guardian: Signer<'info>,
+
+    /// CHECK: The upgradeable loader
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub loader: UncheckedAccount<'info>,
+}
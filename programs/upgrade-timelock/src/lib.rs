@@ -0,0 +1,61 @@
+//! Upgrade Timelock Program
+//!
+//! Holds the upgrade authority of the workspace programs. An upgrade is
+//! queued with the hash of its buffer, and can only be deployed once the
+//! minimum delay has passed and the buffer still matches the hash, so users
+//! can inspect the new code and exit before it takes effect. The guardian
+//! can cancel a queued upgrade in the meantime.
+//!
+//! Key features:
+//! - Timelock PDA as the upgrade authority of every governed program
+//! - Queueing by the admin (the governance PDA once governance is live)
+//! - Buffer hashes checked at queueing and again at deployment
+//! - Permissionless deployment after the delay; cancellation by the guardian
+
+// Anchor 0.31's `#[program]` expansion still calls the deprecated `AccountInfo::realloc`
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("C5PztpFENNHeqBSFnFRsz4BYaXNDWJSrdhmVYyFn42AT");
+
+/// Seeds for the timelock PDA, the upgrade authority of the governed programs
+pub const TIMELOCK_SEED: &[u8] = b"timelock";
+/// Seeds for a queued upgrade (followed by the program and the buffer)
+pub const UPGRADE_SEED: &[u8] = b"upgrade";
+
+#[program]
+pub mod upgrade_timelock {
+    use super::*;
+
+    /// Create the timelock (dark market config authority only)
+    pub fn initialize_timelock(
+        ctx: Context<InitializeTimelock>,
+        admin: Pubkey,
+        guardian: Pubkey,
+        min_delay: i64,
+    ) -> Result<()> {
+        instructions::initialize_timelock(ctx, admin, guardian, min_delay)
+    }
+
+    /// Queue an upgrade of a program to a buffer with the given hash (admin only)
+    pub fn queue_upgrade(ctx: Context<QueueUpgrade>, buffer_hash: [u8; 32]) -> Result<()> {
+        instructions::queue_upgrade(ctx, buffer_hash)
+    }
+
+    /// Deploy a queued upgrade once its delay has passed
+    pub fn execute_upgrade(ctx: Context<ExecuteUpgrade>) -> Result<()> {
+        instructions::execute_upgrade(ctx)
+    }
+
+    /// Cancel a queued upgrade and close its buffer (guardian only)
+    pub fn cancel_upgrade(ctx: Context<CancelUpgrade>) -> Result<()> {
+        instructions::cancel_upgrade(ctx)
+    }
+}
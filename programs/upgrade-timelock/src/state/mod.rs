@@ -0,0 +1,3 @@
+mod timelock;
+
+pub use timelock::*;
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// Shortest delay the timelock accepts (2 days)
+pub const MIN_UPGRADE_DELAY: i64 = 2 * 24 * 60 * 60;
+/// Size of an upgradeable loader buffer's header: state tag and optional authority
+pub const BUFFER_HEADER_LEN: usize = 4 + 1 + 32;
+
+/// The upgrade timelock
+#[account]
+pub struct Timelock {
+    /// Key that queues upgrades
+    pub admin: Pubkey,
+    /// Key that can cancel queued upgrades
+    pub guardian: Pubkey,
+    /// Seconds between queueing and deploying an upgrade
+    pub min_delay: i64,
+    /// Bump for the timelock PDA
+    pub bump: u8,
+}
+
+impl Timelock {
+    pub const LEN: usize = 32 + 32 + 8 + 1; // 73 bytes
+}
+
+/// An upgrade waiting out the delay
+#[account]
+pub struct QueuedUpgrade {
+    /// The program to upgrade
+    pub program: Pubkey,
+    /// Buffer holding the new program data
+    pub buffer: Pubkey,
+    /// SHA-256 of the buffer's program data
+    pub buffer_hash: [u8; 32],
+    /// Time the upgrade was queued
+    pub queued_at: i64,
+    /// Earliest deployment time
+    pub eta: i64,
+    /// Bump for the queued upgrade PDA
+    pub bump: u8,
+}
+
+impl QueuedUpgrade {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 1; // 113 bytes
+}
+
+/// Authority and program data hash of an upgradeable loader buffer
+///
+/// Parsed by hand from the loader's `Buffer` state: a `u32` tag of 1, then
+/// an `Option<Pubkey>` authority, then the program data.
+pub fn read_buffer(data: &[u8]) -> Option<(Option<Pubkey>, [u8; 32])> {
+    if data.len() < BUFFER_HEADER_LEN || data[..4] != 1u32.to_le_bytes() {
+        return None;
+    }
+    let authority = match data[4] {
+        0 => None,
+        1 => Some(Pubkey::try_from(&data[5..BUFFER_HEADER_LEN]).ok()?),
+        _ => return None,
+    };
+    Some((authority, hash(&data[BUFFER_HEADER_LEN..]).to_bytes()))
+}